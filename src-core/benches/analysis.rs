@@ -0,0 +1,84 @@
+//! Benchmarks of the analysis pipeline on generated programs of 1k and 10k statements
+//!
+//! The targets the pipeline is held to, on a release build:
+//! - Parsing grows linearly with the length of the program, 10k statements parse in well under 100ms
+//! - Analyzing 10k statements with the history tracked takes a few seconds at most, and its timeline
+//!   can be taken out in chunks without holding a second copy of it
+
+mod corpus;
+
+use std::future::Future;
+use std::hint::black_box;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use indexmap::IndexMap;
+
+use mv_core::analyzer::{Analyzer, AnalyzerOptions};
+use mv_core::parser::Parser;
+use mv_core::preprocess::preprocess;
+use mv_core::session::Session;
+
+const SIZES: [usize; 2] = [1_000, 10_000];
+
+/// Runs a future to completion on the current thread, the analyzer never waits on anything so polling
+/// it until it is ready is enough
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+
+    for size in SIZES {
+        let source = preprocess(&corpus::generate_program(size, 0));
+
+        group.bench_with_input(BenchmarkId::from_parameter(size), &source, |b, source| {
+            b.iter(|| Parser::new(black_box(source)).parse().unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+fn analyze(c: &mut Criterion) {
+    let mut group = c.benchmark_group("analyze");
+    group.sample_size(10);
+
+    for size in SIZES {
+        let source = preprocess(&corpus::generate_program(size, 0));
+        let statements = Parser::new(&source).parse().unwrap();
+
+        let options = AnalyzerOptions {
+            seed: Some(0),
+            track_history: true,
+            ..Default::default()
+        };
+
+        group.bench_with_input(BenchmarkId::from_parameter(size), &statements, |b, statements| {
+            b.iter(|| {
+                let mut session = Session::new(source.clone(), IndexMap::new(), options.clone());
+                let mut result = block_on(
+                    Analyzer::new(options.clone())
+                        .analyze_statements(statements.clone(), &mut session),
+                )
+                .unwrap();
+
+                result.cross_reference.take_timeline(1_000).count()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, parse, analyze);
+criterion_main!(benches);
@@ -0,0 +1,37 @@
+//! Generates large programs for the benchmarks, mixing the statements the analyzer spends its time on:
+//! declarations, arithmetic, heap allocations and frees, function calls and short loops
+
+use std::fmt::Write;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Generates a program of about `statements` statements, the same program for the same seed
+///
+/// Every block allocated is freed a few statements later, except the ones still allocated when the
+/// program ends, which are leaked like in a real program that forgets them
+pub fn generate_program(statements: usize, seed: u64) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut program = String::from("int helper(int n) { int t = n * 2; return t + 1; }\n");
+    let mut allocated = Vec::new();
+
+    for i in 0..statements {
+        match rng.random_range(0..6) {
+            0 => writeln!(program, "int v{} = {} + 3 * {};", i, i, rng.random_range(0..8)),
+            1 => writeln!(program, "double d{} = {}.5;", i, i),
+            2 => writeln!(program, "int f{} = helper({});", i, i),
+            3 => writeln!(program, "for (int i = 0; i < 3; i++) {{ int w = i; }}"),
+            4 if allocated.len() > 2 => {
+                let pointer = allocated.remove(0);
+                writeln!(program, "delete p{};", pointer)
+            }
+            _ => {
+                allocated.push(i);
+                writeln!(program, "int* p{} = new int;", i)
+            }
+        }
+        .expect("writing to a string never fails");
+    }
+
+    program
+}
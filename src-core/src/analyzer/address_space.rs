@@ -0,0 +1,167 @@
+//! The address space of the simulated process, which places each area of memory at a base address the
+//! way a 32-bit Linux process lays them out: the code and the globals at the bottom, the heap above them
+//! growing up, mappings in the middle and the stack at the top growing down
+//!
+//! The same layout is used on 64-bit architectures, its addresses are only shown wider
+
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use super::helpers::{symbol_alignment, symbol_size};
+use super::memory_model::{MappedRegion, PAGE_SIZE};
+use super::Symbol;
+
+/// Address of the first byte of the code
+pub(crate) const TEXT_BASE_ADDRESS: usize = 0x00010000;
+
+/// Address the visualization assigns to the first byte of the heap
+pub(crate) const HEAP_BASE_ADDRESS: usize = 0x00400000;
+
+/// Address of the first byte of the area regions are mapped in with `mmap`
+pub(crate) const MAPPING_BASE_ADDRESS: usize = 0x40000000;
+
+/// Address past the top of the stack, where the kernel's half of the address space starts and the stack
+/// grows down from
+pub(crate) const STACK_END_ADDRESS: usize = 0xC0000000;
+
+/// Represents an area of the address space
+///
+/// - `Text`: The compiled code of the program
+/// - `Data`: The globals initialized with a value
+/// - `Bss`: The globals left to be zeroed when the program starts
+/// - `Heap`: The blocks allocated with `new` and `malloc`
+/// - `Mappings`: The regions mapped with `mmap`
+/// - `Stack`: The symbols of the frames
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum SegmentKind {
+    Text,
+    Data,
+    Bss,
+    Heap,
+    Mappings,
+    Stack,
+}
+
+/// Represents where an area of the address space is once the program ended
+///
+/// # Fields
+/// - `kind`: The area
+/// - `start`: The address of the first byte of the area
+/// - `size`: The size of the area in bytes, `0` for areas the program put nothing in
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct Segment {
+    pub kind: SegmentKind,
+    pub start: usize,
+    pub size: usize,
+}
+
+/// Lays out the address space of the program, from the lowest address to the highest
+///
+/// The code and the globals aren't simulated, so the text, data and BSS segments are empty and start
+/// where the code would. Data and BSS start on the page after the segment before them, like the loader
+/// maps them
+///
+/// # Arguments
+/// - `stack_addresses`: The address of each symbol on the stack once the program ended
+/// - `heap_capacity`: The size of the heap in bytes, including the bytes no block holds
+/// - `mappings`: The regions mapped with `mmap`, in the order they were mapped
+///
+/// # Returns
+/// - `Vec<Segment>`: The segments, in the order of their addresses
+pub(crate) fn address_space(
+    stack_addresses: &[usize],
+    heap_capacity: usize,
+    mappings: &[MappedRegion],
+) -> Vec<Segment> {
+    let text = Segment {
+        kind: SegmentKind::Text,
+        start: TEXT_BASE_ADDRESS,
+        size: 0,
+    };
+
+    let data = Segment {
+        kind: SegmentKind::Data,
+        start: page_after(&text),
+        size: 0,
+    };
+
+    let bss = Segment {
+        kind: SegmentKind::Bss,
+        start: page_after(&data),
+        size: 0,
+    };
+
+    let stack_start = stack_addresses.last().copied().unwrap_or(STACK_END_ADDRESS);
+
+    vec![
+        text,
+        data,
+        bss,
+        Segment {
+            kind: SegmentKind::Heap,
+            start: HEAP_BASE_ADDRESS,
+            size: heap_capacity,
+        },
+        Segment {
+            kind: SegmentKind::Mappings,
+            start: MAPPING_BASE_ADDRESS,
+            size: mappings.last().map_or(0, |region| region.start + region.size),
+        },
+        Segment {
+            kind: SegmentKind::Stack,
+            start: stack_start,
+            size: STACK_END_ADDRESS - stack_start,
+        },
+    ]
+}
+
+/// Assigns each symbol of a frame its address, the first symbol declared at the top of the frame and
+/// every symbol after it below the one before, like a frame grows down. Each symbol is moved down to
+/// its alignment, leaving padding between symbols of different sizes
+///
+/// # Arguments
+/// - `frame`: The symbols of the frame, in the order they were declared
+/// - `top`: The address past the top of the frame
+/// - `pointer_size`: The size of a pointer in bytes
+///
+/// # Returns
+/// - `Vec<usize>`: The address of the first byte of each symbol, parallel to the frame
+pub(crate) fn stack_addresses<'a>(
+    frame: impl IntoIterator<Item = &'a Symbol>,
+    top: usize,
+    pointer_size: usize,
+) -> Vec<usize> {
+    let mut top = top;
+
+    frame
+        .into_iter()
+        .map(|symbol| {
+            let alignment = symbol_alignment(symbol, pointer_size);
+            top = (top - symbol_size(symbol)) / alignment * alignment;
+            top
+        })
+        .collect()
+}
+
+/// Gets the address past the top of the innermost frame, which sits right below the frames of the calls
+/// it was made from. The top level is the outermost frame and starts at [STACK_END_ADDRESS]
+///
+/// # Arguments
+/// - `callers`: The symbols of the frames of the calls being executed, outermost first
+/// - `pointer_size`: The size of a pointer in bytes
+///
+/// # Returns
+/// - `usize`: The address past the top of the innermost frame
+pub(crate) fn frame_top(callers: &[IndexMap<String, Symbol>], pointer_size: usize) -> usize {
+    callers.iter().fold(STACK_END_ADDRESS, |top, frame| {
+        stack_addresses(frame.values(), top, pointer_size).last().copied().unwrap_or(top)
+    })
+}
+
+/// Gets the address of the first page after a segment
+fn page_after(segment: &Segment) -> usize {
+    (segment.start + segment.size).next_multiple_of(PAGE_SIZE)
+}
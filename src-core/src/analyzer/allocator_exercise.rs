@@ -0,0 +1,324 @@
+//! Exercises where students write the allocation policy themselves and the analyzer runs a workload
+//! against it, reporting how fragmented the heap gets and which allocations fail
+//!
+//! A policy is an expression of the mini-language scoring each free range a block fits in, the block is
+//! placed at the start of the range with the lowest score. The expression can read `start`, `end` and
+//! `size` of the range and the `request` being placed, so `start` is first fit and `size - request` is
+//! best fit
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error::ParserError, Result};
+use crate::lexer::token::TokenKind;
+use crate::parser::ast::{Expr, Lit};
+use crate::parser::Parser;
+
+use super::heap_allocator::{fragmentation, FreeRange};
+use super::helpers::evaluate_expression;
+use super::options::AnalyzerOptions;
+use super::r#type::Type;
+use super::Symbol;
+
+/// Represents an allocator exercise
+///
+/// # Fields
+/// - `id`: The identifier of the exercise
+/// - `title`: The title of the exercise
+/// - `heap_size`: The size of the heap in bytes, it never grows so allocations that don't fit fail
+/// - `workload`: The allocations and frees run against the policy, in order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllocatorExercise {
+    pub id: String,
+    pub title: String,
+    pub heap_size: usize,
+    pub workload: Vec<WorkloadStep>,
+}
+
+/// Represents a step of a workload
+///
+/// - `Allocate`: Allocates `size` bytes for the block called `name`
+/// - `Free`: Frees the block called `name`, nothing happens if its allocation failed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum WorkloadStep {
+    Allocate { name: String, size: usize },
+    Free { name: String },
+}
+
+/// Represents the heap after a step of the workload
+///
+/// # Fields
+/// - `pointer`: Where the policy placed the block, `None` for frees and failed allocations
+/// - `failed`: Whether the step was an allocation no free range could hold
+/// - `in_use`: The bytes allocated after the step
+/// - `fragmentation`: The share of the free bytes outside the largest free range, from `0` when the free
+///   bytes are all in one range to almost `1` when they are scattered in small ones
+/// - `free_list`: The free ranges after the step, by position in the heap
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkloadStepReport {
+    pub pointer: Option<usize>,
+    pub failed: bool,
+    pub in_use: usize,
+    pub fragmentation: f64,
+    pub free_list: Vec<FreeRange>,
+}
+
+/// Represents how a policy did on the workload of an exercise
+///
+/// # Fields
+/// - `exercise`: The identifier of the exercise
+/// - `steps`: The heap after every step, parallel to the workload
+/// - `failures`: The indices of the allocations that failed
+/// - `peak_in_use`: The most bytes allocated at once
+/// - `peak_fragmentation`: The worst fragmentation of any step
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllocatorReport {
+    pub exercise: String,
+    pub steps: Vec<WorkloadStepReport>,
+    pub failures: Vec<usize>,
+    pub peak_in_use: usize,
+    pub peak_fragmentation: f64,
+}
+
+impl AllocatorExercise {
+    /// Deserializes an allocator exercise from JSON
+    ///
+    /// # Arguments
+    /// - `json`: The exercise definition
+    ///
+    /// # Returns
+    /// - [Result](crate::error::Result): A result containing either:
+    ///    - [AllocatorExercise](crate::analyzer::AllocatorExercise): The deserialized exercise
+    ///    - [Error](crate::error::Error): An error if the JSON is not a valid exercise, or its workload
+    ///      frees a block it never allocated
+    pub fn from_json(json: &str) -> Result<AllocatorExercise> {
+        let exercise: AllocatorExercise =
+            serde_json::from_str(json).map_err(|e| format!("Invalid exercise: {}", e))?;
+
+        exercise.validate()?;
+
+        Ok(exercise)
+    }
+
+    /// Checks the heap isn't empty and the workload only frees blocks it allocated
+    fn validate(&self) -> Result<()> {
+        if self.heap_size == 0 {
+            return Err(format!("Invalid exercise: `{}` has an empty heap", self.id).into());
+        }
+
+        let mut live = Vec::new();
+
+        for step in &self.workload {
+            match step {
+                WorkloadStep::Allocate { name, .. } if live.contains(&name) => {
+                    return Err(format!("Invalid exercise: `{}` is allocated twice", name).into());
+                }
+                WorkloadStep::Allocate { name, .. } => live.push(name),
+                WorkloadStep::Free { name } if live.contains(&name) => {
+                    live.retain(|other| *other != name);
+                }
+                WorkloadStep::Free { name } => {
+                    return Err(format!("Invalid exercise: block `{}` not found!", name).into());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Runs the workload of the exercise against a policy
+    ///
+    /// # Arguments
+    /// - `policy`: The expression scoring the free ranges, like `size - request`
+    /// - `options`: The options to run with, which decide the alignment of blocks
+    ///
+    /// # Returns
+    /// - [Result](crate::error::Result): A result containing either:
+    ///    - [AllocatorReport](crate::analyzer::AllocatorReport): The heap after every step
+    ///    - [Error](crate::error::Error): An error if the exercise is invalid, or the policy doesn't parse
+    ///      or doesn't score a range with an integer
+    pub fn run(&self, policy: &str, options: &AnalyzerOptions) -> Result<AllocatorReport> {
+        self.validate()?;
+
+        let policy = parse_policy(policy)?;
+
+        // blocks are never smaller than the alignment, like the allocator of the analyzer reserves them
+        let alignment = options.architecture.heap_alignment();
+
+        let mut free_list = vec![FreeRange {
+            start: 0,
+            end: self.heap_size - 1,
+            size: self.heap_size,
+        }];
+
+        let mut blocks: IndexMap<&str, (usize, usize)> = IndexMap::new();
+        let mut report = AllocatorReport {
+            exercise: self.id.clone(),
+            steps: Vec::new(),
+            failures: Vec::new(),
+            peak_in_use: 0,
+            peak_fragmentation: 0.0,
+        };
+
+        for (index, step) in self.workload.iter().enumerate() {
+            let mut pointer = None;
+
+            match step {
+                WorkloadStep::Allocate { name, size } => {
+                    let reserved_size = align_up((*size).max(alignment), alignment);
+
+                    match pick_range(&policy, &free_list, reserved_size, alignment)? {
+                        Some(range) => {
+                            let start = align_up(free_list[range].start, alignment);
+                            split_range(&mut free_list, range, start, reserved_size);
+                            blocks.insert(name, (start, reserved_size));
+                            pointer = Some(start);
+                        }
+                        None => report.failures.push(index),
+                    }
+                }
+
+                WorkloadStep::Free { name } => {
+                    if let Some((start, size)) = blocks.shift_remove(name.as_str()) {
+                        release_range(&mut free_list, start, size);
+                    }
+                }
+            }
+
+            let in_use = blocks.values().map(|(_, size)| size).sum();
+            let fragmentation = fragmentation(&free_list);
+
+            report.peak_in_use = report.peak_in_use.max(in_use);
+            report.peak_fragmentation = report.peak_fragmentation.max(fragmentation);
+            report.steps.push(WorkloadStepReport {
+                pointer,
+                failed: report.failures.last() == Some(&index),
+                in_use,
+                fragmentation,
+                free_list: free_list.clone(),
+            });
+        }
+
+        Ok(report)
+    }
+}
+
+/// Parses a policy, which has to be a single expression
+fn parse_policy(policy: &str) -> Result<Expr> {
+    let mut parser = Parser::new(policy);
+    let expr = parser.parse_expression()?;
+
+    if parser.peek() != TokenKind::EOF {
+        return Err(ParserError(
+            format!("Expected the end of the policy but found `{}`", parser.peek()),
+            1,
+            1,
+        ));
+    }
+
+    Ok(expr)
+}
+
+/// Scores the free ranges a block fits in with the policy, the first range wins ties
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - `Option<usize>`: The index of the range with the lowest score, `None` if the block fits in none
+///    - [Error](crate::error::Error): An error if the policy can't be evaluated to an integer
+fn pick_range(
+    policy: &Expr,
+    free_list: &[FreeRange],
+    reserved_size: usize,
+    alignment: usize,
+) -> Result<Option<usize>> {
+    let mut best: Option<(usize, i128)> = None;
+
+    for (index, range) in free_list.iter().enumerate() {
+        if align_up(range.start, alignment) + reserved_size - 1 > range.end {
+            continue;
+        }
+
+        let mut symbols = IndexMap::new();
+
+        for (name, value) in [
+            ("start", range.start),
+            ("end", range.end),
+            ("size", range.size),
+            ("request", reserved_size),
+        ] {
+            symbols.insert(
+                name.to_string(),
+                Symbol::Variable {
+                    vtype: Type::Long,
+                    name: name.to_string(),
+                    value: Some(value.to_string()),
+                    size: 8,
+                    string: None,
+                },
+            );
+        }
+
+        let score = match evaluate_expression(policy, &symbols, 1, 1)? {
+            Lit::Int(score) => score,
+            other => {
+                return Err(
+                    format!("The policy scored a range with `{}`, not an integer", other).into()
+                );
+            }
+        };
+
+        if best.is_none_or(|(_, best_score)| score < best_score) {
+            best = Some((index, score));
+        }
+    }
+
+    Ok(best.map(|(index, _)| index))
+}
+
+/// Takes a block out of a free range, keeping what is left on either side of it
+fn split_range(free_list: &mut Vec<FreeRange>, index: usize, start: usize, size: usize) {
+    let range = free_list.remove(index);
+    let mut rest = Vec::new();
+
+    if start > range.start {
+        rest.push(free_range(range.start, start - 1));
+    }
+
+    if start + size <= range.end {
+        rest.push(free_range(start + size, range.end));
+    }
+
+    free_list.splice(index..index, rest);
+}
+
+/// Gives a block back to the free list, merging it with the free ranges it touches
+fn release_range(free_list: &mut Vec<FreeRange>, start: usize, size: usize) {
+    let index = free_list.partition_point(|range| range.start < start);
+    free_list.insert(index, free_range(start, start + size - 1));
+
+    if index + 1 < free_list.len() && free_list[index].end + 1 == free_list[index + 1].start {
+        let next = free_list.remove(index + 1);
+        free_list[index] = free_range(free_list[index].start, next.end);
+    }
+
+    if index > 0 && free_list[index - 1].end + 1 == free_list[index].start {
+        let current = free_list.remove(index);
+        free_list[index - 1] = free_range(free_list[index - 1].start, current.end);
+    }
+}
+
+fn free_range(start: usize, end: usize) -> FreeRange {
+    FreeRange {
+        start,
+        end,
+        size: end - start + 1,
+    }
+}
+
+fn align_up(pointer: usize, alignment: usize) -> usize {
+    (pointer + alignment - 1) & !(alignment - 1)
+}
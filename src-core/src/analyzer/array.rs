@@ -0,0 +1,617 @@
+//! Arrays on the stack, whose elements are laid out row after row, and heap blocks indexed through a
+//! pointer
+//!
+//! `int m[3][4]` is 3 rows of 4 elements, `m[1][2]` is the element `1 * 4 + 2` of the flat memory
+//! underneath. After `int* p = (int*)malloc(3 * sizeof(int))`, `p[2]` is the element 8 bytes into
+//! the block
+
+use indexmap::IndexMap;
+
+use crate::{
+    diagnostics::{Diagnostic, Severity},
+    error::{Error::AnalyzerError, Result},
+    parser::ast::{Expr, Lit, Statement},
+};
+
+use super::heap_allocator::BlockMember;
+use super::helpers::{
+    check_integer_range, evaluate_expression, validate_variable_assignment, variable_literal,
+};
+use super::memory_functions::out_of_bounds_range;
+use super::memory_model::MemoryModel;
+use super::r#type::Type;
+use super::{AllocationType, Symbol};
+
+/// Represents whether an index falls inside an array or a heap block
+///
+/// - `Within`: The index is in bounds, and where the element is
+/// - `Outside`: The index is out of bounds, and why. It fails the analysis in strict mode, in
+///   permissive mode the write is skipped with a warning
+pub(crate) enum Bounds<T> {
+    Within(T),
+    Outside(String),
+}
+
+/// Represents an element of a heap block indexed through a pointer
+///
+/// # Fields
+/// - `block`: The starting position of the block in the heap
+/// - `offset`: Where the element starts, in bytes from the start of the block
+/// - `size`: The size of the element in bytes
+pub(crate) struct HeapElement {
+    pub(crate) block: usize,
+    pub(crate) offset: usize,
+    pub(crate) size: usize,
+}
+
+/// Gets the size of an array, every element of it
+///
+/// # Arguments
+/// - `vtype`: The type of the elements
+/// - `dimensions`: How many elements each dimension has
+/// - `pointer_size`: The size of a pointer in bytes
+///
+/// # Returns
+/// - `usize`: The size of the array in bytes
+pub(crate) fn array_size(vtype: &Type, dimensions: &[usize], pointer_size: usize) -> usize {
+    dimensions.iter().product::<usize>() * vtype.get_variable_size(pointer_size)
+}
+
+/// Gets the name of an element from its position in the flat memory, like `m[1][2]`
+pub(crate) fn element_name(name: &str, dimensions: &[usize], mut index: usize) -> String {
+    let mut indices = Vec::with_capacity(dimensions.len());
+
+    for dimension in dimensions.iter().rev() {
+        indices.push(index % dimension);
+        index /= dimension;
+    }
+
+    let indices: String = indices.iter().rev().map(|index| format!("[{}]", index)).collect();
+
+    format!("{}{}", name, indices)
+}
+
+/// Gets the position of an element in the flat memory of an array, row after row
+///
+/// # Arguments
+/// - `name`: The name of the array
+/// - `dimensions`: How many elements each dimension has
+/// - `indices`: The index of the element in each dimension
+/// - `symbols`: A reference to the symbol table, which the indices may read variables from
+/// - `line`: The line of the statement
+/// - `column`: The column errors point at
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - [Bounds](Bounds): The position of the element, or why an index is out of bounds
+///    - [Error](crate::error::Error): An error if there isn't an index for each dimension, or an index
+///      isn't an integer
+pub(crate) fn element_index(
+    name: &str,
+    dimensions: &[usize],
+    indices: &[Expr],
+    symbols: &IndexMap<String, Symbol>,
+    line: usize,
+    column: usize,
+) -> Result<Bounds<usize>> {
+    if indices.len() != dimensions.len() {
+        return Err(AnalyzerError(
+            format!(
+                "Expected {} indices into array `{}` but found {}",
+                dimensions.len(),
+                name,
+                indices.len()
+            ),
+            line,
+            column,
+        ));
+    }
+
+    let mut position = 0;
+
+    for (index, dimension) in indices.iter().zip(dimensions) {
+        let index = index_value(index, &format!("array `{}`", name), symbols, line, column)?;
+
+        if index < 0 || index as usize >= *dimension {
+            return Ok(Bounds::Outside(format!(
+                "Index {} is out of bounds for array `{}`, which has {} elements in that dimension",
+                index, name, dimension
+            )));
+        }
+
+        position = position * dimension + index as usize;
+    }
+
+    Ok(Bounds::Within(position))
+}
+
+/// Gets the element of the heap block a pointer points to, like `p[2]`
+///
+/// The index counts from where the pointer points, so after `p++` the index `-1` is the first
+/// element of the block
+///
+/// # Arguments
+/// - `name`: The name of the pointer
+/// - `pointer`: The pointer
+/// - `indices`: The indices the pointer is indexed with, a pointer takes a single one
+/// - `symbols`: A reference to the symbol table, which the index may read variables from
+/// - `pointer_size`: The size of a pointer in bytes
+/// - `line`: The line of the statement
+/// - `column`: The column errors point at
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - [Bounds](Bounds): The element, or why the index is out of bounds of the block
+///    - [Error](crate::error::Error): An error if the pointer doesn't point to a heap block of values,
+///      or isn't indexed with a single integer
+pub(crate) fn heap_element(
+    name: &str,
+    pointer: &Symbol,
+    indices: &[Expr],
+    symbols: &IndexMap<String, Symbol>,
+    pointer_size: usize,
+    line: usize,
+    column: usize,
+) -> Result<Bounds<HeapElement>> {
+    let Symbol::Pointer {
+        ptype,
+        value,
+        heap_pointer,
+        allocation_type,
+        indirection,
+        value_size,
+        offset,
+        ..
+    } = pointer
+    else {
+        return Err(AnalyzerError(
+            format!("Cannot index `{}`, it isn't an array", name),
+            line,
+            column,
+        ));
+    };
+
+    if *indirection > 1 {
+        return Err(AnalyzerError(
+            format!("Indexing pointer to pointer `{}` is not supported yet", name),
+            line,
+            column,
+        ));
+    }
+
+    if *ptype == Type::Void {
+        return Err(AnalyzerError(
+            format!("Cannot index `void*` `{}`, cast it to a typed pointer first", name),
+            line,
+            column,
+        ));
+    }
+
+    if let Type::Class(class_name) = ptype {
+        return Err(AnalyzerError(
+            format!("Cannot index `{}*` `{}`, it points to a single object", class_name, name),
+            line,
+            column,
+        ));
+    }
+
+    let reason = match allocation_type {
+        AllocationType::Heap => None,
+        AllocationType::Null => Some(format!("Cannot index null pointer `{}`", name)),
+        AllocationType::Dangling => {
+            Some(format!("Cannot write through dangling pointer `{}`", name))
+        }
+        AllocationType::Mapped => {
+            Some(format!("Cannot write through `{}`, it points to a mapping", name))
+        }
+        AllocationType::Stack => {
+            Some(format!("Cannot index `{}`, it doesn't point to a heap block", name))
+        }
+    };
+
+    if let Some(reason) = reason {
+        return Err(AnalyzerError(reason, line, column));
+    }
+
+    if let Some(Symbol::Object { .. }) = value.as_deref() {
+        return Err(AnalyzerError(
+            format!("Cannot index `{}`, it points to an object", name),
+            line,
+            column,
+        ));
+    }
+
+    let Some(heap_pointer) = heap_pointer else {
+        return Err(AnalyzerError(format!("Heap pointer not found for `{}`", name), line, column));
+    };
+
+    let [index] = indices else {
+        return Err(AnalyzerError(
+            format!("Expected 1 index into pointer `{}` but found {}", name, indices.len()),
+            line,
+            column,
+        ));
+    };
+
+    let index = index_value(index, &format!("pointer `{}`", name), symbols, line, column)?;
+    let size = ptype.get_variable_size(pointer_size);
+    let position = *offset as i64 + index * size as i64;
+
+    if position < 0 || position as usize + size > *value_size {
+        let pointed = match offset {
+            0 => String::new(),
+            offset => format!(" and `{}` points to element {}", name, offset / size),
+        };
+
+        let elements = value_size / size;
+
+        return Ok(Bounds::Outside(format!(
+            "Index {} is out of bounds for `{}`, its block holds {} element{}{}",
+            index,
+            name,
+            elements,
+            if elements == 1 { "" } else { "s" },
+            pointed
+        )));
+    }
+
+    Ok(Bounds::Within(HeapElement {
+        block: heap_pointer - offset,
+        offset: position as usize,
+        size,
+    }))
+}
+
+/// Reads an element of an array or of the heap block a pointer points to, like `a[1]` in `a[1] += 2;`
+///
+/// # Arguments
+/// - `name`: The name of the array or pointer
+/// - `indices`: The indices it is indexed with
+/// - `symbols`: A reference to the symbol table
+/// - `memory`: The memory model a heap block is in
+/// - `line`: The line of the statement
+/// - `column`: The column errors point at
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - `Lit`: The value of the element
+///    - [Error](crate::error::Error): An error if the element can't be read, is out of bounds or isn't
+///      initialized
+pub(crate) fn element_literal(
+    name: &str,
+    indices: &[Expr],
+    symbols: &IndexMap<String, Symbol>,
+    memory: &dyn MemoryModel,
+    line: usize,
+    column: usize,
+) -> Result<Lit> {
+    let (vtype, element, value) = match symbols.get(name) {
+        Some(Symbol::Array {
+            vtype,
+            dimensions,
+            elements,
+            ..
+        }) => match element_index(name, dimensions, indices, symbols, line, column)? {
+            Bounds::Within(index) => {
+                (vtype, element_name(name, dimensions, index), elements[index].clone())
+            }
+            Bounds::Outside(reason) => return Err(AnalyzerError(reason, line, column)),
+        },
+
+        Some(pointer @ Symbol::Pointer { ptype, .. }) => {
+            let element = match heap_element(
+                name,
+                pointer,
+                indices,
+                symbols,
+                memory.pointer_size(),
+                line,
+                column,
+            )? {
+                Bounds::Within(element) => element,
+                Bounds::Outside(reason) => return Err(AnalyzerError(reason, line, column)),
+            };
+
+            let block = memory.block(element.block).ok_or_else(|| {
+                AnalyzerError(format!("Heap pointer not found for `{}`", name), line, column)
+            })?;
+
+            // a block of several elements keeps each one as a member, a single value is its contents
+            let value = match &block.members {
+                Some(members) => members
+                    .iter()
+                    .find(|member| member.offset == element.offset)
+                    .and_then(|member| member.value.clone()),
+                None if element.offset == 0 && !block.metadata.is_empty() => {
+                    Some(block.metadata.clone())
+                }
+                None => None,
+            };
+
+            (ptype, format!("{}[{}]", name, element.offset / element.size), value)
+        }
+
+        Some(_) => {
+            return Err(AnalyzerError(
+                format!("Cannot index `{}`, it isn't an array", name),
+                line,
+                column,
+            ));
+        }
+
+        None => {
+            return Err(AnalyzerError(format!("Array `{}` not found!", name), line, column));
+        }
+    };
+
+    let Some(value) = value else {
+        return Err(AnalyzerError(format!("`{}` not initialized!", element), line, column));
+    };
+
+    variable_literal(vtype, &value)
+        .ok_or_else(|| AnalyzerError(format!("Cannot evaluate `{}`", element), line, column))
+}
+
+/// Writes an element of the heap block a pointer points to, like `p[2] = 7`
+///
+/// Each element written is shown as a field of the block at its offset, like the fields of an
+/// object
+///
+/// # Arguments
+/// - `name`: The name of the pointer
+/// - `indices`: The indices the pointer is indexed with
+/// - `new_value`: The value written
+/// - `symbols`: A mutable reference to the symbol table
+/// - `memory`: The memory model the block is in
+/// - `line`: The line of the statement
+/// - `column`: The column errors point at
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - `()`: If the element was written
+///    - [Error](crate::error::Error): An error if the element can't be written, or the index is out of
+///      bounds
+pub(crate) fn assign_heap_element(
+    name: &str,
+    indices: &[Expr],
+    new_value: Box<Expr>,
+    symbols: &mut IndexMap<String, Symbol>,
+    memory: &mut dyn MemoryModel,
+    line: usize,
+    column: usize,
+) -> Result<()> {
+    let Some(pointer) = symbols.get(name) else {
+        return Err(AnalyzerError(format!("Array `{}` not found!", name), line, column));
+    };
+
+    let element =
+        match heap_element(name, pointer, indices, symbols, memory.pointer_size(), line, column)? {
+            Bounds::Within(element) => element,
+            Bounds::Outside(reason) => return Err(AnalyzerError(reason, line, column)),
+        };
+
+    // an element is only found through a pointer
+    let Symbol::Pointer { ptype, .. } = pointer else {
+        return Ok(());
+    };
+
+    let element_name = format!("[{}]", element.offset / element.size);
+    let new_value =
+        validate_variable_assignment(new_value, &element_name, ptype, symbols, line, column)?;
+
+    check_integer_range(
+        ptype,
+        new_value.as_deref(),
+        &element_name,
+        memory.pointer_size(),
+        line,
+        column,
+    )?;
+
+    write_heap_elements(
+        name,
+        element.block,
+        element.size,
+        vec![(element.offset, new_value)],
+        symbols,
+        memory,
+    )
+}
+
+/// Writes elements of a heap block, each shown as a field of the block at its offset like the fields of
+/// an object
+///
+/// # Arguments
+/// - `name`: The name of the pointer the elements are written through
+/// - `block`: The starting position of the block in the heap
+/// - `size`: The size of an element in bytes
+/// - `elements`: Where each element starts, in bytes from the start of the block, with its value
+/// - `symbols`: A mutable reference to the symbol table
+/// - `memory`: The memory model the block is in
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - `()`: If the elements were written
+///    - [Error](crate::error::Error): An error if the block isn't in the memory model
+pub(crate) fn write_heap_elements(
+    name: &str,
+    block: usize,
+    size: usize,
+    elements: Vec<(usize, Option<String>)>,
+    symbols: &mut IndexMap<String, Symbol>,
+    memory: &mut dyn MemoryModel,
+) -> Result<()> {
+    let mut members =
+        memory.block(block).and_then(|block| block.members.clone()).unwrap_or_default();
+
+    members.retain(|member| elements.iter().all(|(offset, _)| member.offset != *offset));
+
+    for (offset, value) in &elements {
+        members.push(BlockMember {
+            name: format!("[{}]", offset / size),
+            offset: *offset,
+            size,
+            value: value.clone(),
+            points_to: None,
+        });
+    }
+
+    members.sort_by_key(|member| member.offset);
+
+    memory.update_members(block, members)?;
+
+    // the element the pointer points to is the value it shows, like after `*p = 7`
+    if let Some(Symbol::Pointer { value, offset, .. }) = symbols.get_mut(name) {
+        let pointed = elements.into_iter().find(|(element, _)| element == offset);
+
+        if let Some((_, new_value)) = pointed {
+            *value = new_value.map(|value| Box::new(Symbol::Literal { value }));
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the value a pointer points to in the heap, like `*p = 7`
+///
+/// A block of a single value shows it as the contents of the block. In a block of several elements,
+/// or when the pointer was moved with pointer arithmetic, the element the pointer points to is written
+/// the way `p[0] = 7` writes it
+///
+/// # Arguments
+/// - `name`: The name of the pointer
+/// - `new_value`: The value written
+/// - `symbols`: A mutable reference to the symbol table
+/// - `memory`: The memory model the block is in
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - `()`: If the value was written
+///    - [Error](crate::error::Error): An error if the block isn't in the memory model
+pub(crate) fn write_pointee(
+    name: &str,
+    new_value: String,
+    symbols: &mut IndexMap<String, Symbol>,
+    memory: &mut dyn MemoryModel,
+) -> Result<()> {
+    let Some(Symbol::Pointer {
+        ptype,
+        value,
+        heap_pointer: Some(heap_pointer),
+        value_size,
+        offset,
+        ..
+    }) = symbols.get_mut(name)
+    else {
+        return Ok(());
+    };
+
+    let block = *heap_pointer - *offset;
+    let size = ptype.get_variable_size(memory.pointer_size());
+
+    if *offset == 0 && *value_size <= size {
+        memory.update_metadata(block, new_value.clone())?;
+        *value = Some(Box::new(Symbol::Literal { value: new_value }));
+
+        return Ok(());
+    }
+
+    let offset = *offset;
+    write_heap_elements(name, block, size, vec![(offset, Some(new_value))], symbols, memory)
+}
+
+/// Finds a write out of the bounds of an array or a heap block, through an index or a `memset` or
+/// `memcpy`, which permissive mode skips with a warning instead of failing the analysis
+///
+/// # Arguments
+/// - `statement`: The statement about to run
+/// - `symbols`: A reference to the symbol table
+/// - `pointer_size`: The size of a pointer in bytes
+///
+/// # Returns
+/// - `Option<Diagnostic>`: The warning, `None` if the statement doesn't write out of bounds.
+///   Any other problem with the statement is left for it to report when it runs
+pub(crate) fn out_of_bounds_write(
+    statement: &Statement,
+    symbols: &IndexMap<String, Symbol>,
+    pointer_size: usize,
+) -> Option<Diagnostic> {
+    // a `memset` or `memcpy` past the end is reported at its size, which is what runs past it
+    if let Statement::Memset {
+        line, size_column, ..
+    }
+    | Statement::Memcpy {
+        line, size_column, ..
+    } = statement
+    {
+        let reason = out_of_bounds_range(statement, symbols, pointer_size)?;
+
+        return Some(Diagnostic {
+            severity: Severity::Warning,
+            message: format!("{}, the write was skipped", reason),
+            line_number: Some(*line),
+            column_number: Some(*size_column),
+        });
+    }
+
+    let Statement::ArrayAssignment {
+        var_name,
+        indices,
+        line,
+        index_column,
+        ..
+    } = statement
+    else {
+        return None;
+    };
+
+    let reason = match symbols.get(var_name)? {
+        Symbol::Array { dimensions, .. } => {
+            match element_index(var_name, dimensions, indices, symbols, *line, *index_column) {
+                Ok(Bounds::Outside(reason)) => reason,
+                _ => return None,
+            }
+        }
+        pointer => {
+            match heap_element(
+                var_name,
+                pointer,
+                indices,
+                symbols,
+                pointer_size,
+                *line,
+                *index_column,
+            ) {
+                Ok(Bounds::Outside(reason)) => reason,
+                _ => return None,
+            }
+        }
+    };
+
+    Some(Diagnostic {
+        severity: Severity::Warning,
+        message: format!("{}, the write was skipped", reason),
+        line_number: Some(*line),
+        column_number: Some(*index_column),
+    })
+}
+
+/// Evaluates an index, which has to be an integer
+fn index_value(
+    index: &Expr,
+    indexed: &str,
+    symbols: &IndexMap<String, Symbol>,
+    line: usize,
+    column: usize,
+) -> Result<i64> {
+    match evaluate_expression(index, symbols, line, column)? {
+        Lit::Int(index) => i64::try_from(index).map_err(|_| {
+            AnalyzerError(format!("Index {} is out of bounds for {}", index, indexed), line, column)
+        }),
+        lit => Err(AnalyzerError(
+            format!("Cannot index {} with `{}`", indexed, lit.to_string()),
+            line,
+            column,
+        )),
+    }
+}
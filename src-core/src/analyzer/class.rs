@@ -0,0 +1,623 @@
+//! Classes, whose objects keep a value for each field and run methods that read and write those fields
+//!
+//! A method runs in a frame of its own like a function, with the address of its object passed as the
+//! hidden `this` pointer. The fields of the object are read and written by name in the body, and the
+//! object is updated once the method returns
+//!
+//! The constructor of a class runs when an object is declared, and its destructor when the object goes
+//! out of scope, so an object can release the heap blocks its pointer fields own like RAII does in C++
+
+use std::ops::Range;
+
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use crate::error::{Error::AnalyzerError, Result};
+use crate::layout::place_members;
+use crate::parser::ast::{Expr, Lit, Statement};
+
+use super::cross_reference::SourceRef;
+use super::frame::CallStack;
+use super::heap_allocator::BlockMember;
+use super::helpers::{evaluate_expression, symbol_size, variable_literal};
+use super::r#type::Type;
+use super::{AllocationType, Symbol};
+
+/// Represents a field of an object
+///
+/// # Fields
+/// - `name`: The name of the field
+/// - `ftype`: The type of the field
+/// - `value`: The value of the field, `None` until it is written
+/// - `pointer`: The pointer a field like `int* data;` holds, `None` for fields holding values
+/// - `public`: Whether the field can be used outside the methods of its class
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct ClassField {
+    pub name: String,
+    pub ftype: Type,
+    pub value: Option<String>,
+    pub pointer: Option<Box<Symbol>>,
+    pub public: bool,
+}
+
+/// Represents a class defined in the program, its methods are kept with the functions
+///
+/// # Fields
+/// - `fields`: The fields its objects start with
+/// - `private_methods`: The methods that can't be called outside the class
+/// - `source`: The definition of the class
+#[derive(Debug, Clone)]
+pub(crate) struct Class {
+    pub(crate) fields: Vec<ClassField>,
+    pub(crate) private_methods: Vec<String>,
+    pub(crate) source: SourceRef,
+}
+
+/// Gets the name a method is kept under with the functions, like `Counter::add`
+pub(crate) fn method_name(class_name: &str, method: &str) -> String {
+    format!("{}::{}", class_name, method)
+}
+
+/// Gets the name the constructor of a class is kept under, like `Counter::Counter`
+pub(crate) fn constructor_name(class_name: &str) -> String {
+    method_name(class_name, class_name)
+}
+
+/// Gets the name the destructor of a class is kept under, like `Counter::~Counter`
+pub(crate) fn destructor_name(class_name: &str) -> String {
+    method_name(class_name, &format!("~{}", class_name))
+}
+
+/// Gets the size of a field, a pointer field is as wide as a pointer
+fn field_size(field: &ClassField, pointer_size: usize) -> usize {
+    match field.pointer {
+        Some(_) => pointer_size,
+        None => field.ftype.get_variable_size(pointer_size),
+    }
+}
+
+/// Gets the size of each field of an object, in the order they are laid out
+fn field_sizes(fields: &[ClassField], pointer_size: usize) -> Vec<usize> {
+    fields.iter().map(|field| field_size(field, pointer_size)).collect()
+}
+
+/// Gets where each field of an object starts, the fields are laid out in order, each aligned to its own
+/// size
+///
+/// # Returns
+/// - `Vec<usize>`: The offset of each field in bytes from the start of the object
+fn field_offsets(fields: &[ClassField], pointer_size: usize) -> Vec<usize> {
+    place_members(&field_sizes(fields, pointer_size)).offsets
+}
+
+/// Gets the size of an object, whose fields are laid out in order, each aligned to its own size
+///
+/// The object is padded to the alignment of its largest field, so the fields of objects next to each
+/// other stay aligned, and an object without fields still takes a byte like in C++
+///
+/// # Arguments
+/// - `fields`: The fields of the object
+/// - `pointer_size`: The size of a pointer in bytes, which `long` is as wide as
+///
+/// # Returns
+/// - `usize`: The size of the object in bytes
+pub(crate) fn object_size(fields: &[ClassField], pointer_size: usize) -> usize {
+    place_members(&field_sizes(fields, pointer_size)).size
+}
+
+/// Gets the alignment of an object, the size of its most aligned field
+pub(crate) fn object_alignment(fields: &[ClassField], pointer_size: usize) -> usize {
+    place_members(&field_sizes(fields, pointer_size)).alignment
+}
+
+/// Gets the fields of an object allocated with `new`, laid out the way they are in its heap block
+pub(crate) fn block_members(object: &Symbol, pointer_size: usize) -> Vec<BlockMember> {
+    let Symbol::Object { fields, .. } = object else {
+        return Vec::new();
+    };
+
+    fields
+        .iter()
+        .zip(field_offsets(fields, pointer_size))
+        .map(|(field, offset)| BlockMember {
+            name: field.name.clone(),
+            offset,
+            size: field_size(field, pointer_size),
+            value: field.value.clone(),
+            points_to: match field.pointer.as_deref() {
+                Some(Symbol::Pointer {
+                    heap_pointer,
+                    allocation_type: AllocationType::Heap,
+                    ..
+                }) => *heap_pointer,
+                _ => None,
+            },
+        })
+        .collect()
+}
+
+/// Finds the method a call like `c.add(2)` runs
+///
+/// # Arguments
+/// - `call_stack`: The call stack, which holds the classes and their methods
+/// - `symbols`: The symbols the object is looked up in
+/// - `var_name`: The name of the object
+/// - `method`: The name of the method
+/// - `line`: The line of the call
+/// - `column`: The column of the call
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - `String`: The name the method is kept under with the functions
+///    - [Error](crate::error::Error): An error if `var_name` isn't an object, or its class has no public
+///      method by that name
+pub(crate) fn resolve_method(
+    call_stack: &CallStack,
+    symbols: &IndexMap<String, Symbol>,
+    var_name: &str,
+    method: &str,
+    line: usize,
+    column: usize,
+) -> Result<String> {
+    let class_name = match symbols.get(var_name) {
+        Some(Symbol::Object { class_name, .. }) => class_name,
+
+        Some(Symbol::Mutex { .. }) => {
+            return Err(AnalyzerError(
+                format!(
+                    "Expected `lock` or `unlock` but found `{}`, mutexes have no other methods",
+                    method
+                ),
+                line,
+                column,
+            ));
+        }
+
+        Some(_) => {
+            return Err(AnalyzerError(
+                format!("Cannot call `{}` on `{}`, it isn't an object", method, var_name),
+                line,
+                column,
+            ));
+        }
+
+        None => {
+            return Err(AnalyzerError(format!("Object `{}` not found!", var_name), line, column));
+        }
+    };
+
+    if method == class_name {
+        return Err(AnalyzerError(
+            format!("The constructor of `{}` only runs when an object is declared", class_name),
+            line,
+            column,
+        ));
+    }
+
+    let name = method_name(class_name, method);
+
+    if !call_stack.functions.contains_key(&name) {
+        return Err(AnalyzerError(
+            format!("Method `{}` not found in class `{}`!", method, class_name),
+            line,
+            column,
+        ));
+    }
+
+    if call_stack.classes[class_name].private_methods.iter().any(|other| other == method) {
+        return Err(AnalyzerError(
+            format!("Method `{}` of class `{}` is private", method, class_name),
+            line,
+            column,
+        ));
+    }
+
+    Ok(name)
+}
+
+/// Finds the destructor `delete` runs on the object a pointer holds, `None` if the pointer doesn't hold
+/// an object allocated with `new` or its class has no destructor
+pub(crate) fn heap_destructor(
+    symbols: &IndexMap<String, Symbol>,
+    pointer_name: &str,
+    call_stack: &CallStack,
+) -> Option<String> {
+    match symbols.get(pointer_name) {
+        Some(Symbol::Pointer {
+            value: Some(object),
+            allocation_type: AllocationType::Heap,
+            ..
+        }) => match object.as_ref() {
+            Symbol::Object { class_name, .. } => Some(destructor_name(class_name)),
+            _ => None,
+        },
+        _ => None,
+    }
+    .filter(|destructor| call_stack.functions.contains_key(destructor))
+}
+
+/// Copies the fields of the object `source` into another object of its class, member by member like the
+/// copy assignment operator C++ generates for a class. A pointer field is copied as an address, so both
+/// objects point at the same block afterwards
+///
+/// # Arguments
+/// - `class_name`: The class of the object assigned to
+/// - `fields`: The fields of the object assigned to
+/// - `source`: The name of the object assigned
+/// - `symbols`: A reference to the symbol table
+/// - `line`: The line of the assignment
+/// - `column`: The column errors point at
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - `()`: If the fields were copied
+///    - [Error](crate::error::Error): An error if `source` isn't an object of the same class
+pub(crate) fn copy_fields(
+    class_name: &str,
+    fields: &mut [ClassField],
+    source: &str,
+    symbols: &IndexMap<String, Symbol>,
+    line: usize,
+    column: usize,
+) -> Result<()> {
+    match symbols.get(source) {
+        Some(Symbol::Object {
+            class_name: source_class,
+            fields: source_fields,
+            ..
+        }) if source_class == class_name => {
+            fields.clone_from_slice(source_fields);
+            Ok(())
+        }
+
+        Some(Symbol::Object {
+            class_name: source_class,
+            ..
+        }) => Err(AnalyzerError(
+            format!(
+                "Cannot assign `{}` of class `{}` to an object of class `{}`",
+                source, source_class, class_name
+            ),
+            line,
+            column,
+        )),
+
+        Some(_) => Err(AnalyzerError(
+            format!(
+                "Cannot assign `{}` to an object of class `{}`, it isn't an object",
+                source, class_name
+            ),
+            line,
+            column,
+        )),
+
+        None => Err(AnalyzerError(format!("Variable `{}` not found!", source), line, column)),
+    }
+}
+
+/// Finds a field of an object to read or write it from outside the methods of its class
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - `usize`: The index of the field
+///    - [Error](crate::error::Error): An error if the class has no such field, or it is private or a
+///      pointer, which only the methods of the class can use
+pub(crate) fn public_field(
+    fields: &[ClassField],
+    class_name: &str,
+    field: &str,
+    line: usize,
+    column: usize,
+) -> Result<usize> {
+    match fields.iter().position(|other| other.name == field) {
+        Some(index) if !fields[index].public => Err(AnalyzerError(
+            format!("Field `{}` of class `{}` is private", field, class_name),
+            line,
+            column,
+        )),
+        Some(index) if fields[index].pointer.is_some() => Err(AnalyzerError(
+            format!(
+                "Field `{}` of class `{}` is a pointer, only its methods can use it",
+                field, class_name
+            ),
+            line,
+            column,
+        )),
+        Some(index) => Ok(index),
+        None => Err(AnalyzerError(
+            format!("Field `{}` not found in class `{}`!", field, class_name),
+            line,
+            column,
+        )),
+    }
+}
+
+/// Creates the `this` pointer of a method, pointing at the object the method was called on
+///
+/// `this` points at the whole object, into the heap block `heap_pointer` starts for an object allocated
+/// with `new`
+pub(crate) fn this_pointer(
+    object: &Symbol,
+    heap_pointer: Option<usize>,
+    pointer_size: usize,
+) -> Symbol {
+    let class_name = match object {
+        Symbol::Object { class_name, .. } => class_name.clone(),
+        _ => unreachable!("`this` points at an object"),
+    };
+
+    Symbol::Pointer {
+        ptype: Type::Class(class_name),
+        name: "this".to_string(),
+        value: Some(Box::new(object.clone())),
+        heap_pointer,
+        allocation_type: match heap_pointer {
+            Some(_) => AllocationType::Heap,
+            None => AllocationType::Stack,
+        },
+        pointer_size,
+        value_size: symbol_size(object),
+        indirection: 1,
+        unique: false,
+        shared: false,
+        control_block: None,
+        offset: 0,
+    }
+}
+
+/// Puts the fields of an object in the frame of a method, so its body reads and writes them by name
+///
+/// Fields named like a parameter are left out, the parameter hides them like it does in C++
+///
+/// # Returns
+/// - `Range<usize>`: Where the fields are in the frame, to take them out once the method returns
+pub(crate) fn enter_fields(
+    object: &Symbol,
+    frame_symbols: &mut IndexMap<String, Symbol>,
+    pointer_size: usize,
+) -> Range<usize> {
+    let first = frame_symbols.len();
+
+    if let Symbol::Object { fields, .. } = object {
+        for field in fields {
+            if frame_symbols.contains_key(&field.name) {
+                continue;
+            }
+
+            let symbol = match &field.pointer {
+                Some(pointer) => pointer.as_ref().clone(),
+                None => Symbol::Variable {
+                    vtype: field.ftype.clone(),
+                    name: field.name.clone(),
+                    value: field.value.clone(),
+                    size: field.ftype.get_variable_size(pointer_size),
+                    string: None,
+                },
+            };
+
+            frame_symbols.insert(field.name.clone(), symbol);
+        }
+    }
+
+    first..frame_symbols.len()
+}
+
+/// Takes the fields of an object back out of the frame of a method once it returns, writing what the
+/// method left in them to the object and to the copy `this` points at
+///
+/// `var_name` names the object, or the pointer holding an object allocated with `new`
+pub(crate) fn leave_fields(
+    var_name: &str,
+    fields: Range<usize>,
+    frame_symbols: &mut IndexMap<String, Symbol>,
+    symbols: &mut IndexMap<String, Symbol>,
+) {
+    let written: Vec<Symbol> = frame_symbols.drain(fields).map(|(_, symbol)| symbol).collect();
+
+    // the fields a parameter hides were only written through `this`, so its copy holds them
+    let hidden = match frame_symbols.get("this") {
+        Some(Symbol::Pointer {
+            value: Some(this), ..
+        }) => match this.as_ref() {
+            Symbol::Object { fields, .. } => fields
+                .iter()
+                .filter(|field| frame_symbols.contains_key(&field.name))
+                .cloned()
+                .collect(),
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    };
+
+    // an object allocated with `new` is held by the pointer to it
+    let object = match symbols.get_mut(var_name) {
+        Some(Symbol::Pointer {
+            value: Some(object),
+            ..
+        }) => object.as_mut(),
+        Some(object) => object,
+        None => return,
+    };
+
+    if let Symbol::Object { fields, .. } = object {
+        for hidden in hidden {
+            if let Some(field) = fields.iter_mut().find(|field| field.name == hidden.name) {
+                *field = hidden;
+            }
+        }
+
+        for symbol in written {
+            let (Symbol::Variable { name, .. } | Symbol::Pointer { name, .. }) = &symbol else {
+                continue;
+            };
+
+            let Some(field) = fields.iter_mut().find(|field| field.name == *name) else {
+                continue;
+            };
+
+            match symbol {
+                Symbol::Variable { value, .. } => field.value = value,
+                pointer => field.pointer = Some(Box::new(pointer)),
+            }
+        }
+    }
+
+    if let Some(Symbol::Pointer { value, .. }) = frame_symbols.get_mut("this") {
+        *value = Some(Box::new(object.clone()));
+    }
+}
+
+/// Whether `this` points at an object with a field named `member`
+fn this_field(symbols: &IndexMap<String, Symbol>, member: &str) -> bool {
+    match symbols.get("this") {
+        Some(Symbol::Pointer {
+            value: Some(this), ..
+        }) => match this.as_ref() {
+            Symbol::Object { fields, .. } => fields.iter().any(|field| field.name == member),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Whether a parameter of the running method is named like the field `member`, hiding it
+fn hidden_field(call_stack: &CallStack, member: &str) -> bool {
+    call_stack.active.last().and_then(|method| call_stack.functions.get(method)).is_some_and(
+        |method| method.parameters.iter().any(|parameter| parameter.param_name == member),
+    )
+}
+
+/// Reads a field of the object a pointer points to, like `p->x` in `p->x *= 2;`
+///
+/// In a method `this->x` is read from the frame, where the method writes the field, unless a parameter
+/// named like it hides it
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - `Lit`: The value of the field
+///    - [Error](crate::error::Error): An error if the pointer doesn't point to an object, or the field
+///      can't be read or isn't initialized
+pub(crate) fn pointer_member_literal(
+    pointer_name: &str,
+    member: &str,
+    symbols: &IndexMap<String, Symbol>,
+    call_stack: &CallStack,
+    line: usize,
+    column: usize,
+) -> Result<Lit> {
+    if pointer_name == "this" && this_field(symbols, member) && !hidden_field(call_stack, member) {
+        return evaluate_expression(&Expr::Ident(member.to_owned()), symbols, line, column);
+    }
+
+    let Some(Symbol::Pointer {
+        value,
+        allocation_type,
+        ..
+    }) = symbols.get(pointer_name)
+    else {
+        return Err(AnalyzerError(
+            format!("Cannot use `->` on `{}`, it isn't a pointer", pointer_name),
+            line,
+            column,
+        ));
+    };
+
+    match allocation_type {
+        AllocationType::Null => {
+            return Err(AnalyzerError(
+                format!("Cannot dereference null pointer `{}`", pointer_name),
+                line,
+                column,
+            ));
+        }
+        AllocationType::Dangling => {
+            return Err(AnalyzerError(
+                format!("Cannot dereference dangling pointer `{}`", pointer_name),
+                line,
+                column,
+            ));
+        }
+        _ => {}
+    }
+
+    let Some(Symbol::Object {
+        class_name, fields, ..
+    }) = value.as_deref()
+    else {
+        return Err(AnalyzerError(
+            format!(
+                "Cannot read member `{}` through `{}`, it doesn't point to an object",
+                member, pointer_name
+            ),
+            line,
+            column,
+        ));
+    };
+
+    // the methods of a class read its private fields through `this`
+    let index = match pointer_name {
+        "this" => fields.iter().position(|field| field.name == member).ok_or_else(|| {
+            AnalyzerError(
+                format!("Field `{}` not found in class `{}`!", member, class_name),
+                line,
+                column,
+            )
+        })?,
+        _ => public_field(fields, class_name, member, line, column)?,
+    };
+
+    let field_name = format!("{}->{}", pointer_name, member);
+
+    match &fields[index] {
+        ClassField {
+            pointer: Some(_), ..
+        } => Err(AnalyzerError(
+            format!("Cannot use `{}` in arithmetic, it isn't a variable", field_name),
+            line,
+            column,
+        )),
+        ClassField {
+            ftype,
+            value: Some(value),
+            ..
+        } => variable_literal(ftype, value).ok_or_else(|| {
+            AnalyzerError(format!("Cannot evaluate `{}`", field_name), line, column)
+        }),
+        _ => Err(AnalyzerError(format!("`{}` not initialized!", field_name), line, column)),
+    }
+}
+
+/// Rewrites `this->x = ...;` in a method into `x = ...;`, since the field is in the frame of the method
+/// under its own name
+///
+/// A field hidden by a parameter named like it is left to the assignment through `this`, which writes
+/// the copy `this` points at
+pub(crate) fn this_member_statement(
+    statement: Statement,
+    symbols: &IndexMap<String, Symbol>,
+    call_stack: &CallStack,
+) -> Statement {
+    match statement {
+        Statement::PointerMemberAssignment {
+            pointer_name,
+            member,
+            new_value,
+            line,
+            pointer_ident_column,
+            member_column,
+        } if pointer_name == "this"
+            && this_field(symbols, &member)
+            && !hidden_field(call_stack, &member) =>
+        {
+            Statement::VariableAssignment {
+                var_name: member,
+                new_value,
+                line,
+                var_ident_column: pointer_ident_column,
+                assignment_column: member_column,
+            }
+        }
+        statement => statement,
+    }
+}
@@ -0,0 +1,942 @@
+//! Links the output of the analyzer back to the statements that produced it
+
+use std::iter::Peekable;
+use std::vec;
+
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use crate::diagnostics::Diagnostic;
+use crate::parser::ast::{Release, Statement};
+
+use super::heap_allocator::{HeapBlock, HeapBlockState, HeapGrowth};
+use super::heatmap::AccessCount;
+use super::memory_model::MemoryModel;
+use super::tags::MemoryTag;
+use super::Symbol;
+
+/// Points at the statement that produced part of the output
+///
+/// # Fields
+/// - `statement`: The path to the statement in the AST, the index of a top level statement followed by
+///   the index within each function body or block it is nested in, the body of a method is nested in its
+///   class by the index of the method
+/// - `line`: The line of the statement
+/// - `column`: The column of the name the statement declares or uses
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct SourceRef {
+    pub statement: Vec<usize>,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl SourceRef {
+    /// Creates a reference to a statement
+    ///
+    /// # Arguments
+    /// - `statement`: The path to the statement in the AST
+    /// - `line`: The line of the statement
+    /// - `column`: The column of the name the statement declares or uses
+    ///
+    /// # Returns
+    /// - [SourceRef](crate::analyzer::SourceRef): A new reference
+    pub(crate) fn new(statement: &[usize], line: usize, column: usize) -> Self {
+        Self {
+            statement: statement.to_vec(),
+            line,
+            column,
+        }
+    }
+
+    /// Creates a reference to a statement at the given path
+    pub(crate) fn of_statement(path: &[usize], statement: &Statement) -> Self {
+        Self::new(path, statement.line(), statement.column())
+    }
+}
+
+/// Links the symbols of a frame or a block to their statements
+///
+/// # Fields
+/// - `source`: The call that pushed the frame, or the block that was left
+/// - `symbols`: The declaration of each symbol, in the same order as the symbols
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct ScopeReference {
+    pub source: SourceRef,
+    pub symbols: Vec<SourceRef>,
+}
+
+/// Represents when a heap block was alive, measured in steps of the program
+///
+/// A step is a single statement being run, counted in the order the statements ran, so a loop or a
+/// function called twice takes a step for every statement each time it runs
+///
+/// # Fields
+/// - `allocated`: The step that allocated the block
+/// - `released`: The step that freed or leaked the block, `None` if it is still allocated
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct HeapLifetime {
+    pub allocated: usize,
+    pub released: Option<usize>,
+}
+
+/// Represents a variable taking on a new value
+///
+/// # Fields
+/// - `step`: The step that changed the value
+/// - `value`: The new value, `None` while the variable is uninitialized
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct ValueChange {
+    pub step: usize,
+    pub value: Option<String>,
+}
+
+/// Represents a value being copied from one symbol into another
+///
+/// # Fields
+/// - `step`: The step that made the copy
+/// - `source`: The statement that made the copy
+/// - `from`: The symbol copied from, `None` for the temporary `std::swap` copies through
+/// - `to`: The symbol copied to, `None` for the temporary `std::swap` copies through
+/// - `bytes`: The number of bytes copied
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct ValueCopy {
+    pub step: usize,
+    pub source: SourceRef,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub bytes: usize,
+}
+
+/// Represents a `std::unique_ptr` handing its block to another, leaving itself null instead of copying
+///
+/// # Fields
+/// - `step`: The step that made the move
+/// - `source`: The statement that made the move
+/// - `from`: The pointer moved from
+/// - `to`: The pointer moved to
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct ValueMove {
+    pub step: usize,
+    pub source: SourceRef,
+    pub from: String,
+    pub to: String,
+}
+
+/// Represents a cast that lost part of the value it converted, like the fraction of a `double` cast to
+/// an `int`
+///
+/// # Fields
+/// - `step`: The step that made the cast
+/// - `source`: The statement the cast is part of
+/// - `cast`: The cast as it is written
+/// - `from`: The value before the cast
+/// - `to`: The value after the cast
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct LossyCast {
+    pub step: usize,
+    pub source: SourceRef,
+    pub cast: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// Represents a statement the parser skipped in
+/// [permissive](crate::analyzer::Strictness::Permissive) mode, so the editor can gray out the span it
+/// takes up
+///
+/// # Fields
+/// - `line`: The line the statement starts on
+/// - `column`: The column the statement starts at
+/// - `end_line`: The line the statement ends on
+/// - `end_column`: The column of the last character of the statement
+/// - `reason`: Why it was skipped, like `Templates are not supported`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct IgnoredStatement {
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+    pub reason: String,
+}
+
+/// Finds every statement the parser skipped, those in the bodies of functions, methods, blocks, branches,
+/// loops and threads included
+///
+/// # Arguments
+/// - `statements`: The statements of the program
+///
+/// # Returns
+/// - `Vec<IgnoredStatement>`: The skipped statements, in the order they are in the source
+pub(crate) fn ignored_statements(statements: &[Statement]) -> Vec<IgnoredStatement> {
+    let mut ignored = Vec::new();
+
+    for statement in statements {
+        collect_ignored(statement, &mut ignored);
+    }
+
+    ignored
+}
+
+fn collect_ignored(statement: &Statement, ignored: &mut Vec<IgnoredStatement>) {
+    let nested: Vec<&Statement> = match statement {
+        Statement::Ignored {
+            reason,
+            line,
+            column,
+            end_line,
+            end_column,
+        } => {
+            ignored.push(IgnoredStatement {
+                line: *line,
+                column: *column,
+                end_line: *end_line,
+                end_column: *end_column,
+                reason: reason.clone(),
+            });
+
+            return;
+        }
+
+        Statement::FunctionDefinition { body, .. } => body.iter().collect(),
+
+        Statement::Block { statements, .. } | Statement::ThreadSpawn { statements, .. } => {
+            statements.iter().collect()
+        }
+
+        Statement::If {
+            then_branch,
+            else_branch,
+            ..
+        } => [Some(then_branch), else_branch.as_ref()]
+            .into_iter()
+            .flatten()
+            .map(|branch| branch.as_ref())
+            .collect(),
+
+        Statement::While { body, update, .. } => [Some(body), update.as_ref()]
+            .into_iter()
+            .flatten()
+            .map(|statement| statement.as_ref())
+            .collect(),
+
+        Statement::ClassDefinition { methods, .. } => {
+            methods.iter().map(|method| &method.definition).collect()
+        }
+
+        _ => Vec::new(),
+    };
+
+    for statement in nested {
+        collect_ignored(statement, ignored);
+    }
+}
+
+/// Represents a statement a spawned thread ran, the steps of the threads interleave in an order picked from
+/// the [seed](crate::analyzer::AnalyzerOptions::seed)
+///
+/// # Fields
+/// - `thread`: The thread that ran the statement, threads are numbered from 1 in the order they were spawned
+/// - `step`: The step the statement ran at
+/// - `source`: The statement
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct ThreadStep {
+    pub thread: usize,
+    pub step: usize,
+    pub source: SourceRef,
+}
+
+/// Represents a thread locking or unlocking a mutex, which the thread owns from the step it locks it until
+/// the step it unlocks it
+///
+/// # Fields
+/// - `mutex`: The name of the mutex
+/// - `thread`: The thread, `0` for the main thread
+/// - `step`: The step that locked or unlocked the mutex
+/// - `locked`: Whether the mutex was locked, `false` when it was unlocked
+/// - `source`: The statement that locked or unlocked the mutex
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct LockEvent {
+    pub mutex: String,
+    pub thread: usize,
+    pub step: usize,
+    pub locked: bool,
+    pub source: SourceRef,
+}
+
+/// Represents a heap block written by more than one spawned thread without a mutex they all held, so
+/// what it ends up holding depends on the order the threads ran in
+///
+/// # Fields
+/// - `block`: The starting position of the block
+/// - `threads`: The threads that wrote the block so far, in the order they first wrote it
+/// - `step`: The step of the write that made the block race
+/// - `source`: The statement of the write that made the block race
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct DataRace {
+    pub block: usize,
+    pub threads: Vec<usize>,
+    pub step: usize,
+    pub source: SourceRef,
+}
+
+/// Represents the heap growing at a step, so frontends can show it expanding before the blocks past its
+/// old end appear
+///
+/// # Fields
+/// - `step`: The step the heap grew at
+/// - `growth`: How much the heap grew and why
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct HeapGrowthEvent {
+    pub step: usize,
+    pub growth: HeapGrowth,
+}
+
+/// Represents an iteration of a loop, with how much of the heap was in use once it ended so frontends can
+/// show the heap filling up as the loop runs
+///
+/// # Fields
+/// - `source`: The loop
+/// - `iteration`: The iteration, numbered from 1
+/// - `step`: The step the iteration ended at
+/// - `heap_in_use`: The bytes of the heap held by allocated or leaked blocks once the iteration ended
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct LoopIteration {
+    pub source: SourceRef,
+    pub iteration: usize,
+    pub step: usize,
+    pub heap_in_use: usize,
+}
+
+/// Represents the spawned threads that wrote a heap block
+///
+/// # Fields
+/// - `threads`: The threads that wrote the block, in the order they first wrote it
+/// - `locks`: The mutexes held by every write to the block, which guard it while there is one
+/// - `raced`: Whether the block was already reported as a data race
+#[derive(Debug, Clone)]
+pub(crate) struct SharedBlock {
+    pub(crate) threads: Vec<usize>,
+    pub(crate) locks: Vec<String>,
+    pub(crate) raced: bool,
+}
+
+/// Represents the temporary holding the value a call returned, which lives on the stack of the caller
+/// until the end of the full expression the call is part of
+///
+/// # Fields
+/// - `function`: The function whose frame the temporary lived in, `None` at the top level
+/// - `symbol`: The temporary, named after the call that created it
+/// - `created`: The step the call returned at
+/// - `destroyed`: The step the full expression ended at
+/// - `source`: The statement the full expression belongs to
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct Temporary {
+    pub function: Option<String>,
+    pub symbol: Symbol,
+    pub created: usize,
+    pub destroyed: usize,
+    pub source: SourceRef,
+}
+
+/// Maps every part of the output to the statement that produced it, so frontends can highlight the line
+/// behind a symbol or block without matching names
+///
+/// Every list runs parallel to the output it describes
+///
+/// # Fields
+/// - `stack`: The declaration of each stack symbol
+/// - `heap`: The allocation of each heap block, `None` for blocks no statement allocated
+/// - `frames`: The call and declarations of each stack frame
+/// - `scope_exits`: The block and declarations of each scope exit
+/// - `lifetimes`: The lifetime of each heap block, `None` for blocks no statement allocated
+/// - `steps`: How many steps the program took
+/// - `history`: Every value each stack symbol held, empty for pointers, and empty altogether unless
+///   [track_history](crate::analyzer::AnalyzerOptions::track_history) is set
+/// - `copies`: Every value copied between symbols, in the order the copies were made
+/// - `moves`: Every block moved between `std::unique_ptr`s, in the order the moves were made
+/// - `temporaries`: Every temporary a call returned, in the order they were created, and empty altogether
+///   unless [show_temporaries](crate::analyzer::AnalyzerOptions::show_temporaries) is set
+/// - `lossy_casts`: Every cast that lost part of its value, in the order the casts were made
+/// - `thread_steps`: Every statement the spawned threads ran, in the order they ran
+/// - `lock_events`: Every mutex locked or unlocked, in the order it happened
+/// - `data_races`: Every heap block spawned threads wrote without a common mutex, in the order the races
+///   were found
+/// - `loop_iterations`: Every iteration of every loop, in the order they ran
+/// - `heap_growth`: Every time the heap grew, in the order it grew
+/// - `stack_tags`: The tag of each stack symbol, empty altogether unless the options have tags
+/// - `heap_tags`: The tag of each heap block, empty altogether unless the options have tags
+/// - `heap_groups`: The group of each heap block, empty altogether unless the
+///   [heap_order](crate::analyzer::AnalyzerOptions::heap_order) groups blocks
+/// - `heap_ids`: The id of each heap block, the pointer it was allocated for like `p`, qualified by the
+///   function the pointer is local to like `make::p`, followed by `#2`, `#3` and so on for the blocks
+///   allocated for the pointer after the first. The control block of a `std::shared_ptr` is
+///   `p (control block)`. `None` for blocks no statement allocated. Unlike its position the id of a block stays the same
+///   when the program is analyzed again, so notes are kept by it
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct CrossReference {
+    pub stack: Vec<SourceRef>,
+    pub heap: Vec<Option<SourceRef>>,
+    pub frames: Vec<ScopeReference>,
+    pub scope_exits: Vec<ScopeReference>,
+    pub lifetimes: Vec<Option<HeapLifetime>>,
+    pub steps: usize,
+    pub history: Vec<Vec<ValueChange>>,
+    pub copies: Vec<ValueCopy>,
+    pub moves: Vec<ValueMove>,
+    pub temporaries: Vec<Temporary>,
+    pub lossy_casts: Vec<LossyCast>,
+    pub thread_steps: Vec<ThreadStep>,
+    pub lock_events: Vec<LockEvent>,
+    pub data_races: Vec<DataRace>,
+    pub loop_iterations: Vec<LoopIteration>,
+    pub heap_growth: Vec<HeapGrowthEvent>,
+    pub stack_tags: Vec<Option<MemoryTag>>,
+    pub heap_tags: Vec<Option<MemoryTag>>,
+    pub heap_groups: Vec<Option<String>>,
+    pub heap_ids: Vec<Option<String>>,
+}
+
+/// Represents the events of a window of steps, so the timeline of a long program can be handed out in
+/// pieces instead of all at once
+///
+/// # Fields
+/// - `first_step`: The first step of the window
+/// - `last_step`: The last step of the window
+/// - `history`: The values each stack symbol took in the window, parallel to the stack like the
+///   [history](CrossReference::history) is
+/// - `copies`: The values copied in the window
+/// - `moves`: The blocks moved in the window
+/// - `lossy_casts`: The casts that lost part of their value in the window
+/// - `thread_steps`: The statements the spawned threads ran in the window
+/// - `lock_events`: The mutexes locked or unlocked in the window
+/// - `data_races`: The data races found in the window
+/// - `loop_iterations`: The iterations of loops that ended in the window
+/// - `heap_growth`: The times the heap grew in the window
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct TimelineChunk {
+    pub first_step: usize,
+    pub last_step: usize,
+    pub history: Vec<Vec<ValueChange>>,
+    pub copies: Vec<ValueCopy>,
+    pub moves: Vec<ValueMove>,
+    pub lossy_casts: Vec<LossyCast>,
+    pub thread_steps: Vec<ThreadStep>,
+    pub lock_events: Vec<LockEvent>,
+    pub data_races: Vec<DataRace>,
+    pub loop_iterations: Vec<LoopIteration>,
+    pub heap_growth: Vec<HeapGrowthEvent>,
+}
+
+/// Hands out the events taken from a cross reference one window of steps at a time, see
+/// [take_timeline](CrossReference::take_timeline)
+pub struct TimelineChunks {
+    steps_per_chunk: usize,
+    next_step: usize,
+    steps: usize,
+    history: Vec<Peekable<vec::IntoIter<ValueChange>>>,
+    copies: Peekable<vec::IntoIter<ValueCopy>>,
+    moves: Peekable<vec::IntoIter<ValueMove>>,
+    lossy_casts: Peekable<vec::IntoIter<LossyCast>>,
+    thread_steps: Peekable<vec::IntoIter<ThreadStep>>,
+    lock_events: Peekable<vec::IntoIter<LockEvent>>,
+    data_races: Peekable<vec::IntoIter<DataRace>>,
+    loop_iterations: Peekable<vec::IntoIter<LoopIteration>>,
+    heap_growth: Peekable<vec::IntoIter<HeapGrowthEvent>>,
+}
+
+impl CrossReference {
+    /// Takes the events stamped with a step out of the cross reference, to hand them out in windows of
+    /// steps, for programs whose timeline is too long to send in one piece
+    ///
+    /// The rest of the cross reference is left as it was, with the lists of events emptied
+    ///
+    /// # Arguments
+    /// - `steps_per_chunk`: How many steps each window spans, at least 1
+    ///
+    /// # Returns
+    /// - [TimelineChunks](crate::analyzer::TimelineChunks): The windows, in the order of their steps, none
+    ///   if the program took no steps
+    pub fn take_timeline(&mut self, steps_per_chunk: usize) -> TimelineChunks {
+        TimelineChunks {
+            steps_per_chunk: steps_per_chunk.max(1),
+            next_step: 0,
+            steps: self.steps,
+            history: std::mem::take(&mut self.history)
+                .into_iter()
+                .map(|values| by_step(values, |value| value.step))
+                .collect(),
+            copies: by_step(std::mem::take(&mut self.copies), |copy| copy.step),
+            moves: by_step(std::mem::take(&mut self.moves), |value_move| value_move.step),
+            lossy_casts: by_step(std::mem::take(&mut self.lossy_casts), |cast| cast.step),
+            thread_steps: by_step(std::mem::take(&mut self.thread_steps), |step| step.step),
+            lock_events: by_step(std::mem::take(&mut self.lock_events), |event| event.step),
+            data_races: by_step(std::mem::take(&mut self.data_races), |race| race.step),
+            loop_iterations: by_step(std::mem::take(&mut self.loop_iterations), |iteration| {
+                iteration.step
+            }),
+            heap_growth: by_step(std::mem::take(&mut self.heap_growth), |event| event.step),
+        }
+    }
+}
+
+impl Iterator for TimelineChunks {
+    type Item = TimelineChunk;
+
+    fn next(&mut self) -> Option<TimelineChunk> {
+        if self.next_step >= self.steps {
+            return None;
+        }
+
+        let first_step = self.next_step;
+        let last_step = (first_step + self.steps_per_chunk).min(self.steps) - 1;
+        self.next_step = last_step + 1;
+
+        Some(TimelineChunk {
+            first_step,
+            last_step,
+            history: self
+                .history
+                .iter_mut()
+                .map(|values| until(values, last_step, |value| value.step))
+                .collect(),
+            copies: until(&mut self.copies, last_step, |copy| copy.step),
+            moves: until(&mut self.moves, last_step, |value_move| value_move.step),
+            lossy_casts: until(&mut self.lossy_casts, last_step, |cast| cast.step),
+            thread_steps: until(&mut self.thread_steps, last_step, |step| step.step),
+            lock_events: until(&mut self.lock_events, last_step, |event| event.step),
+            data_races: until(&mut self.data_races, last_step, |race| race.step),
+            loop_iterations: until(&mut self.loop_iterations, last_step, |iteration| {
+                iteration.step
+            }),
+            heap_growth: until(&mut self.heap_growth, last_step, |event| event.step),
+        })
+    }
+}
+
+/// Sorts events by their step, keeping the order they were recorded in within a step
+fn by_step<T>(mut events: Vec<T>, step: impl Fn(&T) -> usize) -> Peekable<vec::IntoIter<T>> {
+    events.sort_by_key(step);
+    events.into_iter().peekable()
+}
+
+/// Takes the events up to and including `last_step`
+fn until<T>(
+    events: &mut Peekable<vec::IntoIter<T>>,
+    last_step: usize,
+    step: impl Fn(&T) -> usize,
+) -> Vec<T> {
+    std::iter::from_fn(|| events.next_if(|event| step(event) <= last_step)).collect()
+}
+
+/// Gives each heap block allocated by a statement its id, counting the blocks allocated for each owner
+/// in the order they were allocated
+///
+/// # Arguments
+/// - `heap`: The statement that allocated each heap block
+/// - `owners`: The pointer each heap block was allocated for, parallel to `heap`
+/// - `lifetimes`: The lifetime of each heap block, parallel to `heap`
+fn heap_ids(
+    heap: &[Option<SourceRef>],
+    owners: &[Option<String>],
+    lifetimes: &[Option<HeapLifetime>],
+) -> Vec<Option<String>> {
+    let mut allocated: Vec<usize> =
+        (0..heap.len()).filter(|&index| heap[index].is_some()).collect();
+    allocated.sort_by_key(|&index| lifetimes[index].as_ref().map(|lifetime| lifetime.allocated));
+
+    let mut counts: IndexMap<String, usize> = IndexMap::new();
+    let mut ids = vec![None; heap.len()];
+
+    for index in allocated {
+        let Some(source) = &heap[index] else {
+            continue;
+        };
+
+        let owner = match &owners[index] {
+            Some(owner) => owner.clone(),
+            None => format!("line {}", source.line),
+        };
+
+        let count = counts.entry(owner.clone()).or_default();
+        *count += 1;
+
+        ids[index] = Some(match count {
+            1 => owner,
+            count => format!("{}#{}", owner, count),
+        });
+    }
+
+    ids
+}
+
+/// Collects the statements behind the output while the program is analyzed
+///
+/// # Fields
+/// - `path`: The path to the statement being analyzed
+/// - `declarations`: The declarations of the symbols of each active frame, the top level first
+/// - `allocations`: The latest allocation of each heap block, by the block's starting position
+/// - `owners`: The pointer the latest allocation of each heap block was for, by the block's starting
+///   position
+/// - `frames`: The references of the frames pushed so far
+/// - `scope_exits`: The references of the blocks left so far
+/// - `lifetimes`: The latest lifetime of each heap block, by the block's starting position
+/// - `steps`: How many steps were taken so far, the current step is the last one
+/// - `history`: The values each symbol of the top level frame held so far
+/// - `copies`: The values copied between symbols so far
+/// - `moves`: The blocks moved between `std::unique_ptr`s so far
+/// - `temporaries`: The temporaries calls returned so far
+/// - `lossy_casts`: The casts that lost part of their value so far
+/// - `thread_steps`: The statements the spawned threads ran so far
+/// - `lock_events`: The mutexes locked or unlocked so far
+/// - `shared_blocks`: The threads that wrote each heap block so far, by the block's starting position
+/// - `access_counts`: The reads and writes of each heap block so far, by the block's starting position
+/// - `delete_forms`: The form of `delete` each block allocated with `new` or `new[]` has to be released
+///   with, by the block's starting position
+/// - `data_races`: The data races found so far
+/// - `loop_iterations`: The iterations of loops run so far
+/// - `heap_growth`: The times the heap grew so far
+/// - `warnings`: The writes out of bounds skipped, the misaligned casts, the overlapping copies and the
+///   strings copied past the end of a block so far
+#[derive(Debug, Default)]
+pub(crate) struct SourceMap {
+    pub(crate) path: Vec<usize>,
+    pub(crate) declarations: Vec<Vec<SourceRef>>,
+    pub(crate) allocations: IndexMap<usize, SourceRef>,
+    pub(crate) owners: IndexMap<usize, String>,
+    pub(crate) frames: Vec<ScopeReference>,
+    pub(crate) scope_exits: Vec<ScopeReference>,
+    pub(crate) lifetimes: IndexMap<usize, HeapLifetime>,
+    pub(crate) steps: usize,
+    pub(crate) history: Vec<Vec<ValueChange>>,
+    pub(crate) copies: Vec<ValueCopy>,
+    pub(crate) moves: Vec<ValueMove>,
+    pub(crate) temporaries: Vec<Temporary>,
+    pub(crate) lossy_casts: Vec<LossyCast>,
+    pub(crate) thread_steps: Vec<ThreadStep>,
+    pub(crate) lock_events: Vec<LockEvent>,
+    pub(crate) shared_blocks: IndexMap<usize, SharedBlock>,
+    pub(crate) access_counts: IndexMap<usize, AccessCount>,
+    pub(crate) delete_forms: IndexMap<usize, Release>,
+    pub(crate) data_races: Vec<DataRace>,
+    pub(crate) loop_iterations: Vec<LoopIteration>,
+    pub(crate) heap_growth: Vec<HeapGrowthEvent>,
+    pub(crate) warnings: Vec<Diagnostic>,
+}
+
+impl SourceMap {
+    /// Gets the declarations of the innermost active frame
+    pub(crate) fn frame_declarations(&mut self) -> &mut Vec<SourceRef> {
+        self.declarations.last_mut().expect("the top level frame is always active")
+    }
+
+    /// Records the current step as the allocation of the block starting at `pointer`
+    ///
+    /// # Arguments
+    /// - `pointer`: The starting position of the block
+    /// - `source`: The statement that allocated the block
+    /// - `owner`: The pointer the block was allocated for, qualified by the function it's local to
+    pub(crate) fn allocate(&mut self, pointer: usize, source: SourceRef, owner: String) {
+        // a block allocated where another one was is new to every thread
+        self.shared_blocks.shift_remove(&pointer);
+        self.access_counts.shift_remove(&pointer);
+        self.delete_forms.shift_remove(&pointer);
+        self.allocations.insert(pointer, source);
+        self.owners.insert(pointer, owner);
+        self.lifetimes.insert(
+            pointer,
+            HeapLifetime {
+                allocated: self.steps.saturating_sub(1),
+                released: None,
+            },
+        );
+    }
+
+    /// Ends the lifetime of every block that was freed or leaked since the last call at the current step,
+    /// and records the heap growing since then at the current step too
+    ///
+    /// # Arguments
+    /// - `memory`: The memory model the blocks live in
+    pub(crate) fn record_releases(&mut self, memory: &dyn MemoryModel) {
+        let step = self.steps.saturating_sub(1);
+
+        for growth in &memory.growth()[self.heap_growth.len()..] {
+            self.heap_growth.push(HeapGrowthEvent {
+                step,
+                growth: growth.clone(),
+            });
+        }
+
+        for (pointer, lifetime) in self.lifetimes.iter_mut() {
+            if lifetime.released.is_some() {
+                continue;
+            }
+
+            if let Some(HeapBlock {
+                block_state: HeapBlockState::Free | HeapBlockState::Leaked,
+                ..
+            }) = memory.block(*pointer)
+            {
+                lifetime.released = Some(step);
+            }
+        }
+    }
+
+    /// Records the value of every variable that changed at the current step
+    ///
+    /// # Arguments
+    /// - `symbols`: The symbols of the top level frame, in the order they were declared
+    pub(crate) fn record_values<'a>(&mut self, symbols: impl Iterator<Item = &'a Symbol>) {
+        let step = self.steps.saturating_sub(1);
+
+        for (index, symbol) in symbols.enumerate() {
+            if index == self.history.len() {
+                self.history.push(Vec::new());
+            }
+
+            let Symbol::Variable { value, .. } = symbol else {
+                continue;
+            };
+
+            let history = &mut self.history[index];
+
+            if history.last().is_none_or(|change| change.value != *value) {
+                history.push(ValueChange {
+                    step,
+                    value: value.clone(),
+                });
+            }
+        }
+    }
+
+    /// Records the copies a statement made at the current step
+    ///
+    /// # Arguments
+    /// - `copies`: The name copied from, the name copied to and the number of bytes of each copy, see
+    ///   [value_copies](crate::analyzer::helpers::value_copies)
+    /// - `source`: The statement that made the copies
+    pub(crate) fn record_copies(
+        &mut self,
+        copies: Vec<(Option<String>, Option<String>, usize)>,
+        source: &SourceRef,
+    ) {
+        let step = self.steps.saturating_sub(1);
+
+        for (from, to, bytes) in copies {
+            self.copies.push(ValueCopy {
+                step,
+                source: source.clone(),
+                from,
+                to,
+                bytes,
+            });
+        }
+    }
+
+    /// Records the move a statement made at the current step, if it made one
+    ///
+    /// # Arguments
+    /// - `moves`: The name moved from and the name moved to, see
+    ///   [value_moves](crate::analyzer::helpers::value_moves)
+    /// - `source`: The statement that made the move
+    pub(crate) fn record_moves(&mut self, moves: Option<(String, String)>, source: &SourceRef) {
+        if let Some((from, to)) = moves {
+            self.moves.push(ValueMove {
+                step: self.steps.saturating_sub(1),
+                source: source.clone(),
+                from,
+                to,
+            });
+        }
+    }
+
+    /// Records the mutex a statement locked or unlocked at the current step
+    ///
+    /// # Arguments
+    /// - `mutex`: The name of the mutex
+    /// - `thread`: The thread that locked or unlocked it
+    /// - `locked`: Whether the mutex was locked
+    /// - `source`: The statement that locked or unlocked it
+    pub(crate) fn record_lock(
+        &mut self,
+        mutex: String,
+        thread: usize,
+        locked: bool,
+        source: &SourceRef,
+    ) {
+        self.lock_events.push(LockEvent {
+            mutex,
+            thread,
+            step: self.steps.saturating_sub(1),
+            locked,
+            source: source.clone(),
+        });
+    }
+
+    /// Records the heap blocks a spawned thread wrote at the current step, a block is reported as a
+    /// data race once threads wrote it without a mutex held by every write
+    ///
+    /// # Arguments
+    /// - `blocks`: The starting positions of the blocks written, see
+    ///   [heap_accesses](crate::analyzer::mutex::heap_accesses)
+    /// - `thread`: The thread that wrote them
+    /// - `locks`: The mutexes the thread held
+    /// - `source`: The statement that wrote them
+    pub(crate) fn record_accesses(
+        &mut self,
+        blocks: Vec<usize>,
+        thread: usize,
+        locks: &[String],
+        source: &SourceRef,
+    ) {
+        let step = self.steps.saturating_sub(1);
+
+        for block in blocks {
+            let shared = self.shared_blocks.entry(block).or_insert_with(|| SharedBlock {
+                threads: Vec::new(),
+                locks: locks.to_vec(),
+                raced: false,
+            });
+
+            if !shared.threads.contains(&thread) {
+                shared.threads.push(thread);
+            }
+
+            shared.locks.retain(|lock| locks.contains(lock));
+
+            if shared.threads.len() < 2 || !shared.locks.is_empty() || shared.raced {
+                continue;
+            }
+
+            shared.raced = true;
+
+            self.data_races.push(DataRace {
+                block,
+                threads: shared.threads.clone(),
+                step,
+                source: source.clone(),
+            });
+        }
+    }
+
+    /// Records a read of the heap block starting at `block` at the current step
+    ///
+    /// # Arguments
+    /// - `block`: The starting position of the block read, see
+    ///   [address_block](crate::analyzer::heatmap::address_block)
+    pub(crate) fn record_read(&mut self, block: usize) {
+        self.access_counts.entry(block).or_default().reads += 1;
+    }
+
+    /// Records a write to the heap block starting at `block` at the current step
+    ///
+    /// # Arguments
+    /// - `block`: The starting position of the block written, see
+    ///   [heap_write](crate::analyzer::heatmap::heap_write)
+    pub(crate) fn record_write(&mut self, block: usize) {
+        self.access_counts.entry(block).or_default().writes += 1;
+    }
+
+    /// Records the end of an iteration of a loop at the current step
+    ///
+    /// # Arguments
+    /// - `source`: The loop
+    /// - `iteration`: The iteration that ended, numbered from 1
+    /// - `memory`: The memory model the loop runs in
+    pub(crate) fn record_iteration(
+        &mut self,
+        source: &SourceRef,
+        iteration: usize,
+        memory: &dyn MemoryModel,
+    ) {
+        self.loop_iterations.push(LoopIteration {
+            source: source.clone(),
+            iteration,
+            step: self.steps.saturating_sub(1),
+            heap_in_use: memory.in_use(),
+        });
+    }
+
+    /// Gets how many times each block of the final heap was read and written since it was allocated
+    ///
+    /// # Arguments
+    /// - `heap`: The heap blocks that are output
+    ///
+    /// # Returns
+    /// - `Vec<AccessCount>`: The reads and writes of each block, none for blocks no statement allocated
+    pub(crate) fn heatmap(&self, heap: &[HeapBlock]) -> Vec<AccessCount> {
+        heap.iter()
+            .map(|block| match block.block_state {
+                HeapBlockState::Unallocated | HeapBlockState::Padding => AccessCount::default(),
+                _ => self.access_counts.get(&block.pointer).cloned().unwrap_or_default(),
+            })
+            .collect()
+    }
+
+    /// Builds the cross reference for the final heap
+    ///
+    /// # Arguments
+    /// - `heap`: The heap blocks that are output
+    ///
+    /// # Returns
+    /// - [CrossReference](crate::analyzer::CrossReference): The finished cross reference
+    pub(crate) fn finish(mut self, heap: &[HeapBlock]) -> CrossReference {
+        // only blocks that were allocated at some point were allocated by a statement
+        let allocated = heap.iter().map(|block| match block.block_state {
+            HeapBlockState::Unallocated | HeapBlockState::Padding => None,
+            _ => Some(block.pointer),
+        });
+
+        let lifetimes: Vec<Option<HeapLifetime>> = allocated
+            .clone()
+            .map(|pointer| pointer.and_then(|pointer| self.lifetimes.get(&pointer).cloned()))
+            .collect();
+
+        let owners: Vec<Option<String>> = allocated
+            .clone()
+            .map(|pointer| pointer.and_then(|pointer| self.owners.get(&pointer).cloned()))
+            .collect();
+
+        let heap: Vec<Option<SourceRef>> = allocated
+            .map(|pointer| pointer.and_then(|pointer| self.allocations.get(&pointer).cloned()))
+            .collect();
+        let heap_ids = heap_ids(&heap, &owners, &lifetimes);
+
+        CrossReference {
+            stack: self.declarations.pop().unwrap_or_default(),
+            heap,
+            frames: self.frames,
+            scope_exits: self.scope_exits,
+            lifetimes,
+            steps: self.steps,
+            history: self.history,
+            copies: self.copies,
+            moves: self.moves,
+            temporaries: self.temporaries,
+            lossy_casts: self.lossy_casts,
+            thread_steps: self.thread_steps,
+            lock_events: self.lock_events,
+            data_races: self.data_races,
+            loop_iterations: self.loop_iterations,
+            heap_growth: self.heap_growth,
+            stack_tags: Vec::new(),
+            heap_tags: Vec::new(),
+            heap_groups: Vec::new(),
+            heap_ids,
+        }
+    }
+}
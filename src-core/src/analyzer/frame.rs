@@ -0,0 +1,202 @@
+//! Functions, the call stack they run on and the blocks they leave
+
+use std::collections::VecDeque;
+
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use crate::error::{Error::AnalyzerError, Result};
+use crate::parser::ast::{Parameter, Statement};
+use crate::stack::NESTING_LIMIT;
+
+use super::class::Class;
+use super::cross_reference::{SourceMap, SourceRef};
+use super::r#type::Type;
+use super::union::UnionMember;
+use super::Symbol;
+
+/// How deep calls can nest before the stack overflows when the options don't set a limit, so infinite
+/// recursion ends in an error instead of hanging
+pub(crate) const MAX_CALL_DEPTH: usize = 64;
+
+/// The deepest the options can let calls nest, every call nests at least one block so it stays well
+/// within [NESTING_LIMIT]
+pub(crate) const CALL_DEPTH_LIMIT: usize = 128;
+
+/// How many times a loop can run before the analyzer gives up, so a loop it can't bound ends in an error
+/// instead of hanging
+pub(crate) const MAX_LOOP_ITERATIONS: usize = 1000;
+
+/// Represents the stack frame of a function call
+///
+/// Every call pushes a frame holding its parameters and locals, returning pops it and its symbols go out
+/// of scope. Frames are reported in the order the calls were made
+///
+/// # Fields
+/// - `function`: The name of the function that was called
+/// - `line`: The line of the call
+/// - `depth`: How many calls deep the frame is, calls made from the top level are at depth 1
+/// - `symbols`: The parameters and locals of the frame as they were when the function returned
+/// - `addresses`: The address of each symbol, parallel to `symbols`. The frame sits below the frames of
+///   the calls it was made from, the frames of spawned threads aren't laid out and have none
+/// - `registers`: The parameters passed in registers instead, empty unless the analyzer runs with the
+///   [Registers](crate::analyzer::CallingConvention::Registers) calling convention
+/// - `thread`: The thread whose stack the frame is on, `0` for the main thread
+///
+/// Every spawned thread has a frame of its own at depth 0, named `thread_spawn`, holding its locals
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct StackFrame {
+    pub(crate) function: String,
+    pub(crate) line: usize,
+    pub(crate) depth: usize,
+    pub(crate) symbols: Vec<Symbol>,
+    pub(crate) addresses: Vec<usize>,
+    pub(crate) registers: Vec<RegisterArgument>,
+    pub(crate) thread: usize,
+}
+
+/// Represents an argument passed in a register rather than in the callee's stack frame
+///
+/// # Fields
+/// - `register`: The name of the register
+/// - `symbol`: The parameter the argument was passed as, as it was when the function returned
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct RegisterArgument {
+    pub(crate) register: String,
+    pub(crate) symbol: Symbol,
+}
+
+/// Represents a block `{ ... }` being left, popping the locals declared in it off the stack
+///
+/// Scope exits are reported in the order the blocks ended, so inner blocks come before the blocks
+/// around them
+///
+/// # Fields
+/// - `function`: The function whose frame the block ran in, `None` at the top level
+/// - `line`: The line of the closing `}`
+/// - `depth`: How many blocks deep the block is within its function, outermost blocks are at depth 1
+/// - `symbols`: The locals that were popped, as they were when the block ended
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct ScopeExit {
+    pub(crate) function: Option<String>,
+    pub(crate) line: usize,
+    pub(crate) depth: usize,
+    pub(crate) symbols: Vec<Symbol>,
+}
+
+/// Represents a function defined in the program
+///
+/// # Fields
+/// - `return_type`: The type the function returns, `None` for `void` functions
+/// - `parameters`: The parameters of the function
+/// - `body`: The statements of the function
+/// - `source`: The definition of the function
+#[derive(Debug, Clone)]
+pub(crate) struct Function {
+    pub(crate) return_type: Option<Type>,
+    pub(crate) parameters: Vec<Parameter>,
+    pub(crate) body: Vec<Statement>,
+    pub(crate) source: SourceRef,
+}
+
+/// Represents a thread spawned with `thread_spawn { ... }`, which runs once the main thread reaches the
+/// end of the program
+///
+/// # Fields
+/// - `id`: The number of the thread, threads are numbered from 1 in the order they were spawned
+/// - `statements`: The statements the thread has left to run, with their index in its body
+/// - `source`: The `thread_spawn` statement
+/// - `symbols`: The locals on the stack of the thread so far
+/// - `declarations`: The declarations of the locals so far
+/// - `frame`: The index of the frame the stack of the thread is reported as
+#[derive(Debug)]
+pub(crate) struct Thread {
+    pub(crate) id: usize,
+    pub(crate) statements: VecDeque<(usize, Statement)>,
+    pub(crate) source: SourceRef,
+    pub(crate) symbols: IndexMap<String, Symbol>,
+    pub(crate) declarations: Vec<SourceRef>,
+    pub(crate) frame: usize,
+}
+
+/// Keeps track of the functions of the program and the calls made to them
+///
+/// # Fields
+/// - `functions`: The functions defined so far
+/// - `unions`: The unions defined so far, with the members their instances start with
+/// - `classes`: The classes defined so far, their methods are kept with the functions as `Class::method`
+/// - `active`: The names of the functions currently being executed, innermost last
+/// - `frames`: Every frame pushed so far
+/// - `callers`: The symbols of the frames of the calls being executed, outermost first, which the frame
+///   of the innermost call is laid out below
+/// - `scope_exits`: Every block left so far, in any frame
+/// - `sources`: The statements behind the symbols, blocks, frames and scope exits so far
+/// - `returned`: The value the innermost call returned, until the caller picks it up
+/// - `threads`: The threads spawned so far that haven't finished
+/// - `thread`: The thread running, `0` for the main thread
+/// - `nesting`: How many blocks and operands are being analyzed, one inside the other, across every call
+#[derive(Debug, Default)]
+pub(crate) struct CallStack {
+    pub(crate) functions: IndexMap<String, Function>,
+    pub(crate) unions: IndexMap<String, Vec<UnionMember>>,
+    pub(crate) classes: IndexMap<String, Class>,
+    pub(crate) active: Vec<String>,
+    pub(crate) frames: Vec<StackFrame>,
+    pub(crate) callers: Vec<IndexMap<String, Symbol>>,
+    pub(crate) scope_exits: Vec<ScopeExit>,
+    pub(crate) sources: SourceMap,
+    pub(crate) returned: Option<String>,
+    pub(crate) threads: Vec<Thread>,
+    pub(crate) thread: usize,
+    pub(crate) nesting: usize,
+}
+
+impl CallStack {
+    /// Gets the name a heap block allocated for the pointer with the given name is owned by, the name
+    /// qualified by the function the pointer is local to like `make::p`, unqualified at the top level
+    pub(crate) fn owner(&self, name: &str) -> String {
+        match self.active.last() {
+            Some(function) => format!("{}::{}", function, name),
+            None => name.to_string(),
+        }
+    }
+
+    /// Analyzes a block, an operand or a call nested in the one being analyzed, failing with a stack
+    /// overflow instead of recursing past [NESTING_LIMIT] levels
+    ///
+    /// # Arguments
+    /// - `line`: The line of the nested code
+    /// - `column`: The column of the nested code
+    /// - `analyze`: Analyzes the nested code with the call stack
+    ///
+    /// # Returns
+    /// - [Result](crate::error::Result): A result containing either:
+    ///    - `T`: What the analysis returned
+    ///    - [Error](crate::error::Error): An error if nesting goes too deep or the analysis fails
+    pub(crate) fn nested<T>(
+        &mut self,
+        line: usize,
+        column: usize,
+        analyze: impl FnOnce(&mut CallStack) -> Result<T>,
+    ) -> Result<T> {
+        if self.nesting >= NESTING_LIMIT {
+            return Err(AnalyzerError(
+                format!(
+                    "Stack overflow, blocks, expressions and calls nest deeper than {} levels",
+                    NESTING_LIMIT
+                ),
+                line,
+                column,
+            ));
+        }
+
+        self.nesting += 1;
+        let result = analyze(self);
+        self.nesting -= 1;
+
+        result
+    }
+}
@@ -2,17 +2,46 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::fmt;
 
 use crate::error::Result;
 
 /// Represents the state of a block of memory in the heap
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
 pub(crate) enum HeapBlockState {
     Unallocated,
     Allocated,
     Free,
     Leaked,
+    Padding,
 }
+
+/// Represents why the allocator left bytes of a block unused
+///
+/// - `Alignment`: The block was rounded up so the next block starts on an `alignment` byte boundary
+/// - `MinimumBlockSize`: The allocator never hands out blocks smaller than `minimum_size` bytes
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub(crate) enum GapReason {
+    #[serde(rename_all = "camelCase")]
+    Alignment { alignment: usize },
+    #[serde(rename_all = "camelCase")]
+    MinimumBlockSize { minimum_size: usize },
+}
+
+impl fmt::Display for GapReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GapReason::Alignment { alignment } => write!(f, "alignment to {}", alignment),
+            GapReason::MinimumBlockSize { minimum_size } => {
+                write!(f, "allocator minimum block size of {} bytes", minimum_size)
+            }
+        }
+    }
+}
+
 /// Represents a block of memory in the heap
 ///
 /// # Fields
@@ -20,7 +49,13 @@ pub(crate) enum HeapBlockState {
 /// - `size`: The size of the block in bytes
 /// - `metadata`: A string representing additional data associated with the block
 /// - `pointer`: The starting position of the block in the heap
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// - `gap_reason`: Why the block exists, only set for [Padding](HeapBlockState::Padding) blocks
+/// - `moved`: Where the contents went when the block was reallocated somewhere else, only set for
+///   [Free](HeapBlockState::Free) blocks
+/// - `members`: The fields of the object the block holds, only set for blocks allocated with `new` of a
+///   class, or the elements written through a pointer like `p[2] = 7`
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
 pub struct HeapBlock {
     pub(crate) block_state: HeapBlockState,
     pub(crate) current_pointer_identifier: Option<String>,
@@ -28,6 +63,106 @@ pub struct HeapBlock {
     pub(crate) size: usize,
     pub(crate) metadata: String,
     pub(crate) pointer: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) gap_reason: Option<GapReason>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) moved: Option<CopyEvent>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) members: Option<Vec<BlockMember>>,
+}
+
+/// Represents a field of the object a heap block holds
+///
+/// # Fields
+/// - `name`: The name of the field
+/// - `offset`: Where the field starts, in bytes from the start of the block
+/// - `size`: The size of the field in bytes
+/// - `value`: The value of the field, `None` until it is written and for pointer fields
+/// - `points_to`: The starting position in the heap of the block a pointer field points to, `None` for
+///   fields holding values and pointers that don't point into the heap
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct BlockMember {
+    pub name: String,
+    pub offset: usize,
+    pub size: usize,
+    pub value: Option<String>,
+    pub points_to: Option<usize>,
+}
+
+/// Represents the contents of a block being copied into a new block when it is reallocated
+///
+/// # Fields
+/// - `from`: The starting position of the old block in the heap
+/// - `to`: The starting position of the new block in the heap
+/// - `bytes`: The number of bytes copied
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub(crate) struct CopyEvent {
+    pub(crate) from: usize,
+    pub(crate) to: usize,
+    pub(crate) bytes: usize,
+}
+
+/// Represents why the heap grew
+///
+/// - `NoFreeRange`: No free range could hold a block of `size` bytes
+/// - `PastTheEnd`: A block of `size` bytes kept where an earlier run placed it reached past the end of the
+///   heap
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum GrowthReason {
+    NoFreeRange { size: usize },
+    PastTheEnd { size: usize },
+}
+
+/// Represents the heap growing to make room for a block
+///
+/// # Fields
+/// - `old_size`: The size of the heap in bytes before it grew
+/// - `new_size`: The size of the heap in bytes after it grew, the bytes past `old_size` are new
+/// - `reason`: Why the heap grew
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct HeapGrowth {
+    pub old_size: usize,
+    pub new_size: usize,
+    pub reason: GrowthReason,
+}
+
+/// Represents a range of the heap the allocator can still hand out, as an entry of its free list
+///
+/// # Fields
+/// - `start`: The starting position of the range in the heap
+/// - `end`: The last position of the range in the heap
+/// - `size`: The size of the range in bytes
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct FreeRange {
+    pub start: usize,
+    pub end: usize,
+    pub size: usize,
+}
+
+/// Measures how fragmented the free memory is, as the share of the free bytes outside the largest free
+/// range
+///
+/// # Arguments
+/// - `free_list`: The free ranges of the heap
+///
+/// # Returns
+/// - `f64`: From `0` when the free bytes are all in one range, or there are none, to almost `1` when they
+///   are scattered in small ranges
+pub(crate) fn fragmentation(free_list: &[FreeRange]) -> f64 {
+    let free: usize = free_list.iter().map(|range| range.size).sum();
+    let largest = free_list.iter().map(|range| range.size).max().unwrap_or(0);
+
+    if free == 0 {
+        return 0.0;
+    }
+
+    1.0 - largest as f64 / free as f64
 }
 
 /// Represents a heap allocator.
@@ -67,6 +202,9 @@ impl HeapAllocator {
                     size: 0,
                     metadata: "".to_string(),
                     pointer: usize::MAX,
+                    gap_reason: None,
+                    moved: None,
+                    members: None,
                 };
                 size
             ],
@@ -133,6 +271,9 @@ impl HeapAllocator {
                 size: block_to_write.size,
                 metadata: block_to_write.metadata.clone(),
                 pointer,
+                gap_reason: None,
+                moved: None,
+                members: block_to_write.members.clone(),
             };
         }
 
@@ -166,6 +307,9 @@ impl HeapAllocator {
                 size: value_size,
                 metadata: "".to_string(),
                 pointer: ptr,
+                gap_reason: None,
+                moved: None,
+                members: None,
             },
         )?;
 
@@ -192,6 +336,9 @@ impl HeapAllocator {
                 size,
                 metadata: "Free Block".to_string(),
                 pointer: pointer,
+                gap_reason: None,
+                moved: None,
+                members: None,
             };
         }
 
@@ -312,6 +459,9 @@ impl HeapAllocator {
                 size,
                 metadata: "Leaked Block".to_string(),
                 pointer: pointer,
+                gap_reason: None,
+                moved: None,
+                members: None,
             };
         }
     }
@@ -0,0 +1,97 @@
+//! Orders the heap blocks for the layout the visualization draws them in, so the frontend doesn't have
+//! to rebuild what the allocator knows to sort or group them
+
+use indexmap::IndexMap;
+
+use super::heap_allocator::HeapBlockState;
+use super::options::HeapOrder;
+use super::result::AnalysisResult;
+
+/// Puts the heap blocks of a result in the given order, along with everything in it that is listed per
+/// block
+///
+/// Blocks that compare equal keep their order in the heap, so blocks within a group are sorted by address
+///
+/// # Arguments
+/// - `result`: The result of the analysis, its heap in address order, the order the allocator returns
+///   the blocks in. The groups of grouped orders are added to its cross reference
+/// - `order`: The order to put the blocks in
+pub(crate) fn order_heap(result: &mut AnalysisResult, order: HeapOrder) {
+    let AnalysisResult {
+        heap,
+        heatmap,
+        cross_reference,
+        ..
+    } = result;
+
+    let groups: Vec<Option<String>> = match order {
+        HeapOrder::Address | HeapOrder::Allocation => Vec::new(),
+
+        // a freed block no longer has an owner
+        HeapOrder::Owner => heap
+            .iter()
+            .map(|block| match block.block_state {
+                HeapBlockState::Allocated | HeapBlockState::Leaked => {
+                    block.current_pointer_identifier.clone()
+                }
+                _ => None,
+            })
+            .collect(),
+
+        HeapOrder::Site => cross_reference
+            .heap
+            .iter()
+            .map(|source| source.as_ref().map(|source| format!("line {}", source.line)))
+            .collect(),
+    };
+
+    let mut indices: Vec<usize> = (0..heap.len()).collect();
+
+    match order {
+        HeapOrder::Address => return,
+
+        HeapOrder::Allocation => indices.sort_by_key(|&index| {
+            let allocated =
+                cross_reference.lifetimes[index].as_ref().map(|lifetime| lifetime.allocated);
+            (allocated.is_none(), allocated)
+        }),
+
+        HeapOrder::Owner => {
+            // owners are ranked by where their first block is
+            let mut ranks = IndexMap::new();
+
+            for group in groups.iter().flatten() {
+                let rank = ranks.len();
+                ranks.entry(group).or_insert(rank);
+            }
+
+            indices.sort_by_key(|&index| {
+                groups[index].as_ref().map_or(usize::MAX, |group| ranks[group])
+            });
+        }
+
+        HeapOrder::Site => indices.sort_by_key(|&index| {
+            let line = cross_reference.heap[index].as_ref().map(|source| source.line);
+            (line.is_none(), line)
+        }),
+    }
+
+    cross_reference.heap = reorder(&cross_reference.heap, &indices);
+    cross_reference.lifetimes = reorder(&cross_reference.lifetimes, &indices);
+    cross_reference.heap_ids = reorder(&cross_reference.heap_ids, &indices);
+    *heatmap = reorder(heatmap, &indices);
+    // orders that don't group leave the groups empty, like the tags of code that isn't tagged
+    if !groups.is_empty() {
+        cross_reference.heap_groups = reorder(&groups, &indices);
+    }
+
+    if !cross_reference.heap_tags.is_empty() {
+        cross_reference.heap_tags = reorder(&cross_reference.heap_tags, &indices);
+    }
+
+    *heap = reorder(heap, &indices);
+}
+
+fn reorder<T: Clone>(items: &[T], indices: &[usize]) -> Vec<T> {
+    indices.iter().map(|&index| items[index].clone()).collect()
+}
@@ -0,0 +1,130 @@
+//! Counts how often the program reads and writes each heap block, so the visualization can shade the
+//! blocks it touches the most, like the element a loop keeps writing
+
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use crate::parser::ast::{Expr, Statement};
+
+use super::memory_model::MemoryModel;
+use super::raw_memory::{self, Location};
+use super::Symbol;
+
+/// Represents how many times a heap block was read and written
+///
+/// # Fields
+/// - `reads`: How many times the block was read, like `peek(p)`, `memcpy(q, p, 8);` or `strlen(s)`
+/// - `writes`: How many times the block was written, like `*p = 3;`, `p->x = 3;`, `p[2] = 7;`,
+///   `poke(p, 3);`, `memset(p, 0, 8);` or `strcpy(s, "hi");`
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct AccessCount {
+    pub reads: usize,
+    pub writes: usize,
+}
+
+/// Gets the heap block a statement reads as a whole, like the block `memcpy(q, p, 8);` or
+/// `strcpy(s, t);` copies from
+///
+/// # Arguments
+/// - `statement`: The statement
+/// - `symbols`: A reference to the symbol table
+///
+/// # Returns
+/// - `Option<usize>`: The starting position of the block the statement reads, if it reads one
+pub(crate) fn heap_read(
+    statement: &Statement,
+    symbols: &IndexMap<String, Symbol>,
+) -> Option<usize> {
+    match statement {
+        Statement::Memcpy { source, .. } => pointer_block(source, symbols),
+        Statement::Strcpy { source, .. } => match source.as_ref() {
+            Expr::Ident(source) => pointer_block(source, symbols),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Gets the heap block a statement writes, like `*p = 3;`, `p->x = 3;`, `p[2] = 7;`, `poke(p, 3);`,
+/// `memset(p, 0, 8);` or `strcpy(s, "hi");`. A `strcpy` past the end of the block only counts as
+/// writing the block it was copied into
+///
+/// Unlike [heap_accesses](crate::analyzer::mutex::heap_accesses) freeing a block isn't a write, its
+/// values are left as they were
+///
+/// # Arguments
+/// - `statement`: The statement
+/// - `symbols`: A reference to the symbol table
+/// - `memory`: A reference to the `MemoryModel` the statement is analyzed in
+///
+/// # Returns
+/// - `Option<usize>`: The starting position of the block the statement writes, if it writes one
+pub(crate) fn heap_write(
+    statement: &Statement,
+    symbols: &IndexMap<String, Symbol>,
+    memory: &dyn MemoryModel,
+) -> Option<usize> {
+    match statement {
+        Statement::Deref { pointer_name, .. }
+        | Statement::PointerMemberAssignment { pointer_name, .. }
+        | Statement::ArrayAssignment {
+            var_name: pointer_name,
+            ..
+        }
+        | Statement::Memset {
+            target: pointer_name,
+            ..
+        }
+        | Statement::Memcpy {
+            destination: pointer_name,
+            ..
+        }
+        | Statement::Strcpy {
+            destination: pointer_name,
+            ..
+        } => pointer_block(pointer_name, symbols),
+        Statement::Poke { address, .. } => address_block(address, symbols, memory),
+        _ => None,
+    }
+}
+
+/// Gets the starting position of the heap block a pointer points into
+pub(crate) fn pointer_block(
+    pointer_name: &str,
+    symbols: &IndexMap<String, Symbol>,
+) -> Option<usize> {
+    match symbols.get(pointer_name) {
+        Some(Symbol::Pointer {
+            heap_pointer: Some(heap_pointer),
+            offset,
+            ..
+        }) => Some(heap_pointer - offset),
+        _ => None,
+    }
+}
+
+/// Gets the heap block a raw address is in, like the address `peek` reads
+///
+/// # Arguments
+/// - `address`: The expression giving the address
+/// - `symbols`: A reference to the symbol table
+/// - `memory`: A reference to the `MemoryModel` the address is in
+///
+/// # Returns
+/// - `Option<usize>`: The starting position of the block, `None` if the address is on the stack or
+///   can't be accessed
+pub(crate) fn address_block(
+    address: &Expr,
+    symbols: &IndexMap<String, Symbol>,
+    memory: &dyn MemoryModel,
+) -> Option<usize> {
+    // an address that can't be accessed is reported by the statement using it
+    let address = raw_memory::address(address, symbols, 0, 0).ok()?;
+
+    // heap addresses are resolved the same wherever the frames are
+    match raw_memory::resolve_address(address, symbols, &[], memory, 0, 0).ok()? {
+        Location::Block { pointer, .. } => Some(pointer),
+        Location::Variable { .. } => None,
+    }
+}
@@ -1,13 +1,28 @@
 //! Helper functions that are used by the analyzer module
 
+use std::cmp::Ordering;
+
 use indexmap::IndexMap;
 
 use crate::{
-    error::{Error::AnalyzerError, Result},
-    parser::ast::{self, Expr},
+    diagnostics::{Diagnostic, Severity},
+    error::{
+        Error::{self, AnalyzerError},
+        Result,
+    },
+    export::format_address,
+    lexer::token::TokenKind,
+    parser::ast::{self, Expr, Statement},
 };
 
-use super::{r#type::Type, Symbol};
+use super::{
+    address_space::HEAP_BASE_ADDRESS,
+    class::{block_members, object_alignment, public_field, ClassField},
+    heap_allocator::{HeapBlock, HeapBlockState},
+    memory_model::MemoryModel,
+    r#type::Type,
+    AllocationType, Symbol, UnionMember,
+};
 
 /// Validates a variable assignment.
 ///
@@ -33,7 +48,7 @@ pub(crate) fn validate_variable_assignment(
     line: usize,
     var_ident_column: usize,
 ) -> Result<Option<String>> {
-    match *value {
+    match fold_expression(*value, symbols, line, var_ident_column)? {
         ast::Expr::Literal(lit) => {
             if !var_type.is_correct_literal(&lit) {
                 return Err(AnalyzerError(
@@ -46,7 +61,19 @@ pub(crate) fn validate_variable_assignment(
         }
         ast::Expr::Ident(ident_name) => {
             if let Some(symbol) = symbols.get(&ident_name) {
-                if let Symbol::Variable { value, .. } = symbol {
+                if let Symbol::Variable { value, vtype, .. } = symbol {
+                    // numbers convert into each other, but not into strings
+                    if (*vtype == Type::String) != (*var_type == Type::String) {
+                        return Err(AnalyzerError(
+                            format!(
+                                "Cannot assign `{}` to variable `{}` (incorrect type)",
+                                ident_name, var_name
+                            ),
+                            line,
+                            var_ident_column,
+                        ));
+                    }
+
                     if let Some(value) = value {
                         return Ok(Some(value.clone()));
                     } else {
@@ -79,14 +106,676 @@ pub(crate) fn validate_variable_assignment(
     }
 }
 
+/// Checks that a value assigned to an integer variable fits in its type
+///
+/// # Arguments
+/// - `var_type`: The type of the variable
+/// - `value`: The value assigned to the variable, `None` if it isn't initialized
+/// - `var_name`: The name of the variable
+/// - `pointer_size`: The size of a pointer in bytes, which `long` is as wide as
+/// - `line`: The line of the assignment
+/// - `column`: The column errors point at
+///
+/// # Returns
+/// - `Result<()>`: A result containing either:
+///   - `Ok(())` if the value fits or the variable isn't an integer
+///   - [AnalyzerError](crate::error::Error::AnalyzerError): returns an error if the value doesn't fit
+pub(crate) fn check_integer_range(
+    var_type: &Type,
+    value: Option<&str>,
+    var_name: &str,
+    pointer_size: usize,
+    line: usize,
+    column: usize,
+) -> Result<()> {
+    let (Some((min, max)), Some(value)) = (var_type.integer_range(pointer_size), value) else {
+        return Ok(());
+    };
+
+    match value.parse::<i128>() {
+        Ok(number) if number < min || number > max => Err(AnalyzerError(
+            format!(
+                "`{}` doesn't fit in `{}` variable `{}`, which holds {} to {}",
+                value, var_type, var_name, min, max
+            ),
+            line,
+            column,
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Folds the parts of an expression that are known without running the program, like `sizeof` and
+/// arithmetic, into literals
+///
+/// # Arguments
+/// - `expr`: The expression to fold
+/// - `symbols`: A reference to the symbol table
+/// - `line`: The line of the expression
+/// - `column`: The column errors point at
+///
+/// # Returns
+/// - `Result<Expr>`: A result containing either:
+///   - [Expr](crate::parser::ast::Expr): The folded expression
+///   - [AnalyzerError](crate::error::Error::AnalyzerError): returns an error if the expression can't be folded
+pub(crate) fn fold_expression(
+    expr: Expr,
+    symbols: &IndexMap<String, Symbol>,
+    line: usize,
+    column: usize,
+) -> Result<Expr> {
+    match expr {
+        Expr::SizeofType(_)
+        | Expr::Sizeof(_)
+        | Expr::PrefixOp { .. }
+        | Expr::InfixOp { .. }
+        | Expr::Member { .. } => {
+            Ok(Expr::Literal(evaluate_expression(&expr, symbols, line, column)?))
+        }
+        expr => Ok(expr),
+    }
+}
+
+/// Evaluates an expression to a literal, reading variables from the symbol table
+///
+/// Chars and bools are promoted to integers in arithmetic, and integers to floating point values when
+/// mixed with them, like they are in C++
+pub(crate) fn evaluate_expression(
+    expr: &Expr,
+    symbols: &IndexMap<String, Symbol>,
+    line: usize,
+    column: usize,
+) -> Result<ast::Lit> {
+    match expr {
+        Expr::Literal(lit) => Ok(lit.clone()),
+
+        Expr::Ident(name) => match symbols.get(name) {
+            Some(Symbol::Variable {
+                vtype,
+                value: Some(value),
+                ..
+            }) => variable_literal(vtype, value)
+                .ok_or_else(|| AnalyzerError(format!("Cannot evaluate `{}`", name), line, column)),
+
+            Some(Symbol::Variable { value: None, .. }) => {
+                Err(AnalyzerError(format!("Variable `{}` not initialized!", name), line, column))
+            }
+
+            Some(_) => Err(AnalyzerError(
+                format!("Cannot use `{}` in arithmetic, it isn't a variable", name),
+                line,
+                column,
+            )),
+
+            None => Err(AnalyzerError(format!("Variable `{}` not found!", name), line, column)),
+        },
+
+        Expr::Member { var_name, member } => match symbols.get(var_name) {
+            Some(Symbol::Union { members, .. }) => {
+                match members.iter().find(|other| other.name == *member) {
+                    Some(UnionMember {
+                        mtype,
+                        value: Some(value),
+                        ..
+                    }) => variable_literal(mtype, value).ok_or_else(|| {
+                        AnalyzerError(format!("Cannot evaluate `{}`", expr), line, column)
+                    }),
+
+                    Some(_) => Err(AnalyzerError(
+                        format!("Union `{}` not initialized!", var_name),
+                        line,
+                        column,
+                    )),
+
+                    None => Err(AnalyzerError(
+                        format!("Member `{}` not found in union `{}`!", member, var_name),
+                        line,
+                        column,
+                    )),
+                }
+            }
+
+            Some(Symbol::Object {
+                class_name, fields, ..
+            }) => match &fields[public_field(fields, class_name, member, line, column)?] {
+                ClassField {
+                    ftype,
+                    value: Some(value),
+                    ..
+                } => variable_literal(ftype, value).ok_or_else(|| {
+                    AnalyzerError(format!("Cannot evaluate `{}`", expr), line, column)
+                }),
+
+                _ => Err(AnalyzerError(
+                    format!("Field `{}` of `{}` not initialized!", member, var_name),
+                    line,
+                    column,
+                )),
+            },
+
+            Some(_) => Err(AnalyzerError(
+                format!(
+                    "Cannot read member `{}` of `{}`, it isn't a union or an object",
+                    member, var_name
+                ),
+                line,
+                column,
+            )),
+
+            None => Err(AnalyzerError(format!("Variable `{}` not found!", var_name), line, column)),
+        },
+
+        Expr::SizeofType(size_type) => {
+            Ok(ast::Lit::Int(Type::from_token(*size_type)?.get_size() as i128))
+        }
+
+        Expr::Sizeof(expr) => {
+            Ok(ast::Lit::Int(size_of_expression(expr, symbols, line, column)? as i128))
+        }
+
+        Expr::PrefixOp {
+            op: TokenKind::Minus,
+            expr,
+        } => match promote(evaluate_expression(expr, symbols, line, column)?) {
+            ast::Lit::Int(value) => value
+                .checked_neg()
+                .map(ast::Lit::Int)
+                .ok_or_else(|| AnalyzerError(format!("`{}` overflows", expr), line, column)),
+            ast::Lit::Float(value) => Ok(ast::Lit::Float(-value)),
+            _ => Err(AnalyzerError(format!("Cannot negate `{}`", expr), line, column)),
+        },
+
+        // `+x` promotes `x` like arithmetic does, so `+c` is the code of the char `c`
+        Expr::PrefixOp {
+            op: TokenKind::Plus,
+            expr,
+        } => match promote(evaluate_expression(expr, symbols, line, column)?) {
+            value @ (ast::Lit::Int(_) | ast::Lit::Float(_)) => Ok(value),
+            _ => Err(AnalyzerError(format!("Cannot apply `+` to `{}`", expr), line, column)),
+        },
+
+        Expr::InfixOp { op, lhs, rhs } => {
+            let lhs_value = promote(evaluate_expression(lhs, symbols, line, column)?);
+            let rhs_value = promote(evaluate_expression(rhs, symbols, line, column)?);
+
+            match (&lhs_value, &rhs_value) {
+                (ast::Lit::Str(lhs_value), ast::Lit::Str(rhs_value)) if *op == TokenKind::Plus => {
+                    return Ok(ast::Lit::Str(format!("{}{}", lhs_value, rhs_value)));
+                }
+
+                // strings compare character by character, like `std::string` does
+                (ast::Lit::Str(lhs_value), ast::Lit::Str(rhs_value)) if is_comparison(*op) => {
+                    return Ok(ast::Lit::Bool(compare(*op, lhs_value.partial_cmp(rhs_value))));
+                }
+
+                (ast::Lit::Str(_), _) | (_, ast::Lit::Str(_)) => {
+                    return Err(AnalyzerError(
+                        format!(
+                            "Strings can only be joined with `+` or compared, cannot evaluate `{}`",
+                            expr
+                        ),
+                        line,
+                        column,
+                    ));
+                }
+
+                _ => {}
+            }
+
+            if is_comparison(*op) {
+                let ordering = match (lhs_value, rhs_value) {
+                    (ast::Lit::Int(lhs_value), ast::Lit::Int(rhs_value)) => {
+                        lhs_value.partial_cmp(&rhs_value)
+                    }
+                    (lhs_value, rhs_value) => as_float(lhs_value).partial_cmp(&as_float(rhs_value)),
+                };
+
+                return Ok(ast::Lit::Bool(compare(*op, ordering)));
+            }
+
+            let is_zero = match rhs_value {
+                ast::Lit::Int(value) => value == 0,
+                ast::Lit::Float(value) => value == 0.0,
+                _ => false,
+            };
+
+            if *op == TokenKind::Slash && is_zero {
+                return Err(AnalyzerError(format!("Division by zero in `{}`", expr), line, column));
+            }
+
+            match (lhs_value, rhs_value) {
+                (ast::Lit::Int(lhs_value), ast::Lit::Int(rhs_value)) => {
+                    // integer division truncates towards zero, like it does in C++
+                    let value = match op {
+                        TokenKind::Plus => lhs_value.checked_add(rhs_value),
+                        TokenKind::Minus => lhs_value.checked_sub(rhs_value),
+                        TokenKind::Asterisk => lhs_value.checked_mul(rhs_value),
+                        TokenKind::Slash => lhs_value.checked_div(rhs_value),
+                        _ => unreachable!("comparisons are evaluated above"),
+                    };
+
+                    value
+                        .map(ast::Lit::Int)
+                        .ok_or_else(|| AnalyzerError(format!("`{}` overflows", expr), line, column))
+                }
+
+                (lhs_value, rhs_value) => {
+                    let (lhs_value, rhs_value) = (as_float(lhs_value), as_float(rhs_value));
+
+                    Ok(ast::Lit::Float(match op {
+                        TokenKind::Plus => lhs_value + rhs_value,
+                        TokenKind::Minus => lhs_value - rhs_value,
+                        TokenKind::Asterisk => lhs_value * rhs_value,
+                        TokenKind::Slash => lhs_value / rhs_value,
+                        _ => unreachable!("comparisons are evaluated above"),
+                    }))
+                }
+            }
+        }
+
+        _ => Err(AnalyzerError(format!("Cannot evaluate `{}`", expr), line, column)),
+    }
+}
+
+/// Evaluates the condition of a conditional, numbers hold when they aren't zero and pointers when they
+/// aren't null, like in C++
+///
+/// # Arguments
+/// - `expr`: The condition
+/// - `symbols`: A reference to the symbol table
+/// - `line`: The line of the conditional
+/// - `column`: The column errors point at
+///
+/// # Returns
+/// - `Result<bool>`: A result containing either:
+///   - `bool`: Whether the condition holds
+///   - [AnalyzerError](crate::error::Error::AnalyzerError): returns an error if the condition can't be evaluated
+pub(crate) fn evaluate_condition(
+    expr: &Expr,
+    symbols: &IndexMap<String, Symbol>,
+    line: usize,
+    column: usize,
+) -> Result<bool> {
+    let symbol = match expr {
+        Expr::Ident(name) => symbols.get(name),
+        _ => None,
+    };
+
+    if let Some(Symbol::Pointer {
+        allocation_type, ..
+    }) = symbol
+    {
+        return Ok(*allocation_type != AllocationType::Null);
+    }
+
+    match evaluate_expression(expr, symbols, line, column)? {
+        ast::Lit::Str(_) => Err(AnalyzerError(
+            format!("Cannot use `{}` as a condition, it is a string", expr),
+            line,
+            column,
+        )),
+        ast::Lit::Bool(value) => Ok(value),
+        ast::Lit::Char(value) => Ok(value != '\0'),
+        ast::Lit::Int(value) => Ok(value != 0),
+        ast::Lit::Float(value) => Ok(value != 0.0),
+        ast::Lit::Float32(value) => Ok(value != 0.0),
+    }
+}
+
+/// Reads the value of a variable back into a literal of its type
+pub(crate) fn variable_literal(vtype: &Type, value: &str) -> Option<ast::Lit> {
+    match vtype {
+        Type::Integer
+        | Type::Short
+        | Type::UnsignedShort
+        | Type::UnsignedInt
+        | Type::Long
+        | Type::UnsignedLong
+        | Type::LongLong
+        | Type::UnsignedLongLong => value.parse().ok().map(ast::Lit::Int),
+        Type::Float | Type::Double => value.parse().ok().map(ast::Lit::Float),
+        Type::Char => value.chars().next().map(ast::Lit::Char),
+        Type::Bool => value.parse().ok().map(ast::Lit::Bool),
+        Type::String => ast::Lit::from_str(value).ok(),
+        // no variable is `void`, and objects aren't variables
+        Type::Void | Type::Class(_) => None,
+    }
+}
+
+/// Reads the value a pointer points to, like `*p` in `*p = *p + 1`
+///
+/// A pointer to a variable reads the variable, or the copy of it the pointer holds when the variable
+/// belongs to a caller. A pointer into the heap reads the element it points to in its block
+///
+/// # Arguments
+/// - `name`: The name of the pointer
+/// - `symbols`: A reference to the symbol table
+/// - `memory`: A reference to the `MemoryModel` the heap is in
+/// - `line`: The line of the read
+/// - `column`: The column errors point at
+///
+/// # Returns
+/// - `Result<ast::Lit>`: A result containing either:
+///   - [Lit](crate::parser::ast::Lit): The value the pointer points to
+///   - [AnalyzerError](crate::error::Error::AnalyzerError): returns an error if the pointer can't be
+///     dereferenced or what it points to isn't initialized
+pub(crate) fn pointee_literal(
+    name: &str,
+    symbols: &IndexMap<String, Symbol>,
+    memory: &dyn MemoryModel,
+    line: usize,
+    column: usize,
+) -> Result<ast::Lit> {
+    let Some(Symbol::Pointer {
+        ptype,
+        value,
+        heap_pointer,
+        allocation_type,
+        offset,
+        value_size,
+        ..
+    }) = symbols.get(name)
+    else {
+        return Err(AnalyzerError(format!("Pointer `{}` not found!", name), line, column));
+    };
+
+    let not_initialized =
+        || AnalyzerError(format!("Value `*{}` not initialized!", name), line, column);
+
+    let value = match allocation_type {
+        AllocationType::Null => {
+            return Err(AnalyzerError(
+                format!("Cannot dereference null pointer `{}`", name),
+                line,
+                column,
+            ));
+        }
+
+        AllocationType::Dangling => {
+            return Err(AnalyzerError(
+                format!("Cannot dereference dangling pointer `{}`", name),
+                line,
+                column,
+            ));
+        }
+
+        AllocationType::Heap => {
+            if *offset >= *value_size {
+                return Err(AnalyzerError(
+                    format!("Cannot dereference `{}`, it points past the end of its block", name),
+                    line,
+                    column,
+                ));
+            }
+
+            let block = heap_pointer
+                .and_then(|heap_pointer| memory.block(heap_pointer - offset))
+                .ok_or_else(|| {
+                AnalyzerError(format!("Heap pointer not found for `{}`", name), line, column)
+            })?;
+
+            // a block of several elements keeps each one as a member, a single value is its contents
+            match &block.members {
+                Some(members) => members
+                    .iter()
+                    .find(|member| member.offset == *offset)
+                    .and_then(|member| member.value.clone())
+                    .ok_or_else(not_initialized)?,
+                None if *offset == 0 && !block.metadata.is_empty() => block.metadata.clone(),
+                None => return Err(not_initialized()),
+            }
+        }
+
+        _ => match value.as_deref() {
+            Some(Symbol::Variable {
+                name: variable,
+                value: copy,
+                ..
+            }) => match symbols.get(variable) {
+                Some(Symbol::Variable { value, .. }) => value.clone(),
+                _ => copy.clone(),
+            }
+            .ok_or_else(not_initialized)?,
+
+            _ => {
+                return Err(AnalyzerError(
+                    format!("Cannot read what `{}` points to", name),
+                    line,
+                    column,
+                ));
+            }
+        },
+    };
+
+    variable_literal(ptype, &value)
+        .ok_or_else(|| AnalyzerError(format!("Cannot evaluate `*{}`", name), line, column))
+}
+
+/// Converts a value to another type the way a cast does in C++
+///
+/// Floating point values are truncated towards zero, integers wrap around to fit narrower types, and
+/// chars and bools convert to and from the numbers they hold
+///
+/// # Arguments
+/// - `lit`: The value being cast
+/// - `cast_type`: The type the value is cast to
+/// - `pointer_size`: The size of a pointer in bytes, which `long` is as wide as
+/// - `line`: The line of the cast
+/// - `column`: The column errors point at
+///
+/// # Returns
+/// - `Result<(ast::Lit, bool)>`: A result containing either:
+///   - A tuple with the converted value and whether the cast lost part of the value
+///   - [AnalyzerError](crate::error::Error::AnalyzerError): returns an error if the value can't be cast
+pub(crate) fn cast_literal(
+    lit: ast::Lit,
+    cast_type: &Type,
+    pointer_size: usize,
+    line: usize,
+    column: usize,
+) -> Result<(ast::Lit, bool)> {
+    let cast = format!("({}){}", cast_type, lit);
+
+    let value = match promote(lit) {
+        ast::Lit::Str(_) => {
+            return Err(AnalyzerError(
+                format!("Cannot evaluate `{}`, strings can't be cast to numbers", cast),
+                line,
+                column,
+            ));
+        }
+        value => value,
+    };
+
+    match cast_type {
+        Type::Float | Type::Double => {
+            let number = as_float(value.clone());
+
+            let converted = match cast_type {
+                Type::Float => number as f32 as f64,
+                _ => number,
+            };
+
+            let lost = match value {
+                ast::Lit::Int(value) => converted as i128 != value,
+                _ => converted != number,
+            };
+
+            Ok((ast::Lit::Float(converted), lost))
+        }
+
+        Type::Bool => {
+            let number = as_float(value);
+            Ok((ast::Lit::Bool(number != 0.0), number != 0.0 && number != 1.0))
+        }
+
+        _ => {
+            // chars hold the characters of a single byte
+            let (min, max) = match cast_type {
+                Type::Char => (0, u8::MAX as i128),
+                _ => cast_type.integer_range(pointer_size).ok_or_else(|| {
+                    AnalyzerError(format!("Cannot evaluate `{}`", cast), line, column)
+                })?,
+            };
+
+            let (number, fraction) = match value {
+                ast::Lit::Int(value) => (value, false),
+                ast::Lit::Float(value) => {
+                    // a floating point value that doesn't fit is undefined behavior, it doesn't wrap
+                    if !(min as f64..=max as f64).contains(&value.trunc()) {
+                        return Err(AnalyzerError(
+                            format!(
+                                "`{}` is undefined behavior, `{}` only holds {} to {}",
+                                cast, cast_type, min, max
+                            ),
+                            line,
+                            column,
+                        ));
+                    }
+
+                    (value.trunc() as i128, value.fract() != 0.0)
+                }
+                _ => unreachable!("promoted literals are numbers"),
+            };
+
+            let wrapped = (number - min).rem_euclid(max - min + 1) + min;
+            let lost = fraction || wrapped != number;
+
+            if *cast_type == Type::Char {
+                return Ok((ast::Lit::Char(wrapped as u8 as char), lost));
+            }
+
+            Ok((ast::Lit::Int(wrapped), lost))
+        }
+    }
+}
+
+/// Checks if an infix operator compares its sides, which makes it evaluate to a `bool`
+fn is_comparison(op: TokenKind) -> bool {
+    matches!(
+        op,
+        TokenKind::Less
+            | TokenKind::LessEq
+            | TokenKind::Greater
+            | TokenKind::GreaterEq
+            | TokenKind::EqEq
+            | TokenKind::NotEq
+    )
+}
+
+/// Applies a comparison to how its sides are ordered, `NaN` is ordered with nothing so only `!=` holds
+fn compare(op: TokenKind, ordering: Option<Ordering>) -> bool {
+    match op {
+        TokenKind::Less => ordering == Some(Ordering::Less),
+        TokenKind::LessEq => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+        TokenKind::Greater => ordering == Some(Ordering::Greater),
+        TokenKind::GreaterEq => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+        TokenKind::EqEq => ordering == Some(Ordering::Equal),
+        TokenKind::NotEq => ordering != Some(Ordering::Equal),
+        _ => unreachable!("only comparisons are compared"),
+    }
+}
+
+/// Promotes chars and bools to integers, the way C++ does before arithmetic, and `float`s to `double`s
+fn promote(lit: ast::Lit) -> ast::Lit {
+    match lit {
+        ast::Lit::Char(value) => ast::Lit::Int(value as i128),
+        ast::Lit::Bool(value) => ast::Lit::Int(value as i128),
+        ast::Lit::Float32(value) => ast::Lit::Float(value as f64),
+        lit => lit,
+    }
+}
+
+fn as_float(lit: ast::Lit) -> f64 {
+    match lit {
+        ast::Lit::Int(value) => value as f64,
+        ast::Lit::Float(value) => value,
+        _ => unreachable!("promoted literals are numbers"),
+    }
+}
+
+/// Gets the size of the value an expression evaluates to, like `sizeof` does
+fn size_of_expression(
+    expr: &Expr,
+    symbols: &IndexMap<String, Symbol>,
+    line: usize,
+    column: usize,
+) -> Result<usize> {
+    match expr {
+        // unsuffixed floating point literals are doubles in C++
+        Expr::Literal(lit) => Ok(match lit {
+            ast::Lit::Int(_) => Type::Integer,
+            ast::Lit::Float(_) => Type::Double,
+            ast::Lit::Float32(_) => Type::Float,
+            ast::Lit::Char(_) => Type::Char,
+            ast::Lit::Bool(_) => Type::Bool,
+            // string literals are arrays of chars ending with a null character
+            ast::Lit::Str(value) => return Ok(value.len() + 1),
+        }
+        .get_size()),
+
+        Expr::Ident(name) => symbols
+            .get(name)
+            .map(symbol_size)
+            .ok_or_else(|| AnalyzerError(format!("Variable `{}` not found!", name), line, column)),
+
+        Expr::Dereference(pointer) => match pointer.as_ref() {
+            Expr::Ident(name) => match symbols.get(name) {
+                Some(Symbol::Pointer {
+                    ptype,
+                    indirection,
+                    pointer_size,
+                    ..
+                }) if ptype.is_value() || *indirection > 1 => {
+                    Ok(pointee_size(ptype, *indirection, *pointer_size))
+                }
+
+                Some(Symbol::Pointer {
+                    ptype: Type::Class(class_name),
+                    ..
+                }) => Err(AnalyzerError(
+                    format!(
+                        "Cannot dereference `{}*` `{}`, use `->` to reach its fields",
+                        class_name, name
+                    ),
+                    line,
+                    column,
+                )),
+
+                Some(Symbol::Pointer { .. }) => Err(AnalyzerError(
+                    format!(
+                        "Cannot dereference `void*` `{}`, cast it to a typed pointer first",
+                        name
+                    ),
+                    line,
+                    column,
+                )),
+
+                Some(_) => Err(AnalyzerError(
+                    format!("Cannot dereference `{}`, it isn't a pointer", name),
+                    line,
+                    column,
+                )),
+
+                None => Err(AnalyzerError(format!("Pointer `{}` not found!", name), line, column)),
+            },
+
+            _ => Err(AnalyzerError(format!("Cannot take the size of `{}`", expr), line, column)),
+        },
+
+        _ => Err(AnalyzerError(format!("Cannot take the size of `{}`", expr), line, column)),
+    }
+}
+
 /// Validates a pointer assignment.
 ///
 /// This function checks if a value can be assigned to a pointer based on the symbol table. The value can
-/// either be a literal or a variable.
+/// either be a literal or a variable, or a pointer one level of indirection below for pointers to pointers.
 ///
 /// # Arguments
 /// - `value`: A boxed [Expr](crate::parser::ast::Expr) representing the value to be assigned to the pointer. This can be a literal
 ///   or an identifier.
+/// - `indirection`: The number of `*` in the type of the pointer being assigned to.
 /// - `symbols`: A reference to the symbol table
 ///
 /// # Returns
@@ -95,6 +784,7 @@ pub(crate) fn validate_variable_assignment(
 ///   - [AnalyzerError](crate::error::Error::AnalyzerError): returns an error if the assignment is invalid
 pub(crate) fn validate_pointer_assignment<'a>(
     value: Box<Expr>,
+    indirection: usize,
     symbols: &'a IndexMap<String, Symbol>,
     line: usize,
     pointer_ident_column: usize,
@@ -108,14 +798,33 @@ pub(crate) fn validate_pointer_assignment<'a>(
 
         ast::Expr::Ident(ident_name) => {
             if let Some(symbol) = symbols.get(&ident_name) {
-                if let Symbol::Variable { .. } = symbol {
-                    return Ok(Some(Box::new(symbol.clone())));
-                } else {
-                    return Err(AnalyzerError(
+                match symbol {
+                    Symbol::Variable { .. } if indirection == 1 => {
+                        Ok(Some(Box::new(symbol.clone())))
+                    }
+
+                    Symbol::Pointer {
+                        indirection: target_indirection,
+                        ..
+                    } if *target_indirection + 1 == indirection => {
+                        Ok(Some(Box::new(symbol.clone())))
+                    }
+
+                    _ if indirection > 1 => Err(AnalyzerError(
+                        format!(
+                            "A pointer with {} `*` can only point to a pointer with {}!",
+                            indirection,
+                            indirection - 1
+                        ),
+                        line,
+                        pointer_ident_column,
+                    )),
+
+                    _ => Err(AnalyzerError(
                         format!("Pointers can only point to variables or literals!",),
                         line,
                         pointer_ident_column,
-                    ));
+                    )),
                 }
             } else {
                 return Err(AnalyzerError(
@@ -132,3 +841,1198 @@ pub(crate) fn validate_pointer_assignment<'a>(
         )),
     }
 }
+
+/// Builds the pointer a pointer parameter of a function starts out as, which holds a copy of the address
+/// it is passed
+///
+/// The address is either taken from a variable of the caller like `&x`, or copied from a pointer of the
+/// caller, which the parameter then points to the same place as
+///
+/// # Arguments
+/// - `argument`: The argument passed to the parameter
+/// - `parameter`: The parameter
+/// - `symbols`: A reference to the symbols of the caller
+/// - `pointer_size`: The size of a pointer in bytes
+/// - `line`: The line of the call
+/// - `column`: The column errors point at
+///
+/// # Returns
+/// - `Result<Symbol>`: A result containing either:
+///   - `Symbol`: The pointer, named after the parameter
+///   - [AnalyzerError](crate::error::Error::AnalyzerError): returns an error if the argument isn't an
+///     address of the parameter's type
+pub(crate) fn pointer_argument(
+    argument: Expr,
+    parameter: &ast::Parameter,
+    symbols: &IndexMap<String, Symbol>,
+    pointer_size: usize,
+    line: usize,
+    column: usize,
+) -> Result<Symbol> {
+    let ptype = Type::from_token(parameter.param_type)?;
+    let indirection = parameter.indirection;
+
+    let incorrect_type = |argument: &str| {
+        AnalyzerError(
+            format!(
+                "Cannot pass `{}` to pointer parameter `{}` (incorrect type)",
+                argument, parameter.param_name
+            ),
+            line,
+            column,
+        )
+    };
+
+    match argument {
+        Expr::AddressOf(target) => {
+            let Expr::Ident(target) = *target else {
+                return Err(AnalyzerError(
+                    format!("Cannot take the address of `{}`", target),
+                    line,
+                    column,
+                ));
+            };
+
+            let value = validate_pointer_assignment(
+                Box::new(Expr::Ident(target.clone())),
+                indirection,
+                symbols,
+                line,
+                column,
+            )?;
+
+            let target_type = match value.as_deref() {
+                Some(Symbol::Variable { vtype, .. }) => vtype,
+                Some(Symbol::Pointer { ptype, .. }) => ptype,
+                _ => return Err(incorrect_type(&format!("&{}", target))),
+            };
+
+            if *target_type != ptype {
+                return Err(incorrect_type(&format!("&{}", target)));
+            }
+
+            Ok(Symbol::Pointer {
+                value_size: pointee_size(&ptype, indirection, pointer_size),
+                ptype,
+                name: parameter.param_name.clone(),
+                value,
+                heap_pointer: None,
+                allocation_type: AllocationType::Stack,
+                pointer_size,
+                indirection,
+                unique: false,
+                shared: false,
+                control_block: None,
+                offset: 0,
+            })
+        }
+
+        Expr::Ident(source) => match symbols.get(&source) {
+            Some(Symbol::Pointer { unique: true, .. }) => Err(AnalyzerError(
+                format!(
+                    "Cannot pass `std::unique_ptr` `{}` to `{}`, it can't be copied",
+                    source, parameter.param_name
+                ),
+                line,
+                column,
+            )),
+
+            Some(Symbol::Pointer { shared: true, .. }) => Err(AnalyzerError(
+                format!(
+                    "Cannot pass `std::shared_ptr` `{}` to `{}`, it isn't a raw pointer",
+                    source, parameter.param_name
+                ),
+                line,
+                column,
+            )),
+
+            Some(
+                pointer @ Symbol::Pointer {
+                    ptype: source_type,
+                    indirection: source_indirection,
+                    ..
+                },
+            ) if *source_type == ptype && *source_indirection == indirection => {
+                let mut pointer = pointer.clone();
+
+                if let Symbol::Pointer { name, .. } = &mut pointer {
+                    *name = parameter.param_name.clone();
+                }
+
+                Ok(pointer)
+            }
+
+            Some(_) => Err(incorrect_type(&source)),
+            None => Err(AnalyzerError(format!("Pointer `{}` not found!", source), line, column)),
+        },
+
+        argument => Err(AnalyzerError(
+            format!(
+                "Cannot pass `{}` to pointer parameter `{}`, it takes an address or a pointer",
+                argument, parameter.param_name
+            ),
+            line,
+            column,
+        )),
+    }
+}
+
+/// Validates a pointer cast like `(int*)p` assigned to a pointer
+///
+/// Pointers to any type can be cast to each other and to and from `void*`, the address they hold stays
+/// the same. A pointer to a variable can only be cast to `void*` and back to a pointer to the variable's
+/// type
+///
+/// # Arguments
+/// - `value`: The value assigned to the pointer
+/// - `ptype`: The base type of the pointer assigned to
+/// - `indirection`: The number of `*` in the type of the pointer assigned to
+/// - `pointer_name`: The name of the pointer assigned to
+/// - `symbols`: A reference to the symbol table
+///
+/// # Returns
+/// - `Result<Option<Symbol>>`: A result containing either:
+///   - `Option<Symbol>`: The pointer that is cast, `None` if the value isn't a cast
+///   - [AnalyzerError](crate::error::Error::AnalyzerError): returns an error if the cast is invalid
+pub(crate) fn validate_pointer_cast(
+    value: &Expr,
+    ptype: &Type,
+    indirection: usize,
+    pointer_name: &str,
+    symbols: &IndexMap<String, Symbol>,
+    line: usize,
+    column: usize,
+) -> Result<Option<Symbol>> {
+    let Expr::PointerCast { pointee_type, expr } = value else {
+        return Ok(None);
+    };
+
+    if indirection > 1 || !ptype.is_type(*pointee_type) {
+        return Err(AnalyzerError(
+            format!("Cannot assign `{}` to pointer `{}` (incorrect type)", value, pointer_name),
+            line,
+            column,
+        ));
+    }
+
+    let Expr::Ident(source_name) = expr.as_ref() else {
+        return Err(AnalyzerError(
+            format!("Expected a pointer to cast but found `{}`", expr),
+            line,
+            column,
+        ));
+    };
+
+    validate_raw_pointer(symbols, source_name, &format!("({}*)", pointee_type), line, column)?;
+
+    let Some(
+        source @ Symbol::Pointer {
+            value: source_value,
+            allocation_type,
+            value_size,
+            pointer_size,
+            indirection: source_indirection,
+            offset,
+            ..
+        },
+    ) = symbols.get(source_name)
+    else {
+        let message = match symbols.get(source_name) {
+            Some(_) => format!("Cannot cast `{}`, it isn't a pointer", source_name),
+            None => format!("Pointer `{}` not found!", source_name),
+        };
+
+        return Err(AnalyzerError(message, line, column));
+    };
+
+    if *source_indirection > 1 {
+        return Err(AnalyzerError(
+            format!("Cannot cast pointer to pointer `{}` yet", source_name),
+            line,
+            column,
+        ));
+    }
+
+    // what is left of the block has to hold at least one value of the type
+    if *allocation_type == AllocationType::Heap
+        && *ptype != Type::Void
+        && value_size - offset < ptype.get_variable_size(*pointer_size)
+    {
+        return Err(AnalyzerError(
+            format!(
+                "Cannot cast `{}` to `{}*`, the {} bytes it points to are fewer than the size of `{}`",
+                source_name,
+                ptype,
+                value_size - offset,
+                ptype
+            ),
+            line,
+            column,
+        ));
+    }
+
+    // a pointer to a variable can only be cast to a pointer of the variable's type
+    match source_value.as_deref() {
+        Some(Symbol::Variable { vtype, name, .. }) if *ptype != Type::Void && vtype != ptype => {
+            Err(AnalyzerError(
+                format!(
+                    "Cannot cast `{}` to `{}*`, it points to `{}` variable `{}`",
+                    source_name, ptype, vtype, name
+                ),
+                line,
+                column,
+            ))
+        }
+        _ => Ok(Some(source.clone())),
+    }
+}
+
+/// Points a pointer where the pointer it was cast from points, a pointer cast from a dangling pointer
+/// dangles too
+///
+/// # Arguments
+/// - `source`: The pointer that is cast
+/// - `ptype`: The base type of the pointer assigned to
+/// - `pointer_name`: The name of the pointer assigned to
+/// - `memory`: The memory model the statements are analyzed in
+///
+/// # Returns
+/// - `Result<Symbol>`: A result containing either:
+///   - [Symbol](crate::analyzer::Symbol): The pointer assigned to
+///   - An error if the memory model couldn't be updated
+pub(crate) fn cast_pointer(
+    source: Symbol,
+    ptype: Type,
+    pointer_name: String,
+    memory: &mut dyn MemoryModel,
+) -> Result<Symbol> {
+    let Symbol::Pointer {
+        value,
+        heap_pointer,
+        allocation_type,
+        value_size,
+        offset,
+        ..
+    } = source
+    else {
+        unreachable!("only pointers are cast");
+    };
+
+    if let (AllocationType::Dangling, Some(heap_pointer)) = (&allocation_type, heap_pointer) {
+        memory.insert_dangling_pointer(heap_pointer - offset, pointer_name.clone())?;
+    }
+
+    // a pointer into the heap shares the size of the block, other pointers know the size of what they
+    // point to from their type
+    let value_size = match allocation_type {
+        AllocationType::Heap | AllocationType::Dangling => value_size,
+        _ => pointee_size(&ptype, 1, memory.pointer_size()),
+    };
+
+    Ok(Symbol::Pointer {
+        ptype,
+        name: pointer_name,
+        value,
+        heap_pointer,
+        allocation_type,
+        pointer_size: memory.pointer_size(),
+        value_size,
+        indirection: 1,
+        unique: false,
+        shared: false,
+        control_block: None,
+        offset,
+    })
+}
+
+/// Finds a cast that points a pointer at an address its type isn't aligned to, like `(int*)v` where
+/// `v` points one byte into a block. The cast still runs, reading or writing through the pointer is
+/// what would go wrong on hardware that needs aligned values
+///
+/// # Arguments
+/// - `statement`: The statement about to run
+/// - `symbols`: A reference to the symbol table
+/// - `pointer_size`: The size of a pointer in bytes, which `long` is as wide as
+///
+/// # Returns
+/// - `Option<Diagnostic>`: The warning, `None` if the statement doesn't cast a heap pointer to an
+///   address its type isn't aligned to. Any other problem with the cast is left for the statement to
+///   report when it runs
+pub(crate) fn misaligned_cast(
+    statement: &Statement,
+    symbols: &IndexMap<String, Symbol>,
+    pointer_size: usize,
+) -> Option<Diagnostic> {
+    let (pointer_name, value, line, column) = match statement {
+        Statement::PointerDeclaration {
+            pointer_name,
+            value,
+            line,
+            pointer_ident_column,
+            ..
+        }
+        | Statement::PointerAssignment {
+            pointer_name,
+            new_value: value,
+            line,
+            pointer_ident_column,
+        } => (pointer_name, value, *line, *pointer_ident_column),
+        _ => return None,
+    };
+
+    let Expr::PointerCast { pointee_type, expr } = value.as_ref() else {
+        return None;
+    };
+
+    let Expr::Ident(source_name) = expr.as_ref() else {
+        return None;
+    };
+
+    let ptype = Type::from_token(*pointee_type).ok()?;
+
+    // values of a type are aligned to their own size, like the fields of an object
+    let alignment = match ptype {
+        Type::Void | Type::String | Type::Class(_) => return None,
+        ref ptype => ptype.get_variable_size(pointer_size),
+    };
+
+    let Some(Symbol::Pointer {
+        allocation_type: AllocationType::Heap,
+        heap_pointer: Some(address),
+        ..
+    }) = symbols.get(source_name)
+    else {
+        return None;
+    };
+
+    if address % alignment == 0 {
+        return None;
+    }
+
+    Some(Diagnostic {
+        severity: Severity::Warning,
+        message: format!(
+            "`{}` points `{}` at {}, which isn't a multiple of {}, the alignment of `{}`",
+            value,
+            pointer_name,
+            format_address(HEAP_BASE_ADDRESS + address, pointer_size),
+            alignment,
+            ptype
+        ),
+        line_number: Some(line),
+        column_number: Some(column),
+    })
+}
+
+/// Finds a `double` or `long double` literal stored in a `float`, like `float f = 3.5;`, which C++
+/// narrows to the precision of a `float` without a word
+///
+/// # Arguments
+/// - `statement`: The statement about to run
+/// - `symbols`: A reference to the symbol table
+///
+/// # Returns
+/// - `Option<Diagnostic>`: The warning, `None` if the statement doesn't store a `double` literal in a
+///   `float` variable
+pub(crate) fn narrowed_literal(
+    statement: &Statement,
+    symbols: &IndexMap<String, Symbol>,
+) -> Option<Diagnostic> {
+    let (var_name, value, line, column) = match statement {
+        Statement::VariableDeclaration {
+            var_type: TokenKind::KwFloat,
+            var_name,
+            value,
+            line,
+            var_ident_column,
+        } => (var_name, value, *line, *var_ident_column),
+        Statement::VariableAssignment {
+            var_name,
+            new_value,
+            line,
+            var_ident_column,
+            ..
+        } if matches!(
+            symbols.get(var_name),
+            Some(Symbol::Variable {
+                vtype: Type::Float,
+                ..
+            })
+        ) =>
+        {
+            (var_name, new_value, *line, *var_ident_column)
+        }
+        _ => return None,
+    };
+
+    let Expr::Literal(ast::Lit::Float(literal)) = value.as_ref() else {
+        return None;
+    };
+
+    Some(Diagnostic {
+        severity: Severity::Warning,
+        message: format!(
+            "The `double` literal `{}` is narrowed to a `float` in `{}`, which holds {}, write `{}f` to \
+             make it a `float` literal",
+            literal,
+            var_name,
+            *literal as f32 as f64,
+            literal
+        ),
+        line_number: Some(line),
+        column_number: Some(column),
+    })
+}
+
+/// Gets the other raw pointers that point into the same heap block as a pointer, which casts like
+/// `(int*)p` leave behind
+///
+/// # Arguments
+/// - `symbols`: A reference to the symbol table
+/// - `pointer`: The pointer
+///
+/// # Returns
+/// - `Vec<String>`: The names of the other pointers, empty if the pointer doesn't point into the heap
+pub(crate) fn block_aliases(symbols: &IndexMap<String, Symbol>, pointer: &Symbol) -> Vec<String> {
+    let block = |symbol: &Symbol| match symbol {
+        Symbol::Pointer {
+            heap_pointer: Some(heap_pointer),
+            allocation_type: AllocationType::Heap,
+            unique: false,
+            shared: false,
+            offset,
+            ..
+        } => Some(heap_pointer - offset),
+        _ => None,
+    };
+
+    let (Some(target), Symbol::Pointer { name, .. }) = (block(pointer), pointer) else {
+        return Vec::new();
+    };
+
+    symbols
+        .iter()
+        .filter(|(alias, symbol)| *alias != name && block(symbol) == Some(target))
+        .map(|(alias, _)| alias.clone())
+        .collect()
+}
+
+/// Lets go of the heap block a raw pointer pointed to when the pointer is reassigned or goes away
+///
+/// The block leaks unless another pointer still points to it, which takes it over if the pointer
+/// owned it, or a pointer of a caller owns it like when the block was passed to a function
+///
+/// # Arguments
+/// - `memory`: The memory model the block is in
+/// - `block`: The starting position of the block
+/// - `size`: The size of the block in bytes
+/// - `pointer_name`: The name of the pointer letting go of the block
+/// - `aliases`: The other pointers to the block
+///
+/// # Returns
+/// - `Result<()>`: An error if the memory model couldn't be updated
+pub(crate) fn release_block(
+    memory: &mut dyn MemoryModel,
+    block: usize,
+    size: usize,
+    pointer_name: &str,
+    aliases: &[String],
+) -> Result<()> {
+    let owner = memory.block(block).and_then(|block| block.current_pointer_identifier.clone());
+    let owned = owner.as_deref() == Some(pointer_name);
+
+    let Some(alias) = aliases.first() else {
+        if owned || owner.is_none() {
+            memory.leak(block, size);
+        }
+
+        return Ok(());
+    };
+
+    if owned {
+        memory.update_owner(block, alias.clone())?;
+    }
+
+    Ok(())
+}
+
+/// Leaves the pointers that still point to a block dangling after it was freed or moved
+///
+/// # Arguments
+/// - `symbols`: A mutable reference to the symbol table
+/// - `aliases`: The pointers to the block
+/// - `block`: The starting position of the block
+/// - `memory`: The memory model the block is in
+///
+/// # Returns
+/// - `Result<()>`: An error if the memory model couldn't be updated
+pub(crate) fn leave_dangling(
+    symbols: &mut IndexMap<String, Symbol>,
+    aliases: &[String],
+    block: usize,
+    memory: &mut dyn MemoryModel,
+) -> Result<()> {
+    for alias in aliases {
+        if let Some(Symbol::Pointer {
+            allocation_type, ..
+        }) = symbols.get_mut(alias)
+        {
+            *allocation_type = AllocationType::Dangling;
+            memory.insert_dangling_pointer(block, alias.clone())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Hands what a function did through its pointer parameters back to the caller once the function returns
+///
+/// Values written through a parameter pointing to a variable of the caller are written to the variable,
+/// and pointers of the caller to a block the function freed are left dangling
+///
+/// # Arguments
+/// - `frame_symbols`: A reference to the symbols of the function's frame
+/// - `parameters`: The parameters of the function
+/// - `symbols`: A mutable reference to the symbols of the caller
+/// - `memory`: The memory model the blocks are in
+///
+/// # Returns
+/// - `Result<()>`: An error if the memory model couldn't be updated
+pub(crate) fn write_through(
+    frame_symbols: &IndexMap<String, Symbol>,
+    parameters: &[ast::Parameter],
+    symbols: &mut IndexMap<String, Symbol>,
+    memory: &mut dyn MemoryModel,
+) -> Result<()> {
+    for parameter in parameters {
+        let Some(Symbol::Pointer {
+            allocation_type: AllocationType::Stack,
+            value: Some(pointee),
+            ..
+        }) = frame_symbols.get(&parameter.param_name)
+        else {
+            continue;
+        };
+
+        let Symbol::Variable {
+            name,
+            value: written,
+            ..
+        } = pointee.as_ref()
+        else {
+            continue;
+        };
+
+        if let Some(Symbol::Variable { value, .. }) = symbols.get_mut(name) {
+            *value = written.clone();
+        }
+    }
+
+    let freed: Vec<(String, usize)> = symbols
+        .iter()
+        .filter_map(|(name, symbol)| match symbol {
+            Symbol::Pointer {
+                heap_pointer: Some(heap_pointer),
+                allocation_type: AllocationType::Heap,
+                unique: false,
+                shared: false,
+                offset,
+                ..
+            } => Some((name.clone(), heap_pointer - offset)),
+            _ => None,
+        })
+        .filter(|(_, block)| {
+            matches!(
+                memory.block(*block),
+                Some(HeapBlock {
+                    block_state: HeapBlockState::Free,
+                    ..
+                })
+            )
+        })
+        .collect();
+
+    for (name, block) in freed {
+        leave_dangling(symbols, &[name], block, memory)?;
+    }
+
+    Ok(())
+}
+
+/// Gets the size of what a pointer points to, which is another pointer for pointers to pointers
+///
+/// # Arguments
+/// - `ptype`: The base type of the pointer
+/// - `indirection`: The number of `*` in the type of the pointer
+/// - `pointer_size`: The size of a pointer in bytes
+///
+/// # Returns
+/// - `usize`: The size of the value pointed to in bytes
+pub(crate) fn pointee_size(ptype: &Type, indirection: usize, pointer_size: usize) -> usize {
+    match ptype {
+        _ if indirection > 1 => pointer_size,
+        // a `void*` has to be cast before what it points to can be used, and an object isn't a value
+        Type::Void | Type::Class(_) => 0,
+        _ => ptype.get_variable_size(pointer_size),
+    }
+}
+
+/// Reads the value assigned to a pointer as arithmetic on the pointer itself, like `p + 1`, `1 + p` or `p - 1`
+///
+/// # Arguments
+/// - `expr`: The value assigned to the pointer
+/// - `pointer_name`: The name of the pointer assigned to
+/// - `symbols`: A reference to the symbol table
+///
+/// # Returns
+/// - `Result<Option<i64>>`: A result containing either:
+///   - `Option<i64>`: How many elements the pointer moves by, `None` if the value isn't arithmetic on the pointer
+///   - [AnalyzerError](crate::error::Error::AnalyzerError): returns an error if the step isn't an integer
+pub(crate) fn pointer_step(
+    expr: &Expr,
+    pointer_name: &str,
+    symbols: &IndexMap<String, Symbol>,
+    line: usize,
+    column: usize,
+) -> Result<Option<i64>> {
+    let (step, sign) = match expr {
+        Expr::InfixOp {
+            op: TokenKind::Plus,
+            lhs,
+            rhs,
+        } => match (lhs.as_ref(), rhs.as_ref()) {
+            (Expr::Ident(name), step) | (step, Expr::Ident(name)) if name == pointer_name => {
+                (step, 1)
+            }
+            _ => return Ok(None),
+        },
+
+        Expr::InfixOp {
+            op: TokenKind::Minus,
+            lhs,
+            rhs,
+        } if matches!(lhs.as_ref(), Expr::Ident(name) if name == pointer_name) => {
+            (rhs.as_ref(), -1)
+        }
+
+        _ => return Ok(None),
+    };
+
+    match promote(evaluate_expression(step, symbols, line, column)?) {
+        ast::Lit::Int(step) => i64::try_from(sign * step).map(Some).map_err(|_| {
+            AnalyzerError(
+                format!("Cannot move pointer `{}` by `{}`, it is too far", pointer_name, step),
+                line,
+                column,
+            )
+        }),
+        _ => Err(AnalyzerError(
+            format!("Cannot move pointer `{}` by `{}`, it isn't an integer", pointer_name, step),
+            line,
+            column,
+        )),
+    }
+}
+
+/// Moves a pointer by whole elements within its heap block, like `p++` or `p + 1`
+///
+/// The pointer may stop one past the end of its block, but not before its start
+///
+/// # Arguments
+/// - `pointer`: The pointer to move
+/// - `step`: How many elements the pointer moves by
+/// - `pointer_name`: The name of the pointer moved, used in errors
+/// - `line`: The line of the statement
+/// - `column`: The column errors point at
+///
+/// # Returns
+/// - `Result<()>`: An error if the pointer doesn't point into a heap block, or would leave it
+pub(crate) fn move_pointer(
+    pointer: &mut Symbol,
+    step: i64,
+    pointer_name: &str,
+    line: usize,
+    column: usize,
+) -> Result<()> {
+    let Symbol::Pointer {
+        ptype,
+        heap_pointer,
+        allocation_type,
+        pointer_size,
+        value_size,
+        indirection,
+        offset,
+        ..
+    } = pointer
+    else {
+        unreachable!("only pointers are moved");
+    };
+
+    if *ptype == Type::Void && *indirection == 1 {
+        return Err(AnalyzerError(
+            format!(
+                "Cannot move `void*` `{}`, the size of what it points to is unknown",
+                pointer_name
+            ),
+            line,
+            column,
+        ));
+    }
+
+    if let (Type::Class(class_name), 1) = (&*ptype, *indirection) {
+        return Err(AnalyzerError(
+            format!(
+                "Cannot move `{}*` `{}`, it points to a single object",
+                class_name, pointer_name
+            ),
+            line,
+            column,
+        ));
+    }
+
+    let (AllocationType::Heap, Some(heap_pointer)) = (&*allocation_type, heap_pointer.as_mut())
+    else {
+        return Err(AnalyzerError(
+            format!(
+                "Pointer arithmetic on `{}` is only supported inside a heap block",
+                pointer_name
+            ),
+            line,
+            column,
+        ));
+    };
+
+    // the pointer moves by whole elements and may stop one past the end of its block
+    let new_offset =
+        *offset as i64 + step * pointee_size(ptype, *indirection, *pointer_size) as i64;
+
+    if new_offset < 0 {
+        return Err(AnalyzerError(
+            format!("Pointer `{}` moves before the start of its block", pointer_name),
+            line,
+            column,
+        ));
+    }
+
+    if new_offset as usize > *value_size {
+        return Err(AnalyzerError(
+            format!(
+                "Pointer `{}` moves past the end of its block of {} bytes",
+                pointer_name, value_size
+            ),
+            line,
+            column,
+        ));
+    }
+
+    *heap_pointer = *heap_pointer - *offset + new_offset as usize;
+    *offset = new_offset as usize;
+
+    Ok(())
+}
+
+/// Builds a pointer declared as a copy of another pointer, like `int* q = p;`, or as pointer
+/// arithmetic on one, like `int* q = p + 1;`
+///
+/// The copy points where the pointer it was copied from points, moved by whole elements for pointer
+/// arithmetic, and a copy of a dangling pointer dangles too
+///
+/// # Arguments
+/// - `value`: The value assigned to the pointer
+/// - `ptype`: The base type of the pointer declared
+/// - `indirection`: The number of `*` in the type of the pointer declared
+/// - `pointer_name`: The name of the pointer declared
+/// - `symbols`: A reference to the symbol table
+/// - `memory`: The memory model the statements are analyzed in
+/// - `line`: The line of the declaration
+/// - `column`: The column errors point at
+///
+/// # Returns
+/// - `Result<Symbol>`: A result containing either:
+///   - [Symbol](crate::analyzer::Symbol): The pointer declared
+///   - [AnalyzerError](crate::error::Error::AnalyzerError): returns an error if the value isn't a
+///     pointer of the same type, or the arithmetic moves it out of its block
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn copy_pointer(
+    value: &Expr,
+    ptype: &Type,
+    indirection: usize,
+    pointer_name: &str,
+    symbols: &IndexMap<String, Symbol>,
+    memory: &mut dyn MemoryModel,
+    line: usize,
+    column: usize,
+) -> Result<Symbol> {
+    let is_pointer = |expr: &Expr| match expr {
+        Expr::Ident(name) => matches!(symbols.get(name), Some(Symbol::Pointer { .. })),
+        _ => false,
+    };
+
+    let source_name = match value {
+        Expr::Ident(name) => name,
+        Expr::InfixOp { lhs, rhs, .. } => match (lhs.as_ref(), rhs.as_ref()) {
+            (Expr::Ident(name), _) if is_pointer(lhs) => name,
+            (_, Expr::Ident(name)) if is_pointer(rhs) => name,
+            _ => {
+                return Err(AnalyzerError(
+                    format!("Expected an address or a pointer but found `{}`", value),
+                    line,
+                    column,
+                ));
+            }
+        },
+        _ => {
+            return Err(AnalyzerError(
+                format!("Expected an address or a pointer but found `{}`", value),
+                line,
+                column,
+            ));
+        }
+    };
+
+    let step = match value {
+        Expr::Ident(_) => 0,
+        _ => pointer_step(value, source_name, symbols, line, column)?.ok_or_else(|| {
+            AnalyzerError(
+                format!(
+                    "Cannot assign `{}` to pointer `{}`, it isn't an address",
+                    value, pointer_name
+                ),
+                line,
+                column,
+            )
+        })?,
+    };
+
+    let mut pointer = match symbols.get(source_name) {
+        Some(Symbol::Pointer { unique: true, .. }) => {
+            return Err(unique_copy_error(source_name, line, column));
+        }
+
+        Some(Symbol::Pointer { shared: true, .. }) => {
+            return Err(AnalyzerError(
+                format!(
+                    "Cannot assign `std::shared_ptr` `{}` to `{}`, it isn't a raw pointer",
+                    source_name, pointer_name
+                ),
+                line,
+                column,
+            ));
+        }
+
+        Some(
+            pointer @ Symbol::Pointer {
+                ptype: source_type,
+                indirection: source_indirection,
+                ..
+            },
+        ) if source_type == ptype && *source_indirection == indirection => pointer.clone(),
+
+        Some(_) => {
+            return Err(AnalyzerError(
+                format!("Cannot assign `{}` to pointer `{}` (incorrect type)", value, pointer_name),
+                line,
+                column,
+            ));
+        }
+
+        None => {
+            return Err(AnalyzerError(
+                format!("Pointer `{}` not found!", source_name),
+                line,
+                column,
+            ));
+        }
+    };
+
+    if step != 0 {
+        move_pointer(&mut pointer, step, source_name, line, column)?;
+    }
+
+    if let Symbol::Pointer {
+        name,
+        allocation_type,
+        heap_pointer,
+        offset,
+        ..
+    } = &mut pointer
+    {
+        if let (AllocationType::Dangling, Some(heap_pointer)) = (&*allocation_type, heap_pointer) {
+            memory.insert_dangling_pointer(*heap_pointer - *offset, pointer_name.to_owned())?;
+        }
+
+        *name = pointer_name.to_owned();
+    }
+
+    Ok(pointer)
+}
+
+/// Gets the copies of values a statement makes between symbols, the way value semantics copy every byte
+///
+/// `std::swap` copies through a temporary, which is `None` in the copies it takes part in. Assigning an
+/// object copies it member by member, like the copy assignment operator C++ generates, so each field is
+/// a copy of its own named like `s.x`
+///
+/// # Arguments
+/// - `statement`: The statement to look at
+/// - `symbols`: A reference to the symbol table, before the statement runs
+/// - `pointer_size`: The size of a pointer in bytes
+///
+/// # Returns
+/// - `Vec<(Option<String>, Option<String>, usize)>`: The name copied from, the name copied to and the
+///   number of bytes copied, in the order the copies happen
+pub(crate) fn value_copies(
+    statement: &Statement,
+    symbols: &IndexMap<String, Symbol>,
+    pointer_size: usize,
+) -> Vec<(Option<String>, Option<String>, usize)> {
+    // both ends of a copy have the same type, so whichever end is a symbol has the size
+    let copy = |from: Option<&String>, to: Option<&String>| {
+        let bytes = [to, from].into_iter().flatten().find_map(|name| symbols.get(name));
+
+        (from.cloned(), to.cloned(), bytes.map_or(0, symbol_size))
+    };
+
+    match statement {
+        Statement::VariableDeclaration {
+            var_name, value, ..
+        } => match value.as_ref() {
+            Expr::Ident(source) => vec![copy(Some(source), Some(var_name))],
+            _ => Vec::new(),
+        },
+
+        Statement::VariableAssignment {
+            var_name,
+            new_value,
+            ..
+        } => match (new_value.as_ref(), symbols.get(var_name)) {
+            (Expr::Ident(source), Some(Symbol::Object { .. })) => match symbols.get(source) {
+                Some(object @ Symbol::Object { .. }) => block_members(object, pointer_size)
+                    .into_iter()
+                    .map(|member| {
+                        (
+                            Some(format!("{}.{}", source, member.name)),
+                            Some(format!("{}.{}", var_name, member.name)),
+                            member.size,
+                        )
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            },
+            (Expr::Ident(source), _) => vec![copy(Some(source), Some(var_name))],
+            _ => Vec::new(),
+        },
+
+        // copying a `std::shared_ptr` copies the pointer, the block gets another owner instead
+        Statement::SharedPointerDeclaration {
+            pointer_name,
+            value: ast::SharedValue::Copy { source },
+            ..
+        }
+        | Statement::SharedPointerAssignment {
+            pointer_name,
+            value: ast::SharedValue::Copy { source },
+            ..
+        } => vec![copy(Some(source), Some(pointer_name))],
+
+        Statement::Swap { lhs, rhs, .. } => vec![
+            copy(Some(lhs), None),
+            copy(Some(rhs), Some(lhs)),
+            copy(None, Some(rhs)),
+        ],
+
+        _ => Vec::new(),
+    }
+}
+
+/// Gets the move a statement makes, from a `std::unique_ptr` or `std::shared_ptr` to another
+///
+/// # Arguments
+/// - `statement`: The statement
+///
+/// # Returns
+/// - `Option<(String, String)>`: The name moved from and the name moved to, `None` if nothing is moved
+pub(crate) fn value_moves(statement: &Statement) -> Option<(String, String)> {
+    match statement {
+        Statement::UniquePointerDeclaration {
+            pointer_name,
+            value: ast::UniqueValue::Move { source },
+            ..
+        }
+        | Statement::UniquePointerAssignment {
+            pointer_name,
+            value: ast::UniqueValue::Move { source },
+            ..
+        }
+        | Statement::SharedPointerDeclaration {
+            pointer_name,
+            value: ast::SharedValue::Move { source },
+            ..
+        }
+        | Statement::SharedPointerAssignment {
+            pointer_name,
+            value: ast::SharedValue::Move { source },
+            ..
+        } => Some((source.clone(), pointer_name.clone())),
+
+        _ => None,
+    }
+}
+
+/// Checks that raw pointer operations like `new`, `delete` and `&x` aren't used on a smart pointer
+///
+/// # Arguments
+/// - `symbols`: A reference to the symbol table
+/// - `pointer_name`: The pointer the operation is used on
+/// - `operation`: The operation, as it is written
+///
+/// # Returns
+/// - `Result<()>`: An [AnalyzerError](crate::error::Error::AnalyzerError) if the pointer is a
+///   `std::unique_ptr` or a `std::shared_ptr`
+pub(crate) fn validate_raw_pointer(
+    symbols: &IndexMap<String, Symbol>,
+    pointer_name: &str,
+    operation: &str,
+    line: usize,
+    column: usize,
+) -> Result<()> {
+    match symbols.get(pointer_name) {
+        Some(Symbol::Pointer { unique: true, .. }) => Err(AnalyzerError(
+            format!("Cannot use `{}` on `std::unique_ptr` `{}`", operation, pointer_name),
+            line,
+            column,
+        )),
+        Some(Symbol::Pointer { shared: true, .. }) => Err(AnalyzerError(
+            format!("Cannot use `{}` on `std::shared_ptr` `{}`", operation, pointer_name),
+            line,
+            column,
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Builds the error for copying a `std::unique_ptr`, which only ever has one owner
+pub(crate) fn unique_copy_error(source: &str, line: usize, column: usize) -> Error {
+    AnalyzerError(
+        format!(
+            "Cannot copy `std::unique_ptr` `{0}`, move it with `std::move({0})` instead",
+            source
+        ),
+        line,
+        column,
+    )
+}
+
+/// Gets the number of bytes a symbol takes up on the stack
+///
+/// # Arguments
+/// - `symbol`: The symbol
+///
+/// # Returns
+/// - `usize`: The size in bytes, `0` for literals
+pub(crate) fn symbol_size(symbol: &Symbol) -> usize {
+    match symbol {
+        Symbol::Variable { size, .. } => *size,
+        Symbol::Pointer { pointer_size, .. } => *pointer_size,
+        Symbol::Literal { .. } => 0,
+        Symbol::Union { size, .. }
+        | Symbol::Object { size, .. }
+        | Symbol::Array { size, .. }
+        | Symbol::Mutex { size, .. } => *size,
+    }
+}
+
+/// Gets the alignment of a symbol on the stack, which its address is a multiple of
+///
+/// Like the members of an object, values are aligned to their own size, an array to the size of its
+/// elements and an object to its most aligned field. A mutex is a struct of integers and pointers, so
+/// it is aligned like a pointer
+///
+/// # Arguments
+/// - `symbol`: The symbol
+/// - `pointer_size`: The size of a pointer in bytes
+///
+/// # Returns
+/// - `usize`: The alignment in bytes, `1` for literals
+pub(crate) fn symbol_alignment(symbol: &Symbol, pointer_size: usize) -> usize {
+    match symbol {
+        Symbol::Variable { size, .. } | Symbol::Union { size, .. } => *size,
+        Symbol::Pointer { .. } | Symbol::Mutex { .. } => pointer_size,
+        Symbol::Literal { .. } => 1,
+        Symbol::Object { fields, .. } => object_alignment(fields, pointer_size),
+        Symbol::Array { vtype, .. } => vtype.get_variable_size(pointer_size),
+    }
+    .max(1)
+}
+
+/// Gets the name of a symbol, literals have none
+pub(crate) fn symbol_name(symbol: &Symbol) -> Option<&str> {
+    match symbol {
+        Symbol::Variable { name, .. }
+        | Symbol::Pointer { name, .. }
+        | Symbol::Union { name, .. }
+        | Symbol::Object { name, .. }
+        | Symbol::Array { name, .. }
+        | Symbol::Mutex { name, .. } => Some(name),
+        Symbol::Literal { .. } => None,
+    }
+}
+
+/// Checks that `delete` or `delete[]` matches how the block it releases was allocated, `new` or `new[]`,
+/// releasing it with the other form is undefined behavior
+///
+/// # Arguments
+/// - `pointer_name`: The name of the pointer being released
+/// - `release`: How the pointer is released
+/// - `symbols`: A reference to the symbol table
+/// - `delete_forms`: The form of `delete` each block allocated with `new` or `new[]` has to be
+///   released with, by the block's starting position
+/// - `line`: The line of the statement
+/// - `column`: The column of the pointer
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - `()`: If the forms match, or the block wasn't allocated with `new` or `new[]`
+///    - [Error](crate::error::Error): An error naming the form the block has to be released with
+pub(crate) fn check_delete_form(
+    pointer_name: &str,
+    release: ast::Release,
+    symbols: &IndexMap<String, Symbol>,
+    delete_forms: &IndexMap<usize, ast::Release>,
+    line: usize,
+    column: usize,
+) -> Result<()> {
+    // `free` isn't a form of `delete`, any other problem with the pointer is left for the statement
+    if release == ast::Release::Free {
+        return Ok(());
+    }
+
+    let Some(Symbol::Pointer {
+        heap_pointer: Some(heap_pointer),
+        allocation_type: AllocationType::Heap,
+        offset,
+        ..
+    }) = symbols.get(pointer_name)
+    else {
+        return Ok(());
+    };
+
+    let expected = match delete_forms.get(&(heap_pointer - offset)) {
+        Some(expected) if *expected != release => *expected,
+        _ => return Ok(()),
+    };
+
+    let allocated_with = match expected {
+        ast::Release::DeleteArray => "new[]",
+        _ => "new",
+    };
+
+    Err(AnalyzerError(
+        format!(
+            "Cannot `{}` `{}`, its block was allocated with `{}`, use `{} {};` instead",
+            release, pointer_name, allocated_with, expected, pointer_name
+        ),
+        line,
+        column,
+    ))
+}
@@ -0,0 +1,234 @@
+//! Edits the memory an analysis ended with from outside of the source code, so an instructor can
+//! poke a value or free a block during a demo and show what happens to the rest of the program's
+//! memory
+
+use std::ops::Range;
+
+use indexmap::IndexMap;
+
+use crate::error::{Error, Result};
+use crate::export::format_address;
+use crate::lexer::token::TokenKind;
+use crate::parser::Parser;
+
+use super::address_space::HEAP_BASE_ADDRESS;
+use super::heap_allocator::{FreeRange, HeapBlockState};
+use super::helpers::{check_integer_range, symbol_name, validate_variable_assignment};
+use super::options::AnalyzerOptions;
+use super::overview::heap_overview;
+use super::r#type::Type;
+use super::random_heap_allocator::release_range;
+use super::result::AnalysisResult;
+use super::{AllocationType, Symbol};
+
+/// Sets the value of a variable on the stack, along with the copies of it the pointers to it show
+///
+/// # Arguments
+/// - `stack`: The symbols on the stack, like the stack of an analysis
+/// - `name`: The name of the variable
+/// - `value`: The new value, a literal like `5` or `'a'`, or an expression of the other variables
+///   like `x + 1`
+/// - `options`: The options the stack was analyzed with, which decide the range of the integer types
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - `()`: The variable was set
+///    - [Error](crate::error::Error): An error if the variable isn't found, is a `std::string`, or the
+///      value doesn't fit its type
+pub fn set_variable_value(
+    stack: &mut [Symbol],
+    name: &str,
+    value: &str,
+    options: &AnalyzerOptions,
+) -> Result<()> {
+    let symbols: IndexMap<String, Symbol> = stack
+        .iter()
+        .filter_map(|symbol| Some((symbol_name(symbol)?.to_owned(), symbol.clone())))
+        .collect();
+
+    let vtype = match symbols.get(name) {
+        Some(Symbol::Variable {
+            vtype: Type::String,
+            ..
+        }) => {
+            return Err(format!(
+                "Variable `{}` is a `std::string`, its characters can only be set by the source",
+                name
+            )
+            .into());
+        }
+        Some(Symbol::Variable { vtype, .. }) => vtype.clone(),
+        Some(_) => return Err(format!("Symbol `{}` isn't a variable", name).into()),
+        None => return Err(format!("Variable `{}` not found!", name).into()),
+    };
+
+    let mut parser = Parser::new(value);
+    let expr = parser.parse_expression().map_err(message_only)?;
+
+    if parser.peek() != TokenKind::EOF {
+        return Err(format!("Expected a single value for `{}` but found `{}`", name, value).into());
+    }
+
+    let new_value = validate_variable_assignment(Box::new(expr), name, &vtype, &symbols, 0, 0)
+        .map_err(message_only)?;
+
+    check_integer_range(
+        &vtype,
+        new_value.as_deref(),
+        name,
+        options.architecture.pointer_size(),
+        0,
+        0,
+    )
+    .map_err(message_only)?;
+
+    for symbol in stack.iter_mut() {
+        set_value(symbol, name, &new_value);
+    }
+
+    Ok(())
+}
+
+/// Frees a heap block like `delete` would, leaving the pointers to it dangling
+///
+/// The padding after the block is freed along with it, and the free list is rebuilt from the heap so
+/// the freed block is merged with the free ranges next to it
+///
+/// # Arguments
+/// - `result`: The result of the analysis, the lifetime of the block ends at its last step
+/// - `block`: The starting position of the block in the heap
+/// - `options`: The options the result was analyzed with, which decide how wide addresses are shown
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - `()`: The block was freed
+///    - [Error](crate::error::Error): An error if no allocated block starts at `block`
+pub fn free_block(
+    result: &mut AnalysisResult,
+    block: usize,
+    options: &AnalyzerOptions,
+) -> Result<()> {
+    let AnalysisResult {
+        stack,
+        heap,
+        cross_reference,
+        ..
+    } = result;
+
+    let Some(index) = heap.iter().position(|heap_block| {
+        heap_block.pointer == block
+            && matches!(heap_block.block_state, HeapBlockState::Allocated | HeapBlockState::Leaked)
+    }) else {
+        return Err(format!(
+            "Allocated heap block at {} not found!",
+            format_address(HEAP_BASE_ADDRESS + block, options.architecture.pointer_size())
+        )
+        .into());
+    };
+
+    let end = block + heap[index].size;
+    let mut dangling = Vec::new();
+
+    for symbol in stack.iter_mut() {
+        leave_dangling(symbol, &(block..end), &mut dangling);
+    }
+
+    let freed = &mut heap[index];
+    freed.block_state = HeapBlockState::Free;
+    freed.current_pointer_identifier = None;
+    freed.dangling_pointer_identifiers = (!dangling.is_empty()).then_some(dangling);
+    freed.metadata = "Free Block".to_string();
+    freed.members = None;
+
+    let mut freed_end = end;
+
+    if let Some(padding) = heap.iter_mut().find(|heap_block| {
+        heap_block.pointer == end && heap_block.block_state == HeapBlockState::Padding
+    }) {
+        padding.block_state = HeapBlockState::Free;
+        padding.metadata = "Free Block".to_string();
+        padding.gap_reason = None;
+        freed_end += padding.size;
+    }
+
+    if let Some(Some(lifetime)) = cross_reference.lifetimes.get_mut(index) {
+        lifetime.released.get_or_insert(cross_reference.steps);
+    }
+
+    // the freed bytes go back in the free list the way the allocator frees them
+    let mut free_list: Vec<(usize, usize)> =
+        result.free_list.iter().map(|range| (range.start, range.end)).collect();
+    release_range(&mut free_list, block, freed_end - 1);
+
+    result.free_list = free_list
+        .into_iter()
+        .map(|(start, end)| FreeRange {
+            start,
+            end,
+            size: end - start + 1,
+        })
+        .collect();
+    result.heap_overview = heap_overview(&result.heap, result.heap_capacity);
+
+    Ok(())
+}
+
+/// Sets the value of a variable, and of the copies of it held by the pointers to it
+fn set_value(symbol: &mut Symbol, name: &str, new_value: &Option<String>) {
+    match symbol {
+        Symbol::Variable {
+            name: variable_name,
+            value,
+            ..
+        } if variable_name == name => *value = new_value.clone(),
+
+        Symbol::Pointer {
+            allocation_type: AllocationType::Stack,
+            value: Some(pointee),
+            ..
+        } => set_value(pointee, name, new_value),
+
+        _ => {}
+    }
+}
+
+/// Leaves the pointers into a range of the heap dangling, keeping the names of the ones on the stack
+fn leave_dangling(symbol: &mut Symbol, range: &Range<usize>, dangling: &mut Vec<String>) {
+    match symbol {
+        Symbol::Pointer {
+            name,
+            allocation_type: allocation_type @ AllocationType::Heap,
+            heap_pointer: Some(heap_pointer),
+            ..
+        } if range.contains(heap_pointer) => {
+            *allocation_type = AllocationType::Dangling;
+
+            if !dangling.contains(name) {
+                dangling.push(name.clone());
+            }
+        }
+
+        // the copies of a pointer held by pointers to it, like `p` in `int** pp = &p;`
+        Symbol::Pointer {
+            allocation_type: AllocationType::Stack,
+            value: Some(pointee),
+            ..
+        } => leave_dangling(pointee, range, dangling),
+
+        Symbol::Object { fields, .. } => {
+            for pointer in fields.iter_mut().filter_map(|field| field.pointer.as_mut()) {
+                leave_dangling(pointer, range, dangling);
+            }
+        }
+
+        _ => {}
+    }
+}
+
+/// Drops the line and column of an error, which point nowhere since the edit isn't in the source
+fn message_only(error: Error) -> Error {
+    match error {
+        Error::AnalyzerError(message, ..) | Error::ParserError(message, ..) => Error::Msg(message),
+        error => error,
+    }
+}
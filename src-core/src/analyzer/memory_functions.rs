@@ -0,0 +1,837 @@
+//! `memset` and `memcpy`, which fill and copy a range of bytes of a heap block or an array at once
+//! instead of one element at a time, and `strcpy` and `strlen` over the `char`s of one
+//!
+//! The simulated memory keeps the value of each element rather than its bytes, so a range has to
+//! cover whole elements. `memset(p, 1, 2 * sizeof(int))` gives each `int` the bytes `0x01010101`,
+//! which is `16843009` and not `1`
+
+use indexmap::{IndexMap, IndexSet};
+
+use crate::{
+    diagnostics::{Diagnostic, Severity},
+    error::{Error::AnalyzerError, Result},
+    export::format_address,
+    lexer::token::TokenKind,
+    parser::ast::{Expr, Lit, Statement},
+    parser::expression::unescape_string,
+};
+
+use super::address_space::HEAP_BASE_ADDRESS;
+use super::array::write_heap_elements;
+use super::heap_allocator::{BlockMember, HeapBlockState};
+use super::helpers::{cast_literal, evaluate_expression};
+use super::memory_model::MemoryModel;
+use super::r#type::Type;
+use super::{AllocationType, Symbol};
+
+/// Represents where the elements a pointer or an array refers to are kept
+///
+/// - `Array`: The elements of the array with the given name
+/// - `Block`: The elements of the heap block starting at `block`, which `pointer` points into
+enum Storage {
+    Array(String),
+    Block { pointer: String, block: usize },
+}
+
+impl Storage {
+    /// Checks if the elements are kept in the same place, whichever pointers lead to a block
+    fn is_same(&self, other: &Storage) -> bool {
+        match (self, other) {
+            (Storage::Array(name), Storage::Array(other)) => name == other,
+            (Storage::Block { block, .. }, Storage::Block { block: other, .. }) => block == other,
+            _ => false,
+        }
+    }
+}
+
+/// Represents the elements from where a pointer or an array points to the end of what holds them
+///
+/// # Fields
+/// - `storage`: Where the elements are kept
+/// - `vtype`: The type of the elements
+/// - `element_size`: The size of an element in bytes
+/// - `start`: Where the range starts, in bytes from the start of the storage
+/// - `available`: How many bytes there are from the start of the range to the end of the storage
+struct ElementRange {
+    storage: Storage,
+    vtype: Type,
+    element_size: usize,
+    start: usize,
+    available: usize,
+}
+
+/// Finds a `memset` or `memcpy` running past the end of an array or a heap block, which permissive
+/// mode skips with a warning instead of failing the analysis
+///
+/// # Arguments
+/// - `statement`: The statement about to run
+/// - `symbols`: A reference to the symbol table
+/// - `pointer_size`: The size of a pointer in bytes
+///
+/// # Returns
+/// - `Option<String>`: Why the range is out of bounds, `None` if the statement isn't a `memset` or
+///   `memcpy` out of bounds. Any other problem with the statement is left for it to report when it
+///   runs
+pub(crate) fn out_of_bounds_range(
+    statement: &Statement,
+    symbols: &IndexMap<String, Symbol>,
+    pointer_size: usize,
+) -> Option<String> {
+    let (function, names, size, line) = match statement {
+        Statement::Memset {
+            target, size, line, ..
+        } => (TokenKind::Memset, vec![target], size, *line),
+        Statement::Memcpy {
+            destination,
+            source,
+            size,
+            line,
+            ..
+        } => (TokenKind::Memcpy, vec![destination, source], size, *line),
+        _ => return None,
+    };
+
+    let bytes = byte_count(size, function, symbols, line, 0).ok()?;
+
+    names.into_iter().find_map(|name| {
+        let range = element_range(name, function, symbols, pointer_size, line, 0).ok()?;
+        bounds(&range, name, function, bytes).err()
+    })
+}
+
+/// Finds a `memcpy` whose ranges overlap, which is undefined behavior in C++. The copy still runs as
+/// if every byte was read before any was written, like `memmove` does
+///
+/// # Arguments
+/// - `statement`: The statement about to run
+/// - `symbols`: A reference to the symbol table
+/// - `pointer_size`: The size of a pointer in bytes
+///
+/// # Returns
+/// - `Option<Diagnostic>`: The warning, `None` if the statement isn't a `memcpy` between overlapping
+///   ranges
+pub(crate) fn overlapping_copy(
+    statement: &Statement,
+    symbols: &IndexMap<String, Symbol>,
+    pointer_size: usize,
+) -> Option<Diagnostic> {
+    let Statement::Memcpy {
+        destination,
+        source,
+        size,
+        line,
+        destination_column,
+        ..
+    } = statement
+    else {
+        return None;
+    };
+
+    let bytes = byte_count(size, TokenKind::Memcpy, symbols, *line, 0).ok()?;
+    let to = element_range(destination, TokenKind::Memcpy, symbols, pointer_size, *line, 0).ok()?;
+    let from = element_range(source, TokenKind::Memcpy, symbols, pointer_size, *line, 0).ok()?;
+
+    if !to.storage.is_same(&from.storage)
+        || bytes == 0
+        || to.start >= from.start + bytes
+        || from.start >= to.start + bytes
+    {
+        return None;
+    }
+
+    Some(Diagnostic {
+        severity: Severity::Warning,
+        message: format!(
+            "`memcpy` of {} bytes from `{}` to `{}` copies between overlapping ranges, which is \
+             undefined behavior, `memmove` copies between them safely",
+            bytes, source, destination
+        ),
+        line_number: Some(*line),
+        column_number: Some(*destination_column),
+    })
+}
+
+/// Sets every byte of a range of a heap block or an array to the same value, like
+/// `memset(p, 0, 4 * sizeof(int))`
+///
+/// # Arguments
+/// - `target`: The name of the pointer or the array the range starts at
+/// - `value`: The value each byte is set to, only its lowest byte is used
+/// - `size`: The number of bytes set
+/// - `symbols`: A mutable reference to the symbol table
+/// - `memory`: The memory model the statements are analyzed in
+/// - `line`: The line of the statement
+/// - `target_column`: The column of the pointer or the array
+/// - `value_column`: The column of the value
+/// - `size_column`: The column of the size
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - `()`: If the range was set
+///    - [Error](crate::error::Error): An error if the target can't be written, the range runs past its
+///      end or covers part of an element
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn memset(
+    target: &str,
+    value: &Expr,
+    size: &Expr,
+    symbols: &mut IndexMap<String, Symbol>,
+    memory: &mut dyn MemoryModel,
+    line: usize,
+    target_column: usize,
+    value_column: usize,
+    size_column: usize,
+) -> Result<()> {
+    let pointer_size = memory.pointer_size();
+    let range =
+        element_range(target, TokenKind::Memset, symbols, pointer_size, line, target_column)?;
+    let bytes = byte_count(size, TokenKind::Memset, symbols, line, size_column)?;
+
+    bounds(&range, target, TokenKind::Memset, bytes)
+        .map_err(|reason| AnalyzerError(reason, line, size_column))?;
+
+    let byte = match evaluate_expression(value, symbols, line, value_column)? {
+        Lit::Int(value) => value as u8,
+        Lit::Char(value) => value as u8,
+        value => {
+            return Err(AnalyzerError(
+                format!("Value `{}` passed to `memset` isn't an integer", value),
+                line,
+                value_column,
+            ));
+        }
+    };
+
+    let element = filled_element(byte, &range.vtype, pointer_size, line, value_column)?;
+
+    write_elements(&range, vec![Some(element); bytes / range.element_size], symbols, memory)
+}
+
+/// Copies a range of bytes of a heap block or an array into another, like
+/// `memcpy(q, p, 4 * sizeof(int))`
+///
+/// # Arguments
+/// - `destination`: The name of the pointer or the array the bytes are copied to
+/// - `source`: The name of the pointer or the array the bytes are copied from
+/// - `size`: The number of bytes copied
+/// - `symbols`: A mutable reference to the symbol table
+/// - `memory`: The memory model the statements are analyzed in
+/// - `line`: The line of the statement
+/// - `destination_column`: The column of the destination
+/// - `source_column`: The column of the source
+/// - `size_column`: The column of the size
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - `()`: If the range was copied
+///    - [Error](crate::error::Error): An error if either range can't be accessed, runs past its end or
+///      covers part of an element, or the elements aren't of the same type
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn memcpy(
+    destination: &str,
+    source: &str,
+    size: &Expr,
+    symbols: &mut IndexMap<String, Symbol>,
+    memory: &mut dyn MemoryModel,
+    line: usize,
+    destination_column: usize,
+    source_column: usize,
+    size_column: usize,
+) -> Result<()> {
+    let pointer_size = memory.pointer_size();
+    let to = element_range(
+        destination,
+        TokenKind::Memcpy,
+        symbols,
+        pointer_size,
+        line,
+        destination_column,
+    )?;
+    let from =
+        element_range(source, TokenKind::Memcpy, symbols, pointer_size, line, source_column)?;
+    let bytes = byte_count(size, TokenKind::Memcpy, symbols, line, size_column)?;
+
+    if to.vtype != from.vtype {
+        return Err(AnalyzerError(
+            format!(
+                "Cannot copy the `{}` elements of `{}` into the `{}` elements of `{}`",
+                from.vtype, source, to.vtype, destination
+            ),
+            line,
+            source_column,
+        ));
+    }
+
+    bounds(&to, destination, TokenKind::Memcpy, bytes)
+        .and_then(|_| bounds(&from, source, TokenKind::Memcpy, bytes))
+        .map_err(|reason| AnalyzerError(reason, line, size_column))?;
+
+    // every element is read before any is written, so overlapping ranges copy like `memmove`
+    let elements = read_elements(&from, bytes / from.element_size, symbols, memory);
+
+    write_elements(&to, elements, symbols, memory)
+}
+
+/// Finds a `strcpy` running past the end of a heap block, whose characters go on into the memory
+/// after the block like they would in C++, over whatever blocks are allocated there
+///
+/// # Arguments
+/// - `statement`: The statement about to run
+/// - `symbols`: A reference to the symbol table
+/// - `memory`: A reference to the `MemoryModel` the statement is analyzed in
+///
+/// # Returns
+/// - `Option<Diagnostic>`: The warning, `None` if the statement isn't a `strcpy` past the end of a
+///   heap block. Any other problem with the statement is left for it to report when it runs
+pub(crate) fn string_overrun(
+    statement: &Statement,
+    symbols: &IndexMap<String, Symbol>,
+    memory: &dyn MemoryModel,
+) -> Option<Diagnostic> {
+    let Statement::Strcpy {
+        destination,
+        source,
+        line,
+        destination_column,
+        ..
+    } = statement
+    else {
+        return None;
+    };
+
+    let pointer_size = memory.pointer_size();
+    let to = char_range(destination, TokenKind::Strcpy, symbols, pointer_size, *line, 0).ok()?;
+    let bytes = string_characters(source, symbols, memory, *line, 0).ok()?.len();
+
+    let Storage::Block { block, .. } = to.storage else {
+        return None;
+    };
+
+    if bytes <= to.available {
+        return None;
+    }
+
+    let overwritten: Vec<_> = overrun_bytes(&to, bytes, memory)
+        .into_iter()
+        .map(|(pointer, ..)| pointer)
+        .filter(|pointer| *pointer != block)
+        .collect::<IndexSet<_>>()
+        .into_iter()
+        .map(|pointer| {
+            match memory.block(pointer).and_then(|block| block.current_pointer_identifier.clone()) {
+                Some(owner) => format!("the block of `{}`", owner),
+                None => format!(
+                    "the block at {}",
+                    format_address(HEAP_BASE_ADDRESS + pointer, pointer_size)
+                ),
+            }
+        })
+        .collect();
+
+    let landed = match overwritten.is_empty() {
+        true => "memory no allocated block holds".to_string(),
+        false => overwritten.join(" and "),
+    };
+
+    Some(Diagnostic {
+        severity: Severity::Warning,
+        message: format!(
+            "`strcpy` of {} bytes runs {} bytes past the end of `{}`, which has {} bytes from \
+             where it points, and writes over {}",
+            bytes,
+            bytes - to.available,
+            destination,
+            to.available,
+            landed
+        ),
+        line_number: Some(*line),
+        column_number: Some(*destination_column),
+    })
+}
+
+/// Copies a null-terminated string along with its terminator into a heap block or an array of `char`s,
+/// like `strcpy(s, "hello")` or `strcpy(s, t)`
+///
+/// A copy running past the end of a heap block goes on into the memory after it, writing its
+/// characters over the blocks allocated there, see [string_overrun]. Characters landing in memory no
+/// allocated block holds aren't kept
+///
+/// # Arguments
+/// - `destination`: The name of the pointer or the array the string is copied to
+/// - `source`: The string literal, or the pointer or the array the string is copied from
+/// - `symbols`: A mutable reference to the symbol table
+/// - `memory`: The memory model the statements are analyzed in
+/// - `line`: The line of the statement
+/// - `destination_column`: The column of the destination
+/// - `source_column`: The column of the source
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - `()`: If the string was copied
+///    - [Error](crate::error::Error): An error if either side doesn't hold `char`s, the source isn't
+///      null-terminated or the string runs past the end of an array
+pub(crate) fn strcpy(
+    destination: &str,
+    source: &Expr,
+    symbols: &mut IndexMap<String, Symbol>,
+    memory: &mut dyn MemoryModel,
+    line: usize,
+    destination_column: usize,
+    source_column: usize,
+) -> Result<()> {
+    let pointer_size = memory.pointer_size();
+    let to = char_range(
+        destination,
+        TokenKind::Strcpy,
+        symbols,
+        pointer_size,
+        line,
+        destination_column,
+    )?;
+    let characters = string_characters(source, symbols, memory, line, source_column)?;
+
+    // only the memory after a heap block is modeled, the stack past an array isn't
+    if let Storage::Array(_) = to.storage {
+        bounds(&to, destination, TokenKind::Strcpy, characters.len())
+            .map_err(|reason| AnalyzerError(reason, line, source_column))?;
+    }
+
+    let overrun = overrun_bytes(&to, characters.len(), memory);
+
+    let fitting =
+        characters.iter().take(to.available).map(|character| Some(character.to_string())).collect();
+
+    write_elements(&to, fitting, symbols, memory)?;
+
+    let mut overwritten: IndexMap<usize, Vec<(usize, usize)>> = IndexMap::new();
+
+    for (pointer, offset, index) in overrun {
+        overwritten.entry(pointer).or_default().push((offset, index));
+    }
+
+    for (pointer, bytes) in overwritten {
+        let mut members =
+            memory.block(pointer).and_then(|block| block.members.clone()).unwrap_or_default();
+
+        // whatever the characters land on is overwritten, even part of a larger element
+        members.retain(|member| {
+            bytes
+                .iter()
+                .all(|(offset, _)| !(member.offset..member.offset + member.size).contains(offset))
+        });
+
+        for (offset, index) in bytes {
+            members.push(BlockMember {
+                name: format!("{}[{}]", destination, index),
+                offset,
+                size: 1,
+                value: Some(characters[index].to_string()),
+                points_to: None,
+            });
+        }
+
+        members.sort_by_key(|member| member.offset);
+
+        memory.update_members(pointer, members)?;
+    }
+
+    Ok(())
+}
+
+/// Counts the characters of a null-terminated string before its terminator, like `strlen(s)`
+///
+/// # Arguments
+/// - `name`: The name of the pointer or the array the string starts at
+/// - `symbols`: A reference to the symbol table
+/// - `memory`: A reference to the `MemoryModel` the statements are analyzed in
+/// - `line`: The line of the statement
+/// - `column`: The column errors point at
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - `usize`: The length of the string
+///    - [Error](crate::error::Error): An error if `name` doesn't hold `char`s or holds no null
+///      terminator from where it points
+pub(crate) fn strlen(
+    name: &str,
+    symbols: &IndexMap<String, Symbol>,
+    memory: &dyn MemoryModel,
+    line: usize,
+    column: usize,
+) -> Result<usize> {
+    let range = char_range(name, TokenKind::Strlen, symbols, memory.pointer_size(), line, column)?;
+
+    Ok(terminated(&range, name, TokenKind::Strlen, symbols, memory, line, column)?.len() - 1)
+}
+
+/// Gets the elements a pointer or an array of `char`s refers to, from where it points
+fn char_range(
+    name: &str,
+    function: TokenKind,
+    symbols: &IndexMap<String, Symbol>,
+    pointer_size: usize,
+    line: usize,
+    column: usize,
+) -> Result<ElementRange> {
+    let range = element_range(name, function, symbols, pointer_size, line, column)?;
+
+    if range.vtype != Type::Char {
+        return Err(AnalyzerError(
+            format!(
+                "Cannot pass `{}` to `{}`, its elements are `{}`s and not `char`s",
+                name, function, range.vtype
+            ),
+            line,
+            column,
+        ));
+    }
+
+    Ok(range)
+}
+
+/// Gets the characters `strcpy` copies, along with the null terminator ending them
+fn string_characters(
+    source: &Expr,
+    symbols: &IndexMap<String, Symbol>,
+    memory: &dyn MemoryModel,
+    line: usize,
+    column: usize,
+) -> Result<Vec<char>> {
+    match source {
+        Expr::Literal(Lit::Str(text)) => match unescape_string(text) {
+            Some(string) => Ok(string.chars().chain(['\0']).collect()),
+            None => Err(AnalyzerError(
+                format!("String `\"{}\"` passed to `strcpy` has an invalid escape", text),
+                line,
+                column,
+            )),
+        },
+
+        Expr::Ident(name) => {
+            let range =
+                char_range(name, TokenKind::Strcpy, symbols, memory.pointer_size(), line, column)?;
+
+            terminated(&range, name, TokenKind::Strcpy, symbols, memory, line, column)
+        }
+
+        source => Err(AnalyzerError(
+            format!(
+                "Expected string literal or pointer or array of `char`s passed to `strcpy` but \
+                 found `{}`",
+                source
+            ),
+            line,
+            column,
+        )),
+    }
+}
+
+/// Reads the characters of a range up to and including the first null terminator
+fn terminated(
+    range: &ElementRange,
+    name: &str,
+    function: TokenKind,
+    symbols: &IndexMap<String, Symbol>,
+    memory: &dyn MemoryModel,
+    line: usize,
+    column: usize,
+) -> Result<Vec<char>> {
+    let mut characters = Vec::new();
+
+    for (index, element) in
+        read_elements(range, range.available, symbols, memory).into_iter().enumerate()
+    {
+        let Some(element) = element else {
+            return Err(AnalyzerError(
+                format!(
+                    "`{}` reads `{}[{}]`, which holds garbage, nothing was written to it yet",
+                    function, name, index
+                ),
+                line,
+                column,
+            ));
+        };
+
+        let character = element.chars().next().unwrap_or('\0');
+        characters.push(character);
+
+        if character == '\0' {
+            return Ok(characters);
+        }
+    }
+
+    Err(AnalyzerError(
+        format!(
+            "`{}` runs past the end of `{}` without finding a null terminator in its {} bytes",
+            function, name, range.available
+        ),
+        line,
+        column,
+    ))
+}
+
+/// Finds where the bytes a copy writes past the end of a heap block land, as the starting position of
+/// the allocated or leaked block each lands in, its offset in that block and its index from where the
+/// copy started. Bytes landing in memory no allocated block holds are left out
+fn overrun_bytes(
+    range: &ElementRange,
+    bytes: usize,
+    memory: &dyn MemoryModel,
+) -> Vec<(usize, usize, usize)> {
+    let Storage::Block { block, .. } = range.storage else {
+        return Vec::new();
+    };
+
+    let heap = memory.heap();
+
+    (range.available..bytes)
+        .filter_map(|index| {
+            let position = block + range.start + index;
+
+            heap.iter()
+                .find(|block| (block.pointer..block.pointer + block.size).contains(&position))
+                .filter(|block| {
+                    matches!(block.block_state, HeapBlockState::Allocated | HeapBlockState::Leaked)
+                })
+                .map(|block| (block.pointer, position - block.pointer, index))
+        })
+        .collect()
+}
+
+/// Gets the elements a pointer or an array refers to, from where it points
+fn element_range(
+    name: &str,
+    function: TokenKind,
+    symbols: &IndexMap<String, Symbol>,
+    pointer_size: usize,
+    line: usize,
+    column: usize,
+) -> Result<ElementRange> {
+    let error = |message: String| Err(AnalyzerError(message, line, column));
+
+    match symbols.get(name) {
+        Some(Symbol::Array { vtype, size, .. }) if *vtype != Type::String => Ok(ElementRange {
+            storage: Storage::Array(name.to_string()),
+            vtype: vtype.clone(),
+            element_size: vtype.get_variable_size(pointer_size),
+            start: 0,
+            available: *size,
+        }),
+
+        Some(Symbol::Array { .. }) => {
+            error(format!("Cannot pass `{}` to `{}`, it holds `std::string`s", name, function))
+        }
+
+        Some(Symbol::Pointer {
+            ptype,
+            value,
+            heap_pointer,
+            allocation_type,
+            indirection,
+            value_size,
+            offset,
+            ..
+        }) => {
+            let reason = match allocation_type {
+                AllocationType::Heap => None,
+                AllocationType::Null => Some(format!("Cannot pass null pointer `{}`", name)),
+                AllocationType::Dangling => {
+                    Some(format!("Cannot pass dangling pointer `{}`", name))
+                }
+                AllocationType::Mapped => {
+                    Some(format!("Cannot pass `{}`, it points to a mapping", name))
+                }
+                AllocationType::Stack => Some(format!(
+                    "Cannot pass `{}`, it doesn't point to a heap block or an array",
+                    name
+                )),
+            };
+
+            if let Some(reason) = reason {
+                return error(format!("{} to `{}`", reason, function));
+            }
+
+            if *indirection > 1 {
+                return error(format!(
+                    "Passing pointer to pointer `{}` to `{}` is not supported yet",
+                    name, function
+                ));
+            }
+
+            if *ptype == Type::Void {
+                return error(format!(
+                    "Cannot pass `void*` `{}` to `{}`, cast it to a typed pointer first",
+                    name, function
+                ));
+            }
+
+            if matches!(ptype, Type::Class(_))
+                || matches!(value.as_deref(), Some(Symbol::Object { .. }))
+            {
+                return error(format!(
+                    "Cannot pass `{}` to `{}`, it points to an object",
+                    name, function
+                ));
+            }
+
+            let Some(heap_pointer) = heap_pointer else {
+                return error(format!("Heap pointer not found for `{}`", name));
+            };
+
+            Ok(ElementRange {
+                storage: Storage::Block {
+                    pointer: name.to_string(),
+                    block: heap_pointer - offset,
+                },
+                vtype: ptype.clone(),
+                element_size: ptype.get_variable_size(pointer_size),
+                start: *offset,
+                available: value_size.saturating_sub(*offset),
+            })
+        }
+
+        Some(_) => error(format!(
+            "Cannot pass `{}` to `{}`, it isn't a pointer or an array",
+            name, function
+        )),
+
+        None => error(format!("Pointer or array `{}` not found!", name)),
+    }
+}
+
+/// Evaluates the number of bytes passed to `memset` or `memcpy`
+fn byte_count(
+    size: &Expr,
+    function: TokenKind,
+    symbols: &IndexMap<String, Symbol>,
+    line: usize,
+    column: usize,
+) -> Result<usize> {
+    match evaluate_expression(size, symbols, line, column)? {
+        Lit::Int(bytes) if bytes >= 0 => Ok(bytes as usize),
+        _ => Err(AnalyzerError(
+            format!("Size `{}` passed to `{}` isn't a number of bytes", size, function),
+            line,
+            column,
+        )),
+    }
+}
+
+/// Checks that a number of bytes fits in a range and covers whole elements of it
+///
+/// # Returns
+/// - `Result<(), String>`: `Ok(())` if the bytes fit, or why they don't
+fn bounds(
+    range: &ElementRange,
+    name: &str,
+    function: TokenKind,
+    bytes: usize,
+) -> std::result::Result<(), String> {
+    if bytes > range.available {
+        return Err(format!(
+            "`{}` of {} bytes runs past the end of `{}`, which has {} bytes from where it starts",
+            function, bytes, name, range.available
+        ));
+    }
+
+    if !bytes.is_multiple_of(range.element_size) {
+        return Err(format!(
+            "`{}` of {} bytes ends partway through an element of `{}`, whose `{}` elements are {} \
+             bytes each",
+            function, bytes, name, range.vtype, range.element_size
+        ));
+    }
+
+    Ok(())
+}
+
+/// Gets the value of an element whose every byte is `byte`
+fn filled_element(
+    byte: u8,
+    vtype: &Type,
+    pointer_size: usize,
+    line: usize,
+    column: usize,
+) -> Result<String> {
+    let size = vtype.get_variable_size(pointer_size);
+    let bits = (0..size).fold(0u64, |bits, _| (bits << 8) | byte as u64);
+
+    // floating point values are made of the same bits, they aren't converted from the integer
+    let value = match vtype {
+        Type::Float => Lit::Float32(f32::from_bits(bits as u32)),
+        Type::Double => Lit::Float(f64::from_bits(bits)),
+        vtype => cast_literal(Lit::Int(bits as i128), vtype, pointer_size, line, column)?.0,
+    };
+
+    Ok(value.to_string())
+}
+
+/// Reads a number of elements of a range as they are, `None` for the elements nothing was written to yet
+fn read_elements(
+    range: &ElementRange,
+    count: usize,
+    symbols: &IndexMap<String, Symbol>,
+    memory: &dyn MemoryModel,
+) -> Vec<Option<String>> {
+    let offsets = (0..count).map(|index| range.start + index * range.element_size);
+
+    match &range.storage {
+        Storage::Array(name) => match symbols.get(name) {
+            Some(Symbol::Array { elements, .. }) => offsets
+                .map(|offset| elements.get(offset / range.element_size).cloned().flatten())
+                .collect(),
+            _ => vec![None; count],
+        },
+
+        Storage::Block { block, .. } => {
+            let block = memory.block(*block);
+            let members = block.and_then(|block| block.members.clone());
+
+            // a block of a single value written through `*p`, or zeroed by `calloc`, keeps what it
+            // holds in its contents rather than in elements, an empty block was never written
+            let contents =
+                block.map(|block| block.metadata.clone()).filter(|value| !value.is_empty());
+
+            offsets
+                .map(|offset| {
+                    match members.iter().flatten().find(|member| member.offset == offset) {
+                        Some(member) => member.value.clone(),
+                        None => contents.clone(),
+                    }
+                })
+                .collect()
+        }
+    }
+}
+
+/// Writes elements of a range one after another, from its start
+fn write_elements(
+    range: &ElementRange,
+    values: Vec<Option<String>>,
+    symbols: &mut IndexMap<String, Symbol>,
+    memory: &mut dyn MemoryModel,
+) -> Result<()> {
+    match &range.storage {
+        Storage::Array(name) => {
+            if let Some(Symbol::Array { elements, .. }) = symbols.get_mut(name) {
+                let first = range.start / range.element_size;
+
+                for (element, value) in elements.iter_mut().skip(first).zip(values) {
+                    *element = value;
+                }
+            }
+
+            Ok(())
+        }
+
+        Storage::Block { pointer, block } => {
+            let elements = values
+                .into_iter()
+                .enumerate()
+                .map(|(index, value)| (range.start + index * range.element_size, value))
+                .collect();
+
+            write_heap_elements(pointer, *block, range.element_size, elements, symbols, memory)
+        }
+    }
+}
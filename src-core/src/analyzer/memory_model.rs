@@ -0,0 +1,342 @@
+//! The memory model the analyzer lays symbols out in
+//!
+//! Statement handling only talks to the [MemoryModel](crate::analyzer::memory_model::MemoryModel) trait, so
+//! a different machine (flat embedded RAM, a heap that is only reachable through references) can be added
+//! by implementing the trait without touching how statements are analyzed
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+
+use super::heap_allocator::{BlockMember, CopyEvent, FreeRange, HeapBlock, HeapGrowth};
+use super::options::AnalyzerOptions;
+use super::random_heap_allocator::HeapAllocator;
+
+/// The size of a page, which mappings are made of
+pub(crate) const PAGE_SIZE: usize = 4096;
+
+/// The most bytes the heap grows to, every byte of the heap is a cell of its own
+pub(crate) const MAX_HEAP_SIZE: usize = 1 << 16;
+
+/// Represents a region mapped like an anonymous `mmap`, which lives in an area of its own between the
+/// heap and the stack and stays mapped until `munmap` unmaps it or the program ends
+///
+/// # Fields
+/// - `owner`: The pointer the region was mapped for
+/// - `start`: The starting position of the region in the mapped area
+/// - `size`: The size of the region in bytes, a whole number of pages
+/// - `requested`: The number of bytes asked for, the rest of the last page is unused
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct MappedRegion {
+    pub owner: String,
+    pub start: usize,
+    pub size: usize,
+    pub requested: usize,
+}
+
+/// Represents the rules of the machine being simulated: how big pointers are and how the heap hands out,
+/// frees and tracks blocks
+///
+/// Models must be `Send` since the analysis is awaited across threads on desktop
+pub(crate) trait MemoryModel: Send {
+    /// Gets the size of a pointer in bytes
+    fn pointer_size(&self) -> usize;
+
+    /// Allocates a block for the pointer with the given identifier
+    ///
+    /// # Arguments
+    /// - `identifier`: The identifier of the pointer that owns the block
+    /// - `size`: The size of the block in bytes
+    /// - `starting_pointers`: The starting pointers of previous runs, so blocks stay where they were
+    ///
+    /// # Returns
+    /// - [Result](crate::error::Result): A result containing either:
+    ///    - `usize`: The starting position of the block in the heap
+    ///    - [Error](crate::error::Error): An error if there is insufficient memory
+    fn allocate(
+        &mut self,
+        identifier: &str,
+        size: usize,
+        starting_pointers: &mut IndexMap<String, usize>,
+    ) -> Result<usize>;
+
+    /// Resizes the block starting at the specified position like `realloc`, moving it if it can't be
+    /// resized in place
+    ///
+    /// # Arguments
+    /// - `identifier`: The identifier of the pointer that owns the block
+    /// - `pointer`: The starting position of the block in the heap
+    /// - `old_size`: The size of the block in bytes
+    /// - `new_size`: The size the block is resized to in bytes
+    /// - `starting_pointers`: The starting pointers of previous runs
+    ///
+    /// # Returns
+    /// - [Result](crate::error::Result): A result containing either:
+    ///    - `(usize, Option<CopyEvent>)`: The starting position of the block and the copy that moved it, if
+    ///      it was moved
+    ///    - [Error](crate::error::Error): An error if there is insufficient memory
+    fn reallocate(
+        &mut self,
+        identifier: &str,
+        pointer: usize,
+        old_size: usize,
+        new_size: usize,
+        starting_pointers: &mut IndexMap<String, usize>,
+    ) -> Result<(usize, Option<CopyEvent>)>;
+
+    /// Frees the block starting at the specified position
+    fn free(&mut self, pointer: usize, size: usize);
+
+    /// Marks the block starting at the specified position as leaked
+    fn leak(&mut self, pointer: usize, size: usize);
+
+    /// Updates the value shown for the block starting at the specified position
+    fn update_metadata(&mut self, pointer: usize, metadata: String) -> Result<()>;
+
+    /// Updates the fields shown for the object held by the block starting at the specified position
+    fn update_members(&mut self, pointer: usize, members: Vec<BlockMember>) -> Result<()>;
+
+    /// Hands the block starting at the specified position to another pointer
+    fn update_owner(&mut self, pointer: usize, identifier: String) -> Result<()>;
+
+    /// Records that a pointer still points to the block after it was freed
+    fn insert_dangling_pointer(&mut self, pointer: usize, identifier: String) -> Result<()>;
+
+    /// Records that a pointer no longer points to the freed block
+    fn remove_dangling_pointer(&mut self, pointer: usize, identifier: String) -> Result<()>;
+
+    /// Gets the block that starts at the specified position
+    fn block(&self, pointer: usize) -> Option<&HeapBlock>;
+
+    /// Validates the model's internal bookkeeping, returning a description of the first inconsistency
+    fn check_consistency(&self) -> std::result::Result<(), String>;
+
+    /// Builds a list of all memory blocks in the heap in a format suitable for visualization
+    fn heap(&self) -> Vec<HeapBlock>;
+
+    /// Gets the ranges the heap can still hand out, in the order the allocator looks through them
+    fn free_list(&self) -> Vec<FreeRange>;
+
+    /// Gets the bytes held by allocated and leaked blocks, without building the heap
+    fn in_use(&self) -> usize;
+
+    /// Gets the size of the heap in bytes, including the bytes no block holds
+    fn capacity(&self) -> usize;
+
+    /// Gets every time the heap grew to make room for a block, in the order it grew
+    fn growth(&self) -> &[HeapGrowth];
+
+    /// Maps a region for the pointer with the given identifier, rounded up to whole pages and placed after
+    /// the regions mapped before it
+    ///
+    /// # Arguments
+    /// - `identifier`: The identifier of the pointer the region is mapped for
+    /// - `size`: The number of bytes asked for
+    fn map(&mut self, identifier: &str, size: usize);
+
+    /// Unmaps the region mapped for the pointer with the given identifier
+    ///
+    /// # Arguments
+    /// - `identifier`: The identifier of the pointer the region was mapped for
+    ///
+    /// # Returns
+    /// - `Option<MappedRegion>`: The region that was unmapped, `None` if no region was mapped for the
+    ///   pointer
+    fn unmap(&mut self, identifier: &str) -> Option<MappedRegion>;
+
+    /// Gets the regions mapped so far, in the order they were mapped
+    fn mappings(&self) -> Vec<MappedRegion>;
+}
+
+/// Represents a hosted C++ program: a stack, and a heap managed by a `malloc` that places blocks randomly
+///
+/// # Fields
+/// - `allocator`: The [HeapAllocator](crate::analyzer::random_heap_allocator::HeapAllocator) managing the heap
+/// - `pointer_size`: The size of a pointer in bytes, set by the architecture
+/// - `mappings`: The regions mapped so far, in the order they were mapped
+/// - `in_use`: The bytes held by allocated and leaked blocks, kept up to date so long programs don't build
+///   the heap to count them
+pub(crate) struct HostedMemoryModel {
+    allocator: HeapAllocator,
+    pointer_size: usize,
+    mappings: Vec<MappedRegion>,
+    in_use: usize,
+}
+
+impl HostedMemoryModel {
+    /// Creates the memory model for the given options
+    ///
+    /// # Arguments
+    /// - `options`: The [AnalyzerOptions](crate::analyzer::AnalyzerOptions) to run with
+    ///
+    /// # Returns
+    /// - [HostedMemoryModel](crate::analyzer::memory_model::HostedMemoryModel): A new memory model
+    pub(crate) fn new(options: &AnalyzerOptions) -> Self {
+        let mut allocator = match options.layout {
+            // a heap that fills the panel grows by another panel, rather than doubling past the screen
+            Some(layout) => {
+                let mut allocator =
+                    HeapAllocator::new_infinite(layout.heap_size(), 2.0, Some(MAX_HEAP_SIZE));
+                allocator.grow_by(layout.heap_size());
+                allocator
+            }
+            None => HeapAllocator::new_infinite(20, 2.0, Some(MAX_HEAP_SIZE)),
+        };
+
+        if let Some(seed) = options.seed {
+            allocator.seed(seed);
+        }
+
+        // Blocks are never smaller than the alignment, like the chunks of a real `malloc`
+        let heap_alignment = options.architecture.heap_alignment();
+        allocator.align(heap_alignment, heap_alignment);
+
+        Self {
+            allocator,
+            pointer_size: options.architecture.pointer_size(),
+            mappings: Vec::new(),
+            in_use: 0,
+        }
+    }
+}
+
+impl MemoryModel for HostedMemoryModel {
+    fn pointer_size(&self) -> usize {
+        self.pointer_size
+    }
+
+    fn allocate(
+        &mut self,
+        identifier: &str,
+        size: usize,
+        starting_pointers: &mut IndexMap<String, usize>,
+    ) -> Result<usize> {
+        check_heap_limit(size)?;
+
+        let pointer = self.allocator.allocate_and_write(identifier, size, starting_pointers)?;
+        self.in_use += size;
+
+        Ok(pointer)
+    }
+
+    fn reallocate(
+        &mut self,
+        identifier: &str,
+        pointer: usize,
+        old_size: usize,
+        new_size: usize,
+        starting_pointers: &mut IndexMap<String, usize>,
+    ) -> Result<(usize, Option<CopyEvent>)> {
+        check_heap_limit(new_size)?;
+
+        let reallocation = self.allocator.reallocate(
+            identifier,
+            pointer,
+            old_size,
+            new_size,
+            starting_pointers,
+        )?;
+        self.in_use = self.in_use - old_size + new_size;
+
+        Ok(reallocation)
+    }
+
+    fn free(&mut self, pointer: usize, size: usize) {
+        self.allocator.free(pointer, size);
+        self.in_use -= size;
+    }
+
+    fn leak(&mut self, pointer: usize, size: usize) {
+        self.allocator.leak(pointer, size);
+    }
+
+    fn update_metadata(&mut self, pointer: usize, metadata: String) -> Result<()> {
+        self.allocator.update_metadata(pointer, metadata)
+    }
+
+    fn update_members(&mut self, pointer: usize, members: Vec<BlockMember>) -> Result<()> {
+        self.allocator.update_members(pointer, members)
+    }
+
+    fn update_owner(&mut self, pointer: usize, identifier: String) -> Result<()> {
+        self.allocator.update_pointer_identifier(pointer, identifier)
+    }
+
+    fn insert_dangling_pointer(&mut self, pointer: usize, identifier: String) -> Result<()> {
+        self.allocator.insert_dangling_pointer(pointer, identifier)
+    }
+
+    fn remove_dangling_pointer(&mut self, pointer: usize, identifier: String) -> Result<()> {
+        self.allocator.remove_dangling_pointer(pointer, identifier)
+    }
+
+    fn block(&self, pointer: usize) -> Option<&HeapBlock> {
+        self.allocator.block(pointer)
+    }
+
+    fn check_consistency(&self) -> std::result::Result<(), String> {
+        self.allocator.check_consistency()
+    }
+
+    fn heap(&self) -> Vec<HeapBlock> {
+        self.allocator.get_heap()
+    }
+
+    fn free_list(&self) -> Vec<FreeRange> {
+        self.allocator.free_ranges()
+    }
+
+    fn in_use(&self) -> usize {
+        self.in_use
+    }
+
+    fn capacity(&self) -> usize {
+        self.allocator.capacity()
+    }
+
+    fn growth(&self) -> &[HeapGrowth] {
+        self.allocator.growth()
+    }
+
+    fn map(&mut self, identifier: &str, size: usize) {
+        let start = self.mappings.last().map_or(0, |region| region.start + region.size);
+
+        self.mappings.push(MappedRegion {
+            owner: identifier.to_owned(),
+            start,
+            size: size.div_ceil(PAGE_SIZE) * PAGE_SIZE,
+            requested: size,
+        });
+    }
+
+    fn unmap(&mut self, identifier: &str) -> Option<MappedRegion> {
+        let index = self.mappings.iter().position(|region| region.owner == identifier)?;
+
+        Some(self.mappings.remove(index))
+    }
+
+    fn mappings(&self) -> Vec<MappedRegion> {
+        self.mappings.clone()
+    }
+}
+
+/// Checks that a block fits in the heap at all, before the heap grows for it
+///
+/// # Arguments
+/// - `size`: The size of the block in bytes
+///
+/// # Returns
+/// - `Result<()>`: An error if the block is bigger than [MAX_HEAP_SIZE]
+fn check_heap_limit(size: usize) -> Result<()> {
+    if size > MAX_HEAP_SIZE {
+        return Err(format!(
+            "Cannot allocate {} bytes, the heap holds at most {} bytes",
+            size, MAX_HEAP_SIZE
+        )
+        .into());
+    }
+
+    Ok(())
+}
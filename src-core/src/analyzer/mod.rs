@@ -1,22 +1,109 @@
 //! # Analyzer
 //! Responsible for analyzing the parsed source code and generating a visualization of the stack and the heap
 
-mod heap_allocator;
+pub(crate) mod address_space;
+mod allocator_exercise;
+mod array;
+mod class;
+mod cross_reference;
+mod frame;
+pub(crate) mod heap_allocator;
+mod heap_order;
+mod heatmap;
 mod helpers;
+mod memory_edit;
+mod memory_functions;
+mod memory_model;
+mod mutex;
+mod notes;
+mod options;
+mod overview;
+mod preview;
 mod random_heap_allocator;
-mod r#type;
+mod raw_memory;
+mod reachability;
+mod result;
+mod smart_pointer;
+mod string;
+mod tags;
+pub(crate) mod r#type;
+mod union;
+mod what_if;
 
+pub use address_space::{Segment, SegmentKind};
+pub use allocator_exercise::{
+    AllocatorExercise, AllocatorReport, WorkloadStep, WorkloadStepReport,
+};
 use async_trait::async_trait;
-use heap_allocator::HeapBlock;
-use helpers::{validate_pointer_assignment, validate_variable_assignment};
+pub use class::ClassField;
+pub use cross_reference::{
+    CrossReference, DataRace, HeapGrowthEvent, HeapLifetime, IgnoredStatement, LockEvent,
+    LoopIteration, LossyCast, ScopeReference, SourceRef, Temporary, ThreadStep, TimelineChunk,
+    TimelineChunks, ValueChange, ValueCopy, ValueMove,
+};
+pub use frame::{RegisterArgument, ScopeExit, StackFrame};
+pub use heap_allocator::{FreeRange, GrowthReason, HeapBlock, HeapGrowth};
+pub use heatmap::AccessCount;
+use helpers::{
+    block_aliases, cast_literal, cast_pointer, check_delete_form, check_integer_range,
+    copy_pointer, evaluate_condition, evaluate_expression, fold_expression, leave_dangling,
+    misaligned_cast, move_pointer, narrowed_literal, pointee_literal, pointee_size,
+    pointer_argument, pointer_step, release_block, symbol_size, unique_copy_error,
+    validate_pointer_assignment, validate_pointer_cast, validate_raw_pointer,
+    validate_variable_assignment, value_copies, value_moves, variable_literal, write_through,
+};
 use indexmap::IndexMap;
+pub use memory_edit::{free_block, set_variable_value};
+pub use memory_model::MappedRegion;
+pub use notes::{place_notes, Note, NotePlace, NoteTarget, PlacedNote};
+pub use options::{
+    AnalyzerOptions, Architecture, CallingConvention, HeapOrder, LayoutHint, Strictness,
+};
+pub use overview::{HeapCluster, HeapOverview, UnallocatedRun, ZoomLevel, OVERVIEW_THRESHOLD};
+pub use preview::{preview_statement, StatementPreview};
+pub use reachability::{access_paths, AccessPath};
+pub use result::{AnalysisError, AnalysisErrorDetails, AnalysisResult};
 use serde::Serialize;
+pub use string::StringBuffer;
+pub use tags::{read_tag_annotations, MemoryTag};
+pub use union::UnionMember;
+pub use what_if::{query_allocation, AllocationQuery, Placement, PlacementStrategy};
 
-use self::random_heap_allocator::HeapAllocator;
+use self::array::{
+    array_size, assign_heap_element, element_index, element_literal, element_name,
+    out_of_bounds_write, write_pointee, Bounds,
+};
+use self::class::{
+    block_members, constructor_name, copy_fields, destructor_name, enter_fields, heap_destructor,
+    leave_fields, method_name, object_size, pointer_member_literal, public_field, resolve_method,
+    this_member_statement, this_pointer, Class,
+};
+use self::cross_reference::ignored_statements;
+use self::frame::{
+    CallStack, Function, Thread, CALL_DEPTH_LIMIT, MAX_CALL_DEPTH, MAX_LOOP_ITERATIONS,
+};
+use self::heatmap::{address_block, heap_read, heap_write, pointer_block};
+use self::memory_functions::{memcpy, memset, overlapping_copy, strcpy, string_overrun, strlen};
+use self::memory_model::{HostedMemoryModel, MemoryModel, PAGE_SIZE};
+use self::mutex::{heap_accesses, held_locks, lock_owner, mutex_owner, mutex_size};
 use self::r#type::Type;
+use self::raw_memory::Location;
+use self::smart_pointer::{
+    allocate_control_block, release_shared, retain, smart_pointer_statement,
+};
+use self::string::{assign_string, string_contents};
+use self::union::{union_size, write_member};
 use crate::{
-    error::{Error::AnalyzerError, Result},
+    diagnostics::{finalize, DEFAULT_DIAGNOSTIC_LIMIT},
+    error::{
+        Error::{AnalyzerError, InternalError},
+        Result,
+    },
+    export::format_address,
+    lexer::token::TokenKind,
     parser::ast::{self, Statement},
+    rng::Rng,
+    stack::on_deep_stack,
 };
 
 /// Represents the type of memory allocation for a symbol.
@@ -25,46 +112,86 @@ use crate::{
 ///
 /// - `Stack`: Represents memory allocated on the stack.
 /// - `Heap`: Represents memory allocated on the heap.
+/// - `Mapped`: Represents a region mapped with `mmap`, outside the heap.
 /// - `None`: Represents no allocation or undefined allocation type.
 #[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
 pub enum AllocationType {
     Stack,
     Heap,
+    Mapped,
     Dangling,
     Null,
 }
 
 /// Represents different types of symbols used in the language.
 ///
-/// The `Symbol` enum defines three main categories of symbols:
+/// The `Symbol` enum defines four main categories of symbols:
 ///
 /// - **Variable**:
 ///   - `vtype`: Type of the variable.
 ///   - `name`: Variable's name.
 ///   - `value`: Optional value of the variable.
 ///   - `size`: Size of the variable.
+///   - `string`: Where the characters of a `std::string` are kept, `None` for other types.
 ///
 /// - **Pointer**:
 ///   - `ptype`: Type of the pointer.
 ///   - `name`: Pointer's name.
 ///   - `value`: Optional value, pointing to another `Symbol` (if applicable).
 ///   - `heap_pointer`: Optional pointer to a location in the heap.
+///   - `offset`: How many bytes `heap_pointer` was moved into its block by pointer arithmetic.
 ///   - `allocation_type`: Type of memory allocation (e.g., `Stack`, `Heap`).
 ///   - `pointer_size`: Size of the pointer.
 ///   - `value_size`: Size of the value pointed to.
+///   - `indirection`: Number of `*` in the pointer's type, `2` for a pointer to a pointer.
+///   - `unique`: Whether the pointer is a `std::unique_ptr`, which frees its block when it goes away.
+///   - `shared`: Whether the pointer is a `std::shared_ptr`, which frees its block once its last owner
+///     goes away.
+///   - `control_block`: Where the control block counting the owners of a `std::shared_ptr`'s block starts
+///     in the heap, `None` while it is null.
 ///
 /// - **Literal**:
 ///   - `value`: The literal's value as a string.
 ///
+/// - **Union**:
+///   - `union_name`: Name of the union the variable is an instance of.
+///   - `name`: Variable's name.
+///   - `members`: The members of the union, each reading the same bytes.
+///   - `active`: The member written last, `None` until a member is written.
+///   - `bytes`: The bytes the members share.
+///   - `size`: Size of the union, which is the size of its largest member.
+///
+/// - **Object**:
+///   - `class_name`: Name of the class the variable is an object of.
+///   - `name`: Variable's name.
+///   - `fields`: The fields of the object, in the order they are laid out.
+///   - `size`: Size of the object, its fields along with the padding aligning them.
+///
+/// - **Array**:
+///   - `vtype`: Type of the elements.
+///   - `name`: Array's name.
+///   - `dimensions`: How many elements each dimension has, rows before columns.
+///   - `elements`: The values of the elements, row after row like they are laid out, `None` while
+///     uninitialized.
+///   - `size`: Size of the array, every element of it.
+///
+/// - **Mutex**:
+///   - `name`: Mutex's name.
+///   - `owner`: The thread that locked the mutex, `0` for the main thread, `None` while it is unlocked.
+///   - `size`: Size of the mutex.
+///
 /// This enum is used to manage and categorize symbols in various contexts such as variable declarations,
 /// pointer management, and literal values.
 #[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
 pub enum Symbol {
     Variable {
         vtype: Type,
         name: String,
         value: Option<String>,
         size: usize,
+        string: Option<StringBuffer>,
     },
 
     Pointer {
@@ -75,11 +202,46 @@ pub enum Symbol {
         allocation_type: AllocationType,
         pointer_size: usize,
         value_size: usize,
+        indirection: usize,
+        unique: bool,
+        shared: bool,
+        control_block: Option<usize>,
+        offset: usize,
     },
 
     Literal {
         value: String,
     },
+
+    Union {
+        union_name: String,
+        name: String,
+        members: Vec<UnionMember>,
+        active: Option<String>,
+        bytes: Vec<u8>,
+        size: usize,
+    },
+
+    Object {
+        class_name: String,
+        name: String,
+        fields: Vec<ClassField>,
+        size: usize,
+    },
+
+    Array {
+        vtype: Type,
+        name: String,
+        dimensions: Vec<usize>,
+        elements: Vec<Option<String>>,
+        size: usize,
+    },
+
+    Mutex {
+        name: String,
+        owner: Option<usize>,
+        size: usize,
+    },
 }
 
 #[async_trait]
@@ -89,25 +251,39 @@ pub trait AnalyzerState {
 }
 
 #[derive(Default)]
-pub struct Analyzer {}
+pub struct Analyzer {
+    options: AnalyzerOptions,
+}
 
 impl Analyzer {
+    /// Creates a new analyzer that runs with the given options
+    ///
+    /// # Arguments
+    /// - `options`: The [AnalyzerOptions](crate::analyzer::AnalyzerOptions) to run with
+    ///
+    /// # Returns
+    /// - [Analyzer](crate::analyzer::Analyzer): A new analyzer
+    pub fn new(options: AnalyzerOptions) -> Self {
+        Self { options }
+    }
+
     /// Analyzes statements produced by the parser and generates a visualization of the stack and heap.
     ///
     /// This function processes a vector of statements to generate a visual representation of the stack and heap.
-    /// It uses the application state to determine the heap size, which is adjusted based on the user's screen size.
+    /// The heap starts as big as the panel showing it when the options carry a [LayoutHint], so the
+    /// visualization fills the panel before it has to scroll.
     ///
     /// # Arguments
     ///
-    /// - `state`: A reference to the application state, which provides information on heap size.
+    /// - `state`: A reference to the application state, which keeps where the blocks of each pointer were
+    ///   placed between runs.
     /// - `statements`: A vector of statements to be analyzed.
     ///
     /// # Returns
     ///
-    /// - `Result<(Vec<Symbol>, Vec<HeapBlock>), Error>`: A result containing either:
-    ///   - A tuple with:
-    ///     - `Vec<Symbol>`: A vector of symbols representing the stack and heap data.
-    ///     - `Vec<HeapBlock>`: A vector of heap blocks representing memory allocations.
+    /// - `Result<AnalysisResult, Error>`: A result containing either:
+    ///   - [AnalysisResult]: The stack, heap, frames and scope exits the program ended with, what the
+    ///     memory looks like and the statements that produced it.
     ///
     ///   Or:
     ///   - An `Error` if the analysis fails.
@@ -117,506 +293,3348 @@ impl Analyzer {
         &self,
         statements: Vec<Statement>,
         state: &mut S,
-    ) -> Result<(Vec<Symbol>, Vec<HeapBlock>)> {
+    ) -> Result<AnalysisResult> {
         let mut starting_pointers = state.get_starting_pointers().await;
 
         let mut stack_symbols: IndexMap<String, Symbol> = IndexMap::new();
-        let mut allocator = HeapAllocator::new_infinite(20, 2.0, None);
+        let mut memory = self.memory_model();
+        let mut call_stack = CallStack::default();
+        call_stack.sources.declarations.push(Vec::new());
 
-        for statement in statements {
-            self.analyze_statement(
-                statement,
+        let ignored = ignored_statements(&statements);
+
+        on_deep_stack(|| {
+            self.analyze_block(
+                statements,
                 &mut stack_symbols,
-                &mut allocator,
+                memory.as_mut(),
                 &mut starting_pointers,
+                &mut call_stack,
+                0,
+                0,
             )?;
-        }
+
+            self.run_threads(
+                &mut stack_symbols,
+                memory.as_mut(),
+                &mut starting_pointers,
+                &mut call_stack,
+            )
+        })?;
 
         let stack_symbols_vec: Vec<Symbol> = stack_symbols.into_iter().map(|(_, v)| v).collect();
 
-        self.clean_starting_pointers(&mut starting_pointers, &stack_symbols_vec);
+        // Pointers in frames and blocks keep their starting pointers too, so their blocks stay put between
+        // runs
+        let all_symbols: Vec<Symbol> = stack_symbols_vec
+            .iter()
+            .chain(call_stack.frames.iter().flat_map(|frame| &frame.symbols))
+            .chain(call_stack.scope_exits.iter().flat_map(|scope_exit| &scope_exit.symbols))
+            .cloned()
+            .collect();
+
+        self.clean_starting_pointers(&mut starting_pointers, &all_symbols);
 
         state.set_starting_pointers(starting_pointers.clone()).await;
 
-        Ok((stack_symbols_vec, allocator.get_heap()))
+        let heap = memory.heap();
+        let heatmap = call_stack.sources.heatmap(&heap);
+        let warnings = finalize(
+            std::mem::take(&mut call_stack.sources.warnings),
+            self.options.diagnostic_limit.unwrap_or(DEFAULT_DIAGNOSTIC_LIMIT),
+        );
+        let mut cross_reference = call_stack.sources.finish(&heap);
+        tags::tag_memory(&mut cross_reference, &stack_symbols_vec, &heap, &self.options);
+
+        let heap_capacity = memory.capacity();
+        let mappings = memory.mappings();
+        let stack_addresses = address_space::stack_addresses(
+            &stack_symbols_vec,
+            address_space::STACK_END_ADDRESS,
+            memory.pointer_size(),
+        );
+        let segments = address_space::address_space(&stack_addresses, heap_capacity, &mappings);
+
+        let mut result = AnalysisResult {
+            stack: stack_symbols_vec,
+            heap,
+            frames: call_stack.frames,
+            scope_exits: call_stack.scope_exits,
+            heap_capacity,
+            free_list: memory.free_list(),
+            mappings,
+            heatmap,
+            heap_overview: None,
+            address_space: segments,
+            stack_addresses,
+            ignored,
+            warnings,
+            cross_reference,
+        };
+
+        heap_order::order_heap(&mut result, self.options.heap_order);
+        result.heap_overview = overview::heap_overview(&result.heap, result.heap_capacity);
+
+        Ok(result)
     }
 
-    /// Cleans up the starting pointers by removing any pointers that are not in the stack symbols vector.
+    /// Analyzes a list of statements, either the whole program, the body of a function or a block.
+    ///
+    /// Function definitions, calls, returns, blocks, conditionals, loops, unions and threads are handled here
+    /// since they need the call stack, every other statement is passed on to
+    /// [analyze_statement](Analyzer::analyze_statement).
     ///
     /// # Arguments
     ///
+    /// - `statements`: The statements to be analyzed.
+    /// - `stack_symbols`: A mutable reference to the symbols of the current frame.
+    /// - `memory`: A mutable reference to the `MemoryModel` the statements are analyzed in.
     /// - `starting_pointers`: A mutable reference to a `IndexMap<String, usize>` containing starting pointers.
-    /// - `stack_symbols_vec`: A reference to a vector of `Symbol` representing the stack symbols.
+    /// - `call_stack`: A mutable reference to the `CallStack` of the program.
+    /// - `scope_depth`: How many blocks deep the statements are within their function.
+    /// - `first_index`: The index of the first statement in the body it belongs to, threads run theirs
+    ///   one at a time.
     ///
     /// # Returns
     ///
-    /// - Nothing
-    fn clean_starting_pointers(
+    /// - `Result<bool, Error>`: A result containing either:
+    ///  - `bool`: Whether a `return` was reached, in which case the rest of the function is skipped.
+    /// - An `Error` if the analysis fails.
+    #[allow(clippy::too_many_arguments)]
+    fn analyze_block(
         &self,
+        statements: Vec<Statement>,
+        stack_symbols: &mut IndexMap<String, Symbol>,
+        memory: &mut dyn MemoryModel,
         starting_pointers: &mut IndexMap<String, usize>,
-        stack_symbols_vec: &Vec<Symbol>,
-    ) {
-        for entry in starting_pointers.clone() {
-            if !stack_symbols_vec.iter().any(|symbol| {
-                let symbol_name = match symbol {
-                    Symbol::Variable { name, .. } => name,
-                    Symbol::Pointer { name, .. } => name,
-                    _ => &String::new(),
-                };
+        call_stack: &mut CallStack,
+        scope_depth: usize,
+        first_index: usize,
+    ) -> Result<bool> {
+        let statement = statements.first();
+        let line = statement.map_or(0, Statement::line);
+        let column = statement.map_or(0, Statement::column);
 
-                symbol_name == &entry.0
-            }) {
-                starting_pointers.shift_remove_entry(&entry.0);
-            }
-        }
+        call_stack.nested(line, column, |call_stack| {
+            self.analyze_block_statements(
+                statements,
+                stack_symbols,
+                memory,
+                starting_pointers,
+                call_stack,
+                scope_depth,
+                first_index,
+            )
+        })
     }
 
-    /// Analyzes a single statement and updates the stack symbols and heap allocator accordingly.
-    ///
-    /// # Arguments
-    ///
-    /// - `statement`: The statement to be analyzed.
-    /// - `stack_symbols`: A mutable reference to a `IndexMap<String, Symbol>` containing stack symbols.
-    /// - `allocator`: A mutable reference to a `HeapAllocator` instance.
-    /// - `starting_pointers`: A mutable reference to a `IndexMap<String, usize>` containing starting pointers.
-    ///
-    /// # Returns
-    ///
-    /// - `Result<(), Error>`: A result containing either:
-    ///  - `Ok(())` if the analysis is successful.
-    /// - An `Error` if the analysis fails.
-    fn analyze_statement(
+    /// Analyzes the statements of a block once [analyze_block](Analyzer::analyze_block) made sure it
+    /// isn't nested too deep, taking the same arguments
+    #[allow(clippy::too_many_arguments)]
+    fn analyze_block_statements(
         &self,
-        statement: Statement,
+        statements: Vec<Statement>,
         stack_symbols: &mut IndexMap<String, Symbol>,
-        allocator: &mut HeapAllocator,
+        memory: &mut dyn MemoryModel,
         starting_pointers: &mut IndexMap<String, usize>,
-    ) -> Result<()> {
-        match statement {
-            ast::Statement::VariableDeclaration {
-                var_type,
-                var_name,
-                value,
-                line,
-                var_ident_column,
-            } => {
-                let value = validate_variable_assignment(
-                    value,
-                    &var_name,
-                    &Type::from_token(var_type)?,
-                    &stack_symbols,
-                    line,
-                    var_ident_column,
-                )?;
+        call_stack: &mut CallStack,
+        scope_depth: usize,
+        first_index: usize,
+    ) -> Result<bool> {
+        let self_check = cfg!(debug_assertions) || self.options.self_check;
 
-                if stack_symbols.contains_key(&var_name) {
-                    return Err(AnalyzerError(
-                        format!("Variable `{}` already declared!", var_name),
-                        line,
-                        var_ident_column,
-                    ));
-                }
+        let base_path = call_stack.sources.path.clone();
 
-                let vtype = Type::from_token(var_type)?;
-                stack_symbols.insert(
-                    var_name.clone(),
-                    Symbol::Variable {
-                        vtype,
-                        name: var_name,
-                        value,
-                        size: vtype.get_size(),
-                    },
-                );
+        for (index, statement) in (first_index..).zip(statements) {
+            let line = statement.line();
+
+            call_stack.sources.path = [base_path.as_slice(), &[index]].concat();
+
+            // a statement the parser skipped takes no step, it is only reported
+            if let Statement::Ignored { .. } = statement {
+                continue;
             }
 
-            ast::Statement::VariableDeclarationWithoutAssignment {
-                var_type,
-                var_name,
-                line,
-                var_ident_column,
-            } => {
-                if stack_symbols.contains_key(&var_name) {
-                    return Err(AnalyzerError(
-                        format!("Variable `{}` already declared!", var_name),
-                        line,
-                        var_ident_column,
-                    ));
-                }
+            call_stack.sources.steps += 1;
+            let source = SourceRef::of_statement(&call_stack.sources.path, &statement);
 
-                let vtype = Type::from_token(var_type)?;
-                stack_symbols.insert(
-                    var_name.clone(),
-                    Symbol::Variable {
-                        vtype,
-                        name: var_name,
-                        value: None,
-                        size: vtype.get_size(),
-                    },
-                );
+            // Calls whose values are used run before the statement, each leaving a temporary that lives
+            // until the end of the statement
+            let mut statement = statement;
+            let mut temporaries = Vec::new();
+
+            for expr in statement.expressions_mut() {
+                temporaries.extend(self.evaluate_calls(
+                    expr,
+                    &source,
+                    stack_symbols,
+                    memory,
+                    starting_pointers,
+                    call_stack,
+                )?);
             }
 
-            ast::Statement::VariableAssignment {
-                var_name,
-                new_value,
-                line,
-                var_ident_column,
-                assignment_column,
-            } => {
-                let cloned_symbols = stack_symbols.clone();
-                if let Some(symbol) = stack_symbols.get_mut(&var_name) {
-                    if let Symbol::Variable { value, vtype, .. } = symbol {
-                        let new_value = validate_variable_assignment(
-                            new_value,
-                            &var_name,
-                            vtype,
-                            &cloned_symbols,
-                            line,
-                            var_ident_column,
-                        )?;
-                        *value = new_value;
-                    } else {
-                        return Err(AnalyzerError(
-                            format!(
-                                "Invalid use case of assignment operator for symbol `{}`",
-                                var_name
-                            ),
-                            line,
-                            assignment_column,
-                        ));
-                    }
-                } else {
-                    return Err(AnalyzerError(
-                        format!("Variable `{}` not found!", var_name),
-                        line,
-                        var_ident_column,
-                    ));
+            if !temporaries.is_empty() {
+                call_stack.sources.record_releases(memory);
+            }
+
+            if self.options.show_temporaries {
+                let destroyed = call_stack.sources.steps.saturating_sub(1);
+
+                for (symbol, created) in temporaries {
+                    call_stack.sources.temporaries.push(Temporary {
+                        function: call_stack.active.last().cloned(),
+                        symbol,
+                        created,
+                        destroyed,
+                        source: source.clone(),
+                    });
                 }
             }
 
-            ast::Statement::PointerDeclaration {
-                base_type,
+            let statement = smart_pointer_statement(statement, stack_symbols)?;
+            let statement = this_member_statement(statement, stack_symbols, call_stack);
+
+            // `delete` runs the destructor of an object allocated with `new` before its block is freed
+            if let Statement::Delete {
                 pointer_name,
-                value,
+                release,
                 line,
                 pointer_ident_column,
-            } => {
-                if stack_symbols.contains_key(&pointer_name) {
-                    return Err(AnalyzerError(
-                        format!("Pointer `{}` already declared!", pointer_name),
-                        line,
-                        pointer_ident_column,
-                    ));
+            } = &statement
+            {
+                check_delete_form(
+                    pointer_name,
+                    *release,
+                    stack_symbols,
+                    &call_stack.sources.delete_forms,
+                    *line,
+                    *pointer_ident_column,
+                )?;
+
+                if let Some(destructor) = heap_destructor(stack_symbols, pointer_name, call_stack) {
+                    self.call_function(
+                        destructor,
+                        Vec::new(),
+                        Some(pointer_name.clone()),
+                        *line,
+                        *pointer_ident_column,
+                        stack_symbols,
+                        memory,
+                        starting_pointers,
+                        call_stack,
+                    )?;
+
+                    call_stack.sources.record_releases(memory);
                 }
+            }
 
-                let value =
-                    validate_pointer_assignment(value, &stack_symbols, line, pointer_ident_column)?;
+            match statement {
+                Statement::FunctionDefinition {
+                    return_type,
+                    function_name,
+                    parameters,
+                    body,
+                    line,
+                    function_ident_column,
+                } => {
+                    if !call_stack.active.is_empty() || scope_depth > 0 {
+                        return Err(AnalyzerError(
+                            "Functions can only be defined at the top level".to_string(),
+                            line,
+                            function_ident_column,
+                        ));
+                    }
 
-                let ptype = Type::from_token(base_type)?;
+                    if call_stack.functions.contains_key(&function_name) {
+                        return Err(AnalyzerError(
+                            format!("Function `{}` already defined!", function_name),
+                            line,
+                            function_ident_column,
+                        ));
+                    }
 
-                stack_symbols.insert(
-                    pointer_name.clone(),
-                    Symbol::Pointer {
-                        ptype: Type::from_token(base_type)?,
-                        name: pointer_name,
-                        value,
-                        allocation_type: AllocationType::Stack,
-                        heap_pointer: None,
-                        pointer_size: 4,
-                        value_size: ptype.get_size(),
-                    },
-                );
-            }
+                    let return_type = return_type.map(Type::from_token).transpose()?;
 
-            ast::Statement::PointerDeclarationHeap {
-                base_type,
-                pointer_name,
-                line,
-                pointer_ident_column,
-            } => {
-                if stack_symbols.contains_key(&pointer_name) {
-                    return Err(AnalyzerError(
-                        format!("Pointer `{}` already declared!", &pointer_name),
+                    call_stack.functions.insert(
+                        function_name,
+                        Function {
+                            return_type,
+                            parameters,
+                            body,
+                            source,
+                        },
+                    );
+                }
+
+                Statement::FunctionCall {
+                    function_name,
+                    arguments,
+                    line,
+                    function_ident_column,
+                } => {
+                    self.call_function(
+                        function_name,
+                        arguments,
+                        None,
                         line,
-                        pointer_ident_column,
-                    ));
+                        function_ident_column,
+                        stack_symbols,
+                        memory,
+                        starting_pointers,
+                        call_stack,
+                    )?;
+
+                    call_stack.sources.record_releases(memory);
                 }
 
-                let ptype = Type::from_token(base_type)?;
+                Statement::MethodCall {
+                    var_name,
+                    method,
+                    arguments,
+                    line,
+                    method_column,
+                    ..
+                } => {
+                    let function_name = resolve_method(
+                        call_stack,
+                        stack_symbols,
+                        &var_name,
+                        &method,
+                        line,
+                        method_column,
+                    )?;
 
-                let res = allocator.allocate_and_write(
-                    &pointer_name,
-                    ptype.get_size(),
-                    starting_pointers,
-                );
+                    self.call_function(
+                        function_name,
+                        arguments,
+                        Some(var_name),
+                        line,
+                        method_column,
+                        stack_symbols,
+                        memory,
+                        starting_pointers,
+                        call_stack,
+                    )?;
 
-                if let Err(e) = res {
-                    return Err(AnalyzerError(e.to_string(), line, pointer_ident_column));
+                    call_stack.sources.record_releases(memory);
                 }
 
-                stack_symbols.insert(
-                    pointer_name.clone(),
-                    Symbol::Pointer {
-                        ptype,
-                        name: pointer_name,
-                        value: Some(Box::new(Symbol::Literal {
-                            value: ptype.get_garbage_value(),
-                        })),
-                        heap_pointer: Some(res.unwrap()),
-                        allocation_type: AllocationType::Heap,
-                        pointer_size: 4,
-                        value_size: ptype.get_size(),
-                    },
-                );
-            }
-
-            ast::Statement::PointerDeclarationNull {
-                base_type,
-                pointer_name,
-                line,
-                pointer_ident_column,
-            } => {
-                if stack_symbols.contains_key(&pointer_name) {
-                    return Err(AnalyzerError(
-                        format!("Pointer `{}` already declared!", &pointer_name),
-                        line,
-                        pointer_ident_column,
-                    ));
+                Statement::Return {
+                    value,
+                    line,
+                    return_column,
+                } => {
+                    let Some(function_name) = call_stack.active.last() else {
+                        return Err(AnalyzerError(
+                            "`return` can only be used inside a function".to_string(),
+                            line,
+                            return_column,
+                        ));
+                    };
+
+                    match (&call_stack.functions[function_name].return_type, value) {
+                        (None, Some(_)) => {
+                            return Err(AnalyzerError(
+                                format!(
+                                    "Function `{}` returns `void` and can't return a value",
+                                    function_name
+                                ),
+                                line,
+                                return_column,
+                            ));
+                        }
+
+                        (Some(_), None) => {
+                            return Err(AnalyzerError(
+                                format!("Function `{}` must return a value", function_name),
+                                line,
+                                return_column,
+                            ));
+                        }
+
+                        (Some(return_type), Some(value)) => {
+                            call_stack.returned = validate_variable_assignment(
+                                value,
+                                function_name,
+                                return_type,
+                                stack_symbols,
+                                line,
+                                return_column,
+                            )?;
+                        }
+
+                        (None, None) => {}
+                    }
+
+                    return Ok(true);
                 }
 
-                let ptype = Type::from_token(base_type)?;
+                Statement::Block {
+                    statements,
+                    end_line,
+                    brace_column,
+                    ..
+                } => {
+                    // Locals are only ever appended, so the ones declared in the block are the tail of
+                    // the stack
+                    let outer_symbols = stack_symbols.len();
 
-                stack_symbols.insert(
-                    pointer_name.clone(),
-                    Symbol::Pointer {
-                        ptype,
-                        name: pointer_name,
-                        value: None,
-                        allocation_type: AllocationType::Null,
-                        heap_pointer: None,
-                        pointer_size: 4,
-                        value_size: ptype.get_size(),
-                    },
-                );
-            }
+                    let returned = self.analyze_block(
+                        statements,
+                        stack_symbols,
+                        memory,
+                        starting_pointers,
+                        call_stack,
+                        scope_depth + 1,
+                        0,
+                    )?;
 
-            ast::Statement::PointerAssignment {
-                pointer_name,
-                new_value,
-                line,
-                pointer_ident_column,
-            } => {
-                let new_value = validate_pointer_assignment(
-                    new_value,
-                    &stack_symbols,
-                    line,
-                    pointer_ident_column,
-                )?;
+                    self.run_destructors(
+                        outer_symbols,
+                        end_line,
+                        brace_column,
+                        stack_symbols,
+                        memory,
+                        starting_pointers,
+                        call_stack,
+                    )?;
 
-                if let Some(symbol) = stack_symbols.get_mut(&pointer_name) {
-                    if let Symbol::Pointer {
-                        name,
-                        value,
-                        allocation_type,
-                        heap_pointer,
-                        value_size,
-                        ..
-                    } = symbol
-                    {
-                        if *allocation_type != AllocationType::Dangling {
-                            if let Some(heap_pointer) = heap_pointer {
-                                allocator.leak(*heap_pointer, *value_size);
-                            }
-                        } else {
-                            if let Some(heap_pointer) = heap_pointer {
-                                allocator
-                                    .remove_dangling_pointer(*heap_pointer, name.to_string())?;
-                            }
-                        }
+                    let block_symbols = stack_symbols.split_off(outer_symbols);
 
-                        *value = new_value;
-                        *allocation_type = AllocationType::Stack;
-                        *heap_pointer = None;
-                    } else {
-                        return Err(AnalyzerError(
-                            format!(
-                                "Invalid use case of assignment operator for symbol `{}`",
-                                pointer_name
-                            ),
-                            line,
-                            pointer_ident_column,
-                        ));
+                    if call_stack.active.is_empty() {
+                        call_stack.sources.history.truncate(outer_symbols);
+                    }
+
+                    self.release_symbols(block_symbols.values(), stack_symbols, memory)?;
+                    call_stack.sources.record_releases(memory);
+
+                    let declarations =
+                        call_stack.sources.frame_declarations().split_off(outer_symbols);
+                    call_stack.sources.scope_exits.push(ScopeReference {
+                        source,
+                        symbols: declarations,
+                    });
+
+                    call_stack.scope_exits.push(ScopeExit {
+                        function: call_stack.active.last().cloned(),
+                        line: end_line,
+                        depth: scope_depth + 1,
+                        symbols: block_symbols.into_values().collect(),
+                    });
+
+                    if returned {
+                        return Ok(true);
                     }
-                } else {
-                    return Err(AnalyzerError(
-                        format!("Pointer `{}` not found!", pointer_name),
-                        line,
-                        pointer_ident_column,
-                    ));
                 }
-            }
 
-            ast::Statement::PointerAssignmentHeap {
-                pointer_name,
-                new_type,
-                line,
-                pointer_ident_column,
-                new_type_column,
-            } => {
-                if let Some(symbol) = stack_symbols.get_mut(&pointer_name) {
-                    if let Symbol::Pointer {
-                        ptype,
-                        name,
-                        value,
-                        allocation_type,
-                        heap_pointer,
-                        value_size,
-                        ..
-                    } = symbol
+                Statement::If {
+                    condition,
+                    then_branch,
+                    else_branch,
+                    line,
+                    if_column,
+                } => {
+                    // only the branch taken runs, as a block with the conditional as its parent
+                    let branch = if evaluate_condition(&condition, stack_symbols, line, if_column)?
                     {
-                        if !ptype.is_type(new_type) {
+                        Some((0, *then_branch))
+                    } else {
+                        else_branch.map(|else_branch| (1, *else_branch))
+                    };
+
+                    if let Some((branch_index, branch)) = branch {
+                        let returned = self.analyze_block(
+                            vec![branch],
+                            stack_symbols,
+                            memory,
+                            starting_pointers,
+                            call_stack,
+                            scope_depth,
+                            branch_index,
+                        )?;
+
+                        if returned {
+                            return Ok(true);
+                        }
+                    }
+                }
+
+                Statement::While {
+                    condition,
+                    body,
+                    update,
+                    line,
+                    loop_column,
+                } => {
+                    // every iteration runs the body and the update as a block with the loop as its parent
+                    let loop_path = call_stack.sources.path.clone();
+                    let mut iterations = 0;
+
+                    while evaluate_condition(&condition, stack_symbols, line, loop_column)? {
+                        if iterations == MAX_LOOP_ITERATIONS {
                             return Err(AnalyzerError(
                                 format!(
-                                    "Cannot assign `{}` to pointer `{}` (incorrect type)",
-                                    &new_type, &pointer_name
+                                    "Loop still runs after {} iterations, is it infinite?",
+                                    MAX_LOOP_ITERATIONS
                                 ),
                                 line,
-                                new_type_column,
+                                loop_column,
                             ));
                         }
 
-                        if *allocation_type != AllocationType::Dangling {
-                            if let Some(heap_pointer) = heap_pointer {
-                                allocator.leak(*heap_pointer, *value_size);
-                            }
-                        } else {
-                            if let Some(heap_pointer) = heap_pointer {
-                                allocator
-                                    .remove_dangling_pointer(*heap_pointer, name.to_string())?;
-                            }
-                        }
+                        iterations += 1;
+                        call_stack.sources.path = loop_path.clone();
+
+                        let statements = std::iter::once(body.as_ref().clone())
+                            .chain(update.as_deref().cloned())
+                            .collect();
 
-                        let res = allocator.allocate_and_write(
-                            &pointer_name,
-                            *value_size,
+                        let returned = self.analyze_block(
+                            statements,
+                            stack_symbols,
+                            memory,
                             starting_pointers,
-                        );
+                            call_stack,
+                            scope_depth,
+                            0,
+                        )?;
 
-                        if let Err(e) = res {
-                            return Err(AnalyzerError(e.to_string(), line, pointer_ident_column));
+                        if returned {
+                            return Ok(true);
                         }
 
-                        *allocation_type = AllocationType::Heap;
-                        *value = Some(Box::new(Symbol::Literal {
-                            value: "".to_owned(),
-                        }));
-                        *heap_pointer = Some(res.unwrap());
+                        call_stack.sources.record_iteration(&source, iterations, memory);
                     }
-                } else {
-                    return Err(AnalyzerError(
-                        format!("Pointer `{}` not found!", pointer_name),
+                }
+
+                Statement::ThreadSpawn {
+                    statements,
+                    line,
+                    spawn_column,
+                    ..
+                } => {
+                    if !call_stack.active.is_empty() || scope_depth > 0 {
+                        return Err(AnalyzerError(
+                            "Threads can only be spawned at the top level".to_string(),
+                            line,
+                            spawn_column,
+                        ));
+                    }
+
+                    let id = call_stack.threads.len() + 1;
+                    let frame = call_stack.frames.len();
+
+                    call_stack.frames.push(StackFrame {
+                        function: "thread_spawn".to_owned(),
                         line,
-                        pointer_ident_column,
-                    ));
+                        depth: 0,
+                        symbols: Vec::new(),
+                        addresses: Vec::new(),
+                        registers: Vec::new(),
+                        thread: id,
+                    });
+
+                    call_stack.sources.frames.push(ScopeReference {
+                        source: source.clone(),
+                        symbols: Vec::new(),
+                    });
+
+                    call_stack.threads.push(Thread {
+                        id,
+                        statements: statements.into_iter().enumerate().collect(),
+                        source,
+                        symbols: IndexMap::new(),
+                        declarations: Vec::new(),
+                        frame,
+                    });
                 }
-            }
 
-            ast::Statement::PointerAssignmentNull {
-                pointer_name,
-                line,
-                pointer_ident_column,
-            } => {
-                if let Some(symbol) = stack_symbols.get_mut(&pointer_name) {
-                    if let Symbol::Pointer {
-                        name,
-                        value,
-                        allocation_type,
-                        heap_pointer,
-                        value_size,
-                        ..
-                    } = symbol
-                    {
-                        if *allocation_type != AllocationType::Dangling {
-                            if let Some(heap_pointer) = heap_pointer {
-                                allocator.leak(*heap_pointer, *value_size);
-                            }
-                        } else {
-                            if let Some(heap_pointer) = heap_pointer {
-                                allocator
-                                    .remove_dangling_pointer(*heap_pointer, name.to_string())?;
-                            }
+                Statement::UnionDefinition {
+                    union_name,
+                    members,
+                    line,
+                    union_ident_column,
+                } => {
+                    if !call_stack.active.is_empty() || scope_depth > 0 {
+                        return Err(AnalyzerError(
+                            "Unions can only be defined at the top level".to_string(),
+                            line,
+                            union_ident_column,
+                        ));
+                    }
+
+                    if call_stack.unions.contains_key(&union_name) {
+                        return Err(AnalyzerError(
+                            format!("Union `{}` already defined!", union_name),
+                            line,
+                            union_ident_column,
+                        ));
+                    }
+
+                    let mut union_members: Vec<UnionMember> = Vec::new();
+
+                    for member in members {
+                        if union_members.iter().any(|other| other.name == member.member_name) {
+                            return Err(AnalyzerError(
+                                format!("Member `{}` already declared!", member.member_name),
+                                line,
+                                member.member_ident_column,
+                            ));
                         }
 
-                        *value = None;
-                        *allocation_type = AllocationType::Null;
-                        *heap_pointer = None;
+                        union_members.push(UnionMember {
+                            name: member.member_name,
+                            mtype: Type::from_token(member.member_type)?,
+                            value: None,
+                        });
                     }
-                } else {
-                    return Err(AnalyzerError(
-                        format!("Pointer `{}` not found!", pointer_name),
-                        line,
-                        pointer_ident_column,
-                    ));
+
+                    call_stack.unions.insert(union_name, union_members);
                 }
-            }
 
-            ast::Statement::Deref {
-                pointer_name,
-                new_value,
-                line,
-                pointer_ident_column,
-                new_value_column,
-            } => {
-                let cloned_symbols = stack_symbols.clone();
+                Statement::ClassDefinition {
+                    class_name,
+                    fields,
+                    methods,
+                    line,
+                    class_ident_column,
+                } => {
+                    if !call_stack.active.is_empty() || scope_depth > 0 {
+                        return Err(AnalyzerError(
+                            "Classes can only be defined at the top level".to_string(),
+                            line,
+                            class_ident_column,
+                        ));
+                    }
 
-                if let Some(symbol) = stack_symbols.get_mut(&pointer_name) {
-                    if let Symbol::Pointer {
-                        value,
-                        ptype,
-                        allocation_type,
-                        heap_pointer,
-                        ..
-                    } = symbol
+                    if call_stack.classes.contains_key(&class_name)
+                        || call_stack.unions.contains_key(&class_name)
                     {
-                        let pointer_value = value;
-                        let allocation_type = match *allocation_type {
-                            AllocationType::Dangling => AllocationType::Heap,
-                            _ => (*allocation_type).clone(),
-                        };
+                        return Err(AnalyzerError(
+                            format!("Class `{}` already defined!", class_name),
+                            line,
+                            class_ident_column,
+                        ));
+                    }
 
-                        if allocation_type == AllocationType::Null {
+                    let mut class = Class {
+                        fields: Vec::new(),
+                        private_methods: Vec::new(),
+                        source: source.clone(),
+                    };
+
+                    for field in fields {
+                        if class.fields.iter().any(|other| other.name == field.field_name) {
                             return Err(AnalyzerError(
-                                format!("Cannot dereference null pointer `{}`", pointer_name),
+                                format!("Member `{}` already declared!", field.field_name),
                                 line,
-                                pointer_ident_column,
+                                field.field_ident_column,
                             ));
                         }
 
-                        match *new_value {
-                            ast::Expr::Ident(new_ident) => {
-                                if let Some(symbol) = cloned_symbols.get(&new_ident) {
-                                    if let Symbol::Variable { value, .. } = symbol {
-                                        if let Some(value) = value {
-                                            if ptype
-                                                .is_correct_literal(&ast::Lit::from_str(&value)?)
-                                            {
-                                                let new_value = value.to_string();
-                                                let old_symbol = (*pointer_value).clone();
+                        let ftype = Type::from_token(field.field_type)?;
 
-                                                if allocation_type == AllocationType::Heap {
-                                                    *pointer_value =
-                                                        Some(Box::new(Symbol::Literal {
-                                                            value: new_value.clone(),
-                                                        }));
-
-                                                    if let Some(heap_pointer) = heap_pointer {
-                                                        allocator.update_metadata(
-                                                            *heap_pointer,
-                                                            new_value.clone(),
-                                                        )?;
-                                                    } else {
-                                                        return Err(AnalyzerError(
-                                                            format!(
-                                                                "Heap pointer not found for `{}`",
+                        // a pointer field is `nullptr` until a method points it somewhere
+                        let pointer = (field.indirection > 0).then(|| {
+                            Box::new(Symbol::Pointer {
+                                ptype: ftype.clone(),
+                                name: field.field_name.clone(),
+                                value: None,
+                                heap_pointer: None,
+                                allocation_type: AllocationType::Null,
+                                pointer_size: memory.pointer_size(),
+                                value_size: pointee_size(
+                                    &ftype,
+                                    field.indirection,
+                                    memory.pointer_size(),
+                                ),
+                                indirection: field.indirection,
+                                unique: false,
+                                shared: false,
+                                control_block: None,
+                                offset: 0,
+                            })
+                        });
+
+                        class.fields.push(ClassField {
+                            name: field.field_name,
+                            ftype,
+                            value: None,
+                            pointer,
+                            public: field.public,
+                        });
+                    }
+
+                    // the statements of a method are referenced within the class, by the index of the
+                    // method
+                    for (index, method) in methods.into_iter().enumerate() {
+                        let Statement::FunctionDefinition {
+                            return_type,
+                            function_name,
+                            parameters,
+                            body,
+                            line,
+                            function_ident_column,
+                        } = method.definition
+                        else {
+                            continue;
+                        };
+
+                        let name = method_name(&class_name, &function_name);
+
+                        if call_stack.functions.contains_key(&name)
+                            || class.fields.iter().any(|field| field.name == function_name)
+                        {
+                            return Err(AnalyzerError(
+                                format!("Member `{}` already declared!", function_name),
+                                line,
+                                function_ident_column,
+                            ));
+                        }
+
+                        call_stack.functions.insert(
+                            name,
+                            Function {
+                                return_type: return_type.map(Type::from_token).transpose()?,
+                                parameters,
+                                body,
+                                source: SourceRef::new(
+                                    &[source.statement.as_slice(), &[index]].concat(),
+                                    line,
+                                    function_ident_column,
+                                ),
+                            },
+                        );
+
+                        if !method.public {
+                            class.private_methods.push(function_name);
+                        }
+                    }
+
+                    call_stack.classes.insert(class_name, class);
+                }
+
+                Statement::UnionDeclaration {
+                    union_name,
+                    var_name,
+                    arguments,
+                    initializer,
+                    line,
+                    var_ident_column,
+                } => {
+                    let pointer_size = memory.pointer_size();
+                    let constructor = constructor_name(&union_name);
+
+                    // like in C++, a brace initializer is passed to the constructor of a class that
+                    // has one, and initializes the fields of one that doesn't
+                    let (arguments, initializer) = match initializer {
+                        Some(values)
+                            if call_stack.classes.contains_key(&union_name)
+                                && call_stack.functions.contains_key(&constructor) =>
+                        {
+                            (values, None)
+                        }
+                        initializer => (arguments, initializer),
+                    };
+
+                    // `Value v;` declares a union or an object, whichever `Value` was defined as
+                    let symbol = match (
+                        call_stack.unions.get(&union_name),
+                        call_stack.classes.get(&union_name),
+                    ) {
+                        (Some(_), _) if !arguments.is_empty() => {
+                            return Err(AnalyzerError(
+                                format!(
+                                    "Union `{}` has no constructor to pass arguments to",
+                                    union_name
+                                ),
+                                line,
+                                var_ident_column,
+                            ));
+                        }
+
+                        (None, Some(_))
+                            if !arguments.is_empty()
+                                && !call_stack.functions.contains_key(&constructor) =>
+                        {
+                            return Err(AnalyzerError(
+                                format!(
+                                    "Class `{}` has no constructor to pass arguments to",
+                                    union_name
+                                ),
+                                line,
+                                var_ident_column,
+                            ));
+                        }
+
+                        (None, Some(class)) if class.private_methods.contains(&union_name) => {
+                            return Err(AnalyzerError(
+                                format!("The constructor of class `{}` is private", union_name),
+                                line,
+                                var_ident_column,
+                            ));
+                        }
+
+                        (Some(members), _) => {
+                            // the bytes of a union hold garbage until a member is written, shown as
+                            // zero
+                            let size = union_size(members, pointer_size);
+
+                            Symbol::Union {
+                                union_name,
+                                name: var_name.clone(),
+                                members: members.clone(),
+                                active: None,
+                                bytes: vec![0; size],
+                                size,
+                            }
+                        }
+
+                        (None, Some(class)) => Symbol::Object {
+                            class_name: union_name,
+                            name: var_name.clone(),
+                            fields: class.fields.clone(),
+                            size: object_size(&class.fields, pointer_size),
+                        },
+
+                        (None, None) => {
+                            return Err(AnalyzerError(
+                                format!("Union or class `{}` not found!", union_name),
+                                line,
+                                var_ident_column,
+                            ));
+                        }
+                    };
+
+                    if stack_symbols.contains_key(&var_name) {
+                        return Err(AnalyzerError(
+                            format!("Variable `{}` already declared!", var_name),
+                            line,
+                            var_ident_column,
+                        ));
+                    }
+
+                    let members = match (&symbol, &initializer) {
+                        (_, None) => Vec::new(),
+                        (
+                            Symbol::Object {
+                                class_name, fields, ..
+                            },
+                            Some(_),
+                        ) => {
+                            if fields.iter().any(|field| !field.public) {
+                                return Err(AnalyzerError(
+                                    format!(
+                                        "Class `{}` has private fields, it can only be initialized with braces by a constructor",
+                                        class_name
+                                    ),
+                                    line,
+                                    var_ident_column,
+                                ));
+                            }
+
+                            fields.iter().map(|field| field.name.clone()).collect()
+                        }
+                        // only the first member of a union can be initialized
+                        (Symbol::Union { members, .. }, Some(_)) => {
+                            members.iter().take(1).map(|member| member.name.clone()).collect()
+                        }
+                        _ => Vec::new(),
+                    };
+
+                    stack_symbols.insert(var_name.clone(), symbol);
+
+                    call_stack.sources.frame_declarations().push(source.clone());
+
+                    if let Some(values) = initializer {
+                        self.brace_initialize(
+                            &var_name,
+                            members,
+                            values,
+                            line,
+                            var_ident_column,
+                            stack_symbols,
+                            memory,
+                            starting_pointers,
+                            &call_stack.callers,
+                        )?;
+                    }
+
+                    if call_stack.functions.contains_key(&constructor) {
+                        self.call_function(
+                            constructor,
+                            arguments,
+                            Some(var_name),
+                            line,
+                            var_ident_column,
+                            stack_symbols,
+                            memory,
+                            starting_pointers,
+                            call_stack,
+                        )?;
+
+                        call_stack.sources.record_releases(memory);
+                    }
+                }
+
+                Statement::ObjectAllocation {
+                    class_name,
+                    pointer_name,
+                    arguments,
+                    line,
+                    pointer_ident_column,
+                } => {
+                    let pointer_size = memory.pointer_size();
+                    let constructor = constructor_name(&class_name);
+
+                    let Some(class) = call_stack.classes.get(&class_name) else {
+                        return Err(AnalyzerError(
+                            format!("Class `{}` not found!", class_name),
+                            line,
+                            pointer_ident_column,
+                        ));
+                    };
+
+                    if !arguments.is_empty() && !call_stack.functions.contains_key(&constructor) {
+                        return Err(AnalyzerError(
+                            format!(
+                                "Class `{}` has no constructor to pass arguments to",
+                                class_name
+                            ),
+                            line,
+                            pointer_ident_column,
+                        ));
+                    }
+
+                    if class.private_methods.contains(&class_name) {
+                        return Err(AnalyzerError(
+                            format!("The constructor of class `{}` is private", class_name),
+                            line,
+                            pointer_ident_column,
+                        ));
+                    }
+
+                    if stack_symbols.contains_key(&pointer_name) {
+                        return Err(AnalyzerError(
+                            format!("Pointer `{}` already declared!", pointer_name),
+                            line,
+                            pointer_ident_column,
+                        ));
+                    }
+
+                    // the block holds the whole object, the padding after its last field included
+                    let size = object_size(&class.fields, pointer_size);
+                    let object = Symbol::Object {
+                        class_name: class_name.clone(),
+                        name: pointer_name.clone(),
+                        fields: class.fields.clone(),
+                        size,
+                    };
+
+                    let heap_pointer = memory
+                        .allocate(&pointer_name, size, starting_pointers)
+                        .map_err(|e| AnalyzerError(e.to_string(), line, pointer_ident_column))?;
+
+                    memory.update_metadata(heap_pointer, class_name.clone())?;
+                    memory.update_members(heap_pointer, block_members(&object, pointer_size))?;
+
+                    stack_symbols.insert(
+                        pointer_name.clone(),
+                        Symbol::Pointer {
+                            ptype: Type::Class(class_name),
+                            name: pointer_name.clone(),
+                            value: Some(Box::new(object)),
+                            heap_pointer: Some(heap_pointer),
+                            allocation_type: AllocationType::Heap,
+                            pointer_size,
+                            value_size: size,
+                            indirection: 1,
+                            unique: false,
+                            shared: false,
+                            control_block: None,
+                            offset: 0,
+                        },
+                    );
+
+                    call_stack.sources.frame_declarations().push(source.clone());
+                    let owner = call_stack.owner(&pointer_name);
+                    call_stack.sources.allocate(heap_pointer, source, owner);
+                    call_stack.sources.delete_forms.insert(heap_pointer, ast::Release::Delete);
+                    call_stack.sources.record_releases(memory);
+
+                    if call_stack.functions.contains_key(&constructor) {
+                        self.call_function(
+                            constructor,
+                            arguments,
+                            Some(pointer_name),
+                            line,
+                            pointer_ident_column,
+                            stack_symbols,
+                            memory,
+                            starting_pointers,
+                            call_stack,
+                        )?;
+
+                        call_stack.sources.record_releases(memory);
+                    }
+                }
+
+                Statement::Lock {
+                    mutex_name,
+                    line,
+                    mutex_ident_column,
+                } => {
+                    let thread = call_stack.thread;
+                    let owner = mutex_owner(stack_symbols, &mutex_name, line, mutex_ident_column)?;
+
+                    // threads waiting at the top of their body aren't scheduled until the mutex is
+                    // unlocked, only a lock nested in a statement can find it held
+                    match owner {
+                        Some(holder) if *holder == thread => {
+                            return Err(AnalyzerError(
+                                format!(
+                                    "Locking `{}` again deadlocks, this thread already holds it",
+                                    mutex_name
+                                ),
+                                line,
+                                mutex_ident_column,
+                            ));
+                        }
+
+                        Some(holder) => {
+                            return Err(AnalyzerError(
+                                format!(
+                                    "Thread {} holds `{}`, threads only wait for it at the top level",
+                                    holder, mutex_name
+                                ),
+                                line,
+                                mutex_ident_column,
+                            ));
+                        }
+
+                        None => *owner = Some(thread),
+                    }
+
+                    call_stack.sources.record_lock(mutex_name, thread, true, &source);
+                }
+
+                Statement::Unlock {
+                    mutex_name,
+                    line,
+                    mutex_ident_column,
+                } => {
+                    let thread = call_stack.thread;
+                    let owner = mutex_owner(stack_symbols, &mutex_name, line, mutex_ident_column)?;
+
+                    if *owner != Some(thread) {
+                        return Err(AnalyzerError(
+                            format!(
+                                "Unlocking `{}` is undefined behavior, this thread doesn't hold it",
+                                mutex_name
+                            ),
+                            line,
+                            mutex_ident_column,
+                        ));
+                    }
+
+                    *owner = None;
+                    call_stack.sources.record_lock(mutex_name, thread, false, &source);
+                }
+
+                statement => {
+                    let allocating_pointer = match &statement {
+                        Statement::PointerDeclarationHeap { pointer_name, .. }
+                        | Statement::PointerAssignmentHeap { pointer_name, .. }
+                        | Statement::PointerReallocation { pointer_name, .. }
+                        | Statement::UniquePointerDeclaration {
+                            pointer_name,
+                            value: ast::UniqueValue::MakeUnique { .. },
+                            ..
+                        }
+                        | Statement::UniquePointerAssignment {
+                            pointer_name,
+                            value: ast::UniqueValue::MakeUnique { .. },
+                            ..
+                        }
+                        | Statement::SharedPointerDeclaration {
+                            pointer_name,
+                            value: ast::SharedValue::MakeShared { .. },
+                            ..
+                        }
+                        | Statement::SharedPointerAssignment {
+                            pointer_name,
+                            value: ast::SharedValue::MakeShared { .. },
+                            ..
+                        } => Some(pointer_name.clone()),
+                        _ => None,
+                    };
+
+                    // a block allocated with `new` or `new[]` has to be released with the same form
+                    let delete_form = match &statement {
+                        Statement::PointerDeclarationHeap { allocation, .. }
+                        | Statement::PointerAssignmentHeap { allocation, .. } => {
+                            allocation.delete_form()
+                        }
+                        _ => None,
+                    };
+
+                    // A block `realloc` resizes in place keeps the statement that allocated it, so it isn't
+                    // recorded as a new allocation
+                    let resized_block = match &statement {
+                        Statement::PointerReallocation { pointer_name, .. } => {
+                            match stack_symbols.get(pointer_name) {
+                                Some(Symbol::Pointer { heap_pointer, .. }) => *heap_pointer,
+                                _ => None,
+                            }
+                        }
+                        _ => None,
+                    };
+
+                    let copies = value_copies(&statement, stack_symbols, memory.pointer_size());
+                    let moves = value_moves(&statement);
+                    let declared_symbols = stack_symbols.len();
+
+                    // the main thread runs before the others, so only spawned threads can race
+                    let accesses = match call_stack.thread {
+                        0 => Vec::new(),
+                        _ => heap_accesses(&statement, stack_symbols),
+                    };
+                    let read_block = heap_read(&statement, stack_symbols);
+                    let written_block = heap_write(&statement, stack_symbols, memory);
+
+                    // casts to misaligned addresses, `double` literals narrowed to `float`, copies
+                    // between overlapping ranges and strings copied past the end of a block still run
+                    let warnings: Vec<_> =
+                        misaligned_cast(&statement, stack_symbols, memory.pointer_size())
+                            .into_iter()
+                            .chain(narrowed_literal(&statement, stack_symbols))
+                            .chain(overlapping_copy(
+                                &statement,
+                                stack_symbols,
+                                memory.pointer_size(),
+                            ))
+                            .chain(string_overrun(&statement, stack_symbols, memory))
+                            .collect();
+
+                    // in permissive mode a write out of the bounds of an array is skipped with a warning
+                    let out_of_bounds = match self.options.strictness {
+                        Strictness::Strict => None,
+                        Strictness::Permissive => {
+                            out_of_bounds_write(&statement, stack_symbols, memory.pointer_size())
+                        }
+                    };
+
+                    match out_of_bounds {
+                        Some(warning) => call_stack.sources.warnings.push(warning),
+                        None => {
+                            self.analyze_statement(
+                                statement,
+                                stack_symbols,
+                                memory,
+                                starting_pointers,
+                                &call_stack.callers,
+                            )?;
+
+                            if let Some(block) = read_block {
+                                call_stack.sources.record_read(block);
+                            }
+
+                            if let Some(block) = written_block {
+                                call_stack.sources.record_write(block);
+                            }
+
+                            call_stack.sources.warnings.extend(warnings);
+                        }
+                    }
+
+                    call_stack.sources.record_releases(memory);
+                    call_stack.sources.record_copies(copies, &source);
+                    call_stack.sources.record_moves(moves, &source);
+
+                    call_stack.sources.record_accesses(
+                        accesses,
+                        call_stack.thread,
+                        &held_locks(stack_symbols, call_stack.thread),
+                        &source,
+                    );
+
+                    if stack_symbols.len() > declared_symbols {
+                        call_stack.sources.frame_declarations().push(source.clone());
+                    }
+
+                    // a `std::shared_ptr` allocates its control block along with its block
+                    let owner = allocating_pointer
+                        .as_ref()
+                        .map(|name| call_stack.owner(name))
+                        .unwrap_or_default();
+                    let (allocated_block, control_block) =
+                        match allocating_pointer.and_then(|name| stack_symbols.get(&name)) {
+                            Some(Symbol::Pointer {
+                                heap_pointer,
+                                control_block,
+                                ..
+                            }) => (*heap_pointer, *control_block),
+                            _ => (None, None),
+                        };
+
+                    if let Some(control_block) = control_block {
+                        call_stack.sources.allocate(
+                            control_block,
+                            source.clone(),
+                            format!("{} (control block)", owner),
+                        );
+                    }
+
+                    if let Some(heap_pointer) =
+                        allocated_block.filter(|block| Some(*block) != resized_block)
+                    {
+                        call_stack.sources.allocate(heap_pointer, source, owner);
+
+                        if let Some(delete_form) = delete_form {
+                            call_stack.sources.delete_forms.insert(heap_pointer, delete_form);
+                        }
+                    }
+                }
+            }
+
+            // threads record the values of the main thread once they ran a statement, their locals left out
+            if self.options.track_history && call_stack.active.is_empty() && call_stack.thread == 0
+            {
+                call_stack.sources.record_values(stack_symbols.values());
+            }
+
+            if self_check {
+                self.check_consistency(stack_symbols, memory)
+                    .map_err(|e| InternalError(e, line))?;
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Runs the threads the program spawned, once the main thread reached the end of the program like they
+    /// were all joined there.
+    ///
+    /// Every thread has a stack of its own, reported as its frame, and uses the symbols of the main thread
+    /// like a lambda capturing them by reference, so the threads share them along with the heap. The
+    /// threads take turns one statement at a time, in an order picked from the seed, skipping the
+    /// threads waiting for a mutex.
+    ///
+    /// # Arguments
+    ///
+    /// - `stack_symbols`: A mutable reference to the symbols of the main thread.
+    /// - `memory`: A mutable reference to the `MemoryModel` the statements are analyzed in.
+    /// - `starting_pointers`: A mutable reference to a `IndexMap<String, usize>` containing starting pointers.
+    /// - `call_stack`: A mutable reference to the `CallStack` of the program.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<(), Error>`: A result containing either:
+    ///  - `Ok(())` if every thread ran to the end.
+    /// - An `Error` if a statement of a thread fails.
+    fn run_threads(
+        &self,
+        stack_symbols: &mut IndexMap<String, Symbol>,
+        memory: &mut dyn MemoryModel,
+        starting_pointers: &mut IndexMap<String, usize>,
+        call_stack: &mut CallStack,
+    ) -> Result<()> {
+        let mut threads = std::mem::take(&mut call_stack.threads);
+
+        // the schedule has a generator of its own, so spawning threads doesn't move the heap blocks
+        let mut rng = match self.options.seed {
+            Some(seed) => Rng::from_seed(seed),
+            None => Rng::from_entropy(),
+        };
+
+        let main_symbols = stack_symbols.len();
+
+        while !threads.is_empty() {
+            // a thread whose next statement locks a mutex another thread holds waits for it
+            let runnable: Vec<usize> = (0..threads.len())
+                .filter(|&index| {
+                    threads[index].statements.front().is_none_or(|(_, statement)| {
+                        lock_owner(statement, stack_symbols)
+                            .is_none_or(|owner| owner == threads[index].id)
+                    })
+                })
+                .collect();
+
+            if runnable.is_empty() {
+                let (_, statement) =
+                    threads[0].statements.front().expect("waiting threads have statements left");
+
+                return Err(AnalyzerError(
+                    "Deadlock, every thread is waiting for a mutex another thread holds"
+                        .to_string(),
+                    statement.line(),
+                    statement.column(),
+                ));
+            }
+
+            let index = runnable[rng.range_inclusive(0, runnable.len() - 1)];
+            let thread = &mut threads[index];
+
+            let Some((position, statement)) = thread.statements.pop_front() else {
+                let thread = threads.remove(index);
+
+                self.release_symbols(thread.symbols.values(), stack_symbols, memory)?;
+                call_stack.sources.record_releases(memory);
+
+                call_stack.frames[thread.frame].symbols = thread.symbols.into_values().collect();
+                call_stack.sources.frames[thread.frame].symbols = thread.declarations;
+                continue;
+            };
+
+            // The locals of the thread go on top of the symbols of the main thread while it runs
+            stack_symbols.extend(std::mem::take(&mut thread.symbols));
+            call_stack.sources.frame_declarations().append(&mut thread.declarations);
+            call_stack.sources.path = thread.source.statement.clone();
+            call_stack.thread = thread.id;
+
+            call_stack.sources.thread_steps.push(ThreadStep {
+                thread: thread.id,
+                step: call_stack.sources.steps + 1,
+                source: SourceRef::of_statement(
+                    &[thread.source.statement.as_slice(), &[position]].concat(),
+                    &statement,
+                ),
+            });
+
+            self.analyze_block(
+                vec![statement],
+                stack_symbols,
+                memory,
+                starting_pointers,
+                call_stack,
+                1,
+                position,
+            )?;
+
+            thread.symbols = stack_symbols.split_off(main_symbols);
+            thread.declarations = call_stack.sources.frame_declarations().split_off(main_symbols);
+            call_stack.thread = 0;
+
+            if self.options.track_history {
+                call_stack.sources.record_values(stack_symbols.values());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Calls a function: pushes a frame with its parameters, runs its body and pops the frame again.
+    ///
+    /// Once the frame is popped its symbols are out of scope and are released like the locals of a block,
+    /// and the values the function wrote through pointers to variables of the caller are written back to
+    /// them.
+    ///
+    /// A method gets the address of its object as `this` before its parameters, and the fields of the
+    /// object after them, which are written back to the object once the frame is popped.
+    ///
+    /// # Arguments
+    ///
+    /// - `function_name`: The name of the function to call, `Class::method` for a method.
+    /// - `arguments`: The arguments of the call, passed by value.
+    /// - `receiver`: The object a method is called on, `None` for functions.
+    /// - `line`: The line of the call.
+    /// - `function_ident_column`: The column of the function's name in the call.
+    /// - `stack_symbols`: A mutable reference to the symbols of the calling frame.
+    /// - `memory`: A mutable reference to the `MemoryModel` the statements are analyzed in.
+    /// - `starting_pointers`: A mutable reference to a `IndexMap<String, usize>` containing starting pointers.
+    /// - `call_stack`: A mutable reference to the `CallStack` of the program.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<(), Error>`: A result containing either:
+    ///  - `Ok(())` if the call is successful.
+    /// - An `Error` if the call fails.
+    #[allow(clippy::too_many_arguments)]
+    fn call_function(
+        &self,
+        function_name: String,
+        arguments: Vec<ast::Expr>,
+        receiver: Option<String>,
+        line: usize,
+        function_ident_column: usize,
+        stack_symbols: &mut IndexMap<String, Symbol>,
+        memory: &mut dyn MemoryModel,
+        starting_pointers: &mut IndexMap<String, usize>,
+        call_stack: &mut CallStack,
+    ) -> Result<Option<String>> {
+        let Some(function) = call_stack.functions.get(&function_name).cloned() else {
+            return Err(AnalyzerError(
+                format!("Function `{}` not found!", function_name),
+                line,
+                function_ident_column,
+            ));
+        };
+
+        if arguments.len() != function.parameters.len() {
+            return Err(AnalyzerError(
+                format!(
+                    "Function `{}` takes {} argument(s) but {} were given",
+                    function_name,
+                    function.parameters.len(),
+                    arguments.len()
+                ),
+                line,
+                function_ident_column,
+            ));
+        }
+
+        let max_call_depth =
+            self.options.max_call_depth.unwrap_or(MAX_CALL_DEPTH).min(CALL_DEPTH_LIMIT);
+
+        if call_stack.active.len() >= max_call_depth {
+            return Err(AnalyzerError(
+                format!(
+                    "Stack overflow, calls to `{}` nest deeper than {} frames",
+                    function_name, max_call_depth
+                ),
+                line,
+                function_ident_column,
+            ));
+        }
+
+        let mut frame_symbols: IndexMap<String, Symbol> = IndexMap::new();
+        let mut declarations = Vec::new();
+        let mut parameter_types = Vec::new();
+        let mut copies = Vec::new();
+
+        // `this` is passed like the first argument, in the first register when there are registers
+        let (object, heap_object) =
+            match receiver.as_ref().and_then(|receiver| stack_symbols.get(receiver)) {
+                // an object allocated with `new` is reached through the pointer to it
+                Some(Symbol::Pointer {
+                    value: Some(object),
+                    heap_pointer,
+                    ..
+                }) => (Some(object.as_ref().clone()), *heap_pointer),
+                object => (object.cloned(), None),
+            };
+
+        if let Some(object) = &object {
+            declarations.push(function.source.clone());
+            parameter_types.push(Type::UnsignedLong);
+            frame_symbols.insert(
+                "this".to_string(),
+                this_pointer(object, heap_object, memory.pointer_size()),
+            );
+        }
+
+        for (parameter, argument) in function.parameters.iter().zip(arguments) {
+            if frame_symbols.contains_key(&parameter.param_name) {
+                return Err(AnalyzerError(
+                    format!("Parameter `{}` already declared!", parameter.param_name),
+                    line,
+                    parameter.param_ident_column,
+                ));
+            }
+
+            declarations.push(SourceRef::new(
+                &function.source.statement,
+                function.source.line,
+                parameter.param_ident_column,
+            ));
+
+            // the argument is copied into the parameter, an address just like a value
+            if let ast::Expr::Ident(source) = &argument {
+                let bytes = stack_symbols.get(source).map_or(0, symbol_size);
+                copies.push((Some(source.clone()), Some(parameter.param_name.clone()), bytes));
+            }
+
+            if parameter.indirection > 0 {
+                let pointer = pointer_argument(
+                    argument,
+                    parameter,
+                    stack_symbols,
+                    memory.pointer_size(),
+                    line,
+                    function_ident_column,
+                )?;
+
+                // addresses are passed in integer registers
+                parameter_types.push(Type::UnsignedLong);
+                frame_symbols.insert(parameter.param_name.clone(), pointer);
+
+                continue;
+            }
+
+            let vtype = Type::from_token(parameter.param_type)?;
+            parameter_types.push(vtype.clone());
+
+            let value = validate_variable_assignment(
+                Box::new(argument),
+                &parameter.param_name,
+                &vtype,
+                stack_symbols,
+                line,
+                function_ident_column,
+            )?;
+
+            check_integer_range(
+                &vtype,
+                value.as_deref(),
+                &parameter.param_name,
+                memory.pointer_size(),
+                line,
+                function_ident_column,
+            )?;
+
+            frame_symbols.insert(
+                parameter.param_name.clone(),
+                Symbol::Variable {
+                    size: vtype.get_variable_size(memory.pointer_size()),
+                    vtype,
+                    name: parameter.param_name.clone(),
+                    value,
+                    string: None,
+                },
+            );
+        }
+
+        let fields = match &object {
+            Some(object @ Symbol::Object { class_name, .. }) => {
+                let fields = enter_fields(object, &mut frame_symbols, memory.pointer_size());
+                let class_source = &call_stack.classes[class_name].source;
+                declarations.extend(fields.clone().map(|_| class_source.clone()));
+
+                fields
+            }
+            _ => 0..0,
+        };
+
+        let call_source = SourceRef::new(&call_stack.sources.path, line, function_ident_column);
+        call_stack.sources.record_copies(copies, &call_source);
+
+        call_stack.active.push(function_name.clone());
+
+        let frame_index = call_stack.frames.len();
+        call_stack.frames.push(StackFrame {
+            function: function_name,
+            line,
+            depth: call_stack.active.len(),
+            symbols: Vec::new(),
+            addresses: Vec::new(),
+            registers: Vec::new(),
+            thread: call_stack.thread,
+        });
+
+        call_stack.sources.frames.push(ScopeReference {
+            source: call_source,
+            symbols: Vec::new(),
+        });
+        call_stack.sources.declarations.push(declarations);
+
+        // The body's statements are referenced where the function was defined, not where it was called
+        let call_path =
+            std::mem::replace(&mut call_stack.sources.path, function.source.statement.clone());
+
+        // The frame is laid out below the frames of the calls it was made from
+        call_stack.callers.push(stack_symbols.clone());
+
+        self.analyze_block(
+            function.body,
+            &mut frame_symbols,
+            memory,
+            starting_pointers,
+            call_stack,
+            0,
+            0,
+        )?;
+
+        self.run_destructors(
+            0,
+            line,
+            function_ident_column,
+            &mut frame_symbols,
+            memory,
+            starting_pointers,
+            call_stack,
+        )?;
+
+        let frame_top = address_space::frame_top(&call_stack.callers, memory.pointer_size());
+        let addresses: IndexMap<String, usize> = frame_symbols
+            .keys()
+            .cloned()
+            .zip(address_space::stack_addresses(
+                frame_symbols.values(),
+                frame_top,
+                memory.pointer_size(),
+            ))
+            .collect();
+
+        call_stack.callers.pop();
+        call_stack.active.pop();
+        call_stack.sources.path = call_path;
+
+        if let Some(receiver) = &receiver {
+            leave_fields(receiver, fields.clone(), &mut frame_symbols, stack_symbols);
+
+            if let (
+                Some(block),
+                Some(Symbol::Pointer {
+                    value: Some(object),
+                    ..
+                }),
+            ) = (heap_object, stack_symbols.get(receiver))
+            {
+                memory.update_members(block, block_members(object, memory.pointer_size()))?;
+            }
+
+            if let Some(declarations) = call_stack.sources.declarations.last_mut() {
+                declarations.drain(fields);
+            }
+        }
+
+        write_through(&frame_symbols, &function.parameters, stack_symbols, memory)?;
+        self.release_symbols(frame_symbols.values(), stack_symbols, memory)?;
+
+        // Parameters passed in registers are left out of the frame, along with their declarations
+        let registers = self.options.calling_convention.argument_registers(&parameter_types);
+        let declarations = call_stack.sources.declarations.pop().unwrap_or_default();
+        let frame = &mut call_stack.frames[frame_index];
+
+        for (index, (name, symbol)) in frame_symbols.into_iter().enumerate() {
+            match registers.get(index).copied().flatten() {
+                Some(register) => frame.registers.push(RegisterArgument {
+                    register: register.to_owned(),
+                    symbol,
+                }),
+                None => {
+                    frame.addresses.extend(addresses.get(&name));
+                    frame.symbols.push(symbol);
+                }
+            }
+        }
+
+        call_stack.sources.frames[frame_index].symbols = declarations
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| registers.get(*index).copied().flatten().is_none())
+            .map(|(_, declaration)| declaration)
+            .collect();
+
+        Ok(call_stack.returned.take())
+    }
+
+    /// Makes the calls in an expression, arguments first, replacing each call with the value it returned
+    ///
+    /// `peek`s are replaced with the value they read along the way, since their addresses can be calls too
+    ///
+    /// # Arguments
+    ///
+    /// - `expr`: The expression, its calls are replaced in place.
+    /// - `source`: The statement the expression belongs to.
+    /// - `stack_symbols`: A mutable reference to the symbols of the current frame.
+    /// - `memory`: A mutable reference to the `MemoryModel` the statements are analyzed in.
+    /// - `starting_pointers`: A mutable reference to a `IndexMap<String, usize>` containing starting pointers.
+    /// - `call_stack`: A mutable reference to the call stack.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<Vec<(Symbol, usize)>, Error>`: A result containing either:
+    ///  - The temporaries holding the returned values, with the step each call returned at.
+    /// - An `Error` if a call fails or doesn't return a value.
+    fn evaluate_calls(
+        &self,
+        expr: &mut ast::Expr,
+        source: &SourceRef,
+        stack_symbols: &mut IndexMap<String, Symbol>,
+        memory: &mut dyn MemoryModel,
+        starting_pointers: &mut IndexMap<String, usize>,
+        call_stack: &mut CallStack,
+    ) -> Result<Vec<(Symbol, usize)>> {
+        let mut temporaries = Vec::new();
+        let call = expr.to_string();
+
+        // `sizeof` doesn't evaluate its operand, so calls in it are never made
+        let operands = match expr {
+            ast::Expr::Call { arguments, .. } | ast::Expr::MethodCall { arguments, .. } => {
+                arguments.iter_mut().collect()
+            }
+            ast::Expr::AddressOf(expr)
+            | ast::Expr::Dereference(expr)
+            | ast::Expr::PrefixOp { expr, .. }
+            | ast::Expr::PostfixOp { expr, .. }
+            | ast::Expr::Cast { expr, .. }
+            | ast::Expr::Peek(expr) => vec![expr.as_mut()],
+            ast::Expr::InfixOp { lhs, rhs, .. } => vec![lhs.as_mut(), rhs.as_mut()],
+            ast::Expr::Index { indices, .. } => indices.iter_mut().collect(),
+            _ => Vec::new(),
+        };
+
+        for operand in operands {
+            temporaries.extend(call_stack.nested(source.line, source.column, |call_stack| {
+                self.evaluate_calls(
+                    operand,
+                    source,
+                    stack_symbols,
+                    memory,
+                    starting_pointers,
+                    call_stack,
+                )
+            })?);
+        }
+
+        // the size of a `long` depends on the architecture, which only the memory model knows
+        if let ast::Expr::SizeofType(size_type) = expr {
+            let size = Type::from_token(*size_type)?.get_variable_size(memory.pointer_size());
+            *expr = ast::Expr::Literal(ast::Lit::Int(size as i128));
+        }
+
+        // a cast is made once its operand is known, which can be the value a call returned
+        if let ast::Expr::Cast {
+            cast_type,
+            expr: operand,
+        } = expr
+        {
+            let value = evaluate_expression(operand, stack_symbols, source.line, source.column)?;
+
+            let (converted, lost) = cast_literal(
+                value.clone(),
+                &Type::from_token(*cast_type)?,
+                memory.pointer_size(),
+                source.line,
+                source.column,
+            )?;
+
+            if lost {
+                call_stack.sources.lossy_casts.push(LossyCast {
+                    step: call_stack.sources.steps.saturating_sub(1),
+                    source: source.clone(),
+                    cast: call.clone(),
+                    from: value.to_string(),
+                    to: converted.to_string(),
+                });
+            }
+
+            *expr = ast::Expr::Literal(converted);
+        }
+
+        if let ast::Expr::Peek(address) = expr {
+            let value = raw_memory::peek(
+                address,
+                stack_symbols,
+                &call_stack.callers,
+                memory,
+                source.line,
+                source.column,
+            )?;
+
+            if let Some(block) = address_block(address, stack_symbols, memory) {
+                call_stack.sources.record_read(block);
+            }
+
+            *expr = ast::Expr::Literal(value);
+        }
+
+        if let ast::Expr::Strlen(name) = expr {
+            let length = strlen(name, stack_symbols, memory, source.line, source.column)?;
+
+            if let Some(block) = pointer_block(name, stack_symbols) {
+                call_stack.sources.record_read(block);
+            }
+
+            *expr = ast::Expr::Literal(ast::Lit::Int(length as i128));
+        }
+
+        // `*p` reads what the pointer points to, a pointer to a pointer is left for the statement
+        let dereferenced = match expr {
+            ast::Expr::Dereference(pointer) => match pointer.as_ref() {
+                ast::Expr::Ident(name)
+                    if matches!(
+                        stack_symbols.get(name),
+                        Some(Symbol::Pointer { indirection: 1, .. })
+                    ) =>
+                {
+                    Some(name.clone())
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+
+        if let Some(name) = dereferenced {
+            let value = pointee_literal(&name, stack_symbols, memory, source.line, source.column)?;
+
+            if let Some(block) = pointer_block(&name, stack_symbols) {
+                call_stack.sources.record_read(block);
+            }
+
+            *expr = ast::Expr::Literal(value);
+        }
+
+        // a compound assignment like `a[1] += 2` or `p->x *= 2` reads what it writes
+        if let ast::Expr::Index { var_name, indices } = expr {
+            let value = element_literal(
+                var_name,
+                indices,
+                stack_symbols,
+                memory,
+                source.line,
+                source.column,
+            )?;
+
+            if let Some(block) = pointer_block(var_name, stack_symbols) {
+                call_stack.sources.record_read(block);
+            }
+
+            *expr = ast::Expr::Literal(value);
+        }
+
+        if let ast::Expr::PointerMember {
+            pointer_name,
+            member,
+        } = expr
+        {
+            let value = pointer_member_literal(
+                pointer_name,
+                member,
+                stack_symbols,
+                call_stack,
+                source.line,
+                source.column,
+            )?;
+
+            if let Some(block) = pointer_block(pointer_name, stack_symbols) {
+                call_stack.sources.record_read(block);
+            }
+
+            *expr = ast::Expr::Literal(value);
+        }
+
+        let (function_name, arguments, receiver) = match expr {
+            ast::Expr::Call {
+                function_name,
+                arguments,
+            } => (function_name.clone(), arguments, None),
+
+            ast::Expr::MethodCall {
+                var_name,
+                method,
+                arguments,
+            } => {
+                let function_name = resolve_method(
+                    call_stack,
+                    stack_symbols,
+                    var_name,
+                    method,
+                    source.line,
+                    source.column,
+                )?;
+
+                (function_name, arguments, Some(var_name.clone()))
+            }
+
+            _ => return Ok(temporaries),
+        };
+
+        let return_type = match call_stack.functions.get(function_name.as_str()) {
+            Some(Function {
+                return_type: None, ..
+            }) => {
+                return Err(AnalyzerError(
+                    format!("Function `{}` returns `void` and has no value to use", function_name),
+                    source.line,
+                    source.column,
+                ));
+            }
+            function => function.and_then(|function| function.return_type.clone()),
+        };
+
+        let value = self.call_function(
+            function_name.clone(),
+            std::mem::take(arguments),
+            receiver,
+            source.line,
+            source.column,
+            stack_symbols,
+            memory,
+            starting_pointers,
+            call_stack,
+        )?;
+
+        let (Some(return_type), Some(value)) = (return_type, value) else {
+            return Err(AnalyzerError(
+                format!("Function `{}` ended without returning a value", function_name),
+                source.line,
+                source.column,
+            ));
+        };
+
+        let Some(literal) = variable_literal(&return_type, &value) else {
+            return Err(AnalyzerError(
+                format!("`{}` returned `{}`, which isn't a `{}`", call, value, return_type),
+                source.line,
+                source.column,
+            ));
+        };
+
+        *expr = ast::Expr::Literal(literal);
+
+        temporaries.push((
+            Symbol::Variable {
+                size: return_type.get_variable_size(memory.pointer_size()),
+                vtype: return_type,
+                name: call,
+                value: Some(value),
+                string: None,
+            },
+            call_stack.sources.steps.saturating_sub(1),
+        ));
+
+        Ok(temporaries)
+    }
+
+    /// Releases the heap blocks of symbols that went out of scope.
+    ///
+    /// Blocks that only the symbols' pointers pointed to are leaked, and the symbols no longer count as
+    /// dangling references to freed blocks.
+    ///
+    /// # Arguments
+    ///
+    /// - `symbols`: The symbols that went out of scope.
+    /// - `remaining`: The symbols still in scope, a block one of them points to doesn't leak.
+    /// - `memory`: A mutable reference to the `MemoryModel` the statements are analyzed in.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<(), Error>`: A result containing either:
+    ///  - `Ok(())` if the symbols were released.
+    /// - An `Error` if the memory model couldn't be updated.
+    fn release_symbols<'a>(
+        &self,
+        symbols: impl Iterator<Item = &'a Symbol>,
+        remaining: &IndexMap<String, Symbol>,
+        memory: &mut dyn MemoryModel,
+    ) -> Result<()> {
+        for symbol in symbols {
+            // an object lets go of what its pointer fields point to, once its destructor had the
+            // chance to free it
+            if let Symbol::Object { fields, .. } = symbol {
+                let pointers: Vec<&Symbol> =
+                    fields.iter().filter_map(|field| field.pointer.as_deref()).collect();
+
+                self.release_symbols(pointers.into_iter(), remaining, memory)?;
+            }
+
+            // a `std::string` gives its heap buffer back when it goes away
+            if let Symbol::Variable {
+                string:
+                    Some(
+                        buffer @ StringBuffer {
+                            heap_pointer: Some(heap_pointer),
+                            ..
+                        },
+                    ),
+                ..
+            } = symbol
+            {
+                memory.free(*heap_pointer, buffer.heap_size());
+            }
+
+            if let Symbol::Pointer {
+                name,
+                heap_pointer: Some(heap_pointer),
+                allocation_type,
+                value_size,
+                unique,
+                shared,
+                control_block,
+                offset,
+                ..
+            } = symbol
+            {
+                let block = heap_pointer - offset;
+
+                match allocation_type {
+                    // a `std::unique_ptr` going away frees its block
+                    AllocationType::Heap if *unique => memory.free(block, *value_size),
+                    // a `std::shared_ptr` going away frees its block if it was the last owner
+                    AllocationType::Heap if *shared => {
+                        if let Some(control_block) = control_block {
+                            release_shared(
+                                memory,
+                                name,
+                                block,
+                                *value_size,
+                                *control_block,
+                                remaining,
+                            )?;
+                        }
+                    }
+                    AllocationType::Heap => release_block(
+                        memory,
+                        block,
+                        *value_size,
+                        name,
+                        &block_aliases(remaining, symbol),
+                    )?,
+                    AllocationType::Dangling => {
+                        memory.remove_dangling_pointer(block, name.clone())?
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Runs the destructors of the objects going out of scope, the last one declared first like in C++
+    ///
+    /// # Arguments
+    ///
+    /// - `first`: The index of the first symbol going out of scope, the ones after it go too.
+    /// - `line`: The line the scope ends on, where the destructors are called from.
+    /// - `column`: The column the destructors are called from.
+    /// - `stack_symbols`: A mutable reference to the symbols of the current frame.
+    /// - `memory`: A mutable reference to the `MemoryModel` the statements are analyzed in.
+    /// - `starting_pointers`: A mutable reference to a `IndexMap<String, usize>` containing starting pointers.
+    /// - `call_stack`: A mutable reference to the call stack.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<(), Error>`: An error if a destructor fails.
+    #[allow(clippy::too_many_arguments)]
+    fn run_destructors(
+        &self,
+        first: usize,
+        line: usize,
+        column: usize,
+        stack_symbols: &mut IndexMap<String, Symbol>,
+        memory: &mut dyn MemoryModel,
+        starting_pointers: &mut IndexMap<String, usize>,
+        call_stack: &mut CallStack,
+    ) -> Result<()> {
+        let destructors: Vec<(String, String)> = stack_symbols
+            .iter()
+            .skip(first)
+            .rev()
+            .filter_map(|(name, symbol)| match symbol {
+                Symbol::Object { class_name, .. } => {
+                    Some((destructor_name(class_name), name.clone()))
+                }
+                _ => None,
+            })
+            .filter(|(destructor, _)| call_stack.functions.contains_key(destructor))
+            .collect();
+
+        // the value a `return` left for the caller is put aside while the destructors run
+        let returned = call_stack.returned.take();
+
+        for (destructor, name) in destructors {
+            self.call_function(
+                destructor,
+                Vec::new(),
+                Some(name),
+                line,
+                column,
+                stack_symbols,
+                memory,
+                starting_pointers,
+                call_stack,
+            )?;
+
+            call_stack.sources.record_releases(memory);
+        }
+
+        call_stack.returned = returned;
+
+        Ok(())
+    }
+
+    /// Initializes an object or a union from a brace initializer like `{1, 2}`, writing each value to a
+    /// member in the order the members are declared. Like in C++, the fields of an object left out of
+    /// the initializer are zeroed
+    ///
+    /// # Arguments
+    ///
+    /// - `var_name`: The name of the object or union.
+    /// - `members`: The members the initializer can write, in order.
+    /// - `values`: The values of the initializer.
+    /// - `line`: The line of the declaration.
+    /// - `column`: The column of the name of the object or union.
+    /// - `stack_symbols`: A mutable reference to the symbols of the current frame.
+    /// - `memory`: A mutable reference to the `MemoryModel` the statements are analyzed in.
+    /// - `starting_pointers`: A mutable reference to a `IndexMap<String, usize>` containing starting pointers.
+    /// - `callers`: The symbols of the frames of the calls being executed, outermost first.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<(), Error>`: An error if there are more values than members, or a value can't be
+    ///   written to its member.
+    #[allow(clippy::too_many_arguments)]
+    fn brace_initialize(
+        &self,
+        var_name: &str,
+        members: Vec<String>,
+        values: Vec<ast::Expr>,
+        line: usize,
+        column: usize,
+        stack_symbols: &mut IndexMap<String, Symbol>,
+        memory: &mut dyn MemoryModel,
+        starting_pointers: &mut IndexMap<String, usize>,
+        callers: &[IndexMap<String, Symbol>],
+    ) -> Result<()> {
+        if values.len() > members.len() {
+            return Err(AnalyzerError(
+                format!(
+                    "Too many initializers for `{}`, at most {} can be given",
+                    var_name,
+                    members.len()
+                ),
+                line,
+                column,
+            ));
+        }
+
+        let initialized = values.len();
+
+        for (member, new_value) in members.into_iter().zip(values) {
+            self.analyze_statement(
+                Statement::MemberAssignment {
+                    var_name: var_name.to_owned(),
+                    member,
+                    new_value: Box::new(new_value),
+                    line,
+                    var_ident_column: column,
+                    member_column: column,
+                },
+                stack_symbols,
+                memory,
+                starting_pointers,
+                callers,
+            )?;
+        }
+
+        if let Some(Symbol::Object { fields, .. }) = stack_symbols.get_mut(var_name) {
+            for field in fields.iter_mut().skip(initialized).filter(|field| field.pointer.is_none())
+            {
+                field.value = Some(field.ftype.get_zero_value());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Creates the memory model the statements are analyzed in
+    ///
+    /// # Returns
+    ///
+    /// - `Box<dyn MemoryModel>`: The memory model for the analyzer's options
+    fn memory_model(&self) -> Box<dyn MemoryModel> {
+        Box::new(HostedMemoryModel::new(&self.options))
+    }
+
+    /// Cleans up the starting pointers by removing any pointers that are not in the stack symbols vector.
+    ///
+    /// # Arguments
+    ///
+    /// - `starting_pointers`: A mutable reference to a `IndexMap<String, usize>` containing starting pointers.
+    /// - `stack_symbols_vec`: A reference to a vector of `Symbol` representing the stack symbols.
+    ///
+    /// # Returns
+    ///
+    /// - Nothing
+    fn clean_starting_pointers(
+        &self,
+        starting_pointers: &mut IndexMap<String, usize>,
+        stack_symbols_vec: &Vec<Symbol>,
+    ) {
+        for entry in starting_pointers.clone() {
+            if !stack_symbols_vec.iter().any(|symbol| {
+                let symbol_name = match symbol {
+                    Symbol::Variable { name, .. } => name,
+                    Symbol::Pointer { name, .. } => name,
+                    _ => &String::new(),
+                };
+
+                symbol_name == &entry.0
+            }) {
+                starting_pointers.shift_remove_entry(&entry.0);
+            }
+        }
+    }
+
+    /// Validates that the heap and the pointers on the stack agree with each other, catching allocator bugs
+    /// before they turn into a bogus visualization
+    ///
+    /// # Arguments
+    ///
+    /// - `stack_symbols`: A reference to a `IndexMap<String, Symbol>` containing stack symbols.
+    /// - `memory`: A reference to the `MemoryModel` the statements are analyzed in.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<(), String>`: A description of the first inconsistency found
+    fn check_consistency(
+        &self,
+        stack_symbols: &IndexMap<String, Symbol>,
+        memory: &dyn MemoryModel,
+    ) -> std::result::Result<(), String> {
+        memory.check_consistency()?;
+
+        for symbol in stack_symbols.values() {
+            let Symbol::Pointer {
+                name,
+                heap_pointer,
+                allocation_type: AllocationType::Heap,
+                value_size,
+                offset,
+                control_block,
+                ..
+            } = symbol
+            else {
+                continue;
+            };
+
+            // a block `std::shared_ptr`s own is shown under any one of them
+            let shares_block = |identifier: &String| match stack_symbols.get(identifier) {
+                Some(Symbol::Pointer {
+                    control_block: Some(other),
+                    ..
+                }) => Some(*other) == *control_block,
+                _ => false,
+            };
+
+            let Some(heap_pointer) = heap_pointer else {
+                return Err(format!("Heap pointer `{}` doesn't point to the heap", name));
+            };
+
+            if offset > value_size {
+                return Err(format!("Pointer `{}` points past the end of its block", name));
+            }
+
+            match memory.block(heap_pointer - offset) {
+                Some(HeapBlock {
+                    block_state: heap_allocator::HeapBlockState::Allocated,
+                    current_pointer_identifier: Some(identifier),
+                    size,
+                    ..
+                // a block passed to a function is owned by a pointer of the caller
+                }) if size == value_size
+                    && (identifier == name
+                        || block_aliases(stack_symbols, symbol).contains(identifier)
+                        || shares_block(identifier)
+                        || !stack_symbols.contains_key(identifier)) => {}
+
+                _ => {
+                    return Err(format!(
+                        "Pointer `{}` points to {} but no matching allocated block starts there",
+                        name,
+                        heap_pointer - offset
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Analyzes a single statement and updates the stack symbols and memory model accordingly.
+    ///
+    /// # Arguments
+    ///
+    /// - `statement`: The statement to be analyzed.
+    /// - `stack_symbols`: A mutable reference to a `IndexMap<String, Symbol>` containing stack symbols.
+    /// - `memory`: A mutable reference to the `MemoryModel` the statements are analyzed in.
+    /// - `starting_pointers`: A mutable reference to a `IndexMap<String, usize>` containing starting pointers.
+    /// - `callers`: The symbols of the frames of the calls being executed, outermost first, which raw
+    ///   addresses can point into.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<(), Error>`: A result containing either:
+    ///  - `Ok(())` if the analysis is successful.
+    /// - An `Error` if the analysis fails.
+    fn analyze_statement(
+        &self,
+        statement: Statement,
+        stack_symbols: &mut IndexMap<String, Symbol>,
+        memory: &mut dyn MemoryModel,
+        starting_pointers: &mut IndexMap<String, usize>,
+        callers: &[IndexMap<String, Symbol>],
+    ) -> Result<()> {
+        match statement {
+            ast::Statement::VariableDeclaration {
+                var_type,
+                var_name,
+                value,
+                line,
+                var_ident_column,
+            } => {
+                let value = validate_variable_assignment(
+                    value,
+                    &var_name,
+                    &Type::from_token(var_type)?,
+                    &stack_symbols,
+                    line,
+                    var_ident_column,
+                )?;
+
+                if stack_symbols.contains_key(&var_name) {
+                    return Err(AnalyzerError(
+                        format!("Variable `{}` already declared!", var_name),
+                        line,
+                        var_ident_column,
+                    ));
+                }
+
+                let vtype = Type::from_token(var_type)?;
+                check_integer_range(
+                    &vtype,
+                    value.as_deref(),
+                    &var_name,
+                    memory.pointer_size(),
+                    line,
+                    var_ident_column,
+                )?;
+
+                let string = match (&vtype, &value) {
+                    (Type::String, Some(value)) => {
+                        string_contents(value, &var_name, line, var_ident_column)?;
+
+                        let mut buffer = StringBuffer::default();
+                        assign_string(&var_name, &mut buffer, value, memory, starting_pointers)?;
+                        Some(buffer)
+                    }
+                    _ => None,
+                };
+
+                stack_symbols.insert(
+                    var_name.clone(),
+                    Symbol::Variable {
+                        size: vtype.get_variable_size(memory.pointer_size()),
+                        vtype,
+                        name: var_name,
+                        value,
+                        string,
+                    },
+                );
+            }
+
+            ast::Statement::VariableDeclarationWithoutAssignment {
+                var_type,
+                var_name,
+                line,
+                var_ident_column,
+            } => {
+                if stack_symbols.contains_key(&var_name) {
+                    return Err(AnalyzerError(
+                        format!("Variable `{}` already declared!", var_name),
+                        line,
+                        var_ident_column,
+                    ));
+                }
+
+                // a `std::string` is constructed empty, other variables hold garbage until assigned
+                let vtype = Type::from_token(var_type)?;
+                let (value, string) = match vtype {
+                    Type::String => (Some(vtype.get_zero_value()), Some(StringBuffer::default())),
+                    _ => (None, None),
+                };
+
+                stack_symbols.insert(
+                    var_name.clone(),
+                    Symbol::Variable {
+                        size: vtype.get_variable_size(memory.pointer_size()),
+                        vtype,
+                        name: var_name,
+                        value,
+                        string,
+                    },
+                );
+            }
+
+            ast::Statement::VariableAssignment {
+                var_name,
+                new_value,
+                line,
+                var_ident_column,
+                assignment_column,
+            } => {
+                let cloned_symbols = stack_symbols.clone();
+
+                let step = match stack_symbols.get(&var_name) {
+                    Some(Symbol::Pointer {
+                        unique: false,
+                        shared: false,
+                        ..
+                    }) => {
+                        pointer_step(&new_value, &var_name, stack_symbols, line, assignment_column)?
+                    }
+                    _ => None,
+                };
+
+                if let Some(symbol) = stack_symbols.get_mut(&var_name) {
+                    if let Symbol::Variable {
+                        value,
+                        vtype,
+                        string,
+                        ..
+                    } = symbol
+                    {
+                        let new_value = validate_variable_assignment(
+                            new_value,
+                            &var_name,
+                            vtype,
+                            &cloned_symbols,
+                            line,
+                            var_ident_column,
+                        )?;
+
+                        check_integer_range(
+                            vtype,
+                            new_value.as_deref(),
+                            &var_name,
+                            memory.pointer_size(),
+                            line,
+                            var_ident_column,
+                        )?;
+
+                        if let (Some(buffer), Some(new_value)) = (string.as_mut(), &new_value) {
+                            string_contents(new_value, &var_name, line, var_ident_column)?;
+                            assign_string(&var_name, buffer, new_value, memory, starting_pointers)?;
+                        }
+
+                        *value = new_value;
+                    } else if let (Symbol::Pointer { .. }, Some(step)) = (&*symbol, step) {
+                        move_pointer(symbol, step, &var_name, line, assignment_column)?;
+                    } else if let (
+                        Symbol::Object {
+                            class_name, fields, ..
+                        },
+                        ast::Expr::Ident(source),
+                    ) = (&mut *symbol, new_value.as_ref())
+                    {
+                        copy_fields(
+                            class_name,
+                            fields,
+                            source,
+                            &cloned_symbols,
+                            line,
+                            assignment_column,
+                        )?;
+                    } else if let (Symbol::Pointer { unique: true, .. }, ast::Expr::Ident(source)) =
+                        (symbol, new_value.as_ref())
+                    {
+                        return Err(unique_copy_error(source, line, assignment_column));
+                    } else {
+                        return Err(AnalyzerError(
+                            format!(
+                                "Invalid use case of assignment operator for symbol `{}`",
+                                var_name
+                            ),
+                            line,
+                            assignment_column,
+                        ));
+                    }
+                } else {
+                    return Err(AnalyzerError(
+                        format!("Variable `{}` not found!", var_name),
+                        line,
+                        var_ident_column,
+                    ));
+                }
+            }
+
+            ast::Statement::PointerDeclaration {
+                base_type,
+                indirection,
+                pointer_name,
+                value,
+                line,
+                pointer_ident_column,
+            } => {
+                if stack_symbols.contains_key(&pointer_name) {
+                    return Err(AnalyzerError(
+                        format!("Pointer `{}` already declared!", pointer_name),
+                        line,
+                        pointer_ident_column,
+                    ));
+                }
+
+                let ptype = Type::from_token(base_type)?;
+
+                let cast = validate_pointer_cast(
+                    &value,
+                    &ptype,
+                    indirection,
+                    &pointer_name,
+                    stack_symbols,
+                    line,
+                    pointer_ident_column,
+                )?;
+
+                if let Some(source) = cast {
+                    let pointer = cast_pointer(source, ptype, pointer_name.clone(), memory)?;
+                    stack_symbols.insert(pointer_name, pointer);
+
+                    return Ok(());
+                }
+
+                // anything but an address is a copy of another pointer, like `p` or `p + 1`
+                let ast::Expr::AddressOf(value) = *value else {
+                    let pointer = copy_pointer(
+                        &value,
+                        &ptype,
+                        indirection,
+                        &pointer_name,
+                        stack_symbols,
+                        memory,
+                        line,
+                        pointer_ident_column,
+                    )?;
+                    stack_symbols.insert(pointer_name, pointer);
+
+                    return Ok(());
+                };
+
+                let value = validate_pointer_assignment(
+                    value,
+                    indirection,
+                    stack_symbols,
+                    line,
+                    pointer_ident_column,
+                )?;
+
+                stack_symbols.insert(
+                    pointer_name.clone(),
+                    Symbol::Pointer {
+                        ptype: Type::from_token(base_type)?,
+                        name: pointer_name,
+                        value,
+                        allocation_type: AllocationType::Stack,
+                        heap_pointer: None,
+                        pointer_size: memory.pointer_size(),
+                        value_size: pointee_size(&ptype, indirection, memory.pointer_size()),
+                        indirection,
+                        unique: false,
+                        shared: false,
+                        control_block: None,
+                        offset: 0,
+                    },
+                );
+            }
+
+            ast::Statement::PointerDeclarationHeap {
+                base_type,
+                allocation,
+                new_type,
+                pointer_name,
+                line,
+                pointer_ident_column,
+            } => {
+                if stack_symbols.contains_key(&pointer_name) {
+                    return Err(AnalyzerError(
+                        format!("Pointer `{}` already declared!", &pointer_name),
+                        line,
+                        pointer_ident_column,
+                    ));
+                }
+
+                let ptype = Type::from_token(base_type)?;
+
+                // a block for a `void*` can be a number of bytes
+                let value_size = match Type::from_token(new_type)? {
+                    Type::Void => allocation.count(),
+                    new_type => {
+                        allocation.count() * new_type.get_variable_size(memory.pointer_size())
+                    }
+                };
+
+                let res = memory.allocate(&pointer_name, value_size, starting_pointers);
+
+                if let Err(e) = res {
+                    return Err(AnalyzerError(e.to_string(), line, pointer_ident_column));
+                }
+
+                let heap_pointer = res.unwrap();
+
+                // `calloc` zeroes the block while `malloc` leaves whatever garbage was there
+                let value = match allocation {
+                    ast::Allocation::New | ast::Allocation::NewArray { .. } => {
+                        ptype.get_garbage_value()
+                    }
+                    ast::Allocation::Malloc { .. } => "".to_owned(),
+                    ast::Allocation::Calloc { .. } => {
+                        memory.update_metadata(heap_pointer, ptype.get_zero_value())?;
+                        ptype.get_zero_value()
+                    }
+                };
+
+                stack_symbols.insert(
+                    pointer_name.clone(),
+                    Symbol::Pointer {
+                        ptype,
+                        name: pointer_name,
+                        value: Some(Box::new(Symbol::Literal { value })),
+                        heap_pointer: Some(heap_pointer),
+                        allocation_type: AllocationType::Heap,
+                        pointer_size: memory.pointer_size(),
+                        value_size,
+                        indirection: 1,
+                        unique: false,
+                        shared: false,
+                        control_block: None,
+                        offset: 0,
+                    },
+                );
+            }
+
+            ast::Statement::MemoryMap {
+                base_type,
+                count,
+                size_type,
+                pointer_name,
+                line,
+                pointer_ident_column,
+            } => {
+                if stack_symbols.contains_key(&pointer_name) {
+                    return Err(AnalyzerError(
+                        format!("Pointer `{}` already declared!", &pointer_name),
+                        line,
+                        pointer_ident_column,
+                    ));
+                }
+
+                let ptype = Type::from_token(base_type)?;
+
+                let size = match Type::from_token(size_type)? {
+                    Type::Void => count,
+                    size_type => count * size_type.get_variable_size(memory.pointer_size()),
+                };
+
+                memory.map(&pointer_name, size);
+
+                stack_symbols.insert(
+                    pointer_name.clone(),
+                    Symbol::Pointer {
+                        ptype: ptype.clone(),
+                        name: pointer_name,
+                        value: None,
+                        allocation_type: AllocationType::Mapped,
+                        heap_pointer: None,
+                        pointer_size: memory.pointer_size(),
+                        value_size: pointee_size(&ptype, 1, memory.pointer_size()),
+                        indirection: 1,
+                        unique: false,
+                        shared: false,
+                        control_block: None,
+                        offset: 0,
+                    },
+                );
+            }
+
+            ast::Statement::MemoryUnmap {
+                pointer_name,
+                count,
+                size_type,
+                line,
+                pointer_ident_column,
+                length_column,
+            } => {
+                match stack_symbols.get(&pointer_name) {
+                    Some(Symbol::Pointer {
+                        allocation_type: AllocationType::Mapped,
+                        ..
+                    }) => {}
+                    Some(_) => {
+                        return Err(AnalyzerError(
+                            format!(
+                                "Cannot unmap `{}`, it wasn't mapped with `mmap`",
+                                pointer_name
+                            ),
+                            line,
+                            pointer_ident_column,
+                        ));
+                    }
+                    None => {
+                        return Err(AnalyzerError(
+                            format!("Pointer `{}` not found!", pointer_name),
+                            line,
+                            pointer_ident_column,
+                        ));
+                    }
+                }
+
+                let Some(region) =
+                    memory.mappings().into_iter().find(|region| region.owner == pointer_name)
+                else {
+                    return Err(AnalyzerError(
+                        format!(
+                            "Cannot unmap `{}`, the region it points to was mapped for another pointer",
+                            pointer_name
+                        ),
+                        line,
+                        pointer_ident_column,
+                    ));
+                };
+
+                let length = match Type::from_token(size_type)? {
+                    Type::Void => count,
+                    size_type => count * size_type.get_variable_size(memory.pointer_size()),
+                };
+
+                // the length is rounded up to whole pages like the size passed to `mmap`, so the 5000
+                // bytes `mmap(5000)` asked for unmap all 8192 bytes of its region
+                if length == 0 || length.div_ceil(PAGE_SIZE) * PAGE_SIZE != region.size {
+                    return Err(AnalyzerError(
+                        format!(
+                            "Cannot unmap {} bytes of `{}`, its region is {} bytes and unmapping part \
+                             of a region is not supported",
+                            length, pointer_name, region.size
+                        ),
+                        line,
+                        length_column,
+                    ));
+                }
+
+                memory.unmap(&pointer_name);
+
+                if let Some(Symbol::Pointer {
+                    allocation_type, ..
+                }) = stack_symbols.get_mut(&pointer_name)
+                {
+                    *allocation_type = AllocationType::Dangling;
+                }
+            }
+
+            ast::Statement::PointerDeclarationNull {
+                base_type,
+                indirection,
+                pointer_name,
+                line,
+                pointer_ident_column,
+            } => {
+                if stack_symbols.contains_key(&pointer_name) {
+                    return Err(AnalyzerError(
+                        format!("Pointer `{}` already declared!", &pointer_name),
+                        line,
+                        pointer_ident_column,
+                    ));
+                }
+
+                let ptype = Type::from_token(base_type)?;
+
+                stack_symbols.insert(
+                    pointer_name.clone(),
+                    Symbol::Pointer {
+                        ptype: ptype.clone(),
+                        name: pointer_name,
+                        value: None,
+                        allocation_type: AllocationType::Null,
+                        heap_pointer: None,
+                        pointer_size: memory.pointer_size(),
+                        value_size: pointee_size(&ptype, indirection, memory.pointer_size()),
+                        indirection,
+                        unique: false,
+                        shared: false,
+                        control_block: None,
+                        offset: 0,
+                    },
+                );
+            }
+
+            ast::Statement::PointerAssignment {
+                pointer_name,
+                new_value,
+                line,
+                pointer_ident_column,
+            } => {
+                validate_raw_pointer(
+                    stack_symbols,
+                    &pointer_name,
+                    "&",
+                    line,
+                    pointer_ident_column,
+                )?;
+
+                let indirection = match stack_symbols.get(&pointer_name) {
+                    Some(Symbol::Pointer { indirection, .. }) => *indirection,
+                    _ => 1,
+                };
+
+                let cast = match stack_symbols.get(&pointer_name) {
+                    Some(Symbol::Pointer { ptype, .. }) => validate_pointer_cast(
+                        &new_value,
+                        ptype,
+                        indirection,
+                        &pointer_name,
+                        stack_symbols,
+                        line,
+                        pointer_ident_column,
+                    )?,
+                    _ => None,
+                };
+
+                // casting a pointer to its own type leaves it where it is
+                if matches!(&cast, Some(Symbol::Pointer { name, .. }) if *name == pointer_name) {
+                    return Ok(());
+                }
+
+                let new_value = match cast {
+                    Some(_) => None,
+                    None => validate_pointer_assignment(
+                        new_value,
+                        indirection,
+                        stack_symbols,
+                        line,
+                        pointer_ident_column,
+                    )?,
+                };
+
+                let aliases = stack_symbols
+                    .get(&pointer_name)
+                    .map_or_else(Vec::new, |pointer| block_aliases(stack_symbols, pointer));
+
+                if let Some(symbol) = stack_symbols.get_mut(&pointer_name) {
+                    if let Symbol::Pointer {
+                        ptype,
+                        name,
+                        value,
+                        allocation_type,
+                        heap_pointer,
+                        value_size,
+                        offset,
+                        ..
+                    } = symbol
+                    {
+                        if *allocation_type != AllocationType::Dangling {
+                            if let Some(heap_pointer) = heap_pointer {
+                                release_block(
+                                    memory,
+                                    *heap_pointer - *offset,
+                                    *value_size,
+                                    name,
+                                    &aliases,
+                                )?;
+                            }
+                        } else {
+                            if let Some(heap_pointer) = heap_pointer {
+                                memory.remove_dangling_pointer(
+                                    *heap_pointer - *offset,
+                                    name.to_string(),
+                                )?;
+                            }
+                        }
+
+                        *value = new_value;
+                        *allocation_type = AllocationType::Stack;
+                        *heap_pointer = None;
+                        *offset = 0;
+                        *value_size = pointee_size(ptype, indirection, memory.pointer_size());
+                    } else {
+                        return Err(AnalyzerError(
+                            format!(
+                                "Invalid use case of assignment operator for symbol `{}`",
+                                pointer_name
+                            ),
+                            line,
+                            pointer_ident_column,
+                        ));
+                    }
+
+                    if let (Some(source), Symbol::Pointer { ptype, .. }) = (cast, &*symbol) {
+                        *symbol = cast_pointer(source, ptype.clone(), pointer_name, memory)?;
+                    }
+                } else {
+                    return Err(AnalyzerError(
+                        format!("Pointer `{}` not found!", pointer_name),
+                        line,
+                        pointer_ident_column,
+                    ));
+                }
+            }
+
+            ast::Statement::PointerAssignmentHeap {
+                pointer_name,
+                allocation,
+                new_type,
+                line,
+                pointer_ident_column,
+                new_type_column,
+            } => {
+                validate_raw_pointer(stack_symbols, &pointer_name, "new", line, new_type_column)?;
+
+                let aliases = stack_symbols
+                    .get(&pointer_name)
+                    .map_or_else(Vec::new, |pointer| block_aliases(stack_symbols, pointer));
+
+                if let Some(symbol) = stack_symbols.get_mut(&pointer_name) {
+                    if let Symbol::Pointer {
+                        ptype,
+                        name,
+                        value,
+                        allocation_type,
+                        heap_pointer,
+                        value_size,
+                        indirection,
+                        offset,
+                        ..
+                    } = symbol
+                    {
+                        if *indirection > 1 {
+                            return Err(AnalyzerError(
+                                format!(
+                                    "Pointer to pointer `{}` can't point to the heap yet",
+                                    pointer_name
+                                ),
+                                line,
+                                new_type_column,
+                            ));
+                        }
+
+                        // a number of bytes has to hold a whole number of the values the pointer
+                        // points to
+                        let value_type_size = ptype.get_variable_size(memory.pointer_size());
+
+                        if new_type == TokenKind::KwVoid
+                            && *ptype != Type::Void
+                            && allocation.count() % value_type_size != 0
+                        {
+                            let allocator = match allocation {
+                                ast::Allocation::Calloc { .. } => "calloc",
+                                _ => "malloc",
+                            };
+
+                            return Err(AnalyzerError(
+                                format!(
+                                    "The {} bytes passed to `{}` aren't a multiple of {}, the size of `{}`",
+                                    allocation.count(),
+                                    allocator,
+                                    value_type_size,
+                                    ptype
+                                ),
+                                line,
+                                new_type_column,
+                            ));
+                        }
+
+                        if !ptype.is_type(new_type)
+                            && *ptype != Type::Void
+                            && new_type != TokenKind::KwVoid
+                        {
+                            return Err(AnalyzerError(
+                                format!(
+                                    "Cannot assign `{}` to pointer `{}` (incorrect type)",
+                                    &new_type, &pointer_name
+                                ),
+                                line,
+                                new_type_column,
+                            ));
+                        }
+
+                        if *allocation_type != AllocationType::Dangling {
+                            if let Some(heap_pointer) = heap_pointer {
+                                release_block(
+                                    memory,
+                                    *heap_pointer - *offset,
+                                    *value_size,
+                                    name,
+                                    &aliases,
+                                )?;
+                            }
+                        } else {
+                            if let Some(heap_pointer) = heap_pointer {
+                                memory.remove_dangling_pointer(
+                                    *heap_pointer - *offset,
+                                    name.to_string(),
+                                )?;
+                            }
+                        }
+
+                        *value_size = match Type::from_token(new_type)? {
+                            Type::Void => allocation.count(),
+                            new_type => {
+                                allocation.count()
+                                    * new_type.get_variable_size(memory.pointer_size())
+                            }
+                        };
+
+                        let res = memory.allocate(&pointer_name, *value_size, starting_pointers);
+
+                        if let Err(e) = res {
+                            return Err(AnalyzerError(e.to_string(), line, pointer_ident_column));
+                        }
+
+                        let new_heap_pointer = res.unwrap();
+
+                        let new_value = match allocation {
+                            ast::Allocation::New
+                            | ast::Allocation::NewArray { .. }
+                            | ast::Allocation::Malloc { .. } => "".to_owned(),
+                            ast::Allocation::Calloc { .. } => {
+                                memory.update_metadata(new_heap_pointer, ptype.get_zero_value())?;
+                                ptype.get_zero_value()
+                            }
+                        };
+
+                        *allocation_type = AllocationType::Heap;
+                        *value = Some(Box::new(Symbol::Literal { value: new_value }));
+                        *heap_pointer = Some(new_heap_pointer);
+                        *offset = 0;
+                    }
+                } else {
+                    return Err(AnalyzerError(
+                        format!("Pointer `{}` not found!", pointer_name),
+                        line,
+                        pointer_ident_column,
+                    ));
+                }
+            }
+
+            ast::Statement::PointerAssignmentNull {
+                pointer_name,
+                line,
+                pointer_ident_column,
+            } => {
+                let aliases = stack_symbols
+                    .get(&pointer_name)
+                    .map_or_else(Vec::new, |pointer| block_aliases(stack_symbols, pointer));
+
+                if let Some(symbol) = stack_symbols.get_mut(&pointer_name) {
+                    if let Symbol::Pointer {
+                        name,
+                        value,
+                        allocation_type,
+                        heap_pointer,
+                        value_size,
+                        unique,
+                        offset,
+                        ..
+                    } = symbol
+                    {
+                        // a `std::unique_ptr` frees the block it owned instead of leaking it
+                        if *unique {
+                            if let Some(heap_pointer) = heap_pointer {
+                                memory.free(*heap_pointer, *value_size);
+                            }
+                        } else if *allocation_type != AllocationType::Dangling {
+                            if let Some(heap_pointer) = heap_pointer {
+                                release_block(
+                                    memory,
+                                    *heap_pointer - *offset,
+                                    *value_size,
+                                    name,
+                                    &aliases,
+                                )?;
+                            }
+                        } else {
+                            if let Some(heap_pointer) = heap_pointer {
+                                memory.remove_dangling_pointer(
+                                    *heap_pointer - *offset,
+                                    name.to_string(),
+                                )?;
+                            }
+                        }
+
+                        *value = None;
+                        *allocation_type = AllocationType::Null;
+                        *heap_pointer = None;
+                        *offset = 0;
+                    }
+                } else {
+                    return Err(AnalyzerError(
+                        format!("Pointer `{}` not found!", pointer_name),
+                        line,
+                        pointer_ident_column,
+                    ));
+                }
+            }
+
+            ast::Statement::PointerReallocation {
+                pointer_name,
+                source_name,
+                count,
+                new_type,
+                line,
+                pointer_ident_column,
+                source_column,
+                size_column,
+            } => {
+                validate_raw_pointer(stack_symbols, &pointer_name, "realloc", line, source_column)?;
+
+                if source_name != pointer_name {
+                    return Err(AnalyzerError(
+                        format!(
+                            "`realloc` can only be assigned back to the pointer it resizes, like `{0} = realloc({0}, ...)`",
+                            source_name
+                        ),
+                        line,
+                        pointer_ident_column,
+                    ));
+                }
+
+                let aliases = stack_symbols
+                    .get(&pointer_name)
+                    .map_or_else(Vec::new, |pointer| block_aliases(stack_symbols, pointer));
+
+                let Some(Symbol::Pointer {
+                    ptype,
+                    value,
+                    allocation_type,
+                    heap_pointer,
+                    value_size,
+                    indirection,
+                    offset,
+                    ..
+                }) = stack_symbols.get_mut(&pointer_name)
+                else {
+                    return Err(AnalyzerError(
+                        format!("Pointer `{}` not found!", pointer_name),
+                        line,
+                        source_column,
+                    ));
+                };
+
+                if *indirection > 1 {
+                    return Err(AnalyzerError(
+                        format!(
+                            "Pointer to pointer `{}` can't point to the heap yet",
+                            pointer_name
+                        ),
+                        line,
+                        source_column,
+                    ));
+                }
+
+                if *offset != 0 {
+                    return Err(AnalyzerError(
+                        format!(
+                            "Cannot reallocate `{}`, it doesn't point to the start of its block",
+                            pointer_name
+                        ),
+                        line,
+                        source_column,
+                    ));
+                }
+
+                // a `void*` can be resized for any type
+                if let Some(new_type) =
+                    new_type.filter(|new_type| !ptype.is_type(*new_type) && *ptype != Type::Void)
+                {
+                    return Err(AnalyzerError(
+                        format!(
+                            "Cannot reallocate `{}` for pointer `{}` (incorrect type)",
+                            new_type, pointer_name
+                        ),
+                        line,
+                        size_column,
+                    ));
+                }
+
+                let new_size = match new_type {
+                    Some(new_type) => {
+                        count * Type::from_token(new_type)?.get_variable_size(memory.pointer_size())
+                    }
+                    None => count,
+                };
+
+                if new_size == 0 {
+                    return Err(AnalyzerError(
+                        "Reallocating to 0 bytes is not supported, use `free` instead".to_string(),
+                        line,
+                        size_column,
+                    ));
+                }
+
+                let mut moved_from = None;
+
+                match allocation_type {
+                    AllocationType::Heap => {
+                        let old_heap_pointer =
+                            heap_pointer.expect("heap pointers point to a block");
+
+                        let (new_heap_pointer, _) = memory
+                            .reallocate(
+                                &pointer_name,
+                                old_heap_pointer,
+                                *value_size,
+                                new_size,
+                                starting_pointers,
+                            )
+                            .map_err(|e| {
+                                AnalyzerError(e.to_string(), line, pointer_ident_column)
+                            })?;
+
+                        *heap_pointer = Some(new_heap_pointer);
+                        moved_from = Some(old_heap_pointer).filter(|old| *old != new_heap_pointer);
+                    }
+
+                    // `realloc` of a null pointer allocates like `malloc`
+                    AllocationType::Null => {
+                        let new_heap_pointer =
+                            memory.allocate(&pointer_name, new_size, starting_pointers).map_err(
+                                |e| AnalyzerError(e.to_string(), line, pointer_ident_column),
+                            )?;
+
+                        *value = Some(Box::new(Symbol::Literal {
+                            value: "".to_owned(),
+                        }));
+                        *allocation_type = AllocationType::Heap;
+                        *heap_pointer = Some(new_heap_pointer);
+                    }
+
+                    AllocationType::Dangling => {
+                        return Err(AnalyzerError(
+                            format!("Cannot reallocate dangling pointer `{}`", pointer_name),
+                            line,
+                            source_column,
+                        ));
+                    }
+
+                    AllocationType::Stack => {
+                        return Err(AnalyzerError(
+                            format!("Cannot reallocate stack pointer `{}`", pointer_name),
+                            line,
+                            source_column,
+                        ));
+                    }
+
+                    AllocationType::Mapped => {
+                        return Err(AnalyzerError(
+                            format!(
+                                "Cannot reallocate `{}`, it was mapped with `mmap`",
+                                pointer_name
+                            ),
+                            line,
+                            source_column,
+                        ));
+                    }
+                }
+
+                *value_size = new_size;
+
+                // pointers cast from the pointer still point where the block was
+                match moved_from {
+                    Some(old_heap_pointer) => {
+                        leave_dangling(stack_symbols, &aliases, old_heap_pointer, memory)?
+                    }
+                    None => {
+                        for alias in &aliases {
+                            if let Some(Symbol::Pointer { value_size, .. }) =
+                                stack_symbols.get_mut(alias)
+                            {
+                                *value_size = new_size;
+                            }
+                        }
+                    }
+                }
+            }
+
+            ast::Statement::Deref {
+                pointer_name,
+                new_value,
+                line,
+                pointer_ident_column,
+                new_value_column,
+            } => {
+                let cloned_symbols = stack_symbols.clone();
+                let new_value =
+                    Box::new(fold_expression(*new_value, &cloned_symbols, line, new_value_column)?);
+
+                if let Some(symbol) = stack_symbols.get_mut(&pointer_name) {
+                    if let Symbol::Pointer {
+                        value,
+                        ptype,
+                        allocation_type,
+                        heap_pointer,
+                        indirection,
+                        value_size,
+                        offset,
+                        ..
+                    } = symbol
+                    {
+                        if *indirection > 1 {
+                            return Err(AnalyzerError(
+                                format!(
+                                    "Assigning through pointer to pointer `{}` is not supported yet",
+                                    pointer_name
+                                ),
+                                line,
+                                pointer_ident_column,
+                            ));
+                        }
+
+                        if *ptype == Type::Void {
+                            return Err(AnalyzerError(
+                                format!(
+                                    "Cannot dereference `void*` `{}`, cast it to a typed pointer first",
+                                    pointer_name
+                                ),
+                                line,
+                                pointer_ident_column,
+                            ));
+                        }
+
+                        if let Type::Class(class_name) = ptype {
+                            return Err(AnalyzerError(
+                                format!(
+                                    "Cannot assign through `{}*` `{}`, use `->` to reach its fields",
+                                    class_name, pointer_name
+                                ),
+                                line,
+                                pointer_ident_column,
+                            ));
+                        }
+
+                        let pointer_value = value;
+                        let allocation_type = match *allocation_type {
+                            AllocationType::Dangling => AllocationType::Heap,
+                            _ => (*allocation_type).clone(),
+                        };
+
+                        if allocation_type == AllocationType::Null {
+                            return Err(AnalyzerError(
+                                format!("Cannot dereference null pointer `{}`", pointer_name),
+                                line,
+                                pointer_ident_column,
+                            ));
+                        }
+
+                        if allocation_type == AllocationType::Mapped {
+                            return Err(AnalyzerError(
+                                format!(
+                                    "Cannot write through `{}`, it points to a mapping",
+                                    pointer_name
+                                ),
+                                line,
+                                pointer_ident_column,
+                            ));
+                        }
+
+                        if allocation_type == AllocationType::Heap && *offset >= *value_size {
+                            return Err(AnalyzerError(
+                                format!(
+                                    "Cannot dereference `{}`, it points past the end of its block",
+                                    pointer_name
+                                ),
+                                line,
+                                pointer_ident_column,
+                            ));
+                        }
+
+                        match *new_value {
+                            ast::Expr::Ident(new_ident) => {
+                                if let Some(symbol) = cloned_symbols.get(&new_ident) {
+                                    if let Symbol::Variable { value, .. } = symbol {
+                                        if let Some(value) = value {
+                                            if ptype
+                                                .is_correct_literal(&ast::Lit::from_str(&value)?)
+                                            {
+                                                let new_value = value.to_string();
+                                                let old_symbol = (*pointer_value).clone();
+
+                                                if allocation_type == AllocationType::Heap {
+                                                    if heap_pointer.is_none() {
+                                                        return Err(AnalyzerError(
+                                                            format!(
+                                                                "Heap pointer not found for `{}`",
                                                                 pointer_name
                                                             ),
                                                             line,
@@ -624,169 +3642,1528 @@ impl Analyzer {
                                                         ));
                                                     }
 
-                                                    return Ok(());
-                                                }
+                                                    return write_pointee(
+                                                        &pointer_name,
+                                                        new_value,
+                                                        stack_symbols,
+                                                        memory,
+                                                    );
+                                                }
+
+                                                // a pointer passed to a function carries what is
+                                                // written through it back to the caller
+                                                if let Some(Symbol::Variable { value, .. }) =
+                                                    pointer_value.as_deref_mut()
+                                                {
+                                                    *value = Some(new_value.clone());
+                                                }
+
+                                                if let Some(old_symbol) = old_symbol {
+                                                    if let Symbol::Variable { name, .. } =
+                                                        *old_symbol
+                                                    {
+                                                        if let Some(symbol) =
+                                                            stack_symbols.get_mut(&name)
+                                                        {
+                                                            if let Symbol::Variable {
+                                                                value, ..
+                                                            } = symbol
+                                                            {
+                                                                *value = Some(new_value);
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            } else {
+                                                return Err(AnalyzerError(
+                                                    format!(
+                                                        "Cannot assign `{}` to pointer `{}` (incorrect type)",
+                                                        value, pointer_name
+                                                    ),
+                                                    line,
+                                                    new_value_column,
+                                                ));
+                                            }
+                                        } else {
+                                            return Err(AnalyzerError(
+                                                format!(
+                                                    "Variable `{}` not initialized!",
+                                                    new_ident
+                                                ),
+                                                line,
+                                                new_value_column,
+                                            ));
+                                        }
+                                    } else {
+                                        return Err(AnalyzerError(
+                                            format!("Can only assign variables to pointers!",),
+                                            line,
+                                            new_value_column,
+                                        ));
+                                    }
+                                } else {
+                                    return Err(AnalyzerError(
+                                        format!("Variable `{}` not found!", new_ident),
+                                        line,
+                                        new_value_column,
+                                    ));
+                                }
+                            }
+                            ast::Expr::Literal(lit) => {
+                                if ptype.is_correct_literal(&lit) {
+                                    check_integer_range(
+                                        ptype,
+                                        Some(&lit.to_string()),
+                                        &format!("*{}", pointer_name),
+                                        self.options.architecture.pointer_size(),
+                                        line,
+                                        new_value_column,
+                                    )?;
+
+                                    let old_symbol = (*pointer_value).clone();
+
+                                    if allocation_type == AllocationType::Heap {
+                                        if heap_pointer.is_none() {
+                                            return Err(AnalyzerError(
+                                                format!(
+                                                    "Heap pointer not found for `{}`",
+                                                    pointer_name
+                                                ),
+                                                line,
+                                                pointer_ident_column,
+                                            ));
+                                        }
+
+                                        return write_pointee(
+                                            &pointer_name,
+                                            lit.to_string(),
+                                            stack_symbols,
+                                            memory,
+                                        );
+                                    }
+
+                                    // a pointer passed to a function carries what is written through
+                                    // it back to the caller
+                                    if let Some(Symbol::Variable { value, .. }) =
+                                        pointer_value.as_deref_mut()
+                                    {
+                                        *value = Some(lit.to_string());
+                                    }
+
+                                    if let Some(old_symbol) = old_symbol {
+                                        if let Symbol::Variable { name, .. } = *old_symbol {
+                                            if let Some(symbol) = stack_symbols.get_mut(&name) {
+                                                if let Symbol::Variable { value, .. } = symbol {
+                                                    *value = Some(lit.to_string());
+                                                }
+                                            }
+                                        }
+                                    }
+                                } else {
+                                    return Err(AnalyzerError(
+                                        format!(
+                                            "Cannot assign `{}` to pointer `{}` (incorrect type)",
+                                            lit, pointer_name
+                                        ),
+                                        line,
+                                        new_value_column,
+                                    ));
+                                }
+                            }
+                            new_value => {
+                                return Err(AnalyzerError(
+                                    format!(
+                                        "Cannot assign `{}` to pointer `{}` (incorrect type)",
+                                        new_value, pointer_name
+                                    ),
+                                    line,
+                                    new_value_column,
+                                ));
+                            }
+                        };
+                    }
+                } else {
+                    return Err(AnalyzerError(
+                        format!("Pointer `{}` not found!", pointer_name),
+                        line,
+                        pointer_ident_column,
+                    ));
+                }
+            }
+
+            Statement::Delete {
+                pointer_name,
+                line,
+                pointer_ident_column,
+                ..
+            } => {
+                validate_raw_pointer(
+                    stack_symbols,
+                    &pointer_name,
+                    "delete",
+                    line,
+                    pointer_ident_column,
+                )?;
+
+                // a mapping isn't part of the heap, so `delete` and `free` can't give it back
+                if let Some(Symbol::Pointer {
+                    allocation_type: AllocationType::Mapped,
+                    ..
+                }) = stack_symbols.get(&pointer_name)
+                {
+                    return Err(AnalyzerError(
+                        format!("Cannot free `{}`, it was mapped with `mmap`", pointer_name),
+                        line,
+                        pointer_ident_column,
+                    ));
+                }
+
+                let aliases = stack_symbols
+                    .get(&pointer_name)
+                    .map_or_else(Vec::new, |pointer| block_aliases(stack_symbols, pointer));
+
+                let mut freed = None;
+
+                if let Some(symbol) = stack_symbols.get_mut(&pointer_name) {
+                    if let Symbol::Pointer {
+                        heap_pointer,
+                        value_size,
+                        allocation_type,
+                        offset,
+                        ..
+                    } = symbol
+                    {
+                        if *allocation_type == AllocationType::Stack {
+                            return Err(AnalyzerError(
+                                format!("Cannot delete stack pointer `{}`", pointer_name),
+                                line,
+                                pointer_ident_column,
+                            ));
+                        }
+
+                        if *allocation_type == AllocationType::Null {
+                            return Err(AnalyzerError(
+                                format!("Cannot delete null pointer `{}`", pointer_name),
+                                line,
+                                pointer_ident_column,
+                            ));
+                        }
+
+                        if *allocation_type == AllocationType::Dangling {
+                            return Err(AnalyzerError(
+                                format!("Cannot delete dangling pointer `{}`", pointer_name),
+                                line,
+                                pointer_ident_column,
+                            ));
+                        }
+
+                        if *offset != 0 {
+                            return Err(AnalyzerError(
+                                format!(
+                                    "Cannot delete `{}`, it doesn't point to the start of its block",
+                                    pointer_name
+                                ),
+                                line,
+                                pointer_ident_column,
+                            ));
+                        }
+
+                        *allocation_type = AllocationType::Dangling;
+
+                        if let Some(heap_pointer) = heap_pointer {
+                            memory.free(*heap_pointer, *value_size);
+                            memory.insert_dangling_pointer(*heap_pointer, pointer_name)?;
+                            freed = Some(*heap_pointer);
+                        }
+                    }
+                } else {
+                    return Err(AnalyzerError(
+                        format!("Pointer `{}` not found!", pointer_name),
+                        line,
+                        pointer_ident_column,
+                    ));
+                }
+
+                // pointers cast from the one freed dangle along with it
+                if let Some(block) = freed {
+                    leave_dangling(stack_symbols, &aliases, block, memory)?;
+                }
+            }
+
+            Statement::Swap {
+                lhs,
+                rhs,
+                line,
+                lhs_column,
+                rhs_column,
+            } => {
+                let lhs_symbol = stack_symbols.get(&lhs).cloned().ok_or_else(|| {
+                    AnalyzerError(format!("Variable `{}` not found!", lhs), line, lhs_column)
+                })?;
+
+                let rhs_symbol = stack_symbols.get(&rhs).cloned().ok_or_else(|| {
+                    AnalyzerError(format!("Variable `{}` not found!", rhs), line, rhs_column)
+                })?;
+
+                match (lhs_symbol, rhs_symbol) {
+                    (
+                        Symbol::Variable {
+                            vtype: lhs_type,
+                            value: lhs_value,
+                            string: lhs_string,
+                            ..
+                        },
+                        Symbol::Variable {
+                            vtype: rhs_type,
+                            value: rhs_value,
+                            string: rhs_string,
+                            ..
+                        },
+                    ) => {
+                        if lhs_type != rhs_type {
+                            return Err(AnalyzerError(
+                                format!("Cannot swap `{}` and `{}` (incorrect type)", lhs, rhs),
+                                line,
+                                rhs_column,
+                            ));
+                        }
+
+                        // strings swap their buffers, so heap buffers change owners without being copied
+                        for (name, new_value, new_string) in
+                            [(&lhs, rhs_value, rhs_string), (&rhs, lhs_value, lhs_string)]
+                        {
+                            if let Some(heap_pointer) =
+                                new_string.as_ref().and_then(|string| string.heap_pointer)
+                            {
+                                memory.update_owner(heap_pointer, name.clone())?;
+                            }
+
+                            if let Some(Symbol::Variable { value, string, .. }) =
+                                stack_symbols.get_mut(name)
+                            {
+                                *value = new_value;
+                                *string = new_string;
+                            }
+                        }
+                    }
+
+                    (
+                        mut lhs_pointer @ Symbol::Pointer { .. },
+                        mut rhs_pointer @ Symbol::Pointer { .. },
+                    ) => {
+                        let same_type = matches!(
+                            (&lhs_pointer, &rhs_pointer),
+                            (
+                                Symbol::Pointer {
+                                    ptype: lhs_type,
+                                    indirection: lhs_indirection,
+                                    unique: lhs_unique,
+                                    shared: lhs_shared,
+                                    ..
+                                },
+                                Symbol::Pointer {
+                                    ptype: rhs_type,
+                                    indirection: rhs_indirection,
+                                    unique: rhs_unique,
+                                    shared: rhs_shared,
+                                    ..
+                                },
+                            ) if lhs_type == rhs_type
+                                && lhs_indirection == rhs_indirection
+                                && lhs_unique == rhs_unique
+                                && lhs_shared == rhs_shared
+                        );
+
+                        if !same_type {
+                            return Err(AnalyzerError(
+                                format!("Cannot swap `{}` and `{}` (incorrect type)", lhs, rhs),
+                                line,
+                                rhs_column,
+                            ));
+                        }
+
+                        // The pointers trade what they point to, so the blocks they own or dangle at
+                        // change hands too
+                        for pointer in [&lhs_pointer, &rhs_pointer] {
+                            if let Symbol::Pointer {
+                                name,
+                                heap_pointer: Some(heap_pointer),
+                                allocation_type: AllocationType::Dangling,
+                                offset,
+                                ..
+                            } = pointer
+                            {
+                                memory
+                                    .remove_dangling_pointer(heap_pointer - offset, name.clone())?;
+                            }
+                        }
+
+                        for (pointer, new_name) in
+                            [(&mut lhs_pointer, &rhs), (&mut rhs_pointer, &lhs)]
+                        {
+                            if let Symbol::Pointer {
+                                name,
+                                heap_pointer,
+                                allocation_type,
+                                offset,
+                                control_block,
+                                ..
+                            } = pointer
+                            {
+                                if let Some(control_block) = control_block {
+                                    memory.update_owner(*control_block, new_name.clone())?;
+                                }
+
+                                match (allocation_type, heap_pointer) {
+                                    (AllocationType::Heap, Some(heap_pointer)) => memory
+                                        .update_owner(*heap_pointer - *offset, new_name.clone())?,
+                                    (AllocationType::Dangling, Some(heap_pointer)) => memory
+                                        .insert_dangling_pointer(
+                                            *heap_pointer - *offset,
+                                            new_name.clone(),
+                                        )?,
+                                    _ => {}
+                                }
+
+                                *name = new_name.clone();
+                            }
+                        }
+
+                        stack_symbols.insert(lhs, rhs_pointer);
+                        stack_symbols.insert(rhs, lhs_pointer);
+                    }
+
+                    _ => {
+                        return Err(AnalyzerError(
+                            format!("Cannot swap variable and pointer `{}` and `{}`", lhs, rhs),
+                            line,
+                            rhs_column,
+                        ));
+                    }
+                }
+            }
+
+            Statement::UniquePointerDeclaration {
+                base_type,
+                pointer_name,
+                value,
+                line,
+                pointer_ident_column,
+                value_column,
+            } => {
+                if stack_symbols.contains_key(&pointer_name) {
+                    return Err(AnalyzerError(
+                        format!("Pointer `{}` already declared!", &pointer_name),
+                        line,
+                        pointer_ident_column,
+                    ));
+                }
+
+                let ptype = Type::from_token(base_type)?;
+
+                stack_symbols.insert(
+                    pointer_name.clone(),
+                    Symbol::Pointer {
+                        ptype: ptype.clone(),
+                        name: pointer_name.clone(),
+                        value: None,
+                        heap_pointer: None,
+                        allocation_type: AllocationType::Null,
+                        pointer_size: memory.pointer_size(),
+                        value_size: ptype.get_variable_size(memory.pointer_size()),
+                        indirection: 1,
+                        unique: true,
+                        shared: false,
+                        control_block: None,
+                        offset: 0,
+                    },
+                );
+
+                // the pointer starts out null and is then initialized like it is assigned to
+                self.analyze_statement(
+                    Statement::UniquePointerAssignment {
+                        pointer_name,
+                        value,
+                        line,
+                        pointer_ident_column,
+                        value_column,
+                    },
+                    stack_symbols,
+                    memory,
+                    starting_pointers,
+                    callers,
+                )?;
+            }
 
-                                                if let Some(old_symbol) = old_symbol {
-                                                    if let Symbol::Variable { name, .. } =
-                                                        *old_symbol
-                                                    {
-                                                        if let Some(symbol) =
-                                                            stack_symbols.get_mut(&name)
-                                                        {
-                                                            if let Symbol::Variable {
-                                                                value, ..
-                                                            } = symbol
-                                                            {
-                                                                *value = Some(new_value);
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            } else {
-                                                return Err(AnalyzerError(
-                                                    format!(
-                                                        "Cannot assign `{}` to pointer `{}` (incorrect type)",
-                                                        value, pointer_name
-                                                    ),
-                                                    line,
-                                                    new_value_column,
-                                                ));
-                                            }
-                                        } else {
-                                            return Err(AnalyzerError(
-                                                format!(
-                                                    "Variable `{}` not initialized!",
-                                                    new_ident
-                                                ),
-                                                line,
-                                                new_value_column,
-                                            ));
-                                        }
-                                    } else {
-                                        return Err(AnalyzerError(
-                                            format!("Can only assign variables to pointers!",),
-                                            line,
-                                            new_value_column,
-                                        ));
-                                    }
-                                } else {
-                                    return Err(AnalyzerError(
-                                        format!("Variable `{}` not found!", new_ident),
-                                        line,
-                                        new_value_column,
-                                    ));
-                                }
+            Statement::UniquePointerAssignment {
+                pointer_name,
+                value,
+                line,
+                pointer_ident_column,
+                value_column,
+            } => {
+                let ptype = match stack_symbols.get(&pointer_name) {
+                    Some(Symbol::Pointer {
+                        ptype,
+                        unique: true,
+                        ..
+                    }) => ptype.clone(),
+
+                    Some(_) => {
+                        return Err(AnalyzerError(
+                            format!("`{}` is not a `std::unique_ptr`", pointer_name),
+                            line,
+                            pointer_ident_column,
+                        ));
+                    }
+
+                    None => {
+                        return Err(AnalyzerError(
+                            format!("Pointer `{}` not found!", pointer_name),
+                            line,
+                            pointer_ident_column,
+                        ));
+                    }
+                };
+
+                let (new_value, new_heap_pointer, new_allocation_type) = match value {
+                    ast::UniqueValue::MakeUnique { make_type, value } => {
+                        if !ptype.is_type(make_type) {
+                            return Err(AnalyzerError(
+                                format!(
+                                    "Cannot assign `std::make_unique<{}>` to `{}` (incorrect type)",
+                                    make_type, pointer_name
+                                ),
+                                line,
+                                value_column,
+                            ));
+                        }
+
+                        // without an argument the value is zeroed
+                        let initial_value = match value {
+                            Some(value) => validate_variable_assignment(
+                                value,
+                                &pointer_name,
+                                &ptype,
+                                stack_symbols,
+                                line,
+                                value_column,
+                            )?
+                            .unwrap_or_default(),
+                            None => ptype.get_zero_value(),
+                        };
+
+                        let heap_pointer = memory
+                            .allocate(
+                                &pointer_name,
+                                ptype.get_variable_size(memory.pointer_size()),
+                                starting_pointers,
+                            )
+                            .map_err(|e| AnalyzerError(e.to_string(), line, value_column))?;
+
+                        memory.update_metadata(heap_pointer, initial_value.clone())?;
+
+                        (
+                            Some(Box::new(Symbol::Literal {
+                                value: initial_value,
+                            })),
+                            Some(heap_pointer),
+                            AllocationType::Heap,
+                        )
+                    }
+
+                    ast::UniqueValue::Move { source } => {
+                        if source == pointer_name {
+                            return Err(AnalyzerError(
+                                format!("Cannot move `{}` into itself", source),
+                                line,
+                                value_column,
+                            ));
+                        }
+
+                        let Some(Symbol::Pointer {
+                            ptype: source_type,
+                            value,
+                            heap_pointer,
+                            allocation_type,
+                            unique,
+                            ..
+                        }) = stack_symbols.get_mut(&source)
+                        else {
+                            return Err(AnalyzerError(
+                                format!("Pointer `{}` not found!", source),
+                                line,
+                                value_column,
+                            ));
+                        };
+
+                        if !*unique {
+                            return Err(AnalyzerError(
+                                format!(
+                                    "Cannot move from `{}`, it isn't a `std::unique_ptr`",
+                                    source
+                                ),
+                                line,
+                                value_column,
+                            ));
+                        }
+
+                        if *source_type != ptype {
+                            return Err(AnalyzerError(
+                                format!(
+                                    "Cannot move `{}` into `{}` (incorrect type)",
+                                    source, pointer_name
+                                ),
+                                line,
+                                value_column,
+                            ));
+                        }
+
+                        // the block changes hands and the source is left null
+                        let moved = (
+                            value.take(),
+                            heap_pointer.take(),
+                            std::mem::replace(allocation_type, AllocationType::Null),
+                        );
+
+                        if let Some(heap_pointer) = moved.1 {
+                            memory.update_owner(heap_pointer, pointer_name.clone())?;
+                        }
+
+                        moved
+                    }
+
+                    ast::UniqueValue::Copy { source } => {
+                        return Err(unique_copy_error(&source, line, value_column));
+                    }
+
+                    ast::UniqueValue::Null => (None, None, AllocationType::Null),
+                };
+
+                if let Some(Symbol::Pointer {
+                    value,
+                    heap_pointer,
+                    allocation_type,
+                    value_size,
+                    ..
+                }) = stack_symbols.get_mut(&pointer_name)
+                {
+                    // the block the pointer owned before is freed once it has its new value
+                    if let Some(old_heap_pointer) = heap_pointer {
+                        memory.free(*old_heap_pointer, *value_size);
+                    }
+
+                    *value = new_value;
+                    *heap_pointer = new_heap_pointer;
+                    *allocation_type = new_allocation_type;
+                    *value_size = ptype.get_variable_size(memory.pointer_size());
+                }
+            }
+
+            Statement::SharedPointerDeclaration {
+                base_type,
+                pointer_name,
+                value,
+                line,
+                pointer_ident_column,
+                value_column,
+            } => {
+                if stack_symbols.contains_key(&pointer_name) {
+                    return Err(AnalyzerError(
+                        format!("Pointer `{}` already declared!", &pointer_name),
+                        line,
+                        pointer_ident_column,
+                    ));
+                }
+
+                let ptype = Type::from_token(base_type)?;
+
+                stack_symbols.insert(
+                    pointer_name.clone(),
+                    Symbol::Pointer {
+                        ptype: ptype.clone(),
+                        name: pointer_name.clone(),
+                        value: None,
+                        heap_pointer: None,
+                        allocation_type: AllocationType::Null,
+                        // one pointer to the block and another to its control block
+                        pointer_size: 2 * memory.pointer_size(),
+                        value_size: ptype.get_variable_size(memory.pointer_size()),
+                        indirection: 1,
+                        unique: false,
+                        shared: true,
+                        control_block: None,
+                        offset: 0,
+                    },
+                );
+
+                // the pointer starts out null and is then initialized like it is assigned to
+                self.analyze_statement(
+                    Statement::SharedPointerAssignment {
+                        pointer_name,
+                        value,
+                        line,
+                        pointer_ident_column,
+                        value_column,
+                    },
+                    stack_symbols,
+                    memory,
+                    starting_pointers,
+                    callers,
+                )?;
+            }
+
+            Statement::SharedPointerAssignment {
+                pointer_name,
+                value,
+                line,
+                pointer_ident_column,
+                value_column,
+            } => {
+                let ptype = match stack_symbols.get(&pointer_name) {
+                    Some(Symbol::Pointer {
+                        ptype,
+                        shared: true,
+                        ..
+                    }) => ptype.clone(),
+
+                    Some(_) => {
+                        return Err(AnalyzerError(
+                            format!("`{}` is not a `std::shared_ptr`", pointer_name),
+                            line,
+                            pointer_ident_column,
+                        ));
+                    }
+
+                    None => {
+                        return Err(AnalyzerError(
+                            format!("Pointer `{}` not found!", pointer_name),
+                            line,
+                            pointer_ident_column,
+                        ));
+                    }
+                };
+
+                let (new_value, new_heap_pointer, new_control_block, new_allocation_type) =
+                    match value {
+                        ast::SharedValue::MakeShared { make_type, value } => {
+                            if !ptype.is_type(make_type) {
+                                return Err(AnalyzerError(
+                                    format!(
+                                        "Cannot assign `std::make_shared<{}>` to `{}` (incorrect \
+                                         type)",
+                                        make_type, pointer_name
+                                    ),
+                                    line,
+                                    value_column,
+                                ));
                             }
-                            ast::Expr::Literal(lit) => {
-                                if ptype.is_correct_literal(&lit) {
-                                    let old_symbol = (*pointer_value).clone();
 
-                                    if allocation_type == AllocationType::Heap {
-                                        *pointer_value = Some(Box::new(Symbol::Literal {
-                                            value: lit.to_string(),
-                                        }));
+                            // without an argument the value is zeroed
+                            let initial_value = match value {
+                                Some(value) => validate_variable_assignment(
+                                    value,
+                                    &pointer_name,
+                                    &ptype,
+                                    stack_symbols,
+                                    line,
+                                    value_column,
+                                )?
+                                .unwrap_or_default(),
+                                None => ptype.get_zero_value(),
+                            };
 
-                                        if let Some(heap_pointer) = heap_pointer {
-                                            allocator
-                                                .update_metadata(*heap_pointer, lit.to_string())?;
-                                        } else {
-                                            return Err(AnalyzerError(
-                                                format!(
-                                                    "Heap pointer not found for `{}`",
-                                                    pointer_name
-                                                ),
-                                                line,
-                                                pointer_ident_column,
-                                            ));
-                                        }
+                            let heap_pointer = memory
+                                .allocate(
+                                    &pointer_name,
+                                    ptype.get_variable_size(memory.pointer_size()),
+                                    starting_pointers,
+                                )
+                                .map_err(|e| AnalyzerError(e.to_string(), line, value_column))?;
+
+                            memory.update_metadata(heap_pointer, initial_value.clone())?;
+
+                            let control_block =
+                                allocate_control_block(memory, &pointer_name, starting_pointers)
+                                    .map_err(|e| {
+                                        AnalyzerError(e.to_string(), line, value_column)
+                                    })?;
+
+                            (
+                                Some(Box::new(Symbol::Literal {
+                                    value: initial_value,
+                                })),
+                                Some(heap_pointer),
+                                Some(control_block),
+                                AllocationType::Heap,
+                            )
+                        }
+
+                        ast::SharedValue::Copy { source } => {
+                            let Some(Symbol::Pointer {
+                                ptype: source_type,
+                                value,
+                                heap_pointer,
+                                allocation_type,
+                                shared,
+                                control_block,
+                                ..
+                            }) = stack_symbols.get(&source)
+                            else {
+                                return Err(AnalyzerError(
+                                    format!("Pointer `{}` not found!", source),
+                                    line,
+                                    value_column,
+                                ));
+                            };
+
+                            if !*shared {
+                                return Err(AnalyzerError(
+                                    format!(
+                                        "Cannot copy `{}` into `{}`, it isn't a `std::shared_ptr`",
+                                        source, pointer_name
+                                    ),
+                                    line,
+                                    value_column,
+                                ));
+                            }
+
+                            if *source_type != ptype {
+                                return Err(AnalyzerError(
+                                    format!(
+                                        "Cannot assign `{}` to `{}` (incorrect type)",
+                                        source, pointer_name
+                                    ),
+                                    line,
+                                    value_column,
+                                ));
+                            }
+
+                            // assigning a pointer to itself leaves it as it was
+                            if source == pointer_name {
+                                return Ok(());
+                            }
+
+                            if let Some(control_block) = control_block {
+                                retain(memory, *control_block)?;
+                            }
+
+                            (value.clone(), *heap_pointer, *control_block, allocation_type.clone())
+                        }
+
+                        ast::SharedValue::Move { source } => {
+                            if source == pointer_name {
+                                return Err(AnalyzerError(
+                                    format!("Cannot move `{}` into itself", source),
+                                    line,
+                                    value_column,
+                                ));
+                            }
+
+                            let Some(Symbol::Pointer {
+                                ptype: source_type,
+                                value,
+                                heap_pointer,
+                                allocation_type,
+                                shared,
+                                control_block,
+                                ..
+                            }) = stack_symbols.get_mut(&source)
+                            else {
+                                return Err(AnalyzerError(
+                                    format!("Pointer `{}` not found!", source),
+                                    line,
+                                    value_column,
+                                ));
+                            };
+
+                            if !*shared {
+                                return Err(AnalyzerError(
+                                    format!(
+                                        "Cannot move from `{}`, it isn't a `std::shared_ptr`",
+                                        source
+                                    ),
+                                    line,
+                                    value_column,
+                                ));
+                            }
+
+                            if *source_type != ptype {
+                                return Err(AnalyzerError(
+                                    format!(
+                                        "Cannot move `{}` into `{}` (incorrect type)",
+                                        source, pointer_name
+                                    ),
+                                    line,
+                                    value_column,
+                                ));
+                            }
+
+                            // the ownership changes hands without changing the count, and the
+                            // source is left null
+                            let moved = (
+                                value.take(),
+                                heap_pointer.take(),
+                                control_block.take(),
+                                std::mem::replace(allocation_type, AllocationType::Null),
+                            );
+
+                            if let (Some(heap_pointer), Some(control_block)) = (moved.1, moved.2) {
+                                memory.update_owner(heap_pointer, pointer_name.clone())?;
+                                memory.update_owner(control_block, pointer_name.clone())?;
+                            }
+
+                            moved
+                        }
+
+                        ast::SharedValue::Null => (None, None, None, AllocationType::Null),
+                    };
+
+                // the block the pointer owned before loses an owner once it has its new value
+                if let Some(Symbol::Pointer {
+                    heap_pointer: Some(heap_pointer),
+                    control_block: Some(control_block),
+                    value_size,
+                    ..
+                }) = stack_symbols.get(&pointer_name)
+                {
+                    release_shared(
+                        memory,
+                        &pointer_name,
+                        *heap_pointer,
+                        *value_size,
+                        *control_block,
+                        stack_symbols,
+                    )?;
+                }
+
+                if let Some(Symbol::Pointer {
+                    value,
+                    heap_pointer,
+                    allocation_type,
+                    value_size,
+                    control_block,
+                    ..
+                }) = stack_symbols.get_mut(&pointer_name)
+                {
+                    *value = new_value;
+                    *heap_pointer = new_heap_pointer;
+                    *control_block = new_control_block;
+                    *allocation_type = new_allocation_type;
+                    *value_size = ptype.get_variable_size(memory.pointer_size());
+                }
+            }
+
+            Statement::Poke {
+                address,
+                value,
+                line,
+                address_column,
+                value_column,
+            } => {
+                let address = raw_memory::address(&address, stack_symbols, line, address_column)?;
+
+                match raw_memory::resolve_address(
+                    address,
+                    stack_symbols,
+                    callers,
+                    memory,
+                    line,
+                    address_column,
+                )? {
+                    // the frames of the callers are copies while the call runs, a write to them would
+                    // be lost
+                    Location::Variable {
+                        name,
+                        caller: Some(_),
+                    } => {
+                        return Err(AnalyzerError(
+                            format!(
+                                "Address {} holds `{}` of a caller, only the current frame can be poked",
+                                format_address(address as usize, memory.pointer_size()),
+                                name
+                            ),
+                            line,
+                            address_column,
+                        ));
+                    }
+
+                    // poking a variable's address assigns to it
+                    Location::Variable { name, caller: None } => self.analyze_statement(
+                        Statement::VariableAssignment {
+                            var_name: name,
+                            new_value: value,
+                            line,
+                            var_ident_column: address_column,
+                            assignment_column: value_column,
+                        },
+                        stack_symbols,
+                        memory,
+                        starting_pointers,
+                        callers,
+                    )?,
 
-                                        return Ok(()); // continue;
-                                    }
+                    Location::Block { pointer, owner } => {
+                        let new_value = match &owner {
+                            Some((owner, ptype)) => validate_variable_assignment(
+                                value,
+                                owner,
+                                ptype,
+                                stack_symbols,
+                                line,
+                                value_column,
+                            )?
+                            .unwrap_or_default(),
 
-                                    if let Some(old_symbol) = old_symbol {
-                                        if let Symbol::Variable { name, .. } = *old_symbol {
-                                            if let Some(symbol) = stack_symbols.get_mut(&name) {
-                                                if let Symbol::Variable { value, .. } = symbol {
-                                                    *value = Some(lit.to_string());
-                                                }
-                                            }
-                                        }
+                            // nothing says what type a block without a pointer to it holds
+                            None => {
+                                match fold_expression(*value, stack_symbols, line, value_column)? {
+                                    ast::Expr::Literal(lit) => lit.to_string(),
+                                    value => {
+                                        return Err(AnalyzerError(
+                                            format!("Cannot poke `{}`, it isn't a value", value),
+                                            line,
+                                            value_column,
+                                        ));
                                     }
-                                } else {
-                                    return Err(AnalyzerError(
-                                        format!(
-                                            "Cannot assign `{}` to pointer `{}` (incorrect type)",
-                                            lit, pointer_name
-                                        ),
-                                        line,
-                                        new_value_column,
-                                    ));
                                 }
                             }
-                            _ => {}
                         };
+
+                        memory.update_metadata(pointer, new_value.clone())?;
+
+                        if let Some(Symbol::Pointer { value, .. }) =
+                            owner.and_then(|(owner, _)| stack_symbols.get_mut(&owner))
+                        {
+                            *value = Some(Box::new(Symbol::Literal { value: new_value }));
+                        }
                     }
-                } else {
+                }
+            }
+
+            Statement::Memset {
+                target,
+                value,
+                size,
+                line,
+                target_column,
+                value_column,
+                size_column,
+            } => memset(
+                &target,
+                &value,
+                &size,
+                stack_symbols,
+                memory,
+                line,
+                target_column,
+                value_column,
+                size_column,
+            )?,
+
+            Statement::Memcpy {
+                destination,
+                source,
+                size,
+                line,
+                destination_column,
+                source_column,
+                size_column,
+            } => memcpy(
+                &destination,
+                &source,
+                &size,
+                stack_symbols,
+                memory,
+                line,
+                destination_column,
+                source_column,
+                size_column,
+            )?,
+
+            Statement::Strcpy {
+                destination,
+                source,
+                line,
+                destination_column,
+                source_column,
+            } => strcpy(
+                &destination,
+                &source,
+                stack_symbols,
+                memory,
+                line,
+                destination_column,
+                source_column,
+            )?,
+
+            Statement::MemberAssignment {
+                var_name,
+                member,
+                new_value,
+                line,
+                var_ident_column,
+                member_column,
+            } => {
+                let cloned_symbols = stack_symbols.clone();
+                let pointer_size = memory.pointer_size();
+                let assigned = new_value.to_string();
+
+                let Some(symbol) = stack_symbols.get_mut(&var_name) else {
                     return Err(AnalyzerError(
-                        format!("Pointer `{}` not found!", pointer_name),
+                        format!("Variable `{}` not found!", var_name),
                         line,
-                        pointer_ident_column,
+                        var_ident_column,
+                    ));
+                };
+
+                if let Symbol::Object {
+                    class_name, fields, ..
+                } = symbol
+                {
+                    let index = public_field(fields, class_name, &member, line, member_column)?;
+                    let field = &mut fields[index];
+                    let field_name = format!("{}.{}", var_name, member);
+
+                    let new_value = validate_variable_assignment(
+                        new_value,
+                        &field_name,
+                        &field.ftype,
+                        &cloned_symbols,
+                        line,
+                        member_column,
+                    )?;
+
+                    check_integer_range(
+                        &field.ftype,
+                        new_value.as_deref(),
+                        &field_name,
+                        pointer_size,
+                        line,
+                        member_column,
+                    )?;
+
+                    field.value = new_value;
+
+                    return Ok(());
+                }
+
+                let Symbol::Union {
+                    members,
+                    active,
+                    bytes,
+                    ..
+                } = symbol
+                else {
+                    return Err(AnalyzerError(
+                        format!(
+                            "Cannot assign to member `{}` of `{}`, it isn't a union or an object",
+                            member, var_name
+                        ),
+                        line,
+                        var_ident_column,
+                    ));
+                };
+
+                let Some(index) = members.iter().position(|other| other.name == member) else {
+                    return Err(AnalyzerError(
+                        format!("Member `{}` not found in union `{}`!", member, var_name),
+                        line,
+                        member_column,
+                    ));
+                };
+
+                let member_name = format!("{}.{}", var_name, member);
+                let mtype = members[index].mtype.clone();
+
+                let new_value = validate_variable_assignment(
+                    new_value,
+                    &member_name,
+                    &mtype,
+                    &cloned_symbols,
+                    line,
+                    member_column,
+                )?;
+
+                check_integer_range(
+                    &mtype,
+                    new_value.as_deref(),
+                    &member_name,
+                    pointer_size,
+                    line,
+                    member_column,
+                )?;
+
+                if new_value
+                    .and_then(|new_value| {
+                        write_member(members, bytes, index, &new_value, pointer_size)
+                    })
+                    .is_none()
+                {
+                    return Err(AnalyzerError(
+                        format!(
+                            "Cannot assign `{}` to member `{}` (incorrect type)",
+                            assigned, member_name
+                        ),
+                        line,
+                        member_column,
                     ));
                 }
+
+                *active = Some(member);
             }
 
-            Statement::Delete {
+            Statement::PointerMemberAssignment {
                 pointer_name,
+                member,
+                new_value,
                 line,
                 pointer_ident_column,
+                member_column,
             } => {
-                if let Some(symbol) = stack_symbols.get_mut(&pointer_name) {
-                    if let Symbol::Pointer {
-                        heap_pointer,
-                        value_size,
-                        allocation_type,
-                        ..
-                    } = symbol
-                    {
-                        if *allocation_type == AllocationType::Stack {
-                            return Err(AnalyzerError(
-                                format!("Cannot delete stack pointer `{}`", pointer_name),
-                                line,
-                                pointer_ident_column,
-                            ));
-                        }
+                let cloned_symbols = stack_symbols.clone();
+                let pointer_size = memory.pointer_size();
 
-                        if *allocation_type == AllocationType::Null {
-                            return Err(AnalyzerError(
-                                format!("Cannot delete null pointer `{}`", pointer_name),
+                // a field a parameter hides is written to the copy `this` points at, the others were
+                // rewritten into assignments to the fields in the frame
+                let this = match stack_symbols.get_mut(&pointer_name) {
+                    Some(Symbol::Pointer {
+                        value: Some(this), ..
+                    }) if pointer_name == "this" => Some(this.as_mut()),
+                    _ => None,
+                };
+
+                if let Some(Symbol::Object {
+                    class_name, fields, ..
+                }) = this
+                {
+                    let Some(field) = fields.iter_mut().find(|field| field.name == member) else {
+                        return Err(AnalyzerError(
+                            format!("Field `{}` not found in class `{}`!", member, class_name),
+                            line,
+                            member_column,
+                        ));
+                    };
+
+                    if field.pointer.is_some() {
+                        return Err(AnalyzerError(
+                            format!(
+                                "Cannot assign to pointer field `this->{}` while a parameter hides it",
+                                member
+                            ),
+                            line,
+                            member_column,
+                        ));
+                    }
+
+                    let field_name = format!("this->{}", member);
+                    let new_value = validate_variable_assignment(
+                        new_value,
+                        &field_name,
+                        &field.ftype,
+                        &cloned_symbols,
+                        line,
+                        member_column,
+                    )?;
+
+                    check_integer_range(
+                        &field.ftype,
+                        new_value.as_deref(),
+                        &field_name,
+                        pointer_size,
+                        line,
+                        member_column,
+                    )?;
+
+                    field.value = new_value;
+
+                    return Ok(());
+                }
+
+                let Some(symbol) = stack_symbols.get_mut(&pointer_name) else {
+                    return Err(AnalyzerError(
+                        format!("Pointer `{}` not found!", pointer_name),
+                        line,
+                        pointer_ident_column,
+                    ));
+                };
+
+                let Symbol::Pointer {
+                    value,
+                    heap_pointer,
+                    allocation_type,
+                    ..
+                } = symbol
+                else {
+                    return Err(AnalyzerError(
+                        format!("Cannot use `->` on `{}`, it isn't a pointer", pointer_name),
+                        line,
+                        pointer_ident_column,
+                    ));
+                };
+
+                match allocation_type {
+                    AllocationType::Null => {
+                        return Err(AnalyzerError(
+                            format!("Cannot dereference null pointer `{}`", pointer_name),
+                            line,
+                            pointer_ident_column,
+                        ));
+                    }
+                    AllocationType::Dangling => {
+                        return Err(AnalyzerError(
+                            format!("Cannot write through dangling pointer `{}`", pointer_name),
+                            line,
+                            pointer_ident_column,
+                        ));
+                    }
+                    _ => {}
+                }
+
+                let (
+                    Some(block),
+                    Some(Symbol::Object {
+                        class_name, fields, ..
+                    }),
+                ) = (*heap_pointer, value.as_deref_mut())
+                else {
+                    return Err(AnalyzerError(
+                        format!(
+                            "Cannot assign to member `{}` through `{}`, it doesn't point to an object",
+                            member, pointer_name
+                        ),
+                        line,
+                        member_column,
+                    ));
+                };
+
+                let index = public_field(fields, class_name, &member, line, member_column)?;
+                let field = &mut fields[index];
+                let field_name = format!("{}->{}", pointer_name, member);
+
+                let new_value = validate_variable_assignment(
+                    new_value,
+                    &field_name,
+                    &field.ftype,
+                    &cloned_symbols,
+                    line,
+                    member_column,
+                )?;
+
+                check_integer_range(
+                    &field.ftype,
+                    new_value.as_deref(),
+                    &field_name,
+                    pointer_size,
+                    line,
+                    member_column,
+                )?;
+
+                field.value = new_value;
+
+                // the write lands at the field's offset in the block, and every pointer to the block
+                // sees the object it now holds
+                let object = value.clone();
+
+                if let Some(object) = &object {
+                    memory.update_members(block, block_members(object, pointer_size))?;
+                }
+
+                for symbol in stack_symbols.values_mut() {
+                    match symbol {
+                        Symbol::Pointer {
+                            heap_pointer: Some(other),
+                            value: value @ Some(_),
+                            ..
+                        } if *other == block => *value = object.clone(),
+                        _ => {}
+                    }
+                }
+            }
+
+            ast::Statement::ArrayDeclaration {
+                var_type,
+                var_name,
+                dimensions,
+                values,
+                line,
+                var_ident_column,
+            } => {
+                if stack_symbols.contains_key(&var_name) {
+                    return Err(AnalyzerError(
+                        format!("Variable `{}` already declared!", var_name),
+                        line,
+                        var_ident_column,
+                    ));
+                }
+
+                let vtype = Type::from_token(var_type)?;
+                let len = dimensions.iter().product();
+
+                // the elements a brace initializer leaves out start at zero, an array declared without
+                // one holds garbage until its elements are assigned
+                let elements = match values {
+                    Some(values) => values
+                        .into_iter()
+                        .enumerate()
+                        .map(|(index, value)| {
+                            let Some(value) = value else {
+                                return Ok(Some(vtype.get_zero_value()));
+                            };
+
+                            let element = element_name(&var_name, &dimensions, index);
+                            let value = validate_variable_assignment(
+                                Box::new(value),
+                                &element,
+                                &vtype,
+                                stack_symbols,
                                 line,
-                                pointer_ident_column,
-                            ));
-                        }
+                                var_ident_column,
+                            )?;
 
-                        if *allocation_type == AllocationType::Dangling {
-                            return Err(AnalyzerError(
-                                format!("Cannot delete dangling pointer `{}`", pointer_name),
+                            check_integer_range(
+                                &vtype,
+                                value.as_deref(),
+                                &element,
+                                memory.pointer_size(),
                                 line,
-                                pointer_ident_column,
-                            ));
-                        }
+                                var_ident_column,
+                            )?;
 
-                        *allocation_type = AllocationType::Dangling;
+                            Ok(value)
+                        })
+                        .collect::<Result<Vec<_>>>()?,
+                    None => vec![None; len],
+                };
 
-                        if let Some(heap_pointer) = heap_pointer {
-                            allocator.free(*heap_pointer, *value_size);
-                            allocator.insert_dangling_pointer(*heap_pointer, pointer_name)?;
-                        }
+                stack_symbols.insert(
+                    var_name.clone(),
+                    Symbol::Array {
+                        size: array_size(&vtype, &dimensions, memory.pointer_size()),
+                        vtype,
+                        name: var_name,
+                        dimensions,
+                        elements,
+                    },
+                );
+            }
+
+            Statement::ArrayAssignment {
+                var_name,
+                indices,
+                new_value,
+                line,
+                var_ident_column,
+                index_column,
+            } => {
+                let (vtype, dimensions) = match stack_symbols.get(&var_name) {
+                    Some(Symbol::Array {
+                        vtype, dimensions, ..
+                    }) => (vtype.clone(), dimensions.clone()),
+                    Some(Symbol::Pointer { .. }) => {
+                        return assign_heap_element(
+                            &var_name,
+                            &indices,
+                            new_value,
+                            stack_symbols,
+                            memory,
+                            line,
+                            index_column,
+                        );
                     }
-                } else {
+                    Some(_) => {
+                        return Err(AnalyzerError(
+                            format!("Cannot index `{}`, it isn't an array", var_name),
+                            line,
+                            var_ident_column,
+                        ));
+                    }
+                    None => {
+                        return Err(AnalyzerError(
+                            format!("Array `{}` not found!", var_name),
+                            line,
+                            var_ident_column,
+                        ));
+                    }
+                };
+
+                let index = match element_index(
+                    &var_name,
+                    &dimensions,
+                    &indices,
+                    stack_symbols,
+                    line,
+                    index_column,
+                )? {
+                    Bounds::Within(index) => index,
+                    Bounds::Outside(reason) => {
+                        return Err(AnalyzerError(reason, line, index_column));
+                    }
+                };
+                let element = element_name(&var_name, &dimensions, index);
+
+                let new_value = validate_variable_assignment(
+                    new_value,
+                    &element,
+                    &vtype,
+                    stack_symbols,
+                    line,
+                    index_column,
+                )?;
+
+                check_integer_range(
+                    &vtype,
+                    new_value.as_deref(),
+                    &element,
+                    memory.pointer_size(),
+                    line,
+                    index_column,
+                )?;
+
+                if let Some(Symbol::Array { elements, .. }) = stack_symbols.get_mut(&var_name) {
+                    elements[index] = new_value;
+                }
+            }
+
+            ast::Statement::MutexDeclaration {
+                mutex_name,
+                line,
+                mutex_ident_column,
+            } => {
+                if stack_symbols.contains_key(&mutex_name) {
                     return Err(AnalyzerError(
-                        format!("Pointer `{}` not found!", pointer_name),
+                        format!("Variable `{}` already declared!", mutex_name),
                         line,
-                        pointer_ident_column,
+                        mutex_ident_column,
                     ));
                 }
+
+                stack_symbols.insert(
+                    mutex_name.clone(),
+                    Symbol::Mutex {
+                        name: mutex_name,
+                        owner: None,
+                        size: mutex_size(memory.pointer_size()),
+                    },
+                );
+            }
+
+            Statement::FunctionDefinition { .. }
+            | Statement::FunctionCall { .. }
+            | Statement::Return { .. }
+            | Statement::Block { .. }
+            | Statement::If { .. }
+            | Statement::While { .. }
+            | Statement::UnionDefinition { .. }
+            | Statement::UnionDeclaration { .. }
+            | Statement::ClassDefinition { .. }
+            | Statement::MethodCall { .. }
+            | Statement::ObjectAllocation { .. }
+            | Statement::Lock { .. }
+            | Statement::Unlock { .. }
+            | Statement::ThreadSpawn { .. }
+            | Statement::Ignored { .. } => {
+                unreachable!("function statements and blocks are handled by `analyze_block`")
             }
         }
 
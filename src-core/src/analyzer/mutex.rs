@@ -0,0 +1,129 @@
+//! Mutexes, which threads lock to take turns using the memory they share, and the heap blocks the threads
+//! use without one
+
+use indexmap::IndexMap;
+
+use crate::{
+    error::{Error::AnalyzerError, Result},
+    parser::ast::Statement,
+};
+
+use super::Symbol;
+
+/// Gets the size of a `std::mutex`, which wraps a `pthread_mutex_t` like it does with glibc
+///
+/// # Arguments
+/// - `pointer_size`: The size of a pointer in bytes
+///
+/// # Returns
+/// - `usize`: The size of the mutex in bytes
+pub(crate) fn mutex_size(pointer_size: usize) -> usize {
+    match pointer_size {
+        8 => 40,
+        _ => 24,
+    }
+}
+
+/// Gets the thread that owns a mutex, to lock or unlock it
+///
+/// # Arguments
+/// - `symbols`: A mutable reference to the symbol table
+/// - `mutex_name`: The name of the mutex
+/// - `line`: The line of the statement
+/// - `column`: The column errors point at
+///
+/// # Returns
+/// - `Result<&mut Option<usize>>`: A result containing either:
+///   - `&mut Option<usize>`: The owner of the mutex, `None` while it is unlocked
+///   - [AnalyzerError](crate::error::Error::AnalyzerError): returns an error if there is no such mutex
+pub(crate) fn mutex_owner<'a>(
+    symbols: &'a mut IndexMap<String, Symbol>,
+    mutex_name: &str,
+    line: usize,
+    column: usize,
+) -> Result<&'a mut Option<usize>> {
+    match symbols.get_mut(mutex_name) {
+        Some(Symbol::Mutex { owner, .. }) => Ok(owner),
+        Some(_) => Err(AnalyzerError(
+            format!("Cannot lock or unlock `{}`, it isn't a mutex", mutex_name),
+            line,
+            column,
+        )),
+        None => Err(AnalyzerError(format!("Mutex `{}` not found!", mutex_name), line, column)),
+    }
+}
+
+/// Gets the mutexes a thread holds
+///
+/// # Arguments
+/// - `symbols`: A reference to the symbol table
+/// - `thread`: The thread, `0` for the main thread
+///
+/// # Returns
+/// - `Vec<String>`: The names of the mutexes the thread locked and hasn't unlocked yet
+pub(crate) fn held_locks(symbols: &IndexMap<String, Symbol>, thread: usize) -> Vec<String> {
+    symbols
+        .values()
+        .filter_map(|symbol| match symbol {
+            Symbol::Mutex {
+                name,
+                owner: Some(owner),
+                ..
+            } if *owner == thread => Some(name.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Gets the thread holding the mutex a statement locks, which the statement has to wait for
+///
+/// # Arguments
+/// - `statement`: The statement
+/// - `symbols`: A reference to the symbol table
+///
+/// # Returns
+/// - `Option<usize>`: The thread holding the mutex, `None` if the statement doesn't lock one or nobody
+///   holds it
+pub(crate) fn lock_owner(
+    statement: &Statement,
+    symbols: &IndexMap<String, Symbol>,
+) -> Option<usize> {
+    let Statement::Lock { mutex_name, .. } = statement else {
+        return None;
+    };
+
+    match symbols.get(mutex_name) {
+        Some(Symbol::Mutex { owner, .. }) => *owner,
+        _ => None,
+    }
+}
+
+/// Gets the heap blocks a statement writes through a pointer, like `*p = 3;`, `p->x = 3;` or `delete p;`
+///
+/// # Arguments
+/// - `statement`: The statement
+/// - `symbols`: A reference to the symbol table
+///
+/// # Returns
+/// - `Vec<usize>`: The starting position of each block the statement writes
+pub(crate) fn heap_accesses(
+    statement: &Statement,
+    symbols: &IndexMap<String, Symbol>,
+) -> Vec<usize> {
+    let pointer_name = match statement {
+        Statement::Deref { pointer_name, .. }
+        | Statement::PointerMemberAssignment { pointer_name, .. }
+        | Statement::Delete { pointer_name, .. }
+        | Statement::PointerReallocation { pointer_name, .. } => pointer_name,
+        _ => return Vec::new(),
+    };
+
+    match symbols.get(pointer_name) {
+        Some(Symbol::Pointer {
+            heap_pointer: Some(heap_pointer),
+            offset,
+            ..
+        }) => vec![heap_pointer - offset],
+        _ => Vec::new(),
+    }
+}
@@ -0,0 +1,140 @@
+//! Notes users attach to the symbols and heap blocks of a diagram they are studying, kept by ids that
+//! stay the same when the program is analyzed again so the notes follow what they were written about
+
+use serde::{Deserialize, Serialize};
+
+use super::helpers::symbol_name;
+use super::{AnalysisResult, Symbol};
+
+/// Represents what a note is attached to
+///
+/// - `Symbol`: The symbol with the given name, declared in the frame of the function `frame`, `None` for
+///   the top level, and in the block `scope` levels deep within it, `0` for the body of the function or
+///   the top level itself. The symbol of a function called several times is the one of its first call
+/// - `Block`: The heap block with the given id, one of the
+///   [heap_ids](crate::analyzer::CrossReference::heap_ids) like `make::p`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum NoteTarget {
+    Symbol {
+        name: String,
+        #[serde(default)]
+        frame: Option<String>,
+        #[serde(default)]
+        scope: usize,
+    },
+    Block {
+        id: String,
+    },
+}
+
+/// Represents a note attached to a symbol or a heap block
+///
+/// # Fields
+/// - `target`: What the note is attached to
+/// - `text`: The note, as the user wrote it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Note {
+    pub target: NoteTarget,
+    pub text: String,
+}
+
+/// Represents where the symbol or heap block a note is attached to is in the output of an analysis
+///
+/// - `Stack`: The symbol at `index` in the stack
+/// - `Frame`: The symbol at `index` in the frame at `frame` in the frames
+/// - `ScopeExit`: The symbol at `index` in the scope exit at `exit` in the scope exits
+/// - `Heap`: The block at `index` in the heap
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum NotePlace {
+    Stack { index: usize },
+    Frame { frame: usize, index: usize },
+    ScopeExit { exit: usize, index: usize },
+    Heap { index: usize },
+}
+
+/// Represents a note matched with the output of an analysis
+///
+/// # Fields
+/// - `note`: The note
+/// - `place`: Where the symbol or block the note is attached to is, `None` if the program no longer
+///   has it
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PlacedNote {
+    pub note: Note,
+    pub place: Option<NotePlace>,
+}
+
+/// Matches notes with the symbols and heap blocks of an analysis, so notes written about an earlier
+/// analysis of the program are shown next to the same symbols and blocks
+///
+/// Notes whose symbol or block is gone are kept without a place, the program might get it back
+/// with the next edit
+///
+/// # Arguments
+/// - `notes`: The notes, like the notes of a [Session](crate::session::Session)
+/// - `result`: The analysis, whose stack, frames and scope exits have the symbols and whose cross
+///   reference has the id of each heap block
+///
+/// # Returns
+/// - `Vec<PlacedNote>`: Each of the notes with where it goes, in the order of the notes
+pub fn place_notes(notes: &[Note], result: &AnalysisResult) -> Vec<PlacedNote> {
+    notes
+        .iter()
+        .map(|note| PlacedNote {
+            note: note.clone(),
+            place: place_note(&note.target, result),
+        })
+        .collect()
+}
+
+/// Finds the symbol or heap block a note is attached to in an analysis
+fn place_note(target: &NoteTarget, result: &AnalysisResult) -> Option<NotePlace> {
+    let position = |symbols: &[Symbol], name: &str| {
+        symbols.iter().position(|symbol| symbol_name(symbol) == Some(name))
+    };
+
+    match target {
+        NoteTarget::Symbol {
+            name,
+            frame: None,
+            scope: 0,
+        } => Some(NotePlace::Stack {
+            index: position(&result.stack, name)?,
+        }),
+        NoteTarget::Symbol {
+            name,
+            frame: Some(function),
+            scope: 0,
+        } => result
+            .frames
+            .iter()
+            .enumerate()
+            .filter(|(_, frame)| frame.function == *function)
+            .find_map(|(frame, stack_frame)| {
+                Some(NotePlace::Frame {
+                    frame,
+                    index: position(&stack_frame.symbols, name)?,
+                })
+            }),
+        NoteTarget::Symbol { name, frame, scope } => result
+            .scope_exits
+            .iter()
+            .enumerate()
+            .filter(|(_, exit)| exit.function == *frame && exit.depth == *scope)
+            .find_map(|(exit, scope_exit)| {
+                Some(NotePlace::ScopeExit {
+                    exit,
+                    index: position(&scope_exit.symbols, name)?,
+                })
+            }),
+        NoteTarget::Block { id } => Some(NotePlace::Heap {
+            index: result
+                .cross_reference
+                .heap_ids
+                .iter()
+                .position(|heap_id| heap_id.as_ref() == Some(id))?,
+        }),
+    }
+}
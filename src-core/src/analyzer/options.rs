@@ -0,0 +1,280 @@
+//! Options that change how the analyzer lays out memory
+
+use std::fmt;
+use std::str::FromStr;
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use super::r#type::Type;
+use super::tags::MemoryTag;
+use crate::error::Error;
+
+/// The registers integer arguments are passed in, in order
+const INTEGER_ARGUMENT_REGISTERS: [&str; 6] = ["rdi", "rsi", "rdx", "rcx", "r8", "r9"];
+
+/// The registers floating point arguments are passed in, in order
+const FLOAT_ARGUMENT_REGISTERS: [&str; 8] = [
+    "xmm0", "xmm1", "xmm2", "xmm3", "xmm4", "xmm5", "xmm6", "xmm7",
+];
+
+/// The fewest bytes a hinted heap starts with, so the first few blocks fit without growing it
+const MIN_HINTED_HEAP_SIZE: usize = 16;
+
+/// The most bytes a hinted heap starts with and grows by, every byte of the heap is a cell of its own
+const MAX_HINTED_HEAP_SIZE: usize = 1024;
+
+/// Represents the data model of the machine being simulated
+///
+/// - `Ilp32`: `int`, `long` and pointers are 32 bits wide
+/// - `Lp64`: `long` and pointers are 64 bits wide
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum Architecture {
+    #[default]
+    Ilp32,
+    Lp64,
+}
+
+impl Architecture {
+    /// Gets the size of a pointer in bytes
+    ///
+    /// # Returns
+    /// - `usize`: The size of a pointer in bytes
+    pub fn pointer_size(&self) -> usize {
+        match self {
+            Architecture::Ilp32 => 4,
+            Architecture::Lp64 => 8,
+        }
+    }
+
+    /// Gets the alignment `malloc` guarantees for heap blocks, which is twice the size of a pointer
+    ///
+    /// # Returns
+    /// - `usize`: The alignment in bytes
+    pub fn heap_alignment(&self) -> usize {
+        2 * self.pointer_size()
+    }
+}
+
+impl FromStr for Architecture {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "ilp32" => Ok(Architecture::Ilp32),
+            "lp64" => Ok(Architecture::Lp64),
+            _ => Err(format!("Unknown architecture `{}` (expected `ilp32` or `lp64`)", s).into()),
+        }
+    }
+}
+
+impl fmt::Display for Architecture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Architecture::Ilp32 => write!(f, "ilp32"),
+            Architecture::Lp64 => write!(f, "lp64"),
+        }
+    }
+}
+
+/// Represents where arguments are placed when a function is called
+///
+/// - `Stack`: Every argument is a parameter in the callee's stack frame
+/// - `Registers`: Like x86-64 System V, the first six integer arguments and the first eight floating point
+///   arguments are passed in registers and the rest are spilled to the callee's stack frame
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum CallingConvention {
+    #[default]
+    Stack,
+    Registers,
+}
+
+impl CallingConvention {
+    /// Picks the register each argument is passed in
+    ///
+    /// # Arguments
+    /// - `argument_types`: The types of the arguments, in order
+    ///
+    /// # Returns
+    /// - `Vec<Option<&'static str>>`: The register of each argument, `None` for arguments passed on the stack
+    pub(crate) fn argument_registers(&self, argument_types: &[Type]) -> Vec<Option<&'static str>> {
+        if *self == CallingConvention::Stack {
+            return vec![None; argument_types.len()];
+        }
+
+        let mut integer_registers = INTEGER_ARGUMENT_REGISTERS.into_iter();
+        let mut float_registers = FLOAT_ARGUMENT_REGISTERS.into_iter();
+
+        argument_types
+            .iter()
+            .map(|argument_type| match argument_type {
+                Type::Float | Type::Double => float_registers.next(),
+                _ => integer_registers.next(),
+            })
+            .collect()
+    }
+}
+
+impl FromStr for CallingConvention {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "stack" => Ok(CallingConvention::Stack),
+            "registers" => Ok(CallingConvention::Registers),
+            _ => {
+                Err(format!("Unknown calling convention `{}` (expected `stack` or `registers`)", s)
+                    .into())
+            }
+        }
+    }
+}
+
+impl fmt::Display for CallingConvention {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CallingConvention::Stack => write!(f, "stack"),
+            CallingConvention::Registers => write!(f, "registers"),
+        }
+    }
+}
+
+/// Represents the order heap blocks are returned in, so each layout of the visualization gets the blocks
+/// in the order it draws them
+///
+/// - `Address`: By their position in the heap, the way the allocator laid them out
+/// - `Allocation`: By the step that allocated them, blocks that were never allocated come last
+/// - `Owner`: Grouped by the pointer owning them, in the order the owners first appear in the heap
+/// - `Site`: Grouped by the line of the statement that allocated them
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum HeapOrder {
+    #[default]
+    Address,
+    Allocation,
+    Owner,
+    Site,
+}
+
+/// Represents what happens to statements outside the subset of C++ the analyzer supports
+///
+/// - `Strict`: The first unsupported statement fails the analysis with an error pointing at it
+/// - `Permissive`: Unsupported statements are skipped and reported as ignored, the rest is analyzed
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum Strictness {
+    #[default]
+    Strict,
+    Permissive,
+}
+
+impl FromStr for Strictness {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "strict" => Ok(Strictness::Strict),
+            "permissive" => Ok(Strictness::Permissive),
+            _ => {
+                Err(format!("Unknown strictness `{}` (expected `strict` or `permissive`)", s)
+                    .into())
+            }
+        }
+    }
+}
+
+impl fmt::Display for Strictness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Strictness::Strict => write!(f, "strict"),
+            Strictness::Permissive => write!(f, "permissive"),
+        }
+    }
+}
+
+/// Represents how much of the heap the frontend shows at once
+///
+/// - `Cells`: The heap panel fits `count` one byte cells
+/// - `Panel`: The heap panel is `height` pixels tall and a one byte cell is `cell_height` pixels tall
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum LayoutHint {
+    Cells {
+        count: usize,
+    },
+    #[serde(rename_all = "camelCase")]
+    Panel {
+        height: f64,
+        cell_height: f64,
+    },
+}
+
+impl LayoutHint {
+    /// Gets the size the heap starts with and grows by, a panel full of cells
+    ///
+    /// # Returns
+    /// - `usize`: The size in bytes, never below 16 or above 1024 however small or big the panel is
+    pub fn heap_size(&self) -> usize {
+        let cells = match *self {
+            LayoutHint::Cells { count } => count,
+            LayoutHint::Panel {
+                height,
+                cell_height,
+            } if cell_height > 0.0 => (height / cell_height) as usize,
+            LayoutHint::Panel { .. } => 0,
+        };
+
+        cells.clamp(MIN_HINTED_HEAP_SIZE, MAX_HINTED_HEAP_SIZE)
+    }
+}
+
+/// Represents the options the analyzer runs with
+///
+/// # Fields
+/// - `seed`: Seed for the random placement of heap blocks, `None` picks a different layout on every run
+/// - `architecture`: The data model used for the size of pointers
+/// - `calling_convention`: Where the arguments of function calls are placed
+/// - `heap_order`: The order the heap blocks are returned in
+/// - `self_check`: Whether to validate the memory layout after every statement, always on in debug builds
+/// - `track_history`: Whether to record every value the variables on the stack held
+/// - `max_call_depth`: How deep calls can nest before the stack overflows, `None` allows 64 frames and
+///   no more than 128 are ever allowed
+/// - `show_temporaries`: Whether to report the temporaries holding the values calls return, they are
+///   collapsed into the expressions using them otherwise
+/// - `tags`: The tags of the symbols with the given names and the blocks they own
+/// - `line_tags`: The tags of what the statements on the given lines declare or allocate, usually read from
+///   the `// @tag:` comments in the source
+/// - `layout`: How much of the heap the frontend shows at once, the heap starts as big as the panel and
+///   grows a panel at a time, `None` starts it at 20 bytes and doubles it
+/// - `strictness`: Whether unsupported statements fail the analysis or are skipped, the source has to be
+///   parsed with the same strictness
+/// - `defines`: Macros defined outside the source, like the `-D` flags a project's
+///   `compile_commands.json` compiles it with, the source has to be preprocessed with the same ones
+/// - `diagnostic_limit`: How many distinct warnings are reported before the rest are summarized, `None`
+///   reports [DEFAULT_DIAGNOSTIC_LIMIT](crate::diagnostics::DEFAULT_DIAGNOSTIC_LIMIT)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+#[serde(default, rename_all = "camelCase")]
+pub struct AnalyzerOptions {
+    pub seed: Option<u64>,
+    pub architecture: Architecture,
+    pub calling_convention: CallingConvention,
+    pub heap_order: HeapOrder,
+    pub self_check: bool,
+    pub track_history: bool,
+    pub max_call_depth: Option<usize>,
+    pub show_temporaries: bool,
+    pub tags: IndexMap<String, MemoryTag>,
+    pub line_tags: IndexMap<usize, MemoryTag>,
+    pub layout: Option<LayoutHint>,
+    pub strictness: Strictness,
+    pub defines: IndexMap<String, String>,
+    pub diagnostic_limit: Option<usize>,
+}
@@ -0,0 +1,179 @@
+//! Summarizes a heap with hundreds of blocks, so the frontend can draw an overview of it and the blocks
+//! of the part zoomed into without going through every block on every frame
+
+use serde::Serialize;
+
+use super::heap_allocator::{HeapBlock, HeapBlockState};
+
+/// How many blocks the heap needs before it gets an overview, smaller heaps are drawn block by block
+pub const OVERVIEW_THRESHOLD: usize = 200;
+
+/// The most clusters each zoom level has, from the most detailed level to the least detailed one
+const ZOOM_LEVELS: [usize; 3] = [256, 64, 16];
+
+/// Represents blocks next to each other in the heap summarized as one
+///
+/// # Fields
+/// - `start`: The position in the heap of the first block of the cluster
+/// - `end`: The position in the heap after the last block of the cluster
+/// - `pointer`: The starting position of the first block of the cluster
+/// - `size`: The size of the blocks of the cluster in bytes
+/// - `in_use`: The bytes of the blocks of the cluster that are allocated or leaked
+/// - `allocated`: How many blocks of the cluster are allocated
+/// - `leaked`: How many blocks of the cluster are leaked
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct HeapCluster {
+    pub start: usize,
+    pub end: usize,
+    pub pointer: usize,
+    pub size: usize,
+    pub in_use: usize,
+    pub allocated: usize,
+    pub leaked: usize,
+}
+
+/// Represents the heap summarized at one zoom level
+///
+/// # Fields
+/// - `span`: The bytes of the heap each cluster covers, a cluster has the blocks starting in them
+/// - `clusters`: The clusters, in the order of the heap
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct ZoomLevel {
+    pub span: usize,
+    pub clusters: Vec<HeapCluster>,
+}
+
+/// Represents unallocated blocks next to each other, which the part zoomed into can show as one
+///
+/// # Fields
+/// - `start`: The position in the heap of the first block of the run
+/// - `end`: The position in the heap after the last block of the run
+/// - `pointer`: The starting position of the first block of the run
+/// - `size`: The size of the blocks of the run in bytes
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct UnallocatedRun {
+    pub start: usize,
+    pub end: usize,
+    pub pointer: usize,
+    pub size: usize,
+}
+
+/// Represents the overview of a heap with hundreds of blocks
+///
+/// # Fields
+/// - `levels`: The heap summarized at each zoom level, from the most detailed level to the least
+///   detailed one
+/// - `unallocated_runs`: Every run of two or more unallocated or padding blocks, in the order of the
+///   heap
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct HeapOverview {
+    pub levels: Vec<ZoomLevel>,
+    pub unallocated_runs: Vec<UnallocatedRun>,
+}
+
+/// Summarizes the heap for an overview, once it has [OVERVIEW_THRESHOLD] blocks or more
+///
+/// Blocks are clustered in the order they are output, so a heap ordered by
+/// [heap_order](crate::analyzer::AnalyzerOptions::heap_order) is clustered in that order, a cluster
+/// ending wherever the next block starts in another span of the heap
+///
+/// # Arguments
+/// - `heap`: The heap blocks that are output
+/// - `capacity`: The size of the heap in bytes, including the bytes no block holds
+///
+/// # Returns
+/// - `Option<HeapOverview>`: The overview, `None` if the heap has too few blocks to need one
+pub(crate) fn heap_overview(heap: &[HeapBlock], capacity: usize) -> Option<HeapOverview> {
+    if heap.len() < OVERVIEW_THRESHOLD {
+        return None;
+    }
+
+    let levels = ZOOM_LEVELS
+        .iter()
+        .map(|&clusters| {
+            let span = capacity.div_ceil(clusters).max(1).next_power_of_two();
+
+            ZoomLevel {
+                span,
+                clusters: clusters_of(heap, span),
+            }
+        })
+        .collect();
+
+    Some(HeapOverview {
+        levels,
+        unallocated_runs: unallocated_runs(heap),
+    })
+}
+
+/// Clusters the blocks next to each other that start in the same span of the heap
+fn clusters_of(heap: &[HeapBlock], span: usize) -> Vec<HeapCluster> {
+    let mut clusters: Vec<HeapCluster> = Vec::new();
+
+    for (index, block) in heap.iter().enumerate() {
+        let starts_cluster = match clusters.last() {
+            Some(cluster) => cluster.pointer / span != block.pointer / span,
+            None => true,
+        };
+
+        if starts_cluster {
+            clusters.push(HeapCluster {
+                start: index,
+                pointer: block.pointer,
+                ..HeapCluster::default()
+            });
+        }
+
+        let Some(cluster) = clusters.last_mut() else {
+            continue;
+        };
+
+        cluster.end = index + 1;
+        cluster.size += block.size;
+
+        match block.block_state {
+            HeapBlockState::Allocated => {
+                cluster.in_use += block.size;
+                cluster.allocated += 1;
+            }
+            HeapBlockState::Leaked => {
+                cluster.in_use += block.size;
+                cluster.leaked += 1;
+            }
+            _ => {}
+        }
+    }
+
+    clusters
+}
+
+/// Finds the runs of two or more unallocated or padding blocks
+fn unallocated_runs(heap: &[HeapBlock]) -> Vec<UnallocatedRun> {
+    let mut runs = Vec::new();
+    let mut run: Option<UnallocatedRun> = None;
+
+    for (index, block) in heap.iter().enumerate() {
+        if !matches!(block.block_state, HeapBlockState::Unallocated | HeapBlockState::Padding) {
+            runs.extend(run.take().filter(|run| run.end - run.start > 1));
+            continue;
+        }
+
+        let run = run.get_or_insert(UnallocatedRun {
+            start: index,
+            end: index,
+            pointer: block.pointer,
+            size: 0,
+        });
+
+        run.end = index + 1;
+        run.size += block.size;
+    }
+
+    runs.extend(run.filter(|run| run.end - run.start > 1));
+
+    runs
+}
@@ -0,0 +1,115 @@
+//! Previews what a statement would do to the memory a program ends with, without the statement being
+//! part of the program, so the editor can show what a line does before it is written
+
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use crate::diagnostics::Diagnostic;
+use crate::error::Result;
+use crate::pipeline::{parse_source, source_options};
+use crate::rng::Rng;
+use crate::session::Session;
+
+use super::heap_allocator::HeapBlock;
+use super::helpers::symbol_name;
+use super::{Analyzer, AnalyzerOptions, Symbol};
+
+/// Represents what a statement would change in the memory a program ends with
+///
+/// # Fields
+/// - `added`: The symbols the statement would push on the stack, like the variable it declares
+/// - `changed`: The symbols on the stack the statement would change, as they would be after it ran
+/// - `removed`: The names of the symbols the statement would take off the stack
+/// - `heap`: The heap blocks the statement would allocate, free or write to, as they would be after it
+///   ran
+/// - `warnings`: The warnings the statement would raise
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StatementPreview {
+    pub added: Vec<Symbol>,
+    pub changed: Vec<Symbol>,
+    pub removed: Vec<String>,
+    pub heap: Vec<HeapBlock>,
+    pub warnings: Vec<Diagnostic>,
+}
+
+/// Runs a statement after the last statement of a program and reports what it changed
+///
+/// The program runs twice, once as it is and once with the statement after it, both starting from the
+/// same placements and seed so the blocks the program allocates land in the same place in both runs.
+/// Neither run remembers where it placed its blocks, so the preview doesn't move the blocks of the
+/// next analysis
+///
+/// # Arguments
+/// - `source`: The source code of the program
+/// - `statement`: The statement to preview, like `int* p = new int;`
+/// - `starting_pointers`: Where the blocks of each pointer were placed, like the starting pointers of an
+///   [AnalyzerState](crate::analyzer::AnalyzerState)
+/// - `options`: The options to analyze the program with
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - [StatementPreview](crate::analyzer::StatementPreview): What the statement would change
+///    - [Error](crate::error::Error): An error if the program fails to analyze, `statement` isn't a
+///      single statement, or it fails to run. The line and column of an error in the statement are
+///      counted from the start of `statement`
+pub async fn preview_statement(
+    source: &str,
+    statement: &str,
+    starting_pointers: IndexMap<String, usize>,
+    options: AnalyzerOptions,
+) -> Result<StatementPreview> {
+    let statements = parse_source(source, &options)?;
+    let mut candidate = parse_source(statement, &options)?;
+
+    if candidate.len() != 1 {
+        return Err(format!(
+            "Expected a single statement to preview but found {}",
+            candidate.len()
+        )
+        .into());
+    }
+
+    let mut session =
+        Session::new(source.to_owned(), starting_pointers, source_options(source, options));
+    // both runs make the same random decisions up to the statement, so only its own changes differ
+    session.options.seed.get_or_insert_with(|| Rng::from_entropy().next_u64());
+    let analyzer = Analyzer::new(session.options.clone());
+
+    let before = analyzer.analyze_statements(statements.clone(), &mut session).await?;
+
+    let mut previewed = statements;
+    previewed.append(&mut candidate);
+
+    // the session now holds the placements of the first run, which the second run keeps
+    let after = analyzer.analyze_statements(previewed, &mut session).await?;
+
+    let symbols_before: IndexMap<&str, &Symbol> =
+        before.stack.iter().filter_map(|symbol| Some((symbol_name(symbol)?, symbol))).collect();
+
+    let mut preview = StatementPreview::default();
+
+    for symbol in &after.stack {
+        let Some(name) = symbol_name(symbol) else {
+            continue;
+        };
+
+        match symbols_before.get(name) {
+            None => preview.added.push(symbol.clone()),
+            Some(&earlier) if earlier != symbol => preview.changed.push(symbol.clone()),
+            Some(_) => {}
+        }
+    }
+
+    preview.removed = symbols_before
+        .keys()
+        .filter(|name| !after.stack.iter().any(|symbol| symbol_name(symbol) == Some(**name)))
+        .map(|name| name.to_string())
+        .collect();
+
+    preview.heap = after.heap.into_iter().filter(|block| !before.heap.contains(block)).collect();
+
+    preview.warnings =
+        after.warnings.into_iter().filter(|warning| !before.warnings.contains(warning)).collect();
+
+    Ok(preview)
+}
@@ -1,14 +1,17 @@
 //! Custom heap allocator used to simulate memory allocation and deallocation
 
 use log::info;
-use rand::{rng, Rng};
 
 use indexmap::{IndexMap, IndexSet};
 use serde::{Deserialize, Serialize};
 
 use crate::error::Result;
+use crate::rng::Rng;
 
-use super::heap_allocator::{HeapBlock, HeapBlockState};
+use super::heap_allocator::{
+    BlockMember, CopyEvent, FreeRange, GapReason, GrowthReason, HeapBlock, HeapBlockState,
+    HeapGrowth,
+};
 
 /// Represents a heap allocator.
 ///
@@ -26,7 +29,14 @@ use super::heap_allocator::{HeapBlock, HeapBlockState};
 ///   the pointer and end positions of a free block, helping to efficiently allocate and deallocate memory.
 /// - `infinite_memory`: Whether the heap should grow dynamically when allocation fails.
 /// - `growth_factor`: The factor by which to multiply the heap size when resizing (default: 2.0).
+/// - `growth_step`: The bytes the heap grows by a multiple of when resizing, instead of by `growth_factor`
+///   (default: None).
 /// - `max_size`: Optional maximum size limit for the heap (None means unlimited).
+/// - `rng`: The [Rng](crate::rng::Rng) used to place blocks, seeded from the OS unless [seed](HeapAllocator::seed)
+///   is called.
+/// - `alignment`: The boundary every block starts on and is rounded up to (default: 1).
+/// - `minimum_block_size`: The smallest block the allocator hands out (default: 1).
+/// - `growth`: Every time the heap grew, in the order it grew.
 #[derive(Serialize, Deserialize)]
 pub(crate) struct HeapAllocator {
     heap: Vec<HeapBlock>,
@@ -34,7 +44,12 @@ pub(crate) struct HeapAllocator {
     free_list: Vec<(usize, usize)>,
     infinite_memory: bool,
     growth_factor: f64,
+    growth_step: Option<usize>,
     max_size: Option<usize>,
+    rng: Rng,
+    alignment: usize,
+    minimum_block_size: usize,
+    growth: Vec<HeapGrowth>,
 }
 
 impl HeapAllocator {
@@ -92,6 +107,9 @@ impl HeapAllocator {
                     size: 0,
                     metadata: "Unallocated Block".to_string(),
                     pointer: usize::MAX,
+                    gap_reason: None,
+                    moved: None,
+                    members: None,
                 };
                 size
             ],
@@ -99,7 +117,88 @@ impl HeapAllocator {
             free_list: vec![(0, size - 1)],
             infinite_memory,
             growth_factor,
+            growth_step: None,
             max_size,
+            rng: Rng::from_entropy(),
+            alignment: 1,
+            minimum_block_size: 1,
+            growth: Vec::new(),
+        }
+    }
+
+    /// Reseeds the random number generator so the same sequence of allocations always lands in the same place
+    ///
+    /// # Arguments
+    /// - `seed`: The seed for the random number generator
+    pub(crate) fn seed(&mut self, seed: u64) {
+        self.rng = Rng::from_seed(seed);
+    }
+
+    /// Makes the allocator behave like a real `malloc`, which starts every block on an `alignment` byte
+    /// boundary and never hands out less than `minimum_block_size` bytes
+    ///
+    /// The bytes a block is rounded up by are recorded as [Padding](HeapBlockState::Padding) so the
+    /// visualization can explain them
+    ///
+    /// # Arguments
+    /// - `alignment`: The boundary every block starts on, must be a power of two
+    /// - `minimum_block_size`: The smallest block the allocator hands out
+    pub(crate) fn align(&mut self, alignment: usize, minimum_block_size: usize) {
+        self.alignment = alignment;
+        self.minimum_block_size = minimum_block_size;
+    }
+
+    /// Makes the heap grow by whole steps of `step` bytes when an allocation doesn't fit, rather than by
+    /// the growth factor, so it grows a panel of the visualization at a time
+    ///
+    /// # Arguments
+    /// - `step`: The bytes the heap grows by, enough steps are added at once to fit the allocation
+    pub(crate) fn grow_by(&mut self, step: usize) {
+        self.growth_step = Some(step);
+    }
+
+    /// Rounds a pointer up to the next [alignment](HeapAllocator::align) boundary
+    fn align_up(&self, pointer: usize) -> usize {
+        (pointer + self.alignment - 1) & !(self.alignment - 1)
+    }
+
+    /// Gets the number of bytes the allocator actually reserves for a block
+    ///
+    /// # Arguments
+    /// - `size`: The size requested for the block in bytes
+    ///
+    /// # Returns
+    /// - `usize`: The size rounded up to the minimum block size and the alignment
+    fn reserved_size(&self, size: usize) -> usize {
+        self.align_up(size.max(self.minimum_block_size))
+    }
+
+    /// Gets why a block of the given size needs padding
+    ///
+    /// # Arguments
+    /// - `size`: The size requested for the block in bytes
+    ///
+    /// # Returns
+    /// - `Option<GapReason>`: The reason, or `None` if the block is not padded
+    fn gap_reason(&self, size: usize) -> Option<GapReason> {
+        if self.reserved_size(size) == size {
+            None
+        } else if size < self.minimum_block_size {
+            Some(GapReason::MinimumBlockSize {
+                minimum_size: self.minimum_block_size,
+            })
+        } else {
+            Some(GapReason::Alignment {
+                alignment: self.alignment,
+            })
+        }
+    }
+
+    /// Gets the size the heap grows to before making sure it fits `required_size` more bytes
+    fn grown_size(&self, required_size: usize) -> usize {
+        match self.growth_step {
+            Some(step) => self.size + required_size.next_multiple_of(step),
+            None => (self.size as f64 * self.growth_factor) as usize,
         }
     }
 
@@ -107,21 +206,22 @@ impl HeapAllocator {
     ///
     /// # Arguments
     /// - `required_size`: The minimum size needed for the allocation
+    /// - `reason`: Why the heap has to grow, recorded with the [growth](HeapAllocator::growth)
     ///
     /// # Returns
     /// - `Result<()>`: An error if the resize operation fails or succeeds
-    fn resize_heap(&mut self, required_size: usize) -> Result<()> {
+    fn resize_heap(&mut self, required_size: usize, reason: GrowthReason) -> Result<()> {
         if !self.infinite_memory {
             return Err("Infinite memory is disabled".into());
         }
 
         let new_size = if let Some(max_size) = self.max_size {
-            let calculated_size = (self.size as f64 * self.growth_factor) as usize;
+            let calculated_size = self.grown_size(required_size);
             let min_required = self.size + required_size;
             let target_size = std::cmp::max(calculated_size, min_required);
             std::cmp::min(target_size, max_size)
         } else {
-            let calculated_size = (self.size as f64 * self.growth_factor) as usize;
+            let calculated_size = self.grown_size(required_size);
             let min_required = self.size + required_size;
             std::cmp::max(calculated_size, min_required)
         };
@@ -147,12 +247,20 @@ impl HeapAllocator {
                 size: 0,
                 metadata: "Unallocated Block".to_string(),
                 pointer: usize::MAX,
+                gap_reason: None,
+                moved: None,
+                members: None,
             },
         );
 
         // Add the new memory region to the free list
-        self.free_list.push((old_size, new_size - 1));
+        release_range(&mut self.free_list, old_size, new_size - 1);
         self.size = new_size;
+        self.growth.push(HeapGrowth {
+            old_size,
+            new_size,
+            reason,
+        });
 
         info!("Heap resized from {} to {} bytes", old_size, new_size);
         Ok(())
@@ -212,7 +320,7 @@ impl HeapAllocator {
                     // Represents one byte blocks in the heap
                     pointer = block_start_pointer;
                 } else {
-                    pointer = rng().random_range(block_start_pointer..block_end_pointer);
+                    pointer = self.rng.range_inclusive(block_start_pointer, block_end_pointer - 1);
                 }
 
                 info!("Random Pointer: {:?}", pointer);
@@ -224,18 +332,24 @@ impl HeapAllocator {
                 continue;
             }
 
-            while (block_end_pointer - block_start_pointer + 1) >= size {
-                let block_size = block_end_pointer - pointer + 1;
+            if is_random_start {
+                pointer = self.align_up(pointer);
+            }
 
-                if block_size >= size {
+            // Keep trying while there is an aligned position the block fits at
+            while self.align_up(block_start_pointer) + size - 1 <= block_end_pointer {
+                if pointer + size - 1 <= block_end_pointer {
+                    let block_size = block_end_pointer - pointer + 1;
                     let allocated_start = pointer;
                     let allocated_end = allocated_start + size - 1;
 
+                    // the bytes before and after the block stay free, in the order of their addresses
                     if block_size > size {
-                        self.free_list[i] = (allocated_end + 1, block_end_pointer);
-
                         if pointer != block_start_pointer {
-                            self.free_list.insert(i + 1, (block_start_pointer, pointer - 1));
+                            self.free_list[i] = (block_start_pointer, pointer - 1);
+                            self.free_list.insert(i + 1, (allocated_end + 1, block_end_pointer));
+                        } else {
+                            self.free_list[i] = (allocated_end + 1, block_end_pointer);
                         }
                     } else {
                         if pointer != block_start_pointer {
@@ -257,7 +371,8 @@ impl HeapAllocator {
 
                     return Ok((allocated_start, None));
                 } else {
-                    pointer = rng().random_range(block_start_pointer..=block_end_pointer);
+                    pointer = self.rng.range_inclusive(block_start_pointer, block_end_pointer);
+                    pointer = self.align_up(pointer);
                 }
             }
         }
@@ -265,7 +380,7 @@ impl HeapAllocator {
         // If infinite memory is enabled, try to resize the heap
         if self.infinite_memory {
             info!("Allocation failed, attempting to resize heap...");
-            if let Err(e) = self.resize_heap(size) {
+            if let Err(e) = self.resize_heap(size, GrowthReason::NoFreeRange { size }) {
                 return Err(format!("Failed to resize heap: {}", e).into());
             }
 
@@ -291,7 +406,11 @@ impl HeapAllocator {
             // If infinite memory is enabled, try to resize the heap
             if self.infinite_memory {
                 info!("Write operation out of bounds, attempting to resize heap...");
-                if let Err(e) = self.resize_heap(block_to_write.size) {
+                let reason = GrowthReason::PastTheEnd {
+                    size: block_to_write.size,
+                };
+
+                if let Err(e) = self.resize_heap(block_to_write.size, reason) {
                     return Err(format!("Failed to resize heap for write operation: {}", e).into());
                 }
             } else {
@@ -309,6 +428,9 @@ impl HeapAllocator {
                 size: block_to_write.size,
                 metadata: block_to_write.metadata.clone(),
                 pointer,
+                gap_reason: None,
+                moved: None,
+                members: block_to_write.members.clone(),
             };
         }
 
@@ -328,7 +450,7 @@ impl HeapAllocator {
     ///    - [Error](crate::error::Error): An error if there is insufficient memory
     pub(crate) fn allocate_and_write(
         &mut self,
-        current_pointer_identifier: &String,
+        current_pointer_identifier: &str,
         value_size: usize,
         starting_pointers: &mut IndexMap<String, usize>,
     ) -> Result<usize> {
@@ -339,7 +461,8 @@ impl HeapAllocator {
                 None
             };
 
-        let (ptr, start_pointer) = self.allocate(value_size, starting_pointer)?;
+        let reserved_size = self.reserved_size(value_size);
+        let (ptr, start_pointer) = self.allocate(reserved_size, starting_pointer)?;
 
         if let None = starting_pointer {
             starting_pointers
@@ -354,26 +477,182 @@ impl HeapAllocator {
             ptr,
             HeapBlock {
                 block_state: HeapBlockState::Allocated,
-                current_pointer_identifier: Some(current_pointer_identifier.clone()),
+                current_pointer_identifier: Some(current_pointer_identifier.to_string()),
                 dangling_pointer_identifiers: None,
                 size: value_size,
                 metadata: "".to_string(),
                 pointer: ptr,
+                gap_reason: None,
+                moved: None,
+                members: None,
             },
         )?;
 
+        if let Some(reason) = self.gap_reason(value_size) {
+            self.pad(ptr + value_size, reserved_size - value_size, reason);
+        }
+
         Ok(ptr)
     }
 
+    /// Resizes a block like `realloc`, moving it only when it can't be resized where it is
+    ///
+    /// A block shrinks in place and hands its tail back to the free list, and grows in place when the
+    /// bytes right after it are free or it reaches the end of the heap, which grows to fit it. Otherwise the contents are copied into a new block, up to the smaller
+    /// of the two sizes, and the old block is freed with a record of where its contents went
+    ///
+    /// # Arguments
+    /// - `current_pointer_identifier`: The identifier of the pointer that owns the block
+    /// - `pointer`: The starting position of the old block in the heap
+    /// - `old_size`: The size of the old block in bytes
+    /// - `new_size`: The size of the new block in bytes
+    /// - `starting_pointers`: The starting pointers of previous runs
+    ///
+    /// # Returns
+    /// - [Result](crate::error::Result): A result containing either:
+    ///    - `(usize, Option<CopyEvent>)`: The starting position of the block and the copy that moved the
+    ///      contents, `None` if the block was resized in place
+    ///    - [Error](crate::error::Error): An error if there is insufficient memory
+    pub(crate) fn reallocate(
+        &mut self,
+        current_pointer_identifier: &str,
+        pointer: usize,
+        old_size: usize,
+        new_size: usize,
+        starting_pointers: &mut IndexMap<String, usize>,
+    ) -> Result<(usize, Option<CopyEvent>)> {
+        let metadata = self.heap[pointer].metadata.clone();
+        let old_end = pointer + self.reserved_size(old_size);
+        let new_end = pointer + self.reserved_size(new_size);
+
+        // a block that reaches the end of the heap grows in place with the heap growing under it, if the
+        // heap can't grow the block is moved and fails to find room there
+        let reaches_the_end =
+            old_end == self.size || self.free_list.last() == Some(&(old_end, self.size - 1));
+
+        if new_end > self.size && reaches_the_end {
+            let reason = GrowthReason::NoFreeRange {
+                size: new_end - old_end,
+            };
+            let _ = self.resize_heap(new_end - self.size, reason);
+        }
+
+        if new_end <= old_end {
+            if new_end < old_end {
+                self.unallocate(new_end, old_end - new_end);
+                release_range(&mut self.free_list, new_end, old_end - 1);
+            }
+        } else if let Some(i) =
+            self.free_list.iter().position(|&(start, end)| start == old_end && end >= new_end - 1)
+        {
+            let (_, end) = self.free_list[i];
+
+            if end >= new_end {
+                self.free_list[i] = (new_end, end);
+            } else {
+                self.free_list.remove(i);
+            }
+        } else {
+            let new_pointer =
+                self.allocate_and_write(current_pointer_identifier, new_size, starting_pointers)?;
+            self.update_metadata(new_pointer, metadata)?;
+            self.free(pointer, old_size);
+
+            let copy = CopyEvent {
+                from: pointer,
+                to: new_pointer,
+                bytes: old_size.min(new_size),
+            };
+
+            for i in pointer..old_end {
+                self.heap[i].moved = Some(copy.clone());
+            }
+
+            info!("Reallocated block from {} to {}", pointer, new_pointer);
+
+            return Ok((new_pointer, Some(copy)));
+        }
+
+        self.write(
+            pointer,
+            HeapBlock {
+                block_state: HeapBlockState::Allocated,
+                current_pointer_identifier: Some(current_pointer_identifier.to_string()),
+                dangling_pointer_identifiers: None,
+                size: new_size,
+                metadata,
+                pointer,
+                gap_reason: None,
+                moved: None,
+                members: None,
+            },
+        )?;
+
+        if let Some(reason) = self.gap_reason(new_size) {
+            self.pad(pointer + new_size, new_end - pointer - new_size, reason);
+        }
+
+        info!("Resized block at {} in place to {} bytes", pointer, new_size);
+
+        Ok((pointer, None))
+    }
+
+    /// Hands bytes back to the heap as if they were never allocated
+    ///
+    /// # Arguments
+    /// - `pointer`: The starting position of the bytes in the heap
+    /// - `size`: The number of bytes
+    fn unallocate(&mut self, pointer: usize, size: usize) {
+        for i in pointer..pointer + size {
+            self.heap[i] = HeapBlock {
+                block_state: HeapBlockState::Unallocated,
+                current_pointer_identifier: None,
+                dangling_pointer_identifiers: None,
+                size: 0,
+                metadata: "Unallocated Block".to_string(),
+                pointer: usize::MAX,
+                gap_reason: None,
+                moved: None,
+                members: None,
+            };
+        }
+    }
+
+    /// Marks the unused tail of an allocated block as padding
+    ///
+    /// # Arguments
+    /// - `pointer`: The starting position of the padding in the heap
+    /// - `size`: The size of the padding in bytes
+    /// - `reason`: Why the allocator reserved the bytes
+    fn pad(&mut self, pointer: usize, size: usize, reason: GapReason) {
+        for i in pointer..pointer + size {
+            self.heap[i] = HeapBlock {
+                block_state: HeapBlockState::Padding,
+                current_pointer_identifier: None,
+                dangling_pointer_identifiers: None,
+                size,
+                metadata: format!("Padding ({})", reason),
+                pointer,
+                gap_reason: Some(reason.clone()),
+                moved: None,
+                members: None,
+            };
+        }
+    }
+
     /// Frees a block of memory starting at the specified position
     ///
-    /// This function marks the memory block as free and adds it to the free list.
-    /// free blocks to mimic real-world heap behavior
+    /// This function marks the memory block as free and adds it to the free list, merged with the free
+    /// ranges next to it like real-world allocators coalesce free blocks
+    ///
+    /// Any padding after the block is freed along with it
     ///
     /// # Arguments
     /// - `pointer`: The starting position of the block to free in the heap
     /// - `size`: The size of the block to free in bytes
     pub(crate) fn free(&mut self, pointer: usize, size: usize) {
+        let size = self.reserved_size(size);
+
         for i in pointer..pointer + size {
             self.heap[i] = HeapBlock {
                 block_state: HeapBlockState::Free,
@@ -384,10 +663,13 @@ impl HeapAllocator {
                 size,
                 metadata: "Free Block".to_string(),
                 pointer: pointer,
+                gap_reason: None,
+                moved: None,
+                members: None,
             };
         }
 
-        self.free_list.push((pointer, pointer + size - 1));
+        release_range(&mut self.free_list, pointer, pointer + size - 1);
     }
 
     /// Updates the metadata of a block of memory starting at the specified position
@@ -412,6 +694,58 @@ impl HeapAllocator {
         Ok(())
     }
 
+    /// Updates the fields shown for the object held by the block starting at the specified position
+    ///
+    /// # Arguments
+    /// - `pointer`: The starting position of the block in the heap
+    /// - `members`: The fields of the object, in the order they are laid out
+    ///
+    /// # Returns
+    /// - `Result<()>`: An error if the update operation is out of bounds or succeeds
+    pub(crate) fn update_members(
+        &mut self,
+        pointer: usize,
+        members: Vec<BlockMember>,
+    ) -> Result<()> {
+        let end = pointer + self.heap[pointer].size - 1;
+
+        if pointer >= self.size || end >= self.size {
+            return Err("Invalid members update operation: out of bounds".into());
+        }
+
+        for i in pointer..=end {
+            self.heap[i].members = Some(members.clone());
+        }
+
+        Ok(())
+    }
+
+    /// Hands an allocated block of memory starting at the specified position to another pointer
+    ///
+    /// # Arguments
+    /// - `pointer`: The starting position of the block in the heap
+    /// - `current_pointer_identifier`: The identifier of the pointer that owns the block now
+    ///
+    /// # Returns
+    /// - `Result<()>`: An error if the update operation is out of bounds or succeeds
+    pub(crate) fn update_pointer_identifier(
+        &mut self,
+        pointer: usize,
+        current_pointer_identifier: String,
+    ) -> Result<()> {
+        let end = pointer + self.heap[pointer].size - 1;
+
+        if pointer >= self.size || end >= self.size {
+            return Err("Invalid pointer identifier update operation: out of bounds".into());
+        }
+
+        for i in pointer..=end {
+            self.heap[i].current_pointer_identifier = Some(current_pointer_identifier.clone());
+        }
+
+        Ok(())
+    }
+
     /// Updates the dangling pointers of a block of memory starting at the specified position
     /// with the specified dangling pointer identifier
     ///
@@ -499,6 +833,9 @@ impl HeapAllocator {
                 size,
                 metadata: "Leaked Block".to_string(),
                 pointer: pointer,
+                gap_reason: None,
+                moved: None,
+                members: None,
             };
         }
     }
@@ -530,6 +867,9 @@ impl HeapAllocator {
                         size: unallocated_size,
                         metadata: "Unallocated Block".to_string(),
                         pointer: start,
+                        gap_reason: None,
+                        moved: None,
+                        members: None,
                     });
 
                     unallocated_start = None;
@@ -548,12 +888,110 @@ impl HeapAllocator {
                 size: unallocated_size,
                 metadata: "Unallocated Block".to_string(),
                 pointer: start,
+                gap_reason: None,
+                moved: None,
+                members: None,
             });
         }
 
         new_heap
     }
 
+    /// Gets every time the heap grew, in the order it grew
+    pub(crate) fn growth(&self) -> &[HeapGrowth] {
+        &self.growth
+    }
+
+    /// Gets the size of the heap in bytes, which grows as blocks need room
+    pub(crate) fn capacity(&self) -> usize {
+        self.size
+    }
+
+    /// Gets the free list in the order the allocator searches it for a range a block fits in, which is
+    /// the order of their addresses
+    ///
+    /// A freed block is merged with the free ranges right before and after it, so the ranges left apart
+    /// show how fragmented the heap is
+    ///
+    /// # Returns
+    /// - `Vec<FreeRange>`: The ranges of the free list
+    pub(crate) fn free_ranges(&self) -> Vec<FreeRange> {
+        self.free_list
+            .iter()
+            .map(|&(start, end)| FreeRange {
+                start,
+                end,
+                size: end - start + 1,
+            })
+            .collect()
+    }
+
+    /// Gets the block that starts at the specified position
+    ///
+    /// # Arguments
+    /// - `pointer`: The starting position of the block in the heap
+    ///
+    /// # Returns
+    /// - `Option<&HeapBlock>`: The block, or `None` if no block starts there
+    pub(crate) fn block(&self, pointer: usize) -> Option<&HeapBlock> {
+        self.heap.get(pointer).filter(|block| block.pointer == pointer)
+    }
+
+    /// Validates that the free list and the state of every byte in the heap agree with each other
+    ///
+    /// # Returns
+    /// - `Result<(), String>`: A description of the first inconsistency found
+    pub(crate) fn check_consistency(&self) -> std::result::Result<(), String> {
+        if self.heap.len() != self.size {
+            return Err(format!(
+                "Heap holds {} bytes but its size is {}",
+                self.heap.len(),
+                self.size
+            ));
+        }
+
+        let mut in_free_list = vec![false; self.size];
+
+        for &(start, end) in &self.free_list {
+            if start > end || end >= self.size {
+                return Err(format!("Free list entry {}..={} is out of bounds", start, end));
+            }
+
+            if let Some(offset) = in_free_list[start..=end].iter().position(|&listed| listed) {
+                return Err(format!("Byte {} is in the free list more than once", start + offset));
+            }
+
+            in_free_list[start..=end].fill(true);
+        }
+
+        for (i, block) in self.heap.iter().enumerate() {
+            let is_free =
+                matches!(block.block_state, HeapBlockState::Unallocated | HeapBlockState::Free);
+
+            if is_free != in_free_list[i] {
+                return Err(format!(
+                    "Byte {} is {:?} but {} the free list",
+                    i,
+                    block.block_state,
+                    if in_free_list[i] { "in" } else { "not in" }
+                ));
+            }
+
+            if matches!(block.block_state, HeapBlockState::Unallocated) {
+                continue;
+            }
+
+            if i < block.pointer || i >= block.pointer + block.size {
+                return Err(format!(
+                    "Byte {} is outside of the block it belongs to ({}, {} bytes)",
+                    i, block.pointer, block.size
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Builds a list of all memory blocks in the heap in a format suitable for visualization
     ///
     /// # Returns
@@ -575,3 +1013,26 @@ impl HeapAllocator {
             .collect()
     }
 }
+
+/// Puts a range back in a free list kept in the order of addresses, merged with the free ranges right
+/// before and after it so freeing neighbouring blocks leaves one range
+///
+/// # Arguments
+/// - `free_list`: The free list, as the first and last byte of each range
+/// - `start`: The first byte of the range
+/// - `end`: The last byte of the range
+pub(crate) fn release_range(free_list: &mut Vec<(usize, usize)>, start: usize, end: usize) {
+    let mut index = free_list.partition_point(|&(other, _)| other < start);
+    let (mut start, mut end) = (start, end);
+
+    if index > 0 && free_list[index - 1].1 + 1 == start {
+        index -= 1;
+        start = free_list.remove(index).0;
+    }
+
+    if index < free_list.len() && free_list[index].0 == end + 1 {
+        end = free_list.remove(index).1;
+    }
+
+    free_list.insert(index, (start, end));
+}
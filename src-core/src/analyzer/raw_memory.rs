@@ -0,0 +1,302 @@
+//! Raw addresses for `peek` and `poke`, which read and write the simulated memory directly
+//!
+//! Addresses are the ones the visualization shows, so a value can be read off the diagram and then
+//! looked up by its address
+
+use indexmap::IndexMap;
+
+use crate::error::{Error::AnalyzerError, Result};
+use crate::export::format_address;
+use crate::parser::ast::{self, Expr};
+
+use super::address_space::{stack_addresses, HEAP_BASE_ADDRESS, STACK_END_ADDRESS};
+use super::heap_allocator::HeapBlockState;
+use super::helpers::{evaluate_expression, symbol_size, variable_literal};
+use super::memory_model::MemoryModel;
+use super::{r#type::Type, AllocationType, Symbol};
+
+/// Represents what a raw address refers to
+///
+/// - `Variable`: The start of a variable on the stack, with the index of the caller whose frame it is
+///   in, `None` for the current frame
+/// - `Block`: An element of a heap block, with the starting position of the block and the pointer owning
+///   it with its type, if the owner is in the current frame
+pub(crate) enum Location {
+    Variable {
+        name: String,
+        caller: Option<usize>,
+    },
+    Block {
+        pointer: usize,
+        owner: Option<(String, Type)>,
+    },
+}
+
+/// Evaluates the address passed to `peek` or `poke`
+///
+/// # Arguments
+/// - `address`: The expression giving the address
+/// - `symbols`: A reference to the symbol table
+/// - `line`: The line of the statement
+/// - `column`: The column errors point at
+///
+/// # Returns
+/// - `Result<i64>`: A result containing either:
+///   - `i64`: The address
+///   - [AnalyzerError](crate::error::Error::AnalyzerError): returns an error if the address isn't an integer
+pub(crate) fn address(
+    address: &Expr,
+    symbols: &IndexMap<String, Symbol>,
+    line: usize,
+    column: usize,
+) -> Result<i64> {
+    match evaluate_expression(address, symbols, line, column)? {
+        ast::Lit::Int(value) => i64::try_from(value).map_err(|_| {
+            AnalyzerError(format!("Address `{}` isn't mapped", address), line, column)
+        }),
+        _ => Err(AnalyzerError(format!("Address `{}` isn't an integer", address), line, column)),
+    }
+}
+
+/// Finds what a raw address refers to, checking that it may be read and written
+///
+/// Stack addresses have to be the start of a variable, pointers can't be read or written through their
+/// address. The current frame sits below the frames of its callers, so an address can be in any of
+/// them. Heap addresses have to be an element of an allocated or leaked block
+///
+/// # Arguments
+/// - `address`: The address
+/// - `symbols`: A reference to the symbols of the current frame
+/// - `callers`: The symbols of the frames of the calls being executed, outermost first
+/// - `memory`: A reference to the `MemoryModel` the statements are analyzed in
+/// - `line`: The line of the statement
+/// - `column`: The column errors point at
+///
+/// # Returns
+/// - `Result<Location>`: A result containing either:
+///   - [Location](crate::analyzer::raw_memory::Location): What the address refers to
+///   - [AnalyzerError](crate::error::Error::AnalyzerError): returns an error if the address can't be accessed
+pub(crate) fn resolve_address(
+    address: i64,
+    symbols: &IndexMap<String, Symbol>,
+    callers: &[IndexMap<String, Symbol>],
+    memory: &dyn MemoryModel,
+    line: usize,
+    column: usize,
+) -> Result<Location> {
+    let formatted = format_address(address as usize, memory.pointer_size());
+    let error = |message: String| Err(AnalyzerError(message, line, column));
+
+    let Ok(raw_address) = usize::try_from(address) else {
+        return error(format!("Address {} isn't mapped", formatted));
+    };
+
+    let pointer_size = memory.pointer_size();
+    let frames = callers
+        .iter()
+        .enumerate()
+        .map(|(index, frame)| (Some(index), frame))
+        .chain([(None, symbols)]);
+
+    let mut top = STACK_END_ADDRESS;
+
+    for (caller, frame) in frames {
+        let addresses = stack_addresses(frame.values(), top, pointer_size);
+        top = addresses.last().copied().unwrap_or(top);
+
+        for (symbol, symbol_address) in frame.values().zip(addresses) {
+            let size = symbol_size(symbol);
+
+            if !(symbol_address..symbol_address + size).contains(&raw_address) {
+                continue;
+            }
+
+            return match symbol {
+                Symbol::Variable { name, .. } if raw_address == symbol_address => {
+                    Ok(Location::Variable {
+                        name: name.clone(),
+                        caller,
+                    })
+                }
+
+                Symbol::Variable { name, .. } => {
+                    error(format!("Address {} is in the middle of `{}`", formatted, name))
+                }
+
+                Symbol::Pointer { name, .. } => error(format!(
+                    "Address {} holds pointer `{}`, only values can be peeked or poked",
+                    formatted, name
+                )),
+
+                Symbol::Union { name, .. } => error(format!(
+                    "Address {} holds union `{}`, read or write one of its members instead",
+                    formatted, name
+                )),
+
+                Symbol::Object { name, .. } => error(format!(
+                    "Address {} holds object `{}`, read or write one of its fields instead",
+                    formatted, name
+                )),
+
+                Symbol::Array { name, .. } => error(format!(
+                    "Address {} holds array `{}`, read or write one of its elements instead",
+                    formatted, name
+                )),
+
+                Symbol::Mutex { name, .. } => error(format!(
+                    "Address {} holds mutex `{}`, lock or unlock it instead",
+                    formatted, name
+                )),
+
+                Symbol::Literal { .. } => unreachable!("literals take up no space"),
+            };
+        }
+    }
+
+    let block = raw_address.checked_sub(HEAP_BASE_ADDRESS).and_then(|offset| {
+        memory
+            .heap()
+            .into_iter()
+            .find(|block| (block.pointer..block.pointer + block.size).contains(&offset))
+            .map(|block| (offset - block.pointer, block))
+    });
+
+    let Some((offset, block)) = block else {
+        return error(format!("Address {} isn't mapped", formatted));
+    };
+
+    match block.block_state {
+        HeapBlockState::Allocated | HeapBlockState::Leaked => {}
+        HeapBlockState::Free => return error(format!("Address {} was freed", formatted)),
+        HeapBlockState::Unallocated | HeapBlockState::Padding => {
+            return error(format!("Address {} isn't allocated", formatted));
+        }
+    }
+
+    let string_owner = symbols.values().find_map(|symbol| match symbol {
+        Symbol::Variable {
+            name,
+            string: Some(string),
+            ..
+        } if string.heap_pointer == Some(block.pointer) => Some(name),
+        _ => None,
+    });
+
+    if let Some(name) = string_owner {
+        return error(format!(
+            "Address {} holds the characters of `{}`, only values can be peeked or poked",
+            formatted, name
+        ));
+    }
+
+    // a leaked block keeps the name of the pointer that owned it, which may point somewhere else by now
+    let owner =
+        block.current_pointer_identifier.as_ref().and_then(|owner| match symbols.get(owner) {
+            Some(Symbol::Pointer {
+                ptype,
+                value_size,
+                heap_pointer: Some(heap_pointer),
+                allocation_type: AllocationType::Heap,
+                offset,
+                ..
+            }) if heap_pointer - offset == block.pointer => {
+                Some((owner.clone(), ptype.clone(), *value_size))
+            }
+            _ => None,
+        });
+
+    // the allocator rounds blocks up, so the bytes past what was asked for aren't part of any element
+    match &owner {
+        Some((owner, _, value_size)) if offset >= *value_size => error(format!(
+            "Address {} is past the end of the {} bytes `{}` allocated",
+            formatted, value_size, owner
+        )),
+
+        Some((owner, Type::Class(class_name), _)) => error(format!(
+            "Address {} holds the `{}` object of `{}`, only values can be peeked or poked",
+            formatted, class_name, owner
+        )),
+
+        Some((owner, ptype, _)) if offset % ptype.get_variable_size(memory.pointer_size()) != 0 => {
+            error(format!("Address {} is in the middle of an element of `{}`", formatted, owner))
+        }
+
+        None if offset != 0 => error(format!(
+            "Address {} is in the middle of a block with no pointer to it",
+            formatted
+        )),
+
+        _ => Ok(Location::Block {
+            pointer: block.pointer,
+            owner: owner.map(|(owner, ptype, _)| (owner, ptype)),
+        }),
+    }
+}
+
+/// Reads the value `peek` returns for an address
+///
+/// # Arguments
+/// - `address`: The expression giving the address
+/// - `symbols`: A reference to the symbols of the current frame
+/// - `callers`: The symbols of the frames of the calls being executed, outermost first
+/// - `memory`: A reference to the `MemoryModel` the statements are analyzed in
+/// - `line`: The line of the statement
+/// - `column`: The column errors point at
+///
+/// # Returns
+/// - `Result<ast::Lit>`: A result containing either:
+///   - [Lit](crate::parser::ast::Lit): The value at the address
+///   - [AnalyzerError](crate::error::Error::AnalyzerError): returns an error if the address can't be read
+pub(crate) fn peek(
+    address: &Expr,
+    symbols: &IndexMap<String, Symbol>,
+    callers: &[IndexMap<String, Symbol>],
+    memory: &dyn MemoryModel,
+    line: usize,
+    column: usize,
+) -> Result<ast::Lit> {
+    let raw_address = self::address(address, symbols, line, column)?;
+
+    let (vtype, value) = match resolve_address(raw_address, symbols, callers, memory, line, column)?
+    {
+        Location::Variable { name, caller } => {
+            match caller.map_or(symbols, |caller| &callers[caller]).get(&name) {
+                Some(Symbol::Variable {
+                    vtype,
+                    value: Some(value),
+                    ..
+                }) => (Some(vtype.clone()), value.clone()),
+
+                _ => {
+                    return Err(AnalyzerError(
+                        format!("Variable `{}` not initialized!", name),
+                        line,
+                        column,
+                    ));
+                }
+            }
+        }
+
+        Location::Block { pointer, owner } => (
+            owner.map(|(_, ptype)| ptype),
+            memory.block(pointer).map(|block| block.metadata.clone()).unwrap_or_default(),
+        ),
+    };
+
+    if value.is_empty() {
+        return Err(AnalyzerError(
+            format!(
+                "Address {} holds garbage, nothing was written to it yet",
+                format_address(raw_address as usize, memory.pointer_size())
+            ),
+            line,
+            column,
+        ));
+    }
+
+    match vtype {
+        Some(vtype) => variable_literal(&vtype, &value),
+        None => ast::Lit::from_str(&value).ok(),
+    }
+    .ok_or_else(|| AnalyzerError(format!("Cannot read `{}`", value), line, column))
+}
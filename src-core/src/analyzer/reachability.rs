@@ -0,0 +1,157 @@
+//! Answers who can reach a heap block, so the visualization can explain why a block is still alive
+//! by listing every chain of pointers from the stack that ends at it
+
+use serde::Serialize;
+
+use crate::error::Result;
+
+use super::heap_allocator::{HeapBlock, HeapBlockState};
+use super::helpers::symbol_name;
+use super::{AllocationType, Symbol};
+
+/// Represents a chain of pointers from a symbol on the stack to a heap block, like `pp -> p`
+///
+/// # Fields
+/// - `pointers`: The pointers followed in order, starting at the stack. Fields of objects on the stack
+///   are named like `n.data`, and pointers kept in heap blocks like `h->data`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AccessPath {
+    pub pointers: Vec<String>,
+}
+
+/// Finds every live chain of pointers from the stack to a heap block
+///
+/// Only pointers into allocated blocks are followed, so dangling pointers don't keep a block
+/// reachable. A pointer reaches a block when it points anywhere inside it, and a shared pointer
+/// reaches its control block as well
+///
+/// # Arguments
+/// - `stack`: The symbols on the stack, like the stack of an analysis
+/// - `heap`: The blocks of the heap, like the heap of an analysis
+/// - `block`: The starting position of the block in the heap
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - `Vec<AccessPath>`: The chains of pointers reaching the block, empty if it is unreachable
+///    - [Error](crate::error::Error): An error if no allocated block starts at `block`
+pub fn access_paths(stack: &[Symbol], heap: &[HeapBlock], block: usize) -> Result<Vec<AccessPath>> {
+    if live_block(heap, block).map(|found| found.pointer) != Some(block) {
+        return Err(format!("Allocated heap block at {:#010X} not found!", block).into());
+    }
+
+    let mut paths = Vec::new();
+
+    for symbol in stack {
+        let Some(name) = symbol_name(symbol) else {
+            continue;
+        };
+
+        follow_symbol(symbol, name.to_owned(), &mut Vec::new(), heap, block, &mut paths);
+    }
+
+    Ok(paths)
+}
+
+/// Follows the pointers a symbol holds, a pointer to another pointer or to an object on the stack
+/// leads to the pointers those hold
+fn follow_symbol(
+    symbol: &Symbol,
+    name: String,
+    path: &mut Vec<String>,
+    heap: &[HeapBlock],
+    target: usize,
+    paths: &mut Vec<AccessPath>,
+) {
+    match symbol {
+        Symbol::Pointer {
+            allocation_type: AllocationType::Heap,
+            heap_pointer: Some(heap_pointer),
+            control_block,
+            ..
+        } => {
+            path.push(name);
+
+            for address in std::iter::once(*heap_pointer).chain(*control_block) {
+                follow_address(address, path, &mut Vec::new(), heap, target, paths);
+            }
+
+            path.pop();
+        }
+
+        Symbol::Pointer {
+            allocation_type: AllocationType::Stack,
+            value: Some(pointee),
+            ..
+        } => {
+            let Some(pointee_name) = symbol_name(pointee) else {
+                return;
+            };
+
+            path.push(name);
+            follow_symbol(pointee, pointee_name.to_owned(), path, heap, target, paths);
+            path.pop();
+        }
+
+        Symbol::Object { fields, .. } => {
+            for field in fields {
+                if let Some(pointer) = &field.pointer {
+                    let field_name = format!("{}.{}", name, field.name);
+                    follow_symbol(pointer, field_name, path, heap, target, paths);
+                }
+            }
+        }
+
+        _ => {}
+    }
+}
+
+/// Follows a pointer into the heap, through the pointers kept in the block it points into
+fn follow_address(
+    address: usize,
+    path: &mut Vec<String>,
+    visited: &mut Vec<usize>,
+    heap: &[HeapBlock],
+    target: usize,
+    paths: &mut Vec<AccessPath>,
+) {
+    let Some(block) = live_block(heap, address) else {
+        return;
+    };
+
+    // blocks pointing at each other would otherwise be followed forever
+    if visited.contains(&block.pointer) {
+        return;
+    }
+
+    if block.pointer == target {
+        paths.push(AccessPath {
+            pointers: path.clone(),
+        });
+
+        return;
+    }
+
+    let Some(owner) = path.last().cloned() else {
+        return;
+    };
+
+    for member in block.members.iter().flatten() {
+        let Some(points_to) = member.points_to else {
+            continue;
+        };
+
+        visited.push(block.pointer);
+        path.push(format!("{}->{}", owner, member.name));
+        follow_address(points_to, path, visited, heap, target, paths);
+        path.pop();
+        visited.pop();
+    }
+}
+
+/// Gets the allocated block an address points into
+fn live_block(heap: &[HeapBlock], address: usize) -> Option<&HeapBlock> {
+    heap.iter().find(|block| {
+        matches!(block.block_state, HeapBlockState::Allocated | HeapBlockState::Leaked)
+            && (block.pointer..block.pointer + block.size).contains(&address)
+    })
+}
@@ -0,0 +1,117 @@
+//! What an analysis responds with, the memory the program ended with and what it was built from, or
+//! what went wrong. The WASM build and the desktop app both serialize these, so the frontend gets the
+//! same shapes from either
+
+use serde::Serialize;
+
+use crate::diagnostics::Diagnostic;
+use crate::error::Error;
+
+use super::address_space::Segment;
+use super::cross_reference::{CrossReference, IgnoredStatement};
+use super::frame::{ScopeExit, StackFrame};
+use super::heap_allocator::{FreeRange, HeapBlock};
+use super::heatmap::AccessCount;
+use super::memory_model::MappedRegion;
+use super::overview::HeapOverview;
+use super::Symbol;
+
+/// Represents the result of an analysis, as the WASM build and the desktop app both respond with it
+///
+/// # Fields
+/// - `stack`: The symbols on the stack once the program ended
+/// - `heap`: The blocks of the heap once the program ended
+/// - `frames`: The frames of every function call, in the order the calls were made
+/// - `scope_exits`: The blocks that ended and the locals they popped, in the order they ended
+/// - `heap_capacity`: The size of the heap in bytes once the program ended, including the bytes no block
+///   holds
+/// - `free_list`: The free list of the allocator once the program ended, in the order the allocator looks
+///   through it, which doesn't run parallel to the heap since a range can span several blocks
+/// - `mappings`: The regions mapped with `mmap` and not unmapped with `munmap`, in the order they were
+///   mapped, which aren't part of the heap
+/// - `heatmap`: How many times each heap block was read and written since it was allocated, parallel to
+///   the heap, no reads and writes for blocks no statement allocated
+/// - `heap_overview`: The heap summarized for an overview, `None` unless the heap has
+///   [OVERVIEW_THRESHOLD](crate::analyzer::OVERVIEW_THRESHOLD) blocks or more
+/// - `address_space`: Where the text, data, BSS, heap, mapping and stack segments are in the address
+///   space once the program ended, from the lowest address to the highest
+/// - `stack_addresses`: The address of each stack symbol, parallel to the stack, the first symbol at the
+///   top of the stack segment and every symbol after it below the one before
+/// - `ignored`: Every statement the parser skipped, in the order they are in the source, whether the
+///   program reached them or not
+/// - `warnings`: Every write out of the bounds of an array that
+///   [permissive](crate::analyzer::Strictness::Permissive) mode skipped, every cast to an address its
+///   type isn't aligned to, every `double` literal narrowed to a `float`, every `memcpy` between overlapping ranges and every `strcpy` past the end
+///   of a heap block, in the order they ran. A warning raised again, like in a loop, is reported once
+///   and the warnings past the options' `diagnostic_limit` are summarized
+/// - `cross_reference`: The statements behind the symbols, blocks and frames above
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct AnalysisResult {
+    pub stack: Vec<Symbol>,
+    pub heap: Vec<HeapBlock>,
+    pub frames: Vec<StackFrame>,
+    pub scope_exits: Vec<ScopeExit>,
+    pub heap_capacity: usize,
+    pub free_list: Vec<FreeRange>,
+    pub mappings: Vec<MappedRegion>,
+    pub heatmap: Vec<AccessCount>,
+    pub heap_overview: Option<HeapOverview>,
+    pub address_space: Vec<Segment>,
+    pub stack_addresses: Vec<usize>,
+    pub ignored: Vec<IgnoredStatement>,
+    pub warnings: Vec<Diagnostic>,
+    pub cross_reference: CrossReference,
+}
+
+/// Represents the response of an analysis that failed
+///
+/// # Fields
+/// - `error`: What went wrong
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct AnalysisError {
+    pub error: AnalysisErrorDetails,
+}
+
+/// Represents what went wrong in an analysis
+///
+/// # Fields
+/// - `message`: The error
+/// - `line_number`: The line the error points at, left out for errors that aren't tied to the source
+/// - `column_number`: The column the error points at, left out for errors that aren't tied to the
+///   source
+/// - `internal`: Set when the analyzer's self-check caught an inconsistent layout
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct AnalysisErrorDetails {
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_number: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column_number: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub internal: Option<bool>,
+}
+
+impl From<&Error> for AnalysisError {
+    fn from(error: &Error) -> Self {
+        let (line_number, column_number, internal) = match error {
+            Error::AnalyzerError(_, line, column) | Error::ParserError(_, line, column) => {
+                (Some(*line), Some(*column), None)
+            }
+            // internal errors have no column, the whole line is marked
+            Error::InternalError(_, line) => (Some(*line), Some(1), Some(true)),
+            Error::Msg(_) => (None, None, None),
+        };
+
+        AnalysisError {
+            error: AnalysisErrorDetails {
+                message: error.to_string(),
+                line_number,
+                column_number,
+                internal,
+            },
+        }
+    }
+}
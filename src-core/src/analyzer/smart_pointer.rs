@@ -0,0 +1,264 @@
+//! `std::unique_ptr` and `std::shared_ptr`, which free the block they own by themselves
+//!
+//! A `std::shared_ptr` counts the pointers owning its block in a control block allocated apart from it,
+//! like one constructed from a raw pointer does: a strong count and a weak count, 4 bytes each. Copying
+//! the pointer adds an owner, and the block is freed along with its control block once its last owner
+//! lets go of it
+
+use indexmap::IndexMap;
+
+use crate::{
+    error::{Error::AnalyzerError, Result},
+    parser::ast::{self, Expr, Statement},
+};
+
+use super::heap_allocator::BlockMember;
+use super::memory_model::MemoryModel;
+use super::Symbol;
+
+/// The size of the control block of a `std::shared_ptr`, its strong and weak counts
+pub(crate) const CONTROL_BLOCK_SIZE: usize = 8;
+
+/// Rewrites a statement using a smart pointer like a raw pointer or an object into the smart pointer
+/// statement it stands for
+///
+/// `p.reset();` lets go of the block like assigning `nullptr` does, and assigning `nullptr`, another
+/// pointer or `std::move(q)` to a `std::shared_ptr` changes how many pointers own their blocks
+///
+/// # Arguments
+/// - `statement`: The statement
+/// - `symbols`: A reference to the symbol table
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - [Statement](crate::parser::ast::Statement): The smart pointer statement, or the statement
+///      itself if it doesn't use a smart pointer
+///    - [Error](crate::error::Error): An error if `reset` is passed a raw pointer
+pub(crate) fn smart_pointer_statement(
+    statement: Statement,
+    symbols: &IndexMap<String, Symbol>,
+) -> Result<Statement> {
+    let unique =
+        |name: &str| matches!(symbols.get(name), Some(Symbol::Pointer { unique: true, .. }));
+    let shared =
+        |name: &str| matches!(symbols.get(name), Some(Symbol::Pointer { shared: true, .. }));
+
+    Ok(match statement {
+        Statement::MethodCall {
+            var_name,
+            method,
+            arguments,
+            line,
+            var_ident_column,
+            method_column,
+        } if method == "reset" && (unique(&var_name) || shared(&var_name)) => {
+            if !arguments.is_empty() {
+                return Err(AnalyzerError(
+                    format!(
+                        "Expected `{}.reset()`, a `{}` can't be reset to a raw pointer",
+                        var_name,
+                        if shared(&var_name) { "std::shared_ptr" } else { "std::unique_ptr" }
+                    ),
+                    line,
+                    method_column,
+                ));
+            }
+
+            if shared(&var_name) {
+                Statement::SharedPointerAssignment {
+                    pointer_name: var_name,
+                    value: ast::SharedValue::Null,
+                    line,
+                    pointer_ident_column: var_ident_column,
+                    value_column: method_column,
+                }
+            } else {
+                Statement::UniquePointerAssignment {
+                    pointer_name: var_name,
+                    value: ast::UniqueValue::Null,
+                    line,
+                    pointer_ident_column: var_ident_column,
+                    value_column: method_column,
+                }
+            }
+        }
+
+        Statement::PointerAssignmentNull {
+            pointer_name,
+            line,
+            pointer_ident_column,
+        } if shared(&pointer_name) => Statement::SharedPointerAssignment {
+            pointer_name,
+            value: ast::SharedValue::Null,
+            line,
+            pointer_ident_column,
+            value_column: pointer_ident_column,
+        },
+
+        Statement::VariableAssignment {
+            var_name,
+            new_value,
+            line,
+            var_ident_column,
+            assignment_column,
+        } if shared(&var_name) => match *new_value {
+            Expr::Ident(source) => Statement::SharedPointerAssignment {
+                pointer_name: var_name,
+                value: ast::SharedValue::Copy { source },
+                line,
+                pointer_ident_column: var_ident_column,
+                value_column: assignment_column,
+            },
+            new_value => Statement::VariableAssignment {
+                var_name,
+                new_value: Box::new(new_value),
+                line,
+                var_ident_column,
+                assignment_column,
+            },
+        },
+
+        // the parser reads `p = std::move(q);` as a `std::unique_ptr` assignment, it can't tell
+        // which kind of pointer `p` is
+        Statement::UniquePointerAssignment {
+            pointer_name,
+            value: ast::UniqueValue::Move { source },
+            line,
+            pointer_ident_column,
+            value_column,
+        } if shared(&pointer_name) => Statement::SharedPointerAssignment {
+            pointer_name,
+            value: ast::SharedValue::Move { source },
+            line,
+            pointer_ident_column,
+            value_column,
+        },
+
+        statement => statement,
+    })
+}
+
+/// Allocates the control block of a `std::shared_ptr`'s block, with the pointer as its only owner
+///
+/// # Arguments
+/// - `memory`: A mutable reference to the memory model
+/// - `pointer_name`: The pointer owning the block
+/// - `starting_pointers`: The starting pointers of previous runs, so the control block stays where it was
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - `usize`: The starting position of the control block in the heap
+///    - [Error](crate::error::Error): An error if there is insufficient memory
+pub(crate) fn allocate_control_block(
+    memory: &mut dyn MemoryModel,
+    pointer_name: &str,
+    starting_pointers: &mut IndexMap<String, usize>,
+) -> Result<usize> {
+    // remembered apart from the block itself, which is placed under the pointer's name
+    let control_block = memory.allocate(
+        &format!("{} (control block)", pointer_name),
+        CONTROL_BLOCK_SIZE,
+        starting_pointers,
+    )?;
+
+    memory.update_owner(control_block, pointer_name.to_owned())?;
+    memory.update_metadata(control_block, "control block".to_owned())?;
+    set_strong_count(memory, control_block, 1)?;
+
+    Ok(control_block)
+}
+
+/// Counts one more `std::shared_ptr` owning the block a control block counts the owners of
+pub(crate) fn retain(memory: &mut dyn MemoryModel, control_block: usize) -> Result<()> {
+    let count = strong_count(memory, control_block);
+
+    set_strong_count(memory, control_block, count + 1)
+}
+
+/// Lets go of the block a `std::shared_ptr` owns, freeing it along with its control block if the pointer
+/// was its last owner
+///
+/// Otherwise both blocks are shown under one of the pointers still owning them
+///
+/// # Arguments
+/// - `memory`: A mutable reference to the memory model
+/// - `pointer_name`: The pointer letting go of the block
+/// - `heap_pointer`: The starting position of the block
+/// - `value_size`: The size of the block in bytes
+/// - `control_block`: The starting position of the block's control block
+/// - `symbols`: The symbols that may still own the block
+///
+/// # Returns
+/// - `Result<()>`: An error if the memory model couldn't be updated
+pub(crate) fn release_shared(
+    memory: &mut dyn MemoryModel,
+    pointer_name: &str,
+    heap_pointer: usize,
+    value_size: usize,
+    control_block: usize,
+    symbols: &IndexMap<String, Symbol>,
+) -> Result<()> {
+    let count = strong_count(memory, control_block).saturating_sub(1);
+
+    if count == 0 {
+        memory.free(heap_pointer, value_size);
+        memory.free(control_block, CONTROL_BLOCK_SIZE);
+
+        return Ok(());
+    }
+
+    set_strong_count(memory, control_block, count)?;
+
+    let owner = symbols.iter().find_map(|(name, symbol)| match symbol {
+        Symbol::Pointer {
+            control_block: Some(other),
+            ..
+        } if *other == control_block && name != pointer_name => Some(name.clone()),
+        _ => None,
+    });
+
+    if let Some(owner) = owner {
+        memory.update_owner(heap_pointer, owner.clone())?;
+        memory.update_owner(control_block, owner)?;
+    }
+
+    Ok(())
+}
+
+/// Gets how many `std::shared_ptr`s own the block a control block counts the owners of
+fn strong_count(memory: &dyn MemoryModel, control_block: usize) -> usize {
+    memory
+        .block(control_block)
+        .and_then(|block| block.members.as_ref())
+        .and_then(|members| members.first())
+        .and_then(|strong| strong.value.as_ref())
+        .and_then(|count| count.parse().ok())
+        .unwrap_or(0)
+}
+
+fn set_strong_count(
+    memory: &mut dyn MemoryModel,
+    control_block: usize,
+    count: usize,
+) -> Result<()> {
+    memory.update_members(
+        control_block,
+        vec![
+            BlockMember {
+                name: "strong".to_owned(),
+                offset: 0,
+                size: 4,
+                value: Some(count.to_string()),
+                points_to: None,
+            },
+            // nothing supports `std::weak_ptr`, so no pointer ever observes the block without owning it
+            BlockMember {
+                name: "weak".to_owned(),
+                offset: 4,
+                size: 4,
+                value: Some("0".to_owned()),
+                points_to: None,
+            },
+        ],
+    )
+}
@@ -0,0 +1,129 @@
+//! `std::string` variables, which keep short strings inside themselves and longer ones in a buffer on the
+//! heap
+//!
+//! Strings follow libstdc++: the variable holds a pointer to its characters and their length, followed
+//! by a 16 byte buffer the characters of a short string are kept in, or the capacity of the heap buffer
+//! once they no longer fit. Up to 15 characters and the null character ending them fit in the variable
+//! itself, longer strings get a heap buffer that grows to at least double its capacity, and a string
+//! never moves back into itself once it has a heap buffer
+
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use crate::error::{Error::AnalyzerError, Result};
+use crate::parser::ast;
+
+use super::memory_model::MemoryModel;
+
+/// How many characters fit in the variable itself, without a buffer on the heap
+pub(crate) const SMALL_STRING_CAPACITY: usize = 15;
+
+/// Represents where the characters of a `std::string` are kept
+///
+/// # Fields
+/// - `heap_pointer`: The starting position of the heap buffer, `None` while the characters fit in the
+///   variable itself
+/// - `length`: The number of characters in the string
+/// - `capacity`: How many characters fit in the buffer, not counting the null character ending them
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct StringBuffer {
+    pub heap_pointer: Option<usize>,
+    pub length: usize,
+    pub capacity: usize,
+}
+
+impl Default for StringBuffer {
+    fn default() -> Self {
+        StringBuffer {
+            heap_pointer: None,
+            length: 0,
+            capacity: SMALL_STRING_CAPACITY,
+        }
+    }
+}
+
+impl StringBuffer {
+    /// Gets the size of the heap buffer in bytes, with room for the null character ending the string
+    pub(crate) fn heap_size(&self) -> usize {
+        self.capacity + 1
+    }
+}
+
+/// Reads the characters out of the value of a `std::string` variable
+///
+/// # Arguments
+/// - `value`: The value as it is stored in the symbol table, a quoted string literal
+/// - `var_name`: The name of the variable the value is assigned to
+/// - `line`: The line of the assignment
+/// - `column`: The column errors point at
+///
+/// # Returns
+/// - `Result<String>`: A result containing either:
+///   - `String`: The characters of the string
+///   - [AnalyzerError](crate::error::Error::AnalyzerError): returns an error if the value isn't a string
+pub(crate) fn string_contents(
+    value: &str,
+    var_name: &str,
+    line: usize,
+    column: usize,
+) -> Result<String> {
+    match ast::Lit::from_str(value) {
+        Ok(ast::Lit::Str(contents)) => Ok(contents),
+        _ => Err(AnalyzerError(
+            format!("Cannot assign `{}` to variable `{}` (incorrect type)", value, var_name),
+            line,
+            column,
+        )),
+    }
+}
+
+/// Stores new characters in a `std::string`, moving them to a bigger heap buffer if they don't fit in
+/// the current one
+///
+/// # Arguments
+/// - `var_name`: The name of the string, it owns its heap buffer
+/// - `buffer`: The buffer of the string
+/// - `value`: The new value, a quoted string literal
+/// - `memory`: A mutable reference to the `MemoryModel` the statements are analyzed in
+/// - `starting_pointers`: The starting pointers of previous runs, so buffers stay where they were
+///
+/// # Returns
+/// - `Result<()>`: A result containing either:
+///   - `Ok(())` if the characters were stored
+///   - [Error](crate::error::Error): An error if there is insufficient memory for the buffer
+pub(crate) fn assign_string(
+    var_name: &str,
+    buffer: &mut StringBuffer,
+    value: &str,
+    memory: &mut dyn MemoryModel,
+    starting_pointers: &mut IndexMap<String, usize>,
+) -> Result<()> {
+    let length = ast::Lit::from_str(value)
+        .map(|lit| match lit {
+            ast::Lit::Str(contents) => contents.len(),
+            _ => 0,
+        })
+        .unwrap_or_default();
+
+    if length > buffer.capacity {
+        let old_buffer = buffer.clone();
+
+        buffer.capacity = length.max(2 * old_buffer.capacity);
+        buffer.heap_pointer =
+            Some(memory.allocate(var_name, buffer.heap_size(), starting_pointers)?);
+
+        // the characters are copied over before the old buffer is given back
+        if let Some(heap_pointer) = old_buffer.heap_pointer {
+            memory.free(heap_pointer, old_buffer.heap_size());
+        }
+    }
+
+    buffer.length = length;
+
+    if let Some(heap_pointer) = buffer.heap_pointer {
+        memory.update_metadata(heap_pointer, value.to_owned())?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+//! Tags lesson materials put on symbols and heap blocks, so the same memory is highlighted the same way
+//! every time a lesson is shown
+//!
+//! A tag is either given in the [AnalyzerOptions](crate::analyzer::AnalyzerOptions) for the symbols with a
+//! given name, or written in the source as a `// @tag:name` or `// @tag:name:color` comment. A comment on
+//! the same line as a statement tags what it declares or allocates, a comment on a line of its own tags
+//! the next line with code on it
+
+use indexmap::IndexMap;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::cross_reference::CrossReference;
+use super::heap_allocator::HeapBlock;
+use super::options::AnalyzerOptions;
+use super::Symbol;
+
+lazy_static! {
+    static ref TAG_REGEX: Regex = Regex::new(r#"//\s*@tag:([\w-]+)(?::(\S+))?"#).unwrap();
+}
+
+/// Represents a tag put on a symbol or a heap block
+///
+/// # Fields
+/// - `name`: The name of the tag, like the lesson it belongs to
+/// - `color`: The color to highlight the tagged memory with, any CSS color, `None` leaves it to the frontend
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct MemoryTag {
+    pub name: String,
+    pub color: Option<String>,
+}
+
+/// Reads the `// @tag:` comments in the source code
+///
+/// # Arguments
+/// - `source`: The source code as written by the user
+///
+/// # Returns
+/// - `IndexMap<usize, MemoryTag>`: The tag of each line that has one, by line number
+pub fn read_tag_annotations(source: &str) -> IndexMap<usize, MemoryTag> {
+    let mut tags = IndexMap::new();
+    let mut pending = None;
+
+    for (index, line) in source.lines().enumerate() {
+        let tag = TAG_REGEX.captures(line).map(|captures| MemoryTag {
+            name: captures[1].to_owned(),
+            color: captures.get(2).map(|color| color.as_str().to_owned()),
+        });
+
+        let code = line.split("//").next().unwrap_or_default();
+
+        if code.trim().is_empty() {
+            pending = tag.or(pending);
+            continue;
+        }
+
+        if let Some(tag) = tag.or_else(|| pending.take()) {
+            tags.insert(index + 1, tag);
+        }
+    }
+
+    tags
+}
+
+/// Finds the tag of every stack symbol and heap block
+///
+/// A symbol is tagged by its name first and then by the line that declared it, a block by the line that
+/// allocated it first and then by the name of the pointer owning it
+///
+/// # Arguments
+/// - `cross_reference`: The cross reference of the output, the tags are added to it
+/// - `stack`: The stack symbols, in the same order as the cross reference
+/// - `heap`: The heap blocks, in the same order as the cross reference
+/// - `options`: The options with the tags to look up
+pub(crate) fn tag_memory(
+    cross_reference: &mut CrossReference,
+    stack: &[Symbol],
+    heap: &[HeapBlock],
+    options: &AnalyzerOptions,
+) {
+    if options.tags.is_empty() && options.line_tags.is_empty() {
+        return;
+    }
+
+    let name_tag = |name: Option<&String>| name.and_then(|name| options.tags.get(name)).cloned();
+    let line_tag =
+        |line: Option<usize>| line.and_then(|line| options.line_tags.get(&line)).cloned();
+
+    cross_reference.stack_tags = stack
+        .iter()
+        .zip(&cross_reference.stack)
+        .map(|(symbol, source)| {
+            let name = match symbol {
+                Symbol::Variable { name, .. }
+                | Symbol::Pointer { name, .. }
+                | Symbol::Union { name, .. }
+                | Symbol::Object { name, .. }
+                | Symbol::Array { name, .. }
+                | Symbol::Mutex { name, .. } => Some(name),
+                Symbol::Literal { .. } => None,
+            };
+
+            name_tag(name).or_else(|| line_tag(Some(source.line)))
+        })
+        .collect();
+
+    cross_reference.heap_tags = heap
+        .iter()
+        .zip(&cross_reference.heap)
+        .map(|(block, source)| {
+            line_tag(source.as_ref().map(|source| source.line))
+                .or_else(|| name_tag(block.current_pointer_identifier.as_ref()))
+        })
+        .collect();
+}
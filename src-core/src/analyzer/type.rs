@@ -2,19 +2,36 @@
 //! We use this instead of the [TokenKind](crate::lexer::token::TokenKind) enum to make the code more readable and easier to work with when checking for types
 
 use serde::Serialize;
+use std::fmt;
 
 use crate::error::Result;
 use crate::lexer::token::TokenKind;
 use crate::parser::ast;
 
+use super::string::SMALL_STRING_CAPACITY;
+
 /// Represents the different types that are supported by the language
-#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+///
+/// `Class` is the type of an object of the class or struct with the given name, which pointers to
+/// objects point to
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
 pub enum Type {
     Integer,
+    Short,
+    UnsignedShort,
+    UnsignedInt,
+    Long,
+    UnsignedLong,
+    LongLong,
+    UnsignedLongLong,
     Float,
     Char,
     Double,
     Bool,
+    String,
+    Void,
+    Class(String),
 }
 
 impl Type {
@@ -30,10 +47,19 @@ impl Type {
     pub(crate) fn from_token(token_type: TokenKind) -> Result<Type> {
         match token_type {
             TokenKind::KwInt => Ok(Type::Integer),
+            TokenKind::KwShort => Ok(Type::Short),
+            TokenKind::KwUnsignedShort => Ok(Type::UnsignedShort),
+            TokenKind::KwUnsignedInt => Ok(Type::UnsignedInt),
+            TokenKind::KwLong => Ok(Type::Long),
+            TokenKind::KwUnsignedLong => Ok(Type::UnsignedLong),
+            TokenKind::KwLongLong => Ok(Type::LongLong),
+            TokenKind::KwUnsignedLongLong => Ok(Type::UnsignedLongLong),
             TokenKind::KwFloat => Ok(Type::Float),
             TokenKind::KwChar => Ok(Type::Char),
             TokenKind::KwDouble => Ok(Type::Double),
             TokenKind::KwBool => Ok(Type::Bool),
+            TokenKind::KwString => Ok(Type::String),
+            TokenKind::KwVoid => Ok(Type::Void),
             _ => Err("Invalid Type".into()),
         }
     }
@@ -48,10 +74,19 @@ impl Type {
     pub(crate) fn is_type(&self, value: TokenKind) -> bool {
         match value {
             TokenKind::KwInt => self == &Type::Integer,
+            TokenKind::KwShort => self == &Type::Short,
+            TokenKind::KwUnsignedShort => self == &Type::UnsignedShort,
+            TokenKind::KwUnsignedInt => self == &Type::UnsignedInt,
+            TokenKind::KwLong => self == &Type::Long,
+            TokenKind::KwUnsignedLong => self == &Type::UnsignedLong,
+            TokenKind::KwLongLong => self == &Type::LongLong,
+            TokenKind::KwUnsignedLongLong => self == &Type::UnsignedLongLong,
             TokenKind::KwFloat => self == &Type::Float,
             TokenKind::KwChar => self == &Type::Char,
             TokenKind::KwDouble => self == &Type::Double,
             TokenKind::KwBool => self == &Type::Bool,
+            TokenKind::KwString => self == &Type::String,
+            TokenKind::KwVoid => self == &Type::Void,
             _ => false,
         }
     }
@@ -65,10 +100,13 @@ impl Type {
     /// - `bool`: `true` if the types match, `false` otherwise
     pub(crate) fn is_correct_literal(&self, value: &ast::Lit) -> bool {
         match value {
-            ast::Lit::Int(_) => self == &Type::Integer,
+            ast::Lit::Int(_) => self.is_integer(),
             ast::Lit::Bool(_) => self == &Type::Bool,
-            ast::Lit::Float(_) => self == &Type::Float || self == &Type::Double,
+            ast::Lit::Float(_) | ast::Lit::Float32(_) => {
+                self == &Type::Float || self == &Type::Double
+            }
             ast::Lit::Char(_) => self == &Type::Char,
+            ast::Lit::Str(_) => self == &Type::String,
         }
     }
 
@@ -79,25 +117,139 @@ impl Type {
     pub(crate) fn get_size(&self) -> usize {
         match self {
             Type::Integer => 4,
+            Type::Short | Type::UnsignedShort => 2,
+            Type::UnsignedInt => 4,
+            Type::LongLong | Type::UnsignedLongLong => 8,
             Type::Float => 4,
             Type::Char => 1,
             Type::Double => 8,
             Type::Bool => 1,
+            Type::Long | Type::UnsignedLong => {
+                unreachable!("the size of a `long` depends on the size of a pointer")
+            }
+            Type::String => {
+                unreachable!("the size of a `std::string` depends on the size of a pointer")
+            }
+            Type::Void => unreachable!("a `void*` doesn't know the size of what it points to"),
+            Type::Class(_) => unreachable!("the size of an object depends on its class"),
+        }
+    }
+
+    /// Gets the size of a variable of the type in bytes, a `long` is as wide as a pointer in both data
+    /// models and a `std::string` is laid out like libstdc++'s, a pointer to its characters and their
+    /// length followed by 16 bytes holding either the characters of a short string or the capacity of
+    /// the heap buffer, 32 bytes in LP64 and 24 in ILP32
+    ///
+    /// # Arguments
+    /// - `pointer_size`: The size of a pointer in bytes
+    ///
+    /// # Returns
+    /// - `usize`: The size of the variable in bytes
+    pub(crate) fn get_variable_size(&self, pointer_size: usize) -> usize {
+        match self {
+            Type::Long | Type::UnsignedLong => pointer_size,
+            Type::String => 2 * pointer_size + SMALL_STRING_CAPACITY + 1,
+            _ => self.get_size(),
         }
     }
 
+    /// Checks if the type is one of the integer types
+    pub(crate) fn is_integer(&self) -> bool {
+        matches!(
+            self,
+            Type::Integer
+                | Type::Short
+                | Type::UnsignedShort
+                | Type::UnsignedInt
+                | Type::Long
+                | Type::UnsignedLong
+                | Type::LongLong
+                | Type::UnsignedLongLong
+        )
+    }
+
+    /// Checks if what a pointer to the type points to is a single value it can read and write, which
+    /// what a `void*` points to and objects aren't
+    pub(crate) fn is_value(&self) -> bool {
+        !matches!(self, Type::Void | Type::Class(_))
+    }
+
+    /// Gets the smallest and largest values an integer type can hold
+    ///
+    /// # Arguments
+    /// - `pointer_size`: The size of a pointer in bytes, which `long` is as wide as
+    ///
+    /// # Returns
+    /// - `Option<(i128, i128)>`: The range of the type, `None` if it isn't an integer type
+    pub(crate) fn integer_range(&self, pointer_size: usize) -> Option<(i128, i128)> {
+        if !self.is_integer() {
+            return None;
+        }
+
+        let bits = 8 * self.get_variable_size(pointer_size) as u32;
+
+        Some(match self {
+            Type::UnsignedShort
+            | Type::UnsignedInt
+            | Type::UnsignedLong
+            | Type::UnsignedLongLong => (0, (1 << bits) - 1),
+            _ => (-(1 << (bits - 1)), (1 << (bits - 1)) - 1),
+        })
+    }
+
     /// Gets the default value for the type
     /// This is used when we declare a heap pointer and we need to initialize it with a default value
     ///
     /// # Returns
     /// - `String`: The default value for the type
     pub(crate) fn get_garbage_value(&self) -> String {
+        self.get_zero_value()
+    }
+
+    /// Gets the zero value for the type, which is what `calloc` fills a block with
+    ///
+    /// # Returns
+    /// - `String`: The zero value for the type
+    pub(crate) fn get_zero_value(&self) -> String {
         match self {
             Type::Integer => "0".to_owned(),
+            Type::Short
+            | Type::UnsignedShort
+            | Type::UnsignedInt
+            | Type::Long
+            | Type::UnsignedLong
+            | Type::LongLong
+            | Type::UnsignedLongLong => "0".to_owned(),
             Type::Float => "0.0".to_owned(),
             Type::Char => "'\\0'".to_owned(),
             Type::Double => "0.0".to_owned(),
             Type::Bool => "false".to_owned(),
+            Type::String => "\"\"".to_owned(),
+            // the bytes a `void*` points to
+            Type::Void => "0".to_owned(),
+            Type::Class(_) => "{}".to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Integer => write!(f, "int"),
+            Type::Short => write!(f, "short"),
+            Type::UnsignedShort => write!(f, "unsigned short"),
+            Type::UnsignedInt => write!(f, "unsigned int"),
+            Type::Long => write!(f, "long"),
+            Type::UnsignedLong => write!(f, "unsigned long"),
+            Type::LongLong => write!(f, "long long"),
+            Type::UnsignedLongLong => write!(f, "unsigned long long"),
+            Type::Float => write!(f, "float"),
+            Type::Char => write!(f, "char"),
+            Type::Double => write!(f, "double"),
+            Type::Bool => write!(f, "bool"),
+            Type::String => write!(f, "std::string"),
+            Type::Void => write!(f, "void"),
+            Type::Class(name) => write!(f, "{}", name),
         }
     }
 }
@@ -0,0 +1,107 @@
+//! Unions, whose members all share the same bytes, so writing one member changes what the others read
+
+use serde::Serialize;
+
+use crate::parser::ast;
+
+use super::helpers::variable_literal;
+use super::r#type::Type;
+
+/// Represents a member of a union as it reads the bytes of the union
+///
+/// # Fields
+/// - `name`: The name of the member
+/// - `mtype`: The type of the member
+/// - `value`: The value the bytes of the union hold when read as the member, `None` until a member is
+///   written
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct UnionMember {
+    pub name: String,
+    pub mtype: Type,
+    pub value: Option<String>,
+}
+
+/// Gets the size of a union, which is the size of its largest member
+///
+/// Members are all scalars, so the largest member is also the most aligned one and the union needs no
+/// padding after it
+///
+/// # Arguments
+/// - `members`: The members of the union
+/// - `pointer_size`: The size of a pointer in bytes, which `long` is as wide as
+///
+/// # Returns
+/// - `usize`: The size of the union in bytes
+pub(crate) fn union_size(members: &[UnionMember], pointer_size: usize) -> usize {
+    members.iter().map(|member| member.mtype.get_variable_size(pointer_size)).max().unwrap_or(0)
+}
+
+/// Writes a value through a member of a union, then reads every member back from the bytes
+///
+/// Bytes are little-endian like on x86, and the bytes past the end of the written member keep what they
+/// held before, which is zero until a larger member writes them
+///
+/// # Arguments
+/// - `members`: The members of the union
+/// - `bytes`: The bytes of the union
+/// - `member`: The index of the member being written
+/// - `value`: The value being written, already checked to suit the type of the member
+/// - `pointer_size`: The size of a pointer in bytes, which `long` is as wide as
+///
+/// # Returns
+/// - `Option<()>`: `None` if the value can't be read as the type of the member
+pub(crate) fn write_member(
+    members: &mut [UnionMember],
+    bytes: &mut [u8],
+    member: usize,
+    value: &str,
+    pointer_size: usize,
+) -> Option<()> {
+    let mtype = members[member].mtype.clone();
+    let size = mtype.get_variable_size(pointer_size);
+
+    let encoded: Vec<u8> = match variable_literal(&mtype, value)? {
+        ast::Lit::Int(value) => value.to_le_bytes()[..size].to_vec(),
+        ast::Lit::Float(value) if mtype == Type::Float => (value as f32).to_le_bytes().to_vec(),
+        ast::Lit::Float(value) => value.to_le_bytes().to_vec(),
+        ast::Lit::Float32(value) if mtype == Type::Float => value.to_le_bytes().to_vec(),
+        ast::Lit::Float32(value) => (value as f64).to_le_bytes().to_vec(),
+        ast::Lit::Char(value) => vec![value as u32 as u8],
+        ast::Lit::Bool(value) => vec![value as u8],
+        ast::Lit::Str(_) => return None,
+    };
+
+    bytes[..size].copy_from_slice(&encoded);
+
+    for member in members.iter_mut() {
+        member.value = Some(read_member(bytes, &member.mtype, pointer_size));
+    }
+
+    Some(())
+}
+
+/// Reads the bytes of a union as a member of the given type
+fn read_member(bytes: &[u8], mtype: &Type, pointer_size: usize) -> String {
+    let size = mtype.get_variable_size(pointer_size);
+
+    let mut raw = [0; 8];
+    raw[..size].copy_from_slice(&bytes[..size]);
+    let raw = u64::from_le_bytes(raw);
+
+    match mtype {
+        Type::Float => f32::from_bits(raw as u32).to_string(),
+        Type::Double => f64::from_bits(raw).to_string(),
+        Type::Char => (raw as u8 as char).to_string(),
+        // any byte other than zero reads as `true`
+        Type::Bool => (raw != 0).to_string(),
+        _ => match mtype.integer_range(pointer_size) {
+            // signed integers extend their sign bit into the bytes they don't have
+            Some((min, _)) if min < 0 => {
+                let unused_bits = 64 - 8 * size as u32;
+                (((raw << unused_bits) as i64) >> unused_bits).to_string()
+            }
+            _ => raw.to_string(),
+        },
+    }
+}
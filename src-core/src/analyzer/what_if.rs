@@ -0,0 +1,145 @@
+//! Answers what would happen if a block were allocated now, without allocating it, so the visualization
+//! can compare where different allocation strategies would place the same block
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+
+use super::heap_allocator::FreeRange;
+use super::options::AnalyzerOptions;
+use super::random_heap_allocator::release_range;
+
+/// Represents a way an allocator can pick the free range a block is placed in
+///
+/// - `Random`: The allocator of the analyzer, which takes the first range the block fits in and places it
+///   at a random position within it
+/// - `FirstFit`: The first range the block fits in, at its start
+/// - `BestFit`: The smallest range the block fits in, at its start
+/// - `WorstFit`: The largest range the block fits in, at its start
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum PlacementStrategy {
+    Random,
+    FirstFit,
+    BestFit,
+    WorstFit,
+}
+
+/// Represents where a strategy would place a block
+///
+/// # Fields
+/// - `strategy`: The strategy placing the block
+/// - `range`: The free range the block would be placed in, `None` if it fits in none of them
+/// - `pointer`: The starting position the block would get in the heap, `None` if it doesn't fit or the
+///   strategy places it at a random position
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Placement {
+    pub strategy: PlacementStrategy,
+    pub range: Option<FreeRange>,
+    pub pointer: Option<usize>,
+}
+
+/// Represents the answer to whether an allocation would succeed
+///
+/// # Fields
+/// - `size`: The size requested in bytes
+/// - `reserved_size`: The size the allocator would reserve, rounded up to its alignment and minimum block size
+/// - `fits`: Whether a free range can hold the block, the heap grows to make room when none can
+/// - `placements`: Where each strategy would place the block
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AllocationQuery {
+    pub size: usize,
+    pub reserved_size: usize,
+    pub fits: bool,
+    pub placements: Vec<Placement>,
+}
+
+/// Works out where an allocation of `size` bytes would go, without touching the heap
+///
+/// # Arguments
+/// - `free_list`: The free list of the allocator, like the
+///   [free_list](crate::analyzer::CrossReference::free_list) of an analysis, with the ranges next to each
+///   other merged before the block is placed
+/// - `size`: The size of the block in bytes
+/// - `options`: The options the free list was analyzed with, which decide the alignment of blocks
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - [AllocationQuery](crate::analyzer::AllocationQuery): Whether the block fits and where it would go
+///    - [Error](crate::error::Error): An error if the size is 0
+pub fn query_allocation(
+    free_list: &[FreeRange],
+    size: usize,
+    options: &AnalyzerOptions,
+) -> Result<AllocationQuery> {
+    if size == 0 {
+        return Err("Allocating 0 bytes is not supported".into());
+    }
+
+    // blocks are never smaller than the alignment, like the allocator of the analyzer reserves them
+    let alignment = options.architecture.heap_alignment();
+    let reserved_size = align_up(size.max(alignment), alignment);
+
+    // the free ranges next to each other are one range to the allocator, so a block can fit across them
+    let mut ranges = Vec::new();
+
+    for range in free_list {
+        release_range(&mut ranges, range.start, range.end);
+    }
+
+    let free_list: Vec<FreeRange> = ranges
+        .into_iter()
+        .map(|(start, end)| FreeRange {
+            start,
+            end,
+            size: end - start + 1,
+        })
+        .collect();
+
+    let candidates: Vec<&FreeRange> = free_list
+        .iter()
+        .filter(|range| align_up(range.start, alignment) + reserved_size - 1 <= range.end)
+        .collect();
+
+    // the first candidate wins ties, so strategies that agree pick the same range
+    let placements = [
+        PlacementStrategy::Random,
+        PlacementStrategy::FirstFit,
+        PlacementStrategy::BestFit,
+        PlacementStrategy::WorstFit,
+    ]
+    .into_iter()
+    .map(|strategy| {
+        let range = match strategy {
+            PlacementStrategy::Random | PlacementStrategy::FirstFit => candidates.first().copied(),
+            PlacementStrategy::BestFit => candidates.iter().copied().min_by_key(|range| range.size),
+            PlacementStrategy::WorstFit => {
+                candidates.iter().copied().rev().max_by_key(|range| range.size)
+            }
+        };
+
+        let pointer = match strategy {
+            PlacementStrategy::Random => None,
+            _ => range.map(|range| align_up(range.start, alignment)),
+        };
+
+        Placement {
+            strategy,
+            range: range.cloned(),
+            pointer,
+        }
+    })
+    .collect();
+
+    Ok(AllocationQuery {
+        size,
+        reserved_size,
+        fits: !candidates.is_empty(),
+        placements,
+    })
+}
+
+fn align_up(pointer: usize, alignment: usize) -> usize {
+    (pointer + alignment - 1) & !(alignment - 1)
+}
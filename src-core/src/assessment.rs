@@ -0,0 +1,174 @@
+//! # Assessment
+//! Grading the memory a student's code produces against criteria an instructor wrote
+//!
+//! Unlike the steps of a [Lesson](crate::lesson::Lesson), the expectations of an assessment are hidden:
+//! reports only show the description the instructor gave each criterion, so the expected memory can't be
+//! read off a self-check
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::analyzer::{AnalyzerOptions, HeapBlock, Symbol};
+use crate::diagnostics::Diagnostic;
+use crate::error::{Error, Result};
+use crate::lesson::MemoryExpectation;
+use crate::pipeline::analyze_source;
+use crate::session::Session;
+
+/// Represents an assessment
+///
+/// # Fields
+/// - `id`: The identifier of the assessment
+/// - `title`: The title of the assessment
+/// - `criteria`: The criteria the code is graded on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Assessment {
+    pub id: String,
+    pub title: String,
+    pub criteria: Vec<Criterion>,
+}
+
+/// Represents a graded criterion
+///
+/// # Fields
+/// - `description`: What the criterion checks, as it is shown to the student
+/// - `expectations`: What the memory has to look like for the criterion to be met, never shown
+/// - `points`: How many points the criterion is worth
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Criterion {
+    pub description: String,
+    pub expectations: Vec<MemoryExpectation>,
+    #[serde(default = "default_points")]
+    pub points: u32,
+}
+
+fn default_points() -> u32 {
+    1
+}
+
+/// Represents the graded result of an assessment
+///
+/// # Fields
+/// - `assessment`: The identifier of the assessment
+/// - `criteria`: The result of every criterion, in the order of the assessment
+/// - `score`: The points awarded
+/// - `max_score`: The points all criteria are worth together
+/// - `error`: The error the code failed to analyze with, if it did
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssessmentReport {
+    pub assessment: String,
+    pub criteria: Vec<CriterionReport>,
+    pub score: u32,
+    pub max_score: u32,
+    pub error: Option<Diagnostic>,
+}
+
+/// Represents the graded result of a criterion
+///
+/// # Fields
+/// - `description`: What the criterion checks
+/// - `passed`: Whether every expectation of the criterion is met
+/// - `points`: The points awarded, all of them or none
+/// - `max_points`: How many points the criterion is worth
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CriterionReport {
+    pub description: String,
+    pub passed: bool,
+    pub points: u32,
+    pub max_points: u32,
+}
+
+impl Assessment {
+    /// Deserializes an assessment from JSON
+    ///
+    /// # Arguments
+    /// - `json`: The assessment definition
+    ///
+    /// # Returns
+    /// - [Result](crate::error::Result): A result containing either:
+    ///    - [Assessment](crate::assessment::Assessment): The deserialized assessment
+    ///    - [Error](crate::error::Error): An error if the JSON is not a valid assessment
+    pub fn from_json(json: &str) -> Result<Assessment> {
+        let assessment: Assessment =
+            serde_json::from_str(json).map_err(|e| format!("Invalid assessment: {}", e))?;
+
+        if let Some(criterion) =
+            assessment.criteria.iter().find(|criterion| criterion.expectations.is_empty())
+        {
+            return Err(format!(
+                "Invalid assessment: criterion \"{}\" has no expectations",
+                criterion.description
+            )
+            .into());
+        }
+
+        Ok(assessment)
+    }
+
+    /// Grades the memory the student's code produced
+    ///
+    /// # Arguments
+    /// - `stack`: The stack symbols the analyzer produced, empty if the code failed to analyze
+    /// - `heap`: The heap blocks the analyzer produced, empty if the code failed to analyze
+    /// - `error`: The error the code failed to analyze with, if it did
+    ///
+    /// # Returns
+    /// - [AssessmentReport](crate::assessment::AssessmentReport): The points awarded for every criterion
+    pub fn check(
+        &self,
+        stack: &[Symbol],
+        heap: &[HeapBlock],
+        error: Option<&Error>,
+    ) -> AssessmentReport {
+        let criteria: Vec<CriterionReport> = self
+            .criteria
+            .iter()
+            .map(|criterion| {
+                let passed = criterion
+                    .expectations
+                    .iter()
+                    .all(|expectation| expectation.is_met(stack, heap, error));
+
+                CriterionReport {
+                    description: criterion.description.clone(),
+                    passed,
+                    points: if passed { criterion.points } else { 0 },
+                    max_points: criterion.points,
+                }
+            })
+            .collect();
+
+        AssessmentReport {
+            assessment: self.id.clone(),
+            score: criteria.iter().map(|criterion| criterion.points).sum(),
+            max_score: criteria.iter().map(|criterion| criterion.max_points).sum(),
+            criteria,
+            error: error.map(Diagnostic::from),
+        }
+    }
+
+    /// Analyzes the student's code from scratch and grades it, for grading submissions in batches
+    ///
+    /// Every submission starts from an empty heap, so submissions don't affect each other's layout
+    ///
+    /// # Arguments
+    /// - `source`: The student's code
+    /// - `options`: The options to analyze the code with
+    ///
+    /// # Returns
+    /// - [AssessmentReport](crate::assessment::AssessmentReport): The points awarded for every criterion
+    pub async fn grade(&self, source: &str, options: AnalyzerOptions) -> AssessmentReport {
+        let mut session = Session::new(source.to_owned(), IndexMap::new(), options.clone());
+
+        let result = analyze_source(source, &mut session, options).await;
+
+        match result {
+            Ok(result) => self.check(&result.stack, &result.heap, None),
+            Err(e) => self.check(&[], &[], Some(&e)),
+        }
+    }
+}
@@ -0,0 +1,6 @@
+//! Prints the TypeScript definitions of the analyzer's types, see
+//! [type_definitions](mv_core::typescript::type_definitions)
+
+fn main() {
+    print!("{}", mv_core::typescript::type_definitions());
+}
@@ -0,0 +1,67 @@
+//! # Capabilities
+//! Describes what this version of the core supports, so frontends can feature-detect instead of
+//! assuming they were built against the same version
+
+use serde::Serialize;
+
+use crate::analyzer::{Architecture, CallingConvention, HeapOrder, PlacementStrategy, Strictness};
+use crate::session::SESSION_SCHEMA_VERSION;
+
+/// Represents the features supported by this version of the core
+///
+/// # Fields
+/// - `version`: The version of the core
+/// - `session_schema_version`: The version of the serialized [Session](crate::session::Session) format,
+///   the newest one this version opens
+/// - `dialects`: The source languages the parser understands
+/// - `heap_strategies`: The strategies the heap allocator can place blocks with
+/// - `architectures`: The data models the analyzer can simulate
+/// - `calling_conventions`: The ways the analyzer can place the arguments of calls
+/// - `heap_orders`: The orders the analyzer can return heap blocks in
+/// - `strictness`: How strictly the parser can treat statements outside the supported subset
+/// - `placement_strategies`: The strategies allocation queries compare the placement of a block across
+/// - `exports`: The formats analysis results can be exported to
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Capabilities {
+    pub version: &'static str,
+    pub session_schema_version: u32,
+    pub dialects: Vec<&'static str>,
+    pub heap_strategies: Vec<&'static str>,
+    pub architectures: Vec<Architecture>,
+    pub calling_conventions: Vec<CallingConvention>,
+    pub heap_orders: Vec<HeapOrder>,
+    pub strictness: Vec<Strictness>,
+    pub placement_strategies: Vec<PlacementStrategy>,
+    pub exports: Vec<&'static str>,
+}
+
+/// Gets the features supported by this version of the core
+///
+/// # Returns
+/// - [Capabilities](crate::capabilities::Capabilities): The supported features
+pub fn capabilities() -> Capabilities {
+    Capabilities {
+        version: crate::VERSION,
+        session_schema_version: SESSION_SCHEMA_VERSION,
+        dialects: vec!["cpp"],
+        heap_strategies: vec!["random"],
+        architectures: vec![Architecture::Ilp32, Architecture::Lp64],
+        calling_conventions: vec![CallingConvention::Stack, CallingConvention::Registers],
+        heap_orders: vec![
+            HeapOrder::Address,
+            HeapOrder::Allocation,
+            HeapOrder::Owner,
+            HeapOrder::Site,
+        ],
+        strictness: vec![Strictness::Strict, Strictness::Permissive],
+        placement_strategies: vec![
+            PlacementStrategy::Random,
+            PlacementStrategy::FirstFit,
+            PlacementStrategy::BestFit,
+            PlacementStrategy::WorstFit,
+        ],
+        exports: vec!["ascii", "embed", "playground"],
+    }
+}
@@ -0,0 +1,117 @@
+//! # Diagnostics
+//! The errors and warnings reported to the user, and the rules for which of them are shown
+//!
+//! Analysis stops at the first error, but the warnings of a statement are raised every time it runs, so
+//! a loop can raise the same one thousands of times. [finalize] keeps the warnings of a run short and
+//! readable
+
+use std::fmt;
+
+use indexmap::IndexSet;
+use serde::Serialize;
+
+use crate::error::Error;
+
+/// How many diagnostics are shown before the rest are summarized
+pub const DEFAULT_DIAGNOSTIC_LIMIT: usize = 100;
+
+/// Represents how serious a diagnostic is, errors rank before warnings
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// Represents a single error or warning
+///
+/// # Fields
+/// - `severity`: How serious the diagnostic is
+/// - `message`: What went wrong
+/// - `line_number`: The line the diagnostic points at, `None` if it isn't tied to the source
+/// - `column_number`: The column the diagnostic points at, `None` if it isn't tied to a column
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    pub line_number: Option<usize>,
+    pub column_number: Option<usize>,
+}
+
+impl From<&Error> for Diagnostic {
+    fn from(error: &Error) -> Self {
+        let (message, line_number, column_number) = match error {
+            Error::AnalyzerError(message, line, column)
+            | Error::ParserError(message, line, column) => {
+                (message.clone(), Some(*line), Some(*column))
+            }
+            Error::InternalError(message, line) => (message.clone(), Some(*line), None),
+            Error::Msg(message) => (message.clone(), None, None),
+        };
+
+        Diagnostic {
+            severity: Severity::Error,
+            message,
+            line_number,
+            column_number,
+        }
+    }
+}
+
+/// Prepares diagnostics for display: drops duplicates, ranks errors before warnings and caps how many
+/// are kept
+///
+/// Diagnostics of the same severity keep the order they were reported in. When diagnostics are dropped
+/// because of the cap, a summary such as "3 more errors" is added at the end
+///
+/// # Arguments
+/// - `diagnostics`: The diagnostics in the order they were reported
+/// - `limit`: How many diagnostics to keep, not counting the summary
+///
+/// # Returns
+/// - `Vec<Diagnostic>`: The diagnostics to show
+pub fn finalize(diagnostics: Vec<Diagnostic>, limit: usize) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> =
+        diagnostics.into_iter().collect::<IndexSet<_>>().into_iter().collect();
+
+    // sorting is stable, so the reported order is kept within each severity
+    diagnostics.sort_by_key(|diagnostic| diagnostic.severity);
+
+    if diagnostics.len() <= limit {
+        return diagnostics;
+    }
+
+    let dropped = diagnostics.split_off(limit);
+    let count =
+        |severity| dropped.iter().filter(|diagnostic| diagnostic.severity == severity).count();
+
+    let summary = [Severity::Error, Severity::Warning]
+        .into_iter()
+        .map(|severity| (severity, count(severity)))
+        .filter(|(_, count)| *count > 0)
+        .map(|(severity, count)| {
+            format!("{} more {}{}", count, severity, if count == 1 { "" } else { "s" })
+        })
+        .collect::<Vec<_>>()
+        .join(" and ");
+
+    diagnostics.push(Diagnostic {
+        severity: dropped[0].severity,
+        message: summary,
+        line_number: None,
+        column_number: None,
+    });
+
+    diagnostics
+}
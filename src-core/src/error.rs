@@ -10,6 +10,10 @@ pub enum Error {
     #[error("Parser Error: {0} (Line: {1} Col: {2})")]
     ParserError(String, usize, usize),
 
+    // the analyzer's self-check found the memory layout inconsistent after the statement on the line
+    #[error("Internal Error: {0} (Line: {1})")]
+    InternalError(String, usize),
+
     // generic error just in case no other error is applicable
     #[error("Error: {0}")]
     Msg(String),
@@ -0,0 +1,387 @@
+//! Renders the stack and the heap as a monospace ASCII diagram
+//!
+//! The output is meant to be pasted into places where the interactive visualization is not available,
+//! such as forum posts, plain-text assignments and commit messages
+
+use crate::analyzer::address_space::{stack_addresses, HEAP_BASE_ADDRESS, STACK_END_ADDRESS};
+use crate::analyzer::heap_allocator::{HeapBlock, HeapBlockState};
+use crate::analyzer::{AllocationType, Symbol};
+
+use crate::analyzer::Architecture;
+
+use super::format_address;
+
+/// A single box in the diagram
+struct Row {
+    address: usize,
+    label: String,
+    size: usize,
+    arrow: Option<String>,
+}
+
+/// Renders the stack and the heap as a monospace ASCII diagram
+///
+/// Addresses are assigned the same way the visualization assigns them, so the diagram matches
+/// what the user sees in the app, written as wide as a pointer of the architecture
+///
+/// # Arguments
+/// - `stack`: The stack symbols produced by the analyzer
+/// - `heap`: The heap blocks produced by the analyzer
+/// - `architecture`: The architecture the analyzer simulated
+///
+/// # Returns
+/// - `String`: The rendered diagram
+pub fn render_ascii_diagram(
+    stack: &[Symbol],
+    heap: &[HeapBlock],
+    architecture: Architecture,
+) -> String {
+    let address_size = architecture.pointer_size();
+    let addresses = stack_addresses(stack, STACK_END_ADDRESS, address_size);
+    let mut stack_rows = Vec::new();
+
+    // a pointer to the stack shows the address of the symbol it points to next to its name
+    let stack_target = |name: &String| {
+        let address = stack.iter().zip(&addresses).find_map(|(symbol, address)| match symbol {
+            Symbol::Variable { name: target, .. }
+            | Symbol::Pointer { name: target, .. }
+            | Symbol::Object { name: target, .. }
+                if target == name =>
+            {
+                Some(*address)
+            }
+            _ => None,
+        });
+
+        match address {
+            Some(address) => format!("{} ({})", name, format_address(address, address_size)),
+            None => name.clone(),
+        }
+    };
+
+    for (symbol, &address) in stack.iter().zip(&addresses) {
+        let row = match symbol {
+            Symbol::Variable {
+                vtype,
+                name,
+                value,
+                size,
+                string,
+            } => Row {
+                address,
+                label: match value {
+                    Some(value) => format!("{} {} = {}", vtype, name, value),
+                    None => format!("{} {}", vtype, name),
+                },
+                size: *size,
+                // a `std::string` too long for its small buffer points to its characters on the heap
+                arrow: string.as_ref().and_then(|string| string.heap_pointer).map(|pointer| {
+                    format!("--> {}", format_address(HEAP_BASE_ADDRESS + pointer, address_size))
+                }),
+            },
+
+            Symbol::Pointer {
+                ptype,
+                name,
+                value,
+                heap_pointer,
+                allocation_type,
+                pointer_size,
+                indirection,
+                unique,
+                shared,
+                ..
+            } => {
+                let target = match allocation_type {
+                    AllocationType::Stack => match value.as_deref() {
+                        Some(Symbol::Variable { name, .. })
+                        | Some(Symbol::Pointer { name, .. })
+                        | Some(Symbol::Object { name, .. }) => stack_target(name),
+                        Some(Symbol::Literal { value }) => value.clone(),
+                        _ => "?".to_owned(),
+                    },
+                    AllocationType::Heap => heap_pointer
+                        .map(|pointer| format_address(HEAP_BASE_ADDRESS + pointer, address_size))
+                        .unwrap_or_else(|| "?".to_owned()),
+                    AllocationType::Dangling => heap_pointer
+                        .map(|pointer| {
+                            format!(
+                                "{} (dangling)",
+                                format_address(HEAP_BASE_ADDRESS + pointer, address_size)
+                            )
+                        })
+                        .unwrap_or_else(|| "? (dangling)".to_owned()),
+                    AllocationType::Mapped => "mapped region".to_owned(),
+                    AllocationType::Null => "nullptr".to_owned(),
+                };
+
+                Row {
+                    address,
+                    label: if *unique {
+                        format!("std::unique_ptr<{}> {}", ptype, name)
+                    } else if *shared {
+                        format!("std::shared_ptr<{}> {}", ptype, name)
+                    } else if let Some(Symbol::Object { class_name, .. }) = value.as_deref() {
+                        // an object allocated with `new` is held by a pointer to its class
+                        format!("{}* {}", class_name, name)
+                    } else {
+                        format!("{}{} {}", ptype, "*".repeat(*indirection), name)
+                    },
+                    size: *pointer_size,
+                    arrow: Some(format!("--> {}", target)),
+                }
+            }
+
+            // every member reads the same bytes, the one written last is marked active
+            Symbol::Union {
+                union_name,
+                name,
+                members,
+                active,
+                size,
+                ..
+            } => {
+                let members: Vec<String> = members
+                    .iter()
+                    .map(|member| {
+                        let marker = if active.as_ref() == Some(&member.name) { "*" } else { "" };
+
+                        match &member.value {
+                            Some(value) => format!("{}{} = {}", marker, member.name, value),
+                            None => member.name.clone(),
+                        }
+                    })
+                    .collect();
+
+                Row {
+                    address,
+                    label: format!("union {} {} {{ {} }}", union_name, name, members.join(", ")),
+                    size: *size,
+                    arrow: None,
+                }
+            }
+
+            Symbol::Object {
+                class_name,
+                name,
+                fields,
+                size,
+            } => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|field| match (field.pointer.as_deref(), &field.value) {
+                        // a pointer field shows where it points, like a pointer on the stack does
+                        (
+                            Some(Symbol::Pointer {
+                                heap_pointer: Some(pointer),
+                                allocation_type: AllocationType::Heap,
+                                ..
+                            }),
+                            _,
+                        ) => format!(
+                            "{} --> {}",
+                            field.name,
+                            format_address(HEAP_BASE_ADDRESS + pointer, address_size)
+                        ),
+                        (
+                            Some(Symbol::Pointer {
+                                allocation_type: AllocationType::Null,
+                                ..
+                            }),
+                            _,
+                        ) => format!("{} --> nullptr", field.name),
+                        (Some(_), _) => format!("{} --> ?", field.name),
+                        (None, Some(value)) => format!("{} = {}", field.name, value),
+                        (None, None) => field.name.clone(),
+                    })
+                    .collect();
+
+                Row {
+                    address,
+                    label: format!("{} {} {{ {} }}", class_name, name, fields.join(", ")),
+                    size: *size,
+                    arrow: None,
+                }
+            }
+
+            // the elements of each row are braced on their own, like a two dimensional initializer
+            Symbol::Array {
+                vtype,
+                name,
+                dimensions,
+                elements,
+                size,
+            } => {
+                let columns = dimensions.last().copied().unwrap_or(1);
+                let rows: Vec<String> = elements
+                    .chunks(columns)
+                    .map(|row| {
+                        row.iter()
+                            .map(|element| element.as_deref().unwrap_or("?"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    })
+                    .collect();
+
+                let dimensions: String =
+                    dimensions.iter().map(|dimension| format!("[{}]", dimension)).collect();
+
+                let values = match rows.as_slice() {
+                    [row] => row.clone(),
+                    rows => format!("{{ {} }}", rows.join(" }, { ")),
+                };
+
+                Row {
+                    address,
+                    label: format!("{} {}{} = {{ {} }}", vtype, name, dimensions, values),
+                    size: *size,
+                    arrow: None,
+                }
+            }
+
+            // a mutex is owned by the thread that locked it until it unlocks it
+            Symbol::Mutex { name, owner, size } => Row {
+                address,
+                label: match owner {
+                    Some(owner) => format!("std::mutex {} (locked by thread {})", name, owner),
+                    None => format!("std::mutex {} (unlocked)", name),
+                },
+                size: *size,
+                arrow: None,
+            },
+
+            Symbol::Literal { .. } => continue,
+        };
+
+        stack_rows.push(row);
+    }
+
+    let heap_rows: Vec<Row> = heap
+        .iter()
+        .map(|block| {
+            let label = match block.block_state {
+                HeapBlockState::Unallocated => "unallocated".to_owned(),
+                HeapBlockState::Free => match &block.moved {
+                    Some(copy) => {
+                        format!(
+                            "free (moved to {})",
+                            format_address(HEAP_BASE_ADDRESS + copy.to, address_size)
+                        )
+                    }
+                    None => "free".to_owned(),
+                },
+                HeapBlockState::Leaked => "leaked".to_owned(),
+                HeapBlockState::Padding => match &block.gap_reason {
+                    Some(reason) => format!("padding ({})", reason),
+                    None => "padding".to_owned(),
+                },
+                // each field of an object, or element written through a pointer, is shown at its offset in
+                // the block
+                HeapBlockState::Allocated if block.members.is_some() => {
+                    let members: Vec<String> = block
+                        .members
+                        .iter()
+                        .flatten()
+                        .map(|member| match (&member.value, member.points_to) {
+                            (_, Some(pointer)) => format!(
+                                "+{} {} --> {}",
+                                member.offset,
+                                member.name,
+                                format_address(HEAP_BASE_ADDRESS + pointer, address_size)
+                            ),
+                            (Some(value), None) => {
+                                format!("+{} {} = {}", member.offset, member.name, value)
+                            }
+                            (None, None) => format!("+{} {}", member.offset, member.name),
+                        })
+                        .collect();
+
+                    match block.metadata.as_str() {
+                        "" => format!("{{ {} }}", members.join(", ")),
+                        metadata => format!("{} {{ {} }}", metadata, members.join(", ")),
+                    }
+                }
+                HeapBlockState::Allocated if block.metadata.is_empty() => "allocated".to_owned(),
+                HeapBlockState::Allocated => block.metadata.clone(),
+            };
+
+            let mut owners = Vec::new();
+
+            if let (HeapBlockState::Allocated, Some(identifier)) =
+                (&block.block_state, &block.current_pointer_identifier)
+            {
+                owners.push(identifier.clone());
+            }
+
+            if let Some(identifiers) = &block.dangling_pointer_identifiers {
+                owners.extend(
+                    identifiers.iter().map(|identifier| format!("{} (dangling)", identifier)),
+                );
+            }
+
+            Row {
+                address: HEAP_BASE_ADDRESS + block.pointer,
+                label,
+                size: block.size,
+                arrow: (!owners.is_empty()).then(|| format!("<-- {}", owners.join(", "))),
+            }
+        })
+        .collect();
+
+    let mut diagram = String::new();
+    render_section(&mut diagram, "STACK", &stack_rows, address_size);
+    diagram.push('\n');
+    render_section(&mut diagram, "HEAP", &heap_rows, address_size);
+
+    diagram
+}
+
+/// Renders a titled column of boxes, one box per row, with the address of each box on its top edge
+fn render_section(diagram: &mut String, title: &str, rows: &[Row], address_size: usize) {
+    diagram.push_str(title);
+    diagram.push('\n');
+
+    if rows.is_empty() {
+        diagram.push_str("(empty)\n");
+        return;
+    }
+
+    let labels: Vec<String> =
+        rows.iter().map(|row| escape_control_characters(&row.label)).collect();
+    let label_width = labels.iter().map(String::len).max().unwrap_or(0);
+    let border = format!("+{}+", "-".repeat(label_width + 2));
+    let gutter = " ".repeat(format_address(0, address_size).len());
+
+    for (row, label) in rows.iter().zip(&labels) {
+        diagram.push_str(&format!("{} {}\n", format_address(row.address, address_size), border));
+
+        let mut line =
+            format!("{} | {:<width$} | {:>3} B", gutter, label, row.size, width = label_width);
+
+        if let Some(arrow) = &row.arrow {
+            line.push_str("  ");
+            line.push_str(arrow);
+        }
+
+        diagram.push_str(&line);
+        diagram.push('\n');
+    }
+
+    diagram.push_str(&format!("{} {}\n", gutter, border));
+}
+
+/// Writes the control characters a `char` can hold, like `'\n'`, as their escapes so they don't break
+/// the box they are in
+fn escape_control_characters(label: &str) -> String {
+    label
+        .chars()
+        .map(|c| match c {
+            '\n' => "\\n".to_owned(),
+            '\t' => "\\t".to_owned(),
+            '\r' => "\\r".to_owned(),
+            '\0' => "\\0".to_owned(),
+            c if c.is_control() => format!("\\x{:02X}", c as u32),
+            c => c.to_string(),
+        })
+        .collect()
+}
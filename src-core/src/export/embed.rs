@@ -0,0 +1,145 @@
+//! Generates HTML snippets that embed a live view of a program's memory into static sites such as course
+//! websites or mdBook pages
+//!
+//! The snippet is an editable copy of the source next to the session view of its memory: the symbols on
+//! the stack with their addresses and values, and the blocks of the heap with their states and owners.
+//! It loads the MV WASM module, analyzes the embedded session in the browser and draws the view again as
+//! readers type, with the ASCII diagram [render_ascii_diagram](crate::export::render_ascii_diagram)
+//! draws folded under it. The JS bindings and `.wasm` file `wasm-pack` builds have to be hosted along
+//! with the page
+
+use crate::error::Result;
+use crate::session::Session;
+
+/// Location of the WASM module's JS bindings used when no other location is provided, relative to the page
+/// embedding the snippet
+pub const DEFAULT_WASM_MODULE_URL: &str = "./mv_wasm.js";
+
+/// Renders an HTML snippet embedding an editable copy of the session's source and the session view of
+/// its memory
+///
+/// The id of the snippet's element is a hash of the source that doesn't change between builds or
+/// platforms, so exporting the same program again gives the same snippet. The script finds its element as
+/// the one right before it rather than by id, so the same program can be embedded twice on one page
+///
+/// # Arguments
+/// - `session`: The session to embed
+/// - `wasm_module_url`: The URL of the JS bindings generated by `wasm-pack` for the MV WASM module
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - `String`: The HTML snippet
+///    - [Error](crate::error::Error): An error if the session cannot be serialized
+pub fn render_embed_snippet(session: &Session, wasm_module_url: &str) -> Result<String> {
+    let element_id = format!("mv-embed-{:016x}", fnv1a(session.source.as_bytes()));
+
+    // `</` would end the surrounding script tag early if the source contains `</script>`
+    let session_json = session.to_json()?.replace("</", "<\\/");
+    let wasm_module_url =
+        serde_json::to_string(wasm_module_url).map_err(|e| e.to_string())?.replace("</", "<\\/");
+
+    // a classic script, since a module script has no `document.currentScript` to find its element by
+    Ok(format!(
+        r#"<div class="mv-embed" id="{id}">
+  <textarea class="mv-embed-source" spellcheck="false" rows="{rows}">{source}</textarea>
+  <div class="mv-embed-view">
+    <p class="mv-embed-status">Loading memory visualization...</p>
+    <table class="mv-embed-stack">
+      <caption>Stack</caption>
+      <thead><tr><th>Address</th><th>Name</th><th>Kind</th><th>Value</th></tr></thead>
+      <tbody></tbody>
+    </table>
+    <table class="mv-embed-heap">
+      <caption>Heap</caption>
+      <thead><tr><th>Offset</th><th>Size</th><th>State</th><th>Owner</th></tr></thead>
+      <tbody></tbody>
+    </table>
+    <details>
+      <summary>ASCII diagram</summary>
+      <pre class="mv-embed-diagram"></pre>
+    </details>
+  </div>
+</div>
+<script>
+  (function (root) {{
+    const source = root.querySelector(".mv-embed-source");
+    const status = root.querySelector(".mv-embed-status");
+    const stack = root.querySelector(".mv-embed-stack tbody");
+    const heap = root.querySelector(".mv-embed-heap tbody");
+    const diagram = root.querySelector(".mv-embed-diagram");
+    const session = {session_json};
+
+    function row(cells, className) {{
+      const tr = document.createElement("tr");
+      if (className) tr.className = className;
+      for (const cell of cells) {{
+        const td = document.createElement("td");
+        td.textContent = cell;
+        tr.append(td);
+      }}
+      return tr;
+    }}
+
+    function describe(kind, symbol) {{
+      switch (kind) {{
+        case "Pointer":
+          return symbol.heap_pointer == null ? "-> stack" : "-> heap +" + symbol.heap_pointer;
+        case "Array":
+          return "{{" + symbol.elements.map((element) => element ?? "?").join(", ") + "}}";
+        case "Object":
+          return symbol.fields.map((field) => field.name + ": " + (field.value ?? "?")).join(", ");
+        case "Union":
+          return symbol.active ?? "";
+        default:
+          return symbol.value ?? "?";
+      }}
+    }}
+
+    function show(analysis) {{
+      stack.replaceChildren(...analysis.stack.map((entry, i) => {{
+        const [kind, symbol] = Object.entries(entry)[0];
+        const address = "0x" + analysis.stack_addresses[i].toString(16);
+        return row([address, symbol.name ?? "", kind, describe(kind, symbol)]);
+      }}));
+      heap.replaceChildren(...analysis.heap.map((block) => row(
+        [block.pointer, block.size, block.block_state, block.current_pointer_identifier ?? ""],
+        "mv-embed-" + block.block_state.toLowerCase(),
+      )));
+    }}
+
+    async function render(render_session) {{
+      session.source = source.value;
+      const response = JSON.parse(await render_session(JSON.stringify(session)));
+      status.textContent = response.error ? response.error.message : "";
+      if (response.error) return;
+      show(response.result);
+      diagram.textContent = response.diagram;
+    }}
+
+    import({wasm_module_url}).then(async ({{ default: init, render_session }}) => {{
+      await init();
+      source.addEventListener("input", () => render(render_session));
+      await render(render_session);
+    }});
+  }})(document.currentScript.previousElementSibling);
+</script>
+"#,
+        id = element_id,
+        rows = session.source.lines().count().max(1),
+        source = escape_html(&session.source),
+        wasm_module_url = wasm_module_url,
+        session_json = session_json,
+    ))
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Hashes bytes with 64-bit FNV-1a, which unlike the hasher of the standard library gives the same hash
+/// on every build and platform
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
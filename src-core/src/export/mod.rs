@@ -0,0 +1,16 @@
+//! # Export
+//! Responsible for turning analysis results into formats that can be shared outside of MV
+
+mod ascii;
+mod embed;
+mod playground;
+
+pub use ascii::render_ascii_diagram;
+pub use embed::{DEFAULT_WASM_MODULE_URL, render_embed_snippet};
+pub use playground::render_playground_page;
+
+/// Formats an address the way the visualization shows it, with two hex digits for each byte of a
+/// pointer, like `0x00400000` on a 32-bit architecture and `0x0000000000400000` on a 64-bit one
+pub(crate) fn format_address(address: usize, pointer_size: usize) -> String {
+    format!("0x{:0width$X}", address, width = 2 * pointer_size)
+}
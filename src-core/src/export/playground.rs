@@ -0,0 +1,63 @@
+//! Generates the HTML shell of the offline web playground
+//!
+//! The shell is a single static page around an [embed snippet](crate::export::render_embed_snippet), so
+//! it works from any plain file server without a build step or network access
+
+use indexmap::IndexMap;
+
+use crate::analyzer::AnalyzerOptions;
+use crate::error::Result;
+use crate::session::Session;
+
+use super::render_embed_snippet;
+
+/// Program the playground opens with
+const STARTER_SOURCE: &str = "int x = 5;\nint* p = &x;\nint* h = new int;\n*h = 10;\ndelete h;\n";
+
+/// Renders the page hosting the offline playground
+///
+/// # Arguments
+/// - `wasm_module_url`: The URL of the JS bindings generated by `wasm-pack` for the MV WASM module,
+///   relative to the page
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - `String`: The HTML page
+///    - [Error](crate::error::Error): An error if the starter session cannot be serialized
+pub fn render_playground_page(wasm_module_url: &str) -> Result<String> {
+    let session =
+        Session::new(STARTER_SOURCE.to_owned(), IndexMap::new(), AnalyzerOptions::default());
+    let snippet = render_embed_snippet(&session, wasm_module_url)?;
+
+    Ok(format!(
+        r#"<!doctype html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <meta name="viewport" content="width=device-width, initial-scale=1">
+  <meta name="generator" content="mv-core {version}">
+  <title>MV Playground</title>
+  <style>
+    body {{ font-family: sans-serif; margin: 2rem auto; max-width: 60rem; padding: 0 1rem; }}
+    .mv-embed {{ display: grid; gap: 1rem; grid-template-columns: 1fr 1fr; }}
+    .mv-embed-source, .mv-embed-view {{ font-family: monospace; font-size: 0.9rem; margin: 0; }}
+    .mv-embed-source {{ min-height: 20rem; resize: vertical; }}
+    .mv-embed-view {{ overflow: auto; }}
+    .mv-embed-view table {{ border-collapse: collapse; margin-bottom: 1rem; width: 100%; }}
+    .mv-embed-view caption {{ font-weight: bold; text-align: left; }}
+    .mv-embed-view td, .mv-embed-view th {{ border: 1px solid #ccc; padding: 0.2rem 0.4rem; }}
+    .mv-embed-allocated {{ background: #e3f2fd; }}
+    .mv-embed-free {{ background: #fce4ec; }}
+    .mv-embed-leaked {{ background: #fff3e0; }}
+  </style>
+</head>
+<body>
+  <h1>MV Playground</h1>
+  <p>Write C++ on the left to see its memory on the right.</p>
+{snippet}</body>
+</html>
+"#,
+        version = crate::VERSION,
+        snippet = snippet,
+    ))
+}
@@ -0,0 +1,189 @@
+//! # Layout
+//! Computes where the members of a struct go and how much padding sits between them, without analyzing
+//! a program, so frontends can show how reordering members changes the size of a struct
+//!
+//! Members are laid out the way the analyzer lays out the fields of an object: in order, each aligned to
+//! its own size, with the struct padded to the alignment of its largest member
+
+use serde::Serialize;
+
+use crate::analyzer::r#type::Type;
+use crate::analyzer::Architecture;
+use crate::error::{Error, Result};
+use crate::lexer::token::TokenKind;
+use crate::lexer::Lexer;
+
+/// Represents where a member of a struct goes
+///
+/// # Fields
+/// - `member_type`: The type of the member as it was given, like `int*`
+/// - `offset`: Where the member starts in bytes from the start of the struct
+/// - `size`: The size of the member in bytes
+/// - `padding`: The bytes of padding before the member, aligning it to its size
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct MemberLayout {
+    pub member_type: String,
+    pub offset: usize,
+    pub size: usize,
+    pub padding: usize,
+}
+
+/// Represents how the members of a struct are laid out
+///
+/// # Fields
+/// - `members`: Where each member goes, in the order they were given
+/// - `trailing_padding`: The bytes of padding after the last member, so the members of structs next to
+///   each other in an array stay aligned
+/// - `padding`: The bytes of padding in the struct altogether, before its members and after them
+/// - `alignment`: The alignment of the struct, the size of its largest member
+/// - `size`: The size of the struct in bytes, a struct without members still takes a byte like in C++
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct StructLayout {
+    pub members: Vec<MemberLayout>,
+    pub trailing_padding: usize,
+    pub padding: usize,
+    pub alignment: usize,
+    pub size: usize,
+}
+
+/// Represents where members of the given sizes go, before they are described to the frontend
+///
+/// # Fields
+/// - `offsets`: Where each member starts in bytes
+/// - `alignment`: The alignment of the struct
+/// - `size`: The size of the struct in bytes
+pub(crate) struct Placement {
+    pub(crate) offsets: Vec<usize>,
+    pub(crate) alignment: usize,
+    pub(crate) size: usize,
+}
+
+/// Places members of the given sizes one after the other, each aligned to its own size
+///
+/// # Arguments
+/// - `sizes`: The size of each member in bytes, in the order they are laid out
+///
+/// # Returns
+/// - [Placement]: Where each member starts and how big the struct is
+pub(crate) fn place_members(sizes: &[usize]) -> Placement {
+    let mut end: usize = 0;
+    let mut alignment = 1;
+
+    let offsets = sizes
+        .iter()
+        .map(|&size| {
+            let offset = end.next_multiple_of(size);
+            end = offset + size;
+            alignment = alignment.max(size);
+
+            offset
+        })
+        .collect();
+
+    Placement {
+        offsets,
+        alignment,
+        size: end.max(1).next_multiple_of(alignment),
+    }
+}
+
+/// Computes the layout of a struct with members of the given types, in order, on an architecture
+///
+/// Member types are written like in the source, `int`, `unsigned long` or `char**`, and can be any type
+/// the fields of a class can have
+///
+/// # Arguments
+/// - `member_types`: The type of each member, in the order they are declared
+/// - `architecture`: The data model the struct is laid out for, which decides how wide `long` and
+///   pointers are
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - [StructLayout]: The offset and padding of every member and the size of the struct
+///    - [Error](crate::error::Error): An error if a member type isn't a type fields can have
+pub fn compute_struct_layout(
+    member_types: &[String],
+    architecture: Architecture,
+) -> Result<StructLayout> {
+    let pointer_size = architecture.pointer_size();
+
+    let sizes = member_types
+        .iter()
+        .map(|member_type| member_size(member_type, pointer_size))
+        .collect::<Result<Vec<usize>>>()?;
+
+    let placement = place_members(&sizes);
+
+    let mut end = 0;
+    let mut padding = 0;
+
+    let members = member_types
+        .iter()
+        .zip(sizes)
+        .zip(placement.offsets)
+        .map(|((member_type, size), offset)| {
+            let member = MemberLayout {
+                member_type: member_type.clone(),
+                offset,
+                size,
+                padding: offset - end,
+            };
+
+            padding += member.padding;
+            end = offset + size;
+
+            member
+        })
+        .collect();
+
+    let trailing_padding = placement.size - end;
+
+    Ok(StructLayout {
+        members,
+        trailing_padding,
+        padding: padding + trailing_padding,
+        alignment: placement.alignment,
+        size: placement.size,
+    })
+}
+
+/// Gets the size of a member from its type, a pointer is as wide as the architecture's pointers
+///
+/// # Arguments
+/// - `member_type`: The type of the member, like `double` or `int*`
+/// - `pointer_size`: The size of a pointer in bytes
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - `usize`: The size of the member in bytes
+///    - [Error](crate::error::Error): An error if the type isn't a type fields can have
+fn member_size(member_type: &str, pointer_size: usize) -> Result<usize> {
+    let invalid = || Error::from(format!("`{}` isn't a type a member can have", member_type));
+
+    let mut tokens = Lexer::new(member_type)
+        .map(|token| token.kind)
+        .filter(|kind| !matches!(kind, TokenKind::Whitespace | TokenKind::Comment));
+
+    let base = tokens.next().ok_or_else(invalid)?;
+    let mut indirection = 0;
+
+    for kind in tokens {
+        match kind {
+            TokenKind::Asterisk => indirection += 1,
+            TokenKind::EOF => break,
+            _ => return Err(invalid()),
+        }
+    }
+
+    match Type::from_token(base) {
+        Ok(Type::String) => Err(format!("`{}` members are not supported yet", member_type).into()),
+        Ok(_) if indirection > 0 => Ok(pointer_size),
+        Ok(Type::Void) => Err(format!("A member can't be `{}`", member_type).into()),
+        Ok(base) => Ok(base.get_variable_size(pointer_size)),
+        Err(_) => Err(invalid()),
+    }
+}
@@ -0,0 +1,339 @@
+//! # Lesson
+//! Guided lessons that walk the user through a topic step by step, checking the memory their code
+//! produces against what each step expects
+//!
+//! Lessons are written as JSON. The user's code is analyzed once and every step is checked against the
+//! result, the first step that isn't met yet is the one the user is working on and the only one whose
+//! hints are shown
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analyzer::heap_allocator::HeapBlockState;
+use crate::analyzer::{AllocationType, HeapBlock, Symbol};
+use crate::diagnostics::Diagnostic;
+use crate::error::{Error, Result};
+
+/// Represents a lesson
+///
+/// # Fields
+/// - `id`: The identifier of the lesson, unique among the lessons shown together
+/// - `title`: The title of the lesson
+/// - `description`: What the lesson teaches
+/// - `starter_code`: The code the editor starts with when the lesson is opened
+/// - `steps`: The steps of the lesson, in the order they are worked through
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Lesson {
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub starter_code: String,
+    pub steps: Vec<LessonStep>,
+}
+
+/// Represents a step of a lesson
+///
+/// # Fields
+/// - `instructions`: What the user is asked to do
+/// - `expectations`: What the memory has to look like for the step to pass
+/// - `hints`: Hints shown while the step doesn't pass, from the gentlest to the most explicit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LessonStep {
+    pub instructions: String,
+    #[serde(default)]
+    pub expectations: Vec<MemoryExpectation>,
+    #[serde(default)]
+    pub hints: Vec<String>,
+}
+
+/// Represents something a step expects of the memory
+///
+/// - `Variable`: A variable with the name is on the stack, holding the value if one is given
+/// - `Pointer`: A pointer with the name is on the stack and points to the target
+/// - `AllocatedBlocks`: The heap has exactly this many allocated blocks
+/// - `LeakedBlocks`: The heap has exactly this many leaked blocks
+/// - `Error`: The code fails to analyze, with an error containing the message if one is given, for steps
+///   that show what the analyzer catches
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum MemoryExpectation {
+    Variable {
+        name: String,
+        #[serde(default)]
+        value: Option<String>,
+    },
+    Pointer {
+        name: String,
+        target: PointerTarget,
+    },
+    AllocatedBlocks {
+        count: usize,
+    },
+    LeakedBlocks {
+        count: usize,
+    },
+    Error {
+        #[serde(default)]
+        message: Option<String>,
+    },
+}
+
+/// Represents what a pointer is expected to point to
+///
+/// - `Stack`: The symbol with the name on the stack
+/// - `Heap`: A block on the heap
+/// - `Null`: Nothing, the pointer is `nullptr`
+/// - `Dangling`: A block that was freed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum PointerTarget {
+    Stack { name: String },
+    Heap,
+    Null,
+    Dangling,
+}
+
+/// Represents the result of checking a lesson
+///
+/// # Fields
+/// - `lesson`: The identifier of the lesson
+/// - `steps`: The result of every step, in the order of the lesson
+/// - `current_step`: The index of the first step that doesn't pass, `None` once the lesson is complete
+/// - `error`: The error the code failed to analyze with, if it did
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LessonReport {
+    pub lesson: String,
+    pub steps: Vec<StepReport>,
+    pub current_step: Option<usize>,
+    pub error: Option<Diagnostic>,
+}
+
+/// Represents the result of checking a step
+///
+/// # Fields
+/// - `passed`: Whether every expectation of the step is met
+/// - `expectations`: The result of every expectation, in the order of the step
+/// - `hints`: The hints of the step, only given for the current step
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepReport {
+    pub passed: bool,
+    pub expectations: Vec<ExpectationReport>,
+    pub hints: Vec<String>,
+}
+
+/// Represents the result of checking an expectation
+///
+/// # Fields
+/// - `description`: What was expected, as it is shown to the user
+/// - `passed`: Whether the expectation is met
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpectationReport {
+    pub description: String,
+    pub passed: bool,
+}
+
+impl Lesson {
+    /// Deserializes a lesson from JSON
+    ///
+    /// # Arguments
+    /// - `json`: The lesson definition
+    ///
+    /// # Returns
+    /// - [Result](crate::error::Result): A result containing either:
+    ///    - [Lesson](crate::lesson::Lesson): The deserialized lesson
+    ///    - [Error](crate::error::Error): An error if the JSON is not a valid lesson
+    pub fn from_json(json: &str) -> Result<Lesson> {
+        let lesson: Lesson =
+            serde_json::from_str(json).map_err(|e| format!("Invalid lesson: {}", e))?;
+
+        if lesson.steps.is_empty() {
+            return Err(format!("Invalid lesson: `{}` has no steps", lesson.id).into());
+        }
+
+        Ok(lesson)
+    }
+
+    /// Checks the memory the user's code produced against every step of the lesson
+    ///
+    /// # Arguments
+    /// - `stack`: The stack symbols the analyzer produced, empty if the code failed to analyze
+    /// - `heap`: The heap blocks the analyzer produced, empty if the code failed to analyze
+    /// - `error`: The error the code failed to analyze with, if it did
+    ///
+    /// # Returns
+    /// - [LessonReport](crate::lesson::LessonReport): Which steps pass, with the hints of the current step
+    pub fn check(
+        &self,
+        stack: &[Symbol],
+        heap: &[HeapBlock],
+        error: Option<&Error>,
+    ) -> LessonReport {
+        let mut current_step = None;
+
+        let steps = self
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(index, step)| {
+                let expectations: Vec<ExpectationReport> = step
+                    .expectations
+                    .iter()
+                    .map(|expectation| ExpectationReport {
+                        description: expectation.to_string(),
+                        passed: expectation.is_met(stack, heap, error),
+                    })
+                    .collect();
+
+                let passed = expectations.iter().all(|expectation| expectation.passed);
+                let is_current = !passed && current_step.is_none();
+
+                if is_current {
+                    current_step = Some(index);
+                }
+
+                StepReport {
+                    passed,
+                    expectations,
+                    hints: if is_current { step.hints.clone() } else { Vec::new() },
+                }
+            })
+            .collect();
+
+        LessonReport {
+            lesson: self.id.clone(),
+            steps,
+            current_step,
+            error: error.map(Diagnostic::from),
+        }
+    }
+}
+
+impl MemoryExpectation {
+    /// Checks the expectation against the memory the user's code produced
+    pub(crate) fn is_met(
+        &self,
+        stack: &[Symbol],
+        heap: &[HeapBlock],
+        error: Option<&Error>,
+    ) -> bool {
+        // only `Error` can be met by code that fails to analyze
+        if let MemoryExpectation::Error { message } = self {
+            return error.is_some_and(|error| {
+                message.as_ref().is_none_or(|message| error.to_string().contains(message.as_str()))
+            });
+        }
+
+        if error.is_some() {
+            return false;
+        }
+
+        let count_blocks = |state: fn(&HeapBlockState) -> bool| {
+            heap.iter().filter(|block| state(&block.block_state)).count()
+        };
+
+        match self {
+            MemoryExpectation::Variable {
+                name,
+                value: expected,
+            } => stack.iter().any(|symbol| match symbol {
+                Symbol::Variable {
+                    name: symbol_name,
+                    value,
+                    ..
+                } if symbol_name == name => {
+                    expected.is_none()
+                        || value.as_ref().map(|value| value.trim()) == expected.as_deref()
+                }
+                _ => false,
+            }),
+
+            MemoryExpectation::Pointer { name, target } => {
+                stack.iter().any(|symbol| match symbol {
+                    Symbol::Pointer {
+                        name: symbol_name,
+                        value,
+                        allocation_type,
+                        ..
+                    } if symbol_name == name => match (target, allocation_type) {
+                        (PointerTarget::Stack { name }, AllocationType::Stack) => {
+                            matches!(
+                                value.as_deref(),
+                                Some(Symbol::Variable { name: pointee, .. })
+                                    | Some(Symbol::Pointer { name: pointee, .. }) if pointee == name
+                            )
+                        }
+                        (PointerTarget::Heap, AllocationType::Heap)
+                        | (PointerTarget::Null, AllocationType::Null)
+                        | (PointerTarget::Dangling, AllocationType::Dangling) => true,
+                        _ => false,
+                    },
+                    _ => false,
+                })
+            }
+
+            MemoryExpectation::AllocatedBlocks { count } => {
+                count_blocks(|state| matches!(state, HeapBlockState::Allocated)) == *count
+            }
+
+            MemoryExpectation::LeakedBlocks { count } => {
+                count_blocks(|state| matches!(state, HeapBlockState::Leaked)) == *count
+            }
+
+            MemoryExpectation::Error { .. } => unreachable!("errors are checked first"),
+        }
+    }
+}
+
+impl fmt::Display for MemoryExpectation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MemoryExpectation::Variable { name, value: None } => {
+                write!(f, "Variable `{}` is declared", name)
+            }
+            MemoryExpectation::Variable {
+                name,
+                value: Some(value),
+            } => write!(f, "Variable `{}` holds `{}`", name, value),
+            MemoryExpectation::Pointer { name, target } => {
+                write!(f, "Pointer `{}` points to {}", name, target)
+            }
+            MemoryExpectation::AllocatedBlocks { count } => {
+                write!(f, "{} block{} allocated on the heap", count, plural(*count))
+            }
+            MemoryExpectation::LeakedBlocks { count } => {
+                write!(f, "{} block{} leaked on the heap", count, plural(*count))
+            }
+            MemoryExpectation::Error { message: None } => write!(f, "The code has an error"),
+            MemoryExpectation::Error {
+                message: Some(message),
+            } => write!(f, "The code has an error mentioning \"{}\"", message),
+        }
+    }
+}
+
+impl fmt::Display for PointerTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PointerTarget::Stack { name } => write!(f, "`{}`", name),
+            PointerTarget::Heap => write!(f, "a block on the heap"),
+            PointerTarget::Null => write!(f, "`nullptr`"),
+            PointerTarget::Dangling => write!(f, "a freed block"),
+        }
+    }
+}
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
@@ -4,11 +4,24 @@ pub(crate) mod token;
 use rule::{get_rules, unambiguous_single_char, Rule};
 use token::{Span, Token, TokenKind};
 
+/// Splits the source code into tokens
+///
+/// # Fields
+/// - `input`: The source code
+/// - `cursor`: The position of the next token in the source code
+/// - `eof`: Whether the `EOF` token was produced
+/// - `rules`: The rules matching the tokens that aren't a single unambiguous character
+/// - `newlines`: How many newlines come before the cursor
+/// - `column`: The column of the character before the cursor, `0` right after a newline
+/// - `after_newline`: Whether the character before the cursor is a newline, or there is none
 pub(crate) struct Lexer<'input> {
     input: &'input str,
     cursor: u32,
     eof: bool,
     rules: Vec<Rule>,
+    newlines: u32,
+    column: u32,
+    after_newline: bool,
 }
 
 impl<'input> Lexer<'input> {
@@ -18,9 +31,37 @@ impl<'input> Lexer<'input> {
             cursor: 0,
             eof: false,
             rules: get_rules(),
+            newlines: 0,
+            column: 0,
+            after_newline: true,
         }
     }
 
+    /// Moves the position past the text of a token and gets where the token ends
+    ///
+    /// Positions are tracked as the tokens are produced, so finding where a token is doesn't mean counting
+    /// the lines of everything before it
+    ///
+    /// # Returns
+    /// - `(u32, u32)`: The line and the column the token ends on, the line the last character before its
+    ///   end is on, or the line before if that is a newline
+    fn advance_position(&mut self, text: &str) -> (u32, u32) {
+        for c in text.chars() {
+            if c == '\n' {
+                self.newlines += 1;
+                self.column = 0;
+            } else {
+                self.column += 1;
+            }
+        }
+
+        if let Some(last) = text.chars().last() {
+            self.after_newline = last == '\n';
+        }
+
+        (self.newlines + u32::from(!self.after_newline), self.column)
+    }
+
     pub(crate) fn next_token(&mut self, input: &str) -> Token {
         self.valid_token(input).unwrap_or_else(|| self.invalid_token(input))
     }
@@ -58,6 +99,8 @@ impl<'input> Lexer<'input> {
                 start,
                 end: len as u32,
             },
+            line: 0,
+            column: 0,
         })
     }
 
@@ -76,6 +119,8 @@ impl<'input> Lexer<'input> {
         Token {
             kind: TokenKind::Error,
             span: Span { start, end: len },
+            line: 0,
+            column: 0,
         }
     }
 }
@@ -91,15 +136,22 @@ impl<'input> Iterator for Lexer<'input> {
 
             self.eof = true;
 
+            let (line, column) = self.advance_position("");
+
             Some(Token {
                 kind: TokenKind::EOF,
                 span: Span {
                     start: self.cursor,
                     end: 0,
                 },
+                line,
+                column,
             })
         } else {
-            Some(self.next_token(&self.input[self.cursor as usize..]))
+            let mut token = self.next_token(&self.input[self.cursor as usize..]);
+            (token.line, token.column) = self.advance_position(&self.input[token.span]);
+
+            Some(token)
         }
     }
 }
@@ -17,9 +17,25 @@ fn match_regex(input: &str, r: &Regex) -> Option<u32> {
 }
 
 lazy_static! {
-    static ref FLOAT_REGEX: Regex =
-        Regex::new(r#"^((\d+(\.\d+)?)|(\.\d+))([Ee](\+|-)?\d+)?"#).unwrap();
+    // a suffix makes a `float` like `3.5f` or a `long double` like `2.0L`, it only follows a number
+    // with a fraction or an exponent since `10L` is a `long`
+    static ref FLOAT_REGEX: Regex = Regex::new(
+        r#"^((((\d+\.\d+)|(\.\d+))([Ee](\+|-)?\d+)?|\d+[Ee](\+|-)?\d+)[fFlL]?|\d+)"#
+    )
+    .unwrap();
+    static ref SHORT_REGEX: Regex = Regex::new(r#"^short(\s+int)?\b"#).unwrap();
+    static ref UNSIGNED_SHORT_REGEX: Regex = Regex::new(r#"^unsigned\s+short(\s+int)?\b"#).unwrap();
+    static ref UNSIGNED_INT_REGEX: Regex = Regex::new(r#"^unsigned(\s+int)?\b"#).unwrap();
+    static ref LONG_REGEX: Regex = Regex::new(r#"^long(\s+int)?\b"#).unwrap();
+    static ref UNSIGNED_LONG_REGEX: Regex = Regex::new(r#"^unsigned\s+long(\s+int)?\b"#).unwrap();
+    static ref LONG_LONG_REGEX: Regex = Regex::new(r#"^long\s+long(\s+int)?\b"#).unwrap();
+    static ref UNSIGNED_LONG_LONG_REGEX: Regex =
+        Regex::new(r#"^unsigned\s+long\s+long(\s+int)?\b"#).unwrap();
     static ref BOOL_REGEX: Regex = Regex::new(r#"^(true|false)"#).unwrap();
+    static ref HEX_REGEX: Regex = Regex::new(r#"^0[xX][0-9A-Fa-f]+"#).unwrap();
+    static ref STRING_REGEX: Regex = Regex::new(r#"^"[^"\n]*""#).unwrap();
+    // an escape like `'\n'` or `'\x41'` runs until the closing quote, the parser checks it is valid
+    static ref CHAR_REGEX: Regex = Regex::new(r#"^'(\\[^\n][^'\n]*|[^'\\\n])'"#).unwrap();
     static ref COMMENT_REGEX: Regex = Regex::new(r#"^//[^\n]*\n"#).unwrap();
     static ref IDENTIFIER_REGEX: Regex = Regex::new(r##"^([A-Za-z]|_)([A-Za-z]|_|\d)*"##).unwrap();
 }
@@ -30,6 +46,36 @@ pub(crate) fn get_rules() -> Vec<Rule> {
             kind: TokenKind::KwInt,
             matches: |input| match_keyword(input, "int"),
         },
+        // integer types spelled with several words, `unsigned long long` is the longest match over
+        // `unsigned long` and `unsigned`
+        Rule {
+            kind: TokenKind::KwShort,
+            matches: |input| match_regex(input, &SHORT_REGEX),
+        },
+        Rule {
+            kind: TokenKind::KwUnsignedShort,
+            matches: |input| match_regex(input, &UNSIGNED_SHORT_REGEX),
+        },
+        Rule {
+            kind: TokenKind::KwUnsignedInt,
+            matches: |input| match_regex(input, &UNSIGNED_INT_REGEX),
+        },
+        Rule {
+            kind: TokenKind::KwLong,
+            matches: |input| match_regex(input, &LONG_REGEX),
+        },
+        Rule {
+            kind: TokenKind::KwUnsignedLong,
+            matches: |input| match_regex(input, &UNSIGNED_LONG_REGEX),
+        },
+        Rule {
+            kind: TokenKind::KwLongLong,
+            matches: |input| match_regex(input, &LONG_LONG_REGEX),
+        },
+        Rule {
+            kind: TokenKind::KwUnsignedLongLong,
+            matches: |input| match_regex(input, &UNSIGNED_LONG_LONG_REGEX),
+        },
         Rule {
             kind: TokenKind::KwFloat,
             matches: |input| match_keyword(input, "float"),
@@ -46,6 +92,56 @@ pub(crate) fn get_rules() -> Vec<Rule> {
             kind: TokenKind::KwBool,
             matches: |input| match_keyword(input, "bool"),
         },
+        Rule {
+            kind: TokenKind::KwString,
+            matches: |input| {
+                match_keyword(input, "std::string").or_else(|| match_keyword(input, "string"))
+            },
+        },
+        Rule {
+            kind: TokenKind::KwVoid,
+            matches: |input| match_keyword(input, "void"),
+        },
+        Rule {
+            kind: TokenKind::KwReturn,
+            matches: |input| match_keyword(input, "return"),
+        },
+        Rule {
+            kind: TokenKind::KwUnion,
+            matches: |input| match_keyword(input, "union"),
+        },
+        Rule {
+            kind: TokenKind::KwClass,
+            matches: |input| match_keyword(input, "class"),
+        },
+        Rule {
+            kind: TokenKind::KwStruct,
+            matches: |input| match_keyword(input, "struct"),
+        },
+        Rule {
+            kind: TokenKind::KwPublic,
+            matches: |input| match_keyword(input, "public"),
+        },
+        Rule {
+            kind: TokenKind::KwPrivate,
+            matches: |input| match_keyword(input, "private"),
+        },
+        Rule {
+            kind: TokenKind::KwIf,
+            matches: |input| match_keyword(input, "if"),
+        },
+        Rule {
+            kind: TokenKind::KwElse,
+            matches: |input| match_keyword(input, "else"),
+        },
+        Rule {
+            kind: TokenKind::KwWhile,
+            matches: |input| match_keyword(input, "while"),
+        },
+        Rule {
+            kind: TokenKind::KwFor,
+            matches: |input| match_keyword(input, "for"),
+        },
         Rule {
             kind: TokenKind::New,
             matches: |input| match_keyword(input, "new"),
@@ -58,6 +154,200 @@ pub(crate) fn get_rules() -> Vec<Rule> {
             kind: TokenKind::Null,
             matches: |input| match_keyword(input, "nullptr"),
         },
+        Rule {
+            kind: TokenKind::Malloc,
+            matches: |input| match_keyword(input, "malloc"),
+        },
+        Rule {
+            kind: TokenKind::Calloc,
+            matches: |input| match_keyword(input, "calloc"),
+        },
+        Rule {
+            kind: TokenKind::Realloc,
+            matches: |input| match_keyword(input, "realloc"),
+        },
+        Rule {
+            kind: TokenKind::Free,
+            matches: |input| match_keyword(input, "free"),
+        },
+        Rule {
+            kind: TokenKind::Mmap,
+            matches: |input| match_keyword(input, "mmap"),
+        },
+        Rule {
+            kind: TokenKind::Munmap,
+            matches: |input| match_keyword(input, "munmap"),
+        },
+        Rule {
+            kind: TokenKind::Sizeof,
+            matches: |input| match_keyword(input, "sizeof"),
+        },
+        Rule {
+            kind: TokenKind::Swap,
+            matches: |input| {
+                match_keyword(input, "std::swap").or_else(|| match_keyword(input, "swap"))
+            },
+        },
+        Rule {
+            kind: TokenKind::UniquePtr,
+            matches: |input| {
+                match_keyword(input, "std::unique_ptr")
+                    .or_else(|| match_keyword(input, "unique_ptr"))
+            },
+        },
+        Rule {
+            kind: TokenKind::MakeUnique,
+            matches: |input| {
+                match_keyword(input, "std::make_unique")
+                    .or_else(|| match_keyword(input, "make_unique"))
+            },
+        },
+        Rule {
+            kind: TokenKind::SharedPtr,
+            matches: |input| {
+                match_keyword(input, "std::shared_ptr")
+                    .or_else(|| match_keyword(input, "shared_ptr"))
+            },
+        },
+        Rule {
+            kind: TokenKind::MakeShared,
+            matches: |input| {
+                match_keyword(input, "std::make_shared")
+                    .or_else(|| match_keyword(input, "make_shared"))
+            },
+        },
+        Rule {
+            kind: TokenKind::Move,
+            matches: |input| {
+                match_keyword(input, "std::move").or_else(|| match_keyword(input, "move"))
+            },
+        },
+        Rule {
+            kind: TokenKind::Peek,
+            matches: |input| match_keyword(input, "peek"),
+        },
+        Rule {
+            kind: TokenKind::Poke,
+            matches: |input| match_keyword(input, "poke"),
+        },
+        Rule {
+            kind: TokenKind::Memset,
+            matches: |input| {
+                match_keyword(input, "std::memset").or_else(|| match_keyword(input, "memset"))
+            },
+        },
+        Rule {
+            kind: TokenKind::Memcpy,
+            matches: |input| {
+                match_keyword(input, "std::memcpy").or_else(|| match_keyword(input, "memcpy"))
+            },
+        },
+        Rule {
+            kind: TokenKind::Strcpy,
+            matches: |input| {
+                match_keyword(input, "std::strcpy").or_else(|| match_keyword(input, "strcpy"))
+            },
+        },
+        Rule {
+            kind: TokenKind::Strlen,
+            matches: |input| {
+                match_keyword(input, "std::strlen").or_else(|| match_keyword(input, "strlen"))
+            },
+        },
+        Rule {
+            kind: TokenKind::StaticCast,
+            matches: |input| match_keyword(input, "static_cast"),
+        },
+        Rule {
+            kind: TokenKind::ThreadSpawn,
+            matches: |input| match_keyword(input, "thread_spawn"),
+        },
+        Rule {
+            kind: TokenKind::Mutex,
+            matches: |input| {
+                match_keyword(input, "std::mutex").or_else(|| match_keyword(input, "mutex"))
+            },
+        },
+        // `+` and `-` also start `++`, `--`, `->`, `+=` and `-=`, which are the longer matches
+        Rule {
+            kind: TokenKind::Increment,
+            matches: |input| match_keyword(input, "++"),
+        },
+        Rule {
+            kind: TokenKind::Decrement,
+            matches: |input| match_keyword(input, "--"),
+        },
+        Rule {
+            kind: TokenKind::Arrow,
+            matches: |input| match_keyword(input, "->"),
+        },
+        Rule {
+            kind: TokenKind::PlusEq,
+            matches: |input| match_keyword(input, "+="),
+        },
+        Rule {
+            kind: TokenKind::MinusEq,
+            matches: |input| match_keyword(input, "-="),
+        },
+        Rule {
+            kind: TokenKind::Plus,
+            matches: |input| match_keyword(input, "+"),
+        },
+        Rule {
+            kind: TokenKind::Minus,
+            matches: |input| match_keyword(input, "-"),
+        },
+        // `=`, `<` and `>` also start `==`, `<=` and `>=`, which are the longer matches
+        Rule {
+            kind: TokenKind::EqEq,
+            matches: |input| match_keyword(input, "=="),
+        },
+        Rule {
+            kind: TokenKind::NotEq,
+            matches: |input| match_keyword(input, "!="),
+        },
+        Rule {
+            kind: TokenKind::LessEq,
+            matches: |input| match_keyword(input, "<="),
+        },
+        Rule {
+            kind: TokenKind::GreaterEq,
+            matches: |input| match_keyword(input, ">="),
+        },
+        Rule {
+            kind: TokenKind::Eq,
+            matches: |input| match_keyword(input, "="),
+        },
+        Rule {
+            kind: TokenKind::Less,
+            matches: |input| match_keyword(input, "<"),
+        },
+        Rule {
+            kind: TokenKind::Greater,
+            matches: |input| match_keyword(input, ">"),
+        },
+        // `*` and `/` also start `*=` and `/=`, and `/` starts comments, which are the longer matches
+        Rule {
+            kind: TokenKind::AsteriskEq,
+            matches: |input| match_keyword(input, "*="),
+        },
+        Rule {
+            kind: TokenKind::SlashEq,
+            matches: |input| match_keyword(input, "/="),
+        },
+        Rule {
+            kind: TokenKind::Asterisk,
+            matches: |input| match_keyword(input, "*"),
+        },
+        Rule {
+            kind: TokenKind::Slash,
+            matches: |input| match_keyword(input, "/"),
+        },
+        // `.` also starts floats like `.5`, which are the longer match
+        Rule {
+            kind: TokenKind::Dot,
+            matches: |input| match_keyword(input, "."),
+        },
         Rule {
             kind: TokenKind::Comment,
             matches: move |input| match_regex(input, &COMMENT_REGEX),
@@ -65,11 +355,13 @@ pub(crate) fn get_rules() -> Vec<Rule> {
         Rule {
             kind: TokenKind::Int,
             matches: |input| {
-                input
-                    .char_indices()
-                    .take_while(|(_, c)| c.is_ascii_digit())
-                    .last()
-                    .map(|(pos, _)| pos as u32 + 1)
+                match_regex(input, &HEX_REGEX).or_else(|| {
+                    input
+                        .char_indices()
+                        .take_while(|(_, c)| c.is_ascii_digit())
+                        .last()
+                        .map(|(pos, _)| pos as u32 + 1)
+                })
             },
         },
         Rule {
@@ -82,13 +374,11 @@ pub(crate) fn get_rules() -> Vec<Rule> {
         },
         Rule {
             kind: TokenKind::Char,
-            matches: |input| {
-                if input.starts_with('\'') {
-                    input[1..].char_indices().nth(1).map(|(pos, _)| pos as u32 + 2)
-                } else {
-                    None
-                }
-            },
+            matches: |input| match_regex(input, &CHAR_REGEX),
+        },
+        Rule {
+            kind: TokenKind::String,
+            matches: |input| match_regex(input, &STRING_REGEX),
         },
         Rule {
             kind: TokenKind::Identifier,
@@ -99,11 +389,18 @@ pub(crate) fn get_rules() -> Vec<Rule> {
 
 pub(crate) fn unambiguous_single_char(c: char) -> Option<TokenKind> {
     Some(match c {
-        '=' => TokenKind::Eq,
         '_' => TokenKind::Underscore,
         ';' => TokenKind::SemiColon,
+        ':' => TokenKind::Colon,
+        '~' => TokenKind::Tilde,
+        ',' => TokenKind::Comma,
+        '(' => TokenKind::LParen,
+        ')' => TokenKind::RParen,
+        '{' => TokenKind::LBrace,
+        '}' => TokenKind::RBrace,
+        '[' => TokenKind::LBracket,
+        ']' => TokenKind::RBracket,
         '&' => TokenKind::Reference,
-        '*' => TokenKind::Asterisk,
         _ => return None,
     })
 }
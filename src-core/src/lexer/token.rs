@@ -9,22 +9,90 @@ use serde::{Deserialize, Serialize};
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Copy, Clone)]
 pub enum TokenKind {
     KwInt,
+    KwShort,
+    KwUnsignedShort,
+    KwUnsignedInt,
+    KwLong,
+    KwUnsignedLong,
+    KwLongLong,
+    KwUnsignedLongLong,
     KwFloat,
     KwChar,
     KwDouble,
     KwBool,
+    KwString,
+    KwVoid,
+    KwReturn,
+    KwUnion,
+    KwClass,
+    KwStruct,
+    KwPublic,
+    KwPrivate,
+    KwIf,
+    KwElse,
+    KwWhile,
+    KwFor,
 
     Reference,
     Asterisk,
+    Plus,
+    Minus,
+    Increment,
+    Decrement,
+    Slash,
+    Dot,
+    Arrow,
+    PlusEq,
+    MinusEq,
+    AsteriskEq,
+    SlashEq,
+    Less,
+    Greater,
+    LessEq,
+    GreaterEq,
+    EqEq,
+    NotEq,
     New,
     Delete,
     Null,
+    Malloc,
+    Calloc,
+    Realloc,
+    Mmap,
+    Munmap,
+    Free,
+    Sizeof,
+    Swap,
+    UniquePtr,
+    MakeUnique,
+    SharedPtr,
+    MakeShared,
+    Move,
+    Peek,
+    Poke,
+    Memset,
+    Memcpy,
+    Strcpy,
+    Strlen,
+    StaticCast,
+    ThreadSpawn,
+    Mutex,
 
     Eq,
     Underscore,
     SemiColon,
+    Colon,
+    Tilde,
+    Comma,
+    LParen,
+    RParen,
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
 
     Bool,
+    String,
     Float,
     Char,
     Int,
@@ -40,23 +108,91 @@ impl fmt::Display for TokenKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             TokenKind::KwInt => write!(f, "int"),
+            TokenKind::KwShort => write!(f, "short"),
+            TokenKind::KwUnsignedShort => write!(f, "unsigned short"),
+            TokenKind::KwUnsignedInt => write!(f, "unsigned int"),
+            TokenKind::KwLong => write!(f, "long"),
+            TokenKind::KwUnsignedLong => write!(f, "unsigned long"),
+            TokenKind::KwLongLong => write!(f, "long long"),
+            TokenKind::KwUnsignedLongLong => write!(f, "unsigned long long"),
             TokenKind::KwFloat => write!(f, "float"),
             TokenKind::KwChar => write!(f, "char"),
             TokenKind::KwDouble => write!(f, "double"),
             TokenKind::KwBool => write!(f, "bool"),
+            TokenKind::KwString => write!(f, "std::string"),
+            TokenKind::KwVoid => write!(f, "void"),
+            TokenKind::KwReturn => write!(f, "return"),
+            TokenKind::KwUnion => write!(f, "union"),
+            TokenKind::KwClass => write!(f, "class"),
+            TokenKind::KwStruct => write!(f, "struct"),
+            TokenKind::KwPublic => write!(f, "public"),
+            TokenKind::KwPrivate => write!(f, "private"),
+            TokenKind::KwIf => write!(f, "if"),
+            TokenKind::KwElse => write!(f, "else"),
+            TokenKind::KwWhile => write!(f, "while"),
+            TokenKind::KwFor => write!(f, "for"),
             TokenKind::Reference => write!(f, "&"),
             TokenKind::Asterisk => write!(f, "*"),
+            TokenKind::Plus => write!(f, "+"),
+            TokenKind::Minus => write!(f, "-"),
+            TokenKind::Increment => write!(f, "++"),
+            TokenKind::Decrement => write!(f, "--"),
+            TokenKind::Slash => write!(f, "/"),
+            TokenKind::Dot => write!(f, "."),
+            TokenKind::Arrow => write!(f, "->"),
+            TokenKind::PlusEq => write!(f, "+="),
+            TokenKind::MinusEq => write!(f, "-="),
+            TokenKind::AsteriskEq => write!(f, "*="),
+            TokenKind::SlashEq => write!(f, "/="),
+            TokenKind::Less => write!(f, "<"),
+            TokenKind::Greater => write!(f, ">"),
+            TokenKind::LessEq => write!(f, "<="),
+            TokenKind::GreaterEq => write!(f, ">="),
+            TokenKind::EqEq => write!(f, "=="),
+            TokenKind::NotEq => write!(f, "!="),
             TokenKind::New => write!(f, "new"),
             TokenKind::Delete => write!(f, "delete"),
             TokenKind::Null => write!(f, "null"),
+            TokenKind::Malloc => write!(f, "malloc"),
+            TokenKind::Calloc => write!(f, "calloc"),
+            TokenKind::Realloc => write!(f, "realloc"),
+            TokenKind::Mmap => write!(f, "mmap"),
+            TokenKind::Munmap => write!(f, "munmap"),
+            TokenKind::Free => write!(f, "free"),
+            TokenKind::Sizeof => write!(f, "sizeof"),
+            TokenKind::Swap => write!(f, "std::swap"),
+            TokenKind::UniquePtr => write!(f, "std::unique_ptr"),
+            TokenKind::MakeUnique => write!(f, "std::make_unique"),
+            TokenKind::SharedPtr => write!(f, "std::shared_ptr"),
+            TokenKind::MakeShared => write!(f, "std::make_shared"),
+            TokenKind::Move => write!(f, "std::move"),
+            TokenKind::Peek => write!(f, "peek"),
+            TokenKind::Poke => write!(f, "poke"),
+            TokenKind::Memset => write!(f, "memset"),
+            TokenKind::Memcpy => write!(f, "memcpy"),
+            TokenKind::Strcpy => write!(f, "strcpy"),
+            TokenKind::Strlen => write!(f, "strlen"),
+            TokenKind::StaticCast => write!(f, "static_cast"),
+            TokenKind::ThreadSpawn => write!(f, "thread_spawn"),
+            TokenKind::Mutex => write!(f, "std::mutex"),
             TokenKind::Eq => write!(f, "="),
             TokenKind::Underscore => write!(f, "_"),
             TokenKind::SemiColon => write!(f, ";"),
+            TokenKind::Colon => write!(f, ":"),
+            TokenKind::Tilde => write!(f, "~"),
+            TokenKind::Comma => write!(f, ","),
+            TokenKind::LParen => write!(f, "("),
+            TokenKind::RParen => write!(f, ")"),
+            TokenKind::LBrace => write!(f, "{{"),
+            TokenKind::RBrace => write!(f, "}}"),
+            TokenKind::LBracket => write!(f, "["),
+            TokenKind::RBracket => write!(f, "]"),
             TokenKind::Comment => write!(f, "comment"),
             TokenKind::Int => write!(f, "int"),
             TokenKind::Float => write!(f, "float"),
             TokenKind::Bool => write!(f, "bool"),
             TokenKind::Char => write!(f, "char"),
+            TokenKind::String => write!(f, "string"),
             TokenKind::Identifier => write!(f, "identifier"),
             TokenKind::Error => write!(f, "error"),
             TokenKind::Whitespace => write!(f, "whitespace"),
@@ -71,26 +207,6 @@ pub(crate) struct Span {
     pub(crate) end: u32,
 }
 
-impl Span {
-    fn get_line_number(&self, input: &str) -> usize {
-        input[..(self.start + self.end) as usize].lines().count()
-    }
-
-    fn get_column_number(&self, input: &str) -> usize {
-        let mut column = 0;
-
-        for c in input[..(self.start + self.end) as usize].chars() {
-            if c == '\n' {
-                column = 0;
-            } else {
-                column += 1;
-            }
-        }
-
-        column
-    }
-}
-
 impl From<Span> for Range<usize> {
     fn from(span: Span) -> Self {
         span.start as usize..span.end as usize
@@ -115,10 +231,19 @@ impl Index<Span> for str {
     }
 }
 
+/// Represents a token of the source code
+///
+/// # Fields
+/// - `kind`: What the token is
+/// - `span`: Where the token is in the source code
+/// - `line`: The line the token ends on
+/// - `column`: The column the token ends at
 #[derive(PartialEq, Eq, Clone, Copy, Hash, Serialize)]
 pub struct Token {
     pub(crate) kind: TokenKind,
     pub(crate) span: Span,
+    pub(crate) line: u32,
+    pub(crate) column: u32,
 }
 
 impl Token {
@@ -131,12 +256,12 @@ impl Token {
         &input[self.span]
     }
 
-    pub(crate) fn get_column_number(&self, input: &str) -> usize {
-        self.span.get_column_number(input)
+    pub(crate) fn get_column_number(&self) -> usize {
+        self.column as usize
     }
 
-    pub(crate) fn get_line_number(&self, input: &str) -> usize {
-        self.span.get_line_number(input)
+    pub(crate) fn get_line_number(&self) -> usize {
+        self.line as usize
     }
 }
 
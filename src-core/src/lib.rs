@@ -1,4 +1,21 @@
 pub mod analyzer;
+pub mod assessment;
+pub mod capabilities;
+pub mod diagnostics;
 pub mod error;
+pub mod export;
+pub mod layout;
+pub mod lesson;
 pub mod lexer;
 pub mod parser;
+pub mod pipeline;
+pub mod placement_statistics;
+pub mod preprocess;
+pub(crate) mod rng;
+pub mod session;
+pub(crate) mod stack;
+#[cfg(feature = "typescript")]
+pub mod typescript;
+
+/// Version of the core, frontends report it and compare it against the version they were built with
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
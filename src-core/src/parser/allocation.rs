@@ -0,0 +1,530 @@
+use super::{ast, Parser};
+use crate::analyzer::r#type::Type;
+use crate::error::{Error::ParserError, Result};
+use crate::lexer::token::{Token, TokenKind};
+
+impl<'input, I> Parser<'input, I>
+where
+    I: Iterator<Item = Token>,
+{
+    /// Parses the cast C code puts in front of an allocation, like the `(int*)` in
+    /// `(int*)malloc(sizeof(int))`, or in front of a pointer, like the `(int*)` in `(int*)p`
+    ///
+    /// # Returns
+    /// - [Result](crate::error::Result): A result containing either:
+    ///    - `Option<TokenKind>`: The type cast to, `None` if there is no cast
+    ///    - [Error](crate::error::Error): An error if the cast is malformed
+    pub(crate) fn allocation_cast(&mut self) -> Result<Option<TokenKind>> {
+        if self.peek() != TokenKind::LParen {
+            return Ok(None);
+        }
+
+        self.consume(TokenKind::LParen)?;
+
+        let cast_type = match self.peek() {
+            TokenKind::KwVoid => {
+                self.consume(TokenKind::KwVoid)?;
+                TokenKind::KwVoid
+            }
+            _ => self.allocated_type()?,
+        };
+
+        self.consume(TokenKind::Asterisk)?;
+
+        if self.peek() == TokenKind::Asterisk {
+            let line_number = self.tokens.peek().map_or(0, |token| token.get_line_number());
+
+            let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+            return Err(ParserError(
+                "Casts to pointers to pointers are not supported yet".to_string(),
+                line_number,
+                column_number,
+            ));
+        }
+
+        self.consume(TokenKind::RParen)?;
+
+        Ok(Some(cast_type))
+    }
+
+    /// Parses a C-style allocation such as `malloc(3 * sizeof(int))` or `calloc(3, sizeof(int))`, after
+    /// the optional cast
+    ///
+    /// # Arguments
+    /// - `cast_type`: The type the allocation was cast to, if any
+    ///
+    /// # Returns
+    /// - [Result](crate::error::Result): A result containing either:
+    ///    - A tuple with how the block is allocated, the type of its values and the column of the size.
+    ///      A size that is a number of bytes gives the type cast to, or `void` if there is no cast
+    ///    - [Error](crate::error::Error): An error if the allocation is malformed
+    pub(crate) fn c_allocation(
+        &mut self,
+        cast_type: Option<TokenKind>,
+    ) -> Result<(ast::Allocation, TokenKind, usize)> {
+        let line_number = self.tokens.peek().map_or(0, |token| token.get_line_number());
+
+        let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+        let allocator = self.peek();
+
+        if !matches!(allocator, TokenKind::Malloc | TokenKind::Calloc) {
+            return Err(ParserError(
+                format!("Expected `malloc` or `calloc` but found `{}`", allocator),
+                line_number,
+                column_number,
+            ));
+        }
+
+        self.consume(allocator)?;
+        self.consume(TokenKind::LParen)?;
+
+        let size_column = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+        // `calloc` takes the number of values separately from their size
+        let mut count = 1;
+
+        if allocator == TokenKind::Calloc {
+            (count, _) = self.allocation_size()?;
+            self.consume(TokenKind::Comma)?;
+        }
+
+        let (size_count, size_type) = self.allocation_size()?;
+        count = count.checked_mul(size_count).ok_or_else(|| {
+            ParserError(
+                "The size of the allocation is too large".to_string(),
+                line_number,
+                size_column,
+            )
+        })?;
+
+        self.consume(TokenKind::RParen)?;
+
+        let allocation = match allocator {
+            TokenKind::Malloc => ast::Allocation::Malloc { count },
+            _ => ast::Allocation::Calloc { count },
+        };
+
+        // a number of bytes is as many values of the type cast to as fit in them, and stays a number of
+        // bytes for a `void*`
+        let (allocation, size_type) = match (size_type, cast_type) {
+            (Some(size_type), _) => (allocation, size_type),
+            (None, None | Some(TokenKind::KwVoid)) => (allocation, TokenKind::KwVoid),
+            (None, Some(cast_type)) => {
+                let pointer_size = self.architecture.pointer_size();
+
+                (
+                    values_in_bytes(allocation, cast_type, pointer_size, line_number, size_column)?,
+                    cast_type,
+                )
+            }
+        };
+
+        if let Some(cast_type) =
+            cast_type.filter(|cast_type| *cast_type != size_type && *cast_type != TokenKind::KwVoid)
+        {
+            return Err(ParserError(
+                format!("Cannot cast an allocation of `{}` to `{}*`", size_type, cast_type),
+                line_number,
+                column_number,
+            ));
+        }
+
+        if allocation.count() == 0 {
+            return Err(ParserError(
+                "Allocating 0 bytes is not supported".to_string(),
+                line_number,
+                size_column,
+            ));
+        }
+
+        Ok((allocation, size_type, size_column))
+    }
+
+    /// Parses the number of values `new[]` allocates, like the `[4]` in `new int[4]`, after the type
+    ///
+    /// # Returns
+    /// - [Result](crate::error::Result): A result containing either:
+    ///    - [Allocation](crate::parser::ast::Allocation): `NewArray` with the number of values, or `New`
+    ///      if there is none
+    ///    - [Error](crate::error::Error): An error if the number isn't a positive integer literal
+    pub(crate) fn new_allocation(&mut self) -> Result<ast::Allocation> {
+        if self.peek() != TokenKind::LBracket {
+            return Ok(ast::Allocation::New);
+        }
+
+        self.consume(TokenKind::LBracket)?;
+
+        let line_number = self.tokens.peek().map_or(0, |token| token.get_line_number());
+
+        let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+        let count = match self.next() {
+            Some(token) if token.kind == TokenKind::Int => self.text(token).parse().ok(),
+            _ => None,
+        };
+
+        let Some(count) = count.filter(|count: &usize| *count > 0) else {
+            return Err(ParserError(
+                "The number of values allocated with `new[]` must be a positive integer literal"
+                    .to_string(),
+                line_number,
+                column_number,
+            ));
+        };
+
+        self.consume(TokenKind::RBracket)?;
+
+        Ok(ast::Allocation::NewArray { count })
+    }
+
+    /// Parses an anonymous memory mapping like `mmap(4096)`, after the optional cast
+    ///
+    /// The size is a number of bytes, `sizeof(T)`, or a count multiplied by `sizeof(T)`, and is rounded up
+    /// to whole pages once the region is mapped
+    ///
+    /// # Returns
+    /// - [Result](crate::error::Result): A result containing either:
+    ///    - A tuple with the count and the type given to `sizeof`, the type is `void` when the size is a
+    ///      number of bytes
+    ///    - [Error](crate::error::Error): An error if the mapping is malformed
+    pub(crate) fn memory_map(&mut self) -> Result<(usize, TokenKind)> {
+        let line_number = self.tokens.peek().map_or(0, |token| token.get_line_number());
+
+        self.consume(TokenKind::Mmap)?;
+        self.consume(TokenKind::LParen)?;
+
+        let size_column = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+        let (count, size_type) = self.allocation_size()?;
+
+        self.consume(TokenKind::RParen)?;
+
+        if count == 0 {
+            return Err(ParserError(
+                "Mapping 0 bytes is not supported".to_string(),
+                line_number,
+                size_column,
+            ));
+        }
+
+        Ok((count, size_type.unwrap_or(TokenKind::KwVoid)))
+    }
+
+    /// Parses an unmapping like `munmap(m, 4096);`
+    ///
+    /// The length is a number of bytes, `sizeof(T)`, or a count multiplied by `sizeof(T)`, like the size
+    /// passed to `mmap`
+    ///
+    /// # Arguments
+    /// - `line_number`: The line of the statement
+    ///
+    /// # Returns
+    /// - [Result](crate::error::Result): A result containing either:
+    ///    - [Statement](crate::parser::ast::Statement): The unmapping
+    ///    - [Error](crate::error::Error): An error if the unmapping is malformed
+    pub(crate) fn memory_unmap(&mut self, line_number: usize) -> Result<ast::Statement> {
+        let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+        self.consume(TokenKind::Munmap)?;
+        self.consume(TokenKind::LParen)?;
+
+        let pointer = match self.next() {
+            Some(token) if token.kind == TokenKind::Identifier => token,
+            Some(token) => {
+                return Err(ParserError(
+                    format!("Expected pointer passed to `munmap` but found `{}`", token.kind),
+                    line_number,
+                    column_number,
+                ));
+            }
+            None => {
+                return Err(ParserError(
+                    "Expected pointer passed to `munmap` but found none".to_string(),
+                    line_number,
+                    column_number,
+                ));
+            }
+        };
+
+        self.consume(TokenKind::Comma)?;
+
+        let length_column = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+        let (count, size_type) = self.allocation_size()?;
+
+        self.consume(TokenKind::RParen)?;
+        self.consume(TokenKind::SemiColon)?;
+
+        Ok(ast::Statement::MemoryUnmap {
+            pointer_name: self.text(pointer).to_string(),
+            count,
+            size_type: size_type.unwrap_or(TokenKind::KwVoid),
+            line: line_number,
+            pointer_ident_column: pointer.get_column_number(),
+            length_column,
+        })
+    }
+
+    /// Parses the rest of `p = realloc(p, size);`, after the optional cast
+    ///
+    /// The size is a number of bytes, `sizeof(T)`, or a count multiplied by `sizeof(T)`
+    ///
+    /// # Arguments
+    /// - `pointer`: The pointer being assigned to
+    /// - `cast_type`: The type the allocation was cast to, if any
+    /// - `line_number`: The line of the statement
+    ///
+    /// # Returns
+    /// - [Result](crate::error::Result): A result containing either:
+    ///    - [Statement](crate::parser::ast::Statement): The reallocation
+    ///    - [Error](crate::error::Error): An error if the reallocation is malformed
+    pub(crate) fn c_reallocation(
+        &mut self,
+        pointer: Token,
+        cast_type: Option<TokenKind>,
+        line_number: usize,
+    ) -> Result<ast::Statement> {
+        let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+        self.consume(TokenKind::Realloc)?;
+        self.consume(TokenKind::LParen)?;
+
+        let source = match self.next() {
+            Some(token) if token.kind == TokenKind::Identifier => token,
+            Some(token) => {
+                return Err(ParserError(
+                    format!("Expected pointer passed to `realloc` but found `{}`", token.kind),
+                    line_number,
+                    column_number,
+                ));
+            }
+            None => {
+                return Err(ParserError(
+                    "Expected pointer passed to `realloc` but found none".to_string(),
+                    line_number,
+                    column_number,
+                ));
+            }
+        };
+
+        self.consume(TokenKind::Comma)?;
+
+        let size_column = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+        let (count, size_type) = self.allocation_size()?;
+
+        self.consume(TokenKind::RParen)?;
+        self.consume(TokenKind::SemiColon)?;
+
+        let cast_type = cast_type.filter(|cast_type| *cast_type != TokenKind::KwVoid);
+
+        if let Some((cast_type, size_type)) =
+            cast_type.zip(size_type).filter(|(cast_type, size_type)| cast_type != size_type)
+        {
+            return Err(ParserError(
+                format!("Cannot cast an allocation of `{}` to `{}*`", size_type, cast_type),
+                line_number,
+                column_number,
+            ));
+        }
+
+        // a number of bytes cast to a type is as many values of it as fit in them, like with `malloc`
+        let count = match (size_type, cast_type) {
+            (None, Some(cast_type)) => {
+                let pointer_size = self.architecture.pointer_size();
+                whole_values(count, "realloc", cast_type, pointer_size, line_number, size_column)?
+            }
+            _ => count,
+        };
+
+        Ok(ast::Statement::PointerReallocation {
+            pointer_name: self.text(pointer).to_string(),
+            source_name: self.text(source).to_string(),
+            count,
+            new_type: size_type.or(cast_type),
+            line: line_number,
+            pointer_ident_column: pointer.get_column_number(),
+            source_column: source.get_column_number(),
+            size_column,
+        })
+    }
+
+    /// Parses the size passed to an allocation: a number of bytes, `sizeof(T)`, or a product of them
+    ///
+    /// # Returns
+    /// - [Result](crate::error::Result): A result containing either:
+    ///    - A tuple with the count and the type given to `sizeof`, the size is the count of bytes when
+    ///      there is no `sizeof`
+    ///    - [Error](crate::error::Error): An error if the size is malformed
+    fn allocation_size(&mut self) -> Result<(usize, Option<TokenKind>)> {
+        let mut count: usize = 1;
+        let mut size_type = None;
+
+        loop {
+            let line_number = self.tokens.peek().map_or(0, |token| token.get_line_number());
+
+            let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+            match self.peek() {
+                TokenKind::Int => {
+                    let token = self.next().expect("the token was peeked");
+
+                    let factor = self.text(token).parse::<usize>().map_err(|_| {
+                        ParserError(
+                            format!("Invalid allocation size `{}`", self.text(token)),
+                            line_number,
+                            column_number,
+                        )
+                    })?;
+
+                    count = count.checked_mul(factor).ok_or_else(|| {
+                        ParserError(
+                            "The size of the allocation is too large".to_string(),
+                            line_number,
+                            column_number,
+                        )
+                    })?;
+                }
+
+                TokenKind::Sizeof if size_type.is_none() => {
+                    self.consume(TokenKind::Sizeof)?;
+                    self.consume(TokenKind::LParen)?;
+                    size_type = Some(self.allocated_type()?);
+                    self.consume(TokenKind::RParen)?;
+                }
+
+                _ => {
+                    return Err(ParserError(
+                        format!("Expected allocation size but found `{}`", self.peek()),
+                        line_number,
+                        column_number,
+                    ));
+                }
+            }
+
+            if self.peek() != TokenKind::Asterisk {
+                return Ok((count, size_type));
+            }
+
+            self.consume(TokenKind::Asterisk)?;
+        }
+    }
+
+    /// Parses one of the types a block can be allocated for
+    pub(crate) fn allocated_type(&mut self) -> Result<TokenKind> {
+        let line_number = self.tokens.peek().map_or(0, |token| token.get_line_number());
+
+        let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+        match self.peek() {
+            allocated_type @ TokenKind::KwInt
+            | allocated_type @ TokenKind::KwShort
+            | allocated_type @ TokenKind::KwUnsignedShort
+            | allocated_type @ TokenKind::KwUnsignedInt
+            | allocated_type @ TokenKind::KwLong
+            | allocated_type @ TokenKind::KwUnsignedLong
+            | allocated_type @ TokenKind::KwLongLong
+            | allocated_type @ TokenKind::KwUnsignedLongLong
+            | allocated_type @ TokenKind::KwChar
+            | allocated_type @ TokenKind::KwFloat
+            | allocated_type @ TokenKind::KwDouble
+            | allocated_type @ TokenKind::KwBool => {
+                self.consume(allocated_type)?;
+                Ok(allocated_type)
+            }
+
+            _ => Err(ParserError(
+                format!("Expected type but found `{}`", self.peek()),
+                line_number,
+                column_number,
+            )),
+        }
+    }
+}
+
+/// Converts a block allocated with a number of bytes, like `malloc(8)`, into one of the values of the type
+/// it's for, which the bytes have to hold a whole number of
+///
+/// # Arguments
+/// - `allocation`: The allocation, with the number of bytes as its count
+/// - `value_type`: The type of the values the block holds
+/// - `pointer_size`: The size of a pointer in bytes, which a `long` is as wide as
+/// - `line`: The line of the allocation
+/// - `column`: The column of the size
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - [Allocation](crate::parser::ast::Allocation): The allocation, with the number of values as its count
+///    - [Error](crate::error::Error): An error if the bytes don't hold a whole number of values
+pub(crate) fn values_in_bytes(
+    allocation: ast::Allocation,
+    value_type: TokenKind,
+    pointer_size: usize,
+    line: usize,
+    column: usize,
+) -> Result<ast::Allocation> {
+    Ok(match allocation {
+        ast::Allocation::Calloc { count } => ast::Allocation::Calloc {
+            count: whole_values(count, "calloc", value_type, pointer_size, line, column)?,
+        },
+        allocation => ast::Allocation::Malloc {
+            count: whole_values(
+                allocation.count(),
+                "malloc",
+                value_type,
+                pointer_size,
+                line,
+                column,
+            )?,
+        },
+    })
+}
+
+/// Gets how many values of a type a number of bytes passed to an allocator holds
+///
+/// # Arguments
+/// - `bytes`: The number of bytes
+/// - `allocator`: The function the bytes are passed to, like `malloc`
+/// - `value_type`: The type of the values
+/// - `pointer_size`: The size of a pointer in bytes, which a `long` is as wide as
+/// - `line`: The line of the allocation
+/// - `column`: The column of the size
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - `usize`: The number of values
+///    - [Error](crate::error::Error): An error if the bytes don't hold a whole number of values
+fn whole_values(
+    bytes: usize,
+    allocator: &str,
+    value_type: TokenKind,
+    pointer_size: usize,
+    line: usize,
+    column: usize,
+) -> Result<usize> {
+    let size = match Type::from_token(value_type) {
+        Ok(Type::Void | Type::String) | Err(_) => {
+            return Err(ParserError(
+                format!("The size of a block for a pointer to {} must use `sizeof`", value_type),
+                line,
+                column,
+            ));
+        }
+        Ok(value_type) => value_type.get_variable_size(pointer_size),
+    };
+
+    if !bytes.is_multiple_of(size) {
+        return Err(ParserError(
+            format!(
+                "The {} bytes passed to `{}` aren't a multiple of {}, the size of `{}`",
+                bytes, allocator, size, value_type
+            ),
+            line,
+            column,
+        ));
+    }
+
+    Ok(bytes / size)
+}
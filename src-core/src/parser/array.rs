@@ -0,0 +1,262 @@
+use super::{ast, Parser};
+use crate::error::{Error::ParserError, Result};
+use crate::lexer::token::{Token, TokenKind};
+
+/// How many dimensions an array can have, a row and a column
+const MAX_DIMENSIONS: usize = 2;
+
+impl<'input, I> Parser<'input, I>
+where
+    I: Iterator<Item = Token>,
+{
+    /// Parses the rest of an array declaration like `int m[3][4] = {{1, 2}, {3}};`, starting at the
+    /// first `[`
+    ///
+    /// # Arguments
+    /// - `var_type`: The type of the elements
+    /// - `ident`: The name of the array
+    /// - `line_number`: The line of the declaration
+    ///
+    /// # Returns
+    /// - [Result](crate::error::Result): A result containing either:
+    ///    - [Statement](crate::parser::ast::Statement): The declaration
+    ///    - [Error](crate::error::Error): An error if a dimension or the initializer is malformed
+    pub(crate) fn array_declaration(
+        &mut self,
+        var_type: TokenKind,
+        ident: Token,
+        line_number: usize,
+    ) -> Result<ast::Statement> {
+        let name = self.text(ident).to_string();
+        let var_ident_column = ident.get_column_number();
+
+        if var_type == TokenKind::KwString {
+            return Err(ParserError(
+                "Arrays of `std::string` are not supported yet".to_string(),
+                line_number,
+                var_ident_column,
+            ));
+        }
+
+        if var_type == TokenKind::KwVoid {
+            return Err(ParserError(
+                format!("Cannot declare array `{}` of `void`", name),
+                line_number,
+                var_ident_column,
+            ));
+        }
+
+        let mut dimensions = Vec::new();
+        // the column of the first `[]` when the size of the array is left to its initializer
+        let mut inferred_column = None;
+
+        while self.peek() == TokenKind::LBracket {
+            self.consume(TokenKind::LBracket)?;
+
+            let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+            let dimension = match self.next() {
+                Some(token) if token.kind == TokenKind::Int => self.text(token).parse().ok(),
+                // only the first dimension can be left out, like `int m[][2]`
+                Some(token) if token.kind == TokenKind::RBracket && dimensions.is_empty() => {
+                    inferred_column = Some(column_number);
+                    dimensions.push(0);
+                    continue;
+                }
+                Some(token) if token.kind == TokenKind::RBracket => {
+                    return Err(ParserError(
+                        format!("The size of array `{}` must be given", name),
+                        line_number,
+                        column_number,
+                    ));
+                }
+                _ => None,
+            };
+
+            let Some(dimension) = dimension.filter(|dimension: &usize| *dimension > 0) else {
+                return Err(ParserError(
+                    format!("The size of array `{}` must be a positive integer literal", name),
+                    line_number,
+                    column_number,
+                ));
+            };
+
+            self.consume(TokenKind::RBracket)?;
+            dimensions.push(dimension);
+        }
+
+        if dimensions.len() > MAX_DIMENSIONS {
+            return Err(ParserError(
+                "Arrays with more than two dimensions are not supported yet".to_string(),
+                line_number,
+                var_ident_column,
+            ));
+        }
+
+        // the initializer is either copied like `= {1, 2}` or given directly like `{1, 2}`
+        if self.peek() == TokenKind::Eq {
+            self.consume(TokenKind::Eq)?;
+        }
+
+        let values = match (self.peek(), inferred_column) {
+            (TokenKind::LBrace, _) => {
+                Some(self.array_initializer(&name, &mut dimensions, line_number)?)
+            }
+            (_, Some(column_number)) => {
+                return Err(ParserError(
+                    format!("The size of array `{}` must be given", name),
+                    line_number,
+                    column_number,
+                ));
+            }
+            (_, None) => None,
+        };
+
+        self.end_declarator(var_type)?;
+
+        Ok(ast::Statement::ArrayDeclaration {
+            var_type,
+            var_name: name,
+            dimensions,
+            values,
+            line: line_number,
+            var_ident_column,
+        })
+    }
+
+    /// Parses the rest of an assignment to an element like `m[1][2] = 9;`, starting at the first `[`
+    pub(crate) fn array_assignment(
+        &mut self,
+        ident: Token,
+        line_number: usize,
+    ) -> Result<ast::Statement> {
+        let index_column = self.tokens.peek().map_or(0, |token| token.get_column_number());
+        let mut indices = Vec::new();
+
+        while self.peek() == TokenKind::LBracket {
+            self.consume(TokenKind::LBracket)?;
+            indices.push(self.parse_expression()?);
+            self.consume(TokenKind::RBracket)?;
+        }
+
+        let var_name = self.text(ident).to_string();
+        let new_value = self.assigned_value(ast::Expr::Index {
+            var_name: var_name.clone(),
+            indices: indices.clone(),
+        })?;
+        self.consume(TokenKind::SemiColon)?;
+
+        Ok(ast::Statement::ArrayAssignment {
+            var_name,
+            indices,
+            new_value: Box::new(new_value),
+            line: line_number,
+            var_ident_column: ident.get_column_number(),
+            index_column,
+        })
+    }
+
+    /// Parses the brace initializer of an array, flattened row by row
+    ///
+    /// The rows of a two dimensional array are either braced on their own like `{{1, 2}, {3}}` or
+    /// written one after another like `{1, 2, 3}`, and the elements left out are `None`. A first
+    /// dimension of `0` was left out like `int a[] = {1, 2}`, and is set to as many rows as the
+    /// initializer fills
+    fn array_initializer(
+        &mut self,
+        name: &str,
+        dimensions: &mut [usize],
+        line_number: usize,
+    ) -> Result<Vec<Option<ast::Expr>>> {
+        let inferred = dimensions.first() == Some(&0);
+        let columns = match dimensions.len() {
+            1 => 1,
+            _ => dimensions.last().copied().unwrap_or(1),
+        };
+        let len = match inferred {
+            true => usize::MAX,
+            false => dimensions.iter().product(),
+        };
+        let mut values = Vec::new();
+
+        let brace_column = self.tokens.peek().map_or(0, |token| token.get_column_number());
+        self.consume(TokenKind::LBrace)?;
+
+        let braced_rows = dimensions.len() > 1 && self.peek() == TokenKind::LBrace;
+
+        while !matches!(self.peek(), TokenKind::RBrace | TokenKind::EOF) {
+            let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+            if braced_rows {
+                let row = self.initializer_list()?;
+
+                if row.len() > columns {
+                    return Err(ParserError(
+                        format!("Too many initializers for a row of array `{}`", name),
+                        line_number,
+                        column_number,
+                    ));
+                }
+
+                let padding = columns - row.len();
+                values.extend(row.into_iter().map(Some));
+                values.extend((0..padding).map(|_| None));
+            } else {
+                values.push(Some(self.parse_expression()?));
+            }
+
+            if values.len() > len {
+                return Err(ParserError(
+                    format!("Too many initializers for array `{}`", name),
+                    line_number,
+                    column_number,
+                ));
+            }
+
+            if self.peek() != TokenKind::Comma {
+                break;
+            }
+
+            self.consume(TokenKind::Comma)?;
+        }
+
+        self.consume(TokenKind::RBrace)?;
+
+        if inferred {
+            if values.is_empty() {
+                return Err(ParserError(
+                    format!("The initializer of array `{}` must have at least one element", name),
+                    line_number,
+                    brace_column,
+                ));
+            }
+
+            dimensions[0] = values.len().div_ceil(columns);
+        }
+
+        values.resize_with(dimensions.iter().product(), || None);
+
+        Ok(values)
+    }
+
+    /// Parses a braced list of expressions like `{1, 2}`, which may be empty
+    pub(crate) fn initializer_list(&mut self) -> Result<Vec<ast::Expr>> {
+        self.consume(TokenKind::LBrace)?;
+
+        let mut values = Vec::new();
+
+        while !matches!(self.peek(), TokenKind::RBrace | TokenKind::EOF) {
+            values.push(self.parse_expression()?);
+
+            if self.peek() != TokenKind::Comma {
+                break;
+            }
+
+            self.consume(TokenKind::Comma)?;
+        }
+
+        self.consume(TokenKind::RBrace)?;
+
+        Ok(values)
+    }
+}
@@ -23,6 +23,57 @@ pub enum Expr {
         op: TokenKind,
         expr: Box<Expr>,
     },
+    SizeofType(TokenKind),
+    Sizeof(Box<Expr>),
+    Call {
+        function_name: String,
+        arguments: Vec<Expr>,
+    },
+    Peek(Box<Expr>),
+    // `strlen(s)`, the number of characters before the null terminator `s` points to
+    Strlen(String),
+    // `(int*)p`, a pointer converted to point to another type
+    PointerCast {
+        pointee_type: TokenKind,
+        expr: Box<Expr>,
+    },
+    // `(int)x` or `static_cast<int>(x)`, a value converted to another type
+    Cast {
+        cast_type: TokenKind,
+        expr: Box<Expr>,
+    },
+    // `v.i`, a member of a union or a field of an object
+    Member {
+        var_name: String,
+        member: String,
+    },
+    // `a[1]` or `p[2]`, an element of an array or of the heap block a pointer points to
+    Index {
+        var_name: String,
+        indices: Vec<Expr>,
+    },
+    // `p->x`, a field of the object a pointer points to
+    PointerMember {
+        pointer_name: String,
+        member: String,
+    },
+    // `c.get()`, a call to a method of an object whose value is used
+    MethodCall {
+        var_name: String,
+        method: String,
+        arguments: Vec<Expr>,
+    },
+}
+
+impl Expr {
+    /// Shows the operand of a prefix or postfix operator, in parentheses when it is an infix operation
+    /// like the `p + 1` in `*(p + 1)`
+    fn operand(&self) -> String {
+        match self {
+            Expr::InfixOp { .. } => format!("({})", self),
+            _ => self.to_string(),
+        }
+    }
 }
 
 impl fmt::Display for Expr {
@@ -30,21 +81,61 @@ impl fmt::Display for Expr {
         match self {
             Expr::Literal(lit) => write!(f, "{}", lit),
             Expr::Ident(ident) => write!(f, "{}", ident),
-            Expr::AddressOf(expr) => write!(f, "&{}", expr),
-            Expr::Dereference(expr) => write!(f, "*{}", expr),
-            Expr::PrefixOp { op, expr } => write!(f, "{}{}", op, expr),
+            Expr::AddressOf(expr) => write!(f, "&{}", expr.operand()),
+            Expr::Dereference(expr) => write!(f, "*{}", expr.operand()),
+            Expr::PrefixOp { op, expr } => write!(f, "{}{}", op, expr.operand()),
             Expr::InfixOp { op, lhs, rhs } => write!(f, "{} {} {}", lhs, op, rhs),
-            Expr::PostfixOp { op, expr } => write!(f, "{}{}", expr, op),
+            Expr::PostfixOp { op, expr } => write!(f, "{}{}", expr.operand(), op),
+            Expr::SizeofType(size_type) => write!(f, "sizeof({})", size_type),
+            Expr::Sizeof(expr) => write!(f, "sizeof({})", expr),
+            Expr::Peek(address) => write!(f, "peek({})", address),
+            Expr::Strlen(name) => write!(f, "strlen({})", name),
+            Expr::PointerCast { pointee_type, expr } => {
+                write!(f, "({}*){}", pointee_type, expr.operand())
+            }
+            Expr::Cast { cast_type, expr } => write!(f, "({}){}", cast_type, expr.operand()),
+            Expr::Member { var_name, member } => write!(f, "{}.{}", var_name, member),
+            Expr::Index { var_name, indices } => {
+                let indices: String = indices.iter().map(|index| format!("[{}]", index)).collect();
+
+                write!(f, "{}{}", var_name, indices)
+            }
+            Expr::PointerMember {
+                pointer_name,
+                member,
+            } => write!(f, "{}->{}", pointer_name, member),
+            Expr::Call {
+                function_name,
+                arguments,
+            } => {
+                let arguments: Vec<String> =
+                    arguments.iter().map(|argument| argument.to_string()).collect();
+
+                write!(f, "{}({})", function_name, arguments.join(", "))
+            }
+            Expr::MethodCall {
+                var_name,
+                method,
+                arguments,
+            } => {
+                let arguments: Vec<String> =
+                    arguments.iter().map(|argument| argument.to_string()).collect();
+
+                write!(f, "{}.{}({})", var_name, method, arguments.join(", "))
+            }
         }
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum Lit {
-    Int(i64),
+    Int(i128),
     Float(f64),
+    // `3.5f`, a `float` where a floating point literal without a suffix is a `double`
+    Float32(f32),
     Char(char),
     Bool(bool),
+    Str(String),
 }
 
 impl Lit {
@@ -52,13 +143,16 @@ impl Lit {
         match self {
             Lit::Int(i) => i.to_string(),
             Lit::Float(fl) => fl.to_string(),
+            // the value a `double` would get from the `float`, like `0.1f` is 0.10000000149011612
+            Lit::Float32(fl) => (*fl as f64).to_string(),
             Lit::Char(c) => c.to_string(),
             Lit::Bool(b) => b.to_string(),
+            Lit::Str(s) => format!("\"{}\"", s),
         }
     }
 
     pub(crate) fn from_str(s: &str) -> Result<Lit> {
-        if let Ok(i) = s.parse::<i64>() {
+        if let Ok(i) = s.parse::<i128>() {
             return Ok(Lit::Int(i));
         }
 
@@ -74,6 +168,10 @@ impl Lit {
             return Ok(Lit::Bool(s == "true"));
         }
 
+        if let Some(s) = s.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+            return Ok(Lit::Str(s.to_owned()));
+        }
+
         Err(Error::ParserError("Invalid literal".to_string(), 0, 0))
     }
 }
@@ -83,8 +181,10 @@ impl fmt::Display for Lit {
         match self {
             Lit::Int(i) => write!(f, "{}", i),
             Lit::Float(fl) => write!(f, "{}", fl),
+            Lit::Float32(fl) => write!(f, "{}", *fl as f64),
             Lit::Char(c) => write!(f, "{}", c),
             Lit::Bool(b) => write!(f, "{}", b),
+            Lit::Str(s) => write!(f, "\"{}\"", s),
         }
     }
 }
@@ -116,7 +216,10 @@ pub enum Statement {
 
     PointerDeclaration {
         base_type: TokenKind,
+        // number of `*` in the pointer's type
+        indirection: usize,
         pointer_name: String,
+        // `&x`, a cast like `(int*)v`, or a copy of another pointer like `p` or `p + 1`
         value: Box<Expr>,
         line: usize,
         pointer_ident_column: usize,
@@ -124,13 +227,39 @@ pub enum Statement {
 
     PointerDeclarationHeap {
         base_type: TokenKind,
+        allocation: Allocation,
+        // the type the block is allocated for, `void` when its size is a number of bytes
+        new_type: TokenKind,
+        pointer_name: String,
+        line: usize,
+        pointer_ident_column: usize,
+    },
+
+    // `int* p = (int*)mmap(4096);`, an anonymous mapping in an area of its own instead of the heap
+    MemoryMap {
+        base_type: TokenKind,
+        count: usize,
+        // the type given to `sizeof` in the size, `void` when the size is a number of bytes
+        size_type: TokenKind,
+        pointer_name: String,
+        line: usize,
+        pointer_ident_column: usize,
+    },
+
+    // `munmap(m, 4096);`, unmapping the region mapped for a pointer with `mmap`
+    MemoryUnmap {
         pointer_name: String,
+        count: usize,
+        // the type given to `sizeof` in the length, `void` when the length is a number of bytes
+        size_type: TokenKind,
         line: usize,
         pointer_ident_column: usize,
+        length_column: usize,
     },
 
     PointerDeclarationNull {
         base_type: TokenKind,
+        indirection: usize,
         pointer_name: String,
         line: usize,
         pointer_ident_column: usize,
@@ -145,6 +274,7 @@ pub enum Statement {
 
     PointerAssignmentHeap {
         pointer_name: String,
+        allocation: Allocation,
         new_type: TokenKind,
         line: usize,
         pointer_ident_column: usize,
@@ -157,6 +287,19 @@ pub enum Statement {
         pointer_ident_column: usize,
     },
 
+    PointerReallocation {
+        pointer_name: String,
+        // the pointer passed to `realloc`, which has to be the one assigned to
+        source_name: String,
+        // the size is `count` bytes, or `count` values of `new_type` when `sizeof` is used
+        count: usize,
+        new_type: Option<TokenKind>,
+        line: usize,
+        pointer_ident_column: usize,
+        source_column: usize,
+        size_column: usize,
+    },
+
     Deref {
         pointer_name: String,
         new_value: Box<Expr>,
@@ -167,7 +310,687 @@ pub enum Statement {
 
     Delete {
         pointer_name: String,
+        release: Release,
+        line: usize,
+        pointer_ident_column: usize,
+    },
+
+    UniquePointerDeclaration {
+        base_type: TokenKind,
+        pointer_name: String,
+        value: UniqueValue,
+        line: usize,
+        pointer_ident_column: usize,
+        value_column: usize,
+    },
+
+    UniquePointerAssignment {
+        pointer_name: String,
+        value: UniqueValue,
         line: usize,
         pointer_ident_column: usize,
+        value_column: usize,
     },
+
+    SharedPointerDeclaration {
+        base_type: TokenKind,
+        pointer_name: String,
+        value: SharedValue,
+        line: usize,
+        pointer_ident_column: usize,
+        value_column: usize,
+    },
+
+    SharedPointerAssignment {
+        pointer_name: String,
+        value: SharedValue,
+        line: usize,
+        pointer_ident_column: usize,
+        value_column: usize,
+    },
+
+    Swap {
+        lhs: String,
+        rhs: String,
+        line: usize,
+        lhs_column: usize,
+        rhs_column: usize,
+    },
+
+    Poke {
+        address: Box<Expr>,
+        value: Box<Expr>,
+        line: usize,
+        address_column: usize,
+        value_column: usize,
+    },
+
+    // `memset(p, 0, 4 * sizeof(int))`, every byte of a range set to the same value
+    Memset {
+        target: String,
+        value: Box<Expr>,
+        size: Box<Expr>,
+        line: usize,
+        target_column: usize,
+        value_column: usize,
+        size_column: usize,
+    },
+
+    // `memcpy(q, p, 4 * sizeof(int))`, a range of bytes copied into another
+    Memcpy {
+        destination: String,
+        source: String,
+        size: Box<Expr>,
+        line: usize,
+        destination_column: usize,
+        source_column: usize,
+        size_column: usize,
+    },
+
+    // `strcpy(s, "hello")`, a null-terminated string copied along with its terminator
+    Strcpy {
+        destination: String,
+        source: Box<Expr>,
+        line: usize,
+        destination_column: usize,
+        source_column: usize,
+    },
+
+    FunctionDefinition {
+        // `None` for `void` functions
+        return_type: Option<TokenKind>,
+        function_name: String,
+        parameters: Vec<Parameter>,
+        body: Vec<Statement>,
+        line: usize,
+        function_ident_column: usize,
+    },
+
+    FunctionCall {
+        function_name: String,
+        arguments: Vec<Expr>,
+        line: usize,
+        function_ident_column: usize,
+    },
+
+    Return {
+        value: Option<Box<Expr>>,
+        line: usize,
+        return_column: usize,
+    },
+
+    Block {
+        statements: Vec<Statement>,
+        line: usize,
+        brace_column: usize,
+        // line of the closing `}`, where the block's locals go out of scope
+        end_line: usize,
+    },
+
+    If {
+        condition: Box<Expr>,
+        // a `Block` of the statements run when the condition holds
+        then_branch: Box<Statement>,
+        // a `Block`, or another `If` for `else if`
+        else_branch: Option<Box<Statement>>,
+        line: usize,
+        if_column: usize,
+    },
+
+    // a `for` loop is a `Block` of its initialization and a `While` running its update
+    While {
+        condition: Box<Expr>,
+        // a `Block` of the statements run every iteration
+        body: Box<Statement>,
+        // the update of a `for` loop, run after the body every iteration
+        update: Option<Box<Statement>>,
+        line: usize,
+        loop_column: usize,
+    },
+
+    ThreadSpawn {
+        statements: Vec<Statement>,
+        line: usize,
+        spawn_column: usize,
+        // line of the closing `}`, where the thread ends
+        end_line: usize,
+    },
+
+    UnionDefinition {
+        union_name: String,
+        members: Vec<UnionMember>,
+        line: usize,
+        union_ident_column: usize,
+    },
+
+    // `Counter c(3);` passes the arguments to the constructor of a class, `Point p = {1, 2};` or
+    // `Point p{1, 2};` initializes the fields in order, or the first member of a union
+    UnionDeclaration {
+        union_name: String,
+        var_name: String,
+        arguments: Vec<Expr>,
+        initializer: Option<Vec<Expr>>,
+        line: usize,
+        var_ident_column: usize,
+    },
+
+    MemberAssignment {
+        var_name: String,
+        member: String,
+        new_value: Box<Expr>,
+        line: usize,
+        var_ident_column: usize,
+        member_column: usize,
+    },
+
+    // `p->x = 3;` writes a field of the object a pointer points to
+    PointerMemberAssignment {
+        pointer_name: String,
+        member: String,
+        new_value: Box<Expr>,
+        line: usize,
+        pointer_ident_column: usize,
+        member_column: usize,
+    },
+
+    ClassDefinition {
+        class_name: String,
+        fields: Vec<ClassField>,
+        methods: Vec<Method>,
+        line: usize,
+        class_ident_column: usize,
+    },
+
+    // `c.add(2);`
+    MethodCall {
+        var_name: String,
+        method: String,
+        arguments: Vec<Expr>,
+        line: usize,
+        var_ident_column: usize,
+        method_column: usize,
+    },
+
+    // `int m[3][4] = {{1, 2}, {3}};`, the brace initializer is flattened row by row with `None` for the
+    // elements it leaves out, `None` without an initializer
+    ArrayDeclaration {
+        var_type: TokenKind,
+        var_name: String,
+        dimensions: Vec<usize>,
+        values: Option<Vec<Option<Expr>>>,
+        line: usize,
+        var_ident_column: usize,
+    },
+
+    // `m[1][2] = 9;`
+    ArrayAssignment {
+        var_name: String,
+        indices: Vec<Expr>,
+        new_value: Box<Expr>,
+        line: usize,
+        var_ident_column: usize,
+        index_column: usize,
+    },
+
+    MutexDeclaration {
+        mutex_name: String,
+        line: usize,
+        mutex_ident_column: usize,
+    },
+
+    // `m.lock();`
+    Lock {
+        mutex_name: String,
+        line: usize,
+        mutex_ident_column: usize,
+    },
+
+    // `Point* p = new Point(1, 2);`, the arguments are passed to the constructor of the class
+    ObjectAllocation {
+        class_name: String,
+        pointer_name: String,
+        arguments: Vec<Expr>,
+        line: usize,
+        pointer_ident_column: usize,
+    },
+
+    // `m.unlock();`
+    Unlock {
+        mutex_name: String,
+        line: usize,
+        mutex_ident_column: usize,
+    },
+
+    // a statement outside the supported subset, skipped when parsing permissively, from the start of its
+    // first token to the end of its last
+    Ignored {
+        reason: String,
+        line: usize,
+        column: usize,
+        end_line: usize,
+        end_column: usize,
+    },
+}
+
+/// How a heap block is allocated, which decides what it holds at first
+///
+/// - `New`: `new int`
+/// - `NewArray`: `new int[count]`, the block holds garbage
+/// - `Malloc`: `(int*)malloc(count * sizeof(int))`, the block holds garbage
+/// - `Calloc`: `(int*)calloc(count, sizeof(int))`, the block is zeroed
+///
+/// `count` is the number of values of the pointer's type the block holds, or its number of bytes when
+/// the size is given without `sizeof`
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Allocation {
+    New,
+    NewArray { count: usize },
+    Malloc { count: usize },
+    Calloc { count: usize },
+}
+
+impl Allocation {
+    /// Gets the number of values of the pointer's type the block holds
+    ///
+    /// # Returns
+    /// - `usize`: The number of values, `1` for `new`
+    pub(crate) fn count(&self) -> usize {
+        match self {
+            Allocation::New => 1,
+            Allocation::NewArray { count }
+            | Allocation::Malloc { count }
+            | Allocation::Calloc { count } => *count,
+        }
+    }
+
+    /// Gets the form of `delete` a block allocated this way has to be released with
+    ///
+    /// # Returns
+    /// - `Option<Release>`: `delete` for `new` and `delete[]` for `new[]`, `None` for `malloc` and
+    ///   `calloc`, whose blocks are released with `free`
+    pub(crate) fn delete_form(&self) -> Option<Release> {
+        match self {
+            Allocation::New => Some(Release::Delete),
+            Allocation::NewArray { .. } => Some(Release::DeleteArray),
+            Allocation::Malloc { .. } | Allocation::Calloc { .. } => None,
+        }
+    }
+}
+
+/// How a heap block is released
+///
+/// - `Delete`: `delete p;`
+/// - `DeleteArray`: `delete[] p;`
+/// - `Free`: `free(p);`
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Release {
+    Delete,
+    DeleteArray,
+    Free,
+}
+
+impl fmt::Display for Release {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Release::Delete => write!(f, "delete"),
+            Release::DeleteArray => write!(f, "delete[]"),
+            Release::Free => write!(f, "free"),
+        }
+    }
+}
+
+/// What a `std::unique_ptr` is initialized or assigned with
+///
+/// - `MakeUnique`: `std::make_unique<int>(value)`, a new block holding `value`, or zero without one
+/// - `Move`: `std::move(source)`, the block `source` owns, leaving `source` null
+/// - `Copy`: `source`, which is rejected since a `std::unique_ptr` can't be copied
+/// - `Null`: `nullptr`, or no initializer at all
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum UniqueValue {
+    MakeUnique {
+        make_type: TokenKind,
+        value: Option<Box<Expr>>,
+    },
+    Move {
+        source: String,
+    },
+    Copy {
+        source: String,
+    },
+    Null,
+}
+
+/// What a `std::shared_ptr` is initialized or assigned with
+///
+/// - `MakeShared`: `std::make_shared<int>(value)`, a new block holding `value` along with a control block
+///   counting its owners
+/// - `Copy`: `source`, the block `source` owns, which now has one more owner
+/// - `Move`: `std::move(source)`, the block `source` owns, leaving `source` null
+/// - `Null`: `nullptr`, or no initializer at all
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum SharedValue {
+    MakeShared {
+        make_type: TokenKind,
+        value: Option<Box<Expr>>,
+    },
+    Copy {
+        source: String,
+    },
+    Move {
+        source: String,
+    },
+    Null,
+}
+
+/// A parameter of a function definition, parameters are passed by value and a pointer parameter gets a
+/// copy of the address it is passed
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Parameter {
+    pub(crate) param_type: TokenKind,
+    // number of `*` in the parameter's type, `0` for values
+    pub(crate) indirection: usize,
+    pub(crate) param_name: String,
+    pub(crate) param_ident_column: usize,
+}
+
+/// A member of a union definition, all members of a union share the same storage
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UnionMember {
+    pub(crate) member_type: TokenKind,
+    pub(crate) member_name: String,
+    pub(crate) member_ident_column: usize,
+}
+
+/// A field of a class definition, every object of the class has its own
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ClassField {
+    pub(crate) field_type: TokenKind,
+    pub(crate) field_name: String,
+    pub(crate) field_ident_column: usize,
+    // `1` for a field like `int* data;`
+    pub(crate) indirection: usize,
+    // declared after `public:`, fields are private by default like in C++
+    pub(crate) public: bool,
+}
+
+/// A method of a class definition, a [FunctionDefinition](Statement::FunctionDefinition) that runs on an
+/// object and reads its fields by name
+///
+/// The constructor is the method named like the class and the destructor the one named like the class
+/// after a `~`, like `~Counter`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Method {
+    pub(crate) definition: Statement,
+    pub(crate) public: bool,
+}
+
+impl Statement {
+    /// Gets the line the statement is on
+    ///
+    /// # Returns
+    /// - `usize`: The line number
+    pub(crate) fn line(&self) -> usize {
+        match self {
+            Statement::VariableDeclaration { line, .. }
+            | Statement::VariableDeclarationWithoutAssignment { line, .. }
+            | Statement::VariableAssignment { line, .. }
+            | Statement::PointerDeclaration { line, .. }
+            | Statement::PointerDeclarationHeap { line, .. }
+            | Statement::MemoryMap { line, .. }
+            | Statement::MemoryUnmap { line, .. }
+            | Statement::PointerDeclarationNull { line, .. }
+            | Statement::PointerAssignment { line, .. }
+            | Statement::PointerAssignmentHeap { line, .. }
+            | Statement::PointerAssignmentNull { line, .. }
+            | Statement::PointerReallocation { line, .. }
+            | Statement::Deref { line, .. }
+            | Statement::Delete { line, .. }
+            | Statement::UniquePointerDeclaration { line, .. }
+            | Statement::UniquePointerAssignment { line, .. }
+            | Statement::SharedPointerDeclaration { line, .. }
+            | Statement::SharedPointerAssignment { line, .. }
+            | Statement::Swap { line, .. }
+            | Statement::Poke { line, .. }
+            | Statement::Memset { line, .. }
+            | Statement::Memcpy { line, .. }
+            | Statement::Strcpy { line, .. }
+            | Statement::FunctionDefinition { line, .. }
+            | Statement::FunctionCall { line, .. }
+            | Statement::Return { line, .. }
+            | Statement::Block { line, .. }
+            | Statement::If { line, .. }
+            | Statement::While { line, .. }
+            | Statement::ThreadSpawn { line, .. }
+            | Statement::UnionDefinition { line, .. }
+            | Statement::UnionDeclaration { line, .. }
+            | Statement::MemberAssignment { line, .. }
+            | Statement::PointerMemberAssignment { line, .. }
+            | Statement::ArrayDeclaration { line, .. }
+            | Statement::ArrayAssignment { line, .. }
+            | Statement::ClassDefinition { line, .. }
+            | Statement::MethodCall { line, .. }
+            | Statement::ObjectAllocation { line, .. }
+            | Statement::MutexDeclaration { line, .. }
+            | Statement::Lock { line, .. }
+            | Statement::Unlock { line, .. }
+            | Statement::Ignored { line, .. } => *line,
+        }
+    }
+
+    /// Gets the expressions the statement evaluates, in the order they are evaluated
+    ///
+    /// Function definitions, blocks and threads evaluate theirs when their statements run, so they have
+    /// none, conditionals only have their condition and loops evaluate theirs on every iteration so they
+    /// have none either
+    ///
+    /// # Returns
+    /// - `Vec<&mut Expr>`: The expressions
+    pub(crate) fn expressions_mut(&mut self) -> Vec<&mut Expr> {
+        match self {
+            Statement::VariableDeclaration { value, .. } => vec![value.as_mut()],
+            Statement::VariableAssignment { new_value, .. }
+            | Statement::Deref { new_value, .. }
+            | Statement::MemberAssignment { new_value, .. }
+            | Statement::PointerMemberAssignment { new_value, .. } => vec![new_value.as_mut()],
+            Statement::FunctionCall { arguments, .. }
+            | Statement::MethodCall { arguments, .. }
+            | Statement::ObjectAllocation { arguments, .. } => arguments.iter_mut().collect(),
+            Statement::UnionDeclaration {
+                arguments,
+                initializer,
+                ..
+            } => arguments.iter_mut().chain(initializer.iter_mut().flatten()).collect(),
+            Statement::Return {
+                value: Some(value), ..
+            } => vec![value.as_mut()],
+            Statement::UniquePointerDeclaration {
+                value:
+                    UniqueValue::MakeUnique {
+                        value: Some(value), ..
+                    },
+                ..
+            }
+            | Statement::UniquePointerAssignment {
+                value:
+                    UniqueValue::MakeUnique {
+                        value: Some(value), ..
+                    },
+                ..
+            }
+            | Statement::SharedPointerDeclaration {
+                value:
+                    SharedValue::MakeShared {
+                        value: Some(value), ..
+                    },
+                ..
+            }
+            | Statement::SharedPointerAssignment {
+                value:
+                    SharedValue::MakeShared {
+                        value: Some(value), ..
+                    },
+                ..
+            } => vec![value.as_mut()],
+            Statement::Poke { address, value, .. } => vec![address.as_mut(), value.as_mut()],
+            Statement::Memset { value, size, .. } => vec![value.as_mut(), size.as_mut()],
+            Statement::Memcpy { size, .. } => vec![size.as_mut()],
+            Statement::Strcpy { source, .. } => vec![source.as_mut()],
+            Statement::ArrayDeclaration {
+                values: Some(values),
+                ..
+            } => values.iter_mut().flatten().collect(),
+            Statement::ArrayAssignment {
+                indices, new_value, ..
+            } => indices.iter_mut().chain([new_value.as_mut()]).collect(),
+            Statement::If { condition, .. } => vec![condition.as_mut()],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Gets the column errors about the statement point at, usually the name it declares or uses
+    ///
+    /// # Returns
+    /// - `usize`: The column number
+    pub(crate) fn column(&self) -> usize {
+        match self {
+            Statement::VariableDeclaration {
+                var_ident_column, ..
+            }
+            | Statement::VariableDeclarationWithoutAssignment {
+                var_ident_column, ..
+            }
+            | Statement::VariableAssignment {
+                var_ident_column, ..
+            }
+            | Statement::UnionDeclaration {
+                var_ident_column, ..
+            }
+            | Statement::MemberAssignment {
+                var_ident_column, ..
+            }
+            | Statement::ArrayDeclaration {
+                var_ident_column, ..
+            }
+            | Statement::ArrayAssignment {
+                var_ident_column, ..
+            }
+            | Statement::MethodCall {
+                var_ident_column, ..
+            } => *var_ident_column,
+
+            Statement::PointerDeclaration {
+                pointer_ident_column,
+                ..
+            }
+            | Statement::PointerDeclarationHeap {
+                pointer_ident_column,
+                ..
+            }
+            | Statement::MemoryMap {
+                pointer_ident_column,
+                ..
+            }
+            | Statement::MemoryUnmap {
+                pointer_ident_column,
+                ..
+            }
+            | Statement::PointerDeclarationNull {
+                pointer_ident_column,
+                ..
+            }
+            | Statement::PointerAssignment {
+                pointer_ident_column,
+                ..
+            }
+            | Statement::PointerAssignmentHeap {
+                pointer_ident_column,
+                ..
+            }
+            | Statement::PointerAssignmentNull {
+                pointer_ident_column,
+                ..
+            }
+            | Statement::PointerReallocation {
+                pointer_ident_column,
+                ..
+            }
+            | Statement::Deref {
+                pointer_ident_column,
+                ..
+            }
+            | Statement::Delete {
+                pointer_ident_column,
+                ..
+            }
+            | Statement::UniquePointerDeclaration {
+                pointer_ident_column,
+                ..
+            }
+            | Statement::UniquePointerAssignment {
+                pointer_ident_column,
+                ..
+            }
+            | Statement::SharedPointerDeclaration {
+                pointer_ident_column,
+                ..
+            }
+            | Statement::SharedPointerAssignment {
+                pointer_ident_column,
+                ..
+            }
+            | Statement::ObjectAllocation {
+                pointer_ident_column,
+                ..
+            }
+            | Statement::PointerMemberAssignment {
+                pointer_ident_column,
+                ..
+            } => *pointer_ident_column,
+
+            Statement::Swap { lhs_column, .. } => *lhs_column,
+            Statement::Poke { address_column, .. } => *address_column,
+            Statement::Memset { target_column, .. } => *target_column,
+            Statement::Memcpy {
+                destination_column, ..
+            }
+            | Statement::Strcpy {
+                destination_column, ..
+            } => *destination_column,
+
+            Statement::FunctionDefinition {
+                function_ident_column,
+                ..
+            }
+            | Statement::FunctionCall {
+                function_ident_column,
+                ..
+            } => *function_ident_column,
+
+            Statement::Return { return_column, .. } => *return_column,
+            Statement::Block { brace_column, .. } => *brace_column,
+            Statement::If { if_column, .. } => *if_column,
+            Statement::While { loop_column, .. } => *loop_column,
+            Statement::ThreadSpawn { spawn_column, .. } => *spawn_column,
+
+            Statement::UnionDefinition {
+                union_ident_column, ..
+            } => *union_ident_column,
+
+            Statement::ClassDefinition {
+                class_ident_column, ..
+            } => *class_ident_column,
+
+            Statement::MutexDeclaration {
+                mutex_ident_column, ..
+            }
+            | Statement::Lock {
+                mutex_ident_column, ..
+            }
+            | Statement::Unlock {
+                mutex_ident_column, ..
+            } => *mutex_ident_column,
+
+            Statement::Ignored { column, .. } => *column,
+        }
+    }
 }
@@ -0,0 +1,301 @@
+use super::{ast, Parser};
+use crate::error::{Error::ParserError, Result};
+use crate::lexer::token::{Token, TokenKind};
+
+impl<'input, I> Parser<'input, I>
+where
+    I: Iterator<Item = Token>,
+{
+    /// Parses a class definition like `class Counter { public: int count; void add(int n) { ... } };`,
+    /// or a struct, which is a class whose members are public by default
+    ///
+    /// Objects of the class are declared like unions are, `Counter c;`, or with the arguments of its
+    /// constructor like `Counter c(3);`
+    pub(crate) fn class_definition(&mut self, line_number: usize) -> Result<ast::Statement> {
+        let keyword = self.peek();
+        self.consume(keyword)?;
+
+        let class_ident = self.union_identifier("class name", line_number)?;
+        let class_name = self.text(class_ident).to_string();
+
+        self.consume(TokenKind::LBrace)?;
+
+        let mut fields = Vec::new();
+        let mut methods = Vec::new();
+        // members of a class are private until an access specifier says otherwise, those of a struct
+        // public
+        let mut public = keyword == TokenKind::KwStruct;
+
+        while !matches!(self.peek(), TokenKind::RBrace | TokenKind::EOF) {
+            if let access @ (TokenKind::KwPublic | TokenKind::KwPrivate) = self.peek() {
+                self.consume(access)?;
+                self.consume(TokenKind::Colon)?;
+                public = access == TokenKind::KwPublic;
+
+                continue;
+            }
+
+            match self.class_member(&class_name, public)? {
+                ClassMember::Field(field) => fields.push(field),
+                ClassMember::Method(method) => methods.push(method),
+            }
+        }
+
+        self.consume(TokenKind::RBrace)?;
+        self.consume(TokenKind::SemiColon)?;
+
+        Ok(ast::Statement::ClassDefinition {
+            class_name,
+            fields,
+            methods,
+            line: line_number,
+            class_ident_column: class_ident.get_column_number(),
+        })
+    }
+
+    /// Parses the rest of a call to a method like `c.add(2);`, starting at the `(`
+    pub(crate) fn method_call(
+        &mut self,
+        ident: Token,
+        method: Token,
+        line_number: usize,
+    ) -> Result<ast::Statement> {
+        let arguments = self.arguments()?;
+        self.consume(TokenKind::SemiColon)?;
+
+        Ok(ast::Statement::MethodCall {
+            var_name: self.text(ident).to_string(),
+            method: self.text(method).to_string(),
+            arguments,
+            line: line_number,
+            var_ident_column: ident.get_column_number(),
+            method_column: method.get_column_number(),
+        })
+    }
+
+    /// Parses the rest of an object allocated on the heap like `Point* p = new Point;`, starting at the
+    /// `*`, the arguments of its constructor are passed like `new Point(1, 2)`
+    pub(crate) fn object_allocation(
+        &mut self,
+        class_ident: Token,
+        line_number: usize,
+    ) -> Result<ast::Statement> {
+        self.consume(TokenKind::Asterisk)?;
+
+        let pointer_ident = self.union_identifier("pointer name", line_number)?;
+
+        self.consume(TokenKind::Eq)?;
+        self.consume(TokenKind::New)?;
+
+        let new_ident = self.union_identifier("class name after `new`", line_number)?;
+
+        // without inheritance a pointer to a class can only point to an object of that class
+        if self.text(new_ident) != self.text(class_ident) {
+            return Err(ParserError(
+                format!(
+                    "Expected `new {}` but found `new {}`",
+                    self.text(class_ident),
+                    self.text(new_ident)
+                ),
+                line_number,
+                new_ident.get_column_number(),
+            ));
+        }
+
+        let arguments = match self.peek() {
+            TokenKind::LParen => self.arguments()?,
+            _ => Vec::new(),
+        };
+
+        self.consume(TokenKind::SemiColon)?;
+
+        Ok(ast::Statement::ObjectAllocation {
+            class_name: self.text(class_ident).to_string(),
+            pointer_name: self.text(pointer_ident).to_string(),
+            arguments,
+            line: line_number,
+            pointer_ident_column: pointer_ident.get_column_number(),
+        })
+    }
+
+    /// Parses the rest of an assignment to a field of an object through a pointer like `p->x = 3;`,
+    /// starting at the `->`
+    pub(crate) fn pointer_member_assignment(
+        &mut self,
+        ident: Token,
+        line_number: usize,
+    ) -> Result<ast::Statement> {
+        self.consume(TokenKind::Arrow)?;
+
+        let member = self.union_identifier("member name", line_number)?;
+
+        if self.peek() == TokenKind::LParen {
+            return Err(ParserError(
+                format!(
+                    "Calling `{}` through pointer `{}` is not supported yet",
+                    self.text(member),
+                    self.text(ident)
+                ),
+                line_number,
+                member.get_column_number(),
+            ));
+        }
+
+        let pointer_name = self.text(ident).to_string();
+        let member_name = self.text(member).to_string();
+        let new_value = self.assigned_value(ast::Expr::PointerMember {
+            pointer_name: pointer_name.clone(),
+            member: member_name.clone(),
+        })?;
+        self.consume(TokenKind::SemiColon)?;
+
+        Ok(ast::Statement::PointerMemberAssignment {
+            pointer_name,
+            member: member_name,
+            new_value: Box::new(new_value),
+            line: line_number,
+            pointer_ident_column: ident.get_column_number(),
+            member_column: member.get_column_number(),
+        })
+    }
+
+    /// Parses a field like `int count;` or `int* data;`, a method like `int get() { return count; }`, or
+    /// the constructor `Counter(int n) { ... }` or destructor `~Counter() { ... }` of the class
+    fn class_member(&mut self, class_name: &str, public: bool) -> Result<ClassMember> {
+        let line_number = self.tokens.peek().map_or(0, |token| token.get_line_number());
+
+        let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+        if matches!(self.peek(), TokenKind::Identifier | TokenKind::Tilde) {
+            return self.special_member(class_name, public, line_number);
+        }
+
+        let member_type = match self.peek() {
+            member_type @ TokenKind::KwInt
+            | member_type @ TokenKind::KwShort
+            | member_type @ TokenKind::KwUnsignedShort
+            | member_type @ TokenKind::KwUnsignedInt
+            | member_type @ TokenKind::KwLong
+            | member_type @ TokenKind::KwUnsignedLong
+            | member_type @ TokenKind::KwLongLong
+            | member_type @ TokenKind::KwUnsignedLongLong
+            | member_type @ TokenKind::KwChar
+            | member_type @ TokenKind::KwFloat
+            | member_type @ TokenKind::KwDouble
+            | member_type @ TokenKind::KwBool
+            | member_type @ TokenKind::KwVoid => {
+                self.consume(member_type)?;
+                member_type
+            }
+
+            _ => {
+                return Err(ParserError(
+                    format!("Expected member type but found `{}`", self.peek()),
+                    line_number,
+                    column_number,
+                ));
+            }
+        };
+
+        let mut indirection = 0;
+
+        while self.peek() == TokenKind::Asterisk {
+            self.consume(TokenKind::Asterisk)?;
+            indirection += 1;
+        }
+
+        let ident = self.union_identifier("member name", line_number)?;
+
+        if self.peek() == TokenKind::LParen {
+            if indirection > 0 {
+                return Err(ParserError(
+                    "Methods returning pointers are not supported yet".to_string(),
+                    line_number,
+                    column_number,
+                ));
+            }
+
+            // `void` methods don't return anything
+            let return_type = Some(member_type).filter(|t| *t != TokenKind::KwVoid);
+
+            return Ok(ClassMember::Method(ast::Method {
+                definition: self.function_definition(return_type, ident, line_number)?,
+                public,
+            }));
+        }
+
+        if member_type == TokenKind::KwVoid && indirection == 0 {
+            return Err(ParserError(
+                format!("Field `{}` can't be `void`", self.text(ident)),
+                line_number,
+                ident.get_column_number(),
+            ));
+        }
+
+        self.consume(TokenKind::SemiColon)?;
+
+        Ok(ClassMember::Field(ast::ClassField {
+            field_type: member_type,
+            field_name: self.text(ident).to_string(),
+            field_ident_column: ident.get_column_number(),
+            indirection,
+            public,
+        }))
+    }
+
+    /// Parses the constructor or the destructor of a class, which have no return type
+    fn special_member(
+        &mut self,
+        class_name: &str,
+        public: bool,
+        line_number: usize,
+    ) -> Result<ClassMember> {
+        let destructor = self.peek() == TokenKind::Tilde;
+
+        if destructor {
+            self.consume(TokenKind::Tilde)?;
+        }
+
+        let ident = self.union_identifier("class name", line_number)?;
+
+        if self.text(ident) != class_name || self.peek() != TokenKind::LParen {
+            return Err(ParserError(
+                if destructor {
+                    format!("Expected `~{}` but found `~{}`", class_name, self.text(ident))
+                } else {
+                    format!("Expected member type but found `{}`", self.text(ident))
+                },
+                line_number,
+                ident.get_column_number(),
+            ));
+        }
+
+        let mut definition = self.function_definition(None, ident, line_number)?;
+
+        if let ast::Statement::FunctionDefinition {
+            function_name,
+            parameters,
+            ..
+        } = &mut definition
+        {
+            if destructor && !parameters.is_empty() {
+                return Err(ParserError(
+                    format!("Destructor `~{}` can't take parameters", class_name),
+                    line_number,
+                    ident.get_column_number(),
+                ));
+            }
+
+            if destructor {
+                *function_name = format!("~{}", class_name);
+            }
+        }
+
+        Ok(ClassMember::Method(ast::Method { definition, public }))
+    }
+}
+
+enum ClassMember {
+    Field(ast::ClassField),
+    Method(ast::Method),
+}
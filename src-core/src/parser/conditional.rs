@@ -0,0 +1,84 @@
+use super::{ast, Parser};
+use crate::error::Result;
+use crate::lexer::token::{Token, TokenKind};
+
+impl<'input, I> Parser<'input, I>
+where
+    I: Iterator<Item = Token>,
+{
+    /// Parses a conditional like `if (x < 3) { ... } else { ... }`, an `else if` is parsed as a conditional
+    /// in the `else` branch
+    pub(crate) fn if_statement(
+        &mut self,
+        line_number: usize,
+        column_number: usize,
+    ) -> Result<ast::Statement> {
+        self.consume(TokenKind::KwIf)?;
+        self.consume(TokenKind::LParen)?;
+        let condition = self.parse_expression()?;
+        self.consume(TokenKind::RParen)?;
+
+        let then_branch = self.branch()?;
+
+        if self.peek() != TokenKind::KwElse {
+            return Ok(ast::Statement::If {
+                condition: Box::new(condition),
+                then_branch: Box::new(then_branch),
+                else_branch: None,
+                line: line_number,
+                if_column: column_number,
+            });
+        }
+
+        self.consume(TokenKind::KwElse)?;
+
+        let else_branch = match self.peek() {
+            TokenKind::KwIf => {
+                let else_line = self.tokens.peek().map_or(0, |token| token.get_line_number());
+
+                let else_column = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+                self.if_statement(else_line, else_column)?
+            }
+            _ => self.branch()?,
+        };
+
+        Ok(ast::Statement::If {
+            condition: Box::new(condition),
+            then_branch: Box::new(then_branch),
+            else_branch: Some(Box::new(else_branch)),
+            line: line_number,
+            if_column: column_number,
+        })
+    }
+
+    /// Parses a branch of a conditional or the body of a loop as a block, a branch without braces like
+    /// `if (x) y = 2;` is a block of its single statement, which is a scope of its own like in C++
+    pub(crate) fn branch(&mut self) -> Result<ast::Statement> {
+        let line_number = self.tokens.peek().map_or(0, |token| token.get_line_number());
+
+        let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+        if self.peek() == TokenKind::LBrace {
+            let (statements, end_line) = self.block()?;
+
+            return Ok(ast::Statement::Block {
+                statements,
+                line: line_number,
+                brace_column: column_number,
+                end_line,
+            });
+        }
+
+        let statements = self.statements_or_ignored()?;
+
+        let end_line = statements.last().map_or(line_number, |statement| statement.line());
+
+        Ok(ast::Statement::Block {
+            statements,
+            line: line_number,
+            brace_column: column_number,
+            end_line,
+        })
+    }
+}
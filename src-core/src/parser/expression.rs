@@ -7,16 +7,72 @@ where
     I: Iterator<Item = Token>,
 {
     pub(crate) fn parse_expression(&mut self) -> Result<ast::Expr> {
-        let line_number = self.tokens.peek().map_or(0, |token| token.get_line_number(&self.input));
+        self.expression(0)
+    }
+
+    /// Parses an expression whose infix operators bind at least as tightly as `min_binding_power`
+    ///
+    /// # Arguments
+    /// - `min_binding_power`: The binding power an operator needs to extend the expression
+    ///
+    /// # Returns
+    /// - [Result](crate::error::Result): A result containing either:
+    ///    - [Expr](crate::parser::ast::Expr): The parsed expression
+    ///    - [Error](crate::error::Error): An error if the expression is malformed
+    fn expression(&mut self, min_binding_power: u8) -> Result<ast::Expr> {
+        let mut lhs = self.nested(Self::prefix_expression)?;
+
+        loop {
+            let op = self.peek();
+
+            let Some((left_binding_power, right_binding_power)) = infix_binding_power(op) else {
+                break;
+            };
+
+            if left_binding_power < min_binding_power {
+                break;
+            }
 
-        let column_number =
-            self.tokens.peek().map_or(0, |token| token.get_column_number(&self.input));
+            self.consume(op)?;
+            let rhs = self.nested(|parser| parser.expression(right_binding_power))?;
+
+            lhs = ast::Expr::InfixOp {
+                op,
+                lhs: Box::new(lhs),
+                rhs: Box::new(rhs),
+            };
+        }
+
+        Ok(lhs)
+    }
+
+    /// Parses the pointer converted by a cast like `(int*)p`, after the cast
+    ///
+    /// # Arguments
+    /// - `pointee_type`: The type the pointer is cast to point to
+    ///
+    /// # Returns
+    /// - [Result](crate::error::Result): A result containing either:
+    ///    - [Expr](crate::parser::ast::Expr): The cast
+    ///    - [Error](crate::error::Error): An error if the pointer is malformed
+    pub(crate) fn pointer_cast(&mut self, pointee_type: TokenKind) -> Result<ast::Expr> {
+        Ok(ast::Expr::PointerCast {
+            pointee_type,
+            expr: Box::new(self.expression(PREFIX_BINDING_POWER)?),
+        })
+    }
+
+    fn prefix_expression(&mut self) -> Result<ast::Expr> {
+        let line_number = self.tokens.peek().map_or(0, |token| token.get_line_number());
+
+        let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
 
         match self.peek() {
             lit @ TokenKind::Char
             | lit @ TokenKind::Float
             | lit @ TokenKind::Int
-            | lit @ TokenKind::Bool => {
+            | lit @ TokenKind::Bool
+            | lit @ TokenKind::String => {
                 let literal_text = {
                     let literal_token = self.next().unwrap();
                     self.text(literal_token)
@@ -24,7 +80,16 @@ where
 
                 let lit = match lit {
                     TokenKind::Int => {
-                        let literal: i64 = match literal_text.parse() {
+                        // addresses are written in hex, like `0x00400000`
+                        let parsed = match literal_text
+                            .strip_prefix("0x")
+                            .or_else(|| literal_text.strip_prefix("0X"))
+                        {
+                            Some(digits) => i128::from_str_radix(digits, 16),
+                            None => literal_text.parse(),
+                        };
+
+                        let literal: i128 = match parsed {
                             Ok(literal) => literal,
                             Err(_) => {
                                 return Err(ParserError(
@@ -39,18 +104,25 @@ where
                     }
 
                     TokenKind::Float => {
-                        let literal: f64 = match literal_text.parse() {
-                            Ok(literal) => literal,
-                            Err(_) => {
-                                return Err(ParserError(
-                                    format!("invalid float literal: `{}`", literal_text),
-                                    line_number,
-                                    column_number,
-                                ));
-                            }
+                        let invalid = || {
+                            ParserError(
+                                format!("invalid float literal: `{}`", literal_text),
+                                line_number,
+                                column_number,
+                            )
                         };
 
-                        ast::Lit::Float(literal)
+                        // a `long double` is kept like a `double`, which is as wide as MV goes
+                        match literal_text.strip_suffix(['f', 'F']) {
+                            Some(digits) => match digits.parse::<f32>() {
+                                Ok(literal) if literal.is_finite() => ast::Lit::Float32(literal),
+                                _ => return Err(invalid()),
+                            },
+                            None => match literal_text.trim_end_matches(['l', 'L']).parse() {
+                                Ok(literal) => ast::Lit::Float(literal),
+                                Err(_) => return Err(invalid()),
+                            },
+                        }
                     }
 
                     TokenKind::Bool => {
@@ -69,19 +141,40 @@ where
                     }
 
                     TokenKind::Char => {
-                        let literal = match literal_text.chars().nth(1) {
-                            Some(literal) => literal,
-                            None => {
-                                return Err(ParserError(
-                                    format!("invalid char literal: `{}`", literal_text),
-                                    line_number,
-                                    column_number,
-                                ));
-                            }
+                        let inner = &literal_text[1..literal_text.len() - 1];
+
+                        let literal = match inner.strip_prefix('\\') {
+                            Some(escape) => match unescape(escape) {
+                                Some(literal) => literal,
+                                None => {
+                                    return Err(ParserError(
+                                        format!(
+                                            "invalid escape sequence `\\{}` in char literal: `{}`",
+                                            escape, literal_text
+                                        ),
+                                        line_number,
+                                        column_number,
+                                    ));
+                                }
+                            },
+                            None => match inner.chars().next() {
+                                Some(literal) => literal,
+                                None => {
+                                    return Err(ParserError(
+                                        format!("invalid char literal: `{}`", literal_text),
+                                        line_number,
+                                        column_number,
+                                    ));
+                                }
+                            },
                         };
 
                         ast::Lit::Char(literal)
                     }
+
+                    TokenKind::String => {
+                        ast::Lit::Str(literal_text[1..literal_text.len() - 1].to_owned())
+                    }
                     _ => unreachable!(),
                 };
 
@@ -94,18 +187,181 @@ where
                     self.text(ident_token)
                 };
 
-                Ok(ast::Expr::Ident(ident_text.to_string()))
+                if self.peek() == TokenKind::Dot {
+                    self.consume(TokenKind::Dot)?;
+
+                    let member = match self.next() {
+                        Some(token) if token.kind == TokenKind::Identifier => token,
+                        Some(token) => {
+                            return Err(ParserError(
+                                format!("Expected member name but found `{}`", token.kind),
+                                line_number,
+                                column_number,
+                            ));
+                        }
+                        None => {
+                            return Err(ParserError(
+                                "Expected member name but found none".to_string(),
+                                line_number,
+                                column_number,
+                            ));
+                        }
+                    };
+
+                    if self.peek() == TokenKind::LParen {
+                        return Ok(ast::Expr::MethodCall {
+                            var_name: ident_text.to_string(),
+                            method: self.text(member).to_string(),
+                            arguments: self.arguments()?,
+                        });
+                    }
+
+                    return Ok(ast::Expr::Member {
+                        var_name: ident_text.to_string(),
+                        member: self.text(member).to_string(),
+                    });
+                }
+
+                if self.peek() != TokenKind::LParen {
+                    return Ok(ast::Expr::Ident(ident_text.to_string()));
+                }
+
+                // A call whose value is used, the statements calling functions on their own are
+                // parsed by `function_call`
+                Ok(ast::Expr::Call {
+                    function_name: ident_text.to_string(),
+                    arguments: self.arguments()?,
+                })
             }
 
             op @ TokenKind::Reference => {
                 self.consume(op)?;
 
-                Ok(ast::Expr::AddressOf(Box::new(self.parse_expression()?)))
+                Ok(ast::Expr::AddressOf(Box::new(self.expression(PREFIX_BINDING_POWER)?)))
             }
 
             TokenKind::Asterisk => {
                 self.consume(TokenKind::Asterisk)?;
-                Ok(ast::Expr::Dereference(Box::new(self.parse_expression()?)))
+                Ok(ast::Expr::Dereference(Box::new(self.expression(PREFIX_BINDING_POWER)?)))
+            }
+
+            op @ (TokenKind::Minus | TokenKind::Plus) => {
+                self.consume(op)?;
+
+                // a sign in front of a number is part of the literal, so `-3` goes wherever `3` does
+                Ok(match (op, self.expression(PREFIX_BINDING_POWER)?) {
+                    (TokenKind::Minus, ast::Expr::Literal(ast::Lit::Int(value))) => {
+                        ast::Expr::Literal(ast::Lit::Int(-value))
+                    }
+                    (TokenKind::Minus, ast::Expr::Literal(ast::Lit::Float(value))) => {
+                        ast::Expr::Literal(ast::Lit::Float(-value))
+                    }
+                    (TokenKind::Minus, ast::Expr::Literal(ast::Lit::Float32(value))) => {
+                        ast::Expr::Literal(ast::Lit::Float32(-value))
+                    }
+                    (
+                        TokenKind::Plus,
+                        literal @ ast::Expr::Literal(
+                            ast::Lit::Int(_) | ast::Lit::Float(_) | ast::Lit::Float32(_),
+                        ),
+                    ) => literal,
+                    (op, expr) => ast::Expr::PrefixOp {
+                        op,
+                        expr: Box::new(expr),
+                    },
+                })
+            }
+
+            TokenKind::LParen => {
+                self.consume(TokenKind::LParen)?;
+
+                // a type in parentheses is a C-style cast of the expression after it
+                if is_cast_type(self.peek()) {
+                    let cast_type = self.next().expect("the token was peeked").kind;
+                    self.consume(TokenKind::RParen)?;
+
+                    return Ok(ast::Expr::Cast {
+                        cast_type,
+                        expr: Box::new(self.expression(PREFIX_BINDING_POWER)?),
+                    });
+                }
+
+                let expr = self.parse_expression()?;
+                self.consume(TokenKind::RParen)?;
+                Ok(expr)
+            }
+
+            TokenKind::StaticCast => {
+                self.consume(TokenKind::StaticCast)?;
+                self.consume(TokenKind::Less)?;
+
+                let cast_type = self.peek();
+
+                if !is_cast_type(cast_type) {
+                    return Err(ParserError(
+                        format!("Expected type to cast to but found `{}`", cast_type),
+                        line_number,
+                        column_number,
+                    ));
+                }
+
+                self.consume(cast_type)?;
+                self.consume(TokenKind::Greater)?;
+                self.consume(TokenKind::LParen)?;
+                let expr = self.parse_expression()?;
+                self.consume(TokenKind::RParen)?;
+
+                Ok(ast::Expr::Cast {
+                    cast_type,
+                    expr: Box::new(expr),
+                })
+            }
+
+            TokenKind::Sizeof => {
+                self.consume(TokenKind::Sizeof)?;
+                self.consume(TokenKind::LParen)?;
+
+                let expr = match self.peek() {
+                    size_type @ TokenKind::KwInt
+                    | size_type @ TokenKind::KwShort
+                    | size_type @ TokenKind::KwUnsignedShort
+                    | size_type @ TokenKind::KwUnsignedInt
+                    | size_type @ TokenKind::KwLong
+                    | size_type @ TokenKind::KwUnsignedLong
+                    | size_type @ TokenKind::KwLongLong
+                    | size_type @ TokenKind::KwUnsignedLongLong
+                    | size_type @ TokenKind::KwChar
+                    | size_type @ TokenKind::KwFloat
+                    | size_type @ TokenKind::KwDouble
+                    | size_type @ TokenKind::KwBool => {
+                        self.consume(size_type)?;
+                        ast::Expr::SizeofType(size_type)
+                    }
+
+                    _ => ast::Expr::Sizeof(Box::new(self.parse_expression()?)),
+                };
+
+                self.consume(TokenKind::RParen)?;
+                Ok(expr)
+            }
+
+            TokenKind::Peek => {
+                self.consume(TokenKind::Peek)?;
+                self.consume(TokenKind::LParen)?;
+                let address = self.parse_expression()?;
+                self.consume(TokenKind::RParen)?;
+
+                Ok(ast::Expr::Peek(Box::new(address)))
+            }
+
+            TokenKind::Strlen => {
+                self.consume(TokenKind::Strlen)?;
+                self.consume(TokenKind::LParen)?;
+                let (name, _) =
+                    self.memory_operand(TokenKind::Strlen, line_number, column_number)?;
+                self.consume(TokenKind::RParen)?;
+
+                Ok(ast::Expr::Strlen(name))
             }
 
             _ => Err(ParserError(
@@ -116,3 +372,109 @@ where
         }
     }
 }
+
+/// Binding power of the prefix operators, tighter than every infix operator
+const PREFIX_BINDING_POWER: u8 = 9;
+
+/// Checks if a token is one of the types a value can be cast to
+fn is_cast_type(kind: TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::KwInt
+            | TokenKind::KwShort
+            | TokenKind::KwUnsignedShort
+            | TokenKind::KwUnsignedInt
+            | TokenKind::KwLong
+            | TokenKind::KwUnsignedLong
+            | TokenKind::KwLongLong
+            | TokenKind::KwUnsignedLongLong
+            | TokenKind::KwChar
+            | TokenKind::KwFloat
+            | TokenKind::KwDouble
+            | TokenKind::KwBool
+    )
+}
+
+/// Gets the character an escape sequence stands for, written without its `\\` like `n` or `x41`
+///
+/// Octal escapes take up to three digits and hex escapes any number of them, and both have to fit
+/// in a byte like a `char` does. Returns `None` for an escape C++ doesn't have
+fn unescape(escape: &str) -> Option<char> {
+    let byte = match escape {
+        "n" => b'\n',
+        "t" => b'\t',
+        "r" => b'\r',
+        "a" => 0x07,
+        "b" => 0x08,
+        "f" => 0x0C,
+        "v" => 0x0B,
+        "\\" => b'\\',
+        "'" => b'\'',
+        "\"" => b'"',
+        "?" => b'?',
+        _ => match escape.strip_prefix('x') {
+            Some(digits) if !digits.is_empty() => u8::from_str_radix(digits, 16).ok()?,
+            Some(_) => return None,
+            None if escape.len() <= 3 && escape.chars().all(|c| c.is_digit(8)) => {
+                u8::from_str_radix(escape, 8).ok()?
+            }
+            None => return None,
+        },
+    };
+
+    Some(byte as char)
+}
+
+/// Gets the characters of the text of a string literal, written without its quotes, with its escape
+/// sequences replaced by the characters they stand for. Returns `None` for an escape C++ doesn't have
+pub(crate) fn unescape_string(text: &str) -> Option<String> {
+    let mut characters = text.chars().peekable();
+    let mut string = String::new();
+
+    while let Some(character) = characters.next() {
+        if character != '\\' {
+            string.push(character);
+            continue;
+        }
+
+        let mut escape = characters.next()?.to_string();
+
+        // hex escapes take every digit after them and octal escapes up to three digits
+        match escape.as_str() {
+            "x" => {
+                while let Some(digit) = characters.next_if(|c| c.is_ascii_hexdigit()) {
+                    escape.push(digit);
+                }
+            }
+            first if first.chars().all(|c| c.is_digit(8)) => {
+                while escape.len() < 3 {
+                    let Some(digit) = characters.next_if(|c| c.is_digit(8)) else {
+                        break;
+                    };
+                    escape.push(digit);
+                }
+            }
+            _ => {}
+        }
+
+        string.push(unescape(&escape)?);
+    }
+
+    Some(string)
+}
+
+/// Returns the left and right binding powers of an infix operator, `None` if the token is not one
+///
+/// The right side binds tighter so operators of the same precedence associate to the left, and
+/// comparisons bind looser than arithmetic with `==` and `!=` the loosest, like in C++
+fn infix_binding_power(op: TokenKind) -> Option<(u8, u8)> {
+    match op {
+        TokenKind::EqEq | TokenKind::NotEq => Some((1, 2)),
+        TokenKind::Less | TokenKind::LessEq | TokenKind::Greater | TokenKind::GreaterEq => {
+            Some((3, 4))
+        }
+        TokenKind::Plus | TokenKind::Minus => Some((5, 6)),
+        TokenKind::Asterisk | TokenKind::Slash => Some((7, 8)),
+        _ => None,
+    }
+}
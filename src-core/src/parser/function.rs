@@ -0,0 +1,158 @@
+use super::{ast, Parser};
+use crate::error::{Error::ParserError, Result};
+use crate::lexer::token::{Token, TokenKind};
+
+impl<'input, I> Parser<'input, I>
+where
+    I: Iterator<Item = Token>,
+{
+    /// Parses the rest of a function definition, starting at the `(` after the function's name
+    pub(crate) fn function_definition(
+        &mut self,
+        return_type: Option<TokenKind>,
+        ident: Token,
+        line_number: usize,
+    ) -> Result<ast::Statement> {
+        self.consume(TokenKind::LParen)?;
+
+        let mut parameters = Vec::new();
+
+        while self.peek() != TokenKind::RParen {
+            if !parameters.is_empty() {
+                self.consume(TokenKind::Comma)?;
+            }
+
+            parameters.push(self.parameter()?);
+        }
+
+        self.consume(TokenKind::RParen)?;
+
+        let (body, _) = self.block()?;
+
+        Ok(ast::Statement::FunctionDefinition {
+            return_type,
+            function_name: self.text(ident).to_string(),
+            parameters,
+            body,
+            line: line_number,
+            function_ident_column: ident.get_column_number(),
+        })
+    }
+
+    /// Parses the rest of a function call, starting at the `(` after the function's name
+    pub(crate) fn function_call(
+        &mut self,
+        ident: Token,
+        line_number: usize,
+    ) -> Result<ast::Statement> {
+        let arguments = self.arguments()?;
+        self.consume(TokenKind::SemiColon)?;
+
+        Ok(ast::Statement::FunctionCall {
+            function_name: self.text(ident).to_string(),
+            arguments,
+            line: line_number,
+            function_ident_column: ident.get_column_number(),
+        })
+    }
+
+    /// Parses the arguments of a call, from the `(` to the `)`
+    pub(crate) fn arguments(&mut self) -> Result<Vec<ast::Expr>> {
+        self.consume(TokenKind::LParen)?;
+
+        let mut arguments = Vec::new();
+
+        while self.peek() != TokenKind::RParen {
+            if !arguments.is_empty() {
+                self.consume(TokenKind::Comma)?;
+            }
+
+            arguments.push(self.parse_expression()?);
+        }
+
+        self.consume(TokenKind::RParen)?;
+
+        Ok(arguments)
+    }
+
+    /// Parses the statements between `{` and `}`, also returning the line of the `}`
+    pub(crate) fn block(&mut self) -> Result<(Vec<ast::Statement>, usize)> {
+        self.consume(TokenKind::LBrace)?;
+
+        let mut statements = Vec::new();
+
+        while !matches!(self.peek(), TokenKind::RBrace | TokenKind::EOF) {
+            statements.extend(self.statements_or_ignored()?);
+        }
+
+        let end_line = self.tokens.peek().map_or(0, |token| token.get_line_number());
+
+        self.consume(TokenKind::RBrace)?;
+
+        Ok((statements, end_line))
+    }
+
+    fn parameter(&mut self) -> Result<ast::Parameter> {
+        let line_number = self.tokens.peek().map_or(0, |token| token.get_line_number());
+
+        let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+        let param_type = match self.peek() {
+            param_type @ TokenKind::KwInt
+            | param_type @ TokenKind::KwShort
+            | param_type @ TokenKind::KwUnsignedShort
+            | param_type @ TokenKind::KwUnsignedInt
+            | param_type @ TokenKind::KwLong
+            | param_type @ TokenKind::KwUnsignedLong
+            | param_type @ TokenKind::KwLongLong
+            | param_type @ TokenKind::KwUnsignedLongLong
+            | param_type @ TokenKind::KwChar
+            | param_type @ TokenKind::KwFloat
+            | param_type @ TokenKind::KwDouble
+            | param_type @ TokenKind::KwBool => {
+                self.consume(param_type)?;
+                param_type
+            }
+
+            _ => {
+                return Err(ParserError(
+                    format!("Expected parameter type but found `{}`", self.peek()),
+                    line_number,
+                    column_number,
+                ));
+            }
+        };
+
+        let mut indirection = 0;
+
+        while self.peek() == TokenKind::Asterisk {
+            self.consume(TokenKind::Asterisk)?;
+            indirection += 1;
+        }
+
+        let ident = match self.next() {
+            Some(token) if token.kind == TokenKind::Identifier => token,
+            Some(token) => {
+                return Err(ParserError(
+                    format!("Expected parameter name but found `{}`", token.kind),
+                    line_number,
+                    column_number,
+                ));
+            }
+            None => {
+                return Err(ParserError(
+                    "Expected parameter name but found none".to_string(),
+                    line_number,
+                    column_number,
+                ));
+            }
+        };
+
+        Ok(ast::Parameter {
+            param_type,
+            indirection,
+            param_name: self.text(ident).to_string(),
+            param_ident_column: ident.get_column_number(),
+        })
+    }
+}
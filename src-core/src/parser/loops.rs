@@ -0,0 +1,157 @@
+use super::{ast, Parser};
+use crate::error::{Error::ParserError, Result};
+use crate::lexer::token::{Token, TokenKind};
+
+impl<'input, I> Parser<'input, I>
+where
+    I: Iterator<Item = Token>,
+{
+    /// Parses a loop like `while (i < 3) { ... }`
+    pub(crate) fn while_loop(
+        &mut self,
+        line_number: usize,
+        column_number: usize,
+    ) -> Result<ast::Statement> {
+        self.consume(TokenKind::KwWhile)?;
+        self.consume(TokenKind::LParen)?;
+        let condition = self.parse_expression()?;
+        self.consume(TokenKind::RParen)?;
+
+        let body = self.branch()?;
+
+        Ok(ast::Statement::While {
+            condition: Box::new(condition),
+            body: Box::new(body),
+            update: None,
+            line: line_number,
+            loop_column: column_number,
+        })
+    }
+
+    /// Parses a loop like `for (int i = 0; i < 3; i++) { ... }` into a block holding the initialization,
+    /// which is scoped to the loop, and a `while` loop running the update after the body
+    pub(crate) fn for_loop(
+        &mut self,
+        line_number: usize,
+        column_number: usize,
+    ) -> Result<ast::Statement> {
+        self.consume(TokenKind::KwFor)?;
+        self.consume(TokenKind::LParen)?;
+
+        let mut statements = Vec::new();
+
+        if self.peek() == TokenKind::SemiColon {
+            self.consume(TokenKind::SemiColon)?;
+        } else {
+            statements.extend(self.statements()?);
+        }
+
+        // a `for` loop without a condition runs until the iteration limit stops it
+        let condition = if self.peek() == TokenKind::SemiColon {
+            ast::Expr::Literal(ast::Lit::Bool(true))
+        } else {
+            self.parse_expression()?
+        };
+
+        self.consume(TokenKind::SemiColon)?;
+
+        let update = if self.peek() == TokenKind::RParen {
+            None
+        } else {
+            Some(Box::new(self.loop_update(line_number)?))
+        };
+
+        self.consume(TokenKind::RParen)?;
+
+        let body = self.branch()?;
+
+        let end_line = match &body {
+            ast::Statement::Block { end_line, .. } => *end_line,
+            _ => line_number,
+        };
+
+        statements.push(ast::Statement::While {
+            condition: Box::new(condition),
+            body: Box::new(body),
+            update,
+            line: line_number,
+            loop_column: column_number,
+        });
+
+        Ok(ast::Statement::Block {
+            statements,
+            line: line_number,
+            brace_column: column_number,
+            end_line,
+        })
+    }
+
+    /// Parses the update of a `for` loop, an increment like `i++` or an assignment like `i = i + 2` or
+    /// `i += 2`
+    fn loop_update(&mut self, line_number: usize) -> Result<ast::Statement> {
+        let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+        if let op @ (TokenKind::Increment | TokenKind::Decrement) = self.peek() {
+            self.consume(op)?;
+            let ident = self.loop_variable(line_number)?;
+
+            return Ok(self.increment(ident, op, line_number, column_number));
+        }
+
+        let ident = self.loop_variable(line_number)?;
+
+        let op_column = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+        match self.peek() {
+            op @ (TokenKind::Increment | TokenKind::Decrement) => {
+                self.consume(op)?;
+
+                Ok(self.increment(ident, op, line_number, op_column))
+            }
+
+            op @ (TokenKind::PlusEq
+            | TokenKind::MinusEq
+            | TokenKind::AsteriskEq
+            | TokenKind::SlashEq) => self.compound_assignment(ident, op, line_number),
+
+            TokenKind::Eq => {
+                self.consume(TokenKind::Eq)?;
+
+                Ok(ast::Statement::VariableAssignment {
+                    var_name: self.text(ident).to_string(),
+                    new_value: Box::new(self.parse_expression()?),
+                    line: line_number,
+                    var_ident_column: ident.get_column_number(),
+                    assignment_column: op_column,
+                })
+            }
+
+            other => Err(ParserError(
+                format!(
+                    "Expected `++`, `--`, `=`, `+=`, `-=`, `*=` or `/=` in the update of the loop but found `{}`",
+                    other
+                ),
+                line_number,
+                op_column,
+            )),
+        }
+    }
+
+    fn loop_variable(&mut self, line_number: usize) -> Result<Token> {
+        let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+        match self.next() {
+            Some(token) if token.kind == TokenKind::Identifier => Ok(token),
+            Some(token) => Err(ParserError(
+                format!("Expected identifier but found `{}`", token.kind),
+                line_number,
+                column_number,
+            )),
+            None => Err(ParserError(
+                "Expected identifier but found none".to_string(),
+                line_number,
+                column_number,
+            )),
+        }
+    }
+}
@@ -1,15 +1,27 @@
+pub(crate) mod allocation;
+pub(crate) mod array;
 pub(crate) mod ast;
+pub(crate) mod class;
+pub(crate) mod conditional;
 pub(crate) mod expression;
+pub(crate) mod function;
+pub(crate) mod loops;
+pub(crate) mod mutex;
+pub(crate) mod smart_pointer;
 pub(crate) mod statement;
+pub(crate) mod union;
 
 use std::iter::Peekable;
 
 use super::error::{Error::ParserError, Result};
 
+use crate::analyzer::{AnalyzerOptions, Architecture, Strictness};
+
 use crate::lexer::{
     token::{Token, TokenKind},
     Lexer,
 };
+use crate::stack::{on_deep_stack, NESTING_LIMIT};
 
 pub struct TokenIter<'input> {
     lexer: Lexer<'input>,
@@ -42,13 +54,56 @@ where
 {
     input: &'input str,
     tokens: Peekable<I>,
+    // the type of a declaration whose last declarator ended with `,`, like `int` after `int a,`
+    declarator_list: Option<TokenKind>,
+    strictness: Strictness,
+    // the data model the program is analyzed with, which gives the size of a `long`
+    architecture: Architecture,
+    // how many `{` the consumed tokens left open, how many tokens were consumed and the last of them,
+    // to find where a statement that failed to parse ends
+    depth: usize,
+    consumed: usize,
+    last: Option<Token>,
+    // how many statements and expressions are being parsed, one inside the other
+    nesting: usize,
 }
 
 impl<'input> Parser<'input, TokenIter<'input>> {
     pub fn new(input: &'input str) -> Parser<'input, TokenIter<'input>> {
+        Self::with_strictness(input, Strictness::Strict)
+    }
+
+    /// Creates a parser that skips the statements it can't parse in permissive mode, standing an
+    /// [Ignored](ast::Statement::Ignored) statement in for each of them
+    pub fn with_strictness(
+        input: &'input str,
+        strictness: Strictness,
+    ) -> Parser<'input, TokenIter<'input>> {
+        Self::with_options(
+            input,
+            &AnalyzerOptions {
+                strictness,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Creates a parser for a program analyzed with the given options, which set the strictness and the
+    /// size of the types byte counts are converted to
+    pub fn with_options(
+        input: &'input str,
+        options: &AnalyzerOptions,
+    ) -> Parser<'input, TokenIter<'input>> {
         Parser {
             input,
             tokens: TokenIter::new(input).peekable(),
+            declarator_list: None,
+            strictness: options.strictness,
+            architecture: options.architecture,
+            depth: 0,
+            consumed: 0,
+            last: None,
+            nesting: 0,
         }
     }
 }
@@ -57,14 +112,108 @@ impl<'input, I> Parser<'input, I>
 where
     I: Iterator<Item = Token>,
 {
-    pub fn parse(&mut self) -> Result<Vec<ast::Statement>> {
-        let mut statements = Vec::new();
+    pub fn parse(&mut self) -> Result<Vec<ast::Statement>>
+    where
+        I: Send,
+    {
+        on_deep_stack(|| {
+            let mut statements = Vec::new();
+
+            while self.peek() != TokenKind::EOF {
+                statements.extend(self.statements_or_ignored()?);
+            }
+
+            Ok(statements)
+        })
+    }
 
-        while self.peek() != TokenKind::EOF {
-            statements.push(self.statement()?);
+    /// Parses a statement or an operand nested in the one being parsed, failing instead of recursing
+    /// past [NESTING_LIMIT] levels
+    pub(crate) fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        if self.nesting >= NESTING_LIMIT {
+            let line_number = self.tokens.peek().map_or(0, |token| token.get_line_number());
+
+            let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+            return Err(ParserError(
+                format!("Statements and expressions nest deeper than {} levels", NESTING_LIMIT),
+                line_number,
+                column_number,
+            ));
         }
 
-        Ok(statements)
+        self.nesting += 1;
+        let result = parse(self);
+        self.nesting -= 1;
+
+        result
+    }
+
+    /// Parses the statements that come next, in permissive mode a statement that fails to parse is
+    /// skipped up to its `;` or the `}` closing its body, and reported as ignored with the reason it
+    /// failed and the span it took up
+    pub(crate) fn statements_or_ignored(&mut self) -> Result<Vec<ast::Statement>> {
+        let first = self.tokens.peek().copied();
+        let (depth, consumed) = (self.depth, self.consumed);
+
+        match (self.nested(Self::statements), first) {
+            (Err(ParserError(reason, ..)), Some(first))
+                if self.strictness == Strictness::Permissive =>
+            {
+                self.declarator_list = None;
+                self.skip_statement(depth, consumed);
+
+                // tokens are placed by where they end, a statement starts where its first token starts
+                let last = self.last.filter(|_| self.consumed > consumed).unwrap_or(first);
+
+                Ok(vec![ast::Statement::Ignored {
+                    reason,
+                    line: first.get_line_number(),
+                    column: first.get_column_number() + 1 - self.text(first).chars().count().max(1),
+                    end_line: last.get_line_number(),
+                    end_column: last.get_column_number(),
+                }])
+            }
+            (result, _) => result,
+        }
+    }
+
+    /// Skips the rest of a statement that started at `depth` after `consumed` tokens
+    ///
+    /// The statement ends at a `;` or at the `}` that closes a body it opened, a `}` closing the block
+    /// around it is left for the block
+    fn skip_statement(&mut self, depth: usize, consumed: usize) {
+        loop {
+            if self.depth < depth {
+                return;
+            }
+
+            if self.consumed > consumed
+                && self.depth == depth
+                && matches!(self.last_kind(), Some(TokenKind::SemiColon | TokenKind::RBrace))
+            {
+                // a `}` ending the statement may be followed by the `;` of a definition
+                if self.last_kind() == Some(TokenKind::RBrace)
+                    && self.peek() == TokenKind::SemiColon
+                {
+                    self.next();
+                }
+
+                return;
+            }
+
+            match self.peek() {
+                TokenKind::EOF => return,
+                TokenKind::RBrace if self.depth == depth && depth > 0 => return,
+                _ => {
+                    self.next();
+                }
+            }
+        }
+    }
+
+    fn last_kind(&self) -> Option<TokenKind> {
+        self.last.map(|token| token.kind)
     }
 
     pub(crate) fn text(&self, token: Token) -> &'input str {
@@ -81,14 +230,24 @@ where
     }
 
     pub(crate) fn next(&mut self) -> Option<Token> {
-        self.tokens.next()
+        let token = self.tokens.next()?;
+
+        match token.kind {
+            TokenKind::LBrace => self.depth += 1,
+            TokenKind::RBrace => self.depth = self.depth.saturating_sub(1),
+            _ => {}
+        }
+
+        self.consumed += 1;
+        self.last = Some(token);
+
+        Some(token)
     }
 
     pub(crate) fn consume(&mut self, expected: TokenKind) -> Result<()> {
-        let line_number = self.tokens.peek().map_or(0, |token| token.get_line_number(&self.input));
+        let line_number = self.tokens.peek().map_or(0, |token| token.get_line_number());
 
-        let column_number =
-            self.tokens.peek().map_or(0, |token| token.get_column_number(&self.input));
+        let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
 
         let token = self.next().ok_or_else(|| {
             ParserError(
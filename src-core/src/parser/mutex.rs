@@ -0,0 +1,79 @@
+use super::{ast, Parser};
+use crate::error::{Error::ParserError, Result};
+use crate::lexer::token::{Token, TokenKind};
+
+impl<'input, I> Parser<'input, I>
+where
+    I: Iterator<Item = Token>,
+{
+    /// Parses a mutex declaration like `std::mutex m;`
+    pub(crate) fn mutex_declaration(&mut self, line_number: usize) -> Result<ast::Statement> {
+        self.consume(TokenKind::Mutex)?;
+
+        let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+        let ident = match self.next() {
+            Some(token) if token.kind == TokenKind::Identifier => token,
+            Some(token) => {
+                return Err(ParserError(
+                    format!("Expected identifier but found `{}`", token.kind),
+                    line_number,
+                    column_number,
+                ));
+            }
+            None => {
+                return Err(ParserError(
+                    "Expected identifier but found none".to_string(),
+                    line_number,
+                    column_number,
+                ));
+            }
+        };
+
+        self.consume(TokenKind::SemiColon)?;
+
+        Ok(ast::Statement::MutexDeclaration {
+            mutex_name: self.text(ident).to_string(),
+            line: line_number,
+            mutex_ident_column: ident.get_column_number(),
+        })
+    }
+
+    /// Parses the rest of a call to a method of a mutex like `m.lock();`, starting at the `(`
+    pub(crate) fn mutex_call(
+        &mut self,
+        ident: Token,
+        method: Token,
+        line_number: usize,
+    ) -> Result<ast::Statement> {
+        self.consume(TokenKind::LParen)?;
+        self.consume(TokenKind::RParen)?;
+        self.consume(TokenKind::SemiColon)?;
+
+        let mutex_name = self.text(ident).to_string();
+        let mutex_ident_column = ident.get_column_number();
+
+        match self.text(method) {
+            "lock" => Ok(ast::Statement::Lock {
+                mutex_name,
+                line: line_number,
+                mutex_ident_column,
+            }),
+
+            "unlock" => Ok(ast::Statement::Unlock {
+                mutex_name,
+                line: line_number,
+                mutex_ident_column,
+            }),
+
+            method_name => Err(ParserError(
+                format!(
+                    "Expected `lock` or `unlock` but found `{}`, mutexes have no other methods",
+                    method_name
+                ),
+                line_number,
+                method.get_column_number(),
+            )),
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use super::{ast, Parser};
+use crate::error::{Error::ParserError, Result};
+use crate::lexer::token::{Token, TokenKind};
+
+impl<'input, I> Parser<'input, I>
+where
+    I: Iterator<Item = Token>,
+{
+    /// Parses a `std::unique_ptr<T> p = value;` declaration, starting at `std::unique_ptr`
+    pub(crate) fn unique_pointer_declaration(
+        &mut self,
+        line_number: usize,
+    ) -> Result<ast::Statement> {
+        self.consume(TokenKind::UniquePtr)?;
+        self.consume(TokenKind::Less)?;
+        let base_type = self.allocated_type()?;
+        self.consume(TokenKind::Greater)?;
+
+        let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+        let ident = match self.next() {
+            Some(token) if token.kind == TokenKind::Identifier => token,
+            Some(token) => {
+                return Err(ParserError(
+                    format!("Expected identifier but found `{}`", token.kind),
+                    line_number,
+                    column_number,
+                ));
+            }
+            None => {
+                return Err(ParserError(
+                    "Expected identifier but found none".to_string(),
+                    line_number,
+                    column_number,
+                ));
+            }
+        };
+
+        let pointer_ident_column = ident.get_column_number();
+
+        // a `std::unique_ptr` without an initializer is null
+        let (value, value_column) = if self.peek() == TokenKind::SemiColon {
+            (ast::UniqueValue::Null, pointer_ident_column)
+        } else {
+            self.consume(TokenKind::Eq)?;
+            self.unique_value()?
+        };
+
+        self.consume(TokenKind::SemiColon)?;
+
+        Ok(ast::Statement::UniquePointerDeclaration {
+            base_type,
+            pointer_name: self.text(ident).to_string(),
+            value,
+            line: line_number,
+            pointer_ident_column,
+            value_column,
+        })
+    }
+
+    /// Parses what a `std::unique_ptr` is initialized or assigned with, after the `=`
+    ///
+    /// # Returns
+    /// - [Result](crate::error::Result): A result containing either:
+    ///    - A tuple with the value and its column
+    ///    - [Error](crate::error::Error): An error if the value is malformed
+    pub(crate) fn unique_value(&mut self) -> Result<(ast::UniqueValue, usize)> {
+        let line_number = self.tokens.peek().map_or(0, |token| token.get_line_number());
+
+        let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+        let value = match self.peek() {
+            TokenKind::MakeUnique => {
+                self.consume(TokenKind::MakeUnique)?;
+                self.consume(TokenKind::Less)?;
+                let make_type = self.allocated_type()?;
+                self.consume(TokenKind::Greater)?;
+                self.consume(TokenKind::LParen)?;
+
+                let value = if self.peek() == TokenKind::RParen {
+                    None
+                } else {
+                    Some(Box::new(self.parse_expression()?))
+                };
+
+                self.consume(TokenKind::RParen)?;
+
+                ast::UniqueValue::MakeUnique { make_type, value }
+            }
+
+            TokenKind::Move => ast::UniqueValue::Move {
+                source: self.moved_source(line_number, column_number)?,
+            },
+
+            TokenKind::Identifier => {
+                let source = self.next().expect("the token was peeked");
+
+                ast::UniqueValue::Copy {
+                    source: self.text(source).to_string(),
+                }
+            }
+
+            TokenKind::Null => {
+                self.consume(TokenKind::Null)?;
+                ast::UniqueValue::Null
+            }
+
+            _ => {
+                return Err(ParserError(
+                    format!(
+                        "Expected `std::make_unique`, `std::move` or `nullptr` but found `{}`",
+                        self.peek()
+                    ),
+                    line_number,
+                    column_number,
+                ));
+            }
+        };
+
+        Ok((value, column_number))
+    }
+
+    /// Parses a `std::shared_ptr<T> p = value;` declaration, starting at `std::shared_ptr`
+    pub(crate) fn shared_pointer_declaration(
+        &mut self,
+        line_number: usize,
+    ) -> Result<ast::Statement> {
+        self.consume(TokenKind::SharedPtr)?;
+        self.consume(TokenKind::Less)?;
+        let base_type = self.allocated_type()?;
+        self.consume(TokenKind::Greater)?;
+
+        let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+        let ident = match self.next() {
+            Some(token) if token.kind == TokenKind::Identifier => token,
+            Some(token) => {
+                return Err(ParserError(
+                    format!("Expected identifier but found `{}`", token.kind),
+                    line_number,
+                    column_number,
+                ));
+            }
+            None => {
+                return Err(ParserError(
+                    "Expected identifier but found none".to_string(),
+                    line_number,
+                    column_number,
+                ));
+            }
+        };
+
+        let pointer_ident_column = ident.get_column_number();
+
+        // a `std::shared_ptr` without an initializer is null
+        let (value, value_column) = if self.peek() == TokenKind::SemiColon {
+            (ast::SharedValue::Null, pointer_ident_column)
+        } else {
+            self.consume(TokenKind::Eq)?;
+            self.shared_value()?
+        };
+
+        self.consume(TokenKind::SemiColon)?;
+
+        Ok(ast::Statement::SharedPointerDeclaration {
+            base_type,
+            pointer_name: self.text(ident).to_string(),
+            value,
+            line: line_number,
+            pointer_ident_column,
+            value_column,
+        })
+    }
+
+    /// Parses what a `std::shared_ptr` is initialized or assigned with, after the `=`
+    ///
+    /// # Returns
+    /// - [Result](crate::error::Result): A result containing either:
+    ///    - A tuple with the value and its column
+    ///    - [Error](crate::error::Error): An error if the value is malformed
+    pub(crate) fn shared_value(&mut self) -> Result<(ast::SharedValue, usize)> {
+        let line_number = self.tokens.peek().map_or(0, |token| token.get_line_number());
+
+        let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+        let value = match self.peek() {
+            TokenKind::MakeShared => {
+                self.consume(TokenKind::MakeShared)?;
+                self.consume(TokenKind::Less)?;
+                let make_type = self.allocated_type()?;
+                self.consume(TokenKind::Greater)?;
+                self.consume(TokenKind::LParen)?;
+
+                let value = if self.peek() == TokenKind::RParen {
+                    None
+                } else {
+                    Some(Box::new(self.parse_expression()?))
+                };
+
+                self.consume(TokenKind::RParen)?;
+
+                ast::SharedValue::MakeShared { make_type, value }
+            }
+
+            TokenKind::Move => ast::SharedValue::Move {
+                source: self.moved_source(line_number, column_number)?,
+            },
+
+            TokenKind::Identifier => {
+                let source = self.next().expect("the token was peeked");
+
+                ast::SharedValue::Copy {
+                    source: self.text(source).to_string(),
+                }
+            }
+
+            TokenKind::Null => {
+                self.consume(TokenKind::Null)?;
+                ast::SharedValue::Null
+            }
+
+            _ => {
+                return Err(ParserError(
+                    format!(
+                        "Expected `std::make_shared`, a `std::shared_ptr`, `std::move` or \
+                         `nullptr` but found `{}`",
+                        self.peek()
+                    ),
+                    line_number,
+                    column_number,
+                ));
+            }
+        };
+
+        Ok((value, column_number))
+    }
+
+    /// Parses `std::move(source)` and returns the name of `source`
+    fn moved_source(&mut self, line_number: usize, column_number: usize) -> Result<String> {
+        self.consume(TokenKind::Move)?;
+        self.consume(TokenKind::LParen)?;
+
+        let source = match self.next() {
+            Some(token) if token.kind == TokenKind::Identifier => token,
+            Some(token) => {
+                return Err(ParserError(
+                    format!("Expected symbol passed to `std::move` but found `{}`", token.kind),
+                    line_number,
+                    column_number,
+                ));
+            }
+            None => {
+                return Err(ParserError(
+                    "Expected symbol passed to `std::move` but found none".to_string(),
+                    line_number,
+                    column_number,
+                ));
+            }
+        };
+
+        self.consume(TokenKind::RParen)?;
+
+        Ok(self.text(source).to_string())
+    }
+}
@@ -1,288 +1,255 @@
 use crate::lexer::token::{Token, TokenKind};
 
-use super::{ast, Parser};
+use super::{allocation::values_in_bytes, ast, Parser};
 
 use crate::error::{Error::ParserError, Result};
 
+/// Keywords of statements outside the supported subset, which the lexer reads as identifiers, and why
+/// each of them is rejected
+const UNSUPPORTED_KEYWORDS: [(&str, &str); 15] = [
+    ("auto", "Type deduction with `auto` is not supported"),
+    ("break", "`break` is not supported, loops run until their condition fails"),
+    ("case", "`switch` statements are not supported"),
+    ("catch", "Exceptions are not supported"),
+    ("continue", "`continue` is not supported"),
+    ("do", "`do` loops are not supported"),
+    ("enum", "Enums are not supported"),
+    ("goto", "`goto` is not supported"),
+    ("namespace", "Namespaces are not supported"),
+    ("switch", "`switch` statements are not supported"),
+    ("template", "Templates are not supported"),
+    ("throw", "Exceptions are not supported"),
+    ("try", "Exceptions are not supported"),
+    ("typedef", "Type aliases are not supported"),
+    ("using", "Type aliases and `using` declarations are not supported"),
+];
+
 impl<'input, I> Parser<'input, I>
 where
     I: Iterator<Item = Token>,
 {
+    /// Parses the next statement, a declaration of several variables like `int a, b = 3, *p;` is split
+    /// into one statement for each of them
+    pub(crate) fn statements(&mut self) -> Result<Vec<ast::Statement>> {
+        let mut statements = vec![self.statement()?];
+
+        while let Some(var_type) = self.declarator_list.take() {
+            let line_number = self.tokens.peek().map_or(0, |token| token.get_line_number());
+
+            let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+            let declaration = self.declaration(var_type, line_number, column_number)?;
+
+            if let ast::Statement::FunctionDefinition {
+                function_ident_column,
+                ..
+            } = declaration
+            {
+                return Err(ParserError(
+                    "Functions can't be defined in a list of declarations".to_string(),
+                    line_number,
+                    function_ident_column,
+                ));
+            }
+
+            statements.push(declaration);
+        }
+
+        Ok(statements)
+    }
+
     pub(crate) fn statement(&mut self) -> Result<ast::Statement> {
-        let line_number = self.tokens.peek().map_or(0, |token| token.get_line_number(&self.input));
+        let line_number = self.tokens.peek().map_or(0, |token| token.get_line_number());
 
-        let column_number =
-            self.tokens.peek().map_or(0, |token| token.get_column_number(&self.input));
+        let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
 
         match self.peek() {
             var_type @ TokenKind::KwInt
+            | var_type @ TokenKind::KwShort
+            | var_type @ TokenKind::KwUnsignedShort
+            | var_type @ TokenKind::KwUnsignedInt
+            | var_type @ TokenKind::KwLong
+            | var_type @ TokenKind::KwUnsignedLong
+            | var_type @ TokenKind::KwLongLong
+            | var_type @ TokenKind::KwUnsignedLongLong
             | var_type @ TokenKind::KwChar
             | var_type @ TokenKind::KwFloat
             | var_type @ TokenKind::KwDouble
-            | var_type @ TokenKind::KwBool => {
+            | var_type @ TokenKind::KwBool
+            | var_type @ TokenKind::KwString
+            | var_type @ TokenKind::KwVoid => {
                 self.consume(var_type)?;
+                self.declaration(var_type, line_number, column_number)
+            }
 
-                let mut pointer = false;
-
-                if self.peek() == TokenKind::Asterisk {
-                    pointer = true;
-                    self.consume(TokenKind::Asterisk)?;
-                }
-
+            TokenKind::Asterisk => {
+                self.consume(TokenKind::Asterisk)?;
                 let ident = if let Some(token) = self.next() {
                     token
                 } else {
                     return Err(ParserError(
-                        "Expected identifier but found none".to_string(),
+                        "Expected identifier after dereference operator but found none".to_string(),
                         line_number,
                         column_number,
                     ));
                 };
 
+                let pointer_ident_column = ident.get_column_number();
+
                 if ident.kind != TokenKind::Identifier {
                     return Err(ParserError(
-                        format!("Expected identifier but found `{}`", ident.kind),
+                        format!(
+                            "Expected identifier after dereference operator `*`, but found `{}`",
+                            ident.kind
+                        ),
                         line_number,
                         column_number,
                     ));
                 }
 
                 let name = self.text(ident).to_string();
+                let op = self.assignment_operator()?;
 
-                if pointer {
-                    self.consume(TokenKind::Eq)?;
-
-                    let pointer_ident_column = ident.get_column_number(&self.input);
-
-                    if self.peek() == TokenKind::New {
-                        // Heap allocation
-
-                        self.consume(TokenKind::New)?;
-
-                        match self.peek() {
-                            TokenKind::KwBool => {
-                                self.consume(TokenKind::KwBool)?;
-                                if var_type != TokenKind::Bool {
-                                    return Err(ParserError(
-                                        format!("Expected a pointer to {}", var_type),
-                                        line_number,
-                                        column_number,
-                                    ));
-                                }
-                            }
-                            TokenKind::KwChar => {
-                                self.consume(TokenKind::KwChar)?;
-                                if var_type != TokenKind::KwChar {
-                                    return Err(ParserError(
-                                        format!("Expected a pointer to {}", var_type),
-                                        line_number,
-                                        column_number,
-                                    ));
-                                }
-                            }
-                            TokenKind::KwFloat => {
-                                self.consume(TokenKind::KwFloat)?;
-                                if var_type != TokenKind::KwFloat {
-                                    return Err(ParserError(
-                                        format!("Expected a pointer to {}", var_type),
-                                        line_number,
-                                        column_number,
-                                    ));
-                                }
-                            }
-
-                            TokenKind::KwInt => {
-                                self.consume(TokenKind::KwInt)?;
-                                if var_type != TokenKind::KwInt {
-                                    return Err(ParserError(
-                                        format!("Expected a pointer to {}", var_type),
-                                        line_number,
-                                        column_number,
-                                    ));
-                                }
-                            }
-
-                            TokenKind::KwDouble => {
-                                self.consume(TokenKind::KwDouble)?;
-                                if var_type != TokenKind::KwDouble {
-                                    return Err(ParserError(
-                                        format!("Expected a pointer to {}", var_type),
-                                        line_number,
-                                        column_number,
-                                    ));
-                                }
-                            }
-
-                            _ => {
-                                return Err(ParserError(
-                                    format!(
-                                        "Expected type after `new` but found `{}`",
-                                        self.peek()
-                                    ),
-                                    line_number,
-                                    column_number,
-                                ));
-                            }
-                        }
-
-                        self.consume(TokenKind::SemiColon)?;
+                let new_value_column =
+                    self.tokens.peek().map_or(0, |token| token.get_column_number());
 
-                        return Ok(ast::Statement::PointerDeclarationHeap {
-                            base_type: var_type,
-                            pointer_name: name,
-                            line: line_number,
-                            pointer_ident_column,
-                        });
-                    }
+                let target = ast::Expr::Dereference(Box::new(ast::Expr::Ident(name.clone())));
+                let expression = compound_value(target, op, self.parse_expression()?);
 
-                    if self.peek() == TokenKind::Null {
-                        self.consume(TokenKind::Null)?;
+                match expression {
+                    // a cast and what the pointer points to, like in `*p = *p + 1`, are made into
+                    // literals before the assignment, and arithmetic on them is folded into one
+                    expression @ (ast::Expr::Ident(_)
+                    | ast::Expr::Literal(_)
+                    | ast::Expr::Cast { .. }
+                    | ast::Expr::Dereference(_)
+                    | ast::Expr::PrefixOp { .. }
+                    | ast::Expr::InfixOp { .. }) => {
                         self.consume(TokenKind::SemiColon)?;
 
-                        return Ok(ast::Statement::PointerDeclarationNull {
-                            base_type: var_type,
+                        return Ok(ast::Statement::Deref {
                             pointer_name: name,
+                            new_value: Box::new(expression),
                             line: line_number,
                             pointer_ident_column,
+                            new_value_column,
                         });
                     }
 
-                    let expression = self.parse_expression()?;
-
-                    match expression {
-                        ast::Expr::AddressOf(inner_expr) => {
-                            if let ast::Expr::Ident(ident) = *inner_expr {
-                                self.consume(TokenKind::SemiColon)?;
-
-                                return Ok(ast::Statement::PointerDeclaration {
-                                    base_type: var_type,
-                                    pointer_name: name,
-                                    value: Box::new(ast::Expr::Ident(ident)),
-                                    line: line_number,
-                                    pointer_ident_column,
-                                });
-                            } else {
-                                return Err(ParserError(
-                                    "Expected identifier after reference operator".to_string(),
-                                    line_number,
-                                    column_number,
-                                ));
-                            }
-                        }
-
-                        expression => {
-                            return Err(ParserError(
-                                format!("Expected reference operator but found `{}`", expression),
-                                line_number,
-                                column_number,
-                            ));
-                        }
+                    expression => {
+                        return Err(ParserError(
+                            format!("Expected identifier but found `{}`", expression),
+                            line_number,
+                            column_number,
+                        ));
                     }
                 }
+            }
 
-                if self.peek() == TokenKind::SemiColon {
-                    self.consume(TokenKind::SemiColon)?;
-                    return Ok(ast::Statement::VariableDeclarationWithoutAssignment {
-                        var_type,
-                        var_name: name,
-                        line: line_number,
-                        var_ident_column: ident.get_column_number(&self.input),
-                    });
-                }
+            TokenKind::LBrace => {
+                let (statements, end_line) = self.block()?;
 
-                self.consume(TokenKind::Eq)?;
+                Ok(ast::Statement::Block {
+                    statements,
+                    line: line_number,
+                    brace_column: column_number,
+                    end_line,
+                })
+            }
 
-                let value = self.parse_expression()?;
+            TokenKind::KwReturn => {
+                self.consume(TokenKind::KwReturn)?;
+
+                let value = if self.peek() == TokenKind::SemiColon {
+                    None
+                } else {
+                    Some(Box::new(self.parse_expression()?))
+                };
 
                 self.consume(TokenKind::SemiColon)?;
 
-                Ok(ast::Statement::VariableDeclaration {
-                    var_type,
-                    var_name: name,
-                    value: Box::new(value),
+                Ok(ast::Statement::Return {
+                    value,
                     line: line_number,
-                    var_ident_column: ident.get_column_number(&self.input),
+                    return_column: column_number,
                 })
             }
 
-            TokenKind::Asterisk => {
-                self.consume(TokenKind::Asterisk)?;
-                let ident = if let Some(token) = self.next() {
-                    token
-                } else {
-                    return Err(ParserError(
-                        "Expected identifier after dereference operator but found none".to_string(),
-                        line_number,
-                        column_number,
-                    ));
-                };
+            TokenKind::Identifier => {
+                let ident = self.next().unwrap();
+                let pointer_ident_column = ident.get_column_number();
 
-                let pointer_ident_column = ident.get_column_number(&self.input);
+                if let Some((_, reason)) =
+                    UNSUPPORTED_KEYWORDS.iter().find(|(keyword, _)| *keyword == self.text(ident))
+                {
+                    return Err(ParserError(reason.to_string(), line_number, pointer_ident_column));
+                }
 
-                if ident.kind != TokenKind::Identifier {
+                // `std::vector<int> v;`, the lexer only knows the few names from `std` it supports
+                if self.peek() == TokenKind::Colon {
                     return Err(ParserError(
                         format!(
-                            "Expected identifier after dereference operator `*`, but found `{}`",
-                            ident.kind
+                            "Names from the `{}` namespace are not supported",
+                            self.text(ident)
                         ),
                         line_number,
-                        column_number,
+                        pointer_ident_column,
                     ));
                 }
 
-                let name = self.text(ident).to_string();
-                self.consume(TokenKind::Eq)?;
+                if self.peek() == TokenKind::LParen {
+                    return self.function_call(ident, line_number);
+                }
 
-                let new_value_column =
-                    self.tokens.peek().map_or(0, |token| token.get_column_number(&self.input));
+                // `Point* p = new Point;` allocates an object of a class on the heap
+                if self.peek() == TokenKind::Asterisk {
+                    return self.object_allocation(ident, line_number);
+                }
 
-                let expression = self.parse_expression()?;
+                // `Value v;` declares a union without the `union` keyword, like C++ allows, or an object
+                // of a class
+                if self.peek() == TokenKind::Identifier {
+                    return self.union_declaration(ident, line_number);
+                }
 
-                match expression {
-                    ast::Expr::Ident(ident) => {
-                        self.consume(TokenKind::SemiColon)?;
+                if self.peek() == TokenKind::Dot {
+                    return self.member_assignment(ident, line_number);
+                }
 
-                        return Ok(ast::Statement::Deref {
-                            pointer_name: name,
-                            new_value: Box::new(ast::Expr::Ident(ident)),
-                            line: line_number,
-                            pointer_ident_column,
-                            new_value_column,
-                        });
-                    }
+                if self.peek() == TokenKind::LBracket {
+                    return self.array_assignment(ident, line_number);
+                }
 
-                    ast::Expr::Literal(lit) => {
-                        self.consume(TokenKind::SemiColon)?;
+                if self.peek() == TokenKind::Arrow {
+                    return self.pointer_member_assignment(ident, line_number);
+                }
 
-                        return Ok(ast::Statement::Deref {
-                            pointer_name: name,
-                            new_value: Box::new(ast::Expr::Literal(lit)),
-                            line: line_number,
-                            pointer_ident_column,
-                            new_value_column,
-                        });
-                    }
+                if let op @ (TokenKind::Increment | TokenKind::Decrement) = self.peek() {
+                    let op_column = self.tokens.peek().map_or(0, |token| token.get_column_number());
 
-                    expression => {
-                        return Err(ParserError(
-                            format!("Expected identifier but found `{}`", expression),
-                            line_number,
-                            column_number,
-                        ));
-                    }
+                    self.consume(op)?;
+                    self.consume(TokenKind::SemiColon)?;
+
+                    return Ok(self.increment(ident, op, line_number, op_column));
                 }
-            }
 
-            TokenKind::Identifier => {
-                let ident = self.next().unwrap();
-                let pointer_ident_column = ident.get_column_number(&self.input);
+                if let op @ (TokenKind::PlusEq
+                | TokenKind::MinusEq
+                | TokenKind::AsteriskEq
+                | TokenKind::SlashEq) = self.peek()
+                {
+                    let statement = self.compound_assignment(ident, op, line_number)?;
+                    self.consume(TokenKind::SemiColon)?;
+
+                    return Ok(statement);
+                }
 
                 let name = self.text(ident).to_string();
                 let mut assignment_column = 0;
 
                 if self.peek() == TokenKind::Eq {
                     assignment_column =
-                        self.tokens.peek().map_or(0, |token| token.get_column_number(&self.input));
+                        self.tokens.peek().map_or(0, |token| token.get_column_number());
 
                     self.consume(TokenKind::Eq)?;
                 }
@@ -293,7 +260,7 @@ where
 
                     let new_type;
                     let new_type_column =
-                        self.tokens.peek().map_or(0, |token| token.get_column_number(&self.input));
+                        self.tokens.peek().map_or(0, |token| token.get_column_number());
 
                     match self.peek() {
                         TokenKind::KwBool => {
@@ -325,10 +292,51 @@ where
                         }
                     }
 
+                    let allocation = self.new_allocation()?;
+                    self.consume(TokenKind::SemiColon)?;
+
+                    return Ok(ast::Statement::PointerAssignmentHeap {
+                        pointer_name: name,
+                        allocation,
+                        new_type,
+                        line: line_number,
+                        pointer_ident_column,
+                        new_type_column,
+                    });
+                }
+
+                if matches!(
+                    self.peek(),
+                    TokenKind::LParen | TokenKind::Malloc | TokenKind::Calloc | TokenKind::Realloc
+                ) {
+                    // C-style heap allocation, or a cast of another pointer
+                    let cast_type = self.allocation_cast()?;
+
+                    if self.peek() == TokenKind::Realloc {
+                        return self.c_reallocation(ident, cast_type, line_number);
+                    }
+
+                    if let Some(cast_type) = cast_type
+                        .filter(|_| !matches!(self.peek(), TokenKind::Malloc | TokenKind::Calloc))
+                    {
+                        let new_value = self.pointer_cast(cast_type)?;
+                        self.consume(TokenKind::SemiColon)?;
+
+                        return Ok(ast::Statement::PointerAssignment {
+                            pointer_name: name,
+                            new_value: Box::new(new_value),
+                            line: line_number,
+                            pointer_ident_column,
+                        });
+                    }
+
+                    let (allocation, new_type, new_type_column) = self.c_allocation(cast_type)?;
+
                     self.consume(TokenKind::SemiColon)?;
 
                     return Ok(ast::Statement::PointerAssignmentHeap {
                         pointer_name: name,
+                        allocation,
                         new_type,
                         line: line_number,
                         pointer_ident_column,
@@ -336,6 +344,32 @@ where
                     });
                 }
 
+                if self.peek() == TokenKind::MakeShared {
+                    let (value, value_column) = self.shared_value()?;
+                    self.consume(TokenKind::SemiColon)?;
+
+                    return Ok(ast::Statement::SharedPointerAssignment {
+                        pointer_name: name,
+                        value,
+                        line: line_number,
+                        pointer_ident_column,
+                        value_column,
+                    });
+                }
+
+                if matches!(self.peek(), TokenKind::MakeUnique | TokenKind::Move) {
+                    let (value, value_column) = self.unique_value()?;
+                    self.consume(TokenKind::SemiColon)?;
+
+                    return Ok(ast::Statement::UniquePointerAssignment {
+                        pointer_name: name,
+                        value,
+                        line: line_number,
+                        pointer_ident_column,
+                        value_column,
+                    });
+                }
+
                 if self.peek() == TokenKind::Null {
                     self.consume(TokenKind::Null)?;
                     self.consume(TokenKind::SemiColon)?;
@@ -374,7 +408,7 @@ where
                     var_name: name,
                     new_value: Box::new(expr),
                     line: line_number,
-                    var_ident_column: ident.get_column_number(&self.input),
+                    var_ident_column: ident.get_column_number(),
                     assignment_column,
                 })
             }
@@ -382,6 +416,16 @@ where
             TokenKind::Delete => {
                 self.consume(TokenKind::Delete)?;
 
+                // `delete[]` releases a block allocated with `new[]`
+                let release = match self.peek() {
+                    TokenKind::LBracket => {
+                        self.consume(TokenKind::LBracket)?;
+                        self.consume(TokenKind::RBracket)?;
+                        ast::Release::DeleteArray
+                    }
+                    _ => ast::Release::Delete,
+                };
+
                 let ident = if let Some(token) = self.next() {
                     token
                 } else {
@@ -408,15 +452,842 @@ where
 
                 Ok(ast::Statement::Delete {
                     pointer_name: name,
+                    release,
                     line: line_number,
-                    pointer_ident_column: ident.get_column_number(&self.input),
+                    pointer_ident_column: ident.get_column_number(),
                 })
             }
-            _ => Err(ParserError(
-                format!("Expected statement but found `{}`", self.peek()),
-                line_number,
-                column_number,
-            )),
-        }
+            TokenKind::Munmap => self.memory_unmap(line_number),
+
+            TokenKind::Free => {
+                self.consume(TokenKind::Free)?;
+                self.consume(TokenKind::LParen)?;
+
+                let ident = match self.next() {
+                    Some(token) if token.kind == TokenKind::Identifier => token,
+                    Some(token) => {
+                        return Err(ParserError(
+                            format!("Expected pointer passed to `free` but found `{}`", token.kind),
+                            line_number,
+                            column_number,
+                        ));
+                    }
+                    None => {
+                        return Err(ParserError(
+                            "Expected pointer passed to `free` but found none".to_string(),
+                            line_number,
+                            column_number,
+                        ));
+                    }
+                };
+
+                self.consume(TokenKind::RParen)?;
+                self.consume(TokenKind::SemiColon)?;
+
+                // `free` releases the block just like `delete`
+                Ok(ast::Statement::Delete {
+                    pointer_name: self.text(ident).to_string(),
+                    release: ast::Release::Free,
+                    line: line_number,
+                    pointer_ident_column: ident.get_column_number(),
+                })
+            }
+
+            TokenKind::UniquePtr => self.unique_pointer_declaration(line_number),
+            TokenKind::SharedPtr => self.shared_pointer_declaration(line_number),
+
+            TokenKind::KwUnion => self.union_statement(line_number),
+
+            TokenKind::KwClass | TokenKind::KwStruct => self.class_definition(line_number),
+
+            TokenKind::KwIf => self.if_statement(line_number, column_number),
+
+            TokenKind::KwWhile => self.while_loop(line_number, column_number),
+
+            TokenKind::KwFor => self.for_loop(line_number, column_number),
+
+            TokenKind::Mutex => self.mutex_declaration(line_number),
+
+            TokenKind::ThreadSpawn => {
+                self.consume(TokenKind::ThreadSpawn)?;
+
+                let (statements, end_line) = self.block()?;
+
+                Ok(ast::Statement::ThreadSpawn {
+                    statements,
+                    line: line_number,
+                    spawn_column: column_number,
+                    end_line,
+                })
+            }
+
+            op @ (TokenKind::Increment | TokenKind::Decrement) => {
+                self.consume(op)?;
+
+                let ident = match self.next() {
+                    Some(token) if token.kind == TokenKind::Identifier => token,
+                    Some(token) => {
+                        return Err(ParserError(
+                            format!(
+                                "Expected identifier after `{}` but found `{}`",
+                                op, token.kind
+                            ),
+                            line_number,
+                            column_number,
+                        ));
+                    }
+                    None => {
+                        return Err(ParserError(
+                            format!("Expected identifier after `{}` but found none", op),
+                            line_number,
+                            column_number,
+                        ));
+                    }
+                };
+
+                self.consume(TokenKind::SemiColon)?;
+
+                Ok(self.increment(ident, op, line_number, column_number))
+            }
+
+            TokenKind::Swap => {
+                self.consume(TokenKind::Swap)?;
+                self.consume(TokenKind::LParen)?;
+
+                let mut operands = Vec::new();
+
+                while operands.len() < 2 {
+                    if !operands.is_empty() {
+                        self.consume(TokenKind::Comma)?;
+                    }
+
+                    match self.next() {
+                        Some(token) if token.kind == TokenKind::Identifier => operands.push(token),
+                        Some(token) => {
+                            return Err(ParserError(
+                                format!(
+                                    "Expected symbol passed to `swap` but found `{}`",
+                                    token.kind
+                                ),
+                                line_number,
+                                column_number,
+                            ));
+                        }
+                        None => {
+                            return Err(ParserError(
+                                "Expected symbol passed to `swap` but found none".to_string(),
+                                line_number,
+                                column_number,
+                            ));
+                        }
+                    }
+                }
+
+                self.consume(TokenKind::RParen)?;
+                self.consume(TokenKind::SemiColon)?;
+
+                Ok(ast::Statement::Swap {
+                    lhs: self.text(operands[0]).to_string(),
+                    rhs: self.text(operands[1]).to_string(),
+                    line: line_number,
+                    lhs_column: operands[0].get_column_number(),
+                    rhs_column: operands[1].get_column_number(),
+                })
+            }
+
+            TokenKind::Poke => {
+                self.consume(TokenKind::Poke)?;
+                self.consume(TokenKind::LParen)?;
+
+                let address_column =
+                    self.tokens.peek().map_or(0, |token| token.get_column_number());
+                let address = self.parse_expression()?;
+
+                self.consume(TokenKind::Comma)?;
+
+                let value_column = self.tokens.peek().map_or(0, |token| token.get_column_number());
+                let value = self.parse_expression()?;
+
+                self.consume(TokenKind::RParen)?;
+                self.consume(TokenKind::SemiColon)?;
+
+                Ok(ast::Statement::Poke {
+                    address: Box::new(address),
+                    value: Box::new(value),
+                    line: line_number,
+                    address_column,
+                    value_column,
+                })
+            }
+
+            TokenKind::Memset => {
+                self.consume(TokenKind::Memset)?;
+                self.consume(TokenKind::LParen)?;
+
+                let (target, target_column) =
+                    self.memory_operand(TokenKind::Memset, line_number, column_number)?;
+
+                self.consume(TokenKind::Comma)?;
+
+                let value_column = self.tokens.peek().map_or(0, |token| token.get_column_number());
+                let value = self.parse_expression()?;
+
+                self.consume(TokenKind::Comma)?;
+
+                let size_column = self.tokens.peek().map_or(0, |token| token.get_column_number());
+                let size = self.parse_expression()?;
+
+                self.consume(TokenKind::RParen)?;
+                self.consume(TokenKind::SemiColon)?;
+
+                Ok(ast::Statement::Memset {
+                    target,
+                    value: Box::new(value),
+                    size: Box::new(size),
+                    line: line_number,
+                    target_column,
+                    value_column,
+                    size_column,
+                })
+            }
+
+            TokenKind::Memcpy => {
+                self.consume(TokenKind::Memcpy)?;
+                self.consume(TokenKind::LParen)?;
+
+                let (destination, destination_column) =
+                    self.memory_operand(TokenKind::Memcpy, line_number, column_number)?;
+
+                self.consume(TokenKind::Comma)?;
+
+                let (source, source_column) =
+                    self.memory_operand(TokenKind::Memcpy, line_number, column_number)?;
+
+                self.consume(TokenKind::Comma)?;
+
+                let size_column = self.tokens.peek().map_or(0, |token| token.get_column_number());
+                let size = self.parse_expression()?;
+
+                self.consume(TokenKind::RParen)?;
+                self.consume(TokenKind::SemiColon)?;
+
+                Ok(ast::Statement::Memcpy {
+                    destination,
+                    source,
+                    size: Box::new(size),
+                    line: line_number,
+                    destination_column,
+                    source_column,
+                    size_column,
+                })
+            }
+
+            TokenKind::Strcpy => {
+                self.consume(TokenKind::Strcpy)?;
+                self.consume(TokenKind::LParen)?;
+
+                let (destination, destination_column) =
+                    self.memory_operand(TokenKind::Strcpy, line_number, column_number)?;
+
+                self.consume(TokenKind::Comma)?;
+
+                // the source is a string literal or another pointer or array of `char`s
+                let source_column = self.tokens.peek().map_or(0, |token| token.get_column_number());
+                let source = self.parse_expression()?;
+
+                self.consume(TokenKind::RParen)?;
+                self.consume(TokenKind::SemiColon)?;
+
+                Ok(ast::Statement::Strcpy {
+                    destination,
+                    source: Box::new(source),
+                    line: line_number,
+                    destination_column,
+                    source_column,
+                })
+            }
+
+            _ => Err(ParserError(
+                format!("Expected statement but found `{}`", self.peek()),
+                line_number,
+                column_number,
+            )),
+        }
+    }
+
+    /// Parses a declarator of a declaration, like `*p = &x` in `int a, *p = &x;`, after the type
+    ///
+    /// A declarator ending in `,` leaves the type in `declarator_list`, so the next one is parsed as a
+    /// declaration of its own by [statements](Parser::statements)
+    ///
+    /// # Arguments
+    /// - `var_type`: The type the declaration starts with, pointers add their `*` to it
+    /// - `line_number`: The line of the declaration
+    /// - `column_number`: The column errors about the declaration point at
+    ///
+    /// # Returns
+    /// - [Result](crate::error::Result): A result containing either:
+    ///    - [Statement](crate::parser::ast::Statement): The declaration, or a function definition
+    ///    - [Error](crate::error::Error): An error if the declarator is malformed
+    pub(crate) fn declaration(
+        &mut self,
+        var_type: TokenKind,
+        line_number: usize,
+        column_number: usize,
+    ) -> Result<ast::Statement> {
+        // `int*` has an indirection of 1, `int**` of 2 and so on
+        let mut indirection = 0;
+
+        while self.peek() == TokenKind::Asterisk {
+            indirection += 1;
+            self.consume(TokenKind::Asterisk)?;
+        }
+
+        let pointer = indirection > 0;
+
+        let ident = if let Some(token) = self.next() {
+            token
+        } else {
+            return Err(ParserError(
+                "Expected identifier but found none".to_string(),
+                line_number,
+                column_number,
+            ));
+        };
+
+        if ident.kind != TokenKind::Identifier {
+            return Err(ParserError(
+                format!("Expected identifier but found `{}`", ident.kind),
+                line_number,
+                column_number,
+            ));
+        }
+
+        if var_type == TokenKind::KwString && (pointer || self.peek() == TokenKind::LParen) {
+            return Err(ParserError(
+                "Only `std::string` variables are supported yet".to_string(),
+                line_number,
+                column_number,
+            ));
+        }
+
+        if pointer
+            && !matches!(
+                var_type,
+                TokenKind::KwInt
+                    | TokenKind::KwShort
+                    | TokenKind::KwUnsignedShort
+                    | TokenKind::KwUnsignedInt
+                    | TokenKind::KwLong
+                    | TokenKind::KwUnsignedLong
+                    | TokenKind::KwLongLong
+                    | TokenKind::KwUnsignedLongLong
+                    | TokenKind::KwChar
+                    | TokenKind::KwFloat
+                    | TokenKind::KwDouble
+                    | TokenKind::KwBool
+                    | TokenKind::KwVoid
+            )
+        {
+            return Err(ParserError(
+                format!("Pointers to `{}` are not supported yet", var_type),
+                line_number,
+                column_number,
+            ));
+        }
+
+        if self.peek() == TokenKind::LBracket {
+            if pointer {
+                return Err(ParserError(
+                    "Arrays of pointers are not supported yet".to_string(),
+                    line_number,
+                    column_number,
+                ));
+            }
+
+            return self.array_declaration(var_type, ident, line_number);
+        }
+
+        if self.peek() == TokenKind::LParen {
+            if pointer {
+                return Err(ParserError(
+                    "Functions returning pointers are not supported yet".to_string(),
+                    line_number,
+                    column_number,
+                ));
+            }
+
+            // `void` functions don't return anything
+            let return_type = Some(var_type).filter(|t| *t != TokenKind::KwVoid);
+
+            return self.function_definition(return_type, ident, line_number);
+        }
+
+        let name = self.text(ident).to_string();
+
+        if var_type == TokenKind::KwVoid && !pointer {
+            return Err(ParserError(
+                format!("Variable `{}` can't be `void`, only pointers can", name),
+                line_number,
+                column_number,
+            ));
+        }
+
+        if pointer {
+            let pointer_ident_column = ident.get_column_number();
+
+            // a pointer declared without a value holds garbage, which can't be followed any more than
+            // `nullptr` can
+            if matches!(self.peek(), TokenKind::Comma | TokenKind::SemiColon) {
+                self.end_declarator(var_type)?;
+
+                return Ok(ast::Statement::PointerDeclarationNull {
+                    base_type: var_type,
+                    indirection,
+                    pointer_name: name,
+                    line: line_number,
+                    pointer_ident_column,
+                });
+            }
+
+            self.consume(TokenKind::Eq)?;
+
+            if self.peek() == TokenKind::New {
+                // Heap allocation
+
+                if indirection > 1 {
+                    return Err(ParserError(
+                        "Pointers to pointers can't point to the heap yet".to_string(),
+                        line_number,
+                        column_number,
+                    ));
+                }
+
+                self.consume(TokenKind::New)?;
+
+                match self.peek() {
+                    TokenKind::KwBool => {
+                        self.consume(TokenKind::KwBool)?;
+                        if var_type != TokenKind::Bool {
+                            return Err(ParserError(
+                                format!("Expected a pointer to {}", var_type),
+                                line_number,
+                                column_number,
+                            ));
+                        }
+                    }
+                    TokenKind::KwChar => {
+                        self.consume(TokenKind::KwChar)?;
+                        if var_type != TokenKind::KwChar {
+                            return Err(ParserError(
+                                format!("Expected a pointer to {}", var_type),
+                                line_number,
+                                column_number,
+                            ));
+                        }
+                    }
+                    TokenKind::KwFloat => {
+                        self.consume(TokenKind::KwFloat)?;
+                        if var_type != TokenKind::KwFloat {
+                            return Err(ParserError(
+                                format!("Expected a pointer to {}", var_type),
+                                line_number,
+                                column_number,
+                            ));
+                        }
+                    }
+
+                    TokenKind::KwInt => {
+                        self.consume(TokenKind::KwInt)?;
+                        if var_type != TokenKind::KwInt {
+                            return Err(ParserError(
+                                format!("Expected a pointer to {}", var_type),
+                                line_number,
+                                column_number,
+                            ));
+                        }
+                    }
+
+                    TokenKind::KwDouble => {
+                        self.consume(TokenKind::KwDouble)?;
+                        if var_type != TokenKind::KwDouble {
+                            return Err(ParserError(
+                                format!("Expected a pointer to {}", var_type),
+                                line_number,
+                                column_number,
+                            ));
+                        }
+                    }
+
+                    _ => {
+                        return Err(ParserError(
+                            format!("Expected type after `new` but found `{}`", self.peek()),
+                            line_number,
+                            column_number,
+                        ));
+                    }
+                }
+
+                let allocation = self.new_allocation()?;
+                self.end_declarator(var_type)?;
+
+                return Ok(ast::Statement::PointerDeclarationHeap {
+                    base_type: var_type,
+                    allocation,
+                    new_type: var_type,
+                    pointer_name: name,
+                    line: line_number,
+                    pointer_ident_column,
+                });
+            }
+
+            if matches!(
+                self.peek(),
+                TokenKind::LParen | TokenKind::Malloc | TokenKind::Calloc | TokenKind::Mmap
+            ) {
+                // C-style heap allocation, a memory mapping, or a cast of another pointer
+
+                let cast_type = self.allocation_cast()?;
+
+                if let Some(cast_type) = cast_type.filter(|_| {
+                    !matches!(self.peek(), TokenKind::Malloc | TokenKind::Calloc | TokenKind::Mmap)
+                }) {
+                    let value = self.pointer_cast(cast_type)?;
+                    self.end_declarator(var_type)?;
+
+                    return Ok(ast::Statement::PointerDeclaration {
+                        base_type: var_type,
+                        indirection,
+                        pointer_name: name,
+                        value: Box::new(value),
+                        line: line_number,
+                        pointer_ident_column,
+                    });
+                }
+
+                if indirection > 1 {
+                    return Err(ParserError(
+                        "Pointers to pointers can't point to the heap yet".to_string(),
+                        line_number,
+                        column_number,
+                    ));
+                }
+
+                if self.peek() == TokenKind::Mmap {
+                    if let Some(cast_type) = cast_type.filter(|cast_type| {
+                        *cast_type != var_type && *cast_type != TokenKind::KwVoid
+                    }) {
+                        return Err(ParserError(
+                            format!(
+                                "Cannot cast a mapping to `{}*` for a pointer to {}",
+                                cast_type, var_type
+                            ),
+                            line_number,
+                            column_number,
+                        ));
+                    }
+
+                    let (count, size_type) = self.memory_map()?;
+                    self.end_declarator(var_type)?;
+
+                    return Ok(ast::Statement::MemoryMap {
+                        base_type: var_type,
+                        count,
+                        size_type,
+                        pointer_name: name,
+                        line: line_number,
+                        pointer_ident_column,
+                    });
+                }
+
+                let (allocation, size_type, size_column) = self.c_allocation(cast_type)?;
+
+                // a number of bytes without a cast is as many values of the pointer's type as fit in them
+                let (allocation, size_type) =
+                    if size_type == TokenKind::KwVoid && var_type != TokenKind::KwVoid {
+                        let pointer_size = self.architecture.pointer_size();
+
+                        (
+                            values_in_bytes(
+                                allocation,
+                                var_type,
+                                pointer_size,
+                                line_number,
+                                size_column,
+                            )?,
+                            var_type,
+                        )
+                    } else {
+                        (allocation, size_type)
+                    };
+
+                if size_type != var_type && var_type != TokenKind::KwVoid {
+                    return Err(ParserError(
+                        format!("Cannot allocate `{}` for a pointer to {}", size_type, var_type),
+                        line_number,
+                        column_number,
+                    ));
+                }
+
+                self.end_declarator(var_type)?;
+
+                return Ok(ast::Statement::PointerDeclarationHeap {
+                    base_type: var_type,
+                    allocation,
+                    new_type: size_type,
+                    pointer_name: name,
+                    line: line_number,
+                    pointer_ident_column,
+                });
+            }
+
+            if self.peek() == TokenKind::Null {
+                self.consume(TokenKind::Null)?;
+                self.end_declarator(var_type)?;
+
+                return Ok(ast::Statement::PointerDeclarationNull {
+                    base_type: var_type,
+                    indirection,
+                    pointer_name: name,
+                    line: line_number,
+                    pointer_ident_column,
+                });
+            }
+
+            let expression = self.parse_expression()?;
+
+            match expression {
+                ast::Expr::AddressOf(inner_expr) => {
+                    if let ast::Expr::Ident(ident) = *inner_expr {
+                        self.end_declarator(var_type)?;
+
+                        return Ok(ast::Statement::PointerDeclaration {
+                            base_type: var_type,
+                            indirection,
+                            pointer_name: name,
+                            value: Box::new(ast::Expr::AddressOf(Box::new(ast::Expr::Ident(
+                                ident,
+                            )))),
+                            line: line_number,
+                            pointer_ident_column,
+                        });
+                    } else {
+                        return Err(ParserError(
+                            "Expected identifier after reference operator".to_string(),
+                            line_number,
+                            column_number,
+                        ));
+                    }
+                }
+
+                // a copy of another pointer, or pointer arithmetic on one like `p + 1`
+                expression @ (ast::Expr::Ident(_)
+                | ast::Expr::InfixOp {
+                    op: TokenKind::Plus | TokenKind::Minus,
+                    ..
+                }) => {
+                    self.end_declarator(var_type)?;
+
+                    return Ok(ast::Statement::PointerDeclaration {
+                        base_type: var_type,
+                        indirection,
+                        pointer_name: name,
+                        value: Box::new(expression),
+                        line: line_number,
+                        pointer_ident_column,
+                    });
+                }
+
+                expression => {
+                    return Err(ParserError(
+                        format!("Expected reference operator but found `{}`", expression),
+                        line_number,
+                        column_number,
+                    ));
+                }
+            }
+        }
+
+        if matches!(self.peek(), TokenKind::Comma | TokenKind::SemiColon) {
+            self.end_declarator(var_type)?;
+            return Ok(ast::Statement::VariableDeclarationWithoutAssignment {
+                var_type,
+                var_name: name,
+                line: line_number,
+                var_ident_column: ident.get_column_number(),
+            });
+        }
+
+        self.consume(TokenKind::Eq)?;
+
+        let value = self.parse_expression()?;
+
+        self.end_declarator(var_type)?;
+
+        Ok(ast::Statement::VariableDeclaration {
+            var_type,
+            var_name: name,
+            value: Box::new(value),
+            line: line_number,
+            var_ident_column: ident.get_column_number(),
+        })
+    }
+
+    /// Consumes the `;` ending a declaration, or the `,` before its next declarator
+    pub(crate) fn end_declarator(&mut self, var_type: TokenKind) -> Result<()> {
+        if self.peek() == TokenKind::Comma {
+            self.consume(TokenKind::Comma)?;
+            self.declarator_list = Some(var_type);
+            return Ok(());
+        }
+
+        self.consume(TokenKind::SemiColon)
+    }
+
+    /// Builds the statement for `x++`, `++x`, `x--` or `--x`, which is `x = x + 1` or `x = x - 1` since the
+    /// value of the increment isn't used
+    pub(crate) fn increment(
+        &self,
+        ident: Token,
+        op: TokenKind,
+        line_number: usize,
+        op_column: usize,
+    ) -> ast::Statement {
+        let op = match op {
+            TokenKind::Increment => TokenKind::Plus,
+            _ => TokenKind::Minus,
+        };
+
+        self.read_modify_write(
+            ident,
+            op,
+            ast::Expr::Literal(ast::Lit::Int(1)),
+            line_number,
+            op_column,
+        )
+    }
+
+    /// Parses a compound assignment like `x += 2`, which is `x = x + (2)`, up to the end of its value
+    pub(crate) fn compound_assignment(
+        &mut self,
+        ident: Token,
+        op: TokenKind,
+        line_number: usize,
+    ) -> Result<ast::Statement> {
+        let op_column = self.tokens.peek().map_or(0, |token| token.get_column_number());
+        self.consume(op)?;
+
+        let op = arithmetic_operator(op).unwrap_or(TokenKind::Slash);
+        let value = self.parse_expression()?;
+
+        Ok(self.read_modify_write(ident, op, value, line_number, op_column))
+    }
+
+    /// Consumes the `=` of an assignment, or the operator of a compound assignment like `+=`
+    ///
+    /// # Returns
+    /// - [Result](crate::error::Result): A result containing either:
+    ///    - `Option<TokenKind>`: The operator a compound assignment combines the values with, like `+`
+    ///      for `+=`, or `None` for `=`
+    ///    - [Error](crate::error::Error): An error if the next token isn't an assignment
+    pub(crate) fn assignment_operator(&mut self) -> Result<Option<TokenKind>> {
+        let op = self.peek();
+
+        match arithmetic_operator(op) {
+            Some(arithmetic) => {
+                self.consume(op)?;
+                Ok(Some(arithmetic))
+            }
+            None => {
+                self.consume(TokenKind::Eq)?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Parses the `=` of an assignment to an element, a member or what a pointer points to, and the
+    /// value after it. A compound assignment like `a[1] += 2` assigns `a[1] + (2)`
+    ///
+    /// # Arguments
+    /// - `target`: What is assigned to, read back by a compound assignment
+    pub(crate) fn assigned_value(&mut self, target: ast::Expr) -> Result<ast::Expr> {
+        let op = self.assignment_operator()?;
+
+        Ok(compound_value(target, op, self.parse_expression()?))
+    }
+
+    /// Builds the assignment of a variable's value combined with another, like `x = x + 1`
+    fn read_modify_write(
+        &self,
+        ident: Token,
+        op: TokenKind,
+        value: ast::Expr,
+        line_number: usize,
+        op_column: usize,
+    ) -> ast::Statement {
+        let name = self.text(ident).to_string();
+
+        ast::Statement::VariableAssignment {
+            var_name: name.clone(),
+            new_value: Box::new(ast::Expr::InfixOp {
+                op,
+                lhs: Box::new(ast::Expr::Ident(name)),
+                rhs: Box::new(value),
+            }),
+            line: line_number,
+            var_ident_column: ident.get_column_number(),
+            assignment_column: op_column,
+        }
+    }
+
+    /// Parses the pointer or array passed to `memset`, `memcpy`, `strcpy` or `strlen`, with the column
+    /// it starts at
+    pub(crate) fn memory_operand(
+        &mut self,
+        function: TokenKind,
+        line_number: usize,
+        column_number: usize,
+    ) -> Result<(String, usize)> {
+        match self.next() {
+            Some(token) if token.kind == TokenKind::Identifier => {
+                Ok((self.text(token).to_string(), token.get_column_number()))
+            }
+            Some(token) => Err(ParserError(
+                format!(
+                    "Expected pointer or array passed to `{}` but found `{}`",
+                    function, token.kind
+                ),
+                line_number,
+                column_number,
+            )),
+            None => Err(ParserError(
+                format!("Expected pointer or array passed to `{}` but found none", function),
+                line_number,
+                column_number,
+            )),
+        }
+    }
+}
+
+/// Gets the operator a compound assignment combines the values with, like `+` for `+=`
+fn arithmetic_operator(op: TokenKind) -> Option<TokenKind> {
+    match op {
+        TokenKind::PlusEq => Some(TokenKind::Plus),
+        TokenKind::MinusEq => Some(TokenKind::Minus),
+        TokenKind::AsteriskEq => Some(TokenKind::Asterisk),
+        TokenKind::SlashEq => Some(TokenKind::Slash),
+        _ => None,
+    }
+}
+
+/// Builds the value assigned to `target`, combined with what it holds for a compound assignment
+fn compound_value(target: ast::Expr, op: Option<TokenKind>, value: ast::Expr) -> ast::Expr {
+    match op {
+        Some(op) => ast::Expr::InfixOp {
+            op,
+            lhs: Box::new(target),
+            rhs: Box::new(value),
+        },
+        None => value,
     }
 }
@@ -0,0 +1,184 @@
+use super::{ast, Parser};
+use crate::error::{Error::ParserError, Result};
+use crate::lexer::token::{Token, TokenKind};
+
+impl<'input, I> Parser<'input, I>
+where
+    I: Iterator<Item = Token>,
+{
+    /// Parses a statement starting with `union`, either a definition like `union Value { int i; char c; };`
+    /// or a declaration like `union Value v;`
+    pub(crate) fn union_statement(&mut self, line_number: usize) -> Result<ast::Statement> {
+        self.consume(TokenKind::KwUnion)?;
+
+        let union_ident = self.union_identifier("union name", line_number)?;
+
+        if self.peek() != TokenKind::LBrace {
+            return self.union_declaration(union_ident, line_number);
+        }
+
+        self.consume(TokenKind::LBrace)?;
+
+        let mut members = Vec::new();
+
+        while !matches!(self.peek(), TokenKind::RBrace | TokenKind::EOF) {
+            members.push(self.union_member()?);
+        }
+
+        self.consume(TokenKind::RBrace)?;
+        self.consume(TokenKind::SemiColon)?;
+
+        if members.is_empty() {
+            return Err(ParserError(
+                format!("Union `{}` must have at least one member", self.text(union_ident)),
+                line_number,
+                union_ident.get_column_number(),
+            ));
+        }
+
+        Ok(ast::Statement::UnionDefinition {
+            union_name: self.text(union_ident).to_string(),
+            members,
+            line: line_number,
+            union_ident_column: union_ident.get_column_number(),
+        })
+    }
+
+    /// Parses the rest of a union declaration like `Value v;`, starting at the name of the variable, an
+    /// object can be given the arguments of its constructor like `Counter c(3);` and either can be
+    /// given a brace initializer like `Point p = {1, 2};` or `Point p{1, 2};`
+    pub(crate) fn union_declaration(
+        &mut self,
+        union_ident: Token,
+        line_number: usize,
+    ) -> Result<ast::Statement> {
+        let ident = self.union_identifier("identifier", line_number)?;
+
+        let (arguments, initializer) = match self.peek() {
+            TokenKind::LParen => (self.arguments()?, None),
+            TokenKind::LBrace => (Vec::new(), Some(self.initializer_list()?)),
+            TokenKind::Eq => {
+                self.consume(TokenKind::Eq)?;
+                (Vec::new(), Some(self.initializer_list()?))
+            }
+            _ => (Vec::new(), None),
+        };
+
+        self.consume(TokenKind::SemiColon)?;
+
+        Ok(ast::Statement::UnionDeclaration {
+            union_name: self.text(union_ident).to_string(),
+            var_name: self.text(ident).to_string(),
+            arguments,
+            initializer,
+            line: line_number,
+            var_ident_column: ident.get_column_number(),
+        })
+    }
+
+    /// Parses the rest of an assignment to a member like `v.i = 65;`, starting at the `.`, a call
+    /// like `m.lock();` is a call to a method of a mutex and any other call like `c.add(2);` a call to a
+    /// method of an object
+    pub(crate) fn member_assignment(
+        &mut self,
+        ident: Token,
+        line_number: usize,
+    ) -> Result<ast::Statement> {
+        self.consume(TokenKind::Dot)?;
+
+        let member = self.union_identifier("member name", line_number)?;
+
+        if self.peek() == TokenKind::LParen {
+            return match self.text(member) {
+                "lock" | "unlock" => self.mutex_call(ident, member, line_number),
+                _ => self.method_call(ident, member, line_number),
+            };
+        }
+
+        let var_name = self.text(ident).to_string();
+        let member_name = self.text(member).to_string();
+        let new_value = self.assigned_value(ast::Expr::Member {
+            var_name: var_name.clone(),
+            member: member_name.clone(),
+        })?;
+        self.consume(TokenKind::SemiColon)?;
+
+        Ok(ast::Statement::MemberAssignment {
+            var_name,
+            member: member_name,
+            new_value: Box::new(new_value),
+            line: line_number,
+            var_ident_column: ident.get_column_number(),
+            member_column: member.get_column_number(),
+        })
+    }
+
+    /// Parses a member of a union definition like `int i;`, members are values, not strings or pointers
+    fn union_member(&mut self) -> Result<ast::UnionMember> {
+        let line_number = self.tokens.peek().map_or(0, |token| token.get_line_number());
+
+        let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+        let member_type = match self.peek() {
+            member_type @ TokenKind::KwInt
+            | member_type @ TokenKind::KwShort
+            | member_type @ TokenKind::KwUnsignedShort
+            | member_type @ TokenKind::KwUnsignedInt
+            | member_type @ TokenKind::KwLong
+            | member_type @ TokenKind::KwUnsignedLong
+            | member_type @ TokenKind::KwLongLong
+            | member_type @ TokenKind::KwUnsignedLongLong
+            | member_type @ TokenKind::KwChar
+            | member_type @ TokenKind::KwFloat
+            | member_type @ TokenKind::KwDouble
+            | member_type @ TokenKind::KwBool => {
+                self.consume(member_type)?;
+                member_type
+            }
+
+            _ => {
+                return Err(ParserError(
+                    format!("Expected member type but found `{}`", self.peek()),
+                    line_number,
+                    column_number,
+                ));
+            }
+        };
+
+        if self.peek() == TokenKind::Asterisk {
+            return Err(ParserError(
+                "Pointer members are not supported yet".to_string(),
+                line_number,
+                column_number,
+            ));
+        }
+
+        let ident = self.union_identifier("member name", line_number)?;
+
+        self.consume(TokenKind::SemiColon)?;
+
+        Ok(ast::UnionMember {
+            member_type,
+            member_name: self.text(ident).to_string(),
+            member_ident_column: ident.get_column_number(),
+        })
+    }
+
+    pub(crate) fn union_identifier(&mut self, expected: &str, line_number: usize) -> Result<Token> {
+        let column_number = self.tokens.peek().map_or(0, |token| token.get_column_number());
+
+        match self.next() {
+            Some(token) if token.kind == TokenKind::Identifier => Ok(token),
+            Some(token) => Err(ParserError(
+                format!("Expected {} but found `{}`", expected, token.kind),
+                line_number,
+                column_number,
+            )),
+            None => Err(ParserError(
+                format!("Expected {} but found none", expected),
+                line_number,
+                column_number,
+            )),
+        }
+    }
+}
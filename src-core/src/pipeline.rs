@@ -0,0 +1,65 @@
+//! # Pipeline
+//! Runs a program through every step between the source a frontend sends and the results it shows
+//!
+//! The source is preprocessed with the macros the options define, parsed with their strictness and
+//! architecture, and analyzed with the tags of its `// @tag:` annotations added to the options. Every
+//! frontend and every feature analyzing a program goes through here, so a program comes out the same
+//! whichever of them analyzes it
+
+use crate::analyzer::{
+    read_tag_annotations, AnalysisResult, Analyzer, AnalyzerOptions, AnalyzerState,
+};
+use crate::error::Result;
+use crate::parser::ast::Statement;
+use crate::parser::Parser;
+use crate::preprocess::preprocess_with_defines;
+
+/// Preprocesses and parses source code the way it is analyzed with the given options
+///
+/// # Arguments
+/// - `source`: The source code as written by the user
+/// - `options`: The options the program is analyzed with, which give the macros defined outside the
+///   source, the strictness and the architecture
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - `Vec<Statement>`: The statements of the program
+///    - [Error](crate::error::Error): An error if the program fails to parse
+pub fn parse_source(source: &str, options: &AnalyzerOptions) -> Result<Vec<Statement>> {
+    Parser::with_options(&preprocess_with_defines(source, &options.defines), options).parse()
+}
+
+/// Gets the options a program is analyzed with, the given ones along with the tags its `// @tag:`
+/// annotations give the lines they are on
+///
+/// # Arguments
+/// - `source`: The source code as written by the user
+/// - `options`: The options to analyze the program with
+///
+/// # Returns
+/// - [AnalyzerOptions](crate::analyzer::AnalyzerOptions): The options with the tags of the source
+pub fn source_options(source: &str, mut options: AnalyzerOptions) -> AnalyzerOptions {
+    options.line_tags.extend(read_tag_annotations(source));
+    options
+}
+
+/// Parses and analyzes source code with the given options
+///
+/// # Arguments
+/// - `source`: The source code as written by the user
+/// - `state`: Where the blocks of each pointer were placed between runs
+/// - `options`: The options to analyze the program with
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - [AnalysisResult](crate::analyzer::AnalysisResult): What the memory of the program ends up as
+///    - [Error](crate::error::Error): An error if the program fails to parse or to analyze
+pub async fn analyze_source<S: AnalyzerState>(
+    source: &str,
+    state: &mut S,
+    options: AnalyzerOptions,
+) -> Result<AnalysisResult> {
+    let statements = parse_source(source, &options)?;
+
+    Analyzer::new(source_options(source, options)).analyze_statements(statements, state).await
+}
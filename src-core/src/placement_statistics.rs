@@ -0,0 +1,197 @@
+//! # Placement Statistics
+//! Running the same program under many seeds to see how the random placement of heap blocks behaves
+//! statistically, rather than in the single layout one run produces
+//!
+//! Blocks are matched across runs by the line that allocated them and the order they were allocated in,
+//! so the second block allocated on line 3 is compared with the second block allocated on line 3 in every
+//! other run. Only the blocks still allocated or leaked when the program ends are compared, freed blocks
+//! are merged with the free memory around them
+
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use crate::analyzer::heap_allocator::{fragmentation, HeapBlockState};
+use crate::analyzer::{Analyzer, AnalyzerOptions, HeapOrder};
+use crate::error::Result;
+use crate::pipeline::{parse_source, source_options};
+use crate::session::Session;
+
+/// Represents the layout one seed produced
+///
+/// # Fields
+/// - `seed`: The seed the program ran with
+/// - `heap_size`: The size the heap grew to in bytes
+/// - `fragmentation`: How fragmented the free memory was once the program ended, the share of the free
+///   bytes outside the largest free range
+/// - `placements`: Where each block was placed, by the label it is matched across runs with
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlacementRun {
+    pub seed: u64,
+    pub heap_size: usize,
+    pub fragmentation: f64,
+    pub placements: IndexMap<String, usize>,
+}
+
+/// Represents how far apart the runs placed the same block
+///
+/// # Fields
+/// - `block`: The label of the block, the line that allocated it like `line 3`, followed by `#2`, `#3` and
+///   so on for the blocks allocated on the line after the first
+/// - `min`: The lowest position the block was placed at
+/// - `max`: The highest position the block was placed at
+/// - `distinct`: How many different positions the block was placed at
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressSpread {
+    pub block: String,
+    pub min: usize,
+    pub max: usize,
+    pub distinct: usize,
+}
+
+/// Represents how a measurement is distributed across the runs
+///
+/// # Fields
+/// - `min`: The lowest value
+/// - `max`: The highest value
+/// - `mean`: The average value
+/// - `median`: The middle value, the average of the two middle values for an even number of runs
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Distribution {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub median: f64,
+}
+
+/// Represents the statistics of a program run under many seeds
+///
+/// # Fields
+/// - `runs`: The layout of every run, in the order of the seeds
+/// - `spreads`: How far apart the runs placed each block, in the order the blocks were first allocated
+/// - `fragmentation`: How fragmented the free memory ended up across the runs
+/// - `heap_size`: How big the heap grew across the runs
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlacementStatistics {
+    pub runs: Vec<PlacementRun>,
+    pub spreads: Vec<AddressSpread>,
+    pub fragmentation: Distribution,
+    pub heap_size: Distribution,
+}
+
+/// Analyzes the program once for every seed and aggregates where its heap blocks were placed
+///
+/// Every run starts from an empty heap, so the seed alone decides the layout
+///
+/// # Arguments
+/// - `source`: The source code of the program
+/// - `seeds`: The seeds to run the program with
+/// - `options`: The options to analyze the program with, their seed is replaced by each of the seeds
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - [PlacementStatistics](crate::placement_statistics::PlacementStatistics): The layout of every run
+///      and the statistics across them
+///    - [Error](crate::error::Error): An error if there are no seeds or the program fails to analyze
+pub async fn compare_seeds(
+    source: &str,
+    seeds: &[u64],
+    options: AnalyzerOptions,
+) -> Result<PlacementStatistics> {
+    if seeds.is_empty() {
+        return Err("Comparing placements needs at least one seed".into());
+    }
+
+    let statements = parse_source(source, &options)?;
+    let options = source_options(source, options);
+    let mut runs = Vec::new();
+
+    for &seed in seeds {
+        let mut session = Session::new(source.to_owned(), IndexMap::new(), options.clone());
+        session.options.seed = Some(seed);
+        // blocks come in the order they were allocated, which is the same in every run
+        session.options.heap_order = HeapOrder::Allocation;
+
+        let result = Analyzer::new(session.options.clone())
+            .analyze_statements(statements.clone(), &mut session)
+            .await?;
+
+        let mut placements = IndexMap::new();
+        let mut allocated = IndexMap::new();
+
+        for (block, source) in result.heap.iter().zip(&result.cross_reference.heap) {
+            let (HeapBlockState::Allocated | HeapBlockState::Leaked, Some(source)) =
+                (&block.block_state, source)
+            else {
+                continue;
+            };
+
+            let count: &mut usize = allocated.entry(source.line).or_default();
+            *count += 1;
+
+            let label = match count {
+                1 => format!("line {}", source.line),
+                count => format!("line {}#{}", source.line, count),
+            };
+
+            placements.insert(label, block.pointer);
+        }
+
+        runs.push(PlacementRun {
+            seed,
+            heap_size: result.heap.iter().map(|block| block.size).sum(),
+            fragmentation: fragmentation(&result.free_list),
+            placements,
+        });
+    }
+
+    let mut positions: IndexMap<&str, Vec<usize>> = IndexMap::new();
+
+    for run in &runs {
+        for (label, pointer) in &run.placements {
+            positions.entry(label).or_default().push(*pointer);
+        }
+    }
+
+    let spreads = positions
+        .into_iter()
+        .map(|(label, mut pointers)| {
+            pointers.sort_unstable();
+            pointers.dedup();
+
+            AddressSpread {
+                block: label.to_string(),
+                min: pointers[0],
+                max: pointers[pointers.len() - 1],
+                distinct: pointers.len(),
+            }
+        })
+        .collect();
+
+    Ok(PlacementStatistics {
+        fragmentation: distribution(runs.iter().map(|run| run.fragmentation).collect()),
+        heap_size: distribution(runs.iter().map(|run| run.heap_size as f64).collect()),
+        spreads,
+        runs,
+    })
+}
+
+fn distribution(mut values: Vec<f64>) -> Distribution {
+    values.sort_by(f64::total_cmp);
+
+    let middle = values.len() / 2;
+    let median = match values.len() % 2 {
+        0 => (values[middle - 1] + values[middle]) / 2.0,
+        _ => values[middle],
+    };
+
+    Distribution {
+        min: values[0],
+        max: values[values.len() - 1],
+        mean: values.iter().sum::<f64>() / values.len() as f64,
+        median,
+    }
+}
@@ -0,0 +1,225 @@
+//! # Preprocess
+//! Turns a complete C++ program into the statements the parser understands
+//!
+//! Programs pasted into the editor usually come with includes, `using` directives, macros and a `main`
+//! function around the code being visualized. The passes here strip that scaffolding before parsing.
+//! Every frontend runs the same pipeline so a program is analyzed identically everywhere.
+//!
+//! Passes replace what they remove with spaces and keep every newline, so the line and column numbers
+//! in errors still point into the code the user wrote
+
+use indexmap::IndexMap;
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+lazy_static! {
+    static ref INCLUDE_REGEX: Regex = Regex::new(r#"(?m)^[ \t]*#[ \t]*include\b.*$"#).unwrap();
+    static ref USING_REGEX: Regex = Regex::new(r#"(?m)^[ \t]*using\s+namespace\s+[^;]*;"#).unwrap();
+    static ref DEFINE_REGEX: Regex =
+        Regex::new(r#"(?m)^[ \t]*#[ \t]*define[ \t]+([A-Za-z_]\w*)(?:[ \t]+(.*?))?[ \t]*$"#)
+            .unwrap();
+    static ref MAIN_REGEX: Regex = Regex::new(r#"\bint\s+main\s*\([^)]*\)\s*\{"#).unwrap();
+    static ref RETURN_REGEX: Regex = Regex::new(r#"\breturn\b[^;]*;\s*$"#).unwrap();
+}
+
+/// Represents a single step of the preprocessing pipeline
+///
+/// # Fields
+/// - `name`: The name of the pass
+/// - `run`: The function that transforms the source code
+pub struct Pass {
+    pub name: &'static str,
+    pub run: fn(&str) -> String,
+}
+
+/// The passes of the pipeline in the order they run
+///
+/// Line endings are normalized first so the other passes only have to deal with `\n`, and macros are
+/// expanded before `main` is extracted so a macro can't hide its braces
+pub const PASSES: [Pass; 4] = [
+    Pass {
+        name: "normalize line endings",
+        run: normalize_line_endings,
+    },
+    Pass {
+        name: "strip includes and usings",
+        run: strip_includes_and_usings,
+    },
+    Pass {
+        name: "expand macros",
+        run: expand_macros,
+    },
+    Pass {
+        name: "extract main body",
+        run: extract_main_body,
+    },
+];
+
+/// Runs every pass of the pipeline over the source code
+///
+/// # Arguments
+/// - `source`: The source code as written by the user
+///
+/// # Returns
+/// - `String`: The source code ready to be parsed
+pub fn preprocess(source: &str) -> String {
+    PASSES.iter().fold(source.to_string(), |source, pass| (pass.run)(&source))
+}
+
+/// Runs every pass of the pipeline over the source code, expanding macros defined outside of it too
+///
+/// The macros the source defines itself are expanded first, so they win over the outside ones like a
+/// `#define` after a `-D` flag does
+///
+/// # Arguments
+/// - `source`: The source code as written by the user
+/// - `defines`: The values of the macros defined outside the source, by name
+///
+/// # Returns
+/// - `String`: The source code ready to be parsed
+pub fn preprocess_with_defines(source: &str, defines: &IndexMap<String, String>) -> String {
+    PASSES.iter().fold(source.to_string(), |source, pass| {
+        let source = (pass.run)(&source);
+
+        match pass.name {
+            "expand macros" => expand_defines(&source, defines),
+            _ => source,
+        }
+    })
+}
+
+/// Gets the object-like macros a source defines, like the ones of a header it includes
+///
+/// # Arguments
+/// - `source`: The source code
+///
+/// # Returns
+/// - `IndexMap<String, String>`: The value of each macro by name, in the order they are defined
+pub fn object_macros(source: &str) -> IndexMap<String, String> {
+    DEFINE_REGEX
+        .captures_iter(&normalize_line_endings(source))
+        .map(|captures| {
+            let value = captures.get(2).map_or("", |value| value.as_str());
+            (captures[1].to_string(), value.to_string())
+        })
+        .collect()
+}
+
+/// Converts `\r\n` and lone `\r` line endings to `\n`
+pub fn normalize_line_endings(source: &str) -> String {
+    source.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+/// Blanks out `#include` lines and `using namespace` directives
+pub fn strip_includes_and_usings(source: &str) -> String {
+    let source = INCLUDE_REGEX.replace_all(source, |captures: &Captures| blank(&captures[0]));
+    let source = USING_REGEX.replace_all(&source, |captures: &Captures| blank(&captures[0]));
+
+    source.into_owned()
+}
+
+/// Expands object-like macros (`#define NAME value`) in the code after their definition
+///
+/// Function-like macros are left alone, the parser reports them like any other unsupported code
+pub fn expand_macros(source: &str) -> String {
+    let mut source = source.to_string();
+
+    while let Some(captures) = DEFINE_REGEX.captures(&source) {
+        let definition = captures.get(0).unwrap();
+        let name = captures[1].to_string();
+        let value = captures.get(2).map_or("", |value| value.as_str()).to_string();
+
+        let usage = Regex::new(&format!(r#"\b{}\b"#, regex::escape(&name))).unwrap();
+        let rest = usage.replace_all(&source[definition.end()..], regex::NoExpand(&value));
+
+        source = format!("{}{}{}", &source[..definition.start()], blank(definition.as_str()), rest);
+    }
+
+    source
+}
+
+/// Replaces every use of the given macros with their values
+fn expand_defines(source: &str, defines: &IndexMap<String, String>) -> String {
+    defines.iter().fold(source.to_string(), |source, (name, value)| {
+        let usage = Regex::new(&format!(r#"\b{}\b"#, regex::escape(name))).unwrap();
+        usage.replace_all(&source, regex::NoExpand(value)).into_owned()
+    })
+}
+
+/// Unwraps the body of `int main()` so its statements are analyzed as the program
+///
+/// A trailing `return` is dropped along with the function since it only hands the exit code back. Code
+/// without a `main` function is returned unchanged
+pub fn extract_main_body(source: &str) -> String {
+    let Some(header) = MAIN_REGEX.find(source) else {
+        return source.to_string();
+    };
+
+    // the header ends with the opening brace of the body
+    let Some(close) = matching_brace(source, header.end() - 1) else {
+        return source.to_string();
+    };
+
+    let body = &source[header.end()..close];
+    let body = match RETURN_REGEX.find(body) {
+        Some(exit) => format!("{}{}", &body[..exit.start()], blank(exit.as_str())),
+        None => body.to_string(),
+    };
+
+    format!(
+        "{}{}{} {}",
+        &source[..header.start()],
+        blank(header.as_str()),
+        body,
+        &source[close + 1..]
+    )
+}
+
+/// Finds the brace closing the one at `open`, skipping braces inside comments and literals
+fn matching_brace(source: &str, open: usize) -> Option<usize> {
+    let bytes = source.as_bytes();
+    let mut depth = 0;
+    let mut position = open;
+
+    while position < bytes.len() {
+        match bytes[position] {
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+
+                if depth == 0 {
+                    return Some(position);
+                }
+            }
+
+            b'/' if bytes.get(position + 1) == Some(&b'/') => {
+                while position < bytes.len() && bytes[position] != b'\n' {
+                    position += 1;
+                }
+            }
+
+            quote @ (b'"' | b'\'') => {
+                position += 1;
+
+                while position < bytes.len() && bytes[position] != quote {
+                    if bytes[position] == b'\\' {
+                        position += 1;
+                    }
+
+                    position += 1;
+                }
+            }
+
+            _ => {}
+        }
+
+        position += 1;
+    }
+
+    None
+}
+
+/// Replaces everything but newlines with spaces, keeping the positions of the surrounding code
+fn blank(text: &str) -> String {
+    text.chars().map(|c| if c == '\n' { '\n' } else { ' ' }).collect()
+}
@@ -0,0 +1,74 @@
+//! # RNG
+//! The single source of randomness in the core
+//!
+//! Every random decision goes through this generator so the same source, seed and options produce
+//! byte-identical results on every target. It is implemented here instead of using a generator from
+//! `rand`, whose algorithms and `usize` sampling are free to change between versions and pointer widths,
+//! which would make the desktop app (64-bit) and the web app (`wasm32`) disagree on layouts
+
+use serde::{Deserialize, Serialize};
+
+/// A SplitMix64 pseudo random number generator
+///
+/// # Fields
+/// - `state`: The current state of the generator
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Creates a generator that always produces the same sequence for the same seed
+    ///
+    /// # Arguments
+    /// - `seed`: The seed for the generator
+    ///
+    /// # Returns
+    /// - [Rng](crate::rng::Rng): A new generator
+    pub(crate) fn from_seed(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Creates a generator seeded from the OS, used when the user didn't ask for a seed
+    ///
+    /// # Returns
+    /// - [Rng](crate::rng::Rng): A new generator
+    pub(crate) fn from_entropy() -> Self {
+        Self::from_seed(rand::random())
+    }
+
+    /// Generates the next 64 random bits
+    ///
+    /// # Returns
+    /// - `u64`: The random bits
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// Generates a random number in `start..=end`
+    ///
+    /// The math is done in 64 bits regardless of the width of `usize`, so 32-bit and 64-bit targets draw
+    /// the same numbers
+    ///
+    /// # Arguments
+    /// - `start`: The lowest number that can be generated
+    /// - `end`: The highest number that can be generated
+    ///
+    /// # Returns
+    /// - `usize`: The random number
+    pub(crate) fn range_inclusive(&mut self, start: usize, end: usize) -> usize {
+        debug_assert!(start <= end);
+
+        let span = (end - start) as u64 + 1;
+
+        // Multiply-shift maps the random bits onto the span, the bias is negligible for heap sized spans
+        let offset = ((self.next_u64() as u128 * span as u128) >> 64) as u64;
+
+        start + offset as usize
+    }
+}
@@ -0,0 +1,153 @@
+//! # Session
+//! A session bundles the source code with everything needed to reproduce its visualization somewhere else,
+//! such as the remembered placement of heap blocks
+
+use async_trait::async_trait;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::analyzer::{AnalyzerOptions, AnalyzerState, Note};
+use crate::error::Result;
+
+/// Version of the serialized session format, bumped whenever the format changes
+pub const SESSION_SCHEMA_VERSION: u32 = 2;
+
+/// Upgrades a session from the version at its index to the next one, so a session saved by an older
+/// version of MV keeps opening. A change to the format bumps
+/// [SESSION_SCHEMA_VERSION](crate::session::SESSION_SCHEMA_VERSION) and adds an upgrade here
+const SESSION_UPGRADES: [fn(&mut Map<String, Value>); SESSION_SCHEMA_VERSION as usize] = [
+    // version 0 is a session written before the format had a version, which is otherwise the same
+    // as version 1
+    |_| {},
+    // version 1 is the same as version 2 without notes, which are left empty
+    |_| {},
+];
+
+/// Represents a serializable analysis session
+///
+/// # Fields
+/// - `schema_version`: The version of the format the session was saved with
+/// - `source`: The source code being visualized
+/// - `starting_pointers`: The remembered heap placement of each pointer, so blocks land in the same place
+///   every time the session is analyzed
+/// - `options`: The options the session was analyzed with, so it lays out the same everywhere it's opened
+/// - `notes`: The notes the user attached to the symbols and heap blocks of the diagram
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    pub schema_version: u32,
+    pub source: String,
+    #[serde(default)]
+    pub starting_pointers: IndexMap<String, usize>,
+    #[serde(default)]
+    pub options: AnalyzerOptions,
+    #[serde(default)]
+    pub notes: Vec<Note>,
+}
+
+impl Session {
+    /// Creates a new session for the given source code
+    ///
+    /// # Arguments
+    /// - `source`: The source code being visualized
+    /// - `starting_pointers`: The remembered heap placement of each pointer
+    /// - `options`: The options the source was analyzed with
+    ///
+    /// # Returns
+    /// - [Session](crate::session::Session): A new session using the current schema version, without
+    ///   notes
+    pub fn new(
+        source: String,
+        starting_pointers: IndexMap<String, usize>,
+        options: AnalyzerOptions,
+    ) -> Self {
+        Self {
+            schema_version: SESSION_SCHEMA_VERSION,
+            source,
+            starting_pointers,
+            options,
+            notes: Vec::new(),
+        }
+    }
+
+    /// Serializes the session to JSON
+    ///
+    /// # Returns
+    /// - [Result](crate::error::Result): A result containing either:
+    ///    - `String`: The serialized session
+    ///    - [Error](crate::error::Error): An error if serialization fails
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(|e| e.to_string().into())
+    }
+
+    /// Deserializes a session from JSON, upgrading it first if it was saved with an older version of
+    /// the format
+    ///
+    /// # Arguments
+    /// - `json`: The serialized session
+    ///
+    /// # Returns
+    /// - [Result](crate::error::Result): A result containing either:
+    ///    - [Session](crate::session::Session): The deserialized session
+    ///    - [Error](crate::error::Error): An error if the JSON is not a valid session, or was saved
+    ///      by a newer version of MV
+    pub fn from_json(json: &str) -> Result<Session> {
+        let session: Value =
+            serde_json::from_str(json).map_err(|e| format!("Invalid session: {}", e))?;
+
+        serde_json::from_value(upgrade(session)?)
+            .map_err(|e| format!("Invalid session: {}", e).into())
+    }
+}
+
+/// Upgrades a serialized session to the current version of the format
+///
+/// # Arguments
+/// - `session`: The session as it was saved, without a `schemaVersion` if it is older than the field
+///
+/// # Returns
+/// - [Result](crate::error::Result): A result containing either:
+///    - `Value`: The session in the current version of the format
+///    - [Error](crate::error::Error): An error if the session isn't an object, or its version is newer
+///      than the one this version of MV reads
+fn upgrade(session: Value) -> Result<Value> {
+    let Value::Object(mut session) = session else {
+        return Err("Invalid session: expected an object".into());
+    };
+
+    let version = match session.get("schemaVersion") {
+        None => 0,
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| format!("Invalid session: schema version {} isn't a number", version))?,
+    };
+
+    if version > SESSION_SCHEMA_VERSION {
+        return Err(format!(
+            "The session was saved with schema version {}, but this version of MV only opens sessions up to version {}. Update MV to open it",
+            version, SESSION_SCHEMA_VERSION
+        )
+        .into());
+    }
+
+    for upgrade in &SESSION_UPGRADES[version as usize..] {
+        upgrade(&mut session);
+    }
+
+    session.insert("schemaVersion".to_owned(), SESSION_SCHEMA_VERSION.into());
+
+    Ok(Value::Object(session))
+}
+
+#[async_trait]
+impl AnalyzerState for Session {
+    async fn get_starting_pointers(&mut self) -> IndexMap<String, usize> {
+        self.starting_pointers.clone()
+    }
+
+    async fn set_starting_pointers(&mut self, pointers: IndexMap<String, usize>) {
+        self.starting_pointers = pointers;
+    }
+}
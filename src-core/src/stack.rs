@@ -0,0 +1,40 @@
+//! # Stack
+//! How much of its own stack the core uses
+//!
+//! The parser and the analyzer recurse into every block, expression and call they meet, so the source
+//! decides how deep they go. Nesting is capped at [NESTING_LIMIT] levels and the work runs on a stack
+//! big enough for that many, so a deep program ends in an error instead of aborting the app
+
+/// How deep statements, expressions and calls can nest altogether before the parser or the analyzer
+/// gives up on a program
+pub(crate) const NESTING_LIMIT: usize = 512;
+
+/// The size of the stack the core runs on natively, enough for [NESTING_LIMIT] levels of the deepest
+/// recursion it does in a debug build, which uses several times the stack a release build does
+///
+/// The WASM module has no threads, its stack is sized when it is linked, see `.cargo/config.toml`
+const STACK_SIZE: usize = 64 * 1024 * 1024;
+
+/// Runs work on a stack of its own, so a program nested up to [NESTING_LIMIT] deep doesn't overflow
+/// the stack of the thread that asked for it, like a 2 MB tokio worker
+///
+/// # Arguments
+/// - `work`: The parsing or the analysis to run
+///
+/// # Returns
+/// - `T`: What the work returned
+pub(crate) fn on_deep_stack<T: Send>(work: impl FnOnce() -> T + Send) -> T {
+    if cfg!(target_arch = "wasm32") {
+        return work();
+    }
+
+    std::thread::scope(|scope| {
+        std::thread::Builder::new()
+            .name("mv-core".to_owned())
+            .stack_size(STACK_SIZE)
+            .spawn_scoped(scope, work)
+            .expect("failed to spawn a thread for the core")
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    })
+}
@@ -0,0 +1,255 @@
+//! # TypeScript
+//! Generates the TypeScript definitions of what the analyzer hands the frontends, so they use the
+//! shapes of the Rust types instead of copies kept by hand
+//!
+//! Only built with the `typescript` feature. `make types` writes the definitions to
+//! `src-web/types/analyzer.d.ts`, the definitions are read from the JSON schemas of the types
+
+use schemars::generate::SchemaSettings;
+use serde_json::{Map, Value};
+
+use crate::{
+    analyzer::{AnalysisError, AnalysisResult, AnalyzerOptions},
+    capabilities::Capabilities,
+    diagnostics::Diagnostic,
+    layout::StructLayout,
+};
+
+/// How long a union is written on a single line before each variant gets a line
+const MAX_LINE_WIDTH: usize = 80;
+
+/// Generates the TypeScript definitions of the responses of an analysis, the diagnostics, the
+/// capabilities, the struct layouts and the options the frontends analyze with
+///
+/// # Returns
+/// - `String`: The contents of a `.d.ts` file, an `export` for each type in alphabetical order
+pub fn type_definitions() -> String {
+    let mut responses = SchemaSettings::draft2020_12().for_serialize().into_generator();
+
+    responses.subschema_for::<AnalysisResult>();
+    responses.subschema_for::<AnalysisError>();
+    responses.subschema_for::<Diagnostic>();
+    responses.subschema_for::<Capabilities>();
+    responses.subschema_for::<StructLayout>();
+
+    // the options are read by the analyzer, so the fields it has defaults for can be left out
+    let mut options = SchemaSettings::draft2020_12().for_deserialize().into_generator();
+    options.subschema_for::<AnalyzerOptions>();
+
+    let mut definitions = responses.take_definitions(true);
+
+    for (name, schema) in options.take_definitions(true) {
+        definitions.entry(name).or_insert(schema);
+    }
+
+    let mut names: Vec<&String> = definitions.keys().collect();
+    names.sort();
+
+    let mut output = String::from(
+        "// Generated by `make types` from the types of the analyzer, don't edit it by hand\n",
+    );
+
+    for name in names {
+        output.push('\n');
+        output.push_str(&definition(name, &definitions[name]));
+    }
+
+    output
+}
+
+/// Writes a type as an interface when it is an object, or as a type alias
+fn definition(name: &str, schema: &Value) -> String {
+    let mut output = description(schema, "");
+
+    match schema.get("properties").and_then(Value::as_object) {
+        Some(properties) if union_variants(schema).is_none() => {
+            output.push_str(&format!("export interface {} {{\n", name));
+
+            for (property, property_schema) in properties {
+                output.push_str(&description(property_schema, "  "));
+                output.push_str(&format!(
+                    "  {}{}: {};\n",
+                    property_name(property),
+                    if is_required(schema, property) { "" } else { "?" },
+                    typescript_type(property_schema)
+                ));
+            }
+
+            output.push_str("}\n");
+        }
+
+        _ => match union_variants(schema) {
+            // long unions get a line for each variant
+            Some(variants) if variants.join(" | ").len() > MAX_LINE_WIDTH => {
+                output.push_str(&format!(
+                    "export type {} =\n  | {};\n",
+                    name,
+                    variants.join("\n  | ")
+                ));
+            }
+            _ => output.push_str(&format!("export type {} = {};\n", name, typescript_type(schema))),
+        },
+    }
+
+    output
+}
+
+/// Gets the TypeScript type a schema describes, written on a single line
+fn typescript_type(schema: &Value) -> String {
+    // the `true` schema, which any value matches
+    let Some(object) = schema.as_object() else {
+        return "unknown".to_owned();
+    };
+
+    if let Some(reference) = object.get("$ref").and_then(Value::as_str) {
+        return reference.rsplit('/').next().unwrap_or(reference).to_owned();
+    }
+
+    if let Some(value) = object.get("const") {
+        return literal(value);
+    }
+
+    if let Some(variants) = union_variants(schema) {
+        return variants.join(" | ");
+    }
+
+    if let Some(parts) = object.get("allOf").and_then(Value::as_array) {
+        return parts
+            .iter()
+            .map(|part| parenthesized(typescript_type(part)))
+            .collect::<Vec<_>>()
+            .join(" & ");
+    }
+
+    match object.get("type") {
+        Some(Value::String(json_type)) => primitive_type(json_type, object),
+        Some(Value::Array(json_types)) => json_types
+            .iter()
+            .filter_map(Value::as_str)
+            .map(|json_type| primitive_type(json_type, object))
+            .collect::<Vec<_>>()
+            .join(" | "),
+        _ => "unknown".to_owned(),
+    }
+}
+
+/// Gets the TypeScript type of one of the types of JSON, arrays and objects take their elements from
+/// the rest of the schema
+fn primitive_type(json_type: &str, schema: &Map<String, Value>) -> String {
+    match json_type {
+        "string" => "string".to_owned(),
+        "integer" | "number" => "number".to_owned(),
+        "boolean" => "boolean".to_owned(),
+        "null" => "null".to_owned(),
+
+        "array" => match (schema.get("prefixItems"), schema.get("items")) {
+            // tuples
+            (Some(Value::Array(items)), _) => {
+                format!("[{}]", items.iter().map(typescript_type).collect::<Vec<_>>().join(", "))
+            }
+            (_, Some(items)) => format!("{}[]", parenthesized(typescript_type(items))),
+            _ => "unknown[]".to_owned(),
+        },
+
+        "object" => match (schema.get("properties"), map_values(schema)) {
+            (Some(Value::Object(properties)), _) => {
+                let properties: Vec<String> = properties
+                    .iter()
+                    .map(|(property, property_schema)| {
+                        format!(
+                            "{}{}: {}",
+                            property_name(property),
+                            if required(schema).contains(&property.as_str()) { "" } else { "?" },
+                            typescript_type(property_schema)
+                        )
+                    })
+                    .collect();
+
+                format!("{{ {} }}", properties.join("; "))
+            }
+            (_, Some(values)) => format!("Record<string, {}>", typescript_type(values)),
+            _ => "Record<string, unknown>".to_owned(),
+        },
+
+        _ => "unknown".to_owned(),
+    }
+}
+
+/// Gets the schema of the values of a map, whose keys are either any string or, for maps keyed by
+/// numbers, limited by a pattern
+fn map_values(schema: &Map<String, Value>) -> Option<&Value> {
+    match schema.get("additionalProperties") {
+        Some(values @ Value::Object(_)) => Some(values),
+        _ => schema
+            .get("patternProperties")
+            .and_then(Value::as_object)
+            .and_then(|patterns| patterns.values().next()),
+    }
+}
+
+/// Gets the variants of a schema that is a choice between values or other schemas, like an enum
+fn union_variants(schema: &Value) -> Option<Vec<String>> {
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        return Some(values.iter().map(literal).collect());
+    }
+
+    schema.get("oneOf").or_else(|| schema.get("anyOf")).and_then(Value::as_array).map(|variants| {
+        variants.iter().map(|variant| parenthesized(typescript_type(variant))).collect()
+    })
+}
+
+fn is_required(schema: &Value, property: &str) -> bool {
+    schema.as_object().is_some_and(|schema| required(schema).contains(&property))
+}
+
+fn required(schema: &Map<String, Value>) -> Vec<&str> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect()
+}
+
+/// Writes a value a type is limited to, like `'heap'`
+fn literal(value: &Value) -> String {
+    match value {
+        Value::String(value) => format!("'{}'", value.replace('\'', "\\'")),
+        value => value.to_string(),
+    }
+}
+
+/// Wraps a union or an intersection in parentheses, so it can be the element of an array or part of
+/// another union
+fn parenthesized(typescript_type: String) -> String {
+    match typescript_type.contains(" | ") || typescript_type.contains(" & ") {
+        true if !typescript_type.starts_with('{') => format!("({})", typescript_type),
+        _ => typescript_type,
+    }
+}
+
+/// Quotes the name of a property unless it is a valid identifier
+fn property_name(name: &str) -> String {
+    let identifier = name.chars().enumerate().all(|(index, c)| {
+        c == '_' || c == '$' || c.is_ascii_alphabetic() || (index > 0 && c.is_ascii_digit())
+    });
+
+    match identifier && !name.is_empty() {
+        true => name.to_owned(),
+        false => format!("'{}'", name),
+    }
+}
+
+/// Writes the first paragraph of the doc comment of a type or field as a comment
+fn description(schema: &Value, indent: &str) -> String {
+    let Some(description) = schema.get("description").and_then(Value::as_str) else {
+        return String::new();
+    };
+
+    description
+        .lines()
+        .take_while(|line| !line.trim().is_empty())
+        .map(|line| format!("{}// {}\n", indent, line.trim()))
+        .collect()
+}
@@ -0,0 +1,125 @@
+//! Blocks allocated with `malloc` and `calloc` can be sized in bytes, as long as the bytes hold a whole
+//! number of the values the block is for
+
+mod common;
+
+use common::{analyze_err, analyze_ok};
+
+/// Gets the sizes of the blocks still allocated at the end of a program, smallest first
+fn allocated_sizes(source: &str) -> Vec<usize> {
+    let mut sizes: Vec<usize> = analyze_ok(source)
+        .heap
+        .iter()
+        .map(|block| serde_json::to_value(block).unwrap())
+        .filter(|block| block["block_state"] == "Allocated")
+        .map(|block| block["size"].as_u64().unwrap() as usize)
+        .collect();
+
+    sizes.sort();
+    sizes
+}
+
+#[test]
+fn byte_counts_allocate_whole_values() {
+    let source = "int* p = (int*)malloc(8); int* q = malloc(12); q[2] = 1; int* r; r = malloc(4);";
+
+    assert_eq!(allocated_sizes(source), [4, 8, 12]);
+    assert_eq!(allocated_sizes("double* d = (double*)calloc(2, 8); d[1] = 1.5;"), [16]);
+}
+
+#[test]
+fn byte_count_that_splits_a_value_fails() {
+    let message = analyze_err("int* p = (int*)malloc(6);");
+    assert!(
+        message
+            .contains("The 6 bytes passed to `malloc` aren't a multiple of 4, the size of `int`"),
+        "{message}"
+    );
+
+    let message = analyze_err("int* p; p = malloc(2);");
+    assert!(
+        message
+            .contains("The 2 bytes passed to `malloc` aren't a multiple of 4, the size of `int`"),
+        "{message}"
+    );
+}
+
+#[test]
+fn byte_counts_resize_to_whole_values() {
+    let source = "int* p = (int*)malloc(8); p = (int*)realloc(p, 12); p[2] = 1;";
+    assert_eq!(allocated_sizes(source), [12]);
+
+    let source = "int* p = (int*)malloc(8); p = realloc(p, 16);";
+    assert_eq!(allocated_sizes(source), [16]);
+
+    let message = analyze_err("int* p = (int*)malloc(8); p = (int*)realloc(p, 6);");
+    assert!(
+        message
+            .contains("The 6 bytes passed to `realloc` aren't a multiple of 4, the size of `int`"),
+        "{message}"
+    );
+}
+
+#[test]
+fn sizes_too_large_to_count_fail() {
+    let message = analyze_err("int* p = (int*)malloc(4294967296 * 4294967296);");
+    assert!(message.contains("The size of the allocation is too large"), "{message}");
+
+    let message = analyze_err("int* p = (int*)calloc(4294967296, 4294967296);");
+    assert!(message.contains("The size of the allocation is too large"), "{message}");
+}
+
+#[test]
+fn byte_counts_allocate_every_scalar_type() {
+    let source = "long* l = (long*)malloc(8); short* s = malloc(6); unsigned int* u = (unsigned int*)malloc(4);";
+    assert_eq!(allocated_sizes(source), [4, 6, 8]);
+
+    let message = analyze_err("short* s = (short*)malloc(3);");
+    assert!(
+        message
+            .contains("The 3 bytes passed to `malloc` aren't a multiple of 2, the size of `short`"),
+        "{message}"
+    );
+}
+
+#[test]
+fn blocks_bigger_than_the_heap_fail() {
+    let message = analyze_err("int* p = (int*)malloc(4000000000);");
+    assert!(message.contains("the heap holds at most"), "{message}");
+}
+
+#[test]
+fn freed_blocks_merge_with_their_free_neighbours() {
+    let result = serde_json::to_value(analyze_ok(
+        "char* a = (char*)malloc(8); char* b = (char*)malloc(8); char* c = (char*)malloc(8);
+        free(b); free(a); free(c);",
+    ))
+    .unwrap();
+
+    let ranges: Vec<(u64, u64)> = result["free_list"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|range| (range["start"].as_u64().unwrap(), range["end"].as_u64().unwrap()))
+        .collect();
+
+    // in the order of their addresses, with a used byte between each range and the next
+    assert!(ranges.windows(2).all(|pair| pair[0].1 + 1 < pair[1].0), "{ranges:?}");
+    assert_eq!(ranges, [(0, result["heap_capacity"].as_u64().unwrap() - 1)]);
+}
+
+#[test]
+fn block_at_the_end_of_the_heap_grows_in_place() {
+    let result = serde_json::to_value(analyze_ok(
+        "int* p = (int*)malloc(12); p = (int*)realloc(p, 20); p[4] = 1;",
+    ))
+    .unwrap();
+
+    let heap = result["heap"].as_array().unwrap();
+    let block = heap.iter().find(|block| block["current_pointer_identifier"] == "p").unwrap();
+
+    // the block kept its place and took the bytes the heap grew by
+    assert!(result["heap_capacity"].as_u64().unwrap() >= 24, "{heap:?}");
+    assert_eq!((&block["pointer"], &block["size"]), (&0.into(), &20.into()));
+    assert!(heap.iter().all(|block| block["moved"].is_null()), "{heap:?}");
+}
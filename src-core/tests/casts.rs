@@ -0,0 +1,27 @@
+//! Pointers can be cast between types directly, and a cast to an address the new type isn't aligned to
+//! is warned about
+
+mod common;
+
+use common::analyze_ok;
+
+#[test]
+fn cast_to_a_misaligned_address_warns() {
+    let result = analyze_ok("char* c = (char*)malloc(8 * sizeof(char)); c++; int* p = (int*)c;");
+
+    let messages: Vec<_> = result.warnings.iter().map(|warning| &warning.message).collect();
+
+    assert_eq!(messages.len(), 1, "{messages:?}");
+    assert!(
+        messages[0].ends_with("which isn't a multiple of 4, the alignment of `int`"),
+        "{}",
+        messages[0]
+    );
+}
+
+#[test]
+fn cast_to_an_aligned_address_is_silent() {
+    let result = analyze_ok("char* c = (char*)malloc(8 * sizeof(char)); int* p = (int*)c; *p = 1;");
+
+    assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+}
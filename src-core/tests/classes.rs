@@ -0,0 +1,127 @@
+//! Methods write the fields of their object through `this`, even the fields a parameter hides
+
+mod common;
+
+use serde_json::Value;
+
+use common::{analyze_err, analyze_ok};
+
+/// Gets the value of a field of the object `name` on the stack
+fn field_value(source: &str, name: &str, field: &str) -> Value {
+    let result = serde_json::to_value(analyze_ok(source)).unwrap();
+
+    result["stack"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|symbol| symbol["Object"]["name"] == name)
+        .and_then(|object| {
+            object["Object"]["fields"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|other| other["name"] == field)
+                .map(|other| other["value"].clone())
+        })
+        .unwrap_or_else(|| panic!("expected a field `{name}.{field}`"))
+}
+
+#[test]
+fn this_writes_the_field_of_the_object() {
+    let source =
+        "class Counter { public: int count; void set(int value) { this->count = value; } };
+        Counter c; c.set(4);";
+
+    assert_eq!(field_value(source, "c", "count"), "4");
+}
+
+#[test]
+fn this_writes_the_field_a_parameter_hides() {
+    let source =
+        "class Point { public: int x; int y; Point(int x, int y) { this->x = x; this->y = y; }
+        void shift(int dx) { this->x = x + dx; } };
+        Point p(1, 2); p.shift(3);";
+
+    assert_eq!(field_value(source, "p", "x"), "4");
+    assert_eq!(field_value(source, "p", "y"), "2");
+}
+
+#[test]
+fn this_rejects_a_missing_field() {
+    let error = analyze_err(
+        "class Point { public: int x; void set(int x) { this->z = x; } }; Point p; p.set(4);",
+    );
+
+    assert!(error.contains("Field `z` not found in class `Point`!"), "{error}");
+}
+
+#[test]
+fn pointers_to_objects_have_the_type_of_their_class() {
+    let source = "class Point { public: int x; }; Point* p = new Point(); p->x = 3;";
+    let result = serde_json::to_value(analyze_ok(source)).unwrap();
+
+    let pointer = result["stack"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|symbol| symbol["Pointer"]["name"] == "p")
+        .expect("expected a pointer `p`");
+
+    assert_eq!(pointer["Pointer"]["ptype"], serde_json::json!({ "Class": "Point" }));
+}
+
+#[test]
+fn pointers_to_objects_reach_fields_through_the_arrow() {
+    let error = analyze_err("class Point { public: int x; }; Point* p = new Point(); *p = 3;");
+
+    assert!(error.contains("Cannot assign through `Point*` `p`"), "{error}");
+}
+
+#[test]
+fn assigning_an_object_copies_it_member_by_member() {
+    let source =
+        "class Point { public: int x; double y; }; Point s; s.x = 1; s.y = 2.5; Point t; t = s;";
+    let result = serde_json::to_value(analyze_ok(source)).unwrap();
+
+    assert_eq!(field_value(source, "t", "x"), "1");
+    assert_eq!(field_value(source, "t", "y"), "2.5");
+
+    let copies: Vec<_> = result["cross_reference"]["copies"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|copy| (copy["from"].clone(), copy["to"].clone(), copy["bytes"].clone()))
+        .collect();
+
+    assert_eq!(
+        copies,
+        [
+            ("s.x".into(), "t.x".into(), 4.into()),
+            ("s.y".into(), "t.y".into(), 8.into())
+        ]
+    );
+}
+
+#[test]
+fn objects_are_only_assigned_objects_of_their_class() {
+    let error = analyze_err(
+        "class Point { public: int x; }; class Size { public: int x; }; Point p; Size s; p = s;",
+    );
+
+    assert!(
+        error.contains("Cannot assign `s` of class `Size` to an object of class `Point`"),
+        "{error}"
+    );
+}
+
+#[test]
+fn members_of_structs_are_public_by_default() {
+    let source = "struct Point { int x; int y; void shift(int dx) { x = x + dx; } };
+        Point p; p.x = 1; p.y = 2; p.shift(3);";
+
+    assert_eq!(field_value(source, "p", "x"), "4");
+    assert_eq!(field_value(source, "p", "y"), "2");
+
+    let message = analyze_err("struct Secret { private: int code; }; Secret s; s.code = 1;");
+    assert!(message.contains("Field `code` of class `Secret` is private"), "{message}");
+}
@@ -0,0 +1,52 @@
+//! Helpers the integration tests share
+
+#![allow(dead_code)]
+
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+use indexmap::IndexMap;
+
+use mv_core::analyzer::{AnalysisResult, AnalyzerOptions};
+use mv_core::error::Result;
+use mv_core::pipeline::analyze_source;
+use mv_core::session::Session;
+
+/// Runs a future to completion on the current thread, the analyzer never waits on anything so polling
+/// it until it is ready is enough
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+/// Parses and analyzes a program the way the frontends do, in a fresh session
+pub fn analyze(source: &str, options: AnalyzerOptions) -> Result<AnalysisResult> {
+    let mut session = Session::new(source.to_owned(), IndexMap::new(), options.clone());
+
+    block_on(analyze_source(source, &mut session, options))
+}
+
+/// Analyzes a program with the default options and a fixed seed, failing the test if it doesn't analyze
+pub fn analyze_ok(source: &str) -> AnalysisResult {
+    let options = AnalyzerOptions {
+        seed: Some(0),
+        ..Default::default()
+    };
+
+    analyze(source, options).unwrap_or_else(|error| panic!("{source}\n{error}"))
+}
+
+/// Analyzes a program with the default options, returning the message it fails with
+pub fn analyze_err(source: &str) -> String {
+    match analyze(source, AnalyzerOptions::default()) {
+        Ok(_) => panic!("expected `{source}` to fail"),
+        Err(error) => error.to_string(),
+    }
+}
@@ -0,0 +1,88 @@
+//! Compound assignments like `a[1] += 4` read what they write, whether it is a variable, an element, a
+//! member or what a pointer points to
+
+mod common;
+
+use serde_json::Value;
+
+use common::{analyze_err, analyze_ok};
+
+/// Gets the symbol `name` on the stack, under the kind of symbol it is
+fn stack_symbol(result: &Value, name: &str) -> Value {
+    result["stack"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|symbol| symbol.as_object()?.values().next())
+        .find(|symbol| symbol["name"] == name)
+        .cloned()
+        .unwrap_or_else(|| panic!("expected a symbol `{name}`"))
+}
+
+fn analyze_json(source: &str) -> Value {
+    serde_json::to_value(analyze_ok(source)).unwrap()
+}
+
+#[test]
+fn elements_of_arrays_are_read_and_written() {
+    let result = analyze_json("int a[3] = {1, 2, 3}; int i = 2; a[1] += 4; a[i] *= i + 1;");
+
+    assert_eq!(stack_symbol(&result, "a")["elements"], serde_json::json!(["1", "6", "9"]));
+}
+
+#[test]
+fn elements_of_heap_blocks_are_read_and_written() {
+    let result = analyze_json("int* p = new int[2]; p[1] = 3; p[1] *= 5;");
+
+    let members = result["heap"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|block| block["current_pointer_identifier"] == "p")
+        .map(|block| block["members"].clone())
+        .unwrap();
+
+    assert_eq!(members[0]["name"], "[1]");
+    assert_eq!(members[0]["value"], "15");
+}
+
+#[test]
+fn what_a_pointer_points_to_is_read_and_written() {
+    let result = analyze_json("int x = 1; int* p = &x; *p += 1; *p -= 4;");
+
+    assert_eq!(stack_symbol(&result, "x")["value"], "-2");
+}
+
+#[test]
+fn members_are_read_and_written() {
+    let result = analyze_json(
+        "union Value { int i; float f; }; Value v; v.i = 7; v.i /= 2;
+        class Point { public: int x; }; Point o; o.x = 5; o.x -= 2;",
+    );
+
+    assert_eq!(stack_symbol(&result, "v")["members"][0]["value"], "3");
+    assert_eq!(stack_symbol(&result, "o")["fields"][0]["value"], "3");
+}
+
+#[test]
+fn fields_are_read_and_written_through_pointers() {
+    let result = analyze_json(
+        "class Point { public: int x; void shift(int dx) { x = 1; this->x += dx; } };
+        Point* p = new Point(); p->x = 3; p->x *= 2; Point o; o.shift(4);",
+    );
+
+    assert_eq!(stack_symbol(&result, "p")["value"]["Object"]["fields"][0]["value"], "6");
+    assert_eq!(stack_symbol(&result, "o")["fields"][0]["value"], "5");
+}
+
+#[test]
+fn compound_assignments_check_what_they_read() {
+    let out_of_bounds = analyze_err("int a[2] = {1, 2}; a[2] += 1;");
+    let not_initialized = analyze_err("int* p = new int[2]; p[0] += 1;");
+    let dangling =
+        analyze_err("class Point { public: int x; }; Point* p = new Point(); delete p; p->x += 1;");
+
+    assert!(out_of_bounds.contains("Index 2 is out of bounds"), "{out_of_bounds}");
+    assert!(not_initialized.contains("`p[0]` not initialized!"), "{not_initialized}");
+    assert!(dangling.contains("Cannot dereference dangling pointer `p`"), "{dangling}");
+}
@@ -0,0 +1,33 @@
+//! The same source, seed and options lay the heap out the same way on every target
+//!
+//! Where the blocks land with a few seeds is pinned in `conformance/layout.rs`, which the web app's
+//! `wasm32` build is checked against too by `src-wasm/tests/conformance.rs`, run with
+//! `wasm-pack test --node src-wasm`. After a change that is meant to move the layout, update the pinned
+//! placements there
+
+mod common;
+#[path = "conformance/layout.rs"]
+mod layout;
+
+use common::block_on;
+use layout::{analyze, check_layout, PLACEMENTS};
+
+#[test]
+fn blocks_land_where_they_are_pinned() {
+    for (seed, _) in PLACEMENTS {
+        check_layout(seed, &block_on(analyze(seed)));
+    }
+}
+
+#[test]
+fn analyses_with_the_same_seed_are_identical() {
+    assert_eq!(block_on(analyze(7)), block_on(analyze(7)));
+}
+
+#[test]
+fn seeds_place_blocks_differently() {
+    let heaps: Vec<_> =
+        PLACEMENTS.iter().map(|(seed, _)| block_on(analyze(*seed))["heap"].clone()).collect();
+
+    assert!(heaps.windows(2).all(|pair| pair[0] != pair[1]));
+}
@@ -0,0 +1,87 @@
+//! The layout the conformance tests pin, shared by the native tests in `src-core/tests/conformance.rs`
+//! and the `wasm32` ones in `src-wasm/tests/conformance.rs`
+
+use indexmap::IndexMap;
+use serde_json::Value;
+
+use mv_core::analyzer::{AnalyzerOptions, LayoutHint};
+use mv_core::pipeline::analyze_source;
+use mv_core::session::Session;
+
+const SOURCE: &str = "
+    int x = 5;
+    int* p = new int;
+    *p = 10;
+    int* q = (int*)malloc(3 * sizeof(int));
+    char* s = new char[6];
+    delete p;
+    double* d = new double;
+    free(q);
+";
+
+/// Where the block of each pointer lands with each seed
+pub const PLACEMENTS: [(u64, [(&str, u64); 4]); 3] = [
+    (0, [("p", 232), ("q", 104), ("s", 8), ("d", 0)]),
+    (7, [("p", 104), ("q", 8), ("s", 0), ("d", 248)]),
+    (42, [("p", 192), ("q", 32), ("s", 8), ("d", 0)]),
+];
+
+/// Analyzes the source with a seed, returning the result as the frontends get it
+pub async fn analyze(seed: u64) -> Value {
+    // a heap as big as the panel of the app, which leaves the blocks room to land in different places
+    let options = AnalyzerOptions {
+        seed: Some(seed),
+        layout: Some(LayoutHint::Cells { count: 256 }),
+        ..Default::default()
+    };
+
+    let mut session = Session::new(SOURCE.to_owned(), IndexMap::new(), options.clone());
+    let result = analyze_source(SOURCE, &mut session, options).await.unwrap();
+
+    serde_json::to_value(result).unwrap()
+}
+
+/// Checks that the blocks of a result land where they are pinned for its seed, and that the heap around
+/// them is laid out whole
+pub fn check_layout(seed: u64, result: &Value) {
+    let (_, placements) = PLACEMENTS.iter().find(|(pinned, _)| *pinned == seed).unwrap();
+    let heap = result["heap"].as_array().unwrap();
+
+    // the blocks tile the heap, each starting where the one before it ends
+    let mut end = 0;
+
+    for block in heap {
+        assert_eq!(block["pointer"], end, "seed {seed}: the heap has a gap or an overlap at {end}");
+        end += block["size"].as_u64().unwrap();
+    }
+
+    assert_eq!(result["heap_capacity"], end, "seed {seed}: the blocks don't fill the heap");
+
+    // the address of the block a pointer points to, with the state and the size of the block
+    let block_of = |name: &str| {
+        let address = result["stack"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|symbol| symbol["Pointer"]["name"] == name)
+            .and_then(|pointer| pointer["Pointer"]["heap_pointer"].as_u64())
+            .unwrap_or_else(|| panic!("seed {seed}: expected `{name}` to point into the heap"));
+
+        let block = heap.iter().find(|block| block["pointer"] == address).unwrap();
+
+        (address, block["block_state"].as_str().unwrap(), block["size"].as_u64().unwrap())
+    };
+
+    for (name, pinned) in placements {
+        assert_eq!(block_of(name).0, *pinned, "seed {seed}: the block of `{name}` moved");
+    }
+
+    let (d, d_state, d_size) = block_of("d");
+    let (_, s_state, s_size) = block_of("s");
+
+    assert_eq!((d_state, d_size), ("Allocated", 8), "seed {seed}: the block of `d`");
+    assert_eq!(d % 8, 0, "seed {seed}: the block of `d` isn't aligned for a `double`");
+    assert_eq!((s_state, s_size), ("Allocated", 6), "seed {seed}: the block of `s`");
+    assert_eq!(block_of("p").1, "Free", "seed {seed}: the block of `p`");
+    assert_eq!(block_of("q").1, "Free", "seed {seed}: the block of `q`");
+}
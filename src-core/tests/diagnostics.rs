@@ -0,0 +1,73 @@
+//! Warnings are reported once however many times they are raised, and capped at the options' limit
+
+mod common;
+
+use mv_core::analyzer::AnalyzerOptions;
+use mv_core::diagnostics::Severity;
+
+use common::{analyze, analyze_ok};
+
+const OVERLAPPING_COPIES: &str = "
+    int* p = new int[4];
+    int* q = new int[4];
+    int* r = new int[4];
+    for (int i = 0; i < 3; i++) {
+        memcpy(p, p, 2 * sizeof(int));
+    }
+    memcpy(q, q, 2 * sizeof(int));
+    memcpy(r, r, 2 * sizeof(int));
+";
+
+fn warnings(diagnostic_limit: Option<usize>) -> Vec<(String, Option<usize>)> {
+    let options = AnalyzerOptions {
+        seed: Some(0),
+        diagnostic_limit,
+        ..Default::default()
+    };
+
+    let result = analyze(OVERLAPPING_COPIES, options).unwrap();
+
+    assert!(result.warnings.iter().all(|warning| warning.severity == Severity::Warning));
+
+    result.warnings.into_iter().map(|warning| (warning.message, warning.line_number)).collect()
+}
+
+#[test]
+fn looped_warning_is_reported_once() {
+    let warnings = warnings(None);
+
+    let lines: Vec<_> = warnings.iter().map(|(_, line)| *line).collect();
+    assert_eq!(lines, [Some(6), Some(8), Some(9)]);
+    assert!(warnings[0].0.starts_with("`memcpy` of 8 bytes from `p` to `p`"));
+}
+
+#[test]
+fn warnings_past_the_limit_are_summarized() {
+    let warnings = warnings(Some(2));
+
+    assert_eq!(warnings.len(), 3);
+    assert!(warnings[0].0.contains("from `p` to `p`"));
+    assert!(warnings[1].0.contains("from `q` to `q`"));
+    assert_eq!(warnings[2], ("1 more warning".to_owned(), None));
+}
+
+#[test]
+fn double_literals_narrowed_to_float_are_warned_about() {
+    let result =
+        analyze_ok("float f = 3.5; float g = 0.1L; float h = 0.1f; double d = 0.1; f = 2.25;");
+
+    let warnings: Vec<_> = result
+        .warnings
+        .iter()
+        .map(|warning| (warning.message.as_str(), warning.line_number))
+        .collect();
+
+    assert_eq!(warnings.len(), 3, "{warnings:?}");
+    assert!(warnings[0]
+        .0
+        .starts_with("The `double` literal `3.5` is narrowed to a `float` in `f`"));
+    assert!(warnings[1]
+        .0
+        .contains("`0.1` is narrowed to a `float` in `g`, which holds 0.10000000149011612"));
+    assert!(warnings[2].0.contains("`2.25` is narrowed to a `float` in `f`"));
+}
@@ -0,0 +1,31 @@
+//! Embed snippets are the same every time the same program is exported
+
+use indexmap::IndexMap;
+
+use mv_core::analyzer::AnalyzerOptions;
+use mv_core::export::{render_embed_snippet, DEFAULT_WASM_MODULE_URL};
+use mv_core::session::Session;
+
+fn snippet(source: &str) -> String {
+    let session = Session::new(source.to_owned(), IndexMap::new(), AnalyzerOptions::default());
+
+    render_embed_snippet(&session, DEFAULT_WASM_MODULE_URL).unwrap()
+}
+
+#[test]
+fn element_ids_are_a_fixed_hash_of_the_source() {
+    // the 64-bit FNV-1a hash of the source, which doesn't change between builds or platforms
+    assert!(snippet("int x = 5;").contains(r#"id="mv-embed-e5f75507e0cadb97""#));
+    assert_eq!(snippet("int x = 5;"), snippet("int x = 5;"));
+    assert_ne!(snippet("int x = 5;"), snippet("int x = 6;"));
+}
+
+#[test]
+fn snippets_find_their_element_next_to_their_script() {
+    let snippet = snippet("int x = 5;");
+
+    // the same program embedded twice has the same id, so the script can't look its element up by id
+    assert!(snippet.contains("})(document.currentScript.previousElementSibling);"), "{snippet}");
+    assert!(!snippet.contains("getElementById"), "{snippet}");
+    assert!(!snippet.contains(r#"type="module""#), "{snippet}");
+}
@@ -0,0 +1,41 @@
+//! Integer literals hold any value of the widest integer types, and the declared type decides whether it
+//! fits
+
+mod common;
+
+use serde_json::Value;
+
+use common::{analyze_err, analyze_ok};
+
+/// Gets the value of the variable `name` on the stack
+fn value_of(source: &str, name: &str) -> Value {
+    let result = serde_json::to_value(analyze_ok(source)).unwrap();
+
+    result["stack"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|symbol| symbol["Variable"]["name"] == name)
+        .map(|symbol| symbol["Variable"]["value"].clone())
+        .unwrap_or_else(|| panic!("expected a variable `{name}`"))
+}
+
+#[test]
+fn literals_reach_the_ends_of_the_widest_types() {
+    let source = "unsigned long long u = 18446744073709551615; long long l = -9223372036854775808;";
+
+    assert_eq!(value_of(source, "u"), "18446744073709551615");
+    assert_eq!(value_of(source, "l"), "-9223372036854775808");
+}
+
+#[test]
+fn literals_past_the_declared_type_fail() {
+    let unsigned = analyze_err("unsigned long long u = 18446744073709551616;");
+    let signed = analyze_err("long long l = 9223372036854775808;");
+
+    assert!(
+        unsigned.contains("`18446744073709551616` doesn't fit in `unsigned long long`"),
+        "{unsigned}"
+    );
+    assert!(signed.contains("`9223372036854775808` doesn't fit in `long long`"), "{signed}");
+}
@@ -0,0 +1,47 @@
+//! Regions mapped with `mmap` take whole pages, and `munmap` gives back a whole region
+
+mod common;
+
+use serde_json::Value;
+
+use common::{analyze_err, analyze_ok};
+
+fn analyze_json(source: &str) -> Value {
+    serde_json::to_value(analyze_ok(source)).unwrap()
+}
+
+#[test]
+fn mappings_take_whole_pages() {
+    let result = analyze_json("char* m = (char*)mmap(5000);");
+
+    assert_eq!(result["mappings"][0]["size"], 8192);
+    assert_eq!(result["mappings"][0]["requested"], 5000);
+}
+
+#[test]
+fn unmapping_the_length_of_a_mapping_unmaps_its_pages() {
+    let result =
+        analyze_json("char* m = (char*)mmap(5000); char* n = (char*)mmap(10); munmap(m, 5000);");
+    let mappings = result["mappings"].as_array().unwrap();
+
+    assert_eq!(mappings.len(), 1, "{mappings:?}");
+    assert_eq!(mappings[0]["owner"], "n");
+
+    // any length that rounds up to the pages of the region unmaps it
+    let result = analyze_json("int* m = (int*)mmap(100 * sizeof(int)); munmap(m, 4096);");
+    assert_eq!(result["mappings"], serde_json::json!([]));
+}
+
+#[test]
+fn unmapping_part_of_a_mapping_fails() {
+    let partial = analyze_err("char* m = (char*)mmap(5000); munmap(m, 4096);");
+    let heap = analyze_err("int* p = new int; munmap(p, 4);");
+    let twice = analyze_err("char* m = (char*)mmap(10); munmap(m, 10); munmap(m, 10);");
+
+    assert!(
+        partial.contains("Cannot unmap 4096 bytes of `m`, its region is 8192 bytes"),
+        "{partial}"
+    );
+    assert!(heap.contains("Cannot unmap `p`, it wasn't mapped with `mmap`"), "{heap}");
+    assert!(twice.contains("Cannot unmap `m`, it wasn't mapped with `mmap`"), "{twice}");
+}
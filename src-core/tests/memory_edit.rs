@@ -0,0 +1,52 @@
+//! Freeing a block from outside of the source keeps the free list in step with the heap
+
+mod common;
+
+use mv_core::analyzer::{free_block, AnalyzerOptions};
+use serde_json::Value;
+
+use common::analyze_ok;
+
+/// Gets the free list of a result as `(start, size)` pairs
+fn free_list(result: &Value) -> Vec<(u64, u64)> {
+    result["free_list"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|range| (range["start"].as_u64().unwrap(), range["size"].as_u64().unwrap()))
+        .collect()
+}
+
+#[test]
+fn freed_block_is_merged_into_the_free_list() {
+    let mut result = analyze_ok("int* p = new int; int* q = new int;");
+    let heap = serde_json::to_value(&result).unwrap();
+
+    let block = heap["heap"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|block| block["current_pointer_identifier"] == "p")
+        .map(|block| block["pointer"].as_u64().unwrap())
+        .unwrap();
+
+    let free_before: u64 = free_list(&heap).iter().map(|(_, size)| size).sum();
+
+    free_block(&mut result, block as usize, &AnalyzerOptions::default()).unwrap();
+
+    let ranges = free_list(&serde_json::to_value(&result).unwrap());
+    let free_after: u64 = ranges.iter().map(|(_, size)| size).sum();
+
+    assert!(free_after >= free_before + 4, "{ranges:?}");
+    assert!(ranges.iter().any(|&(start, size)| (start..start + size).contains(&block)));
+    assert!(ranges.windows(2).all(|pair| pair[0].0 + pair[0].1 < pair[1].0), "{ranges:?}");
+}
+
+#[test]
+fn missing_block_is_reported_at_its_address() {
+    let mut result = analyze_ok("int* p = new int;");
+
+    let error = free_block(&mut result, 3, &AnalyzerOptions::default()).unwrap_err();
+
+    assert_eq!(error.to_string(), "Error: Allocated heap block at 0x00400003 not found!");
+}
@@ -0,0 +1,68 @@
+//! `memcpy` copies the elements of a range as they are, elements nothing was written to stay unwritten
+
+mod common;
+
+use serde_json::Value;
+
+use common::analyze_ok;
+
+/// Gets the elements of the block a pointer owns at the end of a program, as `(name, value)` pairs
+fn block_elements(source: &str, owner: &str) -> Vec<(String, Value)> {
+    let result = serde_json::to_value(analyze_ok(source)).unwrap();
+
+    let block = result["heap"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|block| {
+            block["block_state"] == "Allocated" && block["current_pointer_identifier"] == owner
+        })
+        .unwrap_or_else(|| panic!("expected a block owned by `{owner}`"));
+
+    block["members"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|member| (member["name"].as_str().unwrap().to_owned(), member["value"].clone()))
+        .collect()
+}
+
+#[test]
+fn copy_of_unwritten_elements_leaves_them_unwritten() {
+    let source = "int* a = new int[4]; int* b = new int[4]; memcpy(b, a, 8);";
+
+    assert_eq!(
+        block_elements(source, "b"),
+        [
+            ("[0]".to_owned(), Value::Null),
+            ("[1]".to_owned(), Value::Null)
+        ]
+    );
+
+    let source = "int* a = new int[4]; a[1] = 3; memcpy(a, a, 8);";
+
+    assert_eq!(
+        block_elements(source, "a"),
+        [
+            ("[0]".to_owned(), Value::Null),
+            ("[1]".to_owned(), Value::from("3"))
+        ]
+    );
+}
+
+#[test]
+fn copy_of_zeroed_and_written_blocks_keeps_their_values() {
+    let source = "int* c = (int*)calloc(2, 4); int* d = new int[2]; memcpy(d, c, 8);";
+
+    assert_eq!(
+        block_elements(source, "d"),
+        [
+            ("[0]".to_owned(), Value::from("0")),
+            ("[1]".to_owned(), Value::from("0"))
+        ]
+    );
+
+    let source = "int* e = new int; *e = 9; int* f = new int; memcpy(f, e, 4);";
+
+    assert_eq!(block_elements(source, "f"), [("[0]".to_owned(), Value::from("9"))]);
+}
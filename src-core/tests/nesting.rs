@@ -0,0 +1,88 @@
+//! Programs nested deep enough to overflow the stack of the parser or the analyzer end in an error
+//! instead of aborting, on a thread with a stack as small as a tokio worker's
+
+mod common;
+
+use mv_core::analyzer::AnalyzerOptions;
+
+use common::analyze;
+
+/// The smallest stack a frontend runs the core on, tokio's worker threads
+const WORKER_STACK_SIZE: usize = 2 * 1024 * 1024;
+
+/// Analyzes a program on a thread with a worker's stack, returning the message it fails with
+fn overflow(source: String, max_call_depth: Option<usize>) -> String {
+    let options = AnalyzerOptions {
+        max_call_depth,
+        ..Default::default()
+    };
+
+    std::thread::Builder::new()
+        .stack_size(WORKER_STACK_SIZE)
+        .spawn(move || analyze(&source, options).map(|_| ()))
+        .unwrap()
+        .join()
+        .unwrap()
+        .expect_err("expected the program to overflow the stack")
+        .to_string()
+}
+
+#[test]
+fn infinite_recursion_at_the_deepest_allowed_call_depth() {
+    let source = "int f(int n) { return f(n + 1); } int x = f(1);".to_owned();
+
+    let error = overflow(source, Some(usize::MAX));
+
+    assert!(error.contains("calls to `f` nest deeper than 128 frames"), "{error}");
+}
+
+#[test]
+fn recursion_through_nested_blocks() {
+    let source = "
+        void g(int n) {
+            for (int i = 0; i < 1; i++) {
+                if (n > 0) {
+                    while (n > 0) {
+                        if (n > 0) {
+                            g(n + 1);
+                        }
+                    }
+                }
+            }
+        }
+        g(1);
+    "
+    .to_owned();
+
+    let error = overflow(source, Some(usize::MAX));
+
+    assert!(error.contains("Stack overflow, blocks, expressions and calls nest deeper"), "{error}");
+}
+
+#[test]
+fn recursion_inside_nested_parentheses() {
+    let call = format!("{}f(n + 1){}", "(".repeat(400), ")".repeat(400));
+    let source = format!("int f(int n) {{ return {call}; }} int x = f(1);");
+
+    let error = overflow(source, Some(usize::MAX));
+
+    assert!(error.contains("Stack overflow"), "{error}");
+}
+
+#[test]
+fn deeply_nested_blocks() {
+    let source = format!("int x = 0; {}x = 1;{}", "{".repeat(10_000), "}".repeat(10_000));
+
+    let error = overflow(source, None);
+
+    assert!(error.contains("nest deeper than 512 levels"), "{error}");
+}
+
+#[test]
+fn deeply_nested_expression() {
+    let source = format!("int x = {}1{};", "(".repeat(10_000), ")".repeat(10_000));
+
+    let error = overflow(source, None);
+
+    assert!(error.contains("nest deeper than 512 levels"), "{error}");
+}
@@ -0,0 +1,99 @@
+//! Heap blocks are identified by the pointer they were allocated for, and notes follow the symbols and
+//! blocks they were attached to when the program changes
+
+mod common;
+
+use mv_core::analyzer::{place_notes, Note, NotePlace, NoteTarget};
+
+use common::analyze_ok;
+
+/// Gets the ids of the blocks of a program's heap, in the order the heap has them
+fn heap_ids(source: &str) -> Vec<String> {
+    analyze_ok(source).cross_reference.heap_ids.into_iter().flatten().collect()
+}
+
+/// Places a single note on a program, returning where it goes
+fn place(source: &str, target: NoteTarget) -> Option<NotePlace> {
+    let note = Note {
+        target,
+        text: "note".to_owned(),
+    };
+
+    place_notes(&[note], &analyze_ok(source)).remove(0).place
+}
+
+fn symbol(name: &str, frame: Option<&str>, scope: usize) -> NoteTarget {
+    NoteTarget::Symbol {
+        name: name.to_owned(),
+        frame: frame.map(str::to_owned),
+        scope,
+    }
+}
+
+#[test]
+fn block_ids_survive_lines_inserted_above() {
+    let mut ids = heap_ids("int* p = new int;\nint* q = new int;");
+    let mut moved = heap_ids("int x = 1;\n\nint* p = new int;\nint* q = new int;");
+
+    ids.sort();
+    moved.sort();
+
+    assert_eq!(ids, ["p", "q"]);
+    assert_eq!(moved, ids);
+}
+
+#[test]
+fn blocks_allocated_again_for_a_pointer_are_counted() {
+    let mut ids = heap_ids("int* p = new int; int* q = p; p = new int;");
+    ids.sort();
+
+    assert_eq!(ids, ["p", "p#2"]);
+}
+
+#[test]
+fn block_ids_are_qualified_by_function() {
+    let ids = heap_ids("void make() { int* p = new int; } int* p = new int; make();");
+
+    assert!(ids.contains(&"p".to_owned()), "{ids:?}");
+    assert!(ids.contains(&"make::p".to_owned()), "{ids:?}");
+}
+
+#[test]
+fn notes_on_blocks_are_placed_by_id() {
+    let source = "int* p = new int; int* q = new int;";
+    let result = analyze_ok(source);
+
+    let index = result.cross_reference.heap_ids.iter().position(|id| id.as_deref() == Some("q"));
+    let target = NoteTarget::Block { id: "q".to_owned() };
+
+    assert_eq!(place(source, target), index.map(|index| NotePlace::Heap { index }));
+}
+
+#[test]
+fn notes_on_symbols_are_placed_in_their_frame_and_scope() {
+    let source = "int x = 1; void f() { int x = 2; { int y = 3; } } f(); { int y = 4; }";
+
+    assert_eq!(place(source, symbol("x", None, 0)), Some(NotePlace::Stack { index: 0 }));
+    assert_eq!(
+        place(source, symbol("x", Some("f"), 0)),
+        Some(NotePlace::Frame { frame: 0, index: 0 })
+    );
+    assert_eq!(
+        place(source, symbol("y", Some("f"), 1)),
+        Some(NotePlace::ScopeExit { exit: 0, index: 0 })
+    );
+    assert_eq!(
+        place(source, symbol("y", None, 1)),
+        Some(NotePlace::ScopeExit { exit: 1, index: 0 })
+    );
+    assert_eq!(place(source, symbol("y", None, 0)), None);
+}
+
+#[test]
+fn notes_saved_without_a_frame_are_on_the_top_level() {
+    let note: Note =
+        serde_json::from_str(r#"{ "target": { "kind": "symbol", "name": "x" }, "text": "note" }"#)
+            .unwrap();
+
+    assert_eq!(note.target, symbol("x", None, 0));
+}
@@ -0,0 +1,45 @@
+//! Every feature analyzing a program runs it through the same pipeline, with the strictness, defines and
+//! architecture of its options
+
+mod common;
+
+use indexmap::IndexMap;
+
+use mv_core::analyzer::{AnalyzerOptions, Strictness};
+use mv_core::assessment::Assessment;
+
+use common::{analyze, block_on};
+
+const UNSUPPORTED: &str = "int x = 1; std::cout << x; int y = SIZE;";
+
+fn options(strictness: Strictness) -> AnalyzerOptions {
+    AnalyzerOptions {
+        seed: Some(0),
+        strictness,
+        defines: IndexMap::from([("SIZE".to_owned(), "2".to_owned())]),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn analysis_uses_the_strictness_and_defines_of_the_options() {
+    assert!(analyze(UNSUPPORTED, options(Strictness::Strict)).is_err());
+
+    let result = analyze(UNSUPPORTED, options(Strictness::Permissive)).unwrap();
+    assert_eq!(result.ignored.len(), 1);
+}
+
+#[test]
+fn grading_uses_the_strictness_and_defines_of_the_options() {
+    let assessment = Assessment {
+        id: "pipeline".to_owned(),
+        title: "Pipeline".to_owned(),
+        criteria: Vec::new(),
+    };
+
+    let report = block_on(assessment.grade(UNSUPPORTED, options(Strictness::Strict)));
+    assert!(report.error.is_some());
+
+    let report = block_on(assessment.grade(UNSUPPORTED, options(Strictness::Permissive)));
+    assert!(report.error.is_none(), "{:?}", report.error);
+}
@@ -0,0 +1,112 @@
+//! Writes through pointers land on the element they point to, reads through them see what was written,
+//! and pointers can be declared as copies of other pointers moved with pointer arithmetic
+
+mod common;
+
+use serde_json::{json, Value};
+
+use common::{analyze_err, analyze_ok};
+
+/// Gets the elements written to the only allocated block of a program, as `(name, value)` pairs
+fn written_elements(source: &str) -> Vec<(String, String)> {
+    let result = serde_json::to_value(analyze_ok(source)).unwrap();
+
+    let block = result["heap"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|block| block["block_state"] == "Allocated")
+        .expect("expected an allocated block");
+
+    block["members"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|member| {
+            (
+                member["name"].as_str().unwrap().to_owned(),
+                member["value"].as_str().unwrap().to_owned(),
+            )
+        })
+        .collect()
+}
+
+fn elements(elements: &[(&str, &str)]) -> Vec<(String, String)> {
+    elements.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect()
+}
+
+#[test]
+fn write_through_moved_pointer_writes_its_element() {
+    let written = written_elements("int* p = new int[3]; p[0] = 1; p[2] = 3; p++; *p = 5;");
+
+    assert_eq!(written, elements(&[("[0]", "1"), ("[1]", "5"), ("[2]", "3")]));
+}
+
+#[test]
+fn pointer_declared_with_arithmetic_points_into_the_block() {
+    let source = "int* p = new int[3]; int* q = p + 2; *q = 7; int* r = q - 1; *r = 4;";
+
+    assert_eq!(written_elements(source), elements(&[("[1]", "4"), ("[2]", "7")]));
+
+    let result = serde_json::to_value(analyze_ok(source)).unwrap();
+    let offsets: Vec<Value> = result["stack"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|symbol| symbol["Pointer"]["offset"].clone())
+        .collect();
+
+    assert_eq!(offsets, [json!(0), json!(8), json!(4)]);
+}
+
+#[test]
+fn pointer_declared_past_its_block_fails() {
+    let message = analyze_err("int* p = new int[3]; int* q = p + 4;");
+
+    assert!(
+        message.contains("Pointer `p` moves past the end of its block of 12 bytes"),
+        "{message}"
+    );
+}
+
+#[test]
+fn dereferenced_arithmetic_keeps_its_parentheses() {
+    let message = analyze_err("int* p = new int[3]; int x = *(p + 1);");
+
+    assert!(message.contains("`*(p + 1)`"), "{message}");
+}
+
+/// Gets the value of a variable of the last frame of a program
+fn variable_value(source: &str, name: &str) -> Value {
+    let result = serde_json::to_value(analyze_ok(source)).unwrap();
+
+    result["stack"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|symbol| symbol["Variable"]["name"] == name)
+        .map(|symbol| symbol["Variable"]["value"].clone())
+        .unwrap_or_else(|| panic!("expected a variable `{name}`"))
+}
+
+#[test]
+fn write_through_parameter_reads_the_value_it_points_to() {
+    let source = "void inc(int* p) { *p = *p + 1; } int x = 1; inc(&x); inc(&x);";
+
+    assert_eq!(variable_value(source, "x"), json!("3"));
+}
+
+#[test]
+fn dereference_reads_the_element_in_the_heap() {
+    let source = "int* p = new int[3]; p[1] = 6; p++; *p = *p * 2; int y = *p - 1;";
+
+    assert_eq!(written_elements(source), elements(&[("[1]", "12")]));
+    assert_eq!(variable_value(source, "y"), json!("11"));
+}
+
+#[test]
+fn dereference_of_uninitialized_value_fails() {
+    let message = analyze_err("int* p = new int; *p = *p + 1;");
+
+    assert!(message.contains("Value `*p` not initialized!"), "{message}");
+}
@@ -0,0 +1,66 @@
+//! Symbols on the stack are aligned to their size, and the frame of a call sits below the frames of the
+//! calls it was made from
+
+mod common;
+
+use mv_core::analyzer::{AnalyzerOptions, Architecture};
+use serde_json::Value;
+
+use common::{analyze, analyze_ok};
+
+/// Gets the addresses of the symbols of the top level, and of the symbols of each frame
+fn addresses(source: &str) -> (Vec<u64>, Vec<Vec<u64>>) {
+    let result = serde_json::to_value(analyze_ok(source)).unwrap();
+    let numbers = |addresses: &Value| -> Vec<u64> {
+        addresses.as_array().unwrap().iter().map(|address| address.as_u64().unwrap()).collect()
+    };
+
+    let frames = result["frames"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|frame| numbers(&frame["addresses"]))
+        .collect();
+
+    (numbers(&result["stack_addresses"]), frames)
+}
+
+#[test]
+fn symbols_are_aligned_to_their_size() {
+    let (stack, _) = addresses("int x; char c; double y; int* p;");
+
+    assert_eq!(stack, [0xBFFFFFFC, 0xBFFFFFFB, 0xBFFFFFF0, 0xBFFFFFEC]);
+}
+
+#[test]
+fn frames_sit_below_their_callers() {
+    let source = "int a = 1; int f() { int b = 2; return peek(0xBFFFFFFC); } int r = f(); \
+                  int g() { int c = 3; return c; } int s = g();";
+    let (stack, frames) = addresses(source);
+
+    assert_eq!(stack, [0xBFFFFFFC, 0xBFFFFFF8, 0xBFFFFFF4]);
+    assert_eq!(frames, [vec![0xBFFFFFF8], vec![0xBFFFFFF4]]);
+
+    let result = serde_json::to_value(analyze_ok(source)).unwrap();
+
+    assert_eq!(result["stack"][1]["Variable"]["value"], "1");
+}
+
+#[test]
+fn strings_are_laid_out_like_libstdcxx() {
+    let source = "std::string s = \"fifteen chars!!\"; int x;";
+
+    for (architecture, size) in [(Architecture::Ilp32, 24), (Architecture::Lp64, 32)] {
+        let options = AnalyzerOptions {
+            seed: Some(0),
+            architecture,
+            ..Default::default()
+        };
+        let result = serde_json::to_value(analyze(source, options).unwrap()).unwrap();
+        let string = &result["stack"][0]["Variable"];
+
+        assert_eq!(string["size"], size);
+        // the 15 characters fit in the variable itself
+        assert_eq!(string["string"]["heap_pointer"], Value::Null);
+    }
+}
@@ -0,0 +1,23 @@
+//! Asking where a block would go looks at the free list the way the allocator does
+
+use mv_core::analyzer::{query_allocation, AnalyzerOptions, FreeRange};
+
+fn range(start: usize, end: usize) -> FreeRange {
+    FreeRange {
+        start,
+        end,
+        size: end - start + 1,
+    }
+}
+
+#[test]
+fn block_fits_across_free_ranges_next_to_each_other() {
+    // two 16 byte ranges freed one after the other, listed apart and out of order
+    let free_list = [range(48, 63), range(32, 47)];
+
+    let query = query_allocation(&free_list, 24, &AnalyzerOptions::default()).unwrap();
+
+    assert!(query.fits, "{query:?}");
+    assert!(query.placements.iter().all(|placement| placement.range == Some(range(32, 63))));
+    assert_eq!(query.placements[1].pointer, Some(32));
+}
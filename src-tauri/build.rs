@@ -1,3 +1,97 @@
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+use serde_json::{Value, json};
+
 fn main() {
+    write_third_party_licenses();
+
     tauri_build::build();
 }
+
+/// Writes the crates compiled into the app with the licenses they are distributed under, for the
+/// acknowledgments of the About window
+///
+/// Only the crates the app links for the target are listed, walking the normal dependencies from
+/// the app, so build tools and crates of other platforms are left out, as are the crates of the
+/// repository
+fn write_third_party_licenses() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+
+    println!("cargo:rerun-if-changed={}", manifest_dir.join("Cargo.toml").display());
+    println!("cargo:rerun-if-changed={}", manifest_dir.join("Cargo.lock").display());
+    println!("cargo:rerun-if-changed={}", manifest_dir.join("../Cargo.lock").display());
+
+    let output = Command::new(env::var("CARGO").unwrap())
+        .arg("metadata")
+        .args(["--format-version", "1", "--locked"])
+        .args(["--filter-platform", &env::var("TARGET").unwrap()])
+        .arg("--manifest-path")
+        .arg(manifest_dir.join("Cargo.toml"))
+        .output()
+        .expect("failed to run cargo metadata");
+
+    if !output.status.success() {
+        panic!("cargo metadata failed: {}", String::from_utf8_lossy(&output.stderr));
+    }
+
+    let metadata: Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    let nodes = metadata["resolve"]["nodes"].as_array().unwrap();
+
+    let mut linked = HashSet::new();
+    let mut pending = vec![metadata["resolve"]["root"].as_str().unwrap()];
+
+    while let Some(id) = pending.pop() {
+        if !linked.insert(id) {
+            continue;
+        }
+
+        let Some(node) = nodes.iter().find(|node| node["id"] == id) else {
+            continue;
+        };
+
+        for dependency in node["deps"].as_array().unwrap() {
+            let normal = dependency["dep_kinds"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|kind| kind["kind"].is_null());
+
+            if normal {
+                pending.push(dependency["pkg"].as_str().unwrap());
+            }
+        }
+    }
+
+    let mut crates: Vec<Value> = metadata["packages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|package| {
+            // crates without a source, like the core, are part of the repository and under its license
+            linked.contains(package["id"].as_str().unwrap()) && !package["source"].is_null()
+        })
+        .map(|package| {
+            json!({
+                "name": package["name"],
+                "version": package["version"],
+                "license": package["license"],
+                "authors": package["authors"],
+                "repository": package["repository"],
+            })
+        })
+        .collect();
+
+    crates.sort_by(|a, b| {
+        (a["name"].as_str(), a["version"].as_str())
+            .cmp(&(b["name"].as_str(), b["version"].as_str()))
+    });
+
+    fs::write(out_dir.join("third_party_licenses.json"), serde_json::to_string(&crates).unwrap())
+        .unwrap();
+}
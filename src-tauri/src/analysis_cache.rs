@@ -0,0 +1,119 @@
+use std::collections::VecDeque;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use indexmap::IndexMap;
+use mv_core::analyzer::AnalyzerOptions;
+
+/// How many analysis results are kept in the cache
+const ANALYSIS_CACHE_LEN: usize = 16;
+
+/// The placements of the blocks of each pointer, by the name of the pointer
+type Placements = IndexMap<String, usize>;
+
+/// Represents a cached analysis
+///
+/// # Fields
+/// - `window`: The label of the window the source was analyzed in
+/// - `hash`: A hash of the source
+/// - `options`: The options the source was analyzed with
+/// - `placements`: Where the blocks of each pointer were placed when the analysis started
+/// - `response`: The response of the analysis
+/// - `placed`: Where the analysis left the blocks of each pointer
+struct CachedAnalysis {
+    window: String,
+    hash: u64,
+    options: AnalyzerOptions,
+    placements: Placements,
+    response: serde_json::Value,
+    placed: Placements,
+}
+
+/// The responses of the latest analyses, by the window, a hash of the source, the options it was
+/// analyzed with and the placements it started from, so toggling between two versions of the code or analyzing the same
+/// buffer again returns right away
+///
+/// The seed is one of the options, a source analyzed with another seed isn't answered from the cache.
+/// A hit hands back the placements the analysis left along with its response, so the next analysis
+/// starts from the layout the user is shown. The least recently used response is dropped once there are
+/// more than [ANALYSIS_CACHE_LEN]
+#[derive(Default)]
+pub(crate) struct AnalysisCache {
+    entries: VecDeque<CachedAnalysis>,
+}
+
+impl AnalysisCache {
+    /// Returns the response of analyzing the source in the window with the options from the placements,
+    /// and the placements it left, if it is cached
+    pub(crate) fn get(
+        &mut self,
+        window: &str,
+        input: &str,
+        options: &AnalyzerOptions,
+        placements: &Placements,
+    ) -> Option<(serde_json::Value, Placements)> {
+        let hash = source_hash(input);
+        let index = self.entries.iter().position(|entry| {
+            entry.window == window
+                && entry.hash == hash
+                && entry.options == *options
+                && entry.placements == *placements
+        })?;
+
+        // a hit is used again, so it is the last one dropped
+        let entry = self.entries.remove(index)?;
+        let hit = (entry.response.clone(), entry.placed.clone());
+        self.entries.push_back(entry);
+
+        Some(hit)
+    }
+
+    /// Keeps the response of analyzing the source in the window with the options from the placements,
+    /// along with the placements it left
+    pub(crate) fn insert(
+        &mut self,
+        window: &str,
+        input: &str,
+        options: &AnalyzerOptions,
+        placements: Placements,
+        response: &serde_json::Value,
+        placed: Placements,
+    ) {
+        let hash = source_hash(input);
+        self.entries.retain(|entry| {
+            entry.window != window
+                || entry.hash != hash
+                || entry.options != *options
+                || entry.placements != placements
+        });
+
+        if self.entries.len() == ANALYSIS_CACHE_LEN {
+            self.entries.pop_front();
+        }
+
+        self.entries.push_back(CachedAnalysis {
+            window: window.to_owned(),
+            hash,
+            options: options.clone(),
+            placements,
+            response: response.clone(),
+            placed,
+        });
+    }
+
+    /// Forgets every cached response
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Forgets the cached responses of the window, once its layout is rolled again or it is closed
+    pub(crate) fn forget(&mut self, label: &str) {
+        self.entries.retain(|entry| entry.window != label);
+    }
+}
+
+fn source_hash(input: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    input.hash(&mut hasher);
+
+    hasher.finish()
+}
@@ -0,0 +1,49 @@
+use std::collections::{HashMap, VecDeque};
+
+/// How many analysis results are kept for each document
+const ANALYSIS_HISTORY_LEN: usize = 8;
+
+/// The last analysis results of each document, by the label of the window the document is open in, so
+/// the frontend can flip back to the state before an edit without analyzing the source again
+///
+/// Only results that analyzed without errors are kept, an error isn't a state worth comparing with
+#[derive(Default)]
+pub(crate) struct AnalysisHistory {
+    results: HashMap<String, VecDeque<serde_json::Value>>,
+}
+
+impl AnalysisHistory {
+    /// Keeps the result of an analysis of the document open in the window, dropping the oldest result
+    /// once there are more than [ANALYSIS_HISTORY_LEN]
+    ///
+    /// A result the same as the latest one, like the source analyzed again without an edit, isn't kept
+    /// twice
+    pub(crate) fn record(&mut self, label: &str, result: &serde_json::Value) {
+        if result.get("error").is_some() {
+            return;
+        }
+
+        let results = self.results.entry(label.to_string()).or_default();
+
+        if results.back() == Some(result) {
+            return;
+        }
+
+        if results.len() == ANALYSIS_HISTORY_LEN {
+            results.pop_front();
+        }
+
+        results.push_back(result.clone());
+    }
+
+    /// Returns the result `back` analyses before the latest one of the document open in the window, the
+    /// latest one itself for `0`
+    pub(crate) fn previous(&self, label: &str, back: usize) -> Option<&serde_json::Value> {
+        self.results.get(label)?.iter().rev().nth(back)
+    }
+
+    /// Forgets the results of the document open in the window, once the window is closed
+    pub(crate) fn forget(&mut self, label: &str) {
+        self.results.remove(label);
+    }
+}
@@ -0,0 +1,103 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use indexmap::IndexMap;
+use mv_core::analyzer::{AnalysisResult, AnalyzerOptions};
+use mv_core::error::Result as CoreResult;
+use mv_core::export::render_ascii_diagram;
+use mv_core::pipeline::analyze_source;
+use mv_core::session::Session;
+
+use crate::compile_commands::project_defines;
+use crate::error::Result as MVResult;
+use crate::headless::{FileResult, report_files};
+
+/// A source analyzed as part of a batch, `name` tells its result apart from the others, like the
+/// file name of a submission, and is the `file` of its result
+#[derive(Debug, serde::Deserialize)]
+pub(crate) struct NamedSource {
+    pub(crate) name: String,
+    pub(crate) input: String,
+}
+
+/// Analyzes every source on its own, in the order they were given
+///
+/// Each source starts from a fresh heap instead of the placements remembered for the window, so two
+/// solutions analyzed with the same options are laid out the same way wherever they do the same thing.
+/// A source that fails to analyze has its error in its result instead of stopping the whole batch
+pub(crate) async fn analyze_sources(
+    sources: Vec<NamedSource>,
+    options: &AnalyzerOptions,
+) -> Vec<FileResult<AnalysisResult>> {
+    let mut results = Vec::with_capacity(sources.len());
+
+    for source in sources {
+        let result = analyze_detached(&source.input, options).await;
+        results.push(FileResult::new(source.name, result));
+    }
+
+    results
+}
+
+/// Analyzes every file and writes one result per line to the output, for processing a whole assignment from the
+/// command line, e.g. `mv --batch submissions/*.cpp`
+///
+/// Each file is preprocessed with the macros of its project's `compile_commands.json`, if it has one
+pub(crate) fn analyze_files(
+    files: &[impl AsRef<Path>],
+    options: &AnalyzerOptions,
+    output: &mut impl Write,
+) -> MVResult<()> {
+    report_files(files, output, |file, source| {
+        let mut options = options.clone();
+        options.defines.extend(project_defines(file, source));
+
+        tauri::async_runtime::block_on(analyze_detached(source, &options))
+    })
+}
+
+/// Analyzes every file and writes the ASCII diagram of its memory to the output, for pasting into a
+/// report or a terminal, e.g. `mv --ascii list.cpp`
+///
+/// Each diagram is headed by the name of its file when there are several. A file that can't be read or
+/// analyzed has its error written in place of its diagram instead of stopping the others
+pub(crate) fn render_files(
+    files: &[impl AsRef<Path>],
+    options: &AnalyzerOptions,
+    output: &mut impl Write,
+) -> MVResult<()> {
+    for file in files {
+        let file = file.as_ref();
+
+        if files.len() > 1 {
+            writeln!(output, "==> {} <==", file.to_string_lossy())?;
+        }
+
+        let result =
+            fs::read_to_string(file).map_err(|e| e.to_string().into()).and_then(|source| {
+                let mut options = options.clone();
+                options.defines.extend(project_defines(file, &source));
+
+                tauri::async_runtime::block_on(analyze_detached(&source, &options))
+            });
+
+        match result {
+            Ok(analysis) => write!(
+                output,
+                "{}",
+                render_ascii_diagram(&analysis.stack, &analysis.heap, options.architecture)
+            )?,
+            Err(e) => writeln!(output, "{e}")?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses and analyzes the source code in a session of its own
+async fn analyze_detached(input: &str, options: &AnalyzerOptions) -> CoreResult<AnalysisResult> {
+    let mut session = Session::new(input.to_owned(), IndexMap::new(), options.clone());
+
+    analyze_source(input, &mut session, options.clone()).await
+}
@@ -0,0 +1,144 @@
+use std::path::{Path, PathBuf};
+
+use log::warn;
+use mv_core::analyzer::{AnalyzerOptions, Architecture, CallingConvention, Strictness};
+
+use crate::error::Result;
+
+/// Arguments the app was launched with, e.g. `mv path/to/file.cpp --seed 42 --arch ilp32`
+///
+/// A directory like `mv path/to/project` is opened as a workspace instead of a file
+///
+/// `--grade assessment.json` grades the files instead of opening them and exits without a window, so does
+/// `--compare-seeds 100`, printing where 100 different seeds place the heap blocks of the files, and
+/// `--batch`, printing the result of analyzing each file, and `--ascii`, printing the ASCII diagram of the
+/// memory of each file. `--output results.jsonl` writes what they print to a file instead
+#[derive(Debug, Default, Clone)]
+pub(crate) struct CliArgs {
+    pub(crate) files: Vec<PathBuf>,
+    pub(crate) seed: Option<u64>,
+    pub(crate) architecture: Option<Architecture>,
+    pub(crate) calling_convention: Option<CallingConvention>,
+    pub(crate) strictness: Option<Strictness>,
+    pub(crate) portable: bool,
+    pub(crate) headless: Option<HeadlessMode>,
+    pub(crate) output: Option<PathBuf>,
+}
+
+/// What the app does instead of opening a window, with the files it was launched with
+///
+/// - `Grade`: Grades the files against the assessment at the path
+/// - `CompareSeeds`: Places the heap blocks of the files under this many seeds
+/// - `Batch`: Analyzes the files
+/// - `Ascii`: Draws the memory of the files as ASCII diagrams
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum HeadlessMode {
+    Grade(PathBuf),
+    CompareSeeds(u64),
+    Batch,
+    Ascii,
+}
+
+impl CliArgs {
+    /// Parses the arguments the app was launched with
+    ///
+    /// The first argument is the executable. Arguments that aren't flags are treated as file paths
+    /// relative to `cwd`, unknown flags are ignored so platform specific ones passed by the OS don't
+    /// stop the app from launching
+    pub(crate) fn parse(args: &[String], cwd: &Path) -> Result<CliArgs> {
+        let mut cli_args = CliArgs::default();
+        let mut args = args.iter().skip(1);
+
+        while let Some(arg) = args.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if arg.starts_with("--") => (flag, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+
+            match flag {
+                "--seed" => {
+                    let value = flag_value(flag, inline_value, &mut args)?;
+                    cli_args.seed = Some(
+                        value.parse().map_err(|_| format!("Invalid value for --seed: {value}"))?,
+                    );
+                }
+                "--arch" => {
+                    let value = flag_value(flag, inline_value, &mut args)?;
+                    cli_args.architecture = Some(value.parse()?);
+                }
+                "--calling-convention" => {
+                    let value = flag_value(flag, inline_value, &mut args)?;
+                    cli_args.calling_convention = Some(value.parse()?);
+                }
+                "--strictness" => {
+                    let value = flag_value(flag, inline_value, &mut args)?;
+                    cli_args.strictness = Some(value.parse()?);
+                }
+                "--portable" => cli_args.portable = true,
+                "--grade" => {
+                    let assessment = cwd.join(flag_value(flag, inline_value, &mut args)?);
+                    cli_args.set_headless(HeadlessMode::Grade(assessment))?;
+                }
+                "--compare-seeds" => {
+                    let value = flag_value(flag, inline_value, &mut args)?;
+                    let count = value
+                        .parse()
+                        .map_err(|_| format!("Invalid value for --compare-seeds: {value}"))?;
+                    cli_args.set_headless(HeadlessMode::CompareSeeds(count))?;
+                }
+                "--batch" => cli_args.set_headless(HeadlessMode::Batch)?,
+                "--ascii" => cli_args.set_headless(HeadlessMode::Ascii)?,
+                "--output" => {
+                    cli_args.output = Some(cwd.join(flag_value(flag, inline_value, &mut args)?));
+                }
+                flag if flag.starts_with('-') => warn!("Ignoring unknown argument: {arg}"),
+                _ => cli_args.files.push(cwd.join(arg)),
+            }
+        }
+
+        Ok(cli_args)
+    }
+
+    /// Sets what the app does instead of opening a window, only one of `--grade`, `--compare-seeds`,
+    /// `--batch` and `--ascii` can be passed
+    fn set_headless(&mut self, mode: HeadlessMode) -> Result<()> {
+        if self.headless.is_some() {
+            return Err(
+                "Only one of --grade, --compare-seeds, --batch and --ascii can be passed".into()
+            );
+        }
+
+        self.headless = Some(mode);
+        Ok(())
+    }
+
+    /// Overrides the options that were passed on the command line
+    pub(crate) fn apply(&self, options: &mut AnalyzerOptions) {
+        if let Some(seed) = self.seed {
+            options.seed = Some(seed);
+        }
+
+        if let Some(architecture) = self.architecture {
+            options.architecture = architecture;
+        }
+
+        if let Some(calling_convention) = self.calling_convention {
+            options.calling_convention = calling_convention;
+        }
+
+        if let Some(strictness) = self.strictness {
+            options.strictness = strictness;
+        }
+    }
+}
+
+fn flag_value<'a>(
+    flag: &str,
+    inline_value: Option<String>,
+    args: &mut impl Iterator<Item = &'a String>,
+) -> Result<String> {
+    match inline_value.or_else(|| args.next().cloned()) {
+        Some(value) => Ok(value),
+        None => Err(format!("Missing value for {flag}").into()),
+    }
+}
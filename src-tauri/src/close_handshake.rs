@@ -0,0 +1,109 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use log::{error, info, warn};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tokio::sync::oneshot;
+use tokio::time::timeout;
+
+pub(crate) const CLOSE_REQUESTED_EVENT: &str = "close-requested";
+
+/// How long the frontend has to answer a close request before the window is closed anyway, so a
+/// frontend that crashed or never loaded can't keep a window open forever
+const CLOSE_RESPONSE_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Default)]
+struct CloseHandshakeInner {
+    next_request_id: u64,
+    pending: HashMap<u64, oneshot::Sender<bool>>,
+    approved: HashSet<String>,
+}
+
+/// Tracks close requests that are waiting for the frontend to report whether it has unsaved documents
+#[derive(Default)]
+pub(crate) struct CloseHandshake {
+    inner: Mutex<CloseHandshakeInner>,
+}
+
+impl CloseHandshake {
+    /// Returns whether the window has already been cleared to close, consuming the approval
+    pub(crate) fn take_approval(&self, label: &str) -> bool {
+        self.inner.lock().unwrap().approved.remove(label)
+    }
+
+    /// Resolves a pending close request with the frontend's answer
+    ///
+    /// Returns `false` if the request is unknown, which happens when it already timed out
+    pub(crate) fn resolve(&self, request_id: u64, has_unsaved_documents: bool) -> bool {
+        match self.inner.lock().unwrap().pending.remove(&request_id) {
+            Some(tx) => tx.send(has_unsaved_documents).is_ok(),
+            None => false,
+        }
+    }
+
+    fn begin(&self) -> (u64, oneshot::Receiver<bool>) {
+        let mut inner = self.inner.lock().unwrap();
+        let (tx, rx) = oneshot::channel();
+
+        let request_id = inner.next_request_id;
+        inner.next_request_id += 1;
+        inner.pending.insert(request_id, tx);
+
+        (request_id, rx)
+    }
+
+    fn cancel(&self, request_id: u64) {
+        self.inner.lock().unwrap().pending.remove(&request_id);
+    }
+
+    fn approve(&self, label: &str) {
+        self.inner.lock().unwrap().approved.insert(label.to_string());
+    }
+}
+
+/// Asks the frontend of the window whether it has unsaved documents and closes the window once it
+/// answers that it doesn't. The caller is expected to have vetoed the original close request
+pub(crate) fn request_close<R: Runtime>(app_handle: &AppHandle<R>, label: String) {
+    let app_handle = app_handle.clone();
+
+    tauri::async_runtime::spawn(async move {
+        let Some(window) = app_handle.get_webview_window(&label) else {
+            error!("Webview window not found for label: {}", label);
+            return;
+        };
+
+        let handshake = app_handle.state::<CloseHandshake>();
+        let (request_id, response) = handshake.begin();
+
+        let has_unsaved_documents =
+            match window.emit_to(window.label(), CLOSE_REQUESTED_EVENT, request_id) {
+                Ok(_) => match timeout(CLOSE_RESPONSE_TIMEOUT, response).await {
+                    Ok(Ok(has_unsaved_documents)) => has_unsaved_documents,
+                    _ => {
+                        warn!(
+                            "No answer to close request {request_id} for {label}, closing anyway"
+                        );
+                        false
+                    }
+                },
+                Err(e) => {
+                    error!("Failed to emit {CLOSE_REQUESTED_EVENT} event: {e}");
+                    false
+                }
+            };
+
+        handshake.cancel(request_id);
+
+        if has_unsaved_documents {
+            info!("Close of window {label} vetoed, it has unsaved documents");
+            return;
+        }
+
+        handshake.approve(&label);
+
+        if let Err(e) = window.close() {
+            error!("Failed to close window {label}: {e}");
+        }
+    });
+}
@@ -1,18 +1,46 @@
+use std::path::PathBuf;
+use std::time::Instant;
+
 use font_kit::source::SystemSource;
-use log::{info, warn};
+use log::warn;
+use tauri::ipc::Channel;
 use tauri::{AppHandle, Manager, WebviewWindow, command, is_dev};
 use tokio::sync::Mutex;
 use webbrowser;
 
-use mv_core::analyzer::Analyzer;
-use mv_core::error::Error::{AnalyzerError, ParserError};
-use mv_core::parser::Parser;
+use mv_core::analyzer::{
+    AccessPath, AllocationQuery, AllocatorExercise, AllocatorReport, AnalysisError, AnalysisResult,
+    AnalyzerOptions, AnalyzerState, FreeRange, LayoutHint, Note, PlacedNote, StatementPreview,
+    TimelineChunk, access_paths, free_block, place_notes, preview_statement, query_allocation,
+    set_variable_value,
+};
+use mv_core::assessment::{Assessment, AssessmentReport};
+use mv_core::capabilities::{Capabilities, capabilities};
+use mv_core::error::Result as CoreResult;
+use mv_core::export::{DEFAULT_WASM_MODULE_URL, render_ascii_diagram, render_embed_snippet};
+use mv_core::layout::{StructLayout, compute_struct_layout};
+use mv_core::lesson::{Lesson, LessonReport};
+use mv_core::pipeline::analyze_source;
+use mv_core::placement_statistics::{PlacementStatistics, compare_seeds};
+use mv_core::session::Session;
 
 use crate::AppState;
+use crate::batch::{NamedSource, analyze_sources};
+use crate::close_handshake::CloseHandshake;
+use crate::compile_commands::project_defines;
 use crate::desktop_analyzer_state::DesktopAnalyzerState;
 use crate::error::{Error, Result as MVResult};
-use crate::updates::MVUpdater;
-use crate::utils::remove_main_function;
+use crate::headless::FileResult;
+use crate::lessons::load_lessons;
+use crate::licenses::{Licenses, licenses};
+use crate::live_updates::LiveUpdate;
+use crate::notifications::notify_if_long_and_unfocused;
+use crate::open_file::OpenedFiles;
+use crate::paths::AppPaths;
+use crate::playground::{DEFAULT_PLAYGROUND_DIR_NAME, export_offline_playground};
+use crate::secure_session::{ExamKeys, generate_exam_keys, open_session, seal_session};
+use crate::settings::AppSettings;
+use crate::updates::{MVUpdater, UpdateChannel};
 
 #[derive(serde::Serialize)]
 #[serde(default, rename_all = "camelCase")]
@@ -23,12 +51,16 @@ pub(crate) struct AppMetaData {
     name: String,
     app_data_dir: String,
     app_log_dir: String,
+    is_portable: bool,
+    update_channel: UpdateChannel,
+    core: Capabilities,
 }
 
 #[command]
 pub(crate) async fn cmd_metadata(app_handle: AppHandle) -> MVResult<AppMetaData> {
-    let app_data_dir = app_handle.path().app_data_dir()?;
-    let app_log_dir = app_handle.path().app_log_dir()?;
+    let paths = app_handle.state::<AppPaths>();
+    let app_data_dir = paths.data_dir(&app_handle)?;
+    let app_log_dir = paths.log_dir(&app_handle)?;
     Ok(AppMetaData {
         is_dev: is_dev(),
         version: app_handle.package_info().version.to_string(),
@@ -36,6 +68,9 @@ pub(crate) async fn cmd_metadata(app_handle: AppHandle) -> MVResult<AppMetaData>
         name: app_handle.package_info().name.to_string(),
         app_data_dir: app_data_dir.to_string_lossy().to_string(),
         app_log_dir: app_log_dir.to_string_lossy().to_string(),
+        is_portable: paths.is_portable(),
+        update_channel: app_handle.state::<Mutex<MVUpdater>>().lock().await.channel(),
+        core: capabilities(),
     })
 }
 
@@ -47,6 +82,34 @@ pub async fn cmd_download_and_install_update(window: WebviewWindow) -> MVResult<
     Ok(updater.download_and_install_now(&window).await?)
 }
 
+#[command]
+pub(crate) async fn cmd_get_settings(app_handle: AppHandle) -> MVResult<AppSettings> {
+    Ok(app_handle.state::<Mutex<AppSettings>>().lock().await.clone())
+}
+
+#[command]
+pub(crate) async fn cmd_set_automatic_update_checks(
+    app_handle: AppHandle,
+    enabled: bool,
+) -> MVResult<()> {
+    let settings_state = app_handle.state::<Mutex<AppSettings>>();
+    let mut settings = settings_state.lock().await;
+
+    settings.automatic_update_checks = enabled;
+    settings.save(&app_handle)?;
+
+    app_handle.state::<Mutex<MVUpdater>>().lock().await.set_automatic_checks(enabled);
+
+    Ok(())
+}
+
+/// Tells the analyzer how much of the heap the frontend shows, so the next analyses start the heap as big
+/// as the panel. Analyses given options of their own use the layout in those instead
+#[command]
+pub(crate) async fn cmd_set_layout_hint(app_handle: AppHandle, layout: LayoutHint) {
+    app_handle.state::<Mutex<AppState>>().lock().await.analyzer_options.layout = Some(layout);
+}
+
 #[command]
 pub async fn cmd_check_for_updates(window: WebviewWindow) -> MVResult<bool> {
     let state = window.app_handle().state::<Mutex<MVUpdater>>();
@@ -56,75 +119,607 @@ pub async fn cmd_check_for_updates(window: WebviewWindow) -> MVResult<bool> {
     Ok(result)
 }
 
+/// Resolves the options to analyze with, falling back to the ones the app was launched with, overridden
+/// by the ones a later launch opened the window with
+async fn analyzer_options(
+    window: &WebviewWindow,
+    options: Option<AnalyzerOptions>,
+) -> AnalyzerOptions {
+    if let Some(options) = options {
+        return options;
+    }
+
+    let mut options = window.state::<Mutex<AppState>>().lock().await.analyzer_options.clone();
+    window.state::<OpenedFiles>().apply_window_options(window.label(), &mut options);
+
+    options
+}
+
+/// Parses and analyzes the source code, remembering heap placements in the desktop state of the window
+async fn analyze(
+    window: &WebviewWindow,
+    input: &str,
+    options: &AnalyzerOptions,
+) -> CoreResult<AnalysisResult> {
+    let mut state = DesktopAnalyzerState {
+        state: &window.state::<Mutex<AppState>>(),
+        window: window.label(),
+    };
+
+    analyze_source(input, &mut state, options.clone()).await
+}
+
 #[command]
 pub(crate) async fn cmd_analyze_source_code(
-    app_handle: AppHandle,
+    window: WebviewWindow,
     input: String,
+    options: Option<AnalyzerOptions>,
 ) -> serde_json::Value {
-    let sanitized_source_code = remove_main_function(&input);
-
-    let mut parser = Parser::new(&sanitized_source_code);
-
-    match parser.parse() {
-        Ok(statements) => {
-            info!("{:?}", statements);
-
-            let mut state = DesktopAnalyzerState {
-                state: &app_handle.state::<Mutex<AppState>>(),
-            };
-
-            match Analyzer::default().analyze_statements(statements, &mut state).await {
-                Ok((stack, heap)) => {
-                    return serde_json::json!({
-                        "stack": stack,
-                        "heap": heap,
-                    });
-                }
-
-                Err(e) => match e {
-                    AnalyzerError(_, line_number, column_number) => {
-                        return serde_json::json!({
-                            "error": {
-                                "message": e.to_string(),
-                                "line_number": line_number,
-                                "column_number": column_number
-                            }
-                        });
-                    }
-
-                    _ => {
-                        return serde_json::json!({
-                            "error": {
-                                "message": e.to_string()
-                            }
-                        });
-                    }
-                },
+    let started_at = Instant::now();
+    let options = analyzer_options(&window, options).await;
+
+    // the same source analyzed with the same options from the same placements comes out the same, so it
+    // isn't analyzed again, and the placements it left are restored like the analysis would leave them
+    let (cached, placements) = {
+        let state = window.state::<Mutex<AppState>>();
+        let mut state = state.lock().await;
+        let state = &mut *state;
+
+        let mut starting_pointers = state.starting_pointers.lock().await;
+        let placements = starting_pointers.get(window.label()).cloned().unwrap_or_default();
+        let cached = state.analysis_cache.get(window.label(), &input, &options, &placements);
+
+        match cached {
+            Some((response, placed)) => {
+                starting_pointers.insert(window.label().to_owned(), placed);
+                (Some(response), placements)
             }
+            None => (None, placements),
         }
+    };
 
-        Err(e) => match e {
-            ParserError(_, line_number, column_number) => {
-                return serde_json::json!({
-                    "error": {
-                        "message": e.to_string(),
-                        "line_number": line_number,
-                        "column_number": column_number
-                    }
-                });
-            }
+    if let Some(response) = cached {
+        remember_result(&window, &response).await;
+        return response;
+    }
 
-            _ => {
-                return serde_json::json!({
-                    "error": {
-                        "message": e.to_string()
-                    }
-                });
-            }
+    let result = analyze(&window, &input, &options).await;
+
+    notify_if_long_and_unfocused(
+        &window,
+        started_at,
+        "Analysis finished",
+        "The memory visualization is ready",
+    );
+
+    let response = analysis_to_json(result);
+    remember_result(&window, &response).await;
+
+    {
+        let state = window.state::<Mutex<AppState>>();
+        let mut state = state.lock().await;
+        let placed =
+            state.starting_pointers.lock().await.get(window.label()).cloned().unwrap_or_default();
+
+        state.analysis_cache.insert(
+            window.label(),
+            &input,
+            &options,
+            placements,
+            &response,
+            placed,
+        );
+    }
+
+    response
+}
+
+/// Forgets the cached responses of [cmd_analyze_source_code], so the next analysis of every source runs
+/// again
+#[command]
+pub(crate) async fn cmd_clear_analysis_cache(app_handle: AppHandle) {
+    let state = app_handle.state::<Mutex<AppState>>();
+    state.lock().await.analysis_cache.clear();
+}
+
+/// Analyzes the source again with a new seed, forgetting where the blocks of every pointer that isn't
+/// pinned were placed in the window, so users can see that heap addresses are arbitrary while pinned blocks stay put
+///
+/// The response is the one [cmd_analyze_source_code] gives, along with the seed that was used
+#[command]
+pub(crate) async fn cmd_reroll_layout(
+    window: WebviewWindow,
+    input: String,
+    pinned: Vec<String>,
+    options: Option<AnalyzerOptions>,
+) -> serde_json::Value {
+    let mut options = analyzer_options(&window, options).await;
+    // 53 bits, which JS numbers hold exactly, so the frontend can pass the seed back unchanged
+    let seed = rand::random::<u64>() >> 11;
+    options.seed = Some(seed);
+
+    {
+        let state = window.state::<Mutex<AppState>>();
+        let mut state = state.lock().await;
+
+        // only the layout of this window is rolled again, other windows keep theirs
+        if let Some(starting_pointers) =
+            state.starting_pointers.lock().await.get_mut(window.label())
+        {
+            starting_pointers.retain(|name, _| pinned.contains(name));
+        }
+
+        // the cached responses of the window were laid out with the placements that were just forgotten
+        state.analysis_cache.forget(window.label());
+    }
+
+    let mut response = analysis_to_json(analyze(&window, &input, &options).await);
+    response["seed"] = seed.into();
+    remember_result(&window, &response).await;
+
+    response
+}
+
+/// Returns an earlier result of analyzing the document open in the window, so the frontend can show the
+/// state before an edit next to the current one without analyzing the source again
+///
+/// `back` counts the analyses before the latest one, `1` being the one right before it and `0` the
+/// latest itself. There is no result if the document wasn't analyzed that many times, or only the last
+/// few results are kept
+#[command]
+pub(crate) async fn cmd_get_previous_result(
+    window: WebviewWindow,
+    back: usize,
+) -> Option<serde_json::Value> {
+    let state = window.state::<Mutex<AppState>>();
+    let state = state.lock().await;
+
+    state.analysis_history.previous(window.label(), back).cloned()
+}
+
+/// Keeps the response of an analysis in the history of the document open in the window
+async fn remember_result(window: &WebviewWindow, response: &serde_json::Value) {
+    let state = window.state::<Mutex<AppState>>();
+    state.lock().await.analysis_history.record(window.label(), response);
+}
+
+/// Sets a variable of the program open in the window outside of its source, so an instructor can poke
+/// memory live during a demo, and returns the updated result
+///
+/// The value is a literal like `5` or an expression of the other variables like `x + 1`. Edits build
+/// on each other until the source or the options change, the response is the one
+/// [cmd_analyze_source_code] gives
+#[command]
+pub(crate) async fn cmd_set_variable_value(
+    window: WebviewWindow,
+    input: String,
+    name: String,
+    value: String,
+    options: Option<AnalyzerOptions>,
+) -> serde_json::Value {
+    edit_memory(window, input, options, |analysis, options| {
+        set_variable_value(&mut analysis.stack, &name, &value, options)
+    })
+    .await
+}
+
+/// Frees the heap block starting at `block` of the program open in the window outside of its source,
+/// leaving the pointers to it dangling, and returns the updated result
+///
+/// Edits build on each other until the source or the options change, the response is the one
+/// [cmd_analyze_source_code] gives
+#[command]
+pub(crate) async fn cmd_free_block(
+    window: WebviewWindow,
+    input: String,
+    block: usize,
+    options: Option<AnalyzerOptions>,
+) -> serde_json::Value {
+    edit_memory(window, input, options, |analysis, options| free_block(analysis, block, options))
+        .await
+}
+
+/// Applies an edit to the memory the source ends with, starting from the earlier edits of the window
+/// if it made them to the same source
+async fn edit_memory(
+    window: WebviewWindow,
+    input: String,
+    options: Option<AnalyzerOptions>,
+    edit: impl FnOnce(&mut AnalysisResult, &AnalyzerOptions) -> CoreResult<()>,
+) -> serde_json::Value {
+    let options = analyzer_options(&window, options).await;
+
+    let edited = {
+        let state = window.state::<Mutex<AppState>>();
+        let mut state = state.lock().await;
+
+        state.memory_edits.take(window.label(), &input, &options)
+    };
+
+    let mut analysis = match edited {
+        Some(analysis) => analysis,
+        None => match analyze(&window, &input, &options).await {
+            Ok(analysis) => analysis,
+            Err(e) => return analysis_to_json(Err(e)),
         },
+    };
+
+    // a failed edit leaves the memory as it was
+    let result = edit(&mut analysis, &options);
+    let response = analysis_to_json(Ok(analysis.clone()));
+
+    {
+        let state = window.state::<Mutex<AppState>>();
+        state.lock().await.memory_edits.keep(window.label(), input, options, analysis);
+    }
+
+    if let Err(e) = result {
+        return analysis_to_json(Err(e));
+    }
+
+    remember_result(&window, &response).await;
+
+    response
+}
+
+/// Analyzes the source like [cmd_analyze_source_code] does, but sends the timeline of the program over
+/// `on_timeline` in windows of `steps_per_chunk` steps instead of in the response, so long programs don't
+/// have to be held and parsed in a single message by the frontend
+///
+/// The response is the one [cmd_analyze_source_code] gives, with the lists of events in the cross
+/// reference left empty, and is only returned once every window was sent
+#[command]
+pub(crate) async fn cmd_analyze_source_code_streamed(
+    window: WebviewWindow,
+    input: String,
+    steps_per_chunk: usize,
+    options: Option<AnalyzerOptions>,
+    on_timeline: Channel<TimelineChunk>,
+) -> serde_json::Value {
+    let options = analyzer_options(&window, options).await;
+    let mut result = analyze(&window, &input, &options).await;
+
+    if let Ok(analysis) = &mut result {
+        for chunk in analysis.cross_reference.take_timeline(steps_per_chunk) {
+            if let Err(e) = on_timeline.send(chunk) {
+                warn!("Failed to send a window of the timeline: {}", e);
+                break;
+            }
+        }
+    }
+
+    analysis_to_json(result)
+}
+
+/// Analyzes the source of a document being typed into like [cmd_analyze_source_code] does, but sends
+/// the result over `on_update` as the records that changed since the last result sent for the document,
+/// so a keystroke in a large program doesn't send the whole result again
+///
+/// The first result sent for a document is the whole response [cmd_analyze_source_code] gives
+#[command]
+pub(crate) async fn cmd_analyze_live(
+    window: WebviewWindow,
+    document: String,
+    input: String,
+    options: Option<AnalyzerOptions>,
+    on_update: Channel<LiveUpdate>,
+) {
+    let response = cmd_analyze_source_code(window.clone(), input, options).await;
+
+    let update = {
+        let state = window.state::<Mutex<AppState>>();
+        let mut state = state.lock().await;
+
+        state.live_documents.update(window.label(), &document, response)
+    };
+
+    if let Err(e) = on_update.send(update) {
+        warn!("Failed to send the changes of a live analysis: {}", e);
     }
 }
 
+/// Forgets the last result sent for a document by [cmd_analyze_live], once it is closed
+#[command]
+pub(crate) async fn cmd_close_live_document(window: WebviewWindow, document: String) {
+    let state = window.state::<Mutex<AppState>>();
+    state.lock().await.live_documents.close(window.label(), &document);
+}
+
+/// Analyzes several sources in one call, like the submissions of an assignment, instead of one call
+/// per source
+///
+/// The response has one result per source in the order they were given, the name of the source as
+/// its `file` along with the analysis or the error, like the lines `mv --batch` prints
+#[command]
+pub(crate) async fn cmd_analyze_batch(
+    window: WebviewWindow,
+    sources: Vec<NamedSource>,
+    options: Option<AnalyzerOptions>,
+) -> Vec<FileResult<AnalysisResult>> {
+    let options = analyzer_options(&window, options).await;
+
+    analyze_sources(sources, &options).await
+}
+
+/// Builds the response of an analysis, errors are part of the response so the editor can mark them
+pub(crate) fn analysis_to_json(result: CoreResult<AnalysisResult>) -> serde_json::Value {
+    match result {
+        Ok(analysis) => serde_json::to_value(analysis),
+        Err(e) => serde_json::to_value(AnalysisError::from(&e)),
+    }
+    .unwrap()
+}
+
+#[command]
+pub(crate) async fn cmd_export_ascii_diagram(
+    window: WebviewWindow,
+    input: String,
+    options: Option<AnalyzerOptions>,
+) -> MVResult<String> {
+    let started_at = Instant::now();
+    let options = analyzer_options(&window, options).await;
+    let analysis = analyze(&window, &input, &options).await?;
+    let diagram = render_ascii_diagram(&analysis.stack, &analysis.heap, options.architecture);
+
+    notify_if_long_and_unfocused(
+        &window,
+        started_at,
+        "Export finished",
+        "The ASCII diagram is ready",
+    );
+
+    Ok(diagram)
+}
+
+/// Exports the program as an HTML snippet for static sites, an editable copy of the source next to the
+/// session view of its memory that the MV WASM module draws again as readers type
+#[command]
+pub(crate) async fn cmd_export_embed_snippet(
+    window: WebviewWindow,
+    input: String,
+    wasm_module_url: Option<String>,
+    options: Option<AnalyzerOptions>,
+) -> MVResult<String> {
+    let started_at = Instant::now();
+    let options = analyzer_options(&window, options).await;
+
+    // analyzing first validates the source and brings the remembered heap placements up to date
+    analyze(&window, &input, &options).await?;
+
+    let starting_pointers = DesktopAnalyzerState {
+        state: &window.state::<Mutex<AppState>>(),
+        window: window.label(),
+    }
+    .get_starting_pointers()
+    .await;
+
+    let session = Session::new(input, starting_pointers, options);
+    let snippet = render_embed_snippet(
+        &session,
+        wasm_module_url.as_deref().unwrap_or(DEFAULT_WASM_MODULE_URL),
+    )?;
+
+    notify_if_long_and_unfocused(
+        &window,
+        started_at,
+        "Export finished",
+        "The embeddable snippet is ready",
+    );
+
+    Ok(snippet)
+}
+
+/// Answers whether an allocation of `size` bytes would fit in the free list of an analysis and where
+/// each strategy would place it, without analyzing or allocating anything
+#[command]
+pub(crate) async fn cmd_query_allocation(
+    window: WebviewWindow,
+    free_list: Vec<FreeRange>,
+    size: usize,
+    options: Option<AnalyzerOptions>,
+) -> MVResult<AllocationQuery> {
+    let options = analyzer_options(&window, options).await;
+
+    Ok(query_allocation(&free_list, size, &options)?)
+}
+
+/// Computes the offsets, padding and size of a struct with members of the given types on the
+/// architecture of the options, without analyzing anything
+#[command]
+pub(crate) async fn cmd_compute_struct_layout(
+    window: WebviewWindow,
+    member_types: Vec<String>,
+    options: Option<AnalyzerOptions>,
+) -> MVResult<StructLayout> {
+    let options = analyzer_options(&window, options).await;
+
+    Ok(compute_struct_layout(&member_types, options.architecture)?)
+}
+
+/// Analyzes the source code and lists every chain of pointers from the stack that reaches the heap
+/// block starting at `block`, like `pp -> p`
+#[command]
+pub(crate) async fn cmd_access_paths(
+    window: WebviewWindow,
+    input: String,
+    block: usize,
+    options: Option<AnalyzerOptions>,
+) -> MVResult<Vec<AccessPath>> {
+    let options = analyzer_options(&window, options).await;
+    let analysis = analyze(&window, &input, &options).await?;
+
+    Ok(access_paths(&analysis.stack, &analysis.heap, block)?)
+}
+
+/// Analyzes the source code and matches notes with the symbols and heap blocks they were attached to,
+/// so notes written before an edit stay next to what they were written about
+#[command]
+pub(crate) async fn cmd_place_notes(
+    window: WebviewWindow,
+    input: String,
+    notes: Vec<Note>,
+    options: Option<AnalyzerOptions>,
+) -> MVResult<Vec<PlacedNote>> {
+    let options = analyzer_options(&window, options).await;
+    let analysis = analyze(&window, &input, &options).await?;
+
+    Ok(place_notes(&notes, &analysis))
+}
+
+/// Previews what `statement` would change if it ran after the last statement of the source, for the
+/// editor to show what a line does while it is hovered, without remembering the placements it made
+#[command]
+pub(crate) async fn cmd_preview_statement(
+    window: WebviewWindow,
+    input: String,
+    statement: String,
+    options: Option<AnalyzerOptions>,
+) -> MVResult<StatementPreview> {
+    let options = analyzer_options(&window, options).await;
+    let starting_pointers = DesktopAnalyzerState {
+        state: &window.state::<Mutex<AppState>>(),
+        window: window.label(),
+    }
+    .get_starting_pointers()
+    .await;
+
+    Ok(preview_statement(&input, &statement, starting_pointers, options).await?)
+}
+
+/// Analyzes the source code once for every seed and reports how the placement of its heap blocks varies
+/// between them
+#[command]
+pub(crate) async fn cmd_compare_seeds(
+    window: WebviewWindow,
+    input: String,
+    seeds: Vec<u64>,
+    options: Option<AnalyzerOptions>,
+) -> MVResult<PlacementStatistics> {
+    let options = analyzer_options(&window, options).await;
+
+    Ok(compare_seeds(&input, &seeds, options).await?)
+}
+
+/// Runs the workload of an allocator exercise against the policy a student wrote
+#[command]
+pub(crate) async fn cmd_run_allocator_exercise(
+    window: WebviewWindow,
+    exercise: AllocatorExercise,
+    policy: String,
+    options: Option<AnalyzerOptions>,
+) -> MVResult<AllocatorReport> {
+    let options = analyzer_options(&window, options).await;
+
+    Ok(exercise.run(&policy, &options)?)
+}
+
+/// Lists the lessons in the app data directory
+#[command]
+pub(crate) async fn cmd_get_lessons(app_handle: AppHandle) -> MVResult<Vec<Lesson>> {
+    load_lessons(&app_handle, &app_handle.state::<AppPaths>())
+}
+
+/// Checks the source code against the steps of a lesson, code that fails to analyze is reported in the
+/// lesson instead of failing the command since lessons can expect errors
+#[command]
+pub(crate) async fn cmd_check_lesson(
+    window: WebviewWindow,
+    input: String,
+    lesson: Lesson,
+    options: Option<AnalyzerOptions>,
+) -> LessonReport {
+    let options = analyzer_options(&window, options).await;
+
+    match analyze(&window, &input, &options).await {
+        Ok(analysis) => lesson.check(&analysis.stack, &analysis.heap, None),
+        Err(e) => lesson.check(&[], &[], Some(&e)),
+    }
+}
+
+/// Grades the source code against an assessment, so students can check their work before handing it in
+#[command]
+pub(crate) async fn cmd_self_check(
+    window: WebviewWindow,
+    input: String,
+    assessment: Assessment,
+    options: Option<AnalyzerOptions>,
+) -> AssessmentReport {
+    let options = analyzer_options(&window, options).await;
+
+    match analyze(&window, &input, &options).await {
+        Ok(analysis) => assessment.check(&analysis.stack, &analysis.heap, None),
+        Err(e) => assessment.check(&[], &[], Some(&e)),
+    }
+}
+
+/// Exports the session encrypted for an instructor, for exams where solutions are handed in or passed
+/// around and shouldn't be readable by anyone else, along with the notes the student attached to the
+/// diagram
+#[command]
+pub(crate) async fn cmd_export_secure_session(
+    window: WebviewWindow,
+    input: String,
+    instructor_key: String,
+    notes: Option<Vec<Note>>,
+    options: Option<AnalyzerOptions>,
+) -> MVResult<String> {
+    let options = analyzer_options(&window, options).await;
+
+    // analyzing first validates the source and brings the remembered heap placements up to date
+    analyze(&window, &input, &options).await?;
+
+    let starting_pointers = DesktopAnalyzerState {
+        state: &window.state::<Mutex<AppState>>(),
+        window: window.label(),
+    }
+    .get_starting_pointers()
+    .await;
+
+    let mut session = Session::new(input, starting_pointers, options);
+    session.notes = notes.unwrap_or_default();
+
+    seal_session(&session, &instructor_key)
+}
+
+/// Decrypts a session exported with [cmd_export_secure_session] using the instructor's secret key
+#[command]
+pub(crate) async fn cmd_open_secure_session(
+    sealed: String,
+    secret_key: String,
+) -> MVResult<Session> {
+    open_session(&sealed, &secret_key)
+}
+
+/// Generates a key pair for encrypting exam sessions
+#[command]
+pub(crate) async fn cmd_generate_exam_keys() -> ExamKeys {
+    generate_exam_keys()
+}
+
+/// Exports the web playground so it can be hosted without internet access, into the downloads folder
+/// unless a folder is given
+#[command]
+pub(crate) async fn cmd_export_offline_playground(
+    app_handle: AppHandle,
+    dir: Option<String>,
+) -> MVResult<String> {
+    let dir = match dir {
+        Some(dir) => PathBuf::from(dir),
+        None => app_handle.path().download_dir()?.join(DEFAULT_PLAYGROUND_DIR_NAME),
+    };
+
+    let page = export_offline_playground(&app_handle, &dir)?;
+
+    Ok(page.to_string_lossy().to_string())
+}
+
+/// Gets the license of MV along with the crates compiled into the app and their licenses, for the
+/// acknowledgments of the About window
+#[command]
+pub(crate) async fn cmd_get_third_party_licenses() -> MVResult<Licenses> {
+    licenses()
+}
+
 #[command]
 pub(crate) async fn cmd_get_system_fonts() -> MVResult<Vec<String>> {
     let mut fonts = Vec::<String>::new();
@@ -154,3 +749,40 @@ pub(crate) async fn cmd_open_url(url: String) -> MVResult<()> {
     webbrowser::open(&url)?;
     Ok(())
 }
+
+#[command]
+pub(crate) async fn cmd_resolve_close_request(
+    app_handle: AppHandle,
+    request_id: u64,
+    has_unsaved_documents: bool,
+) -> MVResult<bool> {
+    Ok(app_handle.state::<CloseHandshake>().resolve(request_id, has_unsaved_documents))
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OpenedFile {
+    path: String,
+    source_code: String,
+}
+
+/// Returns the file the window was opened with from the command line, if any
+///
+/// The next analyses of the window expand the macros the file is compiled with when its project has a
+/// `compile_commands.json`
+#[command]
+pub(crate) async fn cmd_take_opened_file(window: WebviewWindow) -> MVResult<Option<OpenedFile>> {
+    let opened_files = window.app_handle().state::<OpenedFiles>();
+
+    let Some(path) = opened_files.take(window.label()) else {
+        return Ok(None);
+    };
+
+    let source_code = std::fs::read_to_string(&path)?;
+    opened_files.set_defines(window.label(), project_defines(&path, &source_code));
+
+    Ok(Some(OpenedFile {
+        source_code,
+        path: path.to_string_lossy().to_string(),
+    }))
+}
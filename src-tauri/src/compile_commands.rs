@@ -0,0 +1,263 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use indexmap::IndexMap;
+use log::{info, warn};
+use mv_core::preprocess::object_macros;
+
+/// Where a project's `compile_commands.json` is looked for, in each directory above the opened file or
+/// workspace
+const DATABASE_LOCATIONS: [&str; 2] = ["compile_commands.json", "build/compile_commands.json"];
+
+/// How many headers deep the includes of an opened file are followed
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// An entry of a `compile_commands.json`, how one file of the project is compiled
+///
+/// The compiler is either given as `arguments` or as a single `command` line
+#[derive(Debug, serde::Deserialize)]
+struct CompileCommand {
+    directory: PathBuf,
+    file: PathBuf,
+    #[serde(default)]
+    arguments: Option<Vec<String>>,
+    #[serde(default)]
+    command: Option<String>,
+}
+
+/// The flags of a compile command the preprocessor cares about
+#[derive(Debug, Default)]
+struct CompileFlags {
+    defines: IndexMap<String, String>,
+    include_dirs: Vec<PathBuf>,
+}
+
+/// Gets the macros a file is compiled with in its project, so a file pasted from a project with
+/// project-relative includes and macros is preprocessed like its compiler would
+///
+/// The `-D` flags of the file's entry in the project's `compile_commands.json` come first, then the
+/// object-like macros of the headers it includes, found next to the file or in the `-I` directories.
+/// A file without an entry, like a header, is given the flags of another file in its directory, or of
+/// the first file of the project
+///
+/// # Arguments
+/// - `file`: The path of the opened file
+/// - `source`: The source code of the file
+///
+/// # Returns
+/// - `IndexMap<String, String>`: The value of each macro by name, empty if the file isn't part of a
+///   project with a `compile_commands.json`
+pub(crate) fn project_defines(file: &Path, source: &str) -> IndexMap<String, String> {
+    let Some(database) = find_database(file) else {
+        return IndexMap::new();
+    };
+
+    let Some(commands) = read_database(&database) else {
+        return IndexMap::new();
+    };
+
+    let Some(command) = command_for(&commands, file) else {
+        return IndexMap::new();
+    };
+
+    let mut flags = compile_flags(command);
+    let mut visited = HashSet::new();
+
+    if let Some(directory) = file.parent() {
+        header_defines(source, directory, &mut flags, &mut visited, 0);
+    }
+
+    info!(
+        "Using {} macros from {} for {}",
+        flags.defines.len(),
+        database.display(),
+        file.display()
+    );
+
+    flags.defines
+}
+
+/// Gets the macros the files of a workspace are compiled with, so a file pasted from the project into
+/// a window opened on the workspace is preprocessed like its compiler would
+///
+/// The `-D` flags of every entry of the project's `compile_commands.json` are merged, along with the
+/// object-like macros of the headers each entry's file includes, found next to the file or in the `-I`
+/// directories of its entry
+///
+/// # Arguments
+/// - `workspace`: The path of the opened directory
+///
+/// # Returns
+/// - `IndexMap<String, String>`: The value of each macro by name, empty if the directory isn't part of a
+///   project with a `compile_commands.json`
+pub(crate) fn workspace_defines(workspace: &Path) -> IndexMap<String, String> {
+    let Some(database) = find_database_above(workspace) else {
+        return IndexMap::new();
+    };
+
+    let Some(commands) = read_database(&database) else {
+        return IndexMap::new();
+    };
+
+    let mut defines = IndexMap::new();
+    let mut visited = HashSet::new();
+
+    for command in &commands {
+        let file = command.directory.join(&command.file);
+        let mut flags = compile_flags(command);
+
+        if let (Ok(source), Some(directory)) = (fs::read_to_string(&file), file.parent()) {
+            header_defines(&source, directory, &mut flags, &mut visited, 0);
+        }
+
+        defines.extend(flags.defines);
+    }
+
+    info!(
+        "Using {} macros from {} for the workspace {}",
+        defines.len(),
+        database.display(),
+        workspace.display()
+    );
+
+    defines
+}
+
+fn find_database(file: &Path) -> Option<PathBuf> {
+    find_database_above(file.parent()?)
+}
+
+fn find_database_above(directory: &Path) -> Option<PathBuf> {
+    directory.ancestors().find_map(|directory| {
+        DATABASE_LOCATIONS
+            .iter()
+            .map(|location| directory.join(location))
+            .find(|database| database.is_file())
+    })
+}
+
+fn read_database(database: &Path) -> Option<Vec<CompileCommand>> {
+    match fs::read_to_string(database).map(|json| serde_json::from_str(&json)) {
+        Ok(Ok(commands)) => Some(commands),
+        Ok(Err(e)) => {
+            warn!("Failed to parse {}: {e}", database.display());
+            None
+        }
+        Err(e) => {
+            warn!("Failed to read {}: {e}", database.display());
+            None
+        }
+    }
+}
+
+/// Gets the entry of the file, or of another file in its directory if it has none
+fn command_for<'a>(commands: &'a [CompileCommand], file: &Path) -> Option<&'a CompileCommand> {
+    let file = canonical(file);
+    let entry_file = |command: &CompileCommand| canonical(&command.directory.join(&command.file));
+
+    commands
+        .iter()
+        .find(|command| entry_file(command) == file)
+        .or_else(|| commands.iter().find(|command| entry_file(command).parent() == file.parent()))
+        .or_else(|| commands.first())
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Reads the defines and include directories from the arguments of a compile command
+///
+/// A `command` line is split on whitespace, arguments quoted to hold spaces aren't kept together
+fn compile_flags(command: &CompileCommand) -> CompileFlags {
+    let arguments: Vec<String> = match (&command.arguments, &command.command) {
+        (Some(arguments), _) => arguments.clone(),
+        (None, Some(line)) => line.split_whitespace().map(str::to_owned).collect(),
+        (None, None) => Vec::new(),
+    };
+
+    let mut flags = CompileFlags::default();
+    let mut arguments = arguments.iter();
+
+    while let Some(argument) = arguments.next() {
+        // flags are written either joined to their value like `-DNAME` or apart from it like `-D NAME`
+        let mut value = |flag: &str| match argument.strip_prefix(flag) {
+            Some("") => arguments.next().cloned(),
+            Some(value) => Some(value.to_owned()),
+            None => None,
+        };
+
+        if let Some(define) = value("-D") {
+            // `-DNAME` defines the macro as `1` like compilers do
+            let (name, value) = define.split_once('=').unwrap_or((&define, "1"));
+            flags.defines.insert(name.to_owned(), value.to_owned());
+        } else if let Some(directory) =
+            value("-I").or_else(|| value("-iquote")).or_else(|| value("-isystem"))
+        {
+            flags.include_dirs.push(command.directory.join(directory));
+        }
+    }
+
+    flags
+}
+
+/// Adds the object-like macros of the headers a source includes, and of the headers they include
+fn header_defines(
+    source: &str,
+    directory: &Path,
+    flags: &mut CompileFlags,
+    visited: &mut HashSet<PathBuf>,
+    depth: usize,
+) {
+    if depth == MAX_INCLUDE_DEPTH {
+        return;
+    }
+
+    for header in included_headers(source) {
+        let Some(path) = std::iter::once(directory)
+            .chain(flags.include_dirs.iter().map(PathBuf::as_path))
+            .map(|directory| directory.join(&header))
+            .find(|path| path.is_file())
+        else {
+            // standard headers aren't part of the project
+            continue;
+        };
+
+        let path = canonical(&path);
+
+        if !visited.insert(path.clone()) {
+            continue;
+        }
+
+        let Ok(header_source) = fs::read_to_string(&path) else {
+            continue;
+        };
+
+        flags.defines.extend(object_macros(&header_source));
+
+        if let Some(header_directory) = path.parent() {
+            header_defines(&header_source, header_directory, flags, visited, depth + 1);
+        }
+    }
+}
+
+/// Gets the names of the headers a source includes, like `util.h` in `#include "util.h"`
+fn included_headers(source: &str) -> Vec<String> {
+    source
+        .lines()
+        .filter_map(|line| {
+            let directive = line.trim_start().strip_prefix('#')?.trim_start();
+            let header = directive.strip_prefix("include")?.trim();
+
+            let (open, close) = match header.chars().next()? {
+                '"' => ('"', '"'),
+                '<' => ('<', '>'),
+                _ => return None,
+            };
+
+            let header = header.strip_prefix(open)?;
+            Some(header[..header.find(close)?].to_owned())
+        })
+        .collect()
+}
@@ -4,8 +4,11 @@ use indexmap::IndexMap;
 use mv_core::analyzer::AnalyzerState;
 use tokio::sync::Mutex;
 
+/// The heap placements of the analyses of one window, which are kept apart from those of other windows
+/// so analyzing a program in one window doesn't move the blocks of another
 pub(crate) struct DesktopAnalyzerState<'a> {
     pub state: &'a Mutex<AppState>,
+    pub window: &'a str,
 }
 
 #[async_trait]
@@ -14,11 +17,11 @@ impl<'a> AnalyzerState for DesktopAnalyzerState<'a> {
         let state = self.state.lock().await;
         let pointers_guard = state.starting_pointers.lock().await;
 
-        pointers_guard.clone().unwrap_or_default()
+        pointers_guard.get(self.window).cloned().unwrap_or_default()
     }
 
     async fn set_starting_pointers(&mut self, pointers: IndexMap<String, usize>) {
         let state = self.state.lock().await;
-        *state.starting_pointers.lock().await = Some(pointers);
+        state.starting_pointers.lock().await.insert(self.window.to_owned(), pointers);
     }
 }
@@ -13,6 +13,9 @@ pub(crate) enum Error {
     #[error("JSON error: {0}")]
     JsonError(#[from] serde_json::error::Error),
 
+    #[error("{0}")]
+    CoreError(#[from] mv_core::error::Error),
+
     #[error("IO Error: {0}")]
     WebbrowserError(#[from] std::io::Error),
 
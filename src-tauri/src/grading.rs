@@ -0,0 +1,24 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use mv_core::analyzer::AnalyzerOptions;
+use mv_core::assessment::Assessment;
+
+use crate::error::Result as MVResult;
+use crate::headless::report_files;
+
+/// Grades every file against the assessment and writes one report per line to the output, for instructors grading
+/// submissions from the command line, e.g. `mv --grade assessment.json submissions/*.cpp`
+pub(crate) fn grade_files(
+    assessment: &Path,
+    files: &[impl AsRef<Path>],
+    options: &AnalyzerOptions,
+    output: &mut impl Write,
+) -> MVResult<()> {
+    let assessment = Assessment::from_json(&fs::read_to_string(assessment)?)?;
+
+    report_files(files, output, |_, source| {
+        Ok(tauri::async_runtime::block_on(assessment.grade(source, options.clone())))
+    })
+}
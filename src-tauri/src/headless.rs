@@ -0,0 +1,145 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use mv_core::analyzer::{AnalysisError, AnalysisErrorDetails, AnalyzerOptions};
+use mv_core::error::Result as CoreResult;
+use serde::Serialize;
+
+use crate::cli::{CliArgs, HeadlessMode};
+use crate::error::Result as MVResult;
+use crate::{batch, grading, statistics};
+
+/// The result of one file of a batch, a grade report, the statistics of its placements or its
+/// analysis, or the error that stopped it, e.g. `{"file": "a.cpp", "result": {...}}` or
+/// `{"file": "b.cpp", "error": {"message": "...", "line_number": 3, "column_number": 5}}`
+#[derive(Debug, Serialize)]
+pub(crate) struct FileResult<T> {
+    pub(crate) file: String,
+    #[serde(flatten)]
+    pub(crate) outcome: FileOutcome<T>,
+}
+
+/// Whether a file of a batch went through
+///
+/// - `Result`: What the mode made of the file
+/// - `Error`: Why the file couldn't be read or went wrong, with the line and column the error points
+///   at if it is tied to the source
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum FileOutcome<T> {
+    Result(T),
+    Error(AnalysisErrorDetails),
+}
+
+impl<T> FileResult<T> {
+    pub(crate) fn new(file: impl Into<String>, result: CoreResult<T>) -> Self {
+        let outcome = match result {
+            Ok(result) => FileOutcome::Result(result),
+            Err(e) => FileOutcome::Error(AnalysisError::from(&e).error),
+        };
+
+        FileResult {
+            file: file.into(),
+            outcome,
+        }
+    }
+}
+
+/// Runs the files the app was launched with through the mode it was launched in instead of opening a
+/// window, with the options passed on the command line
+///
+/// What the mode prints goes to the `--output` file if one was passed, to the console otherwise
+///
+/// Returns the code the app exits with, non-zero when the mode couldn't run at all
+pub(crate) fn run(mode: &HeadlessMode, cli_args: &CliArgs) -> i32 {
+    attach_console();
+
+    let mut options = AnalyzerOptions::default();
+    cli_args.apply(&mut options);
+
+    let result = open_output(cli_args).and_then(|mut output| {
+        match mode {
+            HeadlessMode::Grade(assessment) => {
+                grading::grade_files(assessment, &cli_args.files, &options, &mut output)?
+            }
+            HeadlessMode::CompareSeeds(count) => {
+                statistics::compare_files(&cli_args.files, *count, &options, &mut output)?
+            }
+            HeadlessMode::Batch => batch::analyze_files(&cli_args.files, &options, &mut output)?,
+            HeadlessMode::Ascii => batch::render_files(&cli_args.files, &options, &mut output)?,
+        }
+
+        Ok(output.flush()?)
+    });
+
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("{e}");
+            1
+        }
+    }
+}
+
+/// Connects the app to the console it was launched from, so what it prints shows up there
+///
+/// Release builds on Windows are GUI apps, which start without a console and drop everything they
+/// print. The console of the parent process is attached if it has one, a new console is opened
+/// otherwise, like when the app is launched from a shortcut. Elsewhere the app always prints to the
+/// terminal it was launched from
+pub(crate) fn attach_console() {
+    #[cfg(windows)]
+    {
+        const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+        unsafe extern "system" {
+            fn AttachConsole(process_id: u32) -> i32;
+            fn AllocConsole() -> i32;
+        }
+
+        // SAFETY: both only change which console the process is attached to, and fail harmlessly if
+        // it already has one
+        unsafe {
+            if AttachConsole(ATTACH_PARENT_PROCESS) == 0 {
+                AllocConsole();
+            }
+        }
+    }
+}
+
+/// Opens where a headless mode prints its lines, the `--output` file if one was passed, replacing
+/// what it held, or the standard output
+fn open_output(cli_args: &CliArgs) -> MVResult<Box<dyn Write>> {
+    match &cli_args.output {
+        Some(path) => {
+            let file = File::create(path)
+                .map_err(|e| format!("Can't write to {}: {e}", path.to_string_lossy()))?;
+
+            Ok(Box::new(BufWriter::new(file)))
+        }
+        None => Ok(Box::new(io::stdout().lock())),
+    }
+}
+
+/// Reads every file and writes one [FileResult] per line to the output, what `process` makes of its
+/// source or the error that stopped it. Files that can't be read or processed are reported instead of
+/// stopping the whole batch
+pub(crate) fn report_files<T: Serialize>(
+    files: &[impl AsRef<Path>],
+    output: &mut impl Write,
+    mut process: impl FnMut(&Path, &str) -> CoreResult<T>,
+) -> MVResult<()> {
+    for file in files {
+        let file = file.as_ref();
+
+        let result = fs::read_to_string(file)
+            .map_err(|e| e.to_string().into())
+            .and_then(|source| process(file, &source));
+
+        serde_json::to_writer(&mut *output, &FileResult::new(file.to_string_lossy(), result))?;
+        writeln!(output)?;
+    }
+
+    Ok(())
+}
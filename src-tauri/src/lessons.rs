@@ -0,0 +1,51 @@
+use std::fs;
+use std::path::Path;
+
+use log::{info, warn};
+use mv_core::lesson::Lesson;
+use tauri::{AppHandle, Runtime};
+
+use crate::error::Result as MVResult;
+use crate::paths::AppPaths;
+
+/// Folder in the app data directory that lessons are loaded from, one JSON file per lesson
+const LESSONS_DIR_NAME: &str = "lessons";
+
+/// Loads every lesson in the lessons folder, sorted by title
+///
+/// Files that aren't valid lessons are skipped with a warning, so one broken lesson doesn't hide the
+/// others. A missing folder means there are no lessons
+pub(crate) fn load_lessons<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    paths: &AppPaths,
+) -> MVResult<Vec<Lesson>> {
+    let dir = paths.data_dir(app_handle)?.join(LESSONS_DIR_NAME);
+
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut lessons = Vec::new();
+
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+
+        if path.extension().is_none_or(|extension| extension != "json") {
+            continue;
+        }
+
+        match load_lesson(&path) {
+            Ok(lesson) => lessons.push(lesson),
+            Err(e) => warn!("Skipping lesson {}: {}", path.display(), e),
+        }
+    }
+
+    lessons.sort_by(|a, b| a.title.cmp(&b.title));
+
+    info!("Loaded {} lessons from {}", lessons.len(), dir.display());
+    Ok(lessons)
+}
+
+fn load_lesson(path: &Path) -> MVResult<Lesson> {
+    Ok(Lesson::from_json(&fs::read_to_string(path)?)?)
+}
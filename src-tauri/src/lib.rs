@@ -1,31 +1,113 @@
+mod analysis_cache;
+mod analysis_history;
+mod batch;
+mod cli;
+mod close_handshake;
 mod commands;
+mod compile_commands;
 mod desktop_analyzer_state;
 mod error;
+mod grading;
+mod headless;
+mod lessons;
+mod licenses;
+mod live_updates;
+mod memory_edits;
+mod network;
+mod notifications;
+mod open_file;
+mod paths;
+mod playground;
+mod secure_session;
+mod settings;
+mod statistics;
 mod updates;
-mod utils;
 mod window;
 
+use std::path::Path;
+
 use indexmap::IndexMap;
 use log::{error, info, warn};
+use mv_core::analyzer::AnalyzerOptions;
 
 use tauri::{App, Emitter, Manager, RunEvent, State, WindowEvent};
 use tauri_plugin_window_state::{AppHandleExt, StateFlags};
 use tokio::sync::Mutex;
 
+use crate::analysis_cache::AnalysisCache;
+use crate::analysis_history::AnalysisHistory;
+use crate::cli::CliArgs;
+use crate::close_handshake::CloseHandshake;
 use crate::commands::{
-    cmd_analyze_source_code, cmd_check_for_updates, cmd_download_and_install_update,
-    cmd_get_system_fonts, cmd_metadata, cmd_open_url,
+    cmd_access_paths, cmd_analyze_batch, cmd_analyze_live, cmd_analyze_source_code,
+    cmd_analyze_source_code_streamed, cmd_check_for_updates, cmd_check_lesson,
+    cmd_clear_analysis_cache, cmd_close_live_document, cmd_compare_seeds,
+    cmd_compute_struct_layout, cmd_download_and_install_update, cmd_export_ascii_diagram,
+    cmd_export_embed_snippet, cmd_export_offline_playground, cmd_export_secure_session,
+    cmd_free_block, cmd_generate_exam_keys, cmd_get_lessons, cmd_get_previous_result,
+    cmd_get_settings, cmd_get_system_fonts, cmd_get_third_party_licenses, cmd_metadata,
+    cmd_open_secure_session, cmd_open_url, cmd_place_notes, cmd_preview_statement,
+    cmd_query_allocation, cmd_reroll_layout, cmd_resolve_close_request, cmd_run_allocator_exercise,
+    cmd_self_check, cmd_set_automatic_update_checks, cmd_set_layout_hint, cmd_set_variable_value,
+    cmd_take_opened_file,
 };
+use crate::live_updates::LiveDocuments;
+use crate::memory_edits::MemoryEdits;
+use crate::open_file::OpenedFiles;
+use crate::paths::AppPaths;
+use crate::settings::AppSettings;
 use crate::updates::MVUpdater;
 
 #[derive(Default)]
 pub(crate) struct AppState {
-    pub starting_pointers: Mutex<Option<IndexMap<String, usize>>>,
+    pub starting_pointers: Mutex<IndexMap<String, IndexMap<String, usize>>>,
+    pub analyzer_options: AnalyzerOptions,
+    pub analysis_history: AnalysisHistory,
+    pub analysis_cache: AnalysisCache,
+    pub memory_edits: MemoryEdits,
+    pub live_documents: LiveDocuments,
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Parsed before the app is built so the options are in place before the first window loads
+    let cli_args = match CliArgs::parse(
+        &std::env::args().collect::<Vec<String>>(),
+        &std::env::current_dir().unwrap_or_default(),
+    ) {
+        Ok(cli_args) => cli_args,
+        Err(e) => {
+            headless::attach_console();
+            eprintln!("Invalid command line arguments: {e}");
+            std::process::exit(2);
+        }
+    };
+    // grading, comparing seeds, analyzing a batch and drawing diagrams run headless, without building
+    // the app
+    if let Some(mode) = &cli_args.headless {
+        std::process::exit(headless::run(mode, &cli_args));
+    }
+
+    let mut launch_files = cli_args.files.clone();
+    let paths = AppPaths::resolve(cli_args.portable);
+
     tauri::Builder::default()
+        // Has to be registered first so a second instance exits before setting anything else up
+        .plugin(tauri_plugin_single_instance::init(|app_handle, args, cwd| {
+            info!("Forwarded launch from a second instance: {:?}", args);
+
+            let mut cli_args = match CliArgs::parse(&args, Path::new(&cwd)) {
+                Ok(cli_args) => cli_args,
+                Err(e) => {
+                    error!("Invalid arguments from a second instance: {e}");
+                    return;
+                }
+            };
+            let files = std::mem::take(&mut cli_args.files);
+
+            // The options only apply to the windows this launch opens, the other windows keep theirs
+            open_file::open_files(app_handle, files, Some(cli_args));
+        }))
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_os::init())
         .plugin({
@@ -34,18 +116,47 @@ pub fn run() {
             #[cfg(not(debug_assertions))]
             let log_level = log::LevelFilter::Error;
 
-            tauri_plugin_log::Builder::new()
-                .target(tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::LogDir {
+            let log_target = match paths.portable_log_dir() {
+                Some(path) => tauri_plugin_log::TargetKind::Folder {
+                    path,
                     file_name: Some("log".into()),
-                }))
+                },
+                None => tauri_plugin_log::TargetKind::LogDir {
+                    file_name: Some("log".into()),
+                },
+            };
+
+            tauri_plugin_log::Builder::new()
+                .target(tauri_plugin_log::Target::new(log_target))
                 .rotation_strategy(tauri_plugin_log::RotationStrategy::KeepSome(5))
                 .level(log_level)
                 .build()
         })
-        .plugin(tauri_plugin_window_state::Builder::new().build())
-        .setup(|app: &mut App| {
-            app.manage(Mutex::new(MVUpdater::new()));
-            app.manage(Mutex::new(AppState::default()));
+        .plugin({
+            let mut builder = tauri_plugin_window_state::Builder::new();
+
+            // The plugin joins the file name onto the app config directory, an absolute path replaces it
+            if let Some(file) = paths.window_state_file() {
+                builder = builder.with_filename(file.to_string_lossy());
+            }
+
+            builder.build()
+        })
+        .setup(move |app: &mut App| {
+            let mut app_state = AppState::default();
+
+            cli_args.apply(&mut app_state.analyzer_options);
+
+            let portable = paths.is_portable();
+            app.manage(paths);
+
+            let settings = AppSettings::load(app.handle());
+
+            app.manage(Mutex::new(MVUpdater::new(settings.automatic_update_checks, portable)));
+            app.manage(Mutex::new(settings));
+            app.manage(Mutex::new(app_state));
+            app.manage(CloseHandshake::default());
+            app.manage(OpenedFiles::default());
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -53,19 +164,46 @@ pub fn run() {
             cmd_check_for_updates,
             cmd_download_and_install_update,
             cmd_analyze_source_code,
+            cmd_analyze_source_code_streamed,
+            cmd_analyze_live,
+            cmd_close_live_document,
+            cmd_analyze_batch,
+            cmd_clear_analysis_cache,
+            cmd_query_allocation,
+            cmd_compute_struct_layout,
+            cmd_access_paths,
+            cmd_place_notes,
+            cmd_preview_statement,
+            cmd_compare_seeds,
+            cmd_run_allocator_exercise,
+            cmd_reroll_layout,
+            cmd_get_previous_result,
+            cmd_set_variable_value,
+            cmd_free_block,
+            cmd_export_ascii_diagram,
+            cmd_export_embed_snippet,
+            cmd_export_offline_playground,
             cmd_get_system_fonts,
-            cmd_open_url
+            cmd_get_third_party_licenses,
+            cmd_open_url,
+            cmd_resolve_close_request,
+            cmd_get_settings,
+            cmd_set_automatic_update_checks,
+            cmd_set_layout_hint,
+            cmd_take_opened_file,
+            cmd_get_lessons,
+            cmd_check_lesson,
+            cmd_self_check,
+            cmd_export_secure_session,
+            cmd_open_secure_session,
+            cmd_generate_exam_keys
         ])
         .build(tauri::generate_context!())
         .expect("error while running tauri application")
-        .run(|app_handle, event| {
+        .run(move |app_handle, event| {
             match event {
                 RunEvent::Ready => {
-                    window::create_main_window(
-                        &app_handle,
-                        "/",
-                        Some((window::DEFAULT_WINDOW_WIDTH, window::DEFAULT_WINDOW_HEIGHT)),
-                    );
+                    open_file::open_files(app_handle, std::mem::take(&mut launch_files), None);
                 }
 
                 RunEvent::WindowEvent {
@@ -78,17 +216,15 @@ pub fn run() {
                         tauri::async_runtime::spawn(async move {
                             let val: State<'_, Mutex<MVUpdater>> = h.state();
 
-                            if !val.lock().await.is_update_check_due() {
-                                return;
-                            }
-
-                            let update_available = match val.lock().await.check_now(&w).await {
-                                Ok(res) => res,
-                                Err(e) => {
-                                    error!("Error checking for updates: {}", e);
-                                    return;
-                                }
-                            };
+                            let update_available =
+                                match MVUpdater::check_automatically(&val, &w).await {
+                                    Ok(Some(res)) => res,
+                                    Ok(None) => return,
+                                    Err(e) => {
+                                        error!("Error checking for updates: {}", e);
+                                        return;
+                                    }
+                                };
 
                             if let Err(e) = w.emit("update-available", update_available) {
                                 error!("Failed to emit update-available event: {}", e);
@@ -100,10 +236,18 @@ pub fn run() {
                 }
 
                 RunEvent::WindowEvent {
-                    event: WindowEvent::CloseRequested { .. },
+                    event: WindowEvent::CloseRequested { api, .. },
                     label,
                     ..
                 } => {
+                    // Let the frontend veto the close if it has unsaved documents, the window is
+                    // closed again once it confirms it can go
+                    if !app_handle.state::<CloseHandshake>().take_approval(&label) {
+                        api.prevent_close();
+                        close_handshake::request_close(app_handle, label);
+                        return;
+                    }
+
                     if !label.starts_with(window::OTHER_WINDOW_PREFIX)
                         && !(app_handle.webview_windows().len() > 1)
                     {
@@ -116,6 +260,25 @@ pub fn run() {
                         info!("Skipping window state save for label: {}", label);
                     }
                 }
+
+                RunEvent::WindowEvent {
+                    event: WindowEvent::Destroyed,
+                    label,
+                    ..
+                } => {
+                    app_handle.state::<OpenedFiles>().forget(&label);
+
+                    let h = app_handle.clone();
+                    tauri::async_runtime::spawn(async move {
+                        let val: State<'_, Mutex<AppState>> = h.state();
+                        let mut state = val.lock().await;
+                        state.analysis_history.forget(&label);
+                        state.analysis_cache.forget(&label);
+                        state.starting_pointers.lock().await.shift_remove(&label);
+                        state.memory_edits.forget(&label);
+                        state.live_documents.forget(&label);
+                    });
+                }
                 _ => {}
             };
         })
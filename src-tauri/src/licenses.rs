@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result as MVResult;
+
+/// The license MV is distributed under
+const LICENSE: &str = include_str!("../../LICENSE");
+
+/// The crates compiled into the app, written by the build script
+const THIRD_PARTY_CRATES: &str =
+    include_str!(concat!(env!("OUT_DIR"), "/third_party_licenses.json"));
+
+/// A crate compiled into the app, with the license it is distributed under as an SPDX expression
+/// like `MIT OR Apache-2.0`, missing for crates that only ship a license file
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThirdPartyCrate {
+    pub name: String,
+    pub version: String,
+    pub license: Option<String>,
+    pub authors: Vec<String>,
+    pub repository: Option<String>,
+}
+
+/// Everything the acknowledgments of the About window show
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Licenses {
+    pub license: String,
+    pub third_party: Vec<ThirdPartyCrate>,
+}
+
+/// Gets the license of MV and the crates compiled into the app, sorted by name and version
+pub(crate) fn licenses() -> MVResult<Licenses> {
+    Ok(Licenses {
+        license: LICENSE.to_string(),
+        third_party: serde_json::from_str(THIRD_PARTY_CRATES)?,
+    })
+}
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// The last response sent for each document being typed into, by the label of its window and the name
+/// of the document, so the analysis after a keystroke only sends the records that changed since
+#[derive(Default)]
+pub(crate) struct LiveDocuments {
+    responses: HashMap<(String, String), Value>,
+}
+
+/// Represents what is sent for a document after it was analyzed
+///
+/// - `Full`: The whole response, the first time the document is analyzed
+/// - `Changes`: The records of the response that changed since the last one sent, to apply in order
+#[derive(Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub(crate) enum LiveUpdate {
+    Full { response: Value },
+    Changes { changes: Vec<RecordChange> },
+}
+
+/// Represents a change to the last response sent, at a JSON pointer like `/heap/3`
+///
+/// Every element of a list is a record, like a symbol of the stack or a block of the heap, and is sent
+/// whole when anything in it changed
+///
+/// - `Set`: The value at `path` is now `value`, a record that is new or changed
+/// - `Remove`: The field at `path` is gone
+/// - `Truncate`: The list at `path` now ends after `length` records
+#[derive(Clone, Serialize)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub(crate) enum RecordChange {
+    Set { path: String, value: Value },
+    Remove { path: String },
+    Truncate { path: String, length: usize },
+}
+
+impl LiveDocuments {
+    /// Keeps the response of the document and gets what to send for it
+    pub(crate) fn update(&mut self, label: &str, document: &str, response: Value) -> LiveUpdate {
+        let key = (label.to_string(), document.to_string());
+
+        let update = match self.responses.get(&key) {
+            Some(previous) => {
+                let mut changes = Vec::new();
+                diff("", previous, &response, &mut changes);
+
+                LiveUpdate::Changes { changes }
+            }
+            None => LiveUpdate::Full {
+                response: response.clone(),
+            },
+        };
+
+        self.responses.insert(key, response);
+
+        update
+    }
+
+    /// Forgets the response of a document, once it is closed, so opening it again sends it whole
+    pub(crate) fn close(&mut self, label: &str, document: &str) {
+        self.responses.remove(&(label.to_string(), document.to_string()));
+    }
+
+    /// Forgets the responses of every document of the window, once the window is closed
+    pub(crate) fn forget(&mut self, label: &str) {
+        self.responses.retain(|(window, _), _| window != label);
+    }
+}
+
+/// Finds the changes that turn `previous` into `next`, both at `path`
+fn diff(path: &str, previous: &Value, next: &Value, changes: &mut Vec<RecordChange>) {
+    match (previous, next) {
+        (Value::Object(previous), Value::Object(next)) => {
+            for key in previous.keys().filter(|key| !next.contains_key(*key)) {
+                changes.push(RecordChange::Remove {
+                    path: format!("{}/{}", path, pointer_segment(key)),
+                });
+            }
+
+            for (key, value) in next {
+                let path = format!("{}/{}", path, pointer_segment(key));
+
+                match previous.get(key) {
+                    Some(previous) => diff(&path, previous, value, changes),
+                    None => changes.push(RecordChange::Set {
+                        path,
+                        value: value.clone(),
+                    }),
+                }
+            }
+        }
+
+        (Value::Array(previous), Value::Array(next)) => {
+            if next.len() < previous.len() {
+                changes.push(RecordChange::Truncate {
+                    path: path.to_string(),
+                    length: next.len(),
+                });
+            }
+
+            for (index, value) in next.iter().enumerate() {
+                if previous.get(index) != Some(value) {
+                    changes.push(RecordChange::Set {
+                        path: format!("{}/{}", path, index),
+                        value: value.clone(),
+                    });
+                }
+            }
+        }
+
+        (previous, next) if previous != next => changes.push(RecordChange::Set {
+            path: path.to_string(),
+            value: next.clone(),
+        }),
+
+        _ => {}
+    }
+}
+
+/// Escapes a field name for a JSON pointer, where `~` and `/` have a meaning of their own
+fn pointer_segment(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
@@ -0,0 +1,56 @@
+use std::collections::HashMap;
+
+use mv_core::analyzer::{AnalysisResult, AnalyzerOptions};
+
+/// The analysis each window last edited the memory of, by the label of the window, so edits made one
+/// after another during a demo build on each other instead of starting over from the source
+///
+/// An edit of a source that was changed or analyzed with other options since starts from a new
+/// analysis, the edited memory belonged to the program before the change
+#[derive(Default)]
+pub(crate) struct MemoryEdits {
+    analyses: HashMap<String, EditedAnalysis>,
+}
+
+struct EditedAnalysis {
+    input: String,
+    options: AnalyzerOptions,
+    analysis: AnalysisResult,
+}
+
+impl MemoryEdits {
+    /// Takes the edited analysis of the window out, if it was made from the same source and options
+    pub(crate) fn take(
+        &mut self,
+        label: &str,
+        input: &str,
+        options: &AnalyzerOptions,
+    ) -> Option<AnalysisResult> {
+        let edited = self.analyses.remove(label)?;
+
+        (edited.input == input && edited.options == *options).then_some(edited.analysis)
+    }
+
+    /// Keeps the edited analysis of the window, for the next edit to build on
+    pub(crate) fn keep(
+        &mut self,
+        label: &str,
+        input: String,
+        options: AnalyzerOptions,
+        analysis: AnalysisResult,
+    ) {
+        self.analyses.insert(
+            label.to_string(),
+            EditedAnalysis {
+                input,
+                options,
+                analysis,
+            },
+        );
+    }
+
+    /// Forgets the edited analysis of the window, once the window is closed
+    pub(crate) fn forget(&mut self, label: &str) {
+        self.analyses.remove(label);
+    }
+}
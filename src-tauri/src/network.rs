@@ -0,0 +1,135 @@
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use log::info;
+
+/// Host that update checks talk to, used to probe whether the network is reachable
+const UPDATE_HOST: &str = "github.com:443";
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Checks whether the update host can be reached, without making an HTTP request
+pub(crate) async fn is_online() -> bool {
+    tauri::async_runtime::spawn_blocking(|| {
+        let Ok(addresses) = UPDATE_HOST.to_socket_addrs() else {
+            return false;
+        };
+
+        addresses
+            .into_iter()
+            .any(|address| TcpStream::connect_timeout(&address, CONNECT_TIMEOUT).is_ok())
+    })
+    .await
+    .unwrap_or(false)
+}
+
+/// Checks whether the current connection is metered, e.g. a mobile hotspot
+///
+/// Returns `false` when the platform can't tell, so update checks aren't blocked on systems without a
+/// way to detect it
+pub(crate) async fn is_metered() -> bool {
+    tauri::async_runtime::spawn_blocking(|| {
+        let metered = detect_metered_connection().unwrap_or(false);
+        info!("Metered connection: {metered}");
+        metered
+    })
+    .await
+    .unwrap_or(false)
+}
+
+#[cfg(target_os = "windows")]
+fn detect_metered_connection() -> Option<bool> {
+    use std::os::windows::process::CommandExt;
+
+    // Keeps a console window from flashing up while the query runs
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+    let output = std::process::Command::new("powershell")
+        .creation_flags(CREATE_NO_WINDOW)
+        .args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "[Windows.Networking.Connectivity.NetworkInformation,Windows.Networking.Connectivity,ContentType=WindowsRuntime]::GetInternetConnectionProfile().GetConnectionCost().NetworkCostType",
+        ])
+        .output()
+        .ok()?;
+
+    let cost_type = String::from_utf8_lossy(&output.stdout);
+    Some(matches!(cost_type.trim(), "Fixed" | "Variable"))
+}
+
+#[cfg(target_os = "linux")]
+fn detect_metered_connection() -> Option<bool> {
+    // NetworkManager reports `yes` or `yes (guessed)` for metered devices
+    let output = std::process::Command::new("nmcli")
+        .args(["-t", "-f", "GENERAL.METERED", "dev", "show"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .any(|line| line.trim_start_matches("GENERAL.METERED:").starts_with("yes")),
+    )
+}
+
+#[cfg(target_os = "macos")]
+fn detect_metered_connection() -> Option<bool> {
+    use std::ffi::c_void;
+    use std::sync::mpsc;
+
+    use block::{Block, ConcreteBlock};
+
+    type NwObject = *mut c_void;
+
+    #[link(name = "Network", kind = "framework")]
+    unsafe extern "C" {
+        fn nw_path_monitor_create() -> NwObject;
+        fn nw_path_monitor_set_queue(monitor: NwObject, queue: NwObject);
+        fn nw_path_monitor_set_update_handler(monitor: NwObject, handler: *const c_void);
+        fn nw_path_monitor_start(monitor: NwObject);
+        fn nw_path_monitor_cancel(monitor: NwObject);
+        fn nw_path_is_expensive(path: NwObject) -> bool;
+        fn nw_path_is_constrained(path: NwObject) -> bool;
+        fn nw_release(object: NwObject);
+    }
+
+    unsafe extern "C" {
+        fn dispatch_get_global_queue(identifier: isize, flags: usize) -> NwObject;
+    }
+
+    // The path of the connection is handed to the monitor's handler as soon as it starts. It's
+    // expensive on cellular and hotspot connections and constrained in Low Data Mode
+    let (sender, receiver) = mpsc::channel();
+    let handler = ConcreteBlock::new(move |path: NwObject| {
+        // SAFETY: the path is valid while the handler runs
+        let metered = unsafe { nw_path_is_expensive(path) || nw_path_is_constrained(path) };
+        let _ = sender.send(metered);
+    })
+    .copy();
+
+    // SAFETY: the monitor is created, started and released here, the handler is copied by the monitor
+    unsafe {
+        let monitor = nw_path_monitor_create();
+        nw_path_monitor_set_queue(monitor, dispatch_get_global_queue(0, 0));
+        let handler: &Block<(NwObject,), ()> = &handler;
+        nw_path_monitor_set_update_handler(monitor, handler as *const _ as *const c_void);
+        nw_path_monitor_start(monitor);
+
+        let metered = receiver.recv_timeout(CONNECT_TIMEOUT).ok();
+
+        nw_path_monitor_cancel(monitor);
+        nw_release(monitor);
+
+        metered
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+fn detect_metered_connection() -> Option<bool> {
+    None
+}
@@ -0,0 +1,87 @@
+use std::time::{Duration, Instant};
+
+use log::{info, warn};
+use notify_rust::{Notification, NotificationResponse};
+#[cfg(any(windows, target_os = "macos"))]
+use tauri::Manager;
+use tauri::{Runtime, UserAttentionType, WebviewWindow};
+
+/// Operations that finish faster than this don't notify, the user most likely hasn't moved on yet
+pub(crate) const LONG_OPERATION_THRESHOLD: Duration = Duration::from_secs(3);
+
+/// Shows a native notification for a finished operation if the window it belongs to is unfocused
+///
+/// Clicking the notification brings the window that finished the work to the front. The window also
+/// requests the user's attention, so its taskbar or dock entry flashes until it is focused
+///
+/// The notification is shown with `notify-rust` instead of the notification plugin, which doesn't report
+/// when one is clicked
+pub(crate) fn notify_if_unfocused<R: Runtime>(window: &WebviewWindow<R>, title: &str, body: &str) {
+    if window.is_focused().unwrap_or(true) {
+        return;
+    }
+
+    info!("Notifying about finished operation in window {}: {title}", window.label());
+
+    let mut notification = Notification::new();
+    notification.summary(title).body(body).auto_icon();
+
+    // Like the notification plugin, the app only has an identifier the OS knows once it is installed
+    #[cfg(windows)]
+    if !tauri::is_dev() {
+        notification.app_id(&window.config().identifier);
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let _ = notify_rust::set_application(if tauri::is_dev() {
+            "com.apple.Terminal"
+        } else {
+            &window.config().identifier
+        });
+    }
+
+    let window = window.clone();
+
+    // Waiting for the click blocks until the notification is clicked or closed
+    std::thread::spawn(move || {
+        let handle = match notification.show() {
+            Ok(handle) => handle,
+            Err(e) => {
+                warn!("Failed to show notification: {e}");
+                return;
+            }
+        };
+
+        let clicked = |response: &NotificationResponse| {
+            if !matches!(response, NotificationResponse::Default | NotificationResponse::Action(_))
+            {
+                return;
+            }
+
+            if let Err(e) = window.unminimize().and_then(|_| window.set_focus()) {
+                warn!("Failed to focus window {}: {e}", window.label());
+            }
+        };
+
+        if let Err(e) = handle.wait_for_response(clicked) {
+            warn!("Failed to wait for a click on the notification: {e}");
+        }
+    });
+
+    if let Err(e) = window.request_user_attention(Some(UserAttentionType::Informational)) {
+        warn!("Failed to request user attention for window {}: {e}", window.label());
+    }
+}
+
+/// Like [notify_if_unfocused] but only for operations that took at least [LONG_OPERATION_THRESHOLD]
+pub(crate) fn notify_if_long_and_unfocused<R: Runtime>(
+    window: &WebviewWindow<R>,
+    started_at: Instant,
+    title: &str,
+    body: &str,
+) {
+    if started_at.elapsed() >= LONG_OPERATION_THRESHOLD {
+        notify_if_unfocused(window, title, body);
+    }
+}
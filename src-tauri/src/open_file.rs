@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use indexmap::IndexMap;
+use log::{info, warn};
+use mv_core::analyzer::AnalyzerOptions;
+use tauri::{AppHandle, Manager};
+
+use crate::cli::CliArgs;
+use crate::compile_commands::workspace_defines;
+use crate::window;
+
+/// Files passed on the command line that are waiting for the window they were opened in to load them,
+/// the options a later launch opened each window with, and the macros of the project each window's file
+/// or workspace is part of
+#[derive(Default)]
+pub(crate) struct OpenedFiles {
+    pending: Mutex<HashMap<String, PathBuf>>,
+    launch_args: Mutex<HashMap<String, CliArgs>>,
+    defines: Mutex<HashMap<String, IndexMap<String, String>>>,
+}
+
+impl OpenedFiles {
+    /// Removes and returns the file waiting to be loaded by the window with the given label
+    pub(crate) fn take(&self, label: &str) -> Option<PathBuf> {
+        self.pending.lock().unwrap().remove(label)
+    }
+
+    /// Sets the macros the project of the file loaded by the window with the given label defines, in
+    /// place of the ones of the file or workspace it had before
+    pub(crate) fn set_defines(&self, label: &str, defines: IndexMap<String, String>) {
+        self.defines.lock().unwrap().insert(label.to_string(), defines);
+    }
+
+    /// Overrides the options with the ones the window with the given label was opened with by a later
+    /// launch, like `--seed 42`, if it was, and adds the macros of the project of its file or workspace
+    pub(crate) fn apply_window_options(&self, label: &str, options: &mut AnalyzerOptions) {
+        if let Some(cli_args) = self.launch_args.lock().unwrap().get(label) {
+            cli_args.apply(options);
+        }
+
+        if let Some(defines) = self.defines.lock().unwrap().get(label) {
+            options.defines.extend(defines.clone());
+        }
+    }
+
+    /// Forgets the options of a window that was closed
+    pub(crate) fn forget(&self, label: &str) {
+        self.pending.lock().unwrap().remove(label);
+        self.launch_args.lock().unwrap().remove(label);
+        self.defines.lock().unwrap().remove(label);
+    }
+
+    fn insert(&self, label: String, path: PathBuf) {
+        self.pending.lock().unwrap().insert(label, path);
+    }
+
+    fn insert_launch_args(&self, label: String, cli_args: CliArgs) {
+        self.launch_args.lock().unwrap().insert(label, cli_args);
+    }
+}
+
+/// Opens each file in a new main window
+///
+/// A directory is opened as a workspace, its window preprocesses the sources pasted into it with the
+/// macros of the project's `compile_commands.json`
+///
+/// If there are no files, the most recent main window is focused instead so launching the app again
+/// brings the running instance to the front. The options of a later launch, `None` for the first one
+/// whose options are the defaults of every window, are attached to the windows it opens or focuses
+/// before they can analyze anything
+pub(crate) fn open_files(app_handle: &AppHandle, paths: Vec<PathBuf>, cli_args: Option<CliArgs>) {
+    let opened_files = app_handle.state::<OpenedFiles>();
+
+    if paths.is_empty() {
+        match app_handle
+            .webview_windows()
+            .into_values()
+            .filter(|w| w.label().starts_with(window::MAIN_WINDOW_PREFIX))
+            .last()
+        {
+            Some(w) => {
+                if let Some(cli_args) = cli_args {
+                    opened_files.insert_launch_args(w.label().to_string(), cli_args);
+                }
+
+                if let Err(e) = w.unminimize().and_then(|_| w.set_focus()) {
+                    warn!("Failed to focus window {}: {e}", w.label());
+                }
+            }
+            None => {
+                let w = window::create_main_window(app_handle, "/", None);
+
+                if let Some(cli_args) = cli_args {
+                    opened_files.insert_launch_args(w.label().to_string(), cli_args);
+                }
+            }
+        }
+        return;
+    }
+
+    for path in paths {
+        let w = window::create_main_window(app_handle, "/", None);
+
+        if path.is_dir() {
+            info!("Opening the workspace {} in window {}", path.display(), w.label());
+            opened_files.set_defines(w.label(), workspace_defines(&path));
+        } else {
+            info!("Opening {} in window {}", path.display(), w.label());
+            opened_files.insert(w.label().to_string(), path);
+        }
+
+        if let Some(cli_args) = &cli_args {
+            opened_files.insert_launch_args(w.label().to_string(), cli_args.clone());
+        }
+    }
+}
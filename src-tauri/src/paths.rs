@@ -0,0 +1,84 @@
+use std::path::PathBuf;
+
+use log::info;
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::error::Result as MVResult;
+
+/// A file with this name next to the executable turns portable mode on, so lab machines can be set up
+/// by dropping a file instead of changing shortcuts
+const PORTABLE_MARKER_FILE_NAME: &str = "portable";
+
+/// Directory next to the executable that holds everything the app writes in portable mode
+const PORTABLE_DATA_DIR_NAME: &str = "mv-data";
+
+/// Resolves where the app writes its files
+///
+/// Normally these are the OS app directories. In portable mode everything goes to a directory next to
+/// the executable instead, for locked-down machines where the app directories aren't writable
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AppPaths {
+    portable_dir: Option<PathBuf>,
+}
+
+impl AppPaths {
+    /// Turns portable mode on if it was requested on the command line or the marker file exists
+    pub(crate) fn resolve(portable_requested: bool) -> Self {
+        let Some(exe_dir) =
+            std::env::current_exe().ok().and_then(|exe| exe.parent().map(|dir| dir.to_path_buf()))
+        else {
+            return Self::default();
+        };
+
+        if !portable_requested && !exe_dir.join(PORTABLE_MARKER_FILE_NAME).exists() {
+            return Self::default();
+        }
+
+        let portable_dir = exe_dir.join(PORTABLE_DATA_DIR_NAME);
+        info!("Running in portable mode, writing to {}", portable_dir.display());
+
+        Self {
+            portable_dir: Some(portable_dir),
+        }
+    }
+
+    pub(crate) fn is_portable(&self) -> bool {
+        self.portable_dir.is_some()
+    }
+
+    pub(crate) fn config_dir<R: Runtime>(&self, app_handle: &AppHandle<R>) -> MVResult<PathBuf> {
+        match &self.portable_dir {
+            Some(dir) => Ok(dir.join("config")),
+            None => Ok(app_handle.path().app_config_dir()?),
+        }
+    }
+
+    pub(crate) fn data_dir<R: Runtime>(&self, app_handle: &AppHandle<R>) -> MVResult<PathBuf> {
+        match &self.portable_dir {
+            Some(dir) => Ok(dir.join("data")),
+            None => Ok(app_handle.path().app_data_dir()?),
+        }
+    }
+
+    pub(crate) fn log_dir<R: Runtime>(&self, app_handle: &AppHandle<R>) -> MVResult<PathBuf> {
+        match &self.portable_dir {
+            Some(dir) => Ok(dir.join("logs")),
+            None => Ok(app_handle.path().app_log_dir()?),
+        }
+    }
+
+    /// Log directory to use when it differs from the OS default, needed before the app handle exists
+    pub(crate) fn portable_log_dir(&self) -> Option<PathBuf> {
+        self.portable_dir.as_ref().map(|dir| dir.join("logs"))
+    }
+
+    /// Directory for the webview's own storage (local storage, caches), `None` keeps the default
+    pub(crate) fn webview_data_dir(&self) -> Option<PathBuf> {
+        self.portable_dir.as_ref().map(|dir| dir.join("webview"))
+    }
+
+    /// Window state file, which the window state plugin otherwise keeps in the app config directory
+    pub(crate) fn window_state_file(&self) -> Option<PathBuf> {
+        self.portable_dir.as_ref().map(|dir| dir.join("config").join(".window-state.json"))
+    }
+}
@@ -0,0 +1,38 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use log::info;
+use mv_core::export::{DEFAULT_WASM_MODULE_URL, render_playground_page};
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::error::Result as MVResult;
+
+/// Folder the WASM module is bundled into, see `bundle.resources` in `tauri.conf.json`. Bundling the
+/// module built from the same sources as the app keeps exported playgrounds in lockstep with it
+const WASM_RESOURCE_DIR: &str = "wasm";
+const WASM_MODULE_FILES: [&str; 2] = ["mv_wasm.js", "mv_wasm_bg.wasm"];
+
+/// Folder name used when the user doesn't pick where to export to
+pub(crate) const DEFAULT_PLAYGROUND_DIR_NAME: &str = "mv-playground";
+
+/// Writes the web playground to `dir` as static files that can be hosted on any file server
+///
+/// Returns the path of the page to open
+pub(crate) fn export_offline_playground<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    dir: &Path,
+) -> MVResult<PathBuf> {
+    let resource_dir = app_handle.path().resource_dir()?.join(WASM_RESOURCE_DIR);
+
+    fs::create_dir_all(dir)?;
+
+    for file in WASM_MODULE_FILES {
+        fs::copy(resource_dir.join(file), dir.join(file))?;
+    }
+
+    let page = dir.join("index.html");
+    fs::write(&page, render_playground_page(DEFAULT_WASM_MODULE_URL)?)?;
+
+    info!("Exported offline playground to {}", dir.display());
+    Ok(page)
+}
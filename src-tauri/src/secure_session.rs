@@ -0,0 +1,66 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use crypto_box::aead::OsRng;
+use crypto_box::{PublicKey, SecretKey};
+use mv_core::session::Session;
+
+use crate::error::Result as MVResult;
+
+/// First line of an encrypted session, so it can be told apart from a plain one
+const SECURE_SESSION_HEADER: &str = "mv-secure-session-v1";
+
+/// A key pair an instructor encrypts exam sessions with, both keys base64 encoded
+///
+/// The public key is handed out with the exam, the secret key stays with the instructor and is the only
+/// way to read the sessions back
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ExamKeys {
+    public_key: String,
+    secret_key: String,
+}
+
+pub(crate) fn generate_exam_keys() -> ExamKeys {
+    let secret_key = SecretKey::generate(&mut OsRng);
+
+    ExamKeys {
+        public_key: BASE64.encode(secret_key.public_key().as_bytes()),
+        secret_key: BASE64.encode(secret_key.to_bytes()),
+    }
+}
+
+/// Encrypts a session for the instructor with the given public key, as a sealed box so not even the
+/// student who exported it can read it back
+pub(crate) fn seal_session(session: &Session, public_key: &str) -> MVResult<String> {
+    let public_key = PublicKey::from_slice(&decode_key(public_key)?)
+        .map_err(|_| "Invalid instructor key: it must be 32 bytes")?;
+
+    let sealed = public_key
+        .seal(&mut OsRng, session.to_json()?.as_bytes())
+        .map_err(|_| "Failed to encrypt the session")?;
+
+    Ok(format!("{}\n{}\n", SECURE_SESSION_HEADER, BASE64.encode(sealed)))
+}
+
+/// Decrypts a session sealed with [seal_session] using the instructor's secret key
+pub(crate) fn open_session(sealed: &str, secret_key: &str) -> MVResult<Session> {
+    let Some(body) = sealed.trim().strip_prefix(SECURE_SESSION_HEADER) else {
+        return Err("Not an encrypted session".into());
+    };
+
+    let secret_key = SecretKey::from_slice(&decode_key(secret_key)?)
+        .map_err(|_| "Invalid secret key: it must be 32 bytes")?;
+
+    let ciphertext =
+        BASE64.decode(body.trim()).map_err(|e| format!("Corrupted encrypted session: {e}"))?;
+
+    let json = secret_key
+        .unseal(&ciphertext)
+        .map_err(|_| "Failed to decrypt the session, it was encrypted for a different key")?;
+
+    Ok(Session::from_json(&String::from_utf8_lossy(&json))?)
+}
+
+fn decode_key(key: &str) -> MVResult<Vec<u8>> {
+    Ok(BASE64.decode(key.trim()).map_err(|e| format!("Invalid key: {e}"))?)
+}
@@ -0,0 +1,102 @@
+use std::fs;
+use std::path::PathBuf;
+
+use log::warn;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::error::Result as MVResult;
+use crate::paths::AppPaths;
+
+const SETTINGS_FILE_NAME: &str = "settings.json";
+
+/// Version of the settings file, bumped whenever its format changes
+const SETTINGS_SCHEMA_VERSION: u32 = 1;
+
+/// Upgrades the settings from the version at its index to the next one, version 0 being the settings
+/// saved before the file had a version
+const SETTINGS_UPGRADES: [fn(&mut Map<String, Value>); SETTINGS_SCHEMA_VERSION as usize] = [|_| {}];
+
+/// Settings that the backend needs to know about, persisted in the app config directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct AppSettings {
+    pub(crate) schema_version: u32,
+    pub(crate) automatic_update_checks: bool,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            schema_version: SETTINGS_SCHEMA_VERSION,
+            automatic_update_checks: true,
+        }
+    }
+}
+
+impl AppSettings {
+    /// Loads the settings, upgrading them if an older version of MV saved them, and falling back to
+    /// the defaults if they are missing, unreadable or saved by a newer version
+    pub(crate) fn load<R: Runtime>(app_handle: &AppHandle<R>) -> Self {
+        let path = match settings_path(app_handle) {
+            Ok(path) => path,
+            Err(e) => {
+                warn!("Failed to resolve settings path: {e}");
+                return Self::default();
+            }
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => Self::from_json(&contents).unwrap_or_else(|e| {
+                warn!("Ignoring invalid settings in {}: {e}", path.display());
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    fn from_json(json: &str) -> MVResult<Self> {
+        let Value::Object(mut settings) = serde_json::from_str(json)? else {
+            return Err("expected an object".into());
+        };
+
+        let version = match settings.get("schemaVersion") {
+            None => 0,
+            Some(version) => version
+                .as_u64()
+                .and_then(|version| u32::try_from(version).ok())
+                .ok_or_else(|| format!("schema version {version} isn't a number"))?,
+        };
+
+        if version > SETTINGS_SCHEMA_VERSION {
+            return Err(format!(
+                "saved with schema version {version}, newer than version {SETTINGS_SCHEMA_VERSION}"
+            )
+            .into());
+        }
+
+        for upgrade in &SETTINGS_UPGRADES[version as usize..] {
+            upgrade(&mut settings);
+        }
+
+        settings.insert("schemaVersion".to_owned(), SETTINGS_SCHEMA_VERSION.into());
+
+        Ok(serde_json::from_value(Value::Object(settings))?)
+    }
+
+    pub(crate) fn save<R: Runtime>(&self, app_handle: &AppHandle<R>) -> MVResult<()> {
+        let path = settings_path(app_handle)?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+fn settings_path<R: Runtime>(app_handle: &AppHandle<R>) -> MVResult<PathBuf> {
+    Ok(app_handle.state::<AppPaths>().config_dir(app_handle)?.join(SETTINGS_FILE_NAME))
+}
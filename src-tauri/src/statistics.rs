@@ -0,0 +1,24 @@
+use std::io::Write;
+use std::path::Path;
+
+use mv_core::analyzer::AnalyzerOptions;
+use mv_core::placement_statistics::compare_seeds;
+
+use crate::error::Result as MVResult;
+use crate::headless::report_files;
+
+/// Runs every file under the seeds `0` to `count - 1` and writes one report of where their blocks were
+/// placed per line to the output, for exploring how the allocator behaves from the command line, e.g.
+/// `mv --compare-seeds 100 program.cpp`
+pub(crate) fn compare_files(
+    files: &[impl AsRef<Path>],
+    count: u64,
+    options: &AnalyzerOptions,
+    output: &mut impl Write,
+) -> MVResult<()> {
+    let seeds: Vec<u64> = (0..count).collect();
+
+    report_files(files, output, |_, source| {
+        tauri::async_runtime::block_on(compare_seeds(source, &seeds, options.clone()))
+    })
+}
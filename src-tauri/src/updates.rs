@@ -2,13 +2,90 @@ use log::{error, info};
 use std::time::SystemTime;
 
 use crate::error::Result as MVResult;
+use crate::network;
+use crate::notifications::notify_if_unfocused;
 use tauri::{Emitter, Runtime, WebviewWindow};
 use tauri_plugin_updater::UpdaterExt;
+use tokio::sync::Mutex;
 
 const MAX_UPDATE_CHECK_HOURS: u64 = 12;
 
 pub(crate) struct MVUpdater {
     last_update_check: SystemTime,
+    automatic_checks: bool,
+    channel: UpdateChannel,
+}
+
+/// How the running install gets its updates
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum UpdateChannel {
+    /// MV downloads and installs its updates itself
+    SelfUpdate,
+    /// A portable copy, updated by whoever copied it
+    Portable,
+    /// Installed from a deb, rpm or flatpak, updated along with the rest of the system
+    PackageManager,
+    /// No update artifacts are built for this platform or architecture, like a build from source
+    Unsupported,
+}
+
+impl UpdateChannel {
+    /// Finds out how the running install gets its updates
+    pub(crate) fn detect(portable: bool) -> Self {
+        if portable {
+            return UpdateChannel::Portable;
+        }
+
+        // releases are only built for these architectures, on every platform
+        if !matches!(std::env::consts::ARCH, "x86_64" | "aarch64") {
+            return UpdateChannel::Unsupported;
+        }
+
+        platform_channel()
+    }
+}
+
+/// Only the AppImage can replace itself on Linux, packages are installed under `/usr` where the package
+/// manager owns the files
+#[cfg(target_os = "linux")]
+fn platform_channel() -> UpdateChannel {
+    if std::env::var_os("APPIMAGE").is_some() {
+        return UpdateChannel::SelfUpdate;
+    }
+
+    if std::env::var_os("FLATPAK_ID").is_some() || std::path::Path::new("/.flatpak-info").exists() {
+        return UpdateChannel::PackageManager;
+    }
+
+    match std::env::current_exe() {
+        Ok(exe) if exe.starts_with("/usr") => UpdateChannel::PackageManager,
+        _ => UpdateChannel::Unsupported,
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn platform_channel() -> UpdateChannel {
+    UpdateChannel::SelfUpdate
+}
+
+/// What the network looked like when an automatic update check came due
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct NetworkConditions {
+    pub(crate) online: bool,
+    pub(crate) metered: bool,
+}
+
+impl NetworkConditions {
+    /// Probes whether the update server can be reached and whether the connection is metered, which
+    /// blocks for up to a few seconds on a slow network
+    pub(crate) async fn probe() -> Self {
+        let online = network::is_online().await;
+        // there's nothing to save on a connection the check won't use
+        let metered = online && network::is_metered().await;
+
+        NetworkConditions { online, metered }
+    }
 }
 
 #[derive(serde::Serialize, Clone)]
@@ -26,12 +103,29 @@ pub(crate) enum UpdateProgressEvent {
 }
 
 impl MVUpdater {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(automatic_checks: bool, portable: bool) -> Self {
+        let channel = UpdateChannel::detect(portable);
+        info!("Updates come from {:?}", channel);
+
+        Self::with_channel(automatic_checks, channel)
+    }
+
+    fn with_channel(automatic_checks: bool, channel: UpdateChannel) -> Self {
         Self {
             last_update_check: SystemTime::UNIX_EPOCH,
+            automatic_checks,
+            channel,
         }
     }
 
+    pub(crate) fn channel(&self) -> UpdateChannel {
+        self.channel
+    }
+
+    pub(crate) fn set_automatic_checks(&mut self, automatic_checks: bool) {
+        self.automatic_checks = automatic_checks;
+    }
+
     pub(crate) fn is_update_check_due(&self) -> bool {
         if self.last_update_check == SystemTime::UNIX_EPOCH {
             // If this is the first check, we consider it due
@@ -43,15 +137,65 @@ impl MVUpdater {
         seconds_since_last_check >= update_period_seconds
     }
 
+    /// Checks for updates if a check is due and it's a good moment for one
+    ///
+    /// Checks are skipped when the user turned automatic checks off, when the install doesn't update
+    /// itself, when the update server can't be reached and on metered connections. The network is
+    /// probed without holding the lock, so commands waiting on the updater aren't stuck behind the
+    /// probe. Returns `None` if the check was skipped
+    pub(crate) async fn check_automatically<R: Runtime>(
+        updater: &Mutex<MVUpdater>,
+        window: &WebviewWindow<R>,
+    ) -> MVResult<Option<bool>> {
+        if !updater.lock().await.is_automatic_check_due() {
+            return Ok(None);
+        }
+
+        let network = NetworkConditions::probe().await;
+
+        let mut updater = updater.lock().await;
+        // the settings may have changed or another window may have checked during the probe
+        if !updater.should_check_automatically(network) {
+            return Ok(None);
+        }
+
+        Ok(Some(updater.check_now(window).await?))
+    }
+
+    /// Whether an automatic check is due, before looking at the network
+    fn is_automatic_check_due(&self) -> bool {
+        self.automatic_checks
+            && self.channel == UpdateChannel::SelfUpdate
+            && self.is_update_check_due()
+    }
+
+    /// Whether an automatic check should run on the network it would go over
+    fn should_check_automatically(&self, network: NetworkConditions) -> bool {
+        if !self.is_automatic_check_due() {
+            return false;
+        }
+
+        if !network.online {
+            info!("Skipping automatic update check, update server is unreachable");
+            return false;
+        }
+
+        if network.metered {
+            info!("Skipping automatic update check on metered connection");
+            return false;
+        }
+
+        true
+    }
+
     pub(crate) async fn check_now<R: Runtime>(
         &mut self,
         window: &WebviewWindow<R>,
     ) -> MVResult<bool> {
-        #[cfg(target_os = "linux")]
-        {
-            if std::env::var("APPIMAGE").is_err() {
-                return Ok(false);
-            }
+        // Portable installs are managed by whoever copied them, updating would write outside the app directory,
+        // and packages are updated by the package manager that installed them
+        if self.channel != UpdateChannel::SelfUpdate {
+            return Ok(false);
         }
 
         self.last_update_check = SystemTime::now();
@@ -75,6 +219,10 @@ impl MVUpdater {
         use tauri::Manager;
         use tauri_plugin_updater::UpdaterExt;
 
+        if self.channel != UpdateChannel::SelfUpdate {
+            return Ok(false);
+        }
+
         self.last_update_check = SystemTime::now();
 
         let window = Arc::new(window.clone());
@@ -138,6 +286,11 @@ impl MVUpdater {
                 move || {
                     info!("Starting installation phase...");
                     emit_event(&w_install, UpdateProgressEvent::Installing, "installing");
+                    notify_if_unfocused(
+                        w_install.as_ref(),
+                        "Update downloaded",
+                        "MV is installing the update and will restart shortly",
+                    );
                 },
             )
             .await;
@@ -156,3 +309,65 @@ impl MVUpdater {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GOOD_NETWORK: NetworkConditions = NetworkConditions {
+        online: true,
+        metered: false,
+    };
+
+    #[test]
+    fn checks_automatically_on_a_good_network() {
+        let updater = MVUpdater::with_channel(true, UpdateChannel::SelfUpdate);
+
+        assert!(updater.should_check_automatically(GOOD_NETWORK));
+    }
+
+    #[test]
+    fn skips_automatic_checks_when_disabled() {
+        let updater = MVUpdater::with_channel(false, UpdateChannel::SelfUpdate);
+
+        assert!(!updater.should_check_automatically(GOOD_NETWORK));
+    }
+
+    #[test]
+    fn skips_automatic_checks_offline() {
+        let updater = MVUpdater::with_channel(true, UpdateChannel::SelfUpdate);
+        let network = NetworkConditions {
+            online: false,
+            metered: false,
+        };
+
+        assert!(!updater.should_check_automatically(network));
+    }
+
+    #[test]
+    fn skips_automatic_checks_on_metered_connections() {
+        let updater = MVUpdater::with_channel(true, UpdateChannel::SelfUpdate);
+        let network = NetworkConditions {
+            online: true,
+            metered: true,
+        };
+
+        assert!(!updater.should_check_automatically(network));
+    }
+
+    #[test]
+    fn skips_automatic_checks_for_portable_copies() {
+        let updater = MVUpdater::new(true, true);
+
+        assert_eq!(updater.channel(), UpdateChannel::Portable);
+        assert!(!updater.should_check_automatically(GOOD_NETWORK));
+    }
+
+    #[test]
+    fn skips_automatic_checks_until_due_again() {
+        let mut updater = MVUpdater::with_channel(true, UpdateChannel::SelfUpdate);
+        updater.last_update_check = SystemTime::now();
+
+        assert!(!updater.should_check_automatically(GOOD_NETWORK));
+    }
+}
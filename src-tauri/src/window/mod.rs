@@ -8,12 +8,20 @@ use rand::random;
 use tauri::{AppHandle, Manager, Runtime, WebviewUrl, WebviewWindow, WindowEvent};
 use tokio::sync::mpsc;
 
+use crate::paths::AppPaths;
+
 pub(crate) const MAIN_WINDOW_PREFIX: &str = "main_";
 pub(crate) const OTHER_WINDOW_PREFIX: &str = "other_";
 
 pub(crate) const DEFAULT_WINDOW_WIDTH: f64 = 1100.0;
 pub(crate) const DEFAULT_WINDOW_HEIGHT: f64 = 600.0;
 
+/// Share of the monitor's work area a new main window takes up
+const DEFAULT_WINDOW_WORK_AREA_RATIO: f64 = 0.75;
+
+const MAX_DEFAULT_WINDOW_WIDTH: f64 = 1800.0;
+const MAX_DEFAULT_WINDOW_HEIGHT: f64 = 1100.0;
+
 pub(crate) const MIN_WINDOW_WIDTH: f64 = 300.0;
 pub(crate) const MIN_WINDOW_HEIGHT: f64 = 300.0;
 
@@ -51,6 +59,10 @@ pub(crate) fn create_window<R: Runtime>(
             .disable_drag_drop_handler() // Required for frontend Dnd on windows
             .min_inner_size(MIN_WINDOW_WIDTH, MIN_WINDOW_HEIGHT);
 
+    if let Some(dir) = handle.try_state::<AppPaths>().and_then(|paths| paths.webview_data_dir()) {
+        win_builder = win_builder.data_directory(dir);
+    }
+
     if let Some((w, h)) = config.inner_size {
         win_builder = win_builder.inner_size(w, h);
     } else {
@@ -135,6 +147,31 @@ pub(crate) fn create_window<R: Runtime>(
     win
 }
 
+/// Computes the size of a new main window from the primary monitor's work area, so the first window
+/// isn't cramped on small displays or tiny on large ones
+///
+/// Falls back to [DEFAULT_WINDOW_WIDTH] and [DEFAULT_WINDOW_HEIGHT] if the monitor can't be queried
+pub(crate) fn default_window_size<R: Runtime>(handle: &AppHandle<R>) -> (f64, f64) {
+    let monitor = match handle.primary_monitor() {
+        Ok(Some(monitor)) => monitor,
+        _ => return (DEFAULT_WINDOW_WIDTH, DEFAULT_WINDOW_HEIGHT),
+    };
+
+    let work_area = monitor.work_area().size.to_logical::<f64>(monitor.scale_factor());
+
+    // The minimum size wins over the maximum so the window never gets smaller than it's allowed to be,
+    // and the work area wins over both so it never spills off screen
+    let clamp = |available: f64, max: f64, min: f64| {
+        (available * DEFAULT_WINDOW_WORK_AREA_RATIO).min(max).max(min).min(available)
+    };
+
+    (
+        clamp(work_area.width, MAX_DEFAULT_WINDOW_WIDTH, MIN_WINDOW_WIDTH),
+        clamp(work_area.height, MAX_DEFAULT_WINDOW_HEIGHT, MIN_WINDOW_HEIGHT),
+    )
+}
+
+/// Creates a new main window, sized from the primary monitor's work area if no size is given
 pub(crate) fn create_main_window(
     handle: &AppHandle,
     url: &str,
@@ -154,7 +191,7 @@ pub(crate) fn create_main_window(
         url,
         label: label.as_str(),
         title: "MV",
-        inner_size: size,
+        inner_size: Some(size.unwrap_or_else(|| default_window_size(handle))),
         position: Some((100.0 + random::<f64>() * 20.0, 100.0 + random::<f64>() * 20.0)),
         hide_titlebar: true,
         ..Default::default()
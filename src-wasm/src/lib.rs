@@ -3,70 +3,256 @@ mod web_analyzer_state;
 use serde_json::json;
 use wasm_bindgen::prelude::wasm_bindgen;
 
-use mv_core::analyzer::Analyzer;
-use mv_core::error::Error::{AnalyzerError, ParserError};
-use mv_core::parser::Parser;
+use mv_core::analyzer::{
+    AllocatorExercise, AnalysisError, AnalyzerOptions, AnalyzerState, FreeRange, Note,
+};
+use mv_core::capabilities::capabilities;
+use mv_core::error::Error;
+use mv_core::error::Result;
+use mv_core::export::{DEFAULT_WASM_MODULE_URL, render_ascii_diagram, render_embed_snippet};
+use mv_core::pipeline::analyze_source;
+use mv_core::session::Session;
 
 use crate::web_analyzer_state::WebAnalyzerState;
 
 #[wasm_bindgen]
-pub async fn analyze_source_code(input: String) -> String {
-    let sanitized_source_code = input;
+pub async fn analyze_source_code(input: String, options: Option<String>) -> String {
+    let mut state = WebAnalyzerState::default();
+
+    let result = match parse_options(options) {
+        Ok(options) => analyze_source(&input, &mut state, options).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(result) => serde_json::to_string(&result).unwrap(),
+
+        Err(e) => serde_json::to_string(&error_to_json(&e)).unwrap(),
+    }
+}
+
+#[wasm_bindgen]
+pub async fn export_ascii_diagram(input: String, options: Option<String>) -> String {
+    let mut state = WebAnalyzerState::default();
+
+    let options = match parse_options(options) {
+        Ok(options) => options,
+        Err(e) => return serde_json::to_string(&error_to_json(&e)).unwrap(),
+    };
+
+    match analyze_source(&input, &mut state, options.clone()).await {
+        Ok(result) => serde_json::to_string(&json!({
+            "diagram": render_ascii_diagram(&result.stack, &result.heap, options.architecture),
+        }))
+        .unwrap(),
+
+        Err(e) => serde_json::to_string(&error_to_json(&e)).unwrap(),
+    }
+}
+
+/// Exports the program as an HTML snippet for static sites, an editable copy of the source next to the
+/// session view of its memory that this module draws again as readers type
+#[wasm_bindgen]
+pub async fn export_embed_snippet(
+    input: String,
+    wasm_module_url: Option<String>,
+    options: Option<String>,
+) -> String {
+    let mut state = WebAnalyzerState::default();
+
+    let options = match parse_options(options) {
+        Ok(options) => options,
+        Err(e) => return serde_json::to_string(&error_to_json(&e)).unwrap(),
+    };
+
+    let result = match analyze_source(&input, &mut state, options.clone()).await {
+        Ok(_) => {
+            let session = Session::new(input, state.get_starting_pointers().await, options);
+            render_embed_snippet(
+                &session,
+                wasm_module_url.as_deref().unwrap_or(DEFAULT_WASM_MODULE_URL),
+            )
+        }
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(snippet) => serde_json::to_string(&json!({
+            "snippet": snippet,
+        }))
+        .unwrap(),
+
+        Err(e) => serde_json::to_string(&error_to_json(&e)).unwrap(),
+    }
+}
+
+/// Analyzes a serialized [Session](mv_core::session::Session), responding with the result and its ASCII
+/// diagram, this is what embedded snippets call to draw themselves
+#[wasm_bindgen]
+pub async fn render_session(session: String) -> String {
+    let result = match Session::from_json(&session) {
+        Ok(mut session) => {
+            let source = session.source.clone();
+            let options = session.options.clone();
+            analyze_source(&source, &mut session, options.clone())
+                .await
+                .map(|analysis| (analysis, options.architecture))
+        }
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok((result, architecture)) => serde_json::to_string(&json!({
+            "diagram": render_ascii_diagram(&result.stack, &result.heap, architecture),
+            "result": result,
+        }))
+        .unwrap(),
+
+        Err(e) => serde_json::to_string(&error_to_json(&e)).unwrap(),
+    }
+}
+
+/// Analyzes the source code and lists every chain of pointers from the stack that reaches the heap
+/// block starting at `block`, like `pp -> p`
+#[wasm_bindgen]
+pub async fn access_paths(input: String, block: usize, options: Option<String>) -> String {
+    let mut state = WebAnalyzerState::default();
+
+    let result = match parse_options(options) {
+        Ok(options) => analyze_source(&input, &mut state, options).await,
+        Err(e) => Err(e),
+    };
+
+    match result
+        .and_then(|result| mv_core::analyzer::access_paths(&result.stack, &result.heap, block))
+    {
+        Ok(paths) => serde_json::to_string(&json!({
+            "paths": paths,
+        }))
+        .unwrap(),
+
+        Err(e) => serde_json::to_string(&error_to_json(&e)).unwrap(),
+    }
+}
 
-    let mut parser = Parser::new(&sanitized_source_code);
+/// Analyzes the source code and matches notes, passed as JSON, with the symbols and heap blocks they
+/// were attached to
+#[wasm_bindgen]
+pub async fn place_notes(input: String, notes: String, options: Option<String>) -> String {
     let mut state = WebAnalyzerState::default();
 
-    match parser.parse() {
-        Ok(statements) => match Analyzer::default().analyze_statements(statements, &mut state).await {
-            Ok(res) => serde_json::to_string(&json!({
-                "stack": res.0,
-                "heap": res.1,
-            }))
-            .unwrap(),
-
-            Err(e) => match e {
-                AnalyzerError(_, line_number, column_number) => {
-                    return serde_json::to_string(&json!({
-                        "error": {
-                            "message": e.to_string(),
-                            "line_number": line_number,
-                            "column_number": column_number
-                        }
-                    }))
-                    .unwrap();
-                }
-
-                _ => {
-                    return serde_json::to_string(&json!({
-                        "error": {
-                            "message": e.to_string()
-                        }
-                    }))
-                    .unwrap();
-                }
-            },
-        },
-
-        Err(e) => match e {
-            ParserError(_, line_number, column_number) => {
-                return serde_json::to_string(&json!({
-                    "error": {
-                        "message": e.to_string(),
-                        "line_number": line_number,
-                        "column_number": column_number
-                    }
-                }))
-                .unwrap();
-            }
-
-            _ => {
-                return serde_json::to_string(&json!({
-                    "error": {
-                        "message": e.to_string()
-                    }
-                }))
-                .unwrap();
-            }
-        },
+    let result = parse_options(options).and_then(|options| {
+        let notes: Vec<Note> = serde_json::from_str(&notes)
+            .map_err(|e| Error::from(format!("Invalid notes: {}", e)))?;
+
+        Ok((notes, options))
+    });
+
+    let result = match result {
+        Ok((notes, options)) => analyze_source(&input, &mut state, options)
+            .await
+            .map(|result| mv_core::analyzer::place_notes(&notes, &result)),
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(notes) => serde_json::to_string(&json!({
+            "notes": notes,
+        }))
+        .unwrap(),
+
+        Err(e) => serde_json::to_string(&error_to_json(&e)).unwrap(),
     }
 }
+
+/// Previews what `statement` would change if it ran after the last statement of the source, without
+/// remembering the placements it made
+#[wasm_bindgen]
+pub async fn preview_statement(
+    input: String,
+    statement: String,
+    options: Option<String>,
+) -> String {
+    let mut state = WebAnalyzerState::default();
+    let starting_pointers = state.get_starting_pointers().await;
+
+    let result = match parse_options(options) {
+        Ok(options) => {
+            mv_core::analyzer::preview_statement(&input, &statement, starting_pointers, options)
+                .await
+        }
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(preview) => serde_json::to_string(&preview).unwrap(),
+        Err(e) => serde_json::to_string(&error_to_json(&e)).unwrap(),
+    }
+}
+
+/// Answers whether an allocation of `size` bytes would fit in the free list of an analysis, passed as
+/// JSON, and where each strategy would place it
+#[wasm_bindgen]
+pub fn query_allocation(free_list: String, size: usize, options: Option<String>) -> String {
+    let result = parse_options(options).and_then(|options| {
+        let free_list: Vec<FreeRange> = serde_json::from_str(&free_list)
+            .map_err(|e| Error::from(format!("Invalid free list: {}", e)))?;
+
+        mv_core::analyzer::query_allocation(&free_list, size, &options)
+    });
+
+    match result {
+        Ok(query) => serde_json::to_string(&query).unwrap(),
+        Err(e) => serde_json::to_string(&error_to_json(&e)).unwrap(),
+    }
+}
+
+/// Runs the workload of an allocator exercise, passed as JSON, against the policy a student wrote
+#[wasm_bindgen]
+pub fn run_allocator_exercise(exercise: String, policy: String, options: Option<String>) -> String {
+    let result = parse_options(options)
+        .and_then(|options| AllocatorExercise::from_json(&exercise)?.run(&policy, &options));
+
+    match result {
+        Ok(report) => serde_json::to_string(&report).unwrap(),
+        Err(e) => serde_json::to_string(&error_to_json(&e)).unwrap(),
+    }
+}
+
+/// Computes the offsets, padding and size of a struct with members of the given types, passed as a JSON
+/// array like `["char", "int*"]`, on the architecture of the options
+#[wasm_bindgen]
+pub fn compute_struct_layout(member_types: String, options: Option<String>) -> String {
+    let result = parse_options(options).and_then(|options| {
+        let member_types: Vec<String> = serde_json::from_str(&member_types)
+            .map_err(|e| Error::from(format!("Invalid member types: {}", e)))?;
+
+        mv_core::layout::compute_struct_layout(&member_types, options.architecture)
+    });
+
+    match result {
+        Ok(layout) => serde_json::to_string(&layout).unwrap(),
+        Err(e) => serde_json::to_string(&error_to_json(&e)).unwrap(),
+    }
+}
+
+/// Reports what the bundled core supports, so the frontend can feature-detect
+#[wasm_bindgen]
+pub fn get_capabilities() -> String {
+    serde_json::to_string(&capabilities()).unwrap()
+}
+
+/// Parses the [AnalyzerOptions](mv_core::analyzer::AnalyzerOptions) passed from JS as JSON, no options
+/// means the defaults
+fn parse_options(options: Option<String>) -> Result<AnalyzerOptions> {
+    match options {
+        Some(options) => {
+            serde_json::from_str(&options).map_err(|e| format!("Invalid options: {}", e).into())
+        }
+        None => Ok(AnalyzerOptions::default()),
+    }
+}
+
+fn error_to_json(e: &Error) -> serde_json::Value {
+    serde_json::to_value(AnalysisError::from(e)).unwrap()
+}
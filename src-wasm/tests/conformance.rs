@@ -0,0 +1,18 @@
+//! Checks the heap layout of a few seeds on `wasm32`, against the placements the native tests of
+//! `mv-core` pin, so the web app lays programs out like the desktop app
+//!
+//! Run with `wasm-pack test --node`
+
+#[path = "../../src-core/tests/conformance/layout.rs"]
+mod layout;
+
+use wasm_bindgen_test::wasm_bindgen_test;
+
+use layout::{PLACEMENTS, analyze, check_layout};
+
+#[wasm_bindgen_test]
+async fn blocks_land_where_they_are_pinned() {
+    for (seed, _) in PLACEMENTS {
+        check_layout(seed, &analyze(seed).await);
+    }
+}
@@ -0,0 +1,45 @@
+import { X } from 'lucide-react';
+import { Button } from '@/components/ui/button';
+
+interface ClosePromptProps {
+  isOpen: boolean;
+  onCancel: () => void;
+  onDiscard: () => void;
+}
+
+export function ClosePrompt({ isOpen, onCancel, onDiscard }: ClosePromptProps) {
+  if (!isOpen) return null;
+
+  return (
+    <div className="fixed inset-0 z-50 flex items-center justify-center">
+      <div className="fixed inset-0 bg-black/50" onClick={onCancel} />
+      <div className="relative bg-background border rounded-lg shadow-lg p-6 w-full max-w-md mx-4">
+        <div className="flex items-center justify-between mb-4">
+          <h2 className="text-lg font-semibold">Unsaved Changes</h2>
+          <Button variant="ghost" size="icon" onClick={onCancel}>
+            <X className="w-4 h-4" />
+          </Button>
+        </div>
+
+        <div className="space-y-4">
+          <p className="text-sm text-muted-foreground">
+            The code in this window has changes that will be lost if it closes.
+          </p>
+
+          <div className="flex gap-2 pt-2">
+            <Button
+              variant="destructive"
+              onClick={onDiscard}
+              className="flex-1"
+            >
+              Close Without Saving
+            </Button>
+            <Button variant="outline" onClick={onCancel} className="flex-1">
+              Cancel
+            </Button>
+          </div>
+        </div>
+      </div>
+    </div>
+  );
+}
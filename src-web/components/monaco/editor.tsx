@@ -7,6 +7,10 @@ import { useTheme } from '@/providers/theme-provider';
 import { EditorToolbar } from '@/components/monaco/toolbar';
 import { useEditorSettingsStore } from '@/stores/editor';
 import { useLanguage } from '@/hooks/useLanguage';
+import {
+  type Diagnostic,
+  type IgnoredStatement,
+} from '@/hooks/useAnalyzeSourceCode';
 
 type EditorProps = {
   onRun?: (code: string) => void;
@@ -17,6 +21,10 @@ type EditorProps = {
     line_number?: number;
     column_number?: number;
   } | null;
+  // statements skipped in permissive mode, grayed out with the reason on hover
+  ignored?: IgnoredStatement[];
+  // writes skipped in permissive mode, underlined as warnings
+  warnings?: Diagnostic[];
 };
 
 export type EditorRef = {
@@ -25,10 +33,12 @@ export type EditorRef = {
 };
 
 export const Editor = React.forwardRef<EditorRef, EditorProps>((props, ref) => {
-  const { onRun, onChange, code, analyzeError } = props;
+  const { onRun, onChange, code, analyzeError, ignored, warnings } = props;
   const editorRef = React.useRef<monaco.editor.IStandaloneCodeEditor | null>(
     null
   );
+  const ignoredDecorationsRef =
+    React.useRef<monaco.editor.IEditorDecorationsCollection | null>(null);
   const monacoRef = React.useRef<typeof monaco | null>(null);
   const { theme } = useTheme();
   const { settings, fontsError, fontsLoading, setFontSize } =
@@ -156,6 +166,50 @@ export const Editor = React.forwardRef<EditorRef, EditorProps>((props, ref) => {
     }
   }, [analyzeError]);
 
+  React.useEffect(() => {
+    const editor = editorRef.current;
+    const monaco = monacoRef.current;
+    const model = editor?.getModel();
+    if (!monaco || !model) return;
+
+    monaco.editor.setModelMarkers(
+      model,
+      'warning',
+      (warnings ?? [])
+        .filter((warning) => warning.line_number && warning.column_number)
+        .map((warning) => ({
+          startLineNumber: warning.line_number!,
+          endLineNumber: warning.line_number!,
+          startColumn: warning.column_number!,
+          endColumn: warning.column_number!,
+          message: warning.message,
+          severity: monaco.MarkerSeverity.Warning,
+        }))
+    );
+  }, [warnings]);
+
+  React.useEffect(() => {
+    const editor = editorRef.current;
+    if (!editor) return;
+
+    ignoredDecorationsRef.current?.clear();
+    ignoredDecorationsRef.current = editor.createDecorationsCollection(
+      (ignored ?? []).map((statement) => ({
+        range: {
+          startLineNumber: statement.line,
+          startColumn: statement.column,
+          endLineNumber: statement.end_line,
+          // the end column is the last character, the range ends after it
+          endColumn: statement.end_column + 1,
+        },
+        options: {
+          inlineClassName: 'opacity-50',
+          hoverMessage: { value: `Ignored: ${statement.reason}` },
+        },
+      }))
+    );
+  }, [ignored]);
+
   return (
     <div className="h-full w-full overflow-hidden flex flex-col bg-background">
       <EditorToolbar fontsLoading={fontsLoading} fontsError={fontsError} />
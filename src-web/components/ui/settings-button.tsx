@@ -7,6 +7,7 @@ import {
   Sun,
   Info,
   Download,
+  FolderDown,
   Loader2,
   CheckCircle,
   AlertCircle,
@@ -47,6 +48,31 @@ export function SettingsButton(props: SettingsButtonProps) {
   } = useUpdates();
 
   const [isCheckingUpdates, setIsCheckingUpdates] = React.useState(false);
+  const [automaticUpdateChecks, setAutomaticUpdateChecks] =
+    React.useState(true);
+
+  React.useEffect(() => {
+    if (!appInfo.isDesktop) return;
+
+    invokeCmd<{ automaticUpdateChecks: boolean }>('cmd_get_settings')
+      .then((settings) =>
+        setAutomaticUpdateChecks(settings.automaticUpdateChecks)
+      )
+      .catch((error) => console.warn('Failed to load settings', error));
+  }, []);
+
+  const handleAutomaticUpdateChecksChange = React.useCallback(
+    async (enabled: boolean) => {
+      setAutomaticUpdateChecks(enabled);
+      try {
+        await invokeCmd('cmd_set_automatic_update_checks', { enabled });
+      } catch (error) {
+        setAutomaticUpdateChecks(!enabled);
+        toast.error(`Failed to save setting: ${error}`);
+      }
+    },
+    []
+  );
 
   const handleCheckForUpdates = React.useCallback(
     async (e: React.MouseEvent) => {
@@ -97,6 +123,30 @@ export function SettingsButton(props: SettingsButtonProps) {
             />
           </div>
 
+          {appInfo.isDesktop && (
+            <>
+              <DropdownMenuSeparator />
+              <DropdownMenuItem
+                onSelect={async () => {
+                  try {
+                    const page = await invokeCmd<string>(
+                      'cmd_export_offline_playground'
+                    );
+                    toast.success(`Offline playground exported to ${page}`);
+                  } catch (error) {
+                    toast.error(
+                      `Failed to export offline playground: ${error}`
+                    );
+                  }
+                }}
+                className="flex items-center gap-2 cursor-pointer"
+              >
+                <FolderDown className="w-4 h-4" />
+                <span>Export Offline Playground</span>
+              </DropdownMenuItem>
+            </>
+          )}
+
           <DropdownMenuSeparator />
           <DropdownMenuLabel className="text-xs font-medium text-muted-foreground tracking-wide uppercase">
             Help us Improve
@@ -123,7 +173,19 @@ export function SettingsButton(props: SettingsButtonProps) {
           </DropdownMenuItem>
 
           <DropdownMenuSeparator />
-          {appInfo.isDesktop && (
+          {appInfo.isDesktop && appInfo.updateChannel === 'packageManager' && (
+            <>
+              <DropdownMenuLabel className="text-xs font-medium text-muted-foreground tracking-wide uppercase">
+                Updates
+              </DropdownMenuLabel>
+              <div className="px-3 py-2 text-xs flex items-center gap-2 text-muted-foreground">
+                <Info className="w-3 h-3" />
+                <span>Updates are managed by your package manager</span>
+              </div>
+              <DropdownMenuSeparator />
+            </>
+          )}
+          {appInfo.isDesktop && appInfo.updateChannel === 'selfUpdate' && (
             <>
               <DropdownMenuLabel className="text-xs font-medium text-muted-foreground tracking-wide uppercase">
                 Updates
@@ -144,6 +206,14 @@ export function SettingsButton(props: SettingsButtonProps) {
                   {isCheckingUpdates ? 'Checking...' : 'Check for Updates'}
                 </span>
               </div>
+              <div className="flex items-center justify-between px-3 py-2 rounded-md">
+                <span className="text-sm">Check automatically</span>
+                <Switch
+                  checked={automaticUpdateChecks}
+                  onCheckedChange={handleAutomaticUpdateChecksChange}
+                  className="data-[state=checked]:bg-primary"
+                />
+              </div>
               {updateCheckStatus && (
                 <div className="px-3 py-2">
                   <div
@@ -1,3 +1,4 @@
 export const NODE_WIDTH = 200;
 export const BASE_NODE_HEIGHT = 35; // height of a one byte node
 export const HEIGHT_OFFSET = 30;
+export const MAPPED_REGION_NODE_SIZE = 16; // a page is too tall to draw to scale
@@ -4,12 +4,17 @@ import { Position } from '@xyflow/react';
 import { useTheme } from '@/providers/theme-provider';
 
 import { generateRandomColor } from '@/lib/utils';
-import { NODE_WIDTH, HEIGHT_OFFSET } from '../constants';
+import {
+  NODE_WIDTH,
+  HEIGHT_OFFSET,
+  MAPPED_REGION_NODE_SIZE,
+} from '../constants';
 import {
   getHeightFromSize,
   createEdge,
   calculateNodePosition,
   generateNodeId,
+  segmentStart,
 } from '../utils';
 import { type NodeData, type EdgeData } from '@/types/visualizer';
 
@@ -43,7 +48,9 @@ export function useHeapNodes({
     const heapNodesInner: NodeData[] = [];
     const connectionsInner: EdgeData[] = [];
     let index = 0;
-    let address = 0x00400000;
+    const heapBase = segmentStart(analyzeResponse.address_space, 'heap');
+    const mappingBase = segmentStart(analyzeResponse.address_space, 'mappings');
+    let address = heapBase;
 
     for (const block of analyzeResponse.heap) {
       const isFree = block.block_state === 'Free';
@@ -56,6 +63,26 @@ export function useHeapNodes({
       );
 
       const label = block.metadata ? block.metadata : 'null';
+
+      // an object allocated with new shows each field at its offset in the block
+      const members = block.members as
+        | {
+            name: string;
+            offset: number;
+            value: string | null;
+            points_to: number | null;
+          }[]
+        | undefined;
+      const fields = members
+        ?.map((member) => {
+          if (member.points_to === null) {
+            return `+${member.offset} ${member.name} = ${member.value ?? '?'}`;
+          }
+
+          const target = heapBase + member.points_to;
+          return `+${member.offset} ${member.name} --> 0x${target.toString(16).toUpperCase()}`;
+        })
+        .join(', ');
       const heapNodeID = generateNodeId(index, isUnallocated, isFree);
 
       const heapNode: NodeData = {
@@ -71,6 +98,7 @@ export function useHeapNodes({
           extraInfo: {
             address: `0x${address.toString(16).toUpperCase()}`,
             isFree,
+            metadata: fields,
           },
         },
         width: NODE_WIDTH,
@@ -127,6 +155,60 @@ export function useHeapNodes({
       index++;
     }
 
+    // mapped regions live outside the heap, above it
+    for (const region of analyzeResponse.mappings ?? []) {
+      const regionNodeID = `mapped-${region.start}`;
+      const regionAddress = mappingBase + region.start;
+      const yPos = calculateNodePosition(
+        heapNodesInner[heapNodesInner.length - 1] || null,
+        MAPPED_REGION_NODE_SIZE,
+        windowHeight,
+        HEIGHT_OFFSET
+      );
+
+      const regionNode: NodeData = {
+        type: 'memoryBlockNode',
+        id: regionNodeID,
+        position: { x: heapXCoordinate, y: yPos },
+        data: {
+          nodeType: 'heap',
+          label: `mmap (${region.requested} of ${region.size} bytes used)`,
+          value: '',
+          size: region.size.toString(),
+          type: 'Mapped',
+          extraInfo: {
+            address: `0x${regionAddress.toString(16).toUpperCase()}`,
+            isFree: false,
+          },
+        },
+        width: NODE_WIDTH,
+        height: getHeightFromSize(MAPPED_REGION_NODE_SIZE),
+        size: region.size,
+      };
+
+      const owner = stackNodes.find(
+        (stackNode) => stackNode.id === region.owner
+      );
+
+      if (owner) {
+        owner.data.extraInfo.pointingToAddress =
+          regionNode.data.extraInfo.address;
+        regionNode.targetPosition = Position.Left;
+
+        connectionsInner.push(
+          createEdge(
+            `e${owner.id}-${regionNodeID}`,
+            'straight',
+            owner.id,
+            regionNodeID,
+            generateRandomColor(theme)
+          )
+        );
+      }
+
+      heapNodesInner.push(regionNode);
+    }
+
     setHeapNodes(heapNodesInner);
     setHeapConnections(connectionsInner);
   }, [
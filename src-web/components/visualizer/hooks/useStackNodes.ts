@@ -4,7 +4,12 @@ import { Position } from '@xyflow/react';
 import { useTheme } from '@/providers/theme-provider';
 import { generateRandomColor } from '@/lib/utils';
 import { NODE_WIDTH, HEIGHT_OFFSET } from '../constants';
-import { getHeightFromSize, createEdge, calculateNodePosition } from '../utils';
+import {
+  getHeightFromSize,
+  createEdge,
+  calculateNodePosition,
+  segmentStart,
+} from '../utils';
 import { type NodeData, type EdgeData } from '@/types/visualizer';
 
 interface UseStackNodesProps {
@@ -29,9 +34,14 @@ export function useStackNodes({
 
     const stackNodesInner: NodeData[] = [];
     const connectionsInner: EdgeData[] = [];
-    let address = 0xbfffffff;
+    // parallel to the stack, the first symbol at the top of the stack and
+    // every symbol after it below the one before
+    const addresses: number[] = analyzeResponse.stack_addresses ?? [];
+    const heapBase = segmentStart(analyzeResponse.address_space, 'heap');
+
+    for (const [index, symbol] of (analyzeResponse.stack as any[]).entries()) {
+      const address = addresses[index] ?? 0;
 
-    for (const symbol of analyzeResponse.stack) {
       if (symbol.hasOwnProperty('Variable')) {
         const yPos = calculateNodePosition(
           stackNodesInner[stackNodesInner.length - 1] || null,
@@ -61,7 +71,6 @@ export function useStackNodes({
           size: symbol.Variable.size,
         };
 
-        address += symbol.Variable.size;
         stackNodesInner.push(stackNode);
       } else if (symbol.hasOwnProperty('Pointer')) {
         const yPos = calculateNodePosition(
@@ -78,13 +87,21 @@ export function useStackNodes({
           sourcePosition: Position.Right,
           data: {
             nodeType: 'stack',
-            label: `*${symbol.Pointer.name}`,
+            // smart pointers are always a single level of indirection
+            label: symbol.Pointer.unique
+              ? `unique_ptr ${symbol.Pointer.name}`
+              : symbol.Pointer.shared
+                ? `shared_ptr ${symbol.Pointer.name}`
+                : `${'*'.repeat(symbol.Pointer.indirection ?? 1)}${symbol.Pointer.name}`,
             value: '',
             size: symbol.Pointer.pointer_size,
             type: 'Pointer',
             extraInfo: {
               address: `0x${address.toString(16).toUpperCase()}`,
-              pointingToLabel: symbol.Pointer.value?.Variable?.name,
+              // pointers to pointers point at another pointer on the stack
+              pointingToLabel:
+                symbol.Pointer.value?.Variable?.name ??
+                symbol.Pointer.value?.Pointer?.name,
             },
           },
           width: NODE_WIDTH,
@@ -92,7 +109,177 @@ export function useStackNodes({
           size: symbol.Pointer.pointer_size,
         };
 
-        address += symbol.Pointer.pointer_size;
+        stackNodesInner.push(stackNode);
+      } else if (symbol.hasOwnProperty('Union')) {
+        const yPos = calculateNodePosition(
+          stackNodesInner[stackNodesInner.length - 1] || null,
+          symbol.Union.size,
+          windowHeight,
+          HEIGHT_OFFSET
+        );
+
+        // every member reads the same bytes, the active one was written last
+        const members = symbol.Union.members as {
+          name: string;
+          value: string | null;
+        }[];
+        const active = members.find(
+          (member) => member.name === symbol.Union.active
+        );
+
+        const stackNode: NodeData = {
+          type: 'memoryBlockNode',
+          id: symbol.Union.name,
+          position: { x: stackXCoordinate, y: yPos },
+          data: {
+            nodeType: 'stack',
+            label: `union ${symbol.Union.name}`,
+            value: active
+              ? `${active.name} = ${active.value}`
+              : 'Uninitialized',
+            size: symbol.Union.size,
+            type: 'Union',
+            extraInfo: {
+              address: `0x${address.toString(16).toUpperCase()}`,
+              metadata: active
+                ? members
+                    .filter((member) => member !== active)
+                    .map((member) => `${member.name} = ${member.value}`)
+                    .join(', ')
+                : undefined,
+            },
+          },
+          width: NODE_WIDTH,
+          height: getHeightFromSize(symbol.Union.size),
+          size: symbol.Union.size,
+        };
+
+        stackNodesInner.push(stackNode);
+      } else if (symbol.hasOwnProperty('Object')) {
+        const yPos = calculateNodePosition(
+          stackNodesInner[stackNodesInner.length - 1] || null,
+          symbol.Object.size,
+          windowHeight,
+          HEIGHT_OFFSET
+        );
+
+        const fields = symbol.Object.fields as {
+          name: string;
+          value: string | null;
+          pointer: any | null;
+        }[];
+
+        // a pointer field shows the heap address it points to
+        const fieldValue = (field: (typeof fields)[number]) => {
+          if (!field.pointer) return field.value ?? '?';
+
+          const heapPointer = field.pointer.Pointer.heap_pointer as number | null;
+          return heapPointer === null
+            ? 'nullptr'
+            : `0x${(heapBase + heapPointer).toString(16).toUpperCase()}`;
+        };
+
+        const stackNode: NodeData = {
+          type: 'memoryBlockNode',
+          id: symbol.Object.name,
+          position: { x: stackXCoordinate, y: yPos },
+          data: {
+            nodeType: 'stack',
+            label: `${symbol.Object.class_name} ${symbol.Object.name}`,
+            value: fields
+              .map((field) => `${field.name} = ${fieldValue(field)}`)
+              .join(', '),
+            size: symbol.Object.size,
+            type: 'Object',
+            extraInfo: {
+              address: `0x${address.toString(16).toUpperCase()}`,
+            },
+          },
+          width: NODE_WIDTH,
+          height: getHeightFromSize(symbol.Object.size),
+          size: symbol.Object.size,
+        };
+
+        stackNodesInner.push(stackNode);
+      } else if (symbol.hasOwnProperty('Array')) {
+        const yPos = calculateNodePosition(
+          stackNodesInner[stackNodesInner.length - 1] || null,
+          symbol.Array.size,
+          windowHeight,
+          HEIGHT_OFFSET
+        );
+
+        // the elements are laid out row after row, the grid shows the rows
+        // and the value the flat memory underneath
+        const dimensions = symbol.Array.dimensions as number[];
+        const elements = symbol.Array.elements as (string | null)[];
+        const columns = dimensions[dimensions.length - 1];
+
+        const grid =
+          dimensions.length > 1
+            ? Array.from({ length: dimensions[0] }, (_, row) =>
+                elements.slice(row * columns, (row + 1) * columns)
+              )
+            : undefined;
+
+        const stackNode: NodeData = {
+          type: 'memoryBlockNode',
+          id: symbol.Array.name,
+          position: { x: stackXCoordinate, y: yPos },
+          data: {
+            nodeType: 'stack',
+            label: `${symbol.Array.name}${dimensions
+              .map((dimension) => `[${dimension}]`)
+              .join('')}`,
+            value: elements.map((element) => element ?? '?').join(', '),
+            size: symbol.Array.size,
+            type: 'Array',
+            extraInfo: {
+              address: `0x${address.toString(16).toUpperCase()}`,
+              grid,
+            },
+          },
+          width: NODE_WIDTH,
+          height: getHeightFromSize(symbol.Array.size),
+          size: symbol.Array.size,
+        };
+
+        stackNodesInner.push(stackNode);
+      } else if (symbol.hasOwnProperty('Mutex')) {
+        const yPos = calculateNodePosition(
+          stackNodesInner[stackNodesInner.length - 1] || null,
+          symbol.Mutex.size,
+          windowHeight,
+          HEIGHT_OFFSET
+        );
+
+        // the thread that locked the mutex owns it until it unlocks it
+        const owner = symbol.Mutex.owner as number | null;
+
+        const stackNode: NodeData = {
+          type: 'memoryBlockNode',
+          id: symbol.Mutex.name,
+          position: { x: stackXCoordinate, y: yPos },
+          data: {
+            nodeType: 'stack',
+            label: `mutex ${symbol.Mutex.name}`,
+            value:
+              owner === null
+                ? 'Unlocked'
+                : owner === 0
+                  ? 'Locked by main'
+                  : `Locked by thread ${owner}`,
+            size: symbol.Mutex.size,
+            type: 'Mutex',
+            extraInfo: {
+              address: `0x${address.toString(16).toUpperCase()}`,
+            },
+          },
+          width: NODE_WIDTH,
+          height: getHeightFromSize(symbol.Mutex.size),
+          size: symbol.Mutex.size,
+        };
+
         stackNodesInner.push(stackNode);
       }
     }
@@ -16,6 +16,7 @@ export type MemoryBlockNode = Node<{
     address: string;
     pointingToAddress?: string;
     metadata?: string;
+    grid?: (string | null)[][];
     isFree?: boolean;
   };
 }>;
@@ -23,21 +24,47 @@ export type MemoryBlockNode = Node<{
 function getColorFromType(type: string, theme?: string): string {
   const lightColors: { [key: string]: string } = {
     Integer: '#8b4513',
+    Short: '#8b4513',
+    UnsignedShort: '#8b4513',
+    UnsignedInt: '#8b4513',
+    Long: '#8b4513',
+    UnsignedLong: '#8b4513',
+    LongLong: '#8b4513',
+    UnsignedLongLong: '#8b4513',
     Float: '#f78092',
     Double: '#fb7500',
     Char: '#a31b03',
     Bool: '#118a11',
+    String: '#7b3fa0',
     Pointer: '#3484da',
+    Union: '#2a9d8f',
+    Object: '#e76f51',
+    Array: '#457b9d',
+    Mutex: '#e9c46a',
+    Mapped: '#6c757d',
     LB: 'red',
   };
 
   const darkColors: { [key: string]: string } = {
     Integer: '#a76638',
+    Short: '#a76638',
+    UnsignedShort: '#a76638',
+    UnsignedInt: '#a76638',
+    Long: '#a76638',
+    UnsignedLong: '#a76638',
+    LongLong: '#a76638',
+    UnsignedLongLong: '#a76638',
     Float: '#f78092',
     Double: '#fb7500',
     Char: '#ff2600',
     Bool: '#11bd11',
+    String: '#b07fd6',
     Pointer: '#3484da',
+    Union: '#2a9d8f',
+    Object: '#e76f51',
+    Array: '#457b9d',
+    Mutex: '#e9c46a',
+    Mapped: '#6c757d',
     LB: 'red',
   };
 
@@ -114,6 +141,15 @@ export function MemoryBlockNode(props: NodeProps<MemoryBlockNode>) {
       marginTop: '5px',
       fontSize: '12px',
     },
+    grid: {
+      marginTop: '5px',
+      fontSize: '12px',
+      borderCollapse: 'collapse' as const,
+    },
+    gridCell: {
+      padding: '0 6px',
+      textAlign: 'center' as const,
+    },
     value: {
       fontSize: '14px',
       display: 'block',
@@ -183,6 +219,25 @@ export function MemoryBlockNode(props: NodeProps<MemoryBlockNode>) {
 
       {!isCompact && (
         <>
+          {props.data.extraInfo.grid && (
+            <table style={baseStyles.grid} className="text-foreground">
+              <tbody>
+                {props.data.extraInfo.grid.map((row, i) => (
+                  <tr key={i}>
+                    {row.map((element, j) => (
+                      <td
+                        key={j}
+                        style={baseStyles.gridCell}
+                        className="border border-border"
+                      >
+                        {element ?? '?'}
+                      </td>
+                    ))}
+                  </tr>
+                ))}
+              </tbody>
+            </table>
+          )}
           <div style={baseStyles.value} className="text-foreground">
             {props.data.value}
           </div>
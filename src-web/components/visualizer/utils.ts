@@ -1,6 +1,7 @@
 import { MarkerType } from '@xyflow/react';
 import { BASE_NODE_HEIGHT } from './constants';
 import { type NodeData, type EdgeData } from '@/types/visualizer';
+import type { Segment, SegmentKind } from '@/types/analyzer';
 
 export function getHeightFromSize(size: number): number {
   return size * BASE_NODE_HEIGHT;
@@ -53,9 +54,21 @@ export function generateNodeId(
 
 export function calculateMemoryUsage(nodes: NodeData[]): number {
   return nodes.reduce((acc, node) => {
-    if (node.id.includes('free') || node.id.includes('unallocated')) {
+    if (
+      node.id.includes('free') ||
+      node.id.includes('unallocated') ||
+      node.id.includes('mapped')
+    ) {
       return acc;
     }
     return acc + node.size;
   }, 0);
 }
+
+// where an area of the address space starts, as laid out by the analyzer
+export function segmentStart(
+  addressSpace: Segment[] | undefined,
+  kind: SegmentKind
+): number {
+  return addressSpace?.find((segment) => segment.kind === kind)?.start ?? 0;
+}
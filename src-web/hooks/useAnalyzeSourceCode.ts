@@ -1,24 +1,142 @@
 import { useQuery } from '@tanstack/react-query';
+import { Channel } from '@tauri-apps/api/core';
 import { invokeCmd } from '@/lib/tauri';
+import { applyLiveUpdate, type LiveUpdate } from '@/lib/live-updates';
 import { createSourceCodeKey } from '@/lib/source-code-compression';
 import { appInfo } from '@/lib/appInfo';
+import { BASE_NODE_HEIGHT } from '@/components/visualizer/constants';
+import type { CrossReference } from '@/types/analyzer';
+
+export interface StackFrame {
+  function: string;
+  line: number;
+  depth: number;
+  symbols: any[];
+  // parameters passed in registers, only with the registers calling convention
+  registers: RegisterArgument[];
+  // 0 for the main thread, each spawned thread has a `thread_spawn` frame at depth 0
+  thread: number;
+}
+
+export interface RegisterArgument {
+  register: string;
+  symbol: any;
+}
+
+export interface ScopeExit {
+  // null when the block ran at the top level
+  function: string | null;
+  line: number;
+  depth: number;
+  symbols: any[];
+}
+
+// the shapes the analyzer serializes, generated from its types with `make types`
+export type {
+  CrossReference,
+  DataRace,
+  Diagnostic,
+  FreeRange,
+  HeapGrowthEvent,
+  HeapLifetime,
+  IgnoredStatement,
+  LockEvent,
+  LoopIteration,
+  LossyCast,
+  MappedRegion,
+  MemoryTag,
+  ScopeReference,
+  SourceRef,
+  Temporary,
+  ThreadStep,
+  ValueChange,
+  ValueCopy,
+  ValueMove,
+} from '@/types/analyzer';
+
 export interface AnalyzeSourceCodeResponse {
   stack: any[];
   heap: any[];
+  // frames of the function calls, in call order, all of them returned
+  frames?: StackFrame[];
+  // blocks that ended and the locals they popped, in the order they ended
+  scope_exits?: ScopeExit[];
+  // the statement behind each symbol, heap block, frame and scope exit
+  cross_reference?: CrossReference;
+  // the seed blocks were placed with, only set when the layout was re-rolled
+  seed?: number;
   error?: {
     message: string;
     line_number?: number;
     column_number?: number;
+    // set when the analyzer's self-check caught an inconsistent layout
+    internal?: boolean;
   };
 }
 
-async function analyzeSourceDesktop(
+export interface LayoutHint {
+  kind: 'panel';
+  height: number;
+  cellHeight: number;
+}
+
+// the heap starts as big as the panel showing it, one cell per byte
+function layoutHint(): LayoutHint {
+  return {
+    kind: 'panel',
+    height: window.innerHeight,
+    cellHeight: BASE_NODE_HEIGHT,
+  };
+}
+
+// the document the editor of the window is, the backend keeps the last
+// response it sent for it and only sends what changed since
+const LIVE_DOCUMENT = 'editor';
+
+// the last response received for the document, which the changes the
+// backend sends next apply to
+let liveResponse: AnalyzeSourceCodeResponse | null = null;
+
+// analyses run one after the other, so changes are applied in the order the
+// backend computed them
+let liveQueue: Promise<unknown> = Promise.resolve();
+
+async function analyzeSourceLive(
   sourceCode: string
 ): Promise<AnalyzeSourceCodeResponse> {
-  const response = await invokeCmd<AnalyzeSourceCodeResponse>(
-    'cmd_analyze_source_code',
-    { input: sourceCode }
+  // a reloaded window has no response for the backend's changes to apply to
+  if (liveResponse === null) {
+    await invokeCmd('cmd_close_live_document', { document: LIVE_DOCUMENT });
+  }
+
+  const onUpdate = new Channel<LiveUpdate<AnalyzeSourceCodeResponse>>();
+  const update = new Promise<LiveUpdate<AnalyzeSourceCodeResponse>>(
+    (resolve) => {
+      onUpdate.onmessage = resolve;
+    }
   );
+
+  await invokeCmd('cmd_analyze_live', {
+    document: LIVE_DOCUMENT,
+    input: sourceCode,
+    onUpdate,
+  });
+
+  liveResponse = applyLiveUpdate(liveResponse, await update);
+  return liveResponse;
+}
+
+async function analyzeSourceDesktop(
+  sourceCode: string
+): Promise<AnalyzeSourceCodeResponse> {
+  await invokeCmd('cmd_set_layout_hint', { layout: layoutHint() });
+
+  const response = liveQueue.then(() => analyzeSourceLive(sourceCode));
+  liveQueue = response.catch(() => {
+    // the next analysis sends the whole response again
+    liveResponse = null;
+  });
+
   return response;
 }
 
@@ -29,7 +147,12 @@ async function analyzeSourceWeb(
   const wasm = await import(`@mv/wasm`);
   await wasm.default();
 
-  return JSON.parse(await wasm.analyze_source_code(input));
+  return JSON.parse(
+    await wasm.analyze_source_code(
+      input,
+      JSON.stringify({ layout: layoutHint() })
+    )
+  );
 }
 
 export function useAnalyzeSourceCode(sourceCode: string) {
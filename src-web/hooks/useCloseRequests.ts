@@ -0,0 +1,57 @@
+import React from 'react';
+
+import { getCurrentWebviewWindow } from '@tauri-apps/api/webviewWindow';
+
+import { invokeCmd } from '@/lib/tauri';
+import { appInfo } from '@/lib/appInfo';
+
+/**
+ * Answers the backend's close requests for this window. The backend keeps the
+ * window open while `hasUnsavedDocuments` returns true.
+ *
+ * The backend closes the window anyway when no answer comes in time, so a
+ * close with unsaved documents is vetoed right away and the user is asked
+ * afterwards. Discarding the changes closes the window again, which is then
+ * approved.
+ */
+export function useCloseRequests(
+  hasUnsavedDocuments: () => boolean,
+  discardChanges: () => void
+) {
+  const [isPromptOpen, setIsPromptOpen] = React.useState(false);
+
+  const hasUnsavedDocumentsRef = React.useRef(hasUnsavedDocuments);
+  hasUnsavedDocumentsRef.current = hasUnsavedDocuments;
+
+  React.useEffect(() => {
+    if (!appInfo.isDesktop) return;
+
+    const unlistenCloseRequested = getCurrentWebviewWindow().listen(
+      'close-requested',
+      (event) => {
+        const hasUnsaved = hasUnsavedDocumentsRef.current();
+
+        invokeCmd('cmd_resolve_close_request', {
+          requestId: event.payload as number,
+          hasUnsavedDocuments: hasUnsaved,
+        });
+
+        if (hasUnsaved) setIsPromptOpen(true);
+      }
+    );
+
+    return () => {
+      unlistenCloseRequested.then((fn) => fn());
+    };
+  }, []);
+
+  const cancelClose = React.useCallback(() => setIsPromptOpen(false), []);
+
+  const discardAndClose = React.useCallback(() => {
+    setIsPromptOpen(false);
+    discardChanges();
+    getCurrentWebviewWindow().close();
+  }, [discardChanges]);
+
+  return { isPromptOpen, cancelClose, discardAndClose };
+}
@@ -12,6 +12,10 @@ const LANGUAGE_CONFIG = {
 // - Variable declarations:
 //   e.g, int x = 12;
 //        int p = x; (p will be assigned the value of x)
+//        int y = 2 + 3 * x; (+ - * / are evaluated as you'd expect)
+//        int n = -5; (so are a sign in front like -x or +x)
+//        int a, b = 3, *q; (one declaration for each name)
+//        x += 2; (also -=, *= and /=)
   
 // - Pointer declarations:
 //   e.g., int* p = nullptr;
@@ -19,17 +23,169 @@ const LANGUAGE_CONFIG = {
 // - Pointer references to variables:
 //   e.g., p = &x;
 
+// - Pointers to pointers:
+//   e.g., int** pp = &p;
+
 // - Heap pointer declarations:
 //   e.g., p = new int;
+//   e.g., int* a = new int[4];
+
+// - C-style heap allocation (calloc zeroes the block):
+//   e.g., p = (int*)malloc(3 * sizeof(int));
+//         free(p);
+//   (a size in bytes, like malloc(12), has to hold whole
+//   values of the type the block is for)
+
+// - sizeof a type or an expression:
+//   e.g., int size = sizeof(double);
+
+// - Mapping memory outside the heap, in whole pages
+//   of 4096 bytes, and unmapping all of it:
+//   e.g., int* m = (int*)mmap(100 * sizeof(int));
+//         munmap(m, 100 * sizeof(int));
+
+// - Resizing a heap block (it moves when it can't grow in place):
+//   e.g., p = (int*)realloc(p, 4 * sizeof(int));
+
+// - Moving a heap pointer through its block:
+//   e.g., p++;
+//         p = p + 2;
+
+// - Writing an element of a heap block through a pointer
+//   (an index out of bounds is an error, or skipped with
+//   a warning in permissive mode):
+//   e.g., p[2] = 7;
+
+// - Filling or copying whole elements of heap blocks and
+//   arrays (memset sets every byte, so an int set to 1
+//   holds 0x01010101, and overlapping copies are warned about):
+//   e.g., memset(p, 0, 4 * sizeof(int));
+//         memcpy(q, p, 2 * sizeof(int));
+
+// - Copying and measuring null-terminated strings in char
+//   heap blocks and arrays (a copy past the end of a heap
+//   block is warned about and writes over the blocks after it):
+//   e.g., strcpy(s, "hello");
+//         int n = strlen(s);
+
+// - Swapping two variables or two pointers:
+//   e.g., std::swap(x, y);
+
+// - std::unique_ptr, which frees its block by itself
+//   and can be moved but not copied:
+//   e.g., std::unique_ptr<int> u = std::make_unique<int>(5);
+//         std::unique_ptr<int> v = std::move(u);
+//         v.reset();
+
+// - std::shared_ptr, which counts its owners in a control
+//   block on the heap and frees its block with the last one:
+//   e.g., std::shared_ptr<int> s = std::make_shared<int>(5);
+//         std::shared_ptr<int> t = s;
+//         s.reset();
+
+// - Arrays of up to two dimensions, laid out row after
+//   row (elements left out of the initializer are 0):
+//   e.g., int m[2][3] = {{1, 2, 3}, {4}};
+//         int a[] = {1, 2, 3}; (the size comes from the initializer)
+//         m[1][2] = 9;
+
+// - Threads with stacks of their own sharing the heap,
+//   run at the end of the program in turns picked
+//   from the seed:
+//   e.g., thread_spawn { int local = 1; *p = local; }
+
+// - Mutexes, which threads lock to take turns writing
+//   the heap (blocks written by several threads
+//   without one are flagged as data races):
+//   e.g., std::mutex m;
+//         thread_spawn { m.lock(); *p = 2; m.unlock(); }
+
+// - Conditionals, only the branch taken runs
+//   (< > <= >= == != compare values, a pointer holds
+//   when it isn't nullptr):
+//   e.g., if (x >= 10) { p = new int; } else { p = &x; }
+//         if (p) *p = 3;
+
+// - for and while loops, which stop with an error
+//   after 1000 iterations:
+//   e.g., for (int i = 0; i < 3; i++) { int* b = new int; }
+//         while (x > 0) x--;
+
+// - Passing arguments by value (the function gets a copy)
+//   or by pointer (the function writes to the caller's
+//   variable):
+//   e.g., void set(int* p, int n) { *p = n; }
+//         set(&x, 5);
+
+// - Using the value a function returns:
+//   e.g., int y = square(3) + 1;
+
+// - Recursion, with a stack frame for every call (calls
+//   nested more than 64 deep overflow the stack):
+//   e.g., int fact(int n) { if (n <= 1) return 1; return n * fact(n - 1); }
+
+// - std::string, which keeps up to 15 characters in
+//   itself and longer strings in a buffer on the heap:
+//   e.g., std::string s = "short";
+//         s = "a string longer than fifteen";
+
+// - Reading and writing raw addresses from the diagram (the stack
+//   grows down from 0xC0000000, so a first int is at 0xBFFFFFFC):
+//   e.g., poke(0x00400000, 7);
+//         int v = peek(0xBFFFFFFC);
+
+// - Casting a value to another type (doubles are truncated):
+//   e.g., int n = (int)3.7;
+//         char c = static_cast<char>(65);
+
+// - Unions, whose members share the same bytes:
+//   e.g., union Value { int i; char c; };
+//         Value v;
+//         v.i = 65;
+//         char c = v.c;
+
+// - Classes, whose methods read and write the fields of their object:
+//   e.g., class Counter { public: int count; void add(int n) { count = count + n; } };
+//         Counter c;
+//         c.count = 0;
+//         c.add(2);
+
+// - Structs, which are classes whose members are public by default:
+//   e.g., struct Point { int x; int y; };
+//         Point p;
+//         p.x = 3;
+
+// - Brace initializing the fields of a class without a constructor
+//   in order (the fields left out are 0), or the first member of a union:
+//   e.g., class Point { public: int x; int y; };
+//         Point p = {1, 2};
+
+// - Constructors and destructors, which run when an object is declared and goes out of scope:
+//   e.g., class Buffer { public: int* data; Buffer() { data = new int; } ~Buffer() { delete data; } };
+//         { Buffer b; }
+
+// - Allocating an object on the heap, whose block shows each field at its offset:
+//   e.g., Counter* c = new Counter;
+//         c->count = 3;
+//         delete c;
 
 // - Setting a pointer to nullptr:
 //   e.g., p = nullptr;
 
+// - Casting a void pointer back to a typed pointer
+//   (a warning shows the address when it isn't aligned
+//   to the size of the type):
+//   e.g., void* v = malloc(8);
+//         int* q = (int*)v;
+
 // - Dereferencing a pointer:
 //   e.g., *p = 8;
+//         *p = *p + 1;
 
-// - Deleting a pointer:
+// - Deleting a pointer (a block from new[] needs delete[],
+//   using the other form is an error):
 //   e.g., delete p;
+//         delete[] a;
 
 // Each of these actions will have visualizations 
 // to help you understand memory management.
@@ -48,6 +204,11 @@ const LANGUAGE_CONFIG = {
 
 // Experiment with other primitive datatypes  
 // too! (int, double, char, bool) (e.g., double* p = new double; or double x = 3.14;)
+// Variables can also be short, long, long long or unsigned,
+// and long is as wide as a pointer (e.g., unsigned long n = 5;)
+// Chars can be escapes like '\\n', '\\0', '\\\\' or '\\x41'
+// Floating point numbers are doubles unless they end with f,
+// like 3.5f, which makes a 4-byte float
 
 // We are actively working on expanding the 
 // current parser to support more advanced 
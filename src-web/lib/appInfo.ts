@@ -3,6 +3,19 @@ import { invokeCmd } from './tauri';
 const isDesktop =
   typeof window !== 'undefined' && (window as any).__TAURI_INTERNALS__;
 
+export interface CoreCapabilities {
+  version: string;
+  sessionSchemaVersion: number;
+  dialects: string[];
+  heapStrategies: string[];
+  architectures: string[];
+  callingConventions: string[];
+  heapOrders: string[];
+  strictness: string[];
+  placementStrategies: string[];
+  exports: string[];
+}
+
 export interface AppInfo {
   isDev: boolean;
   isDesktop: boolean;
@@ -11,6 +24,11 @@ export interface AppInfo {
   name: string;
   appDataDir?: string;
   appLogDir?: string;
+  isPortable?: boolean;
+  // how the install gets its updates, deb, rpm and flatpak installs are
+  // updated by the package manager
+  updateChannel?: 'selfUpdate' | 'portable' | 'packageManager' | 'unsupported';
+  core?: CoreCapabilities;
 }
 
 declare const __APP_VERSION__: string;
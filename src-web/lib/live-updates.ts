@@ -0,0 +1,93 @@
+// what `cmd_analyze_live` sends for a document, the whole response the first
+// time and the records that changed since the last response after that
+export type LiveUpdate<T> =
+  | { kind: 'full'; response: T }
+  | { kind: 'changes'; changes: RecordChange[] };
+
+// a change to the last response, at a JSON pointer like `/heap/3`
+export type RecordChange =
+  | { op: 'set'; path: string; value: unknown }
+  | { op: 'remove'; path: string }
+  | { op: 'truncate'; path: string; length: number };
+
+type Container = Record<string, unknown> | unknown[];
+
+function pathSegments(path: string): string[] {
+  if (path === '') return [];
+
+  return path
+    .slice(1)
+    .split('/')
+    .map((segment) => segment.replace(/~1/g, '/').replace(/~0/g, '~'));
+}
+
+// applies the update to the last response, copying the lists and objects on
+// the way to each change instead of changing them, so records that didn't
+// change keep their identity
+export function applyLiveUpdate<T>(
+  previous: T | null,
+  update: LiveUpdate<T>
+): T {
+  if (update.kind === 'full') return update.response;
+
+  if (previous === null) {
+    throw new Error('Received changes before the response they apply to');
+  }
+
+  let root = previous as unknown;
+  const copied = new Set<unknown>();
+
+  const copy = (container: Container): Container => {
+    if (copied.has(container)) return container;
+
+    const clone = Array.isArray(container) ? [...container] : { ...container };
+    copied.add(clone);
+    return clone;
+  };
+
+  for (const change of update.changes) {
+    const segments = pathSegments(change.path);
+
+    if (segments.length === 0) {
+      if (change.op === 'set') {
+        root = change.value;
+        continue;
+      }
+
+      if (change.op === 'truncate') {
+        root = copy(root as Container);
+        (root as unknown[]).length = change.length;
+      }
+
+      continue;
+    }
+
+    root = copy(root as Container);
+    let parent = root as Record<string, unknown>;
+
+    for (const segment of segments.slice(0, -1)) {
+      const child = copy(parent[segment] as Container);
+      parent[segment] = child;
+      parent = child as Record<string, unknown>;
+    }
+
+    const last = segments[segments.length - 1];
+
+    switch (change.op) {
+      case 'set':
+        parent[last] = change.value;
+        break;
+      case 'remove':
+        delete parent[last];
+        break;
+      case 'truncate': {
+        const list = copy(parent[last] as Container) as unknown[];
+        list.length = change.length;
+        parent[last] = list;
+        break;
+      }
+    }
+  }
+
+  return root as T;
+}
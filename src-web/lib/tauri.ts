@@ -5,9 +5,22 @@ export type TauriCmd =
   | 'cmd_metadata'
   | 'cmd_check_for_updates'
   | 'cmd_download_and_install_update'
+  | 'cmd_analyze_live'
   | 'cmd_analyze_source_code'
+  | 'cmd_close_live_document'
+  | 'cmd_compute_struct_layout'
+  | 'cmd_export_ascii_diagram'
+  | 'cmd_export_embed_snippet'
+  | 'cmd_export_offline_playground'
+  | 'cmd_get_previous_result'
+  | 'cmd_get_settings'
   | 'cmd_get_system_fonts'
-  | 'cmd_open_url';
+  | 'cmd_get_third_party_licenses'
+  | 'cmd_open_url'
+  | 'cmd_resolve_close_request'
+  | 'cmd_set_automatic_update_checks'
+  | 'cmd_set_layout_hint'
+  | 'cmd_take_opened_file';
 
 export async function invokeCmd<T>(
   cmd: TauriCmd,
@@ -7,11 +7,20 @@ import { AlertCircle, ArrowLeft } from 'lucide-react';
 
 import { appInfo } from '@/lib/appInfo';
 import { WindowTitleBar } from '@/components/desktop/window-titlebar';
+import { ClosePrompt } from '@/components/desktop/close-prompt';
 import { useEditorSettingsStore } from '@/stores/editor';
+import { useDocumentStore } from '@/stores/documents';
+import { useCloseRequests } from '@/hooks/useCloseRequests';
 import { SettingsButton } from '@/components/ui/settings-button';
 
 export const Route = createRootRoute({
   component: () => {
+    const discardChanges = useDocumentStore((state) => state.discardChanges);
+    const { isPromptOpen, cancelClose, discardAndClose } = useCloseRequests(
+      () => useDocumentStore.getState().isDirty(),
+      discardChanges
+    );
+
     React.useEffect(() => {
       useEditorSettingsStore.getState().initializeStore();
 
@@ -62,6 +71,12 @@ export const Route = createRootRoute({
           </div>
         </div>
 
+        <ClosePrompt
+          isOpen={isPromptOpen}
+          onCancel={cancelClose}
+          onDiscard={discardAndClose}
+        />
+
         {appInfo.isDev && <TanStackRouterDevtools />}
       </>
     );
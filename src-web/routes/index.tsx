@@ -9,15 +9,30 @@ import {
   ResizableHandle,
 } from '@/components/ui/resizable';
 import { useAnalyzeSourceCode } from '@/hooks/useAnalyzeSourceCode';
+import { useDocumentStore } from '@/stores/documents';
+import { invokeCmd } from '@/lib/tauri';
+import { appInfo } from '@/lib/appInfo';
 
 export const Route = createFileRoute('/')({
   component: Index,
 });
 
 function Index() {
-  const [sourceCode, setSourceCode] = React.useState('');
+  const sourceCode = useDocumentStore((state) => state.source);
+  const editDocument = useDocumentStore((state) => state.editDocument);
+  const openDocument = useDocumentStore((state) => state.openDocument);
   const [visualizerPanelSize, setVisualizerPanelSize] = React.useState(50);
 
+  React.useEffect(() => {
+    if (!appInfo.isDesktop) return;
+
+    invokeCmd<{ path: string; sourceCode: string } | null>(
+      'cmd_take_opened_file'
+    ).then((openedFile) => {
+      if (openedFile) openDocument(openedFile.sourceCode);
+    });
+  }, [openDocument]);
+
   const {
     data: analyzeResponse,
     isLoading: isAnalyzing,
@@ -32,8 +47,10 @@ function Index() {
             <div className="flex-1 min-h-0 overflow-auto">
               <Editor
                 code={sourceCode}
-                onChange={setSourceCode}
+                onChange={editDocument}
                 analyzeError={analyzeError}
+                ignored={analyzeResponse?.ignored}
+                warnings={analyzeResponse?.warnings}
               />
             </div>
           </div>
@@ -0,0 +1,35 @@
+import { create } from 'zustand';
+
+interface DocumentStore {
+  // the source code in the editor
+  source: string;
+  // the source code as it was opened, edits past it are unsaved
+  savedSource: string;
+
+  openDocument: (source: string) => void;
+  editDocument: (source: string) => void;
+  discardChanges: () => void;
+  isDirty: () => boolean;
+}
+
+export const useDocumentStore = create<DocumentStore>((set, get) => ({
+  source: '',
+  savedSource: '',
+
+  openDocument: (source: string) => {
+    set({ source, savedSource: source });
+  },
+
+  editDocument: (source: string) => {
+    set({ source });
+  },
+
+  discardChanges: () => {
+    set((state) => ({ savedSource: state.source }));
+  },
+
+  isDirty: () => {
+    const { source, savedSource } = get();
+    return source !== savedSource;
+  },
+}));
@@ -35,6 +35,43 @@ const FALLBACK_FONTS = [
   'Garamond',
 ];
 
+// bumped whenever the settings saved in localStorage change shape, with an
+// upgrade from the previous version added to SETTINGS_UPGRADES
+const SETTINGS_SCHEMA_VERSION = 1;
+
+// upgrades the settings from the version at each index to the next one,
+// version 0 being the settings saved before they had a version
+const SETTINGS_UPGRADES: ((
+  settings: Record<string, unknown>
+) => Record<string, unknown>)[] = [(settings) => settings];
+
+const saveSettings = (settings: EditorSettings) => {
+  localStorage.setItem(
+    'editor-settings',
+    JSON.stringify({ ...settings, schemaVersion: SETTINGS_SCHEMA_VERSION })
+  );
+};
+
+// upgrades settings saved by an older version, settings saved by a newer
+// version are ignored rather than misread
+const upgradeSettings = (
+  stored: Record<string, unknown>
+): Partial<EditorSettings> | null => {
+  const { schemaVersion = 0, ...settings } = stored;
+
+  if (
+    typeof schemaVersion !== 'number' ||
+    schemaVersion > SETTINGS_SCHEMA_VERSION
+  ) {
+    return null;
+  }
+
+  return SETTINGS_UPGRADES.slice(schemaVersion).reduce(
+    (upgraded, upgrade) => upgrade(upgraded),
+    settings
+  ) as Partial<EditorSettings>;
+};
+
 const DEFAULT_SETTINGS: EditorSettings = {
   fontFamily: 'Courier New',
   fontSize: 14,
@@ -66,7 +103,7 @@ export const useEditorSettingsStore = create<EditorStore>((set, get) => ({
     set((state) => {
       const updated = { ...state.settings, ...newSettings };
       if (typeof window !== 'undefined') {
-        localStorage.setItem('editor-settings', JSON.stringify(updated));
+        saveSettings(updated);
       }
       return { settings: updated };
     });
@@ -93,7 +130,7 @@ export const useEditorSettingsStore = create<EditorStore>((set, get) => ({
   resetToDefaults: () => {
     set({ settings: DEFAULT_SETTINGS });
     if (typeof window !== 'undefined') {
-      localStorage.setItem('editor-settings', JSON.stringify(DEFAULT_SETTINGS));
+      saveSettings(DEFAULT_SETTINGS);
     }
   },
 
@@ -123,8 +160,8 @@ export const useEditorSettingsStore = create<EditorStore>((set, get) => ({
       const stored = localStorage.getItem('editor-settings');
       if (stored) {
         try {
-          const parsed = JSON.parse(stored);
-          set({ settings: { ...DEFAULT_SETTINGS, ...parsed } });
+          const upgraded = upgradeSettings(JSON.parse(stored));
+          set({ settings: { ...DEFAULT_SETTINGS, ...upgraded } });
         } catch {
           set({ settings: DEFAULT_SETTINGS });
         }
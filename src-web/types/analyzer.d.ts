@@ -0,0 +1,436 @@
+// Generated by `make types` from the types of the analyzer, don't edit it by hand
+
+// Represents how many times a heap block was read and written
+export interface AccessCount {
+  reads: number;
+  writes: number;
+}
+
+// Represents the type of memory allocation for a symbol.
+export type AllocationType = 'Stack' | 'Heap' | 'Mapped' | 'Dangling' | 'Null';
+
+// Represents the response of an analysis that failed
+export interface AnalysisError {
+  error: AnalysisErrorDetails;
+}
+
+// Represents what went wrong in an analysis
+export interface AnalysisErrorDetails {
+  column_number?: number | null;
+  internal?: boolean | null;
+  line_number?: number | null;
+  message: string;
+}
+
+// Represents the result of an analysis, as the WASM build and the desktop app both respond with it
+export interface AnalysisResult {
+  address_space: Segment[];
+  cross_reference: CrossReference;
+  frames: StackFrame[];
+  free_list: FreeRange[];
+  heap: HeapBlock[];
+  heap_capacity: number;
+  heap_overview: HeapOverview | null;
+  heatmap: AccessCount[];
+  ignored: IgnoredStatement[];
+  mappings: MappedRegion[];
+  scope_exits: ScopeExit[];
+  stack: Symbol[];
+  stack_addresses: number[];
+  warnings: Diagnostic[];
+}
+
+// Represents the options the analyzer runs with
+export interface AnalyzerOptions {
+  architecture?: Architecture;
+  callingConvention?: CallingConvention;
+  defines?: Record<string, string>;
+  diagnosticLimit?: number | null;
+  heapOrder?: HeapOrder;
+  layout?: LayoutHint | null;
+  lineTags?: Record<string, MemoryTag>;
+  maxCallDepth?: number | null;
+  seed?: number | null;
+  selfCheck?: boolean;
+  showTemporaries?: boolean;
+  strictness?: Strictness;
+  tags?: Record<string, MemoryTag>;
+  trackHistory?: boolean;
+}
+
+// Represents the data model of the machine being simulated
+export type Architecture = 'ilp32' | 'lp64';
+
+// Represents a field of the object a heap block holds
+export interface BlockMember {
+  name: string;
+  offset: number;
+  points_to: number | null;
+  size: number;
+  value: string | null;
+}
+
+// Represents where arguments are placed when a function is called
+export type CallingConvention = 'stack' | 'registers';
+
+// Represents the features supported by this version of the core
+export interface Capabilities {
+  architectures: Architecture[];
+  callingConventions: CallingConvention[];
+  dialects: string[];
+  exports: string[];
+  heapOrders: HeapOrder[];
+  heapStrategies: string[];
+  placementStrategies: PlacementStrategy[];
+  sessionSchemaVersion: number;
+  strictness: Strictness[];
+  version: string;
+}
+
+// Represents a field of an object
+export interface ClassField {
+  ftype: Type;
+  name: string;
+  pointer: Symbol | null;
+  public: boolean;
+  value: string | null;
+}
+
+// Represents the contents of a block being copied into a new block when it is reallocated
+export interface CopyEvent {
+  bytes: number;
+  from: number;
+  to: number;
+}
+
+// Maps every part of the output to the statement that produced it, so frontends can highlight the line
+// behind a symbol or block without matching names
+export interface CrossReference {
+  copies: ValueCopy[];
+  data_races: DataRace[];
+  frames: ScopeReference[];
+  heap: (SourceRef | null)[];
+  heap_groups: (string | null)[];
+  heap_growth: HeapGrowthEvent[];
+  heap_ids: (string | null)[];
+  heap_tags: (MemoryTag | null)[];
+  history: ValueChange[][];
+  lifetimes: (HeapLifetime | null)[];
+  lock_events: LockEvent[];
+  loop_iterations: LoopIteration[];
+  lossy_casts: LossyCast[];
+  moves: ValueMove[];
+  scope_exits: ScopeReference[];
+  stack: SourceRef[];
+  stack_tags: (MemoryTag | null)[];
+  steps: number;
+  temporaries: Temporary[];
+  thread_steps: ThreadStep[];
+}
+
+// Represents a heap block written by more than one spawned thread without a mutex they all held, so
+// what it ends up holding depends on the order the threads ran in
+export interface DataRace {
+  block: number;
+  source: SourceRef;
+  step: number;
+  threads: number[];
+}
+
+// Represents a single error or warning
+export interface Diagnostic {
+  column_number: number | null;
+  line_number: number | null;
+  message: string;
+  severity: Severity;
+}
+
+// Represents a range of the heap the allocator can still hand out, as an entry of its free list
+export interface FreeRange {
+  end: number;
+  size: number;
+  start: number;
+}
+
+// Represents why the allocator left bytes of a block unused
+export type GapReason =
+  | { alignment: number; kind: 'alignment' }
+  | { kind: 'minimumBlockSize'; minimumSize: number };
+
+// Represents why the heap grew
+export type GrowthReason = { kind: 'noFreeRange'; size: number } | { kind: 'pastTheEnd'; size: number };
+
+// Represents a block of memory in the heap
+export interface HeapBlock {
+  block_state: HeapBlockState;
+  current_pointer_identifier: string | null;
+  dangling_pointer_identifiers: string[] | null;
+  gap_reason?: GapReason | null;
+  members?: BlockMember[] | null;
+  metadata: string;
+  moved?: CopyEvent | null;
+  pointer: number;
+  size: number;
+}
+
+// Represents the state of a block of memory in the heap
+export type HeapBlockState = 'Unallocated' | 'Allocated' | 'Free' | 'Leaked' | 'Padding';
+
+// Represents blocks next to each other in the heap summarized as one
+export interface HeapCluster {
+  allocated: number;
+  end: number;
+  in_use: number;
+  leaked: number;
+  pointer: number;
+  size: number;
+  start: number;
+}
+
+// Represents the heap growing to make room for a block
+export interface HeapGrowth {
+  new_size: number;
+  old_size: number;
+  reason: GrowthReason;
+}
+
+// Represents the heap growing at a step, so frontends can show it expanding before the blocks past its
+// old end appear
+export interface HeapGrowthEvent {
+  growth: HeapGrowth;
+  step: number;
+}
+
+// Represents when a heap block was alive, measured in steps of the program
+export interface HeapLifetime {
+  allocated: number;
+  released: number | null;
+}
+
+// Represents the order heap blocks are returned in, so each layout of the visualization gets the blocks
+// in the order it draws them
+export type HeapOrder = 'address' | 'allocation' | 'owner' | 'site';
+
+// Represents the overview of a heap with hundreds of blocks
+export interface HeapOverview {
+  levels: ZoomLevel[];
+  unallocated_runs: UnallocatedRun[];
+}
+
+// Represents a statement the parser skipped in
+// [permissive](crate::analyzer::Strictness::Permissive) mode, so the editor can gray out the span it
+// takes up
+export interface IgnoredStatement {
+  column: number;
+  end_column: number;
+  end_line: number;
+  line: number;
+  reason: string;
+}
+
+// Represents how much of the heap the frontend shows at once
+export type LayoutHint =
+  | { count: number; kind: 'cells' }
+  | { cellHeight: number; height: number; kind: 'panel' };
+
+// Represents a thread locking or unlocking a mutex, which the thread owns from the step it locks it until
+// the step it unlocks it
+export interface LockEvent {
+  locked: boolean;
+  mutex: string;
+  source: SourceRef;
+  step: number;
+  thread: number;
+}
+
+// Represents an iteration of a loop, with how much of the heap was in use once it ended so frontends can
+// show the heap filling up as the loop runs
+export interface LoopIteration {
+  heap_in_use: number;
+  iteration: number;
+  source: SourceRef;
+  step: number;
+}
+
+// Represents a cast that lost part of the value it converted, like the fraction of a `double` cast to
+// an `int`
+export interface LossyCast {
+  cast: string;
+  from: string;
+  source: SourceRef;
+  step: number;
+  to: string;
+}
+
+// Represents a region mapped like an anonymous `mmap`, which lives in an area of its own between the
+// heap and the stack and stays mapped until `munmap` unmaps it or the program ends
+export interface MappedRegion {
+  owner: string;
+  requested: number;
+  size: number;
+  start: number;
+}
+
+// Represents where a member of a struct goes
+export interface MemberLayout {
+  memberType: string;
+  offset: number;
+  padding: number;
+  size: number;
+}
+
+// Represents a tag put on a symbol or a heap block
+export interface MemoryTag {
+  color: string | null;
+  name: string;
+}
+
+// Represents a way an allocator can pick the free range a block is placed in
+export type PlacementStrategy = 'random' | 'firstFit' | 'bestFit' | 'worstFit';
+
+// Represents an argument passed in a register rather than in the callee's stack frame
+export interface RegisterArgument {
+  register: string;
+  symbol: Symbol;
+}
+
+// Represents a block `{ ... }` being left, popping the locals declared in it off the stack
+export interface ScopeExit {
+  depth: number;
+  function: string | null;
+  line: number;
+  symbols: Symbol[];
+}
+
+// Links the symbols of a frame or a block to their statements
+export interface ScopeReference {
+  source: SourceRef;
+  symbols: SourceRef[];
+}
+
+// Represents where an area of the address space is once the program ended
+export interface Segment {
+  kind: SegmentKind;
+  size: number;
+  start: number;
+}
+
+// Represents an area of the address space
+export type SegmentKind = 'text' | 'data' | 'bss' | 'heap' | 'mappings' | 'stack';
+
+// Represents how serious a diagnostic is, errors rank before warnings
+export type Severity = 'error' | 'warning';
+
+// Points at the statement that produced part of the output
+export interface SourceRef {
+  column: number;
+  line: number;
+  statement: number[];
+}
+
+// Represents the stack frame of a function call
+export interface StackFrame {
+  addresses: number[];
+  depth: number;
+  function: string;
+  line: number;
+  registers: RegisterArgument[];
+  symbols: Symbol[];
+  thread: number;
+}
+
+// Represents what happens to statements outside the subset of C++ the analyzer supports
+export type Strictness = 'strict' | 'permissive';
+
+// Represents where the characters of a `std::string` are kept
+export interface StringBuffer {
+  capacity: number;
+  heap_pointer: number | null;
+  length: number;
+}
+
+// Represents how the members of a struct are laid out
+export interface StructLayout {
+  alignment: number;
+  members: MemberLayout[];
+  padding: number;
+  size: number;
+  trailingPadding: number;
+}
+
+// Represents different types of symbols used in the language.
+export type Symbol =
+  | { Variable: { name: string; size: number; string: StringBuffer | null; value: string | null; vtype: Type } }
+  | { Pointer: { allocation_type: AllocationType; control_block: number | null; heap_pointer: number | null; indirection: number; name: string; offset: number; pointer_size: number; ptype: Type; shared: boolean; unique: boolean; value: Symbol | null; value_size: number } }
+  | { Literal: { value: string } }
+  | { Union: { active: string | null; bytes: number[]; members: UnionMember[]; name: string; size: number; union_name: string } }
+  | { Object: { class_name: string; fields: ClassField[]; name: string; size: number } }
+  | { Array: { dimensions: number[]; elements: (string | null)[]; name: string; size: number; vtype: Type } }
+  | { Mutex: { name: string; owner: number | null; size: number } };
+
+// Represents the temporary holding the value a call returned, which lives on the stack of the caller
+// until the end of the full expression the call is part of
+export interface Temporary {
+  created: number;
+  destroyed: number;
+  function: string | null;
+  source: SourceRef;
+  symbol: Symbol;
+}
+
+// Represents a statement a spawned thread ran, the steps of the threads interleave in an order picked from
+// the [seed](crate::analyzer::AnalyzerOptions::seed)
+export interface ThreadStep {
+  source: SourceRef;
+  step: number;
+  thread: number;
+}
+
+// Represents the different types that are supported by the language
+export type Type =
+  | ('Integer' | 'Short' | 'UnsignedShort' | 'UnsignedInt' | 'Long' | 'UnsignedLong' | 'LongLong' | 'UnsignedLongLong' | 'Float' | 'Char' | 'Double' | 'Bool' | 'String' | 'Void')
+  | { Class: string };
+
+// Represents unallocated blocks next to each other, which the part zoomed into can show as one
+export interface UnallocatedRun {
+  end: number;
+  pointer: number;
+  size: number;
+  start: number;
+}
+
+// Represents a member of a union as it reads the bytes of the union
+export interface UnionMember {
+  mtype: Type;
+  name: string;
+  value: string | null;
+}
+
+// Represents a variable taking on a new value
+export interface ValueChange {
+  step: number;
+  value: string | null;
+}
+
+// Represents a value being copied from one symbol into another
+export interface ValueCopy {
+  bytes: number;
+  from: string | null;
+  source: SourceRef;
+  step: number;
+  to: string | null;
+}
+
+// Represents a `std::unique_ptr` handing its block to another, leaving itself null instead of copying
+export interface ValueMove {
+  from: string;
+  source: SourceRef;
+  step: number;
+  to: string;
+}
+
+// Represents the heap summarized at one zoom level
+export interface ZoomLevel {
+  clusters: HeapCluster[];
+  span: number;
+}
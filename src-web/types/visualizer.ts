@@ -17,6 +17,8 @@ export interface NodeData {
       pointingToAddress?: string;
       pointingToLabel?: string;
       metadata?: string;
+      // the rows of a two dimensional array, null for uninitialized elements
+      grid?: (string | null)[][];
       isFree?: boolean;
     };
   };